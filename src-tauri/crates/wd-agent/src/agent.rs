@@ -1,12 +1,21 @@
+use std::future::Future;
+use std::sync::Arc;
+
+use futures::future::join_all;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use wd_llm::{CompletionResponse, Message, Role, ToolCall};
+use wd_llm::{
+    CompletionRequest, CompletionResponse, LlmClient, LlmError, Message, Role, ToolCall,
+    ToolChoice, ToolDefinition,
+};
 
+use crate::confirm::{ConfirmationChannel, ConfirmationDecision};
 use crate::event::{AgentEvent, EventKind};
 use crate::executor::ToolExecutor;
+use crate::knowledge::KnowledgeStore;
 use crate::memory::WorkingMemory;
-use crate::sandbox::{Sandbox, SandboxConfig};
+use crate::sandbox::{Sandbox, SandboxConfig, SandboxViolation};
 
 /// Configuration for an agent run.
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -82,12 +91,30 @@ pub struct AgentResult {
     pub completed: bool,
 }
 
+/// Stored knowledge entries recalled into working memory per run.
+const RECALL_LIMIT: usize = 8;
+
+/// Callback receiving each event as it is emitted.
+pub type EventSink = Box<dyn Fn(&AgentEvent) + Send + Sync>;
+
 /// The core autonomous agent.
 pub struct Agent {
     pub config: AgentConfig,
     pub sandbox: Sandbox,
     pub memory: WorkingMemory,
     events: Vec<AgentEvent>,
+    sink: Option<EventSink>,
+    confirmations: Option<ConfirmationChannel>,
+    knowledge: Option<Arc<KnowledgeStore>>,
+}
+
+/// Mutable state of one run, shared by the sync and async loops.
+struct RunState {
+    run_id: String,
+    messages: Vec<Message>,
+    steps: Vec<AgentStep>,
+    iterations: usize,
+    total_cost: f64,
 }
 
 impl Agent {
@@ -98,6 +125,9 @@ impl Agent {
             sandbox,
             memory: WorkingMemory::new(),
             events: Vec::new(),
+            sink: None,
+            confirmations: None,
+            knowledge: None,
         }
     }
 
@@ -106,6 +136,31 @@ impl Agent {
         self
     }
 
+    /// Forward every event to `sink` as well as recording it, e.g. to
+    /// stream progress to the UI.
+    pub fn with_event_sink<F>(mut self, sink: F) -> Self
+    where
+        F: Fn(&AgentEvent) + Send + Sync + 'static,
+    {
+        self.sink = Some(Box::new(sink));
+        self
+    }
+
+    /// Suspend async runs on tools the sandbox marks as needing
+    /// confirmation until `channel` delivers a decision. Without a channel
+    /// (and in sync runs) such calls are blocked.
+    pub fn with_confirmation_channel(mut self, channel: ConfirmationChannel) -> Self {
+        self.confirmations = Some(channel);
+        self
+    }
+
+    /// Recall knowledge relevant to each query from `store` into working
+    /// memory before the run starts.
+    pub fn with_knowledge_store(mut self, store: Arc<KnowledgeStore>) -> Self {
+        self.knowledge = Some(store);
+        self
+    }
+
     /// Execute the agent loop synchronously (no real LLM calls — requires
     /// an injected completion function for testability).
     ///
@@ -125,14 +180,170 @@ impl Agent {
             &[wd_llm::ToolDefinition],
         ) -> Result<CompletionResponse, wd_llm::LlmError>,
     {
+        let mut run = self.begin(system_prompt, user_query);
+
+        while !self.limit_reached(&mut run) {
+            let response = match complete_fn(&run.messages, tools) {
+                Ok(r) => r,
+                Err(e) => {
+                    self.emit(EventKind::Error {
+                        message: e.to_string(),
+                    });
+                    break;
+                }
+            };
+
+            let Some(tool_calls) = self.accept_response(&mut run, &response) else {
+                if response.finish_reason.is_tool_use() {
+                    continue;
+                }
+                break;
+            };
+
+            let mut results = Vec::new();
+            for tc in &tool_calls {
+                if !self.gate_tool(&mut run, tc, &mut results) {
+                    continue;
+                }
+                let result = executor.execute(&tc.function.name, &tc.function.arguments);
+                self.record_result(&mut run, tc, result, &mut results);
+            }
+            run.steps.push(AgentStep::ToolResults { results });
+        }
+
+        self.finish(run)
+    }
+
+    /// Execute the agent loop asynchronously. Tool calls go through
+    /// [`ToolExecutor::execute_async`], concurrently when
+    /// `config.parallel_tool_calls` is set.
+    ///
+    /// `complete_fn` receives the conversation so far and the tool list and
+    /// returns the model's next response.
+    pub async fn run_async<F, Fut>(
+        &mut self,
+        system_prompt: &str,
+        user_query: &str,
+        tools: &[ToolDefinition],
+        executor: &ToolExecutor,
+        mut complete_fn: F,
+    ) -> AgentResult
+    where
+        F: FnMut(Vec<Message>, Vec<ToolDefinition>) -> Fut,
+        Fut: Future<Output = Result<CompletionResponse, LlmError>>,
+    {
+        let mut run = self.begin(system_prompt, user_query);
+
+        while !self.limit_reached(&mut run) {
+            let response = match complete_fn(run.messages.clone(), tools.to_vec()).await {
+                Ok(r) => r,
+                Err(e) => {
+                    self.emit(EventKind::Error {
+                        message: e.to_string(),
+                    });
+                    break;
+                }
+            };
+
+            let Some(tool_calls) = self.accept_response(&mut run, &response) else {
+                if response.finish_reason.is_tool_use() {
+                    continue;
+                }
+                break;
+            };
+
+            let mut results = Vec::new();
+            if self.config.parallel_tool_calls {
+                let mut allowed = Vec::new();
+                for tc in &tool_calls {
+                    if let Some(args) = self.gate_tool_async(&mut run, tc, &mut results).await {
+                        allowed.push((tc, args));
+                    }
+                }
+                let outputs = join_all(
+                    allowed
+                        .iter()
+                        .map(|(tc, args)| executor.execute_async(&tc.function.name, args)),
+                )
+                .await;
+                for ((tc, _), result) in allowed.into_iter().zip(outputs) {
+                    self.record_result(&mut run, tc, result, &mut results);
+                }
+            } else {
+                for tc in &tool_calls {
+                    let Some(args) = self.gate_tool_async(&mut run, tc, &mut results).await else {
+                        continue;
+                    };
+                    let result = executor.execute_async(&tc.function.name, &args).await;
+                    self.record_result(&mut run, tc, result, &mut results);
+                }
+            }
+            run.steps.push(AgentStep::ToolResults { results });
+        }
+
+        self.finish(run)
+    }
+
+    /// Run the agent against a live provider through `client`. The model
+    /// and temperature come from `config` when set, else from the client.
+    pub async fn run_with_client(
+        &mut self,
+        system_prompt: &str,
+        user_query: &str,
+        tools: &[ToolDefinition],
+        executor: &ToolExecutor,
+        client: &LlmClient,
+    ) -> AgentResult {
+        let (model, temperature) = self.client_params(client);
+        self.run_async(
+            system_prompt,
+            user_query,
+            tools,
+            executor,
+            |messages, tools| {
+                let req = client_request(&model, temperature, messages, tools);
+                async move { client.complete(&req).await }
+            },
+        )
+        .await
+    }
+
+    /// Model and temperature for requests through `client`.
+    pub(crate) fn client_params(&self, client: &LlmClient) -> (String, Option<f32>) {
+        let model = self
+            .config
+            .model
+            .clone()
+            .unwrap_or_else(|| client.config().model.clone());
+        let temperature = self.config.temperature.or(client.config().temperature);
+        (model, temperature)
+    }
+
+    /// Get all events emitted during runs.
+    pub fn events(&self) -> &[AgentEvent] {
+        &self.events
+    }
+
+    pub(crate) fn emit(&mut self, kind: EventKind) {
+        let event = AgentEvent::new(kind);
+        if let Some(sink) = &self.sink {
+            sink(&event);
+        }
+        self.events.push(event);
+    }
+
+    fn begin(&mut self, system_prompt: &str, user_query: &str) -> RunState {
         let run_id = Uuid::new_v4().to_string();
-        let mut steps = Vec::new();
         let mut messages = Vec::new();
-        let mut iterations = 0usize;
-        let mut total_cost = 0.0f64;
 
         // System prompt
         messages.push(Message::system(system_prompt));
+        // Long-term knowledge relevant to the query
+        if let Some(store) = &self.knowledge {
+            if let Err(e) = self.memory.recall(store, user_query, RECALL_LIMIT) {
+                log::warn!("Failed to recall stored knowledge: {e}");
+            }
+        }
         // Working memory context
         if let Some(ctx) = self.memory.to_context_message() {
             messages.push(ctx);
@@ -140,118 +351,228 @@ impl Agent {
         // User query
         messages.push(Message::user(user_query));
 
+        self.sandbox.reset();
         self.emit(EventKind::RunStarted {
             run_id: run_id.clone(),
             query: user_query.to_string(),
         });
 
-        loop {
-            // Check iteration limit
-            if iterations >= self.config.max_iterations {
-                steps.push(AgentStep::IterationLimit {
-                    iterations,
-                    max: self.config.max_iterations,
-                });
-                self.emit(EventKind::IterationLimitReached { iterations });
-                break;
-            }
-
-            // Check cost limit
-            if total_cost >= self.config.max_cost_usd {
-                steps.push(AgentStep::BudgetExhausted {
-                    spent_usd: total_cost,
-                    limit_usd: self.config.max_cost_usd,
-                });
-                self.emit(EventKind::BudgetExhausted {
-                    spent: total_cost,
-                    limit: self.config.max_cost_usd,
-                });
-                break;
-            }
+        RunState {
+            run_id,
+            messages,
+            steps: Vec::new(),
+            iterations: 0,
+            total_cost: 0.0,
+        }
+    }
 
-            iterations += 1;
-            self.emit(EventKind::IterationStarted {
-                iteration: iterations,
+    /// Check the iteration and budget limits; when within them, start the
+    /// next iteration and return `false`.
+    fn limit_reached(&mut self, run: &mut RunState) -> bool {
+        // Check iteration limit
+        if run.iterations >= self.config.max_iterations {
+            run.steps.push(AgentStep::IterationLimit {
+                iterations: run.iterations,
+                max: self.config.max_iterations,
             });
+            self.emit(EventKind::IterationLimitReached {
+                iterations: run.iterations,
+            });
+            return true;
+        }
 
-            // Call LLM
-            let response = match complete_fn(&messages, tools) {
-                Ok(r) => r,
-                Err(e) => {
-                    self.emit(EventKind::Error {
-                        message: e.to_string(),
-                    });
-                    break;
-                }
-            };
+        // Check cost limit, from both the agent config and the sandbox
+        let limit = match self.sandbox.check_cost(0.0) {
+            Err(SandboxViolation::CostLimitExceeded { limit, .. }) => Some(limit),
+            _ if run.total_cost >= self.config.max_cost_usd => Some(self.config.max_cost_usd),
+            _ => None,
+        };
+        if let Some(limit) = limit {
+            run.steps.push(AgentStep::BudgetExhausted {
+                spent_usd: run.total_cost,
+                limit_usd: limit,
+            });
+            self.emit(EventKind::BudgetExhausted {
+                spent: run.total_cost,
+                limit,
+            });
+            return true;
+        }
 
-            // Track cost
-            if let Some(cost) = response.usage.estimated_cost_usd {
-                total_cost += cost;
-            }
+        run.iterations += 1;
+        self.emit(EventKind::IterationStarted {
+            iteration: run.iterations,
+        });
+        false
+    }
 
-            // Check if assistant wants to call tools
-            if response.message.role == Role::Assistant && !response.message.tool_calls.is_empty() {
-                let tool_calls = response.message.tool_calls.clone();
-                messages.push(response.message.clone());
-                steps.push(AgentStep::ToolCalls {
-                    calls: tool_calls.clone(),
-                });
+    /// Record a model response. Returns the tool calls to execute, or
+    /// `None` when the response is a text answer.
+    fn accept_response(
+        &mut self,
+        run: &mut RunState,
+        response: &CompletionResponse,
+    ) -> Option<Vec<ToolCall>> {
+        // Track cost
+        if let Some(cost) = response.usage.estimated_cost_usd {
+            run.total_cost += cost;
+            self.sandbox.record_cost(cost);
+        }
 
-                // Execute each tool call
-                let mut results = Vec::new();
-                for tc in &tool_calls {
-                    // Sandbox check
-                    if let Err(violation) = self.sandbox.check_tool(&tc.function.name) {
-                        steps.push(AgentStep::SandboxBlock {
-                            tool_name: tc.function.name.clone(),
-                            reason: violation.to_string(),
-                        });
-                        let err_msg = format!("Blocked by sandbox: {violation}");
-                        messages.push(Message::tool_result(&tc.id, &tc.function.name, &err_msg));
-                        results.push((tc.function.name.clone(), err_msg));
-                        continue;
-                    }
+        // Check if assistant wants to call tools
+        if response.message.role == Role::Assistant && !response.message.tool_calls.is_empty() {
+            let tool_calls = response.message.tool_calls.clone();
+            run.messages.push(response.message.clone());
+            run.steps.push(AgentStep::ToolCalls {
+                calls: tool_calls.clone(),
+            });
+            return Some(tool_calls);
+        }
 
-                    self.emit(EventKind::ToolCallStarted {
-                        tool_name: tc.function.name.clone(),
-                    });
+        // Assistant produced a final text message
+        if let Some(content) = &response.message.content {
+            run.messages.push(response.message.clone());
+            run.steps.push(AgentStep::AssistantMessage {
+                content: content.clone(),
+            });
+        }
+        None
+    }
 
-                    let result = executor.execute(&tc.function.name, &tc.function.arguments);
+    /// Ask the sandbox whether `tc` may run. A blocked call is answered with
+    /// an error result so the model can adapt; an allowed one is counted
+    /// against the tool-call limit.
+    fn gate_tool(
+        &mut self,
+        run: &mut RunState,
+        tc: &ToolCall,
+        results: &mut Vec<(String, String)>,
+    ) -> bool {
+        if let Err(violation) = self.sandbox.check_tool(&tc.function.name) {
+            let err_msg = format!("Blocked by sandbox: {violation}");
+            Self::reject_tool(run, tc, violation.to_string(), err_msg, results);
+            return false;
+        }
+        self.admit_tool(tc);
+        true
+    }
 
-                    self.emit(EventKind::ToolCallCompleted {
-                        tool_name: tc.function.name.clone(),
-                        success: result.is_ok(),
-                    });
+    /// Like [`gate_tool`](Self::gate_tool), but a call needing confirmation
+    /// waits for the user's decision when a confirmation channel is set.
+    /// Returns the arguments to run the tool with, or `None` if it must not
+    /// run.
+    async fn gate_tool_async(
+        &mut self,
+        run: &mut RunState,
+        tc: &ToolCall,
+        results: &mut Vec<(String, String)>,
+    ) -> Option<serde_json::Value> {
+        let needs_confirmation = matches!(
+            self.sandbox.check_tool(&tc.function.name),
+            Err(SandboxViolation::ConfirmationRequired { .. })
+        );
+        let channel = match &self.confirmations {
+            Some(channel) if needs_confirmation => channel.clone(),
+            _ => {
+                return self
+                    .gate_tool(run, tc, results)
+                    .then(|| tc.function.arguments.clone())
+            }
+        };
 
-                    let result_str = match result {
-                        Ok(v) => v.to_string(),
-                        Err(e) => format!("Error: {e}"),
-                    };
-                    messages.push(Message::tool_result(&tc.id, &tc.function.name, &result_str));
-                    results.push((tc.function.name.clone(), result_str));
-                }
+        let (request, decision) =
+            channel.request(&run.run_id, &tc.function.name, &tc.function.arguments);
+        self.emit(EventKind::ConfirmationRequested {
+            request_id: request.id.clone(),
+            tool_name: request.tool_name,
+            arguments: request.arguments,
+        });
+        let decision = decision.await;
+        self.emit(EventKind::ConfirmationResolved {
+            request_id: request.id,
+            tool_name: tc.function.name.clone(),
+            decision: decision.clone(),
+        });
 
-                steps.push(AgentStep::ToolResults { results });
-                continue; // Loop back for LLM to process results
+        match decision {
+            ConfirmationDecision::Approve => {
+                self.admit_tool(tc);
+                Some(tc.function.arguments.clone())
             }
-
-            // Assistant produced a final text message
-            if let Some(content) = &response.message.content {
-                messages.push(response.message.clone());
-                steps.push(AgentStep::AssistantMessage {
-                    content: content.clone(),
-                });
+            ConfirmationDecision::Edit { arguments } => {
+                // Keep the history consistent with what actually ran.
+                if let Some(call) = run
+                    .messages
+                    .iter_mut()
+                    .rev()
+                    .flat_map(|m| m.tool_calls.iter_mut())
+                    .find(|c| c.id == tc.id)
+                {
+                    call.function.arguments = arguments.clone();
+                }
+                self.admit_tool(tc);
+                Some(arguments)
             }
-
-            // Check finish reason
-            if response.finish_reason.is_tool_use() {
-                continue;
+            ConfirmationDecision::Deny { reason } => {
+                let reason = match reason {
+                    Some(r) => format!("Denied by user: {r}"),
+                    None => "Denied by user".to_string(),
+                };
+                Self::reject_tool(run, tc, reason.clone(), reason, results);
+                None
             }
-            break;
         }
+    }
+
+    /// Count an allowed call against the tool-call limit and announce it.
+    fn admit_tool(&mut self, tc: &ToolCall) {
+        self.sandbox.record_tool_call();
+        self.emit(EventKind::ToolCallStarted {
+            tool_name: tc.function.name.clone(),
+        });
+    }
+
+    /// Answer a call that must not run with an error result so the model
+    /// can adapt.
+    fn reject_tool(
+        run: &mut RunState,
+        tc: &ToolCall,
+        reason: String,
+        err_msg: String,
+        results: &mut Vec<(String, String)>,
+    ) {
+        run.steps.push(AgentStep::SandboxBlock {
+            tool_name: tc.function.name.clone(),
+            reason,
+        });
+        run.messages
+            .push(Message::tool_result(&tc.id, &tc.function.name, &err_msg));
+        results.push((tc.function.name.clone(), err_msg));
+    }
+
+    fn record_result(
+        &mut self,
+        run: &mut RunState,
+        tc: &ToolCall,
+        result: Result<serde_json::Value, String>,
+        results: &mut Vec<(String, String)>,
+    ) {
+        self.emit(EventKind::ToolCallCompleted {
+            tool_name: tc.function.name.clone(),
+            success: result.is_ok(),
+        });
 
-        let answer = steps.iter().rev().find_map(|s| {
+        let result_str = match result {
+            Ok(v) => v.to_string(),
+            Err(e) => format!("Error: {e}"),
+        };
+        run.messages
+            .push(Message::tool_result(&tc.id, &tc.function.name, &result_str));
+        results.push((tc.function.name.clone(), result_str));
+    }
+
+    fn finish(&mut self, run: RunState) -> AgentResult {
+        let answer = run.steps.iter().rev().find_map(|s| {
             if let AgentStep::AssistantMessage { content } = s {
                 Some(content.clone())
             } else {
@@ -262,29 +583,37 @@ impl Agent {
         let completed = answer.is_some();
 
         self.emit(EventKind::RunCompleted {
-            run_id: run_id.clone(),
-            iterations,
-            cost: total_cost,
+            run_id: run.run_id.clone(),
+            iterations: run.iterations,
+            cost: run.total_cost,
         });
 
         AgentResult {
-            run_id,
+            run_id: run.run_id,
             answer,
-            steps,
-            iterations,
-            total_cost_usd: total_cost,
+            steps: run.steps,
+            iterations: run.iterations,
+            total_cost_usd: run.total_cost,
             completed,
         }
     }
+}
 
-    /// Get all events emitted during runs.
-    pub fn events(&self) -> &[AgentEvent] {
-        &self.events
+/// Build the request [`Agent::run_with_client`] sends for one iteration.
+pub(crate) fn client_request(
+    model: &str,
+    temperature: Option<f32>,
+    messages: Vec<Message>,
+    tools: Vec<ToolDefinition>,
+) -> CompletionRequest {
+    let mut req = CompletionRequest::new(model, messages);
+    if !tools.is_empty() {
+        req = req.with_tools(tools, ToolChoice::Auto);
     }
-
-    fn emit(&mut self, kind: EventKind) {
-        self.events.push(AgentEvent::new(kind));
+    if let Some(t) = temperature {
+        req = req.with_temperature(t as f64);
     }
+    req
 }
 
 // ─── Tests ───────────────────────────────────────────────────────────────────
@@ -497,6 +826,355 @@ mod tests {
             .any(|e| matches!(e.kind, EventKind::RunCompleted { .. })));
     }
 
+    fn two_calls_response() -> CompletionResponse {
+        use wd_llm::{FunctionCall, ToolCall};
+        let call = |id: &str, domain: &str| ToolCall {
+            id: id.into(),
+            function: FunctionCall {
+                name: "whois_lookup".into(),
+                arguments: serde_json::json!({ "domain": domain }),
+            },
+        };
+        CompletionResponse {
+            message: Message::assistant_tool_calls(vec![call("a", "a.com"), call("b", "b.com")]),
+            ..tool_call_response()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_async_parallel_tool_calls() {
+        let config = AgentConfig {
+            parallel_tool_calls: true,
+            ..Default::default()
+        };
+        let mut agent = Agent::new(config);
+        let mut executor = ToolExecutor::new();
+        executor.register_async("whois_lookup", |args| async move {
+            tokio::task::yield_now().await;
+            Ok(serde_json::json!({ "domain": args["domain"], "status": "unavailable" }))
+        });
+
+        let mut call_count = 0;
+        let result = agent
+            .run_async(
+                "System",
+                "Audit a.com and b.com",
+                &[],
+                &executor,
+                |msgs, _| {
+                    call_count += 1;
+                    let first = call_count == 1;
+                    async move {
+                        if first {
+                            Ok(two_calls_response())
+                        } else {
+                            // Both results are fed back, in call order.
+                            let results: Vec<_> =
+                                msgs.iter().filter(|m| m.role == Role::Tool).collect();
+                            assert_eq!(results[0].tool_call_id.as_deref(), Some("a"));
+                            assert!(results[1].content.as_deref().unwrap().contains("b.com"));
+                            Ok(simple_response("Both are registered."))
+                        }
+                    }
+                },
+            )
+            .await;
+
+        assert!(result.completed);
+        assert_eq!(agent.sandbox.tool_call_count(), 2);
+        let results = result.steps.iter().find_map(|s| match s {
+            AgentStep::ToolResults { results } => Some(results),
+            _ => None,
+        });
+        assert_eq!(results.map(Vec::len), Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_run_async_enforces_sandbox_call_limit() {
+        use crate::sandbox::SandboxConfig;
+        let sandbox = Sandbox::new(SandboxConfig {
+            max_tool_calls: 1,
+            ..Default::default()
+        });
+        let mut agent = Agent::new(AgentConfig::default()).with_sandbox(sandbox);
+        let mut executor = ToolExecutor::new();
+        executor.register("whois_lookup", |_| Ok(serde_json::json!("ok")));
+
+        let mut call_count = 0;
+        let result = agent
+            .run_async("System", "Audit", &[], &executor, |_, _| {
+                call_count += 1;
+                let first = call_count == 1;
+                async move {
+                    Ok(if first {
+                        two_calls_response()
+                    } else {
+                        simple_response("done")
+                    })
+                }
+            })
+            .await;
+
+        let blocked: Vec<_> = result
+            .steps
+            .iter()
+            .filter(|s| matches!(s, AgentStep::SandboxBlock { .. }))
+            .collect();
+        assert_eq!(blocked.len(), 1);
+        assert!(result.completed);
+    }
+
+    #[tokio::test]
+    async fn test_event_sink_receives_events() {
+        use std::sync::{Arc, Mutex};
+        let seen = Arc::new(Mutex::new(0usize));
+        let counter = Arc::clone(&seen);
+        let mut agent = Agent::new(AgentConfig::default())
+            .with_event_sink(move |_| *counter.lock().unwrap() += 1);
+        let executor = ToolExecutor::new();
+        agent
+            .run_async("System", "Hello", &[], &executor, |_, _| async {
+                Ok(simple_response("Hi"))
+            })
+            .await;
+        assert_eq!(*seen.lock().unwrap(), agent.events().len());
+    }
+
+    fn confirming_agent(channel: &ConfirmationChannel) -> Agent {
+        let mut sandbox = Sandbox::new(SandboxConfig {
+            require_confirmation: true,
+            ..Default::default()
+        });
+        sandbox.require_confirmation_for("clear_cache");
+        Agent::new(AgentConfig::default())
+            .with_sandbox(sandbox)
+            .with_confirmation_channel(channel.clone())
+    }
+
+    fn clear_cache_call() -> CompletionResponse {
+        use wd_llm::{FunctionCall, ToolCall};
+        let mut response = tool_call_response();
+        response.message = Message::assistant_tool_calls(vec![ToolCall {
+            id: "tc1".into(),
+            function: FunctionCall {
+                name: "clear_cache".into(),
+                arguments: serde_json::json!({"domain": "a.com"}),
+            },
+        }]);
+        response
+    }
+
+    #[tokio::test]
+    async fn test_confirmation_suspends_until_approved() {
+        let channel = ConfirmationChannel::new();
+        let mut agent = confirming_agent(&channel);
+        let mut executor = ToolExecutor::new();
+        executor.register("clear_cache", |args| {
+            Ok(serde_json::json!({ "cleared": args["domain"] }))
+        });
+
+        let mut call_count = 0;
+        let run = agent.run_async("System", "Clear it", &[], &executor, |msgs, _| {
+            call_count += 1;
+            let response = if call_count == 1 {
+                clear_cache_call()
+            } else {
+                assert!(msgs
+                    .last()
+                    .and_then(|m| m.content.as_deref())
+                    .is_some_and(|c| c.contains("cleared")));
+                simple_response("Cache cleared.")
+            };
+            async move { Ok(response) }
+        });
+        let responder = async {
+            let request = loop {
+                if let Some(r) = channel.pending().pop() {
+                    break r;
+                }
+                tokio::task::yield_now().await;
+            };
+            assert_eq!(request.tool_name, "clear_cache");
+            assert_eq!(request.arguments["domain"], "a.com");
+            channel
+                .resolve(&request.id, ConfirmationDecision::Approve)
+                .unwrap();
+        };
+        let (result, ()) = tokio::join!(run, responder);
+
+        assert!(result.completed);
+        assert_eq!(agent.sandbox.tool_call_count(), 1);
+        assert!(agent.events().iter().any(|e| matches!(
+            &e.kind,
+            EventKind::ConfirmationResolved {
+                decision: ConfirmationDecision::Approve,
+                ..
+            }
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_confirmation_edit_and_deny() {
+        for (decision, expected) in [
+            (
+                ConfirmationDecision::Edit {
+                    arguments: serde_json::json!({"domain": "b.com"}),
+                },
+                "b.com",
+            ),
+            (
+                ConfirmationDecision::Deny {
+                    reason: Some("not now".into()),
+                },
+                "Denied by user: not now",
+            ),
+        ] {
+            let channel = ConfirmationChannel::new();
+            let responder = channel.clone();
+            let mut agent = confirming_agent(&channel).with_event_sink(move |e| {
+                if let EventKind::ConfirmationRequested { request_id, .. } = &e.kind {
+                    responder.resolve(request_id, decision.clone()).unwrap();
+                }
+            });
+            let mut executor = ToolExecutor::new();
+            executor.register("clear_cache", |args| Ok(args["domain"].clone()));
+
+            let mut call_count = 0;
+            let result = agent
+                .run_async("System", "Clear it", &[], &executor, |_, _| {
+                    call_count += 1;
+                    let response = if call_count == 1 {
+                        clear_cache_call()
+                    } else {
+                        simple_response("Done.")
+                    };
+                    async move { Ok(response) }
+                })
+                .await;
+
+            let output = result
+                .steps
+                .iter()
+                .find_map(|s| match s {
+                    AgentStep::ToolResults { results } => Some(results[0].1.clone()),
+                    _ => None,
+                })
+                .unwrap();
+            assert!(output.contains(expected), "{output}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_confirmation_gates_builtin_tools() {
+        use crate::builtin::BuiltinTools;
+        use wd_llm::{FunctionCall, ToolCall};
+
+        let dir = std::env::temp_dir().join(format!("wd_agent_confirm_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let cache = dir.join("request-cache.sqlite");
+        wd_db::db_cache_set(&cache.to_string_lossy(), "whois:a.com", "reply", None).unwrap();
+
+        let channel = ConfirmationChannel::new();
+        let mut executor = ToolExecutor::new();
+        BuiltinTools::new(wd_lookup::LookupSettings::default())
+            .with_cache(&cache)
+            .with_export_dir(dir.join("exports"))
+            .install(&mut executor);
+        let mut sandbox = Sandbox::new(SandboxConfig {
+            require_confirmation: true,
+            ..Default::default()
+        });
+        sandbox.require_confirmation_for_defaults();
+        let mut agent = Agent::new(AgentConfig::default())
+            .with_sandbox(sandbox)
+            .with_confirmation_channel(channel.clone());
+
+        let calls = [
+            (
+                "export_csv",
+                serde_json::json!({"data": ["a.com"], "filename": "out"}),
+            ),
+            ("clear_cache", serde_json::json!({})),
+        ];
+        let mut call_count = 0;
+        let run = agent.run_async("System", "Export, then clear", &[], &executor, |_, _| {
+            let response = match calls.get(call_count) {
+                Some((name, arguments)) => {
+                    let mut response = tool_call_response();
+                    response.message = Message::assistant_tool_calls(vec![ToolCall {
+                        id: format!("tc{call_count}"),
+                        function: FunctionCall {
+                            name: name.to_string(),
+                            arguments: arguments.clone(),
+                        },
+                    }]);
+                    response
+                }
+                None => simple_response("Done."),
+            };
+            call_count += 1;
+            async move { Ok(response) }
+        });
+        // Approve the export and deny the clear, as a user would.
+        let responder = async {
+            for expected in ["export_csv", "clear_cache"] {
+                let request = loop {
+                    if let Some(r) = channel.pending().pop() {
+                        break r;
+                    }
+                    tokio::task::yield_now().await;
+                };
+                assert_eq!(request.tool_name, expected);
+                let decision = if expected == "export_csv" {
+                    ConfirmationDecision::Approve
+                } else {
+                    ConfirmationDecision::Deny { reason: None }
+                };
+                channel.resolve(&request.id, decision).unwrap();
+            }
+        };
+        let (result, ()) = tokio::join!(run, responder);
+
+        assert!(result.completed);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("exports").join("out.csv")).unwrap(),
+            "a.com\n"
+        );
+        let stats = wd_db::db_cache_stats(&cache.to_string_lossy()).unwrap();
+        assert_eq!(stats.entries, 1);
+        assert!(channel.pending().is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_knowledge_store_is_recalled_into_context() {
+        use crate::memory::DomainKnowledge;
+        let store = Arc::new(KnowledgeStore::open_in_memory().unwrap());
+        store
+            .remember(
+                &DomainKnowledge::new("example.com", "expiry", "Expires in 12 days"),
+                None,
+            )
+            .unwrap();
+        let mut agent = Agent::new(AgentConfig::default()).with_knowledge_store(store);
+        let executor = ToolExecutor::new();
+        let result = agent.run(
+            "System",
+            "When does example.com expire?",
+            &[],
+            &executor,
+            |msgs, _| {
+                assert!(msgs[1]
+                    .content
+                    .as_deref()
+                    .unwrap()
+                    .contains("Expires in 12 days"));
+                Ok(simple_response("In 12 days."))
+            },
+        );
+        assert!(result.completed);
+    }
+
     #[test]
     fn test_default_config() {
         let c = AgentConfig::default();
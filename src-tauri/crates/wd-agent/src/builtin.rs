@@ -0,0 +1,1232 @@
+//! Built-in tool handlers backing the `Toolbox::full()` definitions with
+//! real lookups: WHOIS/RDAP/DNS via wd-lookup, availability via
+//! wd-availability, risk scoring via wd-threat, expiry phases via wd-expiry
+//! and snapshot history via wd-history, with full-text search over it via
+//! wd-search. Name generation comes from wd-domgen and the request cache
+//! from wd-db. Findings can also be written to the agent's long-term
+//! [`KnowledgeStore`].
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
+use futures::StreamExt;
+use serde_json::{json, Value};
+
+use wd_availability::{get_domain_parameters, is_domain_available, DomainStatus, WhoisParams};
+use wd_db::{db_cache_clear, db_cache_stats};
+use wd_domgen::{
+    mutate_domain, DomainScorer, GeneratorConfig, GeneratorEngine, MutationKind, MutatorConfig,
+};
+use wd_expiry::{DomainExpiry, PhaseEvidence};
+use wd_history::diff::diff_snapshots;
+use wd_history::{HistoryStore, LookupProtocol, Snapshot};
+use wd_lookup::LookupSettings;
+use wd_parser::parse_status_codes;
+use wd_search::{Document, SearchIndex, SearchQuery};
+use wd_threat::{assess_domain, PatternDetector, ThreatCategory};
+
+use crate::executor::ToolExecutor;
+use crate::knowledge::KnowledgeStore;
+use crate::memory::DomainKnowledge;
+
+/// Raw WHOIS text is cut to this many characters before it is handed to
+/// the model.
+const MAX_RAW_CHARS: usize = 4000;
+
+/// Upper bound on domains per `bulk_whois` call.
+const MAX_BULK_DOMAINS: usize = 50;
+
+/// WHOIS queries a `bulk_whois` call keeps in flight at once.
+const BULK_CONCURRENCY: usize = 5;
+
+/// Upper bound on names returned by the generator tools.
+const MAX_GENERATED: usize = 200;
+
+/// Source of WHOIS, RDAP and DNS data for the built-in tools.
+pub trait LookupBackend: Send + Sync {
+    /// Raw WHOIS reply, optionally from a specific server.
+    fn whois(
+        &self,
+        domain: &str,
+        server: Option<&str>,
+    ) -> impl Future<Output = Result<String, String>> + Send;
+
+    /// Raw RDAP JSON.
+    fn rdap(&self, domain: &str) -> impl Future<Output = Result<String, String>> + Send;
+
+    /// Whether the domain has NS records.
+    fn has_nameservers(&self, domain: &str) -> impl Future<Output = Result<bool, String>> + Send;
+
+    /// PTR names of an IP address.
+    fn reverse_dns(&self, ip: &str) -> impl Future<Output = Result<Vec<String>, String>> + Send;
+}
+
+/// Live network backend using the user's lookup settings.
+pub struct NetworkLookup {
+    settings: LookupSettings,
+}
+
+impl NetworkLookup {
+    pub fn new(settings: LookupSettings) -> Self {
+        Self { settings }
+    }
+}
+
+impl LookupBackend for NetworkLookup {
+    async fn whois(&self, domain: &str, server: Option<&str>) -> Result<String, String> {
+        let mut settings = self.settings.clone();
+        if let Some(server) = server {
+            settings.general.server = Some(server.to_string());
+        }
+        let domain = domain.to_string();
+        // The WHOIS client reads its socket synchronously; keep it off the
+        // async workers.
+        tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(wd_lookup::perform_lookup_with_settings(&domain, &settings))
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+
+    async fn rdap(&self, domain: &str) -> Result<String, String> {
+        wd_lookup::rdap_lookup(domain).await
+    }
+
+    async fn has_nameservers(&self, domain: &str) -> Result<bool, String> {
+        wd_lookup::dns_lookup(domain).await
+    }
+
+    async fn reverse_dns(&self, ip: &str) -> Result<Vec<String>, String> {
+        wd_lookup::reverse_dns_lookup(ip).await
+    }
+}
+
+/// Built-in handlers for the lookup, analysis, security and history tools.
+pub struct BuiltinTools<B = NetworkLookup> {
+    backend: B,
+    settings: LookupSettings,
+    history: Option<Arc<HistoryStore>>,
+    knowledge: Option<Arc<KnowledgeStore>>,
+    search: Option<Arc<SearchIndex>>,
+    cache: Option<PathBuf>,
+    export_dir: Option<PathBuf>,
+}
+
+impl BuiltinTools<NetworkLookup> {
+    /// Handlers that query the network with `settings`.
+    pub fn new(settings: LookupSettings) -> Self {
+        Self::with_backend(NetworkLookup::new(settings.clone())).with_settings(settings)
+    }
+}
+
+impl<B: LookupBackend + 'static> BuiltinTools<B> {
+    pub fn with_backend(backend: B) -> Self {
+        Self {
+            backend,
+            settings: LookupSettings::default(),
+            history: None,
+            knowledge: None,
+            search: None,
+            cache: None,
+            export_dir: None,
+        }
+    }
+
+    /// Lookup settings reported by `get_settings`.
+    pub fn with_settings(mut self, settings: LookupSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Serve `get_history` from `store` and record a snapshot for every
+    /// successful `whois_lookup`.
+    pub fn with_history(mut self, store: Arc<HistoryStore>) -> Self {
+        self.history = Some(store);
+        self
+    }
+
+    /// Remember the findings of lookup, availability, expiry and threat
+    /// tools in `store`, tagged with the tool that produced them.
+    pub fn with_knowledge(mut self, store: Arc<KnowledgeStore>) -> Self {
+        self.knowledge = Some(store);
+        self
+    }
+
+    /// Serve `search_records` from `index`. Snapshots recorded through
+    /// [`with_history`](Self::with_history) are indexed as they are taken.
+    pub fn with_search(mut self, index: Arc<SearchIndex>) -> Self {
+        self.search = Some(index);
+        self
+    }
+
+    /// Serve `get_cache_stats` and `clear_cache` from the request cache
+    /// database at `path`.
+    pub fn with_cache(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cache = Some(path.into());
+        self
+    }
+
+    /// Write `export_csv` and `export_json` files into `dir`.
+    pub fn with_export_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.export_dir = Some(dir.into());
+        self
+    }
+
+    /// Names of the tools these handlers implement.
+    pub fn tool_names() -> &'static [&'static str] {
+        &[
+            "whois_lookup",
+            "bulk_whois",
+            "rdap_lookup",
+            "parse_whois",
+            "dns_lookup",
+            "reverse_dns",
+            "check_availability",
+            "check_expiry",
+            "domain_stats",
+            "threat_scan",
+            "homoglyph_check",
+            "generate_typosquats",
+            "generate_domains",
+            "get_history",
+            "diff_whois",
+            "search_records",
+            "export_csv",
+            "export_json",
+            "get_settings",
+            "get_cache_stats",
+            "clear_cache",
+        ]
+    }
+
+    /// Register every built-in handler on `executor`.
+    pub fn install(self, executor: &mut ToolExecutor) {
+        let tools = Arc::new(self);
+        for &name in Self::tool_names() {
+            let tools = Arc::clone(&tools);
+            executor.register_async(name, move |args| {
+                let tools = Arc::clone(&tools);
+                async move { tools.call(name, &args).await }
+            });
+        }
+    }
+
+    /// Run the tool `name` with `args`.
+    pub async fn call(&self, name: &str, args: &Value) -> Result<Value, String> {
+        let result = self.dispatch(name, args).await;
+        if let (Some(store), Ok(value)) = (&self.knowledge, &result) {
+            let items = match value {
+                Value::Array(items) => items.iter().collect(),
+                single => vec![single],
+            };
+            for item in items {
+                let Some((knowledge, ttl)) = knowledge_from(name, item) else {
+                    continue;
+                };
+                if let Err(e) = store.remember(&knowledge, Some(ttl)) {
+                    log::warn!("Failed to store knowledge for {}: {e}", knowledge.domain);
+                }
+            }
+        }
+        result
+    }
+
+    async fn dispatch(&self, name: &str, args: &Value) -> Result<Value, String> {
+        match name {
+            "whois_lookup" => {
+                let domain = domain_arg(args)?;
+                self.whois_lookup(&domain, args["server"].as_str(), true)
+                    .await
+            }
+            "bulk_whois" => self.bulk_whois(args).await,
+            "rdap_lookup" => self.rdap_lookup(&domain_arg(args)?).await,
+            "parse_whois" => {
+                let raw = str_arg(args, "raw_whois")?;
+                Ok(summarize(
+                    &get_domain_parameters(None, None, raw.to_string()),
+                    false,
+                ))
+            }
+            "dns_lookup" => self.dns_lookup(args).await,
+            "reverse_dns" => {
+                let ip = str_arg(args, "ip")?.trim();
+                let names = self.backend.reverse_dns(ip).await?;
+                Ok(json!({ "ip": ip, "found": !names.is_empty(), "names": names }))
+            }
+            "check_availability" => self.check_availability(&domain_arg(args)?).await,
+            "check_expiry" => self.check_expiry(&domain_arg(args)?).await,
+            "domain_stats" => domain_stats(&domains_arg(args)?),
+            "threat_scan" => self.threat_scan(&domain_arg(args)?).await,
+            "homoglyph_check" => homoglyph_check(&domain_arg(args)?),
+            "generate_typosquats" => generate_typosquats(args),
+            "generate_domains" => generate_domains(args),
+            "get_history" => self.get_history(args),
+            "diff_whois" => diff_whois(args),
+            "search_records" => self.search_records(args),
+            "export_csv" | "export_json" => self.export(name, args),
+            "get_settings" => Ok(json!({
+                "lookup": self.settings,
+                "history": self.history.is_some(),
+                "search": self.search.is_some(),
+                "knowledge": self.knowledge.is_some(),
+                "cache": self.cache.as_ref().map(|p| p.display().to_string()),
+                "export_dir": self.export_dir.as_ref().map(|p| p.display().to_string()),
+            })),
+            "get_cache_stats" => {
+                let stats = db_cache_stats(&self.cache_path()?)?;
+                Ok(json!(stats))
+            }
+            "clear_cache" => {
+                let domain = args["domain"]
+                    .as_str()
+                    .filter(|d| !d.trim().is_empty())
+                    .map(normalize_domain);
+                let cleared = db_cache_clear(&self.cache_path()?, domain.as_deref())?;
+                Ok(json!({ "domain": domain, "cleared": cleared }))
+            }
+            other => Err(format!("Unknown tool: {other}")),
+        }
+    }
+
+    async fn whois_lookup(
+        &self,
+        domain: &str,
+        server: Option<&str>,
+        include_raw: bool,
+    ) -> Result<Value, String> {
+        let reply = self.backend.whois(domain, server).await?;
+        let status = is_domain_available(&reply);
+        let params = get_domain_parameters(Some(domain.to_string()), Some(status), reply);
+
+        if let Some(store) = &self.history {
+            let mut snapshot = snapshot_from(domain, &params);
+            match store.insert(&snapshot) {
+                Ok(id) => {
+                    snapshot.id = Some(id);
+                    if let Some(index) = &self.search {
+                        if let Err(e) = index.add(&Document::from_snapshot(&snapshot)) {
+                            log::warn!("Failed to index history snapshot for {domain}: {e}");
+                        }
+                    }
+                }
+                Err(e) => log::warn!("Failed to record history snapshot for {domain}: {e}"),
+            }
+        }
+        Ok(summarize(&params, include_raw))
+    }
+
+    async fn bulk_whois(&self, args: &Value) -> Result<Value, String> {
+        let domains = domains_arg(args)?;
+        if domains.len() > MAX_BULK_DOMAINS {
+            return Err(format!(
+                "Too many domains ({}); at most {MAX_BULK_DOMAINS} per call",
+                domains.len()
+            ));
+        }
+
+        let mut results: Vec<(usize, Value)> =
+            futures::stream::iter(domains.into_iter().enumerate())
+                .map(|(i, domain)| async move {
+                    let value = match self.whois_lookup(&domain, None, false).await {
+                        Ok(v) => v,
+                        Err(e) => json!({ "domain": domain, "error": e }),
+                    };
+                    (i, value)
+                })
+                .buffer_unordered(BULK_CONCURRENCY)
+                .collect()
+                .await;
+        results.sort_by_key(|(i, _)| *i);
+        Ok(Value::Array(results.into_iter().map(|(_, v)| v).collect()))
+    }
+
+    async fn rdap_lookup(&self, domain: &str) -> Result<Value, String> {
+        let body = self.backend.rdap(domain).await?;
+        let json: Value =
+            serde_json::from_str(&body).map_err(|e| format!("Invalid RDAP response: {e}"))?;
+        if let Some(code) = json.get("errorCode") {
+            return Ok(json!({ "domain": domain, "found": false, "error_code": code }));
+        }
+        // Keep the fields an answer needs; full RDAP objects are large.
+        Ok(json!({
+            "domain": domain,
+            "found": true,
+            "ldh_name": json["ldhName"],
+            "status": json["status"],
+            "events": json["events"],
+            "nameservers": json["nameservers"]
+                .as_array()
+                .map(|ns| ns.iter().map(|n| n["ldhName"].clone()).collect::<Vec<_>>()),
+        }))
+    }
+
+    async fn dns_lookup(&self, args: &Value) -> Result<Value, String> {
+        let domain = domain_arg(args)?;
+        let record_type = args["record_type"].as_str().unwrap_or("NS");
+        if !record_type.eq_ignore_ascii_case("NS") {
+            return Err(format!(
+                "Record type {record_type} is not supported; only NS lookups are available"
+            ));
+        }
+        let delegated = self.backend.has_nameservers(&domain).await?;
+        Ok(json!({ "domain": domain, "record_type": "NS", "has_records": delegated }))
+    }
+
+    /// A delegation in DNS settles it; otherwise ask WHOIS.
+    async fn check_availability(&self, domain: &str) -> Result<Value, String> {
+        if self.backend.has_nameservers(domain).await.unwrap_or(false) {
+            return Ok(json!({ "domain": domain, "status": "unavailable", "source": "dns" }));
+        }
+        let reply = self.backend.whois(domain, None).await?;
+        let status = is_domain_available(&reply);
+        Ok(json!({
+            "domain": domain,
+            "status": status.as_str(),
+            "available": status == DomainStatus::Available,
+            "source": "whois",
+        }))
+    }
+
+    async fn check_expiry(&self, domain: &str) -> Result<Value, String> {
+        let reply = self.backend.whois(domain, None).await?;
+        let params = get_domain_parameters(Some(domain.to_string()), None, reply);
+        let expiry = params.expiry_date.as_deref().and_then(parse_whois_date);
+        let mut evidence = PhaseEvidence::from_dates(
+            expiry,
+            params.registrar.as_deref().map(registrar_slug).as_deref(),
+        )
+        .with_status_codes(parse_status_codes(
+            params.whoisreply.as_deref().unwrap_or_default(),
+        ));
+        // RDAP adds lifecycle events; without it the WHOIS evidence stands.
+        match self
+            .backend
+            .rdap(domain)
+            .await
+            .map(|body| serde_json::from_str::<Value>(&body))
+        {
+            Ok(Ok(rdap)) if rdap.get("errorCode").is_none() => evidence = evidence.with_rdap(&rdap),
+            Ok(_) => {}
+            Err(e) => log::debug!("check_expiry: RDAP for {domain} failed: {e}"),
+        }
+        let state = DomainExpiry::assess(domain, &evidence, Utc::now());
+        let mut value = serde_json::to_value(&state).map_err(|e| e.to_string())?;
+        value["renewable"] = json!(state.is_renewable());
+        value["catchable"] = json!(state.is_catchable());
+        Ok(value)
+    }
+
+    /// Score the name alone when WHOIS is unavailable, so a lookup failure
+    /// still yields an answer.
+    async fn threat_scan(&self, domain: &str) -> Result<Value, String> {
+        let params = match self.backend.whois(domain, None).await {
+            Ok(reply) => Some(get_domain_parameters(Some(domain.to_string()), None, reply)),
+            Err(e) => {
+                log::debug!("threat_scan: WHOIS for {domain} failed: {e}");
+                None
+            }
+        };
+        let registrar = params.as_ref().and_then(|p| p.registrar.as_deref());
+        let age_days = params
+            .as_ref()
+            .and_then(|p| p.creation_date.as_deref())
+            .and_then(parse_whois_date)
+            .map(|created| (Utc::now() - created).num_days());
+        let assessment = assess_domain(domain, registrar, age_days, &[]);
+        let mut value = serde_json::to_value(&assessment).map_err(|e| e.to_string())?;
+        value["whois_available"] = json!(params.is_some());
+        Ok(value)
+    }
+
+    fn get_history(&self, args: &Value) -> Result<Value, String> {
+        let domain = domain_arg(args)?;
+        let store = self
+            .history
+            .as_ref()
+            .ok_or("No history store is configured")?;
+        let limit = args["limit"].as_u64().unwrap_or(10) as usize;
+        let snapshots = store
+            .get_domain_snapshots(&domain)
+            .map_err(|e| e.to_string())?;
+        let total = snapshots.len();
+        let recent: Vec<Value> = snapshots
+            .iter()
+            .rev()
+            .take(limit)
+            .map(|s| {
+                json!({
+                    "id": s.id,
+                    "captured_at": s.captured_at.to_rfc3339(),
+                    "protocol": s.protocol.to_string(),
+                    "registrar": s.registrar,
+                    "expiry_date": s.expiry_date.map(|d| d.to_rfc3339()),
+                    "status_codes": s.status_codes,
+                })
+            })
+            .collect();
+        Ok(json!({ "domain": domain, "total": total, "snapshots": recent }))
+    }
+
+    fn search_records(&self, args: &Value) -> Result<Value, String> {
+        let index = self
+            .search
+            .as_ref()
+            .ok_or("No search index is configured")?;
+        let mut query = SearchQuery::parse(str_arg(args, "query")?);
+        if let Some(limit) = args["limit"].as_u64() {
+            query.limit = limit as usize;
+        }
+        let hits = index.search(&query).map_err(|e| e.to_string())?;
+        let results: Vec<Value> = hits
+            .iter()
+            .map(|hit| {
+                let doc = &hit.document;
+                let excerpt: String = doc.text.chars().take(300).collect();
+                json!({
+                    "kind": doc.kind,
+                    "source_id": doc.source_id,
+                    "title": doc.title,
+                    "domain": doc.domain,
+                    "registrar": doc.registrar,
+                    "nameservers": doc.nameservers,
+                    "timestamp": doc.timestamp.to_rfc3339(),
+                    "score": hit.score,
+                    "excerpt": excerpt,
+                })
+            })
+            .collect();
+        Ok(json!({ "total": results.len(), "results": results }))
+    }
+
+    fn cache_path(&self) -> Result<String, String> {
+        self.cache
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string())
+            .ok_or_else(|| "No request cache is configured".to_string())
+    }
+
+    /// `export_csv` writes one row per line; `export_json` checks and
+    /// pretty-prints its input first.
+    fn export(&self, tool: &str, args: &Value) -> Result<Value, String> {
+        let dir = self
+            .export_dir
+            .as_ref()
+            .ok_or("No export directory is configured")?;
+        let (extension, content, rows) = if tool == "export_csv" {
+            let rows: Vec<&str> = args["data"]
+                .as_array()
+                .ok_or("Missing required argument 'data'")?
+                .iter()
+                .filter_map(|r| r.as_str())
+                .collect();
+            ("csv", rows.join("\n") + "\n", rows.len())
+        } else {
+            let data: Value = serde_json::from_str(str_arg(args, "data")?)
+                .map_err(|e| format!("'data' is not valid JSON: {e}"))?;
+            let rows = data.as_array().map_or(1, Vec::len);
+            let pretty = serde_json::to_string_pretty(&data).map_err(|e| e.to_string())?;
+            ("json", pretty, rows)
+        };
+        let name = export_filename(args["filename"].as_str(), extension)?;
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        let path = dir.join(name);
+        std::fs::write(&path, content.as_bytes()).map_err(|e| e.to_string())?;
+        Ok(json!({
+            "path": path.display().to_string(),
+            "rows": rows,
+            "bytes": content.len(),
+        }))
+    }
+}
+
+/// A bare file name inside the export directory: directory parts are
+/// rejected and the extension is forced to `extension`.
+fn export_filename(requested: Option<&str>, extension: &str) -> Result<String, String> {
+    let Some(requested) = requested.map(str::trim).filter(|n| !n.is_empty()) else {
+        return Ok(format!(
+            "agent-export-{}.{extension}",
+            Utc::now().format("%Y%m%d-%H%M%S")
+        ));
+    };
+    let path = Path::new(requested);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .filter(|_| path.file_name().map(|n| n == requested).unwrap_or(false))
+        .filter(|s| !s.starts_with('.'))
+        .ok_or_else(|| format!("Invalid export filename: {requested}"))?;
+    Ok(format!("{stem}.{extension}"))
+}
+
+/// Counts, TLD spread and label shape of a domain list.
+fn domain_stats(domains: &[String]) -> Result<Value, String> {
+    let mut unique: Vec<&str> = domains.iter().map(String::as_str).collect();
+    unique.sort_unstable();
+    unique.dedup();
+
+    let mut tlds: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut lengths = Vec::with_capacity(unique.len());
+    let (mut hyphens, mut digits, mut idn) = (0, 0, 0);
+    for domain in &unique {
+        let (label, tld) = domain.split_once('.').unwrap_or((domain, ""));
+        *tlds.entry(tld).or_default() += 1;
+        lengths.push(label.chars().count());
+        if label.contains('-') {
+            hyphens += 1;
+        }
+        if label.chars().any(|c| c.is_ascii_digit()) {
+            digits += 1;
+        }
+        if domain.starts_with("xn--") || domain.contains(".xn--") || !domain.is_ascii() {
+            idn += 1;
+        }
+    }
+    let average = if lengths.is_empty() {
+        0.0
+    } else {
+        lengths.iter().sum::<usize>() as f64 / lengths.len() as f64
+    };
+    Ok(json!({
+        "total": domains.len(),
+        "unique": unique.len(),
+        "duplicates": domains.len() - unique.len(),
+        "tlds": tlds,
+        "label_length": {
+            "min": lengths.iter().min(),
+            "max": lengths.iter().max(),
+            "average": (average * 10.0).round() / 10.0,
+        },
+        "with_hyphens": hyphens,
+        "with_digits": digits,
+        "idn": idn,
+    }))
+}
+
+/// Look-alike registrations of `domain` under the same suffix.
+fn generate_typosquats(args: &Value) -> Result<Value, String> {
+    let domain = domain_arg(args)?;
+    let (label, suffix) = domain
+        .split_once('.')
+        .ok_or_else(|| format!("'{domain}' has no TLD"))?;
+    let max = max_results_arg(args, 50);
+    let config = MutatorConfig {
+        kinds: vec![
+            MutationKind::Homoglyph,
+            MutationKind::CharSwap,
+            MutationKind::CharDrop,
+            MutationKind::ConsonantDoubling,
+            MutationKind::LeetSpeak,
+            MutationKind::VowelSubstitution,
+            MutationKind::Hyphenation,
+            MutationKind::CommonSuffix,
+        ],
+        max_variants: MAX_GENERATED,
+    };
+    let candidates: Vec<String> = mutate_domain(label, &config)
+        .into_iter()
+        .filter(|v| is_ldh_label(v))
+        .map(|v| format!("{v}.{suffix}"))
+        .take(max)
+        .collect();
+    Ok(json!({
+        "domain": domain,
+        "total": candidates.len(),
+        "candidates": candidates,
+    }))
+}
+
+/// Ranked name suggestions built from keywords.
+fn generate_domains(args: &Value) -> Result<Value, String> {
+    let keywords = string_list(&args["keywords"]);
+    if keywords.is_empty() {
+        return Err("Missing required argument 'keywords'".into());
+    }
+    let mut tlds: Vec<String> = string_list(&args["tlds"])
+        .iter()
+        .map(|t| format!(".{}", t.trim_start_matches('.')))
+        .collect();
+    if tlds.is_empty() {
+        tlds.push(".com".into());
+    }
+    let engine = GeneratorEngine::new(GeneratorConfig {
+        keywords,
+        tlds,
+        max_results: max_results_arg(args, 25),
+        ..Default::default()
+    });
+    let suggestions: Vec<Value> = engine
+        .generate_ranked(&DomainScorer::default())
+        .into_iter()
+        .map(|s| json!({ "domain": s.domain.domain, "score": s.breakdown.total }))
+        .collect();
+    Ok(json!({ "total": suggestions.len(), "suggestions": suggestions }))
+}
+
+fn is_ldh_label(label: &str) -> bool {
+    !label.is_empty()
+        && label.len() <= 63
+        && !label.starts_with('-')
+        && !label.ends_with('-')
+        && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+fn homoglyph_check(domain: &str) -> Result<Value, String> {
+    let indicators: Vec<_> = PatternDetector::new()
+        .analyse_domain(domain)
+        .into_iter()
+        .filter(|i| {
+            matches!(
+                i.category,
+                ThreatCategory::Homoglyph
+                    | ThreatCategory::Typosquat
+                    | ThreatCategory::BrandImpersonation
+            )
+        })
+        .collect();
+    Ok(json!({
+        "domain": domain,
+        "suspicious": !indicators.is_empty(),
+        "indicators": indicators,
+    }))
+}
+
+fn diff_whois(args: &Value) -> Result<Value, String> {
+    let domain = domain_arg(args)?;
+    let parse = |key: &str| -> Result<Snapshot, String> {
+        let raw = str_arg(args, key)?;
+        let params = get_domain_parameters(Some(domain.clone()), None, raw.to_string());
+        Ok(snapshot_from(&domain, &params))
+    };
+    let diff = diff_snapshots(&parse("record_a")?, &parse("record_b")?);
+    Ok(json!({
+        "domain": domain,
+        "changes": diff.len(),
+        "entries": diff.entries,
+    }))
+}
+
+/// Knowledge worth keeping from a tool result, with how long it stays
+/// fresh. WHOIS data changes slowly; availability and expiry phase do not.
+fn knowledge_from(tool: &str, value: &Value) -> Option<(DomainKnowledge, Duration)> {
+    let domain = value["domain"].as_str()?;
+    let text = |key: &str| value[key].as_str().map(str::to_string);
+    let (kind, summary, ttl) = match tool {
+        "whois_lookup" | "bulk_whois" => {
+            let fields: Vec<String> = [
+                ("status", "status"),
+                ("registrar", "registrar"),
+                ("registrant", "registrant"),
+                ("created", "creation_date"),
+                ("expires", "expiry_date"),
+            ]
+            .iter()
+            .filter_map(|(label, key)| text(key).map(|v| format!("{label} {v}")))
+            .collect();
+            if fields.is_empty() {
+                return None;
+            }
+            ("whois", fields.join("; "), Duration::days(7))
+        }
+        "rdap_lookup" => {
+            let summary = if value["found"].as_bool() == Some(true) {
+                format!("RDAP record found; status {}", value["status"])
+            } else {
+                "No RDAP record".to_string()
+            };
+            ("rdap", summary, Duration::days(7))
+        }
+        "check_availability" => (
+            "availability",
+            format!("{} (via {})", text("status")?, text("source")?),
+            Duration::days(1),
+        ),
+        "check_expiry" => {
+            let mut summary = format!("Phase {}", text("phase")?);
+            if let Some(expiry) = text("expiry_date") {
+                summary.push_str(&format!("; expires {expiry}"));
+            }
+            if let Some(days) = value["days_until_expiry"].as_i64() {
+                summary.push_str(&format!(" ({days} days)"));
+            }
+            ("expiry", summary, Duration::days(1))
+        }
+        "threat_scan" => (
+            "threat",
+            value["risk"]["summary"].as_str()?.to_string(),
+            Duration::days(3),
+        ),
+        _ => return None,
+    };
+    let knowledge = DomainKnowledge::new(domain, kind, &summary)
+        .with_data(value.clone())
+        .with_source(tool);
+    Some((knowledge, ttl))
+}
+
+fn str_arg<'a>(args: &'a Value, key: &str) -> Result<&'a str, String> {
+    args[key]
+        .as_str()
+        .filter(|s| !s.trim().is_empty())
+        .ok_or_else(|| format!("Missing required argument '{key}'"))
+}
+
+fn domain_arg(args: &Value) -> Result<String, String> {
+    str_arg(args, "domain").map(normalize_domain)
+}
+
+fn domains_arg(args: &Value) -> Result<Vec<String>, String> {
+    let domains: Vec<String> = args["domains"]
+        .as_array()
+        .ok_or("Missing required argument 'domains'")?
+        .iter()
+        .filter_map(|d| d.as_str())
+        .map(normalize_domain)
+        .filter(|d| !d.is_empty())
+        .collect();
+    Ok(domains)
+}
+
+fn string_list(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|v| v.as_str())
+                .map(|s| s.trim().to_lowercase())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn max_results_arg(args: &Value, default: usize) -> usize {
+    args["max_results"]
+        .as_u64()
+        .map_or(default, |n| n as usize)
+        .clamp(1, MAX_GENERATED)
+}
+
+fn normalize_domain(domain: &str) -> String {
+    domain.trim().trim_end_matches('.').to_lowercase()
+}
+
+/// Compact JSON view of parsed WHOIS parameters.
+fn summarize(params: &WhoisParams, include_raw: bool) -> Value {
+    let mut value = json!({
+        "domain": params.domain,
+        "status": params.status.as_ref().map(|s| s.as_str()),
+        "registrar": params.registrar,
+        "registrant": params.company,
+        "creation_date": params.creation_date,
+        "update_date": params.update_date,
+        "expiry_date": params.expiry_date,
+    });
+    if include_raw {
+        if let Some(raw) = &params.whoisreply {
+            let truncated: String = raw.chars().take(MAX_RAW_CHARS).collect();
+            value["raw"] = json!(truncated);
+            value["raw_truncated"] = json!(truncated.len() < raw.len());
+        }
+    }
+    value
+}
+
+fn snapshot_from(domain: &str, params: &WhoisParams) -> Snapshot {
+    let raw = params.whoisreply.clone().unwrap_or_default();
+    let mut snap = Snapshot::new(domain, LookupProtocol::Whois, raw);
+    if let Some(fields) = &params.whois_json {
+        snap.fields = fields
+            .iter()
+            .map(|(k, v)| (k.to_lowercase(), v.clone()))
+            .collect();
+    }
+    snap.registrar = params.registrar.clone();
+    snap.created_date = params.creation_date.as_deref().and_then(parse_whois_date);
+    snap.updated_date = params.update_date.as_deref().and_then(parse_whois_date);
+    snap.expiry_date = params.expiry_date.as_deref().and_then(parse_whois_date);
+    snap
+}
+
+/// Lowercased first word of a registrar name, matching the slugs used by
+/// `GracePeriod::for_registrar` (e.g. "GoDaddy.com, LLC" → "godaddy").
+fn registrar_slug(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .find(|w| !w.is_empty())
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+/// Parse the date formats commonly found in WHOIS replies.
+fn parse_whois_date(s: &str) -> Option<DateTime<Utc>> {
+    let s = s.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    for fmt in [
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y.%m.%d %H:%M:%S",
+    ] {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(s.trim_end_matches('Z'), fmt) {
+            return Some(dt.and_utc());
+        }
+    }
+    for fmt in ["%Y-%m-%d", "%Y.%m.%d", "%d-%b-%Y", "%d.%m.%Y", "%Y/%m/%d"] {
+        if let Ok(d) = NaiveDate::parse_from_str(s, fmt) {
+            return d.and_hms_opt(0, 0, 0).map(|dt| dt.and_utc());
+        }
+    }
+    None
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Canned replies keyed by domain.
+    #[derive(Default)]
+    struct FakeLookup {
+        whois: HashMap<String, String>,
+        delegated: Vec<String>,
+    }
+
+    impl LookupBackend for FakeLookup {
+        async fn whois(&self, domain: &str, _server: Option<&str>) -> Result<String, String> {
+            self.whois
+                .get(domain)
+                .cloned()
+                .ok_or_else(|| format!("connection refused for {domain}"))
+        }
+
+        async fn rdap(&self, _domain: &str) -> Result<String, String> {
+            Ok(r#"{"errorCode":404}"#.into())
+        }
+
+        async fn has_nameservers(&self, domain: &str) -> Result<bool, String> {
+            Ok(self.delegated.iter().any(|d| d == domain))
+        }
+
+        async fn reverse_dns(&self, ip: &str) -> Result<Vec<String>, String> {
+            Ok(match ip {
+                "192.0.2.1" => vec!["host.taken.com".into()],
+                _ => Vec::new(),
+            })
+        }
+    }
+
+    const TAKEN: &str = "Domain Name: TAKEN.COM\nRegistrar: GoDaddy.com, LLC\n\
+        Creation Date: 2001-05-01T00:00:00Z\nRegistry Expiry Date: 2099-05-01T00:00:00Z\n\
+        Domain Status: clientTransferProhibited\n";
+
+    fn tools() -> BuiltinTools<FakeLookup> {
+        let mut fake = FakeLookup::default();
+        fake.whois.insert("taken.com".into(), TAKEN.into());
+        fake.whois.insert(
+            "free.com".into(),
+            "No match for domain \"FREE.COM\".".into(),
+        );
+        fake.delegated.push("taken.com".into());
+        BuiltinTools::with_backend(fake)
+    }
+
+    #[tokio::test]
+    async fn test_whois_lookup_parses_and_records_history() {
+        let store = Arc::new(HistoryStore::open_in_memory().unwrap());
+        let t = tools().with_history(Arc::clone(&store));
+        let v = t
+            .call("whois_lookup", &json!({"domain": "Taken.com."}))
+            .await
+            .unwrap();
+        assert_eq!(v["status"], "unavailable");
+        assert_eq!(v["registrar"], "GoDaddy.com, LLC");
+        assert_eq!(v["raw_truncated"], false);
+
+        let history = t
+            .call("get_history", &json!({"domain": "taken.com"}))
+            .await
+            .unwrap();
+        assert_eq!(history["total"], 1);
+        assert!(history["snapshots"][0]["expiry_date"]
+            .as_str()
+            .unwrap()
+            .starts_with("2099-05-01"));
+    }
+
+    #[tokio::test]
+    async fn test_recorded_snapshots_are_searchable() {
+        let index = Arc::new(SearchIndex::open_in_memory().unwrap());
+        let t = tools()
+            .with_history(Arc::new(HistoryStore::open_in_memory().unwrap()))
+            .with_search(Arc::clone(&index));
+        t.call("whois_lookup", &json!({"domain": "taken.com"}))
+            .await
+            .unwrap();
+
+        let v = t
+            .call(
+                "search_records",
+                &json!({"query": "registrar:godaddy in:whois last month"}),
+            )
+            .await
+            .unwrap();
+        assert_eq!(v["total"], 1);
+        assert_eq!(v["results"][0]["domain"], "taken.com");
+        assert!(tools()
+            .call("search_records", &json!({"query": "x"}))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_findings_are_stored_as_knowledge() {
+        let store = Arc::new(KnowledgeStore::open_in_memory().unwrap());
+        let t = tools().with_knowledge(Arc::clone(&store));
+        t.call("bulk_whois", &json!({"domains": ["taken.com", "free.com"]}))
+            .await
+            .unwrap();
+        t.call("check_availability", &json!({"domain": "free.com"}))
+            .await
+            .unwrap();
+        t.call("homoglyph_check", &json!({"domain": "taken.com"}))
+            .await
+            .unwrap();
+
+        let taken = store.list(Some("taken.com"), false).unwrap();
+        assert_eq!(taken.len(), 1);
+        let k = &taken[0].knowledge;
+        assert_eq!(k.kind, "whois");
+        assert_eq!(k.source.as_deref(), Some("bulk_whois"));
+        assert!(k.summary.contains("registrar GoDaddy.com, LLC"));
+        assert!(taken[0].expires_at.is_some());
+
+        let free = store.list(Some("free.com"), false).unwrap();
+        assert_eq!(free.len(), 2);
+        assert!(free.iter().any(|e| e.knowledge.kind == "availability"
+            && e.knowledge.summary == "available (via whois)"));
+    }
+
+    #[tokio::test]
+    async fn test_check_availability_uses_dns_then_whois() {
+        let t = tools();
+        let taken = t
+            .call("check_availability", &json!({"domain": "taken.com"}))
+            .await
+            .unwrap();
+        assert_eq!(taken["source"], "dns");
+        let free = t
+            .call("check_availability", &json!({"domain": "free.com"}))
+            .await
+            .unwrap();
+        assert_eq!(free["available"], true);
+    }
+
+    #[tokio::test]
+    async fn test_check_expiry_and_threat_scan() {
+        let t = tools();
+        let expiry = t
+            .call("check_expiry", &json!({"domain": "taken.com"}))
+            .await
+            .unwrap();
+        assert_eq!(expiry["phase"], "active");
+        assert_eq!(expiry["grace"]["auto_renew_days"], 25);
+        assert_eq!(expiry["basis"], "dates");
+        assert_eq!(expiry["renewable"], true);
+
+        // WHOIS failure still produces a name-based assessment.
+        let scan = t
+            .call("threat_scan", &json!({"domain": "paypa1-login.xyz"}))
+            .await
+            .unwrap();
+        assert_eq!(scan["whois_available"], false);
+        assert!(scan["risk"]["score"].as_u64().unwrap() > 0);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_whois_reports_per_domain_errors() {
+        let v = tools()
+            .call(
+                "bulk_whois",
+                &json!({"domains": ["taken.com", "nowhere.com"]}),
+            )
+            .await
+            .unwrap();
+        assert_eq!(v[0]["status"], "unavailable");
+        assert!(v[0].get("raw").is_none());
+        assert!(v[1]["error"].as_str().unwrap().contains("refused"));
+    }
+
+    #[tokio::test]
+    async fn test_argument_errors() {
+        let t = tools();
+        let err = t.call("whois_lookup", &json!({})).await.unwrap_err();
+        assert!(err.contains("domain"));
+        let err = t
+            .call(
+                "dns_lookup",
+                &json!({"domain": "a.com", "record_type": "MX"}),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.contains("only NS"));
+        assert!(t
+            .call("get_history", &json!({"domain": "a.com"}))
+            .await
+            .unwrap_err()
+            .contains("history"));
+    }
+
+    #[tokio::test]
+    async fn test_generator_and_stats_tools() {
+        let t = tools();
+        let squats = t
+            .call(
+                "generate_typosquats",
+                &json!({"domain": "paypal.com", "max_results": 10}),
+            )
+            .await
+            .unwrap();
+        let candidates = squats["candidates"].as_array().unwrap();
+        assert!(!candidates.is_empty() && candidates.len() <= 10);
+        assert!(candidates
+            .iter()
+            .all(|c| c.as_str().unwrap().ends_with(".com") && c != "paypal.com"));
+
+        let names = t
+            .call(
+                "generate_domains",
+                &json!({"keywords": ["cloud"], "tlds": ["io"], "max_results": 5}),
+            )
+            .await
+            .unwrap();
+        assert_eq!(names["total"], 5);
+        assert!(names["suggestions"][0]["domain"]
+            .as_str()
+            .unwrap()
+            .ends_with(".io"));
+
+        let stats = t
+            .call(
+                "domain_stats",
+                &json!({"domains": ["a-b.com", "a-b.com", "x1.net", "xn--bcher-kva.de"]}),
+            )
+            .await
+            .unwrap();
+        assert_eq!(stats["unique"], 3);
+        assert_eq!(stats["duplicates"], 1);
+        assert_eq!(stats["tlds"]["com"], 1);
+        assert_eq!(stats["with_hyphens"], 2);
+        assert_eq!(stats["with_digits"], 1);
+        assert_eq!(stats["idn"], 1);
+
+        let ptr = t
+            .call("reverse_dns", &json!({"ip": "192.0.2.1"}))
+            .await
+            .unwrap();
+        assert_eq!(ptr["names"][0], "host.taken.com");
+    }
+
+    #[tokio::test]
+    async fn test_cache_export_and_settings_tools() {
+        let dir = std::env::temp_dir().join(format!("wd_agent_tools_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let cache = dir.join("request-cache.sqlite");
+        let cache_str = cache.to_string_lossy().to_string();
+        wd_db::db_cache_set(&cache_str, "whois:taken.com", "reply", None).unwrap();
+        wd_db::db_cache_set(&cache_str, "whois:free.com", "reply", None).unwrap();
+
+        let t = tools()
+            .with_cache(&cache)
+            .with_export_dir(dir.join("exports"));
+        let stats = t.call("get_cache_stats", &json!({})).await.unwrap();
+        assert_eq!(stats["entries"], 2);
+        let cleared = t
+            .call("clear_cache", &json!({"domain": "Taken.com"}))
+            .await
+            .unwrap();
+        assert_eq!(cleared["cleared"], 1);
+
+        let csv = t
+            .call(
+                "export_csv",
+                &json!({"data": ["domain,status", "taken.com,unavailable"], "filename": "out"}),
+            )
+            .await
+            .unwrap();
+        let written = std::fs::read_to_string(csv["path"].as_str().unwrap()).unwrap();
+        assert_eq!(written, "domain,status\ntaken.com,unavailable\n");
+        assert!(csv["path"].as_str().unwrap().ends_with("out.csv"));
+        assert!(t
+            .call(
+                "export_json",
+                &json!({"data": "[1]", "filename": "../escape.json"}),
+            )
+            .await
+            .unwrap_err()
+            .contains("Invalid export filename"));
+        assert!(t
+            .call("export_json", &json!({"data": "{oops"}))
+            .await
+            .is_err());
+
+        let settings = t.call("get_settings", &json!({})).await.unwrap();
+        assert!(settings["lookup"]["general"].is_object());
+        assert_eq!(settings["history"], false);
+        assert!(tools()
+            .call("get_cache_stats", &json!({}))
+            .await
+            .unwrap_err()
+            .contains("cache"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_diff_whois() {
+        let newer = TAKEN.replace("GoDaddy.com, LLC", "NameCheap, Inc.");
+        let v = diff_whois(&json!({
+            "domain": "taken.com",
+            "record_a": TAKEN,
+            "record_b": newer,
+        }))
+        .unwrap();
+        assert!(v["changes"].as_u64().unwrap() >= 1);
+    }
+
+    #[test]
+    fn test_parse_whois_date_formats() {
+        for s in [
+            "2025-03-01T10:00:00Z",
+            "2025-03-01T10:00:00.0Z",
+            "2025-03-01 10:00:00",
+            "2025-03-01",
+            "01-Mar-2025",
+        ] {
+            let d = parse_whois_date(s).unwrap_or_else(|| panic!("failed on {s}"));
+            assert_eq!(d.format("%Y-%m-%d").to_string(), "2025-03-01");
+        }
+        assert!(parse_whois_date("soon").is_none());
+        assert_eq!(registrar_slug("GoDaddy.com, LLC"), "godaddy");
+    }
+
+    #[tokio::test]
+    async fn test_install_registers_async_handlers() {
+        let mut executor = ToolExecutor::new();
+        tools().install(&mut executor);
+        assert_eq!(
+            executor.len(),
+            BuiltinTools::<FakeLookup>::tool_names().len()
+        );
+        let v = executor
+            .execute_async("homoglyph_check", &json!({"domain": "example.com"}))
+            .await
+            .unwrap();
+        assert_eq!(v["domain"], "example.com");
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use futures::channel::oneshot;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+/// A tool call waiting for the user's decision.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ConfirmationRequest {
+    pub id: String,
+    pub run_id: String,
+    pub tool_name: String,
+    /// Arguments the model proposed.
+    pub arguments: Value,
+}
+
+/// The user's answer to a [`ConfirmationRequest`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "decision", rename_all = "snake_case")]
+pub enum ConfirmationDecision {
+    /// Run the tool with the proposed arguments.
+    Approve,
+    /// Do not run the tool; the model is told why.
+    Deny {
+        #[serde(default)]
+        reason: Option<String>,
+    },
+    /// Run the tool with replacement arguments.
+    Edit { arguments: Value },
+}
+
+struct Pending {
+    request: ConfirmationRequest,
+    reply: oneshot::Sender<ConfirmationDecision>,
+}
+
+/// Channel between a running agent and whoever answers its confirmation
+/// requests (the GUI or the CLI).
+///
+/// The agent registers a request and suspends until [`resolve`](Self::resolve)
+/// is called with the same ID. Clones share the same pending set, so one
+/// clone can be given to the agent and another kept by the UI.
+#[derive(Clone, Default)]
+pub struct ConfirmationChannel {
+    pending: Arc<Mutex<HashMap<String, Pending>>>,
+}
+
+impl ConfirmationChannel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests currently waiting for a decision.
+    pub fn pending(&self) -> Vec<ConfirmationRequest> {
+        let pending = self.pending.lock().unwrap();
+        pending.values().map(|p| p.request.clone()).collect()
+    }
+
+    /// Answer a pending request. Fails if no request has this ID.
+    pub fn resolve(&self, id: &str, decision: ConfirmationDecision) -> Result<(), String> {
+        let pending = self
+            .pending
+            .lock()
+            .unwrap()
+            .remove(id)
+            .ok_or_else(|| format!("No pending confirmation '{id}'"))?;
+        pending
+            .reply
+            .send(decision)
+            .map_err(|_| format!("Confirmation '{id}' is no longer awaited"))
+    }
+
+    /// Deny every pending request, e.g. when the user closes the run.
+    pub fn deny_all(&self, reason: &str) {
+        let drained: Vec<Pending> = self
+            .pending
+            .lock()
+            .unwrap()
+            .drain()
+            .map(|(_, p)| p)
+            .collect();
+        for p in drained {
+            let _ = p.reply.send(ConfirmationDecision::Deny {
+                reason: Some(reason.to_string()),
+            });
+        }
+    }
+
+    /// Register a request for `tool_name` and return it together with a
+    /// future resolving to the decision. A request dropped without an
+    /// answer counts as denied.
+    pub(crate) fn request(
+        &self,
+        run_id: &str,
+        tool_name: &str,
+        arguments: &Value,
+    ) -> (
+        ConfirmationRequest,
+        impl std::future::Future<Output = ConfirmationDecision>,
+    ) {
+        let request = ConfirmationRequest {
+            id: Uuid::new_v4().to_string(),
+            run_id: run_id.to_string(),
+            tool_name: tool_name.to_string(),
+            arguments: arguments.clone(),
+        };
+        let (reply, rx) = oneshot::channel();
+        self.pending.lock().unwrap().insert(
+            request.id.clone(),
+            Pending {
+                request: request.clone(),
+                reply,
+            },
+        );
+        let decision = async move {
+            rx.await.unwrap_or(ConfirmationDecision::Deny {
+                reason: Some("confirmation abandoned".into()),
+            })
+        };
+        (request, decision)
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_resolve_pending_request() {
+        let channel = ConfirmationChannel::new();
+        let (request, decision) = channel.request("run", "clear_cache", &serde_json::json!({}));
+        assert_eq!(channel.pending().len(), 1);
+
+        channel
+            .resolve(&request.id, ConfirmationDecision::Approve)
+            .unwrap();
+        assert_eq!(decision.await, ConfirmationDecision::Approve);
+        assert!(channel.pending().is_empty());
+    }
+
+    #[test]
+    fn test_resolve_unknown_id() {
+        let channel = ConfirmationChannel::new();
+        assert!(channel
+            .resolve("missing", ConfirmationDecision::Approve)
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_deny_all() {
+        let channel = ConfirmationChannel::new();
+        let (_, decision) = channel.request("run", "export_csv", &serde_json::json!({}));
+        channel.deny_all("run closed");
+        assert_eq!(
+            decision.await,
+            ConfirmationDecision::Deny {
+                reason: Some("run closed".into())
+            }
+        );
+    }
+
+    #[test]
+    fn test_decision_serde() {
+        let d: ConfirmationDecision =
+            serde_json::from_str(r#"{"decision":"edit","arguments":{"domain":"a.com"}}"#).unwrap();
+        assert_eq!(
+            d,
+            ConfirmationDecision::Edit {
+                arguments: serde_json::json!({"domain": "a.com"})
+            }
+        );
+        let d: ConfirmationDecision = serde_json::from_str(r#"{"decision":"deny"}"#).unwrap();
+        assert_eq!(d, ConfirmationDecision::Deny { reason: None });
+    }
+}
@@ -1,4 +1,10 @@
+use std::path::Path;
+
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::agent::{Agent, AgentConfig};
+use crate::replay::{Fixture, ReplayMode, ReplayOutcome, Replayer};
 
 /// Grading of a response.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -10,6 +16,21 @@ pub enum EvalGrade {
     Failed,
 }
 
+impl EvalGrade {
+    /// Grade for a score between 0.0 and 1.0.
+    pub fn from_score(score: f64) -> Self {
+        if score >= 0.8 {
+            Self::Good
+        } else if score >= 0.6 {
+            Self::Acceptable
+        } else if score >= 0.4 {
+            Self::Poor
+        } else {
+            Self::Failed
+        }
+    }
+}
+
 /// Result of evaluating an agent response.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct EvalResult {
@@ -135,15 +156,7 @@ impl ResponseEvaluator {
         let total = checks.len();
         let score = passed as f64 / total as f64;
 
-        let grade = if score >= 0.8 {
-            EvalGrade::Good
-        } else if score >= 0.6 {
-            EvalGrade::Acceptable
-        } else if score >= 0.4 {
-            EvalGrade::Poor
-        } else {
-            EvalGrade::Failed
-        };
+        let grade = EvalGrade::from_score(score);
 
         let summary = format!("{passed}/{total} checks passed (score: {score:.2})",);
 
@@ -156,6 +169,267 @@ impl ResponseEvaluator {
     }
 }
 
+/// A rule a replayed run must satisfy.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum AssertionRule {
+    /// The answer contains `text` (case-insensitive).
+    AnswerContains {
+        text: String,
+    },
+    /// The answer does not contain `text` (case-insensitive).
+    AnswerExcludes {
+        text: String,
+    },
+    ToolCalled {
+        name: String,
+    },
+    ToolNotCalled {
+        name: String,
+    },
+    /// Some call of `tool` had argument `key` equal to `value`.
+    ToolArgument {
+        tool: String,
+        key: String,
+        value: Value,
+    },
+    MaxIterations {
+        max: usize,
+    },
+    /// The run produced a final answer.
+    Completed,
+}
+
+impl AssertionRule {
+    fn check(&self, outcome: &ReplayOutcome) -> EvalCheck {
+        let answer = outcome
+            .result
+            .answer
+            .as_deref()
+            .unwrap_or_default()
+            .to_lowercase();
+        let called = |name: &str| outcome.tool_calls.iter().any(|c| c.name == name);
+        let (name, passed, detail) = match self {
+            Self::AnswerContains { text } => (
+                "answer_contains",
+                answer.contains(&text.to_lowercase()),
+                format!("Answer should contain '{text}'"),
+            ),
+            Self::AnswerExcludes { text } => (
+                "answer_excludes",
+                !answer.contains(&text.to_lowercase()),
+                format!("Answer should not contain '{text}'"),
+            ),
+            Self::ToolCalled { name } => (
+                "tool_called",
+                called(name),
+                format!("Tool '{name}' should be called"),
+            ),
+            Self::ToolNotCalled { name } => (
+                "tool_not_called",
+                !called(name),
+                format!("Tool '{name}' should not be called"),
+            ),
+            Self::ToolArgument { tool, key, value } => (
+                "tool_argument",
+                outcome
+                    .tool_calls
+                    .iter()
+                    .any(|c| &c.name == tool && c.arguments.get(key) == Some(value)),
+                format!("Tool '{tool}' should be called with {key} = {value}"),
+            ),
+            Self::MaxIterations { max } => (
+                "max_iterations",
+                outcome.result.iterations <= *max,
+                format!("Iterations: {} (max {max})", outcome.result.iterations),
+            ),
+            Self::Completed => (
+                "completed",
+                outcome.result.completed,
+                "Run should produce an answer".to_string(),
+            ),
+        };
+        EvalCheck {
+            name: name.into(),
+            passed,
+            detail,
+        }
+    }
+}
+
+/// A regression case: a recorded run and what its replay must satisfy.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EvalCase {
+    pub fixture: Fixture,
+    /// Exact sequence of tools the run must execute. Empty means unchecked.
+    #[serde(default)]
+    pub expected_tools: Vec<String>,
+    #[serde(default)]
+    pub rules: Vec<AssertionRule>,
+    #[serde(default)]
+    pub mode: ReplayMode,
+}
+
+impl EvalCase {
+    pub fn new(fixture: Fixture) -> Self {
+        Self {
+            fixture,
+            expected_tools: Vec::new(),
+            rules: Vec::new(),
+            mode: ReplayMode::default(),
+        }
+    }
+
+    pub fn expect_tools(mut self, tools: &[&str]) -> Self {
+        self.expected_tools = tools.iter().map(|t| t.to_string()).collect();
+        self
+    }
+
+    pub fn with_rule(mut self, rule: AssertionRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    pub fn with_mode(mut self, mode: ReplayMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Score a replay of this case.
+    pub fn score(&self, outcome: &ReplayOutcome) -> EvalResult {
+        let mut checks = Vec::new();
+
+        checks.push(EvalCheck {
+            name: "replay".into(),
+            passed: outcome.divergences.is_empty(),
+            detail: if outcome.divergences.is_empty() {
+                "Replay followed the recording".into()
+            } else {
+                outcome.divergences.join("; ")
+            },
+        });
+
+        if !self.expected_tools.is_empty() {
+            let actual: Vec<&str> = outcome.tool_calls.iter().map(|c| c.name.as_str()).collect();
+            checks.push(EvalCheck {
+                name: "tool_sequence".into(),
+                passed: actual == self.expected_tools,
+                detail: format!("Expected {:?}, got {actual:?}", self.expected_tools),
+            });
+        }
+
+        checks.extend(self.rules.iter().map(|r| r.check(outcome)));
+
+        let passed = checks.iter().filter(|c| c.passed).count();
+        let total = checks.len();
+        let score = passed as f64 / total as f64;
+        EvalResult {
+            grade: EvalGrade::from_score(score),
+            checks,
+            score,
+            summary: format!("{passed}/{total} checks passed (score: {score:.2})"),
+        }
+    }
+}
+
+/// Score of one case in a suite run.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CaseReport {
+    pub name: String,
+    pub result: EvalResult,
+    pub answer: Option<String>,
+}
+
+/// Results of running an [`EvalSuite`].
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SuiteReport {
+    pub cases: Vec<CaseReport>,
+    /// Cases whose checks all passed.
+    pub passed: usize,
+    /// Mean score over all cases.
+    pub score: f64,
+}
+
+/// Replays recorded cases offline and scores them, so prompt and agent
+/// changes can be regression-tested without network access.
+pub struct EvalSuite {
+    cases: Vec<EvalCase>,
+    config: AgentConfig,
+    system_prompt: Option<String>,
+}
+
+impl EvalSuite {
+    pub fn new(cases: Vec<EvalCase>) -> Self {
+        Self {
+            cases,
+            config: AgentConfig::default(),
+            system_prompt: None,
+        }
+    }
+
+    /// Load a suite from a JSON file holding an array of cases.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let data = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        let cases = serde_json::from_str(&data)
+            .map_err(|e| format!("Invalid eval suite {}: {e}", path.display()))?;
+        Ok(Self::new(cases))
+    }
+
+    pub fn with_agent_config(mut self, config: AgentConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Replay every case with `prompt` instead of its recorded system
+    /// prompt. Strict cases will then report a divergence.
+    pub fn with_system_prompt(mut self, prompt: &str) -> Self {
+        self.system_prompt = Some(prompt.to_string());
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.cases.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cases.is_empty()
+    }
+
+    /// Replay and score every case.
+    pub async fn run(&self) -> SuiteReport {
+        let mut cases = Vec::new();
+        for case in &self.cases {
+            let mut replayer = Replayer::new(case.fixture.clone()).with_mode(case.mode);
+            if let Some(prompt) = &self.system_prompt {
+                replayer = replayer.with_system_prompt(prompt);
+            }
+            let mut agent = Agent::new(self.config.clone());
+            let outcome = replayer.run(&mut agent).await;
+            cases.push(CaseReport {
+                name: case.fixture.name.clone(),
+                result: case.score(&outcome),
+                answer: outcome.result.answer,
+            });
+        }
+
+        let passed = cases
+            .iter()
+            .filter(|c| c.result.checks.iter().all(|check| check.passed))
+            .count();
+        let score = if cases.is_empty() {
+            1.0
+        } else {
+            cases.iter().map(|c| c.result.score).sum::<f64>() / cases.len() as f64
+        };
+        SuiteReport {
+            cases,
+            passed,
+            score,
+        }
+    }
+}
+
 // ─── Tests ───────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -238,4 +512,51 @@ mod tests {
         assert!(result.score <= 1.0);
         assert!(!result.summary.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_suite_scores_replayed_cases() {
+        let fixture = crate::replay::tests::record_lookup().await;
+        let good = EvalCase::new(fixture.clone())
+            .expect_tools(&["whois_lookup"])
+            .with_rule(AssertionRule::Completed)
+            .with_rule(AssertionRule::AnswerContains {
+                text: "example registrar".into(),
+            })
+            .with_rule(AssertionRule::ToolArgument {
+                tool: "whois_lookup".into(),
+                key: "domain".into(),
+                value: serde_json::json!("example.com"),
+            });
+        let bad = EvalCase::new(fixture)
+            .expect_tools(&["dns_lookup"])
+            .with_rule(AssertionRule::ToolNotCalled {
+                name: "whois_lookup".into(),
+            })
+            .with_rule(AssertionRule::MaxIterations { max: 1 });
+
+        let json = serde_json::to_string(&vec![good, bad]).unwrap();
+        let cases: Vec<EvalCase> = serde_json::from_str(&json).unwrap();
+        let report = EvalSuite::new(cases).run().await;
+
+        assert_eq!(report.passed, 1);
+        assert!((report.cases[0].result.score - 1.0).abs() < f64::EPSILON);
+        assert_eq!(report.cases[0].result.grade, EvalGrade::Good);
+        assert_eq!(report.cases[1].result.grade, EvalGrade::Failed);
+        assert!((report.cases[1].result.score - 0.25).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_suite_prompt_change_with_lenient_case() {
+        let fixture = crate::replay::tests::record_lookup().await;
+        let case = EvalCase::new(fixture)
+            .with_mode(ReplayMode::Lenient)
+            .with_rule(AssertionRule::Completed);
+        let report = EvalSuite::new(vec![case])
+            .with_system_prompt("Updated prompt")
+            .run()
+            .await;
+        let checks = &report.cases[0].result.checks;
+        assert!(!checks[0].passed); // replay divergence is reported
+        assert!(checks[1].passed);
+    }
 }
@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::confirm::ConfirmationDecision;
+
 /// Kinds of events the agent can emit.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -42,6 +44,16 @@ pub enum EventKind {
     PlanStepCompleted {
         step_index: usize,
     },
+    ConfirmationRequested {
+        request_id: String,
+        tool_name: String,
+        arguments: serde_json::Value,
+    },
+    ConfirmationResolved {
+        request_id: String,
+        tool_name: String,
+        decision: ConfirmationDecision,
+    },
     Thinking {
         content: String,
     },
@@ -1,21 +1,48 @@
 use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
 
 /// Callback type for tool handlers.
 pub type ToolHandlerFn =
     Box<dyn Fn(&serde_json::Value) -> Result<serde_json::Value, String> + Send + Sync>;
 
+/// Boxed future returned by async tool handlers.
+pub type ToolFuture = Pin<Box<dyn Future<Output = Result<serde_json::Value, String>> + Send>>;
+
+/// Callback type for async tool handlers. Arguments are passed by value so
+/// the returned future can outlive the call.
+pub type AsyncToolHandlerFn = Box<dyn Fn(serde_json::Value) -> ToolFuture + Send + Sync>;
+
+/// Callback told about every executed call: tool name, arguments and
+/// result.
+pub type ToolObserverFn =
+    Box<dyn Fn(&str, &serde_json::Value, &Result<serde_json::Value, String>) + Send + Sync>;
+
 /// Executes tool calls by dispatching to registered handlers.
 pub struct ToolExecutor {
     handlers: HashMap<String, ToolHandlerFn>,
+    async_handlers: HashMap<String, AsyncToolHandlerFn>,
+    observer: Option<ToolObserverFn>,
 }
 
 impl ToolExecutor {
     pub fn new() -> Self {
         Self {
             handlers: HashMap::new(),
+            async_handlers: HashMap::new(),
+            observer: None,
         }
     }
 
+    /// Call `observer` after every execution, e.g. to record tool results
+    /// for replay.
+    pub fn set_observer<F>(&mut self, observer: F)
+    where
+        F: Fn(&str, &serde_json::Value, &Result<serde_json::Value, String>) + Send + Sync + 'static,
+    {
+        self.observer = Some(Box::new(observer));
+    }
+
     /// Register a tool handler.
     pub fn register<F>(&mut self, name: &str, handler: F)
     where
@@ -32,35 +59,93 @@ impl ToolExecutor {
         self.register(name, handler);
     }
 
-    /// Execute a tool by name with the given arguments.
+    /// Register an async tool handler. Replaces a sync handler of the
+    /// same name.
+    pub fn register_async<F, Fut>(&mut self, name: &str, handler: F)
+    where
+        F: Fn(serde_json::Value) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<serde_json::Value, String>> + Send + 'static,
+    {
+        self.handlers.remove(name);
+        self.async_handlers.insert(
+            name.to_string(),
+            Box::new(move |args| Box::pin(handler(args))),
+        );
+    }
+
+    /// Execute a tool by name with the given arguments. Async handlers are
+    /// not reachable from here; use [`execute_async`](Self::execute_async).
     pub fn execute(
         &self,
         name: &str,
         args: &serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        let result = self.execute_sync(name, args);
+        self.observe(name, args, &result);
+        result
+    }
+
+    /// Execute a tool by name, awaiting async handlers and calling sync
+    /// ones inline.
+    pub async fn execute_async(
+        &self,
+        name: &str,
+        args: &serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        let result = match self.async_handlers.get(name) {
+            Some(handler) => handler(args.clone()).await,
+            None => self.execute_sync(name, args),
+        };
+        self.observe(name, args, &result);
+        result
+    }
+
+    fn execute_sync(
+        &self,
+        name: &str,
+        args: &serde_json::Value,
     ) -> Result<serde_json::Value, String> {
         match self.handlers.get(name) {
             Some(handler) => handler(args),
+            None if self.async_handlers.contains_key(name) => {
+                Err(format!("Tool '{name}' is async-only"))
+            }
             None => Err(format!("Unknown tool: {name}")),
         }
     }
 
+    fn observe(
+        &self,
+        name: &str,
+        args: &serde_json::Value,
+        result: &Result<serde_json::Value, String>,
+    ) {
+        if let Some(observer) = &self.observer {
+            observer(name, args, result);
+        }
+    }
+
     /// Check if a tool handler is registered.
     pub fn has_handler(&self, name: &str) -> bool {
-        self.handlers.contains_key(name)
+        self.handlers.contains_key(name) || self.async_handlers.contains_key(name)
     }
 
     /// List all registered tool names.
     pub fn registered_tools(&self) -> Vec<String> {
-        self.handlers.keys().cloned().collect()
+        self.handlers
+            .keys()
+            .chain(self.async_handlers.keys())
+            .cloned()
+            .collect()
     }
 
     /// Number of registered tool handlers.
     pub fn len(&self) -> usize {
-        self.handlers.len()
+        self.handlers.len() + self.async_handlers.len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.handlers.is_empty()
+        self.handlers.is_empty() && self.async_handlers.is_empty()
     }
 }
 
@@ -124,6 +209,25 @@ mod tests {
         assert_eq!(tools.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_execute_async_dispatch() {
+        let mut executor = ToolExecutor::new();
+        executor.register("sync", |_| Ok(serde_json::json!("s")));
+        executor.register_async("slow", |args| async move {
+            tokio::task::yield_now().await;
+            Ok(args["n"].clone())
+        });
+        let args = serde_json::json!({"n": 3});
+        assert_eq!(executor.execute_async("slow", &args).await.unwrap(), 3);
+        assert_eq!(executor.execute_async("sync", &args).await.unwrap(), "s");
+        assert!(executor
+            .execute("slow", &args)
+            .unwrap_err()
+            .contains("async"));
+        assert_eq!(executor.len(), 2);
+        assert!(executor.has_handler("slow"));
+    }
+
     #[test]
     fn test_handler_error() {
         let mut executor = ToolExecutor::new();
@@ -0,0 +1,363 @@
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Result as SqlResult};
+use serde::{Deserialize, Serialize};
+
+use crate::memory::DomainKnowledge;
+
+/// A knowledge entry persisted across runs.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StoredKnowledge {
+    pub id: i64,
+    #[serde(flatten)]
+    pub knowledge: DomainKnowledge,
+    pub recorded_at: DateTime<Utc>,
+    /// When the entry goes stale; `None` keeps it until pruned by hand.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl StoredKnowledge {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|e| e <= now)
+    }
+}
+
+/// Persistent SQLite store for the agent's long-term knowledge, holding
+/// one entry per domain and kind.
+pub struct KnowledgeStore {
+    conn: Mutex<Connection>,
+}
+
+impl KnowledgeStore {
+    /// Open or create a knowledge database at the given path.
+    pub fn open(path: &str) -> SqlResult<Self> {
+        let conn = Connection::open(path)?;
+        let store = Self {
+            conn: Mutex::new(conn),
+        };
+        store.init_tables()?;
+        Ok(store)
+    }
+
+    /// Open an in-memory store (for tests).
+    pub fn open_in_memory() -> SqlResult<Self> {
+        let conn = Connection::open_in_memory()?;
+        let store = Self {
+            conn: Mutex::new(conn),
+        };
+        store.init_tables()?;
+        Ok(store)
+    }
+
+    fn init_tables(&self) -> SqlResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute_batch(
+            "
+            PRAGMA journal_mode = WAL;
+            CREATE TABLE IF NOT EXISTS knowledge (
+                id           INTEGER PRIMARY KEY AUTOINCREMENT,
+                domain       TEXT NOT NULL,
+                kind         TEXT NOT NULL,
+                summary      TEXT NOT NULL,
+                data_json    TEXT,
+                source       TEXT,
+                recorded_at  TEXT NOT NULL,
+                expires_at   TEXT,
+                UNIQUE(domain, kind)
+            );
+            CREATE INDEX IF NOT EXISTS idx_knowledge_domain ON knowledge(domain);
+            CREATE INDEX IF NOT EXISTS idx_knowledge_expires ON knowledge(expires_at);
+        ",
+        )?;
+        Ok(())
+    }
+
+    /// Store `knowledge`, replacing any entry with the same domain and kind.
+    /// The entry expires after `ttl`, if given. Returns the entry id.
+    pub fn remember(&self, knowledge: &DomainKnowledge, ttl: Option<Duration>) -> SqlResult<i64> {
+        let now = Utc::now();
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "INSERT INTO knowledge (domain, kind, summary, data_json, source, recorded_at, expires_at)
+             VALUES (?1,?2,?3,?4,?5,?6,?7)
+             ON CONFLICT(domain, kind) DO UPDATE SET
+                summary = excluded.summary,
+                data_json = excluded.data_json,
+                source = excluded.source,
+                recorded_at = excluded.recorded_at,
+                expires_at = excluded.expires_at
+             RETURNING id",
+            params![
+                knowledge.domain.to_lowercase(),
+                knowledge.kind,
+                knowledge.summary,
+                knowledge
+                    .data
+                    .as_ref()
+                    .map(|d| serde_json::to_string(d).unwrap_or_default()),
+                knowledge.source,
+                now.to_rfc3339(),
+                ttl.map(|t| (now + t).to_rfc3339()),
+            ],
+            |row| row.get(0),
+        )
+    }
+
+    /// Fetch an entry by id, expired or not.
+    pub fn get(&self, id: i64) -> SqlResult<Option<StoredKnowledge>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            &format!("{SELECT} WHERE id = ?1"),
+            params![id],
+            row_to_knowledge,
+        )
+        .optional()
+    }
+
+    /// Entries, newest first, optionally for one domain only. Expired
+    /// entries are skipped unless `include_expired` is set.
+    pub fn list(
+        &self,
+        domain: Option<&str>,
+        include_expired: bool,
+    ) -> SqlResult<Vec<StoredKnowledge>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!(
+            "{SELECT} WHERE (?1 IS NULL OR domain = ?1)
+               AND (?2 OR expires_at IS NULL OR expires_at > ?3)
+             ORDER BY recorded_at DESC"
+        ))?;
+        let rows = stmt.query_map(
+            params![
+                domain.map(str::to_lowercase),
+                include_expired,
+                Utc::now().to_rfc3339()
+            ],
+            row_to_knowledge,
+        )?;
+        rows.collect()
+    }
+
+    /// Live entries relevant to `query`, best first. Domains named in the
+    /// query weigh most, then kinds, then keywords in the domain or summary.
+    pub fn search(&self, query: &str, limit: usize) -> SqlResult<Vec<StoredKnowledge>> {
+        let mut terms: Vec<String> = query
+            .split(|c: char| !(c.is_alphanumeric() || c == '.' || c == '-'))
+            .map(|t| t.trim_matches('.').to_lowercase())
+            .collect();
+        // A domain's name also counts as a keyword ("example.com" → "example").
+        let labels: Vec<String> = terms
+            .iter()
+            .filter_map(|t| t.split_once('.').map(|(label, _)| label.to_string()))
+            .collect();
+        terms.extend(labels);
+        terms.retain(|t| t.len() >= 3);
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut scored: Vec<(usize, StoredKnowledge)> = self
+            .list(None, false)?
+            .into_iter()
+            .filter_map(|entry| {
+                let k = &entry.knowledge;
+                let summary = k.summary.to_lowercase();
+                let score: usize = terms
+                    .iter()
+                    .map(|t| {
+                        if *t == k.domain {
+                            4
+                        } else if *t == k.kind {
+                            2
+                        } else if k.domain.contains(t.as_str()) || summary.contains(t.as_str()) {
+                            1
+                        } else {
+                            0
+                        }
+                    })
+                    .sum();
+                (score > 0).then_some((score, entry))
+            })
+            .collect();
+        // `list` is newest first and the sort is stable, so ties keep it.
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        Ok(scored.into_iter().take(limit).map(|(_, e)| e).collect())
+    }
+
+    /// Delete one entry. Returns whether it existed.
+    pub fn forget(&self, id: i64) -> SqlResult<bool> {
+        let conn = self.conn.lock().unwrap();
+        Ok(conn.execute("DELETE FROM knowledge WHERE id = ?1", params![id])? > 0)
+    }
+
+    /// Delete every entry for a domain.
+    pub fn forget_domain(&self, domain: &str) -> SqlResult<usize> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM knowledge WHERE domain = ?1",
+            params![domain.to_lowercase()],
+        )
+    }
+
+    /// Delete entries that have expired.
+    pub fn prune_expired(&self) -> SqlResult<usize> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM knowledge WHERE expires_at IS NOT NULL AND expires_at <= ?1",
+            params![Utc::now().to_rfc3339()],
+        )
+    }
+
+    /// Delete entries recorded before `cutoff`.
+    pub fn prune_older_than(&self, cutoff: DateTime<Utc>) -> SqlResult<usize> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM knowledge WHERE recorded_at < ?1",
+            params![cutoff.to_rfc3339()],
+        )
+    }
+
+    /// Count all entries, expired included.
+    pub fn count(&self) -> SqlResult<i64> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row("SELECT COUNT(*) FROM knowledge", [], |r| r.get(0))
+    }
+}
+
+const SELECT: &str =
+    "SELECT id, domain, kind, summary, data_json, source, recorded_at, expires_at FROM knowledge";
+
+fn row_to_knowledge(row: &rusqlite::Row) -> SqlResult<StoredKnowledge> {
+    let data_json: Option<String> = row.get(4)?;
+    let recorded_at: String = row.get(6)?;
+    let expires_at: Option<String> = row.get(7)?;
+    Ok(StoredKnowledge {
+        id: row.get(0)?,
+        knowledge: DomainKnowledge {
+            domain: row.get(1)?,
+            kind: row.get(2)?,
+            summary: row.get(3)?,
+            data: data_json.and_then(|d| serde_json::from_str(&d).ok()),
+            source: row.get(5)?,
+        },
+        recorded_at: parse_ts(&recorded_at).unwrap_or_else(Utc::now),
+        expires_at: expires_at.as_deref().and_then(parse_ts),
+    })
+}
+
+fn parse_ts(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .ok()
+        .map(|d| d.with_timezone(&Utc))
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> KnowledgeStore {
+        KnowledgeStore::open_in_memory().unwrap()
+    }
+
+    #[test]
+    fn test_remember_replaces_same_kind() {
+        let s = store();
+        let first = s
+            .remember(
+                &DomainKnowledge::new("Example.com", "whois", "Registrar A"),
+                None,
+            )
+            .unwrap();
+        let second = s
+            .remember(
+                &DomainKnowledge::new("example.com", "whois", "Registrar B")
+                    .with_source("whois_lookup"),
+                None,
+            )
+            .unwrap();
+        s.remember(
+            &DomainKnowledge::new("example.com", "dns", "Delegated"),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(s.count().unwrap(), 2);
+        let entry = s.get(first).unwrap().unwrap();
+        assert_eq!(entry.knowledge.summary, "Registrar B");
+        assert_eq!(entry.knowledge.source.as_deref(), Some("whois_lookup"));
+    }
+
+    #[test]
+    fn test_expired_entries_are_hidden_and_pruned() {
+        let s = store();
+        s.remember(
+            &DomainKnowledge::new("old.com", "availability", "Taken"),
+            Some(Duration::seconds(-1)),
+        )
+        .unwrap();
+        s.remember(
+            &DomainKnowledge::new("new.com", "availability", "Available"),
+            Some(Duration::days(1)),
+        )
+        .unwrap();
+
+        assert_eq!(s.list(None, false).unwrap().len(), 1);
+        assert_eq!(s.list(None, true).unwrap().len(), 2);
+        assert!(s.search("old.com", 5).unwrap().is_empty());
+        assert_eq!(s.prune_expired().unwrap(), 1);
+        assert_eq!(s.count().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_search_ranks_domain_matches_first() {
+        let s = store();
+        s.remember(
+            &DomainKnowledge::new("other.com", "whois", "Mentions example in notes"),
+            None,
+        )
+        .unwrap();
+        s.remember(
+            &DomainKnowledge::new("example.com", "threat", "Low risk"),
+            None,
+        )
+        .unwrap();
+        s.remember(
+            &DomainKnowledge::new("unrelated.org", "dns", "Delegated"),
+            None,
+        )
+        .unwrap();
+
+        let hits = s.search("Is example.com a threat?", 10).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].knowledge.domain, "example.com");
+        assert!(s.search("a b", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_forget_and_prune_older_than() {
+        let s = store();
+        let id = s
+            .remember(&DomainKnowledge::new("a.com", "whois", "x"), None)
+            .unwrap();
+        s.remember(&DomainKnowledge::new("b.com", "whois", "y"), None)
+            .unwrap();
+        assert!(s.forget(id).unwrap());
+        assert!(!s.forget(id).unwrap());
+        assert_eq!(s.forget_domain("B.com").unwrap(), 1);
+
+        s.remember(&DomainKnowledge::new("c.com", "whois", "z"), None)
+            .unwrap();
+        assert_eq!(
+            s.prune_older_than(Utc::now() - Duration::days(1)).unwrap(),
+            0
+        );
+        assert_eq!(
+            s.prune_older_than(Utc::now() + Duration::days(1)).unwrap(),
+            1
+        );
+    }
+}
@@ -1,19 +1,31 @@
 pub mod agent;
+pub mod builtin;
+pub mod confirm;
 pub mod eval;
 pub mod event;
 pub mod executor;
+pub mod knowledge;
 pub mod memory;
 pub mod pipeline;
 pub mod planner;
+pub mod replay;
+pub mod runner;
 pub mod sandbox;
 pub mod toolbox;
 
-pub use agent::{Agent, AgentConfig, AgentResult, AgentStep};
-pub use eval::{EvalResult, ResponseEvaluator};
+pub use agent::{Agent, AgentConfig, AgentResult, AgentStep, EventSink};
+pub use builtin::{BuiltinTools, LookupBackend, NetworkLookup};
+pub use confirm::{ConfirmationChannel, ConfirmationDecision, ConfirmationRequest};
+pub use eval::{
+    AssertionRule, CaseReport, EvalCase, EvalResult, EvalSuite, ResponseEvaluator, SuiteReport,
+};
 pub use event::{AgentEvent, EventKind};
-pub use executor::{ToolExecutor, ToolHandler};
+pub use executor::{ToolExecutor, ToolFuture, ToolHandler, ToolObserverFn};
+pub use knowledge::{KnowledgeStore, StoredKnowledge};
 pub use memory::{DomainKnowledge, WorkingMemory};
 pub use pipeline::{Pipeline, PipelineKind, PipelineStep};
-pub use planner::{Plan, PlanStep, TaskPlanner};
-pub use sandbox::{Sandbox, SandboxConfig, SandboxViolation};
+pub use planner::{Plan, PlanStep, PlanStepStatus, TaskPlanner};
+pub use replay::{Exchange, Fixture, Recorder, ReplayMode, ReplayOutcome, Replayer, ToolRecord};
+pub use runner::{PipelineReport, PipelineRun, PipelineRunner, StepOutcome, StepReport};
+pub use sandbox::{Sandbox, SandboxConfig, SandboxViolation, CONFIRMATION_TOOLS};
 pub use toolbox::{ToolCategory, Toolbox};
@@ -1,8 +1,11 @@
 use std::collections::HashMap;
 
+use rusqlite::Result as SqlResult;
 use serde::{Deserialize, Serialize};
 use wd_llm::Message;
 
+use crate::knowledge::KnowledgeStore;
+
 /// Persistent working memory for the agent within a run.
 #[derive(Default)]
 pub struct WorkingMemory {
@@ -47,6 +50,30 @@ impl WorkingMemory {
             .collect()
     }
 
+    /// Load up to `limit` stored entries relevant to `query` from the
+    /// long-term store. Entries already present (same domain and kind) are
+    /// skipped. Returns how many were added.
+    pub fn recall(
+        &mut self,
+        store: &KnowledgeStore,
+        query: &str,
+        limit: usize,
+    ) -> SqlResult<usize> {
+        let mut added = 0;
+        for entry in store.search(query, limit)? {
+            let k = entry.knowledge;
+            let known = self
+                .domain_knowledge
+                .iter()
+                .any(|d| d.domain == k.domain && d.kind == k.kind);
+            if !known {
+                self.domain_knowledge.push(k);
+                added += 1;
+            }
+        }
+        Ok(added)
+    }
+
     /// Convert working memory into a context message for the LLM.
     /// Returns `None` if the memory is empty.
     pub fn to_context_message(&self) -> Option<Message> {
@@ -67,7 +94,11 @@ impl WorkingMemory {
         if !self.domain_knowledge.is_empty() {
             content.push_str("<domain_knowledge>\n");
             for dk in &self.domain_knowledge {
-                content.push_str(&format!("- {} ({}): {}\n", dk.domain, dk.kind, dk.summary));
+                content.push_str(&format!("- {} ({}): {}", dk.domain, dk.kind, dk.summary));
+                if let Some(source) = &dk.source {
+                    content.push_str(&format!(" [{source}]"));
+                }
+                content.push('\n');
             }
             content.push_str("</domain_knowledge>\n");
         }
@@ -109,6 +140,9 @@ pub struct DomainKnowledge {
     /// Structured data (if any).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub data: Option<serde_json::Value>,
+    /// Tool that produced it (if any).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
 }
 
 impl DomainKnowledge {
@@ -118,6 +152,7 @@ impl DomainKnowledge {
             kind: kind.to_string(),
             summary: summary.to_string(),
             data: None,
+            source: None,
         }
     }
 
@@ -125,6 +160,11 @@ impl DomainKnowledge {
         self.data = Some(data);
         self
     }
+
+    pub fn with_source(mut self, source: &str) -> Self {
+        self.source = Some(source.to_string());
+        self
+    }
 }
 
 // ─── Tests ───────────────────────────────────────────────────────────────────
@@ -198,4 +238,24 @@ mod tests {
             .with_data(serde_json::json!({"a": "1.2.3.4"}));
         assert!(dk.data.is_some());
     }
+
+    #[test]
+    fn test_recall_from_store() {
+        let store = KnowledgeStore::open_in_memory().unwrap();
+        store
+            .remember(
+                &DomainKnowledge::new("example.com", "whois", "Registrar X")
+                    .with_source("whois_lookup"),
+                None,
+            )
+            .unwrap();
+        let mut mem = WorkingMemory::new();
+        assert_eq!(
+            mem.recall(&store, "Tell me about example.com", 5).unwrap(),
+            1
+        );
+        assert_eq!(mem.recall(&store, "example.com again", 5).unwrap(), 0);
+        let content = mem.to_context_message().unwrap().content.unwrap();
+        assert!(content.contains("example.com (whois): Registrar X [whois_lookup]"));
+    }
 }
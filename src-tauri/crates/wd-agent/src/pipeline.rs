@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::planner::Plan;
+
 /// Pre-built pipeline kinds.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -235,6 +237,35 @@ impl Pipeline {
         }
     }
 
+    /// Build a plan with one step per pipeline step, each depending on the
+    /// one before it.
+    pub fn to_plan(&self) -> Plan {
+        let mut plan = Plan::new(&self.description);
+        for (i, step) in self.steps.iter().enumerate() {
+            plan.add_step(&step.description, step.required_tools.clone(), i > 0);
+        }
+        plan
+    }
+
+    /// The pre-built pipeline of `kind`. `target` is the domain or brand
+    /// for the kinds that work on one and is ignored by the others.
+    pub fn for_kind(kind: &PipelineKind, target: Option<&str>) -> Result<Self, String> {
+        let target = || {
+            target
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .ok_or_else(|| format!("{kind} needs a target"))
+        };
+        match kind {
+            PipelineKind::DomainAudit => Ok(Self::domain_audit(target()?)),
+            PipelineKind::SecurityScan => Ok(Self::security_scan(target()?)),
+            PipelineKind::BrandProtection => Ok(Self::brand_protection(target()?)),
+            PipelineKind::PortfolioAnalysis => Ok(Self::portfolio_analysis()),
+            PipelineKind::DropWatch => Ok(Self::drop_watch()),
+            PipelineKind::Custom => Err("Custom pipelines have no pre-built steps".into()),
+        }
+    }
+
     /// List all available pipeline kinds with descriptions.
     pub fn catalog() -> Vec<(PipelineKind, &'static str, &'static str)> {
         vec![
@@ -315,12 +346,31 @@ mod tests {
         assert_eq!(catalog.len(), 5);
     }
 
+    #[test]
+    fn test_for_kind() {
+        let p = Pipeline::for_kind(&PipelineKind::SecurityScan, Some("test.com")).unwrap();
+        assert_eq!(p.kind, PipelineKind::SecurityScan);
+        assert!(Pipeline::for_kind(&PipelineKind::DomainAudit, Some(" ")).is_err());
+        assert!(Pipeline::for_kind(&PipelineKind::DropWatch, None).is_ok());
+        assert!(Pipeline::for_kind(&PipelineKind::Custom, None).is_err());
+    }
+
     #[test]
     fn test_pipeline_kind_display() {
         assert_eq!(PipelineKind::DomainAudit.to_string(), "Domain Audit");
         assert_eq!(PipelineKind::DropWatch.to_string(), "Drop Watch");
     }
 
+    #[test]
+    fn test_to_plan() {
+        let p = Pipeline::security_scan("test.com");
+        let plan = p.to_plan();
+        assert_eq!(plan.steps.len(), p.steps.len());
+        assert!(!plan.steps[0].depends_on_previous);
+        assert!(plan.steps[1].depends_on_previous);
+        assert_eq!(plan.steps[1].tool_names, vec!["homoglyph_check"]);
+    }
+
     #[test]
     fn test_pipeline_steps_have_prompts() {
         let p = Pipeline::domain_audit("x.com");
@@ -0,0 +1,491 @@
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use wd_llm::{CompletionResponse, LlmClient, LlmError, Message, ToolDefinition};
+
+use crate::agent::{client_request, Agent, AgentResult};
+use crate::executor::ToolExecutor;
+
+/// One model call of a recorded run.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Exchange {
+    /// Conversation sent to the model.
+    pub messages: Vec<Message>,
+    /// Tools offered to the model.
+    pub tools: Vec<ToolDefinition>,
+    /// The model's response, or the error of a failed call.
+    pub response: Result<CompletionResponse, String>,
+}
+
+/// One executed tool call of a recorded run.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ToolRecord {
+    pub name: String,
+    pub arguments: Value,
+    pub result: Result<Value, String>,
+}
+
+/// A recorded agent run that can be replayed offline.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Fixture {
+    pub name: String,
+    pub system_prompt: String,
+    pub query: String,
+    pub tools: Vec<ToolDefinition>,
+    pub exchanges: Vec<Exchange>,
+    pub tool_calls: Vec<ToolRecord>,
+    /// Final answer of the recorded run.
+    pub answer: Option<String>,
+}
+
+impl Fixture {
+    /// Load a fixture from a JSON file.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let data = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        serde_json::from_str(&data).map_err(|e| format!("Invalid fixture {}: {e}", path.display()))
+    }
+
+    /// Write the fixture as pretty-printed JSON.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let data = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, data).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+    }
+}
+
+#[derive(Default)]
+struct Recording {
+    exchanges: Vec<Exchange>,
+    tool_calls: Vec<ToolRecord>,
+}
+
+/// Captures the model exchanges and tool results of an agent run into a
+/// [`Fixture`].
+#[derive(Clone, Default)]
+pub struct Recorder {
+    inner: Arc<Mutex<Recording>>,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record every call `executor` makes. Replaces any observer already
+    /// set on it.
+    pub fn attach(&self, executor: &mut ToolExecutor) {
+        let inner = Arc::clone(&self.inner);
+        executor.set_observer(move |name, args, result| {
+            inner.lock().unwrap().tool_calls.push(ToolRecord {
+                name: name.to_string(),
+                arguments: args.clone(),
+                result: result.clone(),
+            });
+        });
+    }
+
+    /// Run `agent` as [`Agent::run_async`] does and return its result
+    /// together with the recorded fixture.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn run<F, Fut>(
+        &self,
+        agent: &mut Agent,
+        name: &str,
+        system_prompt: &str,
+        user_query: &str,
+        tools: &[ToolDefinition],
+        executor: &mut ToolExecutor,
+        mut complete_fn: F,
+    ) -> (AgentResult, Fixture)
+    where
+        F: FnMut(Vec<Message>, Vec<ToolDefinition>) -> Fut,
+        Fut: Future<Output = Result<CompletionResponse, LlmError>>,
+    {
+        self.attach(executor);
+        let result = agent
+            .run_async(
+                system_prompt,
+                user_query,
+                tools,
+                executor,
+                |messages, tools| {
+                    let response = complete_fn(messages.clone(), tools.clone());
+                    let inner = Arc::clone(&self.inner);
+                    async move {
+                        let response = response.await;
+                        inner.lock().unwrap().exchanges.push(Exchange {
+                            messages,
+                            tools,
+                            response: response.as_ref().cloned().map_err(|e| e.to_string()),
+                        });
+                        response
+                    }
+                },
+            )
+            .await;
+
+        let recording = std::mem::take(&mut *self.inner.lock().unwrap());
+        let fixture = Fixture {
+            name: name.to_string(),
+            system_prompt: system_prompt.to_string(),
+            query: user_query.to_string(),
+            tools: tools.to_vec(),
+            exchanges: recording.exchanges,
+            tool_calls: recording.tool_calls,
+            answer: result.answer.clone(),
+        };
+        (result, fixture)
+    }
+
+    /// Record a live run through `client`, as [`Agent::run_with_client`].
+    #[allow(clippy::too_many_arguments)]
+    pub async fn run_with_client(
+        &self,
+        agent: &mut Agent,
+        name: &str,
+        system_prompt: &str,
+        user_query: &str,
+        tools: &[ToolDefinition],
+        executor: &mut ToolExecutor,
+        client: &LlmClient,
+    ) -> (AgentResult, Fixture) {
+        let (model, temperature) = agent.client_params(client);
+        self.run(
+            agent,
+            name,
+            system_prompt,
+            user_query,
+            tools,
+            executor,
+            |messages, tools| {
+                let req = client_request(&model, temperature, messages, tools);
+                async move { client.complete(&req).await }
+            },
+        )
+        .await
+    }
+}
+
+/// How closely a replay must follow its recording.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayMode {
+    /// Fail the model call at the first request that differs from the
+    /// recording.
+    #[default]
+    Strict,
+    /// Note differing requests but keep serving the recorded responses.
+    Lenient,
+}
+
+/// Result of replaying a [`Fixture`].
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReplayOutcome {
+    pub result: AgentResult,
+    /// Tool calls made during the replay.
+    pub tool_calls: Vec<ToolRecord>,
+    /// Where the replay departed from the recording, if anywhere.
+    pub divergences: Vec<String>,
+}
+
+/// Replays a [`Fixture`] offline: model responses and tool results come
+/// from the recording instead of the network.
+pub struct Replayer {
+    fixture: Fixture,
+    mode: ReplayMode,
+    system_prompt: Option<String>,
+}
+
+impl Replayer {
+    pub fn new(fixture: Fixture) -> Self {
+        Self {
+            fixture,
+            mode: ReplayMode::default(),
+            system_prompt: None,
+        }
+    }
+
+    pub fn with_mode(mut self, mode: ReplayMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Replay with a different system prompt than the recorded one. The
+    /// first request then differs from the recording, so this is only
+    /// useful in [`ReplayMode::Lenient`].
+    pub fn with_system_prompt(mut self, prompt: &str) -> Self {
+        self.system_prompt = Some(prompt.to_string());
+        self
+    }
+
+    pub fn fixture(&self) -> &Fixture {
+        &self.fixture
+    }
+
+    /// An executor answering each tool with its recorded results, in
+    /// recording order.
+    pub fn executor(&self) -> ToolExecutor {
+        let mut queues: HashMap<String, VecDeque<Result<Value, String>>> = HashMap::new();
+        for call in &self.fixture.tool_calls {
+            queues
+                .entry(call.name.clone())
+                .or_default()
+                .push_back(call.result.clone());
+        }
+        let names: Vec<String> = queues.keys().cloned().collect();
+        let queues = Arc::new(Mutex::new(queues));
+
+        let mut executor = ToolExecutor::new();
+        for name in names {
+            let queues = Arc::clone(&queues);
+            let tool = name.clone();
+            executor.register(&name, move |_| {
+                queues
+                    .lock()
+                    .unwrap()
+                    .get_mut(&tool)
+                    .and_then(VecDeque::pop_front)
+                    .unwrap_or_else(|| Err(format!("No recorded result left for tool '{tool}'")))
+            });
+        }
+        executor
+    }
+
+    /// Replay the fixture through `agent`.
+    pub async fn run(&self, agent: &mut Agent) -> ReplayOutcome {
+        let mut executor = self.executor();
+        let recorder = Recorder::new();
+        recorder.attach(&mut executor);
+
+        let exchanges = &self.fixture.exchanges;
+        let mut next = 0;
+        let mut divergences = Vec::new();
+        let prompt = self
+            .system_prompt
+            .as_deref()
+            .unwrap_or(&self.fixture.system_prompt);
+
+        let result = agent
+            .run_async(
+                prompt,
+                &self.fixture.query,
+                &self.fixture.tools,
+                &executor,
+                |messages, tools| {
+                    next += 1;
+                    let response = match exchanges.get(next - 1) {
+                        None => {
+                            divergences.push(format!("exchange {next}: not in the recording"));
+                            Err(LlmError::Other("replay exhausted".into()))
+                        }
+                        Some(exchange) => match diff_request(exchange, &messages, &tools) {
+                            Some(diff) => {
+                                divergences.push(format!("exchange {next}: {diff}"));
+                                if self.mode == ReplayMode::Strict {
+                                    Err(LlmError::Other(format!("replay diverged: {diff}")))
+                                } else {
+                                    recorded_response(exchange)
+                                }
+                            }
+                            None => recorded_response(exchange),
+                        },
+                    };
+                    async move { response }
+                },
+            )
+            .await;
+
+        if next < exchanges.len() {
+            divergences.push(format!(
+                "only {next} of {} recorded exchanges used",
+                exchanges.len()
+            ));
+        }
+        let tool_calls = std::mem::take(&mut recorder.inner.lock().unwrap().tool_calls);
+        if tool_calls != self.fixture.tool_calls {
+            divergences.push("tool calls differ from the recording".into());
+        }
+
+        ReplayOutcome {
+            result,
+            tool_calls,
+            divergences,
+        }
+    }
+}
+
+fn recorded_response(exchange: &Exchange) -> Result<CompletionResponse, LlmError> {
+    exchange.response.clone().map_err(LlmError::Other)
+}
+
+/// Describe the first difference between a recorded request and a new one.
+fn diff_request(
+    exchange: &Exchange,
+    messages: &[Message],
+    tools: &[ToolDefinition],
+) -> Option<String> {
+    let recorded: Vec<&str> = exchange.tools.iter().map(|t| t.name.as_str()).collect();
+    let offered: Vec<&str> = tools.iter().map(|t| t.name.as_str()).collect();
+    if recorded != offered {
+        return Some(format!("tools {offered:?} instead of {recorded:?}"));
+    }
+
+    if let Some(i) = exchange
+        .messages
+        .iter()
+        .zip(messages)
+        .position(|(a, b)| !same_message(a, b))
+    {
+        return Some(format!(
+            "message {} ({:?}) differs",
+            i + 1,
+            messages[i].role
+        ));
+    }
+    if exchange.messages.len() != messages.len() {
+        return Some(format!(
+            "{} messages instead of {}",
+            messages.len(),
+            exchange.messages.len()
+        ));
+    }
+    None
+}
+
+/// Compare messages ignoring their metadata (timestamps and such).
+fn same_message(a: &Message, b: &Message) -> bool {
+    let calls = |m: &Message| serde_json::to_value(&m.tool_calls).unwrap_or(Value::Null);
+    a.role == b.role
+        && a.content == b.content
+        && a.tool_call_id == b.tool_call_id
+        && a.name == b.name
+        && calls(a) == calls(b)
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::agent::AgentConfig;
+    use wd_llm::{FinishReason, FunctionCall, TokenUsage, ToolBuilder, ToolCall};
+
+    fn response(message: Message, finish_reason: FinishReason) -> CompletionResponse {
+        CompletionResponse {
+            id: "r".into(),
+            model: "test".into(),
+            message,
+            finish_reason,
+            usage: TokenUsage {
+                prompt_tokens: 10,
+                completion_tokens: 5,
+                total_tokens: 15,
+                estimated_cost_usd: Some(0.001),
+            },
+            latency_ms: 10,
+        }
+    }
+
+    /// Record a run that looks up `example.com` and answers from the
+    /// result.
+    pub(crate) async fn record_lookup() -> Fixture {
+        let mut agent = Agent::new(AgentConfig::default());
+        let mut executor = ToolExecutor::new();
+        executor.register("whois_lookup", |args| {
+            Ok(serde_json::json!({ "domain": args["domain"], "registrar": "Example Registrar" }))
+        });
+        let tools = vec![ToolBuilder::new("whois_lookup", "WHOIS lookup").build()];
+
+        let mut calls = 0;
+        let (result, fixture) = Recorder::new()
+            .run(
+                &mut agent,
+                "lookup",
+                "System",
+                "Who is the registrar of example.com?",
+                &tools,
+                &mut executor,
+                |_messages, _tools| {
+                    calls += 1;
+                    let r = if calls == 1 {
+                        response(
+                            Message::assistant_tool_calls(vec![ToolCall {
+                                id: "tc1".into(),
+                                function: FunctionCall {
+                                    name: "whois_lookup".into(),
+                                    arguments: serde_json::json!({"domain": "example.com"}),
+                                },
+                            }]),
+                            FinishReason::ToolUse,
+                        )
+                    } else {
+                        response(
+                            Message::assistant("example.com is registered with Example Registrar."),
+                            FinishReason::Stop,
+                        )
+                    };
+                    async move { Ok(r) }
+                },
+            )
+            .await;
+        assert!(result.completed);
+        fixture
+    }
+
+    #[tokio::test]
+    async fn test_record_captures_exchanges_and_tools() {
+        let fixture = record_lookup().await;
+        assert_eq!(fixture.exchanges.len(), 2);
+        assert_eq!(fixture.exchanges[0].messages.len(), 2);
+        assert_eq!(fixture.exchanges[1].tools[0].name, "whois_lookup");
+        assert_eq!(fixture.tool_calls.len(), 1);
+        assert_eq!(fixture.tool_calls[0].arguments["domain"], "example.com");
+        assert!(fixture.answer.unwrap().contains("Example Registrar"));
+    }
+
+    #[tokio::test]
+    async fn test_replay_is_deterministic() {
+        let fixture = record_lookup().await;
+        let dir = std::env::temp_dir().join(format!("wd-replay-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("lookup.json");
+        fixture.save(&path).unwrap();
+        let loaded = Fixture::load(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let replayer = Replayer::new(loaded);
+        for _ in 0..2 {
+            let mut agent = Agent::new(AgentConfig::default());
+            let outcome = replayer.run(&mut agent).await;
+            assert!(outcome.divergences.is_empty(), "{:?}", outcome.divergences);
+            assert_eq!(outcome.result.answer, fixture.answer);
+            assert_eq!(outcome.tool_calls, fixture.tool_calls);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_strict_replay_stops_on_changed_prompt() {
+        let fixture = record_lookup().await;
+
+        let mut agent = Agent::new(AgentConfig::default());
+        let outcome = Replayer::new(fixture.clone())
+            .with_system_prompt("New prompt")
+            .run(&mut agent)
+            .await;
+        assert!(!outcome.result.completed);
+        assert!(outcome.divergences[0].contains("message 1 (System) differs"));
+
+        let mut agent = Agent::new(AgentConfig::default());
+        let outcome = Replayer::new(fixture)
+            .with_mode(ReplayMode::Lenient)
+            .with_system_prompt("New prompt")
+            .run(&mut agent)
+            .await;
+        assert!(outcome.result.completed);
+        assert_eq!(outcome.divergences.len(), 2);
+    }
+}
@@ -0,0 +1,604 @@
+use std::future::Future;
+
+use serde::{Deserialize, Serialize};
+use wd_llm::{CompletionResponse, LlmClient, LlmError, Message, ToolDefinition};
+
+use crate::agent::{Agent, AgentResult, AgentStep};
+use crate::event::EventKind;
+use crate::executor::ToolExecutor;
+use crate::pipeline::{Pipeline, PipelineKind};
+use crate::planner::{Plan, PlanStepStatus};
+use crate::toolbox::Toolbox;
+
+const DEFAULT_SYSTEM_PROMPT: &str = "You are a domain intelligence analyst working through a \
+     multi-step pipeline. Complete only the current step, calling the available tools where \
+     needed. Findings from earlier steps are provided in working memory.";
+
+/// Outcome of the latest attempt at a pipeline step.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StepOutcome {
+    /// Agent run ID of the attempt.
+    pub run_id: String,
+    /// Final answer of the step (if any).
+    pub answer: Option<String>,
+    pub iterations: usize,
+    pub tool_calls: usize,
+    pub cost_usd: f64,
+    /// Why the step failed, if it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Number of attempts made, including this one.
+    pub attempts: u32,
+}
+
+/// Execution state of a pipeline. Serializable so a run that stopped on a
+/// failed step can be persisted and resumed later.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PipelineRun {
+    pub pipeline: Pipeline,
+    /// Step statuses, one plan step per pipeline step.
+    pub plan: Plan,
+    /// Free-form input appended to every step prompt (e.g. a domain list).
+    #[serde(default)]
+    pub input: Option<String>,
+    /// Latest outcome per step, indexed like `pipeline.steps`.
+    pub outcomes: Vec<Option<StepOutcome>>,
+    /// Cost of all attempts so far.
+    pub total_cost_usd: f64,
+}
+
+impl PipelineRun {
+    pub fn new(pipeline: Pipeline) -> Self {
+        let plan = pipeline.to_plan();
+        let outcomes = vec![None; pipeline.steps.len()];
+        Self {
+            pipeline,
+            plan,
+            input: None,
+            outcomes,
+            total_cost_usd: 0.0,
+        }
+    }
+
+    pub fn with_input(mut self, input: &str) -> Self {
+        self.input = Some(input.to_string());
+        self
+    }
+
+    /// Position of the next step to run: the first one that is neither
+    /// completed nor skipped. A failed step is therefore retried.
+    pub fn next_step(&self) -> Option<usize> {
+        self.plan.steps.iter().position(|s| {
+            s.status != PlanStepStatus::Completed && s.status != PlanStepStatus::Skipped
+        })
+    }
+
+    /// Position of the step the run stopped on, if it failed.
+    pub fn failed_step(&self) -> Option<usize> {
+        self.plan
+            .steps
+            .iter()
+            .position(|s| s.status == PlanStepStatus::Failed)
+    }
+
+    /// Whether every step is completed or skipped.
+    pub fn is_done(&self) -> bool {
+        self.plan.is_done()
+    }
+
+    /// Build the structured report for the run so far.
+    pub fn report(&self) -> PipelineReport {
+        let steps: Vec<StepReport> = self
+            .pipeline
+            .steps
+            .iter()
+            .zip(&self.plan.steps)
+            .zip(&self.outcomes)
+            .map(|((step, plan_step), outcome)| StepReport {
+                index: plan_step.index,
+                name: step.name.clone(),
+                status: plan_step.status.clone(),
+                outcome: outcome.clone(),
+            })
+            .collect();
+
+        let completed = self.is_done();
+        let summary = if completed {
+            steps
+                .iter()
+                .rev()
+                .find_map(|s| s.outcome.as_ref().and_then(|o| o.answer.clone()))
+        } else {
+            None
+        };
+
+        PipelineReport {
+            kind: self.pipeline.kind.clone(),
+            name: self.pipeline.name.clone(),
+            input: self.input.clone(),
+            steps,
+            progress: self.plan.progress(),
+            total_cost_usd: self.total_cost_usd,
+            completed,
+            summary,
+        }
+    }
+}
+
+/// Report for a single step.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StepReport {
+    /// Step number (1-based).
+    pub index: usize,
+    pub name: String,
+    pub status: PlanStepStatus,
+    pub outcome: Option<StepOutcome>,
+}
+
+/// Final structured report of a pipeline run.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PipelineReport {
+    pub kind: PipelineKind,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+    pub steps: Vec<StepReport>,
+    /// Fraction of steps completed (0.0 – 1.0).
+    pub progress: f64,
+    pub total_cost_usd: f64,
+    /// Whether every step completed.
+    pub completed: bool,
+    /// Answer of the last step, once the pipeline has completed.
+    pub summary: Option<String>,
+}
+
+/// Executes a [`Pipeline`] by running each step as an agent run.
+///
+/// Each step gets only the toolbox definitions named in its
+/// `required_tools`, and the answers of earlier steps are passed on as
+/// facts in the agent's [`WorkingMemory`](crate::memory::WorkingMemory).
+pub struct PipelineRunner {
+    agent: Agent,
+    toolbox: Toolbox,
+    system_prompt: String,
+}
+
+impl PipelineRunner {
+    pub fn new(agent: Agent) -> Self {
+        Self {
+            agent,
+            toolbox: Toolbox::full(),
+            system_prompt: DEFAULT_SYSTEM_PROMPT.to_string(),
+        }
+    }
+
+    pub fn with_toolbox(mut self, toolbox: Toolbox) -> Self {
+        self.toolbox = toolbox;
+        self
+    }
+
+    pub fn with_system_prompt(mut self, prompt: &str) -> Self {
+        self.system_prompt = prompt.to_string();
+        self
+    }
+
+    pub fn agent(&self) -> &Agent {
+        &self.agent
+    }
+
+    /// Run the remaining steps of `run` in order, stopping at the first one
+    /// that fails. Calling this again on the same run resumes from the
+    /// failed step.
+    ///
+    /// `complete_fn` is passed to [`Agent::run_async`] for every step.
+    pub async fn run<F, Fut>(
+        &mut self,
+        run: &mut PipelineRun,
+        executor: &ToolExecutor,
+        mut complete_fn: F,
+    ) -> PipelineReport
+    where
+        F: FnMut(Vec<Message>, Vec<ToolDefinition>) -> Fut,
+        Fut: Future<Output = Result<CompletionResponse, LlmError>>,
+    {
+        self.announce(run);
+        while let Some(pos) = run.next_step() {
+            let (query, tools) = match self.prepare(run, pos, executor) {
+                Ok(prepared) => prepared,
+                Err(e) => {
+                    self.fail_unprepared(run, pos, e);
+                    break;
+                }
+            };
+            let result = self
+                .agent
+                .run_async(
+                    &self.system_prompt,
+                    &query,
+                    &tools,
+                    executor,
+                    &mut complete_fn,
+                )
+                .await;
+            if !self.record(run, pos, result) {
+                break;
+            }
+        }
+        run.report()
+    }
+
+    /// Like [`run`](Self::run), against a live provider through `client`.
+    pub async fn run_with_client(
+        &mut self,
+        run: &mut PipelineRun,
+        executor: &ToolExecutor,
+        client: &LlmClient,
+    ) -> PipelineReport {
+        self.announce(run);
+        while let Some(pos) = run.next_step() {
+            let (query, tools) = match self.prepare(run, pos, executor) {
+                Ok(prepared) => prepared,
+                Err(e) => {
+                    self.fail_unprepared(run, pos, e);
+                    break;
+                }
+            };
+            let result = self
+                .agent
+                .run_with_client(&self.system_prompt, &query, &tools, executor, client)
+                .await;
+            if !self.record(run, pos, result) {
+                break;
+            }
+        }
+        run.report()
+    }
+
+    fn announce(&mut self, run: &PipelineRun) {
+        let fresh = run
+            .plan
+            .steps
+            .iter()
+            .all(|s| s.status == PlanStepStatus::Pending);
+        if fresh {
+            self.agent.emit(EventKind::PlanCreated {
+                goal: run.plan.goal.clone(),
+                steps: run.plan.steps.len(),
+            });
+        }
+    }
+
+    /// Mark step `pos` in progress, load earlier answers into working
+    /// memory and return the step's query and tools. Fails without
+    /// starting the step if a required tool has no definition in the
+    /// toolbox or no handler on `executor`.
+    fn prepare(
+        &mut self,
+        run: &mut PipelineRun,
+        pos: usize,
+        executor: &ToolExecutor,
+    ) -> Result<(String, Vec<ToolDefinition>), String> {
+        let step = &run.pipeline.steps[pos];
+        let index = run.plan.steps[pos].index;
+
+        let tools: Vec<ToolDefinition> = self
+            .toolbox
+            .all_definitions()
+            .into_iter()
+            .filter(|t| step.required_tools.contains(&t.name))
+            .collect();
+        let missing: Vec<&str> = step
+            .required_tools
+            .iter()
+            .filter(|name| !tools.iter().any(|t| &t.name == *name) || !executor.has_handler(name))
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            return Err(format!(
+                "step '{}' needs tools that are not available: {}",
+                step.name,
+                missing.join(", ")
+            ));
+        }
+
+        for (earlier, outcome) in run.pipeline.steps[..pos].iter().zip(&run.outcomes) {
+            if let Some(answer) = outcome.as_ref().and_then(|o| o.answer.as_deref()) {
+                self.agent.memory.add_fact(&earlier.name, answer);
+            }
+        }
+
+        let query = match &run.input {
+            Some(input) => format!("{}\n\nInput:\n{input}", step.agent_prompt),
+            None => step.agent_prompt.clone(),
+        };
+
+        run.plan.start_step(index);
+        self.agent.emit(EventKind::PlanStepStarted {
+            step_index: index,
+            description: step.description.clone(),
+        });
+        Ok((query, tools))
+    }
+
+    /// Fail step `pos` before any agent run, e.g. for a missing tool.
+    fn fail_unprepared(&mut self, run: &mut PipelineRun, pos: usize, error: String) {
+        let index = run.plan.steps[pos].index;
+        let attempts = run.outcomes[pos].as_ref().map_or(0, |o| o.attempts) + 1;
+        self.agent.emit(EventKind::Error {
+            message: error.clone(),
+        });
+        run.outcomes[pos] = Some(StepOutcome {
+            run_id: String::new(),
+            answer: None,
+            iterations: 0,
+            tool_calls: 0,
+            cost_usd: 0.0,
+            error: Some(error),
+            attempts,
+        });
+        run.plan.fail_step(index);
+    }
+
+    /// Store the outcome of step `pos`. Returns whether the step completed.
+    fn record(&mut self, run: &mut PipelineRun, pos: usize, result: AgentResult) -> bool {
+        let index = run.plan.steps[pos].index;
+        let attempts = run.outcomes[pos].as_ref().map_or(0, |o| o.attempts) + 1;
+        let tool_calls = result
+            .steps
+            .iter()
+            .map(|s| match s {
+                AgentStep::ToolCalls { calls } => calls.len(),
+                _ => 0,
+            })
+            .sum();
+        let error = (!result.completed).then(|| self.failure_reason(&result));
+
+        run.total_cost_usd += result.total_cost_usd;
+        run.outcomes[pos] = Some(StepOutcome {
+            run_id: result.run_id,
+            answer: result.answer,
+            iterations: result.iterations,
+            tool_calls,
+            cost_usd: result.total_cost_usd,
+            error,
+            attempts,
+        });
+
+        if result.completed {
+            run.plan.complete_step(index);
+            self.agent
+                .emit(EventKind::PlanStepCompleted { step_index: index });
+        } else {
+            run.plan.fail_step(index);
+        }
+        result.completed
+    }
+
+    fn failure_reason(&self, result: &AgentResult) -> String {
+        match result.steps.last() {
+            Some(AgentStep::IterationLimit { iterations, max }) => {
+                format!("iteration limit reached ({iterations}/{max})")
+            }
+            Some(AgentStep::BudgetExhausted {
+                spent_usd,
+                limit_usd,
+            }) => format!("budget exhausted (${spent_usd:.4} of ${limit_usd:.4})"),
+            _ => self
+                .agent
+                .events()
+                .iter()
+                .rev()
+                .take_while(|e| !matches!(e.kind, EventKind::RunStarted { .. }))
+                .find_map(|e| match &e.kind {
+                    EventKind::Error { message } => Some(message.clone()),
+                    _ => None,
+                })
+                .unwrap_or_else(|| "step produced no answer".to_string()),
+        }
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::AgentConfig;
+    use wd_llm::{FinishReason, Role, TokenUsage};
+
+    fn text_response(text: &str) -> CompletionResponse {
+        CompletionResponse {
+            id: "r".into(),
+            model: "test".into(),
+            message: Message::assistant(text),
+            finish_reason: FinishReason::Stop,
+            usage: TokenUsage {
+                prompt_tokens: 10,
+                completion_tokens: 5,
+                total_tokens: 15,
+                estimated_cost_usd: Some(0.001),
+            },
+            latency_ms: 10,
+        }
+    }
+
+    /// An executor with a no-op handler for every tool the pipelines use.
+    fn stub_executor() -> ToolExecutor {
+        let mut executor = ToolExecutor::new();
+        for name in crate::builtin::BuiltinTools::<crate::builtin::NetworkLookup>::tool_names() {
+            executor.register(name, |_args| Ok(serde_json::Value::Null));
+        }
+        executor
+    }
+
+    fn memory_of(messages: &[Message]) -> String {
+        messages
+            .iter()
+            .filter(|m| m.role == Role::System)
+            .filter_map(|m| m.content.clone())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_runs_all_steps_and_passes_outputs() {
+        let mut runner = PipelineRunner::new(Agent::new(AgentConfig::default()));
+        let mut run = PipelineRun::new(Pipeline::domain_audit("example.com"));
+        let executor = stub_executor();
+
+        let mut calls: Vec<(String, Vec<String>)> = Vec::new();
+        let report = runner
+            .run(&mut run, &executor, |messages, tools| {
+                calls.push((
+                    memory_of(&messages),
+                    tools.iter().map(|t| t.name.clone()).collect(),
+                ));
+                let answer = format!("answer {}", calls.len());
+                async move { Ok(text_response(&answer)) }
+            })
+            .await;
+
+        assert!(report.completed);
+        assert_eq!(report.steps.len(), 4);
+        assert!(report
+            .steps
+            .iter()
+            .all(|s| s.status == PlanStepStatus::Completed));
+        assert_eq!(report.summary.as_deref(), Some("answer 4"));
+        assert!((report.progress - 1.0).abs() < f64::EPSILON);
+        assert!((report.total_cost_usd - 0.004).abs() < 1e-9);
+
+        assert_eq!(calls[0].1, vec!["whois_lookup", "parse_whois"]);
+        assert!(calls[3].1.is_empty());
+        assert!(!calls[0].0.contains("answer 1"));
+        assert!(calls[1].0.contains("WHOIS Lookup: answer 1"));
+        assert!(calls[3].0.contains("Security Assessment: answer 3"));
+    }
+
+    #[tokio::test]
+    async fn test_resume_after_failed_step() {
+        let mut runner = PipelineRunner::new(Agent::new(AgentConfig::default()));
+        let mut run = PipelineRun::new(Pipeline::portfolio_analysis()).with_input("a.com\nb.com");
+        let executor = stub_executor();
+
+        let mut n = 0;
+        let report = runner
+            .run(&mut run, &executor, |messages, _tools| {
+                n += 1;
+                let result = if n == 2 {
+                    Err(LlmError::Other("provider down".into()))
+                } else {
+                    Ok(text_response("ok"))
+                };
+                assert!(messages
+                    .last()
+                    .and_then(|m| m.content.as_deref())
+                    .is_some_and(|c| c.ends_with("Input:\na.com\nb.com")));
+                async move { result }
+            })
+            .await;
+
+        assert!(!report.completed);
+        assert!(report.summary.is_none());
+        assert_eq!(report.steps[0].status, PlanStepStatus::Completed);
+        assert_eq!(report.steps[1].status, PlanStepStatus::Failed);
+        assert_eq!(report.steps[2].status, PlanStepStatus::Pending);
+        let failed = report.steps[1].outcome.as_ref().unwrap();
+        assert_eq!(failed.error.as_deref(), Some("provider down"));
+        assert_eq!(run.failed_step(), Some(1));
+
+        // The run survives a round trip through storage and picks up at the
+        // failed step.
+        let json = serde_json::to_string(&run).unwrap();
+        let mut run: PipelineRun = serde_json::from_str(&json).unwrap();
+        let mut n = 0;
+        let report = runner
+            .run(&mut run, &executor, |_messages, _tools| {
+                n += 1;
+                let answer = format!("retry {n}");
+                async move { Ok(text_response(&answer)) }
+            })
+            .await;
+
+        assert_eq!(n, 2);
+        assert!(report.completed);
+        assert_eq!(report.summary.as_deref(), Some("retry 2"));
+        let retried = report.steps[1].outcome.as_ref().unwrap();
+        assert_eq!(retried.attempts, 2);
+        assert!(retried.error.is_none());
+        assert_eq!(report.steps[0].outcome.as_ref().unwrap().attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_iteration_limit_fails_step() {
+        let config = AgentConfig {
+            max_iterations: 0,
+            ..Default::default()
+        };
+        let mut runner = PipelineRunner::new(Agent::new(config));
+        let mut run = PipelineRun::new(Pipeline::drop_watch());
+        let executor = stub_executor();
+
+        let report = runner
+            .run(&mut run, &executor, |_messages, _tools| async {
+                Ok(text_response("unreachable"))
+            })
+            .await;
+
+        assert_eq!(report.steps[0].status, PlanStepStatus::Failed);
+        let outcome = report.steps[0].outcome.as_ref().unwrap();
+        assert_eq!(
+            outcome.error.as_deref(),
+            Some("iteration limit reached (0/0)")
+        );
+        assert!(report.steps[1].outcome.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_missing_tool_fails_step_without_calling_model() {
+        let mut runner = PipelineRunner::new(Agent::new(AgentConfig::default()));
+        let mut run = PipelineRun::new(Pipeline::drop_watch());
+        let mut executor = ToolExecutor::new();
+        executor.register("bulk_whois", |_args| Ok(serde_json::Value::Null));
+
+        let mut called = false;
+        let report = runner
+            .run(&mut run, &executor, |_messages, _tools| {
+                called = true;
+                async { Ok(text_response("unreachable")) }
+            })
+            .await;
+
+        assert!(!called);
+        assert!(!report.completed);
+        assert_eq!(report.steps[0].status, PlanStepStatus::Failed);
+        let error = report.steps[0].outcome.as_ref().unwrap().error.clone();
+        assert!(error.unwrap().contains("check_expiry"));
+        assert!(runner.agent().events().iter().any(
+            |e| matches!(&e.kind, EventKind::Error { message } if message.contains("check_expiry"))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_emits_plan_events() {
+        let mut runner = PipelineRunner::new(Agent::new(AgentConfig::default()));
+        let mut run = PipelineRun::new(Pipeline::security_scan("test.com"));
+        let executor = stub_executor();
+
+        runner
+            .run(&mut run, &executor, |_messages, _tools| async {
+                Ok(text_response("done"))
+            })
+            .await;
+
+        let events = runner.agent().events();
+        assert!(matches!(
+            events[0].kind,
+            EventKind::PlanCreated { steps: 3, .. }
+        ));
+        let completed = events
+            .iter()
+            .filter(|e| matches!(e.kind, EventKind::PlanStepCompleted { .. }))
+            .count();
+        assert_eq!(completed, 3);
+    }
+}
@@ -22,6 +22,9 @@ pub struct SandboxConfig {
     pub require_confirmation: bool,
 }
 
+/// Built-in tools that change state or write files.
+pub const CONFIRMATION_TOOLS: &[&str] = &["clear_cache", "export_csv", "export_json"];
+
 fn default_max_cost() -> f64 {
     5.0
 }
@@ -106,6 +109,13 @@ impl Sandbox {
         self.confirmation_required.insert(name.to_string());
     }
 
+    /// Require confirmation for every tool in [`CONFIRMATION_TOOLS`].
+    pub fn require_confirmation_for_defaults(&mut self) {
+        for name in CONFIRMATION_TOOLS {
+            self.require_confirmation_for(name);
+        }
+    }
+
     /// Check whether a tool call is permitted.
     pub fn check_tool(&self, name: &str) -> Result<(), SandboxViolation> {
         // Check block list
@@ -267,4 +277,16 @@ mod tests {
         // Non-confirmation tools pass
         assert!(sb.check_tool("whois_lookup").is_ok());
     }
+
+    #[test]
+    fn test_default_confirmation_tools() {
+        let config = SandboxConfig {
+            require_confirmation: true,
+            ..Default::default()
+        };
+        let mut sb = Sandbox::new(config);
+        sb.require_confirmation_for_defaults();
+        assert!(sb.check_tool("export_csv").is_err());
+        assert!(sb.check_tool("dns_lookup").is_ok());
+    }
 }
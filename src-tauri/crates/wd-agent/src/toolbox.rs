@@ -283,6 +283,22 @@ impl Toolbox {
                 .param("record_b", ParamType::String, "Second WHOIS record", true)
                 .build(),
         );
+        self.add(
+            ToolCategory::History,
+            ToolBuilder::new(
+                "search_records",
+                "Search stored WHOIS snapshots and past chat sessions",
+            )
+            .param(
+                "query",
+                ParamType::String,
+                "Keywords plus optional filters: registrar:, ns:, domain:, in:chat|whois, \
+                 since:YYYY-MM-DD, until:YYYY-MM-DD, within:30d, or 'last quarter'",
+                true,
+            )
+            .param("limit", ParamType::Integer, "Max results to return", false)
+            .build(),
+        );
     }
 
     fn register_utility_tools(&mut self) {
@@ -0,0 +1,193 @@
+use std::path::Path;
+
+use axum::extract::FromRequestParts;
+use axum::http::header::AUTHORIZATION;
+use axum::http::request::Parts;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use wd_audit::{Action, AuditEvent, Source};
+use wd_proxy::{Permission, Rbac};
+
+use crate::error::{permission_name, ApiError};
+use crate::state::ApiState;
+
+/// Prefix of generated keys, so they are easy to spot in configs and logs.
+const KEY_PREFIX: &str = "wdk_";
+
+/// An API key. Only a hash of the key is kept.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ApiKey {
+    pub name: String,
+    /// SHA-256 of the key, hex encoded.
+    pub key_hash: String,
+    /// RBAC roles the key is bound to.
+    pub roles: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ApiKey {
+    /// RBAC subject the key's roles are bound to.
+    pub fn subject(&self) -> String {
+        format!("key:{}", self.name)
+    }
+}
+
+/// The set of API keys, stored as JSON.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ApiKeys {
+    pub keys: Vec<ApiKey>,
+}
+
+impl ApiKeys {
+    /// Read keys from `path`; a missing file means no keys.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).map_err(|e| e.to_string()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, json).map_err(|e| e.to_string())
+    }
+
+    /// Create a key bound to `roles` and return it. The key itself is not
+    /// stored and cannot be shown again.
+    pub fn create(&mut self, name: &str, roles: Vec<String>) -> Result<String, String> {
+        if name.is_empty() {
+            return Err("key name cannot be empty".into());
+        }
+        if self.get(name).is_some() {
+            return Err(format!("a key named '{}' already exists", name));
+        }
+        let secret: [u8; 24] = rand::random();
+        let key = format!("{}{}", KEY_PREFIX, hex(&secret));
+        self.keys.push(ApiKey {
+            name: name.to_string(),
+            key_hash: hash_key(&key),
+            roles,
+            created_at: Utc::now(),
+        });
+        Ok(key)
+    }
+
+    pub fn revoke(&mut self, name: &str) -> bool {
+        let before = self.keys.len();
+        self.keys.retain(|k| k.name != name);
+        self.keys.len() < before
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ApiKey> {
+        self.keys.iter().find(|k| k.name == name)
+    }
+
+    /// The key matching `key`, if any.
+    pub fn authenticate(&self, key: &str) -> Option<&ApiKey> {
+        let hash = hash_key(key);
+        self.keys
+            .iter()
+            .find(|k| constant_time_eq(k.key_hash.as_bytes(), hash.as_bytes()))
+    }
+
+    /// Bind every key's subject to its roles.
+    pub fn bind(&self, rbac: &Rbac) {
+        for key in &self.keys {
+            rbac.unbind(&key.subject());
+            rbac.bind(key.subject(), key.roles.clone());
+        }
+    }
+}
+
+fn hash_key(key: &str) -> String {
+    hex(&Sha256::digest(key.as_bytes()))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// The authenticated client of a request, from an `Authorization: Bearer`
+/// or `X-Api-Key` header.
+#[derive(Clone, Debug)]
+pub struct Caller {
+    pub key_name: String,
+    pub subject: String,
+}
+
+impl Caller {
+    /// Fail with 403 unless the caller's roles grant `permission`.
+    pub fn require(&self, state: &ApiState, permission: Permission) -> Result<(), ApiError> {
+        if state.rbac.check(&self.subject, &permission) {
+            Ok(())
+        } else {
+            state.record(
+                self.event(Action::PermissionDenied)
+                    .with_target(permission_name(&permission)),
+            );
+            Err(ApiError::Forbidden(permission))
+        }
+    }
+
+    /// An audit event for an action taken by this caller.
+    pub fn event(&self, action: Action) -> AuditEvent {
+        AuditEvent::new(self.subject.clone(), Source::Api, action)
+    }
+}
+
+impl FromRequestParts<ApiState> for Caller {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &ApiState) -> Result<Self, ApiError> {
+        let header = |name| parts.headers.get(name).and_then(|v| v.to_str().ok());
+        let key = header(AUTHORIZATION.as_str())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .or_else(|| header("x-api-key"))
+            .ok_or(ApiError::Unauthorized)?;
+        let keys = state.keys.read().map_err(|e| e.to_string())?;
+        let key = keys
+            .authenticate(key.trim())
+            .ok_or(ApiError::Unauthorized)?;
+        Ok(Self {
+            key_name: key.name.clone(),
+            subject: key.subject(),
+        })
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_authenticate_and_revoke() {
+        let mut keys = ApiKeys::default();
+        let key = keys.create("ci", vec!["viewer".into()]).unwrap();
+        assert!(key.starts_with("wdk_"));
+        assert!(keys.create("ci", vec![]).is_err());
+        assert_eq!(keys.authenticate(&key).unwrap().name, "ci");
+        assert!(keys.authenticate("wdk_nope").is_none());
+        assert_ne!(keys.keys[0].key_hash, key);
+
+        let rbac = Rbac::new();
+        keys.bind(&rbac);
+        assert!(rbac.check("key:ci", &Permission::HistoryRead));
+        assert!(!rbac.check("key:ci", &Permission::Lookup));
+
+        let path = std::env::temp_dir().join(format!("wd-api-keys-{}.json", std::process::id()));
+        keys.save(&path).unwrap();
+        let mut loaded = ApiKeys::load(&path).unwrap();
+        assert!(loaded.authenticate(&key).is_some());
+        assert!(loaded.revoke("ci"));
+        assert!(loaded.authenticate(&key).is_none());
+        std::fs::remove_file(&path).ok();
+    }
+}
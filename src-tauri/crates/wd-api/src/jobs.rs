@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures::stream::{self, Stream};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{watch, Semaphore};
+use wd_bulk::{resolve, LookupFn};
+use wd_export::BulkResult;
+
+/// Finished jobs kept for their results; older ones are dropped first.
+const MAX_FINISHED_JOBS: usize = 50;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkJobState {
+    Running,
+    Completed,
+    Cancelled,
+}
+
+/// Progress of a bulk job.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BulkJobSummary {
+    pub id: String,
+    /// Name of the API key that submitted the job.
+    pub submitted_by: String,
+    pub state: BulkJobState,
+    pub total: usize,
+    pub completed: usize,
+    pub errors: usize,
+    pub created_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+struct Progress {
+    state: BulkJobState,
+    results: Vec<BulkResult>,
+    finished_at: Option<DateTime<Utc>>,
+}
+
+/// A bulk lookup running in the background.
+pub struct BulkJob {
+    pub id: String,
+    pub submitted_by: String,
+    pub created_at: DateTime<Utc>,
+    pub total: usize,
+    progress: Mutex<Progress>,
+    cancelled: AtomicBool,
+    /// Bumped whenever a result arrives or the job finishes.
+    changed: watch::Sender<usize>,
+}
+
+impl BulkJob {
+    pub fn summary(&self) -> BulkJobSummary {
+        let p = self.progress.lock().unwrap();
+        BulkJobSummary {
+            id: self.id.clone(),
+            submitted_by: self.submitted_by.clone(),
+            state: p.state,
+            total: self.total,
+            completed: p.results.len(),
+            errors: p.results.iter().filter(|r| r.error.is_some()).count(),
+            created_at: self.created_at,
+            finished_at: p.finished_at,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.progress.lock().unwrap().state != BulkJobState::Running
+    }
+
+    /// Results so far, in completion order.
+    pub fn results(&self) -> Vec<BulkResult> {
+        self.progress.lock().unwrap().results.clone()
+    }
+
+    /// Stop starting new lookups. Lookups already running still report.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Results as they arrive, starting with those already in, ending when
+    /// the job finishes.
+    pub fn stream(self: Arc<Self>) -> impl Stream<Item = BulkResult> + Send {
+        let rx = self.changed.subscribe();
+        stream::unfold((self, rx, 0usize), |(job, mut rx, next)| async move {
+            loop {
+                rx.borrow_and_update();
+                let (result, finished) = {
+                    let p = job.progress.lock().unwrap();
+                    (
+                        p.results.get(next).cloned(),
+                        p.state != BulkJobState::Running,
+                    )
+                };
+                if let Some(result) = result {
+                    return Some((result, (job, rx, next + 1)));
+                }
+                if finished || rx.changed().await.is_err() {
+                    return None;
+                }
+            }
+        })
+    }
+
+    fn push(&self, result: BulkResult) {
+        let count = {
+            let mut p = self.progress.lock().unwrap();
+            p.results.push(result);
+            p.results.len()
+        };
+        self.changed.send_replace(count);
+    }
+
+    fn finish(&self) {
+        let count = {
+            let mut p = self.progress.lock().unwrap();
+            p.state = if self.cancelled.load(Ordering::SeqCst) {
+                BulkJobState::Cancelled
+            } else {
+                BulkJobState::Completed
+            };
+            p.finished_at = Some(Utc::now());
+            p.results.len()
+        };
+        self.changed.send_replace(count);
+    }
+}
+
+/// Bulk jobs submitted through the API.
+#[derive(Clone, Default)]
+pub struct BulkJobs {
+    jobs: Arc<Mutex<HashMap<String, Arc<BulkJob>>>>,
+}
+
+impl BulkJobs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start looking up `domains` with at most `concurrency` lookups at a
+    /// time.
+    pub fn submit(
+        &self,
+        domains: Vec<String>,
+        submitted_by: &str,
+        lookup: LookupFn,
+        concurrency: usize,
+        timeout: Option<Duration>,
+    ) -> Arc<BulkJob> {
+        let id = format!("bulk-{:016x}", rand::random::<u64>());
+        let (changed, _) = watch::channel(0);
+        let job = Arc::new(BulkJob {
+            id: id.clone(),
+            submitted_by: submitted_by.to_string(),
+            created_at: Utc::now(),
+            total: domains.len(),
+            progress: Mutex::new(Progress {
+                state: BulkJobState::Running,
+                results: Vec::with_capacity(domains.len()),
+                finished_at: None,
+            }),
+            cancelled: AtomicBool::new(false),
+            changed,
+        });
+        {
+            let mut jobs = self.jobs.lock().unwrap();
+            prune(&mut jobs);
+            jobs.insert(id, Arc::clone(&job));
+        }
+
+        let runner = Arc::clone(&job);
+        tokio::spawn(async move {
+            let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+            let tasks: Vec<_> = domains
+                .into_iter()
+                .map(|domain| {
+                    let job = Arc::clone(&runner);
+                    let lookup = Arc::clone(&lookup);
+                    let semaphore = Arc::clone(&semaphore);
+                    tokio::spawn(async move {
+                        let Ok(_permit) = semaphore.acquire().await else {
+                            return;
+                        };
+                        if job.cancelled.load(Ordering::SeqCst) {
+                            return;
+                        }
+                        job.push(resolve(&lookup, domain, timeout).await);
+                    })
+                })
+                .collect();
+            futures::future::join_all(tasks).await;
+            runner.finish();
+        });
+        job
+    }
+
+    pub fn get(&self, id: &str) -> Option<Arc<BulkJob>> {
+        self.jobs.lock().unwrap().get(id).cloned()
+    }
+
+    /// Every job, newest first.
+    pub fn list(&self) -> Vec<BulkJobSummary> {
+        let mut list: Vec<_> = self
+            .jobs
+            .lock()
+            .unwrap()
+            .values()
+            .map(|j| j.summary())
+            .collect();
+        list.sort_by_key(|s| std::cmp::Reverse(s.created_at));
+        list
+    }
+}
+
+fn prune(jobs: &mut HashMap<String, Arc<BulkJob>>) {
+    let mut finished: Vec<_> = jobs
+        .values()
+        .filter(|j| j.is_finished())
+        .map(|j| (j.created_at, j.id.clone()))
+        .collect();
+    if finished.len() < MAX_FINISHED_JOBS {
+        return;
+    }
+    finished.sort();
+    for (_, id) in finished.iter().take(finished.len() + 1 - MAX_FINISHED_JOBS) {
+        jobs.remove(id);
+    }
+}
@@ -0,0 +1,55 @@
+//! # wd-api
+//!
+//! Headless REST/JSON API for `whoisdigger-cli serve`.
+//!
+//! Every endpoint except `/api/v1/health` needs an API key, sent as
+//! `Authorization: Bearer <key>` or `X-Api-Key: <key>`. Each key is bound
+//! to `wd_proxy::Rbac` roles under the subject `key:<name>`, and each
+//! endpoint checks one permission:
+//!
+//! - **lookup** – single lookups
+//! - **bulk_run** – bulk job submit, cancel, status and results
+//! - **history_read** – history
+//! - **watchlist_read** / **watchlist_manage** – watchlist reads and changes
+//! - **scheduler_read** / **scheduler_manage** – scheduled job reads and
+//!   changes
+//! - **export** – exports
+//!
+//! Failed checks go to the RBAC's denial audit and, with
+//! [`ApiState::with_audit`], to the audit log along with every change made
+//! through the API.
+//!
+//! ## Modules
+//!
+//! - **auth** – API keys and the authenticated caller
+//! - **error** – error responses
+//! - **jobs** – in-memory bulk lookup jobs with streamed results, looked up
+//!   and classified by the `wd-bulk` engine the GUI uses
+//! - **routes** – the HTTP routes
+//! - **state** – shared server state
+
+pub mod auth;
+pub mod error;
+pub mod jobs;
+pub mod routes;
+pub mod state;
+
+use std::net::SocketAddr;
+
+pub use auth::{ApiKey, ApiKeys, Caller};
+pub use error::ApiError;
+pub use jobs::{BulkJob, BulkJobState, BulkJobSummary, BulkJobs};
+pub use routes::router;
+pub use state::ApiState;
+pub use wd_bulk::{LookupFn, LookupFuture};
+
+/// Serve the API on `addr` until Ctrl-C.
+pub async fn serve(addr: SocketAddr, state: ApiState) -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    log::info!("API listening on http://{}", listener.local_addr()?);
+    axum::serve(listener, router(state))
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await
+}
@@ -0,0 +1,842 @@
+use std::convert::Infallible;
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::extract::{Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post, put};
+use axum::{Json, Router};
+use chrono::Utc;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use wd_audit::Action;
+use wd_bulk::{expand_domains, resolve};
+use wd_db::{db_history_add, db_history_get_filtered, HistoryEntry};
+use wd_expiry::{ExpiryStore, WatchEntry, WatchPriority};
+use wd_export::ics::IcsCalendar;
+use wd_export::{build_csv, BulkResult};
+use wd_portfolio::PortfolioStore;
+use wd_proxy::Permission;
+use wd_scheduler::{Job, Schedule, SchedulerStore};
+
+use crate::auth::Caller;
+use crate::error::ApiError;
+use crate::jobs::BulkJobSummary;
+use crate::state::ApiState;
+
+/// Lookup timeout when the request does not set one.
+const DEFAULT_TIMEOUT_MS: u64 = 10_000;
+/// Bulk concurrency when the request does not set one.
+const DEFAULT_CONCURRENCY: usize = 5;
+
+/// The API's routes, all under `/api/v1`.
+pub fn router(state: ApiState) -> Router {
+    Router::new()
+        .route("/api/v1/health", get(health))
+        .route("/api/v1/me", get(me))
+        .route("/api/v1/lookup/{domain}", get(lookup))
+        .route("/api/v1/bulk", get(bulk_list).post(bulk_submit))
+        .route("/api/v1/bulk/{id}", get(bulk_status).delete(bulk_cancel))
+        .route("/api/v1/bulk/{id}/results", get(bulk_results))
+        .route("/api/v1/history", get(history))
+        .route("/api/v1/watchlist", get(watchlist))
+        .route(
+            "/api/v1/watchlist/{domain}",
+            put(watchlist_put).delete(watchlist_remove),
+        )
+        .route("/api/v1/jobs", get(jobs_list).post(jobs_create))
+        .route("/api/v1/jobs/{id}", get(jobs_get).delete(jobs_delete))
+        .route("/api/v1/jobs/{id}/pause", post(jobs_pause))
+        .route("/api/v1/jobs/{id}/resume", post(jobs_resume))
+        .route("/api/v1/export/calendar.ics", get(export_calendar))
+        .fallback(|| async { ApiError::NotFound("no such endpoint".into()) })
+        .with_state(state)
+}
+
+/// Run blocking database work off the async runtime.
+async fn blocking<T, F>(f: F) -> Result<T, ApiError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, ApiError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| ApiError::Internal(e.to_string()))?
+}
+
+fn path_str(path: std::path::PathBuf) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+// ─── Service ─────────────────────────────────────────────────────────────────
+
+async fn health() -> Json<Value> {
+    Json(json!({ "status": "ok", "version": env!("CARGO_PKG_VERSION") }))
+}
+
+/// The calling key and what it may do.
+async fn me(State(state): State<ApiState>, caller: Caller) -> Json<Value> {
+    let bindings = state.rbac.get_bindings(&caller.subject);
+    Json(json!({
+        "key": caller.key_name,
+        "roles": bindings.map(|b| b.roles).unwrap_or_default(),
+        "permissions": state.rbac.effective_permissions(&caller.subject),
+    }))
+}
+
+// ─── Lookups ─────────────────────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+struct LookupQuery {
+    timeout_ms: Option<u64>,
+}
+
+/// Look one domain up and record it in the profile's history.
+async fn lookup(
+    State(state): State<ApiState>,
+    caller: Caller,
+    Path(domain): Path<String>,
+    Query(query): Query<LookupQuery>,
+) -> Result<Json<BulkResult>, ApiError> {
+    caller.require(&state, Permission::Lookup)?;
+    let domain = domain.trim().to_lowercase();
+    if domain.is_empty() || !domain.contains('.') {
+        return Err(ApiError::BadRequest(format!("invalid domain '{}'", domain)));
+    }
+    let timeout = Duration::from_millis(query.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS));
+    let result = resolve(&state.lookup, domain, Some(timeout)).await;
+    if result.error.is_none() {
+        let path = path_str(state.profile.history());
+        let (domain, status) = (result.domain.clone(), result.status.clone());
+        if let Err(e) = blocking(move || Ok(db_history_add(&path, &domain, &status)?)).await {
+            log::warn!("could not record lookup history: {e}");
+        }
+    }
+    Ok(Json(result))
+}
+
+#[derive(Deserialize)]
+struct BulkRequest {
+    domains: Vec<String>,
+    /// When set, each domain's first label is looked up under every TLD.
+    #[serde(default)]
+    tlds: Vec<String>,
+    concurrency: Option<usize>,
+    /// Per-domain timeout; 0 for none.
+    timeout_ms: Option<u64>,
+}
+
+async fn bulk_submit(
+    State(state): State<ApiState>,
+    caller: Caller,
+    Json(req): Json<BulkRequest>,
+) -> Result<(StatusCode, Json<BulkJobSummary>), ApiError> {
+    caller.require(&state, Permission::BulkRun)?;
+    let domains = expand_domains(&req.domains, &req.tlds);
+    if domains.is_empty() {
+        return Err(ApiError::BadRequest("no domains given".into()));
+    }
+    if domains.len() > state.max_bulk_domains {
+        return Err(ApiError::BadRequest(format!(
+            "{} domains requested, at most {} allowed",
+            domains.len(),
+            state.max_bulk_domains
+        )));
+    }
+    let concurrency = req
+        .concurrency
+        .unwrap_or(DEFAULT_CONCURRENCY)
+        .clamp(1, state.max_concurrency);
+    let timeout = match req.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS) {
+        0 => None,
+        ms => Some(Duration::from_millis(ms)),
+    };
+    let job = state.jobs.submit(
+        domains,
+        &caller.key_name,
+        state.lookup.clone(),
+        concurrency,
+        timeout,
+    );
+    let summary = job.summary();
+    state.record(
+        caller
+            .event(Action::BulkStarted)
+            .with_target(summary.id.clone())
+            .with_detail("domains", summary.total)
+            .with_detail("concurrency", concurrency),
+    );
+    Ok((StatusCode::ACCEPTED, Json(summary)))
+}
+
+async fn bulk_list(
+    State(state): State<ApiState>,
+    caller: Caller,
+) -> Result<Json<Vec<BulkJobSummary>>, ApiError> {
+    caller.require(&state, Permission::BulkRun)?;
+    Ok(Json(state.jobs.list()))
+}
+
+async fn bulk_status(
+    State(state): State<ApiState>,
+    caller: Caller,
+    Path(id): Path<String>,
+) -> Result<Json<BulkJobSummary>, ApiError> {
+    caller.require(&state, Permission::BulkRun)?;
+    let job = state.jobs.get(&id).ok_or(ApiError::NotFound(id))?;
+    Ok(Json(job.summary()))
+}
+
+/// Stop a bulk job from starting further lookups.
+async fn bulk_cancel(
+    State(state): State<ApiState>,
+    caller: Caller,
+    Path(id): Path<String>,
+) -> Result<Json<BulkJobSummary>, ApiError> {
+    caller.require(&state, Permission::BulkRun)?;
+    let job = state.jobs.get(&id).ok_or(ApiError::NotFound(id))?;
+    job.cancel();
+    state.record(
+        caller
+            .event(Action::BulkStopped)
+            .with_target(job.id.clone()),
+    );
+    Ok(Json(job.summary()))
+}
+
+#[derive(Deserialize)]
+struct ResultsQuery {
+    /// `jsonl` (default), `json` or `csv`.
+    format: Option<String>,
+}
+
+/// A bulk job's results. `jsonl` streams one result per line until the
+/// job finishes; `json` and `csv` return the results so far.
+async fn bulk_results(
+    State(state): State<ApiState>,
+    caller: Caller,
+    Path(id): Path<String>,
+    Query(query): Query<ResultsQuery>,
+) -> Result<Response, ApiError> {
+    caller.require(&state, Permission::BulkRun)?;
+    let job = state.jobs.get(&id).ok_or(ApiError::NotFound(id))?;
+    match query.format.as_deref().unwrap_or("jsonl") {
+        "jsonl" => {
+            let lines = job.stream().map(|r| {
+                let mut line = serde_json::to_string(&r).unwrap_or_default();
+                line.push('\n');
+                Ok::<_, Infallible>(line)
+            });
+            Ok((
+                [(header::CONTENT_TYPE, "application/x-ndjson")],
+                Body::from_stream(lines),
+            )
+                .into_response())
+        }
+        "json" => Ok(Json(job.results()).into_response()),
+        "csv" => Ok((
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}.csv\"", job.id),
+                ),
+            ],
+            build_csv(&job.results()),
+        )
+            .into_response()),
+        other => Err(ApiError::BadRequest(format!("unknown format '{}'", other))),
+    }
+}
+
+// ─── History ─────────────────────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+struct HistoryQuery {
+    /// Substring of the domain.
+    q: Option<String>,
+    status: Option<String>,
+    since_ms: Option<i64>,
+    page: Option<u32>,
+    page_size: Option<u32>,
+}
+
+async fn history(
+    State(state): State<ApiState>,
+    caller: Caller,
+    Query(query): Query<HistoryQuery>,
+) -> Result<Json<Value>, ApiError> {
+    caller.require(&state, Permission::HistoryRead)?;
+    let path = path_str(state.profile.history());
+    let page = query.page.unwrap_or(0);
+    let page_size = query.page_size.unwrap_or(50).clamp(1, 1000);
+    let (entries, total): (Vec<HistoryEntry>, u32) = blocking(move || {
+        Ok(db_history_get_filtered(
+            &path,
+            query.q.as_deref(),
+            query.status.as_deref(),
+            query.since_ms,
+            page,
+            page_size,
+        )?)
+    })
+    .await?;
+    Ok(Json(json!({
+        "entries": entries,
+        "total": total,
+        "page": page,
+        "page_size": page_size,
+    })))
+}
+
+// ─── Watchlist ───────────────────────────────────────────────────────────────
+
+async fn watchlist(
+    State(state): State<ApiState>,
+    caller: Caller,
+) -> Result<Json<Vec<WatchEntry>>, ApiError> {
+    caller.require(&state, Permission::WatchlistRead)?;
+    let path = path_str(state.profile.watchlist());
+    blocking(move || Ok(ExpiryStore::open(&path)?.get_all()?))
+        .await
+        .map(Json)
+}
+
+#[derive(Deserialize, Default)]
+struct WatchRequest {
+    priority: Option<WatchPriority>,
+}
+
+/// Watch a domain, or change the priority of one already watched.
+async fn watchlist_put(
+    State(state): State<ApiState>,
+    caller: Caller,
+    Path(domain): Path<String>,
+    body: Option<Json<WatchRequest>>,
+) -> Result<Json<WatchEntry>, ApiError> {
+    caller.require(&state, Permission::WatchlistManage)?;
+    let req = body.map(|Json(b)| b).unwrap_or_default();
+    let path = path_str(state.profile.watchlist());
+    let domain = domain.trim().to_lowercase();
+    let entry = blocking(move || {
+        let store = ExpiryStore::open(&path)?;
+        let mut entry = store
+            .load()?
+            .get(&domain)
+            .cloned()
+            .unwrap_or_else(|| WatchEntry::new(domain));
+        if let Some(priority) = req.priority {
+            entry.priority = priority;
+        }
+        entry.active = true;
+        store.upsert(&entry)?;
+        Ok(entry)
+    })
+    .await?;
+    state.record(
+        caller
+            .event(Action::WatchlistChanged)
+            .with_target(entry.domain.clone())
+            .with_detail("change", "watch"),
+    );
+    Ok(Json(entry))
+}
+
+async fn watchlist_remove(
+    State(state): State<ApiState>,
+    caller: Caller,
+    Path(domain): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    caller.require(&state, Permission::WatchlistManage)?;
+    let path = path_str(state.profile.watchlist());
+    let target = domain.clone();
+    blocking(move || match ExpiryStore::open(&path)?.remove(&domain)? {
+        true => Ok(()),
+        false => Err(ApiError::NotFound(domain)),
+    })
+    .await?;
+    state.record(
+        caller
+            .event(Action::WatchlistChanged)
+            .with_target(target)
+            .with_detail("change", "unwatch"),
+    );
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ─── Scheduler jobs ──────────────────────────────────────────────────────────
+
+async fn jobs_list(
+    State(state): State<ApiState>,
+    caller: Caller,
+) -> Result<Json<Vec<Job>>, ApiError> {
+    caller.require(&state, Permission::SchedulerRead)?;
+    let path = path_str(state.profile.scheduler());
+    blocking(move || Ok(SchedulerStore::open(&path)?.get_all()?))
+        .await
+        .map(Json)
+}
+
+#[derive(Deserialize)]
+struct JobRequest {
+    name: String,
+    domains: Vec<String>,
+    schedule: Schedule,
+    description: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+async fn jobs_create(
+    State(state): State<ApiState>,
+    caller: Caller,
+    Json(req): Json<JobRequest>,
+) -> Result<(StatusCode, Json<Job>), ApiError> {
+    caller.require(&state, Permission::SchedulerManage)?;
+    let domains = expand_domains(&req.domains, &[]);
+    if req.name.trim().is_empty() || domains.is_empty() {
+        return Err(ApiError::BadRequest(
+            "a job needs a name and domains".into(),
+        ));
+    }
+    let mut job = Job::new(req.name, domains, req.schedule);
+    job.description = req.description;
+    job.tags = req.tags;
+    let path = path_str(state.profile.scheduler());
+    let job = blocking(move || {
+        SchedulerStore::open(&path)?.save(&job)?;
+        Ok(job)
+    })
+    .await?;
+    record_job_change(&state, &caller, &job.id, "create");
+    Ok((StatusCode::CREATED, Json(job)))
+}
+
+async fn jobs_get(
+    State(state): State<ApiState>,
+    caller: Caller,
+    Path(id): Path<String>,
+) -> Result<Json<Job>, ApiError> {
+    caller.require(&state, Permission::SchedulerRead)?;
+    update_job(&state, id, |_| {}).await
+}
+
+async fn jobs_pause(
+    State(state): State<ApiState>,
+    caller: Caller,
+    Path(id): Path<String>,
+) -> Result<Json<Job>, ApiError> {
+    caller.require(&state, Permission::SchedulerManage)?;
+    let job = update_job(&state, id, Job::pause).await?;
+    record_job_change(&state, &caller, &job.id, "pause");
+    Ok(job)
+}
+
+async fn jobs_resume(
+    State(state): State<ApiState>,
+    caller: Caller,
+    Path(id): Path<String>,
+) -> Result<Json<Job>, ApiError> {
+    caller.require(&state, Permission::SchedulerManage)?;
+    let job = update_job(&state, id, Job::resume).await?;
+    record_job_change(&state, &caller, &job.id, "resume");
+    Ok(job)
+}
+
+async fn jobs_delete(
+    State(state): State<ApiState>,
+    caller: Caller,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    caller.require(&state, Permission::SchedulerManage)?;
+    let path = path_str(state.profile.scheduler());
+    let target = id.clone();
+    blocking(move || match SchedulerStore::open(&path)?.delete(&id)? {
+        true => Ok(()),
+        false => Err(ApiError::NotFound(id)),
+    })
+    .await?;
+    record_job_change(&state, &caller, &target, "delete");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn record_job_change(state: &ApiState, caller: &Caller, id: &str, change: &str) {
+    state.record(
+        caller
+            .event(Action::SchedulerChanged)
+            .with_target(id)
+            .with_detail("change", change),
+    );
+}
+
+/// Apply `change` to a stored job and save it.
+async fn update_job(
+    state: &ApiState,
+    id: String,
+    change: fn(&mut Job),
+) -> Result<Json<Job>, ApiError> {
+    let path = path_str(state.profile.scheduler());
+    blocking(move || {
+        let store = SchedulerStore::open(&path)?;
+        let mut job = store.get(&id)?.ok_or(ApiError::NotFound(id))?;
+        change(&mut job);
+        store.save(&job)?;
+        Ok(job)
+    })
+    .await
+    .map(Json)
+}
+
+// ─── Exports ─────────────────────────────────────────────────────────────────
+
+/// Watchlist and portfolio expiry dates as an iCalendar feed.
+async fn export_calendar(
+    State(state): State<ApiState>,
+    caller: Caller,
+) -> Result<Response, ApiError> {
+    caller.require(&state, Permission::Export)?;
+    let profile = state.profile.clone();
+    let ics = blocking(move || {
+        let now = Utc::now();
+        let mut calendar = IcsCalendar::default();
+        if profile.watchlist().is_file() {
+            for entry in ExpiryStore::open(&path_str(profile.watchlist()))?.get_all()? {
+                calendar.add_watch_entry(&entry, now);
+            }
+        }
+        if profile.portfolio().is_file() {
+            for domain in PortfolioStore::open(&path_str(profile.portfolio()))?.get_all()? {
+                calendar.add_owned(&domain, now);
+            }
+        }
+        Ok(calendar.to_ics(now))
+    })
+    .await?;
+    Ok((
+        [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+        ics,
+    )
+        .into_response())
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use axum::http::Request;
+    use tower::ServiceExt;
+    use wd_db::profile::ProfileDir;
+
+    use crate::auth::ApiKeys;
+    use wd_audit::{AuditLog, AuditQuery};
+
+    /// Domains starting with `free` are unregistered, the rest registered.
+    fn stub_lookup() -> wd_bulk::LookupFn {
+        Arc::new(|domain: String| {
+            Box::pin(async move {
+                if domain.starts_with("free") {
+                    Ok(format!(
+                        "No match for domain \"{}\".",
+                        domain.to_uppercase()
+                    ))
+                } else {
+                    Ok(format!(
+                        "Domain Name: {}\nRegistrar: Example Registrar\nCreation Date: 2001-01-01T00:00:00Z\nRegistry Expiry Date: 2030-01-01T00:00:00Z",
+                        domain.to_uppercase()
+                    ))
+                }
+            })
+        })
+    }
+
+    struct Fixture {
+        app: Router,
+        audit: Arc<AuditLog>,
+        dir: std::path::PathBuf,
+        operator: String,
+        viewer: String,
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            std::fs::remove_dir_all(&self.dir).ok();
+        }
+    }
+
+    fn fixture(tag: &str) -> Fixture {
+        let dir = std::env::temp_dir().join(format!("wd-api-{}-{}", tag, std::process::id()));
+        let profile = ProfileDir::open(&dir, "default").unwrap();
+        let mut keys = ApiKeys::default();
+        let operator = keys.create("ops", vec!["operator".into()]).unwrap();
+        let viewer = keys.create("view", vec!["viewer".into()]).unwrap();
+        let state = ApiState::new(profile, keys)
+            .with_lookup(stub_lookup())
+            .with_audit(AuditLog::open_in_memory().unwrap());
+        Fixture {
+            audit: state.audit.clone().unwrap(),
+            app: router(state),
+            dir,
+            operator,
+            viewer,
+        }
+    }
+
+    async fn call(
+        app: &Router,
+        method: &str,
+        uri: &str,
+        key: Option<&str>,
+        body: Option<Value>,
+    ) -> (StatusCode, String) {
+        let mut req = Request::builder().method(method).uri(uri);
+        if let Some(key) = key {
+            req = req.header(header::AUTHORIZATION, format!("Bearer {}", key));
+        }
+        let req = match body {
+            Some(body) => req
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => req.body(Body::empty()),
+        }
+        .unwrap();
+        let res = app.clone().oneshot(req).await.unwrap();
+        let status = res.status();
+        let bytes = axum::body::to_bytes(res.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    #[tokio::test]
+    async fn test_lookup_requires_key_and_permission() {
+        let f = fixture("auth");
+        let (status, _) = call(&f.app, "GET", "/api/v1/health", None, None).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, body) = call(&f.app, "GET", "/api/v1/lookup/example.com", None, None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert!(body.contains("\"unauthorized\""));
+
+        let (status, _) = call(
+            &f.app,
+            "GET",
+            "/api/v1/lookup/example.com",
+            Some("wdk_x"),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, body) = call(
+            &f.app,
+            "GET",
+            "/api/v1/lookup/example.com",
+            Some(&f.viewer),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert!(body.contains("lookup required"));
+
+        let (status, body) = call(
+            &f.app,
+            "GET",
+            "/api/v1/lookup/free.com",
+            Some(&f.operator),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let result: BulkResult = serde_json::from_str(&body).unwrap();
+        assert_eq!(result.status, "available");
+
+        let (status, body) = call(&f.app, "GET", "/api/v1/history", Some(&f.viewer), None).await;
+        assert_eq!(status, StatusCode::OK);
+        let history: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(history["total"], 1);
+        assert_eq!(history["entries"][0]["domain"], "free.com");
+
+        let (status, _) = call(&f.app, "GET", "/api/v1/nope", Some(&f.viewer), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_job_streams_results() {
+        let f = fixture("bulk");
+        let submit = json!({ "domains": ["free", "taken"], "tlds": ["com", "net"] });
+        let (status, _) = call(
+            &f.app,
+            "POST",
+            "/api/v1/bulk",
+            Some(&f.viewer),
+            Some(submit.clone()),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let (status, body) = call(
+            &f.app,
+            "POST",
+            "/api/v1/bulk",
+            Some(&f.operator),
+            Some(submit),
+        )
+        .await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        let summary: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(summary["total"], 4);
+        let id = summary["id"].as_str().unwrap().to_string();
+
+        let uri = format!("/api/v1/bulk/{}/results", id);
+        let (status, body) = call(&f.app, "GET", &uri, Some(&f.operator), None).await;
+        assert_eq!(status, StatusCode::OK);
+        let mut results: Vec<BulkResult> = body
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        results.sort_by(|a, b| a.domain.cmp(&b.domain));
+        let statuses: Vec<_> = results
+            .iter()
+            .map(|r| (r.domain.as_str(), r.status.as_str()))
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                ("free.com", "available"),
+                ("free.net", "available"),
+                ("taken.com", "unavailable"),
+                ("taken.net", "unavailable"),
+            ]
+        );
+
+        let (_, body) = call(
+            &f.app,
+            "GET",
+            &format!("/api/v1/bulk/{}", id),
+            Some(&f.operator),
+            None,
+        )
+        .await;
+        let summary: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(summary["state"], "completed");
+        assert_eq!(summary["completed"], 4);
+
+        let (status, body) = call(
+            &f.app,
+            "GET",
+            &format!("{}?format=csv", uri),
+            Some(&f.operator),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("free.com"));
+
+        let entries = f.audit.query(&AuditQuery::new()).unwrap();
+        let actions: Vec<_> = entries.iter().map(|e| e.event.action).collect();
+        assert_eq!(actions, [Action::PermissionDenied, Action::BulkStarted]);
+        assert_eq!(entries[0].event.actor, "key:view");
+        assert_eq!(entries[0].event.target.as_deref(), Some("bulk_run"));
+        assert_eq!(entries[1].event.target.as_deref(), Some(id.as_str()));
+        assert!(f.audit.verify().unwrap().is_intact());
+
+        let (status, _) = call(
+            &f.app,
+            "GET",
+            "/api/v1/bulk/missing",
+            Some(&f.operator),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_watchlist_and_jobs() {
+        let f = fixture("crud");
+        let (status, _) = call(
+            &f.app,
+            "PUT",
+            "/api/v1/watchlist/example.com",
+            Some(&f.viewer),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, body) = call(
+            &f.app,
+            "PUT",
+            "/api/v1/watchlist/Example.com",
+            Some(&f.operator),
+            Some(json!({ "priority": "high" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        let (_, body) = call(&f.app, "GET", "/api/v1/watchlist", Some(&f.viewer), None).await;
+        let list: Vec<WatchEntry> = serde_json::from_str(&body).unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].domain, "example.com");
+        assert_eq!(list[0].priority, WatchPriority::High);
+
+        let (status, body) = call(
+            &f.app,
+            "GET",
+            "/api/v1/export/calendar.ics",
+            Some(&f.viewer),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.starts_with("BEGIN:VCALENDAR"));
+
+        let (status, _) = call(
+            &f.app,
+            "DELETE",
+            "/api/v1/watchlist/example.com",
+            Some(&f.operator),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = call(
+            &f.app,
+            "DELETE",
+            "/api/v1/watchlist/example.com",
+            Some(&f.operator),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let job = json!({
+            "name": "nightly",
+            "domains": ["example.com"],
+            "schedule": serde_json::to_value(Schedule::daily_at(3)).unwrap(),
+        });
+        let (status, body) =
+            call(&f.app, "POST", "/api/v1/jobs", Some(&f.operator), Some(job)).await;
+        assert_eq!(status, StatusCode::CREATED, "{}", body);
+        let created: Job = serde_json::from_str(&body).unwrap();
+
+        let uri = format!("/api/v1/jobs/{}/pause", created.id);
+        let (status, body) = call(&f.app, "POST", &uri, Some(&f.operator), None).await;
+        assert_eq!(status, StatusCode::OK);
+        let paused: Job = serde_json::from_str(&body).unwrap();
+        assert_eq!(paused.status, wd_scheduler::JobStatus::Paused);
+
+        let (_, body) = call(&f.app, "GET", "/api/v1/jobs", Some(&f.viewer), None).await;
+        let jobs: Vec<Job> = serde_json::from_str(&body).unwrap();
+        assert_eq!(jobs.len(), 1);
+
+        let uri = format!("/api/v1/jobs/{}", created.id);
+        let (status, _) = call(&f.app, "DELETE", &uri, Some(&f.operator), None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = call(&f.app, "GET", &uri, Some(&f.viewer), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
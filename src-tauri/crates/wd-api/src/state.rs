@@ -0,0 +1,83 @@
+use std::sync::{Arc, RwLock};
+
+use wd_audit::{AuditEvent, AuditLog};
+use wd_bulk::{whois, LookupFn};
+use wd_db::profile::ProfileDir;
+use wd_lookup::LookupSettings;
+use wd_proxy::Rbac;
+
+use crate::auth::ApiKeys;
+use crate::jobs::BulkJobs;
+
+/// Shared state of the API server.
+#[derive(Clone)]
+pub struct ApiState {
+    pub keys: Arc<RwLock<ApiKeys>>,
+    pub rbac: Arc<Rbac>,
+    pub jobs: BulkJobs,
+    /// Profile whose databases the API reads and writes.
+    pub profile: Arc<ProfileDir>,
+    pub lookup: LookupFn,
+    /// Upper bound on the concurrency a bulk job may ask for.
+    pub max_concurrency: usize,
+    /// Most domains one bulk job may contain.
+    pub max_bulk_domains: usize,
+    /// Where changes and denied requests are recorded, if anywhere.
+    pub audit: Option<Arc<AuditLog>>,
+}
+
+impl ApiState {
+    /// State with the built-in RBAC roles and default WHOIS lookups.
+    pub fn new(profile: ProfileDir, keys: ApiKeys) -> Self {
+        let rbac = Rbac::new();
+        keys.bind(&rbac);
+        Self {
+            keys: Arc::new(RwLock::new(keys)),
+            rbac: Arc::new(rbac),
+            jobs: BulkJobs::new(),
+            profile: Arc::new(profile),
+            lookup: whois(LookupSettings::default()),
+            max_concurrency: 32,
+            max_bulk_domains: 10_000,
+            audit: None,
+        }
+    }
+
+    /// Use `rbac` for authorization, e.g. one with custom roles imported.
+    /// The keys are bound in it.
+    pub fn with_rbac(mut self, rbac: Rbac) -> Self {
+        self.keys.read().unwrap().bind(&rbac);
+        self.rbac = Arc::new(rbac);
+        self
+    }
+
+    pub fn with_lookup_settings(mut self, settings: LookupSettings) -> Self {
+        self.lookup = whois(settings);
+        self
+    }
+
+    /// Replace the lookup, e.g. with a stub in tests.
+    pub fn with_lookup(mut self, lookup: LookupFn) -> Self {
+        self.lookup = lookup;
+        self
+    }
+
+    pub fn with_max_concurrency(mut self, max: usize) -> Self {
+        self.max_concurrency = max.max(1);
+        self
+    }
+
+    pub fn with_audit(mut self, audit: AuditLog) -> Self {
+        self.audit = Some(Arc::new(audit));
+        self
+    }
+
+    /// Append `event` to the audit log, if one is set.
+    pub fn record(&self, event: AuditEvent) {
+        if let Some(audit) = &self.audit {
+            if let Err(e) = audit.append(event) {
+                log::warn!("could not record API action in the audit log: {e}");
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use wd_agent::{AgentEvent, ConfirmationDecision, EventKind};
+
+use crate::event::{Action, AuditEvent, Source};
+use crate::store::AuditLog;
+
+/// The audit event for an agent event, if it is one worth recording:
+/// run start and end, tool calls and confirmation decisions.
+pub fn from_agent_event(actor: &str, event: &AgentEvent) -> Option<AuditEvent> {
+    let audit = |action| AuditEvent::new(actor, Source::Agent, action);
+    match &event.kind {
+        EventKind::RunStarted { run_id, query } => Some(
+            audit(Action::AgentRunStarted)
+                .with_target(run_id.clone())
+                .with_detail("query", query.clone()),
+        ),
+        EventKind::RunCompleted {
+            run_id,
+            iterations,
+            cost,
+        } => Some(
+            audit(Action::AgentRunCompleted)
+                .with_target(run_id.clone())
+                .with_detail("iterations", *iterations)
+                .with_detail("cost", *cost),
+        ),
+        EventKind::ToolCallStarted { tool_name } => {
+            Some(audit(Action::AgentToolCalled).with_target(tool_name.clone()))
+        }
+        EventKind::ToolCallCompleted { tool_name, success } => Some(
+            audit(Action::AgentToolCompleted)
+                .with_target(tool_name.clone())
+                .with_detail("success", *success),
+        ),
+        EventKind::ConfirmationResolved {
+            tool_name,
+            decision,
+            ..
+        } => {
+            let approved = matches!(decision, ConfirmationDecision::Approve);
+            Some(
+                audit(Action::AgentConfirmation)
+                    .with_target(tool_name.clone())
+                    .with_detail("approved", approved),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// An agent event sink that records to `log` on behalf of `actor`, for
+/// `Agent::with_event_sink`.
+pub fn agent_sink(
+    log: Arc<AuditLog>,
+    actor: impl Into<String>,
+) -> impl Fn(&AgentEvent) + Send + Sync {
+    let actor = actor.into();
+    move |event| {
+        if let Some(audit) = from_agent_event(&actor, event) {
+            if let Err(e) = log.append(audit) {
+                log::warn!("could not record agent event: {e}");
+            }
+        }
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::AuditQuery;
+
+    #[test]
+    fn test_sink_records_tool_calls_only() {
+        let log = Arc::new(AuditLog::open_in_memory().unwrap());
+        let sink = agent_sink(Arc::clone(&log), "user:alice");
+        sink(&AgentEvent::new(EventKind::ToolCallStarted {
+            tool_name: "whois_lookup".into(),
+        }));
+        sink(&AgentEvent::new(EventKind::Thinking {
+            content: "hmm".into(),
+        }));
+        sink(&AgentEvent::new(EventKind::ToolCallCompleted {
+            tool_name: "whois_lookup".into(),
+            success: true,
+        }));
+
+        let entries = log.query(&AuditQuery::new()).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].event.action, Action::AgentToolCalled);
+        assert_eq!(entries[1].event.details["success"], true);
+        assert!(log.verify().unwrap().is_intact());
+    }
+}
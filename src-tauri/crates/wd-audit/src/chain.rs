@@ -0,0 +1,189 @@
+use std::io::BufRead;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::AuditError;
+use crate::event::AuditEvent;
+
+/// `prev_hash` of the first entry.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// An event as stored: numbered, timestamped and chained to the entry
+/// before it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AuditEntry {
+    /// Position in the log, starting at 1 with no gaps.
+    pub seq: u64,
+    /// When the entry was appended, to the millisecond.
+    pub at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub event: AuditEvent,
+    pub prev_hash: String,
+    /// SHA-256 over the fields above, hex encoded.
+    pub hash: String,
+}
+
+impl AuditEntry {
+    /// Chain `event` after the entry `prev_hash` belongs to.
+    pub(crate) fn new(seq: u64, at: DateTime<Utc>, event: AuditEvent, prev_hash: &str) -> Self {
+        let at = DateTime::from_timestamp_millis(at.timestamp_millis()).unwrap_or(at);
+        let mut entry = Self {
+            seq,
+            at,
+            event,
+            prev_hash: prev_hash.to_string(),
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash();
+        entry
+    }
+
+    /// The hash the entry's contents should have.
+    pub fn compute_hash(&self) -> String {
+        let content = serde_json::json!([
+            self.seq,
+            self.at.timestamp_millis(),
+            self.event,
+            self.prev_hash,
+        ]);
+        Sha256::digest(content.to_string().as_bytes())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+}
+
+/// A break in the chain.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Problem {
+    /// Entries between `after` and `next` are missing.
+    Gap { after: u64, next: u64 },
+    /// The entry's contents do not match its hash: it was edited.
+    Altered { seq: u64 },
+    /// The entry does not point at the hash of the entry before it.
+    BrokenLink { seq: u64 },
+}
+
+/// Outcome of checking a chain.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct VerifyReport {
+    pub checked: u64,
+    pub problems: Vec<Problem>,
+    /// Hash of the last entry checked, to compare against a copy kept
+    /// elsewhere; truncating the log cannot be detected otherwise.
+    pub head: Option<String>,
+}
+
+impl VerifyReport {
+    pub fn is_intact(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Check that `entries`, in order, form an unbroken chain from the first
+/// entry of the log.
+pub fn verify_entries<I>(entries: I) -> VerifyReport
+where
+    I: IntoIterator<Item = AuditEntry>,
+{
+    let mut report = VerifyReport::default();
+    let mut prev: Option<(u64, String)> = None;
+    for entry in entries {
+        report.checked += 1;
+        if entry.hash != entry.compute_hash() {
+            report.problems.push(Problem::Altered { seq: entry.seq });
+        }
+        let (expected_seq, expected_prev) = match &prev {
+            Some((seq, hash)) => (seq + 1, hash.as_str()),
+            None => (1, GENESIS_HASH),
+        };
+        if entry.seq != expected_seq {
+            report.problems.push(Problem::Gap {
+                after: expected_seq - 1,
+                next: entry.seq,
+            });
+        } else if entry.prev_hash != expected_prev {
+            report.problems.push(Problem::BrokenLink { seq: entry.seq });
+        }
+        prev = Some((entry.seq, entry.hash));
+    }
+    report.head = prev.map(|(_, hash)| hash);
+    report
+}
+
+/// Verify a full, unfiltered JSONL export of a log.
+pub fn verify_jsonl<R: BufRead>(reader: R) -> Result<VerifyReport, AuditError> {
+    let mut entries = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            entries.push(serde_json::from_str::<AuditEntry>(&line)?);
+        }
+    }
+    Ok(verify_entries(entries))
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{Action, Source};
+
+    fn chain(n: u64) -> Vec<AuditEntry> {
+        let mut prev = GENESIS_HASH.to_string();
+        (1..=n)
+            .map(|seq| {
+                let event = AuditEvent::new("user:a", Source::Cli, Action::CacheCleared)
+                    .with_detail("n", seq);
+                let entry = AuditEntry::new(seq, Utc::now(), event, &prev);
+                prev = entry.hash.clone();
+                entry
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_intact_chain_verifies() {
+        let entries = chain(3);
+        let report = verify_entries(entries.clone());
+        assert!(report.is_intact());
+        assert_eq!(report.checked, 3);
+        assert_eq!(report.head.as_deref(), Some(entries[2].hash.as_str()));
+
+        let jsonl: String = entries
+            .iter()
+            .map(|e| serde_json::to_string(e).unwrap() + "\n")
+            .collect();
+        assert!(verify_jsonl(jsonl.as_bytes()).unwrap().is_intact());
+    }
+
+    #[test]
+    fn test_edit_gap_and_relink_are_detected() {
+        let mut edited = chain(3);
+        edited[1].event.actor = "user:b".into();
+        assert_eq!(
+            verify_entries(edited).problems,
+            vec![Problem::Altered { seq: 2 }]
+        );
+
+        let mut missing = chain(3);
+        missing.remove(1);
+        assert_eq!(
+            verify_entries(missing).problems,
+            vec![Problem::Gap { after: 1, next: 3 }]
+        );
+
+        // Rewriting an entry and its hash still breaks the next link.
+        let mut rehashed = chain(3);
+        rehashed[1].event.actor = "user:b".into();
+        rehashed[1].hash = rehashed[1].compute_hash();
+        assert_eq!(
+            verify_entries(rehashed).problems,
+            vec![Problem::BrokenLink { seq: 3 }]
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Where an action was taken.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Source {
+    Gui,
+    Cli,
+    Api,
+    Agent,
+}
+
+impl Source {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Gui => "gui",
+            Self::Cli => "cli",
+            Self::Api => "api",
+            Self::Agent => "agent",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        [Self::Gui, Self::Cli, Self::Api, Self::Agent]
+            .into_iter()
+            .find(|v| v.as_str() == s)
+    }
+}
+
+/// What was done.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    BulkStarted,
+    BulkStopped,
+    ResultsExported,
+    CacheCleared,
+    CacheMerged,
+    HistoryCleared,
+    HistoryMerged,
+    ProfileImported,
+    ProfileExported,
+    ProfileDeleted,
+    ConfigImported,
+    ConfigDeleted,
+    WatchlistChanged,
+    SchedulerChanged,
+    ApiKeyCreated,
+    ApiKeyRevoked,
+    ServerStarted,
+    PermissionDenied,
+    AgentRunStarted,
+    AgentRunCompleted,
+    AgentToolCalled,
+    AgentToolCompleted,
+    AgentConfirmation,
+}
+
+impl Action {
+    pub const ALL: [Action; 23] = [
+        Self::BulkStarted,
+        Self::BulkStopped,
+        Self::ResultsExported,
+        Self::CacheCleared,
+        Self::CacheMerged,
+        Self::HistoryCleared,
+        Self::HistoryMerged,
+        Self::ProfileImported,
+        Self::ProfileExported,
+        Self::ProfileDeleted,
+        Self::ConfigImported,
+        Self::ConfigDeleted,
+        Self::WatchlistChanged,
+        Self::SchedulerChanged,
+        Self::ApiKeyCreated,
+        Self::ApiKeyRevoked,
+        Self::ServerStarted,
+        Self::PermissionDenied,
+        Self::AgentRunStarted,
+        Self::AgentRunCompleted,
+        Self::AgentToolCalled,
+        Self::AgentToolCompleted,
+        Self::AgentConfirmation,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::BulkStarted => "bulk_started",
+            Self::BulkStopped => "bulk_stopped",
+            Self::ResultsExported => "results_exported",
+            Self::CacheCleared => "cache_cleared",
+            Self::CacheMerged => "cache_merged",
+            Self::HistoryCleared => "history_cleared",
+            Self::HistoryMerged => "history_merged",
+            Self::ProfileImported => "profile_imported",
+            Self::ProfileExported => "profile_exported",
+            Self::ProfileDeleted => "profile_deleted",
+            Self::ConfigImported => "config_imported",
+            Self::ConfigDeleted => "config_deleted",
+            Self::WatchlistChanged => "watchlist_changed",
+            Self::SchedulerChanged => "scheduler_changed",
+            Self::ApiKeyCreated => "api_key_created",
+            Self::ApiKeyRevoked => "api_key_revoked",
+            Self::ServerStarted => "server_started",
+            Self::PermissionDenied => "permission_denied",
+            Self::AgentRunStarted => "agent_run_started",
+            Self::AgentRunCompleted => "agent_run_completed",
+            Self::AgentToolCalled => "agent_tool_called",
+            Self::AgentToolCompleted => "agent_tool_completed",
+            Self::AgentConfirmation => "agent_confirmation",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.as_str() == s)
+    }
+}
+
+/// One recorded action.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AuditEvent {
+    /// Who acted, e.g. `user:alice`, `key:ci` or `agent:<run id>`.
+    pub actor: String,
+    pub source: Source,
+    pub action: Action,
+    /// What was acted on: a profile, job id, domain or path.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub details: BTreeMap<String, serde_json::Value>,
+}
+
+impl AuditEvent {
+    pub fn new(actor: impl Into<String>, source: Source, action: Action) -> Self {
+        Self {
+            actor: actor.into(),
+            source,
+            action,
+            target: None,
+            details: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_target(mut self, target: impl Into<String>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    pub fn with_detail(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<serde_json::Value>,
+    ) -> Self {
+        self.details.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// The local OS user as an actor, `user:<name>`.
+pub fn local_actor() -> String {
+    let name = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "local".into());
+    format!("user:{}", name)
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_action_names_roundtrip() {
+        for action in Action::ALL {
+            let json = serde_json::to_value(action).unwrap();
+            assert_eq!(json, action.as_str());
+            assert_eq!(Action::parse(action.as_str()), Some(action));
+        }
+        assert_eq!(Source::parse("api"), Some(Source::Api));
+        assert_eq!(Action::parse("nope"), None);
+    }
+}
@@ -0,0 +1,27 @@
+//! # wd-audit
+//!
+//! Append-only audit log of who did what: bulk jobs, cache and history
+//! clears, profile imports, API changes and agent tool calls.
+//!
+//! Each entry carries the SHA-256 hash of the entry before it, so editing,
+//! reordering or deleting entries breaks the chain and shows up in
+//! [`AuditLog::verify`] or [`verify_jsonl`].
+//!
+//! ## Modules
+//!
+//! - **event** – actors, sources, actions and the events recorded
+//! - **chain** – hash-chained entries and chain verification
+//! - **store** – SQLite persistence, queries and JSONL export
+//! - **agent** – audit events from agent runs (feature `agent`)
+
+#[cfg(feature = "agent")]
+pub mod agent;
+pub mod chain;
+pub mod error;
+pub mod event;
+pub mod store;
+
+pub use chain::{verify_entries, verify_jsonl, AuditEntry, Problem, VerifyReport, GENESIS_HASH};
+pub use error::AuditError;
+pub use event::{local_actor, Action, AuditEvent, Source};
+pub use store::{AuditLog, AuditQuery, AUDIT_FILE};
@@ -0,0 +1,422 @@
+use std::io::Write;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rusqlite::{
+    params, params_from_iter, Connection, OptionalExtension, Result as SqlResult,
+    TransactionBehavior,
+};
+
+use crate::chain::{verify_entries, AuditEntry, VerifyReport, GENESIS_HASH};
+use crate::error::AuditError;
+use crate::event::{Action, AuditEvent, Source};
+
+/// File name of the app-wide audit log in the app data directory.
+pub const AUDIT_FILE: &str = "audit.sqlite";
+
+/// How long a write waits for another process (GUI, CLI, API) holding the
+/// database lock before giving up.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Filter for [`AuditLog::query`]. Empty matches everything.
+#[derive(Clone, Debug, Default)]
+pub struct AuditQuery {
+    pub actor: Option<String>,
+    pub action: Option<Action>,
+    pub source: Option<Source>,
+    pub target: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    /// Only the most recent `limit` matches.
+    pub limit: Option<usize>,
+}
+
+impl AuditQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn by_actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = Some(actor.into());
+        self
+    }
+
+    pub fn with_action(mut self, action: Action) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    pub fn from_source(mut self, source: Source) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    pub fn on_target(mut self, target: impl Into<String>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    pub fn since(mut self, since: DateTime<Utc>) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    pub fn until(mut self, until: DateTime<Utc>) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+/// SQLite-backed audit log. Entries can only be appended: the table
+/// rejects updates and deletes, and changes made around that are caught
+/// by [`verify`](Self::verify).
+pub struct AuditLog {
+    conn: Mutex<Connection>,
+}
+
+impl AuditLog {
+    pub fn open(path: &str) -> SqlResult<Self> {
+        let conn = Connection::open(path)?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        let log = Self {
+            conn: Mutex::new(conn),
+        };
+        log.init_tables()?;
+        Ok(log)
+    }
+
+    pub fn open_in_memory() -> SqlResult<Self> {
+        let conn = Connection::open_in_memory()?;
+        let log = Self {
+            conn: Mutex::new(conn),
+        };
+        log.init_tables()?;
+        Ok(log)
+    }
+
+    fn init_tables(&self) -> SqlResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute_batch(
+            "
+            PRAGMA journal_mode = WAL;
+            CREATE TABLE IF NOT EXISTS audit (
+                seq       INTEGER PRIMARY KEY,
+                at        INTEGER NOT NULL,
+                actor     TEXT NOT NULL,
+                source    TEXT NOT NULL,
+                action    TEXT NOT NULL,
+                target    TEXT,
+                details   TEXT NOT NULL DEFAULT '{}',
+                prev_hash TEXT NOT NULL,
+                hash      TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_audit_actor ON audit(actor);
+            CREATE INDEX IF NOT EXISTS idx_audit_action ON audit(action);
+            CREATE INDEX IF NOT EXISTS idx_audit_at ON audit(at);
+            CREATE TRIGGER IF NOT EXISTS audit_no_update BEFORE UPDATE ON audit
+            BEGIN SELECT RAISE(ABORT, 'audit log is append-only'); END;
+            CREATE TRIGGER IF NOT EXISTS audit_no_delete BEFORE DELETE ON audit
+            BEGIN SELECT RAISE(ABORT, 'audit log is append-only'); END;
+            ",
+        )?;
+        Ok(())
+    }
+
+    /// Record `event` now and return the stored entry.
+    pub fn append(&self, event: AuditEvent) -> SqlResult<AuditEntry> {
+        let mut conn = self.conn.lock().unwrap();
+        // Take the write lock before reading the chain head so another
+        // process cannot append between the read and the insert.
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let last: Option<(i64, String)> = tx
+            .query_row(
+                "SELECT seq, hash FROM audit ORDER BY seq DESC LIMIT 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let (seq, prev_hash) = match last {
+            Some((seq, hash)) => (seq as u64 + 1, hash),
+            None => (1, GENESIS_HASH.to_string()),
+        };
+        let entry = AuditEntry::new(seq, Utc::now(), event, &prev_hash);
+        tx.execute(
+            "INSERT INTO audit (seq, at, actor, source, action, target, details, prev_hash, hash)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                entry.seq as i64,
+                entry.at.timestamp_millis(),
+                entry.event.actor,
+                entry.event.source.as_str(),
+                entry.event.action.as_str(),
+                entry.event.target,
+                serde_json::to_string(&entry.event.details).unwrap_or_else(|_| "{}".into()),
+                entry.prev_hash,
+                entry.hash,
+            ],
+        )?;
+        tx.commit()?;
+        Ok(entry)
+    }
+
+    /// Entries matching `query`, oldest first.
+    pub fn query(&self, query: &AuditQuery) -> SqlResult<Vec<AuditEntry>> {
+        let mut clauses = Vec::new();
+        let mut values: Vec<rusqlite::types::Value> = Vec::new();
+        if let Some(actor) = &query.actor {
+            clauses.push("actor = ?");
+            values.push(actor.clone().into());
+        }
+        if let Some(action) = query.action {
+            clauses.push("action = ?");
+            values.push(action.as_str().to_string().into());
+        }
+        if let Some(source) = query.source {
+            clauses.push("source = ?");
+            values.push(source.as_str().to_string().into());
+        }
+        if let Some(target) = &query.target {
+            clauses.push("target = ?");
+            values.push(target.clone().into());
+        }
+        if let Some(since) = query.since {
+            clauses.push("at >= ?");
+            values.push(since.timestamp_millis().into());
+        }
+        if let Some(until) = query.until {
+            clauses.push("at <= ?");
+            values.push(until.timestamp_millis().into());
+        }
+        let mut sql = "SELECT seq, at, actor, source, action, target, details, prev_hash, hash
+                       FROM audit"
+            .to_string();
+        if !clauses.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&clauses.join(" AND "));
+        }
+        sql.push_str(" ORDER BY seq DESC");
+        if let Some(limit) = query.limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&sql)?;
+        let mut entries = stmt
+            .query_map(params_from_iter(values), row_to_entry)?
+            .collect::<SqlResult<Vec<_>>>()?;
+        entries.reverse();
+        Ok(entries)
+    }
+
+    /// The most recent entry.
+    pub fn head(&self) -> SqlResult<Option<AuditEntry>> {
+        Ok(self
+            .query(&AuditQuery::new().with_limit(1))?
+            .into_iter()
+            .next())
+    }
+
+    pub fn count(&self) -> SqlResult<i64> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row("SELECT COUNT(*) FROM audit", [], |row| row.get(0))
+    }
+
+    /// Check the whole log for gaps and tampering.
+    pub fn verify(&self) -> SqlResult<VerifyReport> {
+        Ok(verify_entries(self.query(&AuditQuery::new())?))
+    }
+
+    /// Write entries matching `query` to `out`, one JSON object per line.
+    /// Returns the number written.
+    pub fn export_jsonl<W: Write>(
+        &self,
+        query: &AuditQuery,
+        out: &mut W,
+    ) -> Result<usize, AuditError> {
+        let entries = self.query(query)?;
+        for entry in &entries {
+            serde_json::to_writer(&mut *out, entry)?;
+            out.write_all(b"\n")?;
+        }
+        out.flush()?;
+        Ok(entries.len())
+    }
+}
+
+fn row_to_entry(row: &rusqlite::Row) -> SqlResult<AuditEntry> {
+    let at_ms: i64 = row.get(1)?;
+    let source: String = row.get(3)?;
+    let action: String = row.get(4)?;
+    let details: String = row.get(6)?;
+    let invalid = |col: usize, what: &str, value: &str| {
+        rusqlite::Error::FromSqlConversionFailure(
+            col,
+            rusqlite::types::Type::Text,
+            format!("unknown {} '{}'", what, value).into(),
+        )
+    };
+    Ok(AuditEntry {
+        seq: row.get::<_, i64>(0)? as u64,
+        at: DateTime::from_timestamp_millis(at_ms).unwrap_or_default(),
+        event: AuditEvent {
+            actor: row.get(2)?,
+            source: Source::parse(&source).ok_or_else(|| invalid(3, "source", &source))?,
+            action: Action::parse(&action).ok_or_else(|| invalid(4, "action", &action))?,
+            target: row.get(5)?,
+            details: serde_json::from_str(&details).unwrap_or_default(),
+        },
+        prev_hash: row.get(7)?,
+        hash: row.get(8)?,
+    })
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::Problem;
+
+    fn seeded() -> AuditLog {
+        let log = AuditLog::open_in_memory().unwrap();
+        log.append(
+            AuditEvent::new("user:alice", Source::Gui, Action::BulkStarted)
+                .with_detail("domains", 120),
+        )
+        .unwrap();
+        log.append(
+            AuditEvent::new("user:alice", Source::Gui, Action::CacheCleared).with_target("default"),
+        )
+        .unwrap();
+        log.append(
+            AuditEvent::new("key:ci", Source::Api, Action::BulkStarted).with_target("job-1"),
+        )
+        .unwrap();
+        log
+    }
+
+    #[test]
+    fn test_append_chains_entries() {
+        let log = seeded();
+        let entries = log.query(&AuditQuery::new()).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].prev_hash, GENESIS_HASH);
+        assert_eq!(entries[1].prev_hash, entries[0].hash);
+        assert_eq!(entries[0].event.details["domains"], 120);
+        assert_eq!(log.head().unwrap().unwrap().seq, 3);
+        assert!(log.verify().unwrap().is_intact());
+    }
+
+    #[test]
+    fn test_query_filters() {
+        let log = seeded();
+        let alice = log
+            .query(&AuditQuery::new().by_actor("user:alice"))
+            .unwrap();
+        assert_eq!(alice.len(), 2);
+        let bulk = log
+            .query(&AuditQuery::new().with_action(Action::BulkStarted))
+            .unwrap();
+        assert_eq!(bulk.iter().map(|e| e.seq).collect::<Vec<_>>(), vec![1, 3]);
+        let api = log
+            .query(&AuditQuery::new().from_source(Source::Api))
+            .unwrap();
+        assert_eq!(api[0].event.target.as_deref(), Some("job-1"));
+        let latest = log.query(&AuditQuery::new().with_limit(2)).unwrap();
+        assert_eq!(latest.iter().map(|e| e.seq).collect::<Vec<_>>(), vec![2, 3]);
+        let future = Utc::now() + chrono::Duration::hours(1);
+        assert!(log
+            .query(&AuditQuery::new().since(future))
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            log.query(&AuditQuery::new().until(future)).unwrap().len(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_updates_and_deletes_are_rejected() {
+        let log = seeded();
+        let conn = log.conn.lock().unwrap();
+        assert!(conn
+            .execute("UPDATE audit SET actor = 'x' WHERE seq = 1", [])
+            .is_err());
+        assert!(conn.execute("DELETE FROM audit WHERE seq = 2", []).is_err());
+    }
+
+    #[test]
+    fn test_verify_detects_tampering_around_triggers() {
+        let log = seeded();
+        {
+            let conn = log.conn.lock().unwrap();
+            conn.execute_batch(
+                "DROP TRIGGER audit_no_update; DROP TRIGGER audit_no_delete;
+                 UPDATE audit SET actor = 'user:mallory' WHERE seq = 1;
+                 DELETE FROM audit WHERE seq = 2;",
+            )
+            .unwrap();
+        }
+        let report = log.verify().unwrap();
+        assert_eq!(
+            report.problems,
+            vec![
+                Problem::Altered { seq: 1 },
+                Problem::Gap { after: 1, next: 3 }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_concurrent_writers_keep_one_chain() {
+        let path =
+            std::env::temp_dir().join(format!("wd_audit_writers_{}.sqlite", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let path = path.to_string_lossy().into_owned();
+        AuditLog::open(&path).unwrap();
+
+        // One connection per writer, as separate processes would have.
+        let writers: Vec<_> = (0..4)
+            .map(|n| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    let log = AuditLog::open(&path).unwrap();
+                    for _ in 0..25 {
+                        log.append(AuditEvent::new(
+                            format!("user:{n}"),
+                            Source::Cli,
+                            Action::BulkStarted,
+                        ))
+                        .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let report = AuditLog::open(&path).unwrap().verify().unwrap();
+        assert!(report.is_intact(), "{:?}", report.problems);
+        assert_eq!(report.checked, 100);
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{path}{suffix}"));
+        }
+    }
+
+    #[test]
+    fn test_export_jsonl_roundtrips_and_verifies() {
+        let log = seeded();
+        let mut out = Vec::new();
+        assert_eq!(log.export_jsonl(&AuditQuery::new(), &mut out).unwrap(), 3);
+        let report = crate::chain::verify_jsonl(out.as_slice()).unwrap();
+        assert!(report.is_intact());
+        assert_eq!(report.head, log.verify().unwrap().head);
+    }
+}
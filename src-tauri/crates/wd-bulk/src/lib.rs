@@ -0,0 +1,17 @@
+//! Concurrent bulk lookups shared by the desktop app, the CLI and the API.
+//!
+//! A [`BulkRunner`] looks a list of domains up with bounded concurrency,
+//! classifies each reply into a [`wd_availability::DomainStatus`] and returns
+//! [`BulkResult`]s in input order. Lookups are pluggable ([`whois`], [`dns`],
+//! [`rdap`] or any [`LookupFn`]), and a [`BulkControl`] pauses or stops a run
+//! from elsewhere.
+
+pub mod lookup;
+pub mod runner;
+
+pub use lookup::{
+    classify_dns, classify_rdap, dns, expand_domains, rdap, resolve, resolve_with, whois, Classify,
+    LookupFn, LookupFuture,
+};
+pub use runner::{BulkControl, BulkRunner};
+pub use wd_export::BulkResult;
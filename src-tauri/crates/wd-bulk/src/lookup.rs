@@ -0,0 +1,142 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use wd_availability::{get_domain_parameters, is_domain_available, DomainStatus};
+use wd_export::BulkResult;
+use wd_lookup::{dns_lookup, perform_lookup_with_settings, rdap_lookup, LookupSettings};
+
+/// Boxed future of a raw lookup reply.
+pub type LookupFuture = Pin<Box<dyn Future<Output = Result<String, String>> + Send>>;
+
+/// Fetches the raw reply for a domain.
+pub type LookupFn = Arc<dyn Fn(String) -> LookupFuture + Send + Sync>;
+
+/// Turns a raw reply into a domain status.
+pub type Classify = fn(&str) -> DomainStatus;
+
+/// Reply of [`dns`] when the domain has name servers.
+pub const NS_FOUND: &str = "NS found";
+/// Reply of [`dns`] when it has none.
+pub const NO_NS: &str = "No NS";
+
+/// WHOIS lookups with `settings`, as the GUI performs them.
+pub fn whois(settings: LookupSettings) -> LookupFn {
+    let settings = Arc::new(settings);
+    Arc::new(move |domain: String| {
+        let settings = Arc::clone(&settings);
+        // The WHOIS client reads its socket synchronously; on the blocking
+        // pool it leaves the async workers free and a timeout can fire.
+        Box::pin(async move {
+            tokio::task::spawn_blocking(move || {
+                futures::executor::block_on(perform_lookup_with_settings(&domain, &settings))
+            })
+            .await
+            .map_err(|e| e.to_string())?
+        })
+    })
+}
+
+/// NS lookups; the reply is [`NS_FOUND`] or [`NO_NS`]. Classify with
+/// [`classify_dns`].
+pub fn dns() -> LookupFn {
+    Arc::new(|domain: String| {
+        Box::pin(async move {
+            dns_lookup(&domain)
+                .await
+                .map(|found| if found { NS_FOUND } else { NO_NS }.to_string())
+        })
+    })
+}
+
+/// RDAP lookups; the reply is the RDAP JSON. Classify with [`classify_rdap`].
+pub fn rdap() -> LookupFn {
+    Arc::new(|domain: String| Box::pin(async move { rdap_lookup(&domain).await }))
+}
+
+/// A domain without name servers is treated as available.
+pub fn classify_dns(reply: &str) -> DomainStatus {
+    match reply {
+        NS_FOUND => DomainStatus::Unavailable,
+        NO_NS => DomainStatus::Available,
+        _ => DomainStatus::ErrorUnparsable,
+    }
+}
+
+/// A domain object means registered, a 404 error object means available.
+pub fn classify_rdap(reply: &str) -> DomainStatus {
+    let compact: String = reply.chars().filter(|c| !c.is_whitespace()).collect();
+    if compact.contains("\"objectClassName\":\"domain\"") {
+        DomainStatus::Unavailable
+    } else if compact.contains("\"errorCode\":404") {
+        DomainStatus::Available
+    } else if compact.contains("\"errorCode\":429") {
+        DomainStatus::ErrorRateLimiting
+    } else if compact.is_empty() {
+        DomainStatus::ErrorNoContent
+    } else {
+        DomainStatus::ErrorUnparsable
+    }
+}
+
+/// Look `domain` up and classify the reply the way a GUI bulk lookup does.
+pub async fn resolve(lookup: &LookupFn, domain: String, timeout: Option<Duration>) -> BulkResult {
+    resolve_with(lookup, is_domain_available, domain, timeout).await
+}
+
+/// Look `domain` up and classify the reply with `classify`.
+pub async fn resolve_with(
+    lookup: &LookupFn,
+    classify: Classify,
+    domain: String,
+    timeout: Option<Duration>,
+) -> BulkResult {
+    let reply = match timeout {
+        Some(t) => match tokio::time::timeout(t, lookup(domain.clone())).await {
+            Ok(res) => res,
+            Err(_) => Err(format!("Timeout after {}ms", t.as_millis())),
+        },
+        None => lookup(domain.clone()).await,
+    };
+    match reply {
+        Ok(data) => {
+            let status = classify(&data);
+            let params =
+                get_domain_parameters(Some(domain.clone()), Some(status.clone()), data.clone());
+            BulkResult {
+                domain,
+                data: Some(data),
+                error: None,
+                status: status.as_str().to_string(),
+                params: Some(params),
+            }
+        }
+        Err(e) => BulkResult {
+            domain,
+            data: None,
+            error: Some(e),
+            status: "error".into(),
+            params: None,
+        },
+    }
+}
+
+/// Domains to look up: each entry's first label under every TLD in `tlds`,
+/// or the entries as given when `tlds` is empty.
+pub fn expand_domains(domains: &[String], tlds: &[String]) -> Vec<String> {
+    let domains = domains
+        .iter()
+        .map(|d| d.trim().to_lowercase())
+        .filter(|d| !d.is_empty());
+    if tlds.is_empty() {
+        return domains.collect();
+    }
+    domains
+        .flat_map(|d| {
+            let base = d.split('.').next().unwrap_or_default().to_string();
+            tlds.iter()
+                .map(move |t| format!("{}.{}", base, t.trim().trim_start_matches('.')))
+        })
+        .collect()
+}
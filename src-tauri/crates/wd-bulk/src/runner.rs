@@ -0,0 +1,253 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Semaphore;
+use wd_availability::is_domain_available;
+use wd_export::BulkResult;
+
+use crate::lookup::{resolve_with, Classify, LookupFn};
+
+/// How often a paused run checks whether it may continue.
+const PAUSE_POLL: Duration = Duration::from_millis(200);
+
+/// Pause, resume and stop switches for a running bulk lookup. Clones share
+/// the same switches.
+#[derive(Clone, Default)]
+pub struct BulkControl {
+    paused: Arc<AtomicBool>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl BulkControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
+    /// Stop starting new lookups; the rest report as stopped.
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
+    /// Clear both switches before a new run.
+    pub fn reset(&self) {
+        self.stopped.store(false, Ordering::SeqCst);
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::SeqCst)
+    }
+
+    /// Wait out a pause. Returns `false` once the run is stopped.
+    async fn proceed(&self) -> bool {
+        while self.is_paused() && !self.is_stopped() {
+            tokio::time::sleep(PAUSE_POLL).await;
+        }
+        !self.is_stopped()
+    }
+}
+
+/// Looks domains up concurrently and classifies each reply.
+#[derive(Clone)]
+pub struct BulkRunner {
+    lookup: LookupFn,
+    classify: Classify,
+    concurrency: usize,
+    timeout: Option<Duration>,
+    control: BulkControl,
+}
+
+impl BulkRunner {
+    /// A runner over `lookup` classifying WHOIS replies, five lookups at a
+    /// time and without a per-domain timeout.
+    pub fn new(lookup: LookupFn) -> Self {
+        Self {
+            lookup,
+            classify: is_domain_available,
+            concurrency: 5,
+            timeout: None,
+            control: BulkControl::default(),
+        }
+    }
+
+    pub fn with_classifier(mut self, classify: Classify) -> Self {
+        self.classify = classify;
+        self
+    }
+
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Give up on a domain after `timeout`; `None` waits as long as the
+    /// lookup itself does.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_control(mut self, control: BulkControl) -> Self {
+        self.control = control;
+        self
+    }
+
+    pub fn control(&self) -> &BulkControl {
+        &self.control
+    }
+
+    /// Look every domain up. `on_result` sees each result as it arrives with
+    /// the number done so far and the total. Results come back in input
+    /// order; domains not started before a stop report the error "Stopped".
+    pub async fn run<F>(&self, domains: Vec<String>, on_result: F) -> Vec<BulkResult>
+    where
+        F: Fn(&BulkResult, usize, usize) + Send + Sync + 'static,
+    {
+        let total = domains.len();
+        let semaphore = Arc::new(Semaphore::new(self.concurrency));
+        let done = Arc::new(AtomicUsize::new(0));
+        let on_result = Arc::new(on_result);
+
+        let tasks: Vec<_> = domains
+            .into_iter()
+            .map(|domain| {
+                let semaphore = Arc::clone(&semaphore);
+                let done = Arc::clone(&done);
+                let on_result = Arc::clone(&on_result);
+                let lookup = Arc::clone(&self.lookup);
+                let control = self.control.clone();
+                let (classify, timeout) = (self.classify, self.timeout);
+
+                tokio::spawn(async move {
+                    let result = match semaphore.acquire().await {
+                        Ok(_permit) if control.proceed().await => {
+                            resolve_with(&lookup, classify, domain, timeout).await
+                        }
+                        Ok(_) => stopped(domain),
+                        Err(_) => failed(domain, "Semaphore closed"),
+                    };
+                    on_result(&result, done.fetch_add(1, Ordering::SeqCst) + 1, total);
+                    result
+                })
+            })
+            .collect();
+
+        futures::future::join_all(tasks)
+            .await
+            .into_iter()
+            .filter_map(|r| r.ok())
+            .collect()
+    }
+}
+
+fn stopped(domain: String) -> BulkResult {
+    failed(domain, "Stopped")
+}
+
+fn failed(domain: String, error: &str) -> BulkResult {
+    BulkResult {
+        domain,
+        data: None,
+        error: Some(error.into()),
+        status: "error".into(),
+        params: None,
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lookup::{classify_dns, NO_NS, NS_FOUND};
+    use std::sync::Mutex;
+
+    fn stub(reply: &'static str) -> LookupFn {
+        Arc::new(move |domain: String| {
+            Box::pin(async move {
+                if domain.starts_with("bad") {
+                    Err("connection refused".to_string())
+                } else {
+                    Ok(reply.to_string())
+                }
+            })
+        })
+    }
+
+    fn domains(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_run_keeps_input_order_and_reports_progress() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&seen);
+        let runner = BulkRunner::new(stub("No match for domain")).with_concurrency(2);
+        let results = runner
+            .run(
+                domains(&["a.com", "bad.com", "c.com"]),
+                move |r, done, total| {
+                    log.lock().unwrap().push((r.domain.clone(), done, total));
+                },
+            )
+            .await;
+
+        let names: Vec<_> = results.iter().map(|r| r.domain.as_str()).collect();
+        assert_eq!(names, ["a.com", "bad.com", "c.com"]);
+        assert_eq!(results[0].status, "available");
+        assert_eq!(results[1].status, "error");
+        assert_eq!(results[1].error.as_deref(), Some("connection refused"));
+
+        let mut counts: Vec<_> = seen.lock().unwrap().iter().map(|s| s.1).collect();
+        counts.sort();
+        assert_eq!(counts, [1, 2, 3]);
+        assert!(seen.lock().unwrap().iter().all(|s| s.2 == 3));
+    }
+
+    #[tokio::test]
+    async fn test_stopped_run_reports_remaining_domains_as_stopped() {
+        let control = BulkControl::new();
+        control.stop();
+        let runner = BulkRunner::new(stub("Domain Name: A.COM")).with_control(control.clone());
+        let results = runner.run(domains(&["a.com", "b.com"]), |_, _, _| {}).await;
+        assert!(results
+            .iter()
+            .all(|r| r.error.as_deref() == Some("Stopped")));
+
+        control.reset();
+        let results = runner.run(domains(&["a.com"]), |_, _, _| {}).await;
+        assert_eq!(results[0].error, None);
+    }
+
+    #[tokio::test]
+    async fn test_classifier_and_timeout() {
+        let runner = BulkRunner::new(stub(NO_NS)).with_classifier(classify_dns);
+        let results = runner.run(domains(&["a.com"]), |_, _, _| {}).await;
+        assert_eq!(results[0].status, "available");
+        assert_eq!(classify_dns(NS_FOUND).as_str(), "unavailable");
+
+        let slow: LookupFn = Arc::new(|_| {
+            Box::pin(async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok(String::new())
+            })
+        });
+        let runner = BulkRunner::new(slow).with_timeout(Some(Duration::from_millis(10)));
+        let results = runner.run(domains(&["a.com"]), |_, _, _| {}).await;
+        assert_eq!(results[0].error.as_deref(), Some("Timeout after 10ms"));
+    }
+}
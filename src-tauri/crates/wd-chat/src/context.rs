@@ -1,5 +1,9 @@
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
-use wd_llm::{Message, Role, TokenEstimator as TE, ToolDefinition};
+use wd_llm::{LlmError, Message, Role, TokenEstimator, ToolDefinition};
+
+use crate::session::ChatSession;
+use crate::summary::{ConversationSummary, Summarizer};
 
 /// Strategy for managing the conversation context window.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -7,7 +11,8 @@ use wd_llm::{Message, Role, TokenEstimator as TE, ToolDefinition};
 pub enum ContextStrategy {
     /// Keep the last N messages (sliding window).
     SlidingWindow,
-    /// Keep system + first + last messages; summarise the middle.
+    /// Keep system + first + last messages; summarise the middle with
+    /// [`ContextManager::trim_with_summary`] (plain `trim` drops it).
     PinnedEnds,
     /// Always keep system prompt; drop oldest non-system messages.
     DropOldest,
@@ -30,6 +35,9 @@ pub struct ContextConfig {
     /// For Smart: number of recent turns to always keep.
     #[serde(default = "default_pinned_recent")]
     pub pinned_recent: usize,
+    /// For PinnedEnds: tokens set aside for the summary of the middle.
+    #[serde(default = "default_summary_tokens")]
+    pub summary_tokens: usize,
 }
 
 fn default_window_size() -> usize {
@@ -40,6 +48,10 @@ fn default_pinned_recent() -> usize {
     6
 }
 
+fn default_summary_tokens() -> usize {
+    512
+}
+
 impl Default for ContextConfig {
     fn default() -> Self {
         Self {
@@ -48,6 +60,7 @@ impl Default for ContextConfig {
             strategy: ContextStrategy::Smart,
             window_size: default_window_size(),
             pinned_recent: default_pinned_recent(),
+            summary_tokens: default_summary_tokens(),
         }
     }
 }
@@ -55,11 +68,23 @@ impl Default for ContextConfig {
 /// Manages trimming of the message list to fit within the context window.
 pub struct ContextManager {
     config: ContextConfig,
+    estimator: TokenEstimator,
 }
 
 impl ContextManager {
     pub fn new(config: ContextConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            estimator: TokenEstimator::default(),
+        }
+    }
+
+    /// Count tokens with `estimator`, typically the client's
+    /// [`LlmClient::token_estimator`](wd_llm::LlmClient::token_estimator),
+    /// instead of the default character ratio.
+    pub fn with_estimator(mut self, estimator: TokenEstimator) -> Self {
+        self.estimator = estimator;
+        self
     }
 
     /// Trim messages to fit within the configured context budget.
@@ -73,16 +98,105 @@ impl ContextManager {
         }
     }
 
+    /// Build the messages to send for `session`. With the PinnedEnds
+    /// strategy, messages that do not fit between the pinned first turn and
+    /// the most recent ones are replaced by a summary from `summarizer`.
+    ///
+    /// The summary is cached in the session metadata and only extended with
+    /// newly cut messages on later calls. A tool call and its results are
+    /// always kept or summarised together. Other strategies behave as
+    /// [`trim`](Self::trim).
+    pub async fn trim_with_summary(
+        &self,
+        session: &mut ChatSession,
+        tools: &[ToolDefinition],
+        summarizer: &impl Summarizer,
+    ) -> Result<Vec<Message>, LlmError> {
+        let messages = session.build_messages();
+        if self.config.strategy != ContextStrategy::PinnedEnds || self.fits(&messages, tools) {
+            return Ok(self.trim(&messages, tools));
+        }
+
+        let (mut result, non_system): (Vec<Message>, Vec<Message>) =
+            messages.into_iter().partition(|m| m.role == Role::System);
+        if non_system.is_empty() {
+            return Ok(result);
+        }
+        let budget = self
+            .config
+            .max_context_tokens
+            .saturating_sub(self.config.reserved_output_tokens)
+            .saturating_sub(self.config.summary_tokens);
+        let head_end = group_end(&non_system, 1);
+        let mut used: usize = result
+            .iter()
+            .chain(&non_system[..head_end])
+            .map(|m| self.estimator.estimate_message(m))
+            .sum::<usize>()
+            + self.estimator.estimate_tools(tools);
+
+        let mut cut = non_system.len();
+        while cut > head_end {
+            let cost = self.estimator.estimate_message(&non_system[cut - 1]);
+            if used + cost > budget {
+                break;
+            }
+            used += cost;
+            cut -= 1;
+        }
+        let mut cut = group_end(&non_system, cut);
+
+        // A summary is only reusable while the messages it covers are still
+        // there, e.g. not after the session was truncated.
+        let cached = session.metadata.summary.take().filter(|s| {
+            let end = head_end + s.covered;
+            s.start == head_end
+                && s.covered > 0
+                && end <= non_system.len()
+                && group_end(&non_system, end) == end
+        });
+        let summary = match cached {
+            // Already covers at least the middle; keeping its cut leaves the
+            // summary valid at the cost of a shorter tail.
+            Some(s) if head_end + s.covered >= cut => {
+                cut = head_end + s.covered;
+                Some(s)
+            }
+            Some(s) => {
+                let text = summarizer
+                    .summarize(Some(&s.text), &non_system[head_end + s.covered..cut])
+                    .await?;
+                Some(summary_for(text, head_end, cut))
+            }
+            None if cut > head_end => {
+                let text = summarizer
+                    .summarize(None, &non_system[head_end..cut])
+                    .await?;
+                Some(summary_for(text, head_end, cut))
+            }
+            None => None,
+        };
+
+        result.extend_from_slice(&non_system[..head_end]);
+        if let Some(summary) = &summary {
+            result.push(summary.to_message());
+        }
+        result.extend_from_slice(&non_system[cut..]);
+        session.metadata.summary = summary;
+        Ok(result)
+    }
+
     /// How many tokens are available for the model output after accounting
     /// for the given messages and tools.
     pub fn available_output_tokens(&self, messages: &[Message], tools: &[ToolDefinition]) -> usize {
-        TE::available_for_output(messages, tools, self.config.max_context_tokens)
+        self.estimator
+            .available_for_output(messages, tools, self.config.max_context_tokens)
     }
 
     /// Whether the given messages + tools fit within the context window
     /// with enough room for output.
     pub fn fits(&self, messages: &[Message], tools: &[ToolDefinition]) -> bool {
-        TE::fits_context(
+        self.estimator.fits_context(
             messages,
             tools,
             self.config.max_context_tokens,
@@ -132,14 +246,17 @@ impl ContextManager {
         }
 
         // Estimate tokens used so far
-        let tool_tokens = TE::estimate_tools(tools);
-        let system_tokens: usize = result.iter().map(|m| TE::estimate_message(m)).sum();
+        let tool_tokens = self.estimator.estimate_tools(tools);
+        let system_tokens: usize = result
+            .iter()
+            .map(|m| self.estimator.estimate_message(m))
+            .sum();
         let mut used = system_tokens + tool_tokens;
 
         // Add messages from newest to oldest, then reverse
         let mut tail = Vec::new();
         for &m in non_system.iter().rev() {
-            let cost = TE::estimate_message(m);
+            let cost = self.estimator.estimate_message(m);
             if used + cost > budget {
                 break;
             }
@@ -171,30 +288,34 @@ impl ContextManager {
             return result;
         }
 
-        let tool_tokens = TE::estimate_tools(tools);
+        let tool_tokens = self.estimator.estimate_tools(tools);
         let mut used: usize = result
             .iter()
-            .map(|m| TE::estimate_message(m))
+            .map(|m| self.estimator.estimate_message(m))
             .sum::<usize>()
             + tool_tokens;
 
-        // Pin first non-system message
-        let first = non_system[0].clone();
-        used += TE::estimate_message(&first);
-        result.push(first);
+        // Pin the first non-system message along with any tool results
+        // answering it
+        let head_end = group_end(&non_system, 1);
+        for &m in &non_system[..head_end] {
+            used += self.estimator.estimate_message(m);
+            result.push(m.clone());
+        }
 
-        // Add from the end as many as fit
-        let mut tail = Vec::new();
-        for &m in non_system[1..].iter().rev() {
-            let cost = TE::estimate_message(m);
+        // Add from the end as many as fit, never starting on a tool result
+        // whose call was cut
+        let mut cut = non_system.len();
+        while cut > head_end {
+            let cost = self.estimator.estimate_message(non_system[cut - 1]);
             if used + cost > budget {
                 break;
             }
             used += cost;
-            tail.push(m.clone());
+            cut -= 1;
         }
-        tail.reverse();
-        result.extend(tail);
+        let cut = group_end(&non_system, cut);
+        result.extend(non_system[cut..].iter().map(|&m| m.clone()));
 
         result
     }
@@ -213,8 +334,11 @@ impl ContextManager {
         let non_system: Vec<&Message> =
             messages.iter().filter(|m| m.role != Role::System).collect();
 
-        let tool_tokens = TE::estimate_tools(tools);
-        let sys_tokens: usize = system.iter().map(|m| TE::estimate_message(m)).sum();
+        let tool_tokens = self.estimator.estimate_tools(tools);
+        let sys_tokens: usize = system
+            .iter()
+            .map(|m| self.estimator.estimate_message(m))
+            .sum();
         let mut used = sys_tokens + tool_tokens;
 
         // Always pin the last `pinned_recent` messages
@@ -226,13 +350,16 @@ impl ContextManager {
             .map(|&m| m.clone())
             .collect();
 
-        let pinned_tokens: usize = pinned.iter().map(|m| TE::estimate_message(m)).sum();
+        let pinned_tokens: usize = pinned
+            .iter()
+            .map(|m| self.estimator.estimate_message(m))
+            .sum();
         used += pinned_tokens;
 
         // Fill from the earliest non-system messages up to budget
         let mut middle = Vec::new();
         for &m in &non_system[..pinned_start] {
-            let cost = TE::estimate_message(m);
+            let cost = self.estimator.estimate_message(m);
             if used + cost > budget {
                 break;
             }
@@ -247,11 +374,209 @@ impl ContextManager {
     }
 }
 
+/// The index after the tool results that start at `i`, so a cut there
+/// keeps each tool call together with its results.
+fn group_end<M: std::borrow::Borrow<Message>>(messages: &[M], mut i: usize) -> usize {
+    while i < messages.len() && messages[i].borrow().role == Role::Tool {
+        i += 1;
+    }
+    i.min(messages.len())
+}
+
+fn summary_for(text: String, start: usize, end: usize) -> ConversationSummary {
+    ConversationSummary {
+        text,
+        start,
+        covered: end - start,
+        updated_at: Utc::now(),
+    }
+}
+
 // ─── Tests ───────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::persona::PersonaKind;
+    use std::sync::Mutex;
+    use wd_llm::{FunctionCall, ToolCall};
+
+    /// Records `(previous summary, message count)` for every call.
+    #[derive(Default)]
+    struct FakeSummarizer {
+        calls: Mutex<Vec<(Option<String>, usize)>>,
+    }
+
+    impl Summarizer for FakeSummarizer {
+        async fn summarize(
+            &self,
+            previous: Option<&str>,
+            messages: &[Message],
+        ) -> Result<String, LlmError> {
+            let mut calls = self.calls.lock().unwrap();
+            calls.push((previous.map(String::from), messages.len()));
+            Ok(format!("summary #{}", calls.len()))
+        }
+    }
+
+    fn pinned(max_context_tokens: usize) -> ContextManager {
+        ContextManager::new(ContextConfig {
+            strategy: ContextStrategy::PinnedEnds,
+            max_context_tokens,
+            reserved_output_tokens: 50,
+            summary_tokens: 50,
+            ..Default::default()
+        })
+    }
+
+    fn long_session(turns: usize) -> ChatSession {
+        let mut session = ChatSession::new(PersonaKind::DomainExpert, None);
+        session.system_prompt = "You are helpful.".into();
+        for i in 0..turns {
+            session.add_user_message(&format!("Question {i} {}", "x".repeat(100)));
+        }
+        session
+    }
+
+    fn tool_session(groups: usize) -> ChatSession {
+        let mut session = long_session(1);
+        for i in 0..groups {
+            let id = format!("call-{i}");
+            session
+                .messages
+                .push(Message::assistant_tool_calls(vec![ToolCall {
+                    id: id.clone(),
+                    function: FunctionCall {
+                        name: "whois_lookup".into(),
+                        arguments: serde_json::json!({"domain": "a.com"}),
+                    },
+                }]));
+            session
+                .messages
+                .push(Message::tool_result(&id, "whois_lookup", &"r".repeat(120)));
+            session.add_assistant_message(&format!("Finding {i}"));
+        }
+        session
+    }
+
+    /// Every tool result is preceded by its call or another result.
+    fn assert_no_orphan_results(messages: &[Message]) {
+        for (i, m) in messages.iter().enumerate() {
+            if m.role == Role::Tool {
+                let prev = &messages[i - 1];
+                assert!(
+                    prev.role == Role::Tool || !prev.tool_calls.is_empty(),
+                    "orphaned tool result at {i}"
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pinned_ends_summarizes_and_caches() {
+        let mgr = pinned(400);
+        let summarizer = FakeSummarizer::default();
+        let mut session = long_session(20);
+
+        let first = mgr
+            .trim_with_summary(&mut session, &[], &summarizer)
+            .await
+            .unwrap();
+        assert_eq!(first[0].role, Role::System);
+        assert!(first[1]
+            .content
+            .as_deref()
+            .unwrap()
+            .starts_with("Question 0"));
+        assert!(first[2]
+            .content
+            .as_deref()
+            .unwrap()
+            .starts_with("Summary of"));
+        assert_eq!(
+            first.last().unwrap().content,
+            session.messages.last().unwrap().content
+        );
+        let cached = session.metadata.summary.clone().unwrap();
+        assert_eq!(cached.start, 1);
+        assert_eq!(cached.covered + first.len() - 2, session.messages.len());
+
+        // Unchanged conversation: the cached summary is reused.
+        let again = mgr
+            .trim_with_summary(&mut session, &[], &summarizer)
+            .await
+            .unwrap();
+        assert_eq!(again.len(), first.len());
+        assert_eq!(summarizer.calls.lock().unwrap().len(), 1);
+
+        // More turns: the summary is extended, not rebuilt.
+        for i in 0..4 {
+            session.add_assistant_message(&format!("Answer {i} {}", "y".repeat(100)));
+        }
+        mgr.trim_with_summary(&mut session, &[], &summarizer)
+            .await
+            .unwrap();
+        let calls = summarizer.calls.lock().unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[1].0.as_deref(), Some("summary #1"));
+        assert_eq!(calls[1].1, 4);
+        assert!(session.metadata.summary.as_ref().unwrap().covered > cached.covered);
+    }
+
+    #[tokio::test]
+    async fn test_pinned_ends_discards_summary_of_removed_messages() {
+        let mgr = pinned(400);
+        let summarizer = FakeSummarizer::default();
+        let mut session = long_session(30);
+        mgr.trim_with_summary(&mut session, &[], &summarizer)
+            .await
+            .unwrap();
+        let covered = session.metadata.summary.as_ref().unwrap().covered;
+
+        // Drop messages the summary covered; it must be rebuilt, not
+        // indexed past the end of the session.
+        session.messages.truncate(covered);
+        assert!(!mgr.fits(&session.build_messages(), &[]));
+        let msgs = mgr
+            .trim_with_summary(&mut session, &[], &summarizer)
+            .await
+            .unwrap();
+        assert_eq!(summarizer.calls.lock().unwrap().len(), 2);
+        assert_eq!(summarizer.calls.lock().unwrap()[1].0, None);
+        let summary = session.metadata.summary.as_ref().unwrap();
+        assert!(summary.covered < session.messages.len());
+        assert_eq!(
+            msgs.last().unwrap().content,
+            session.messages.last().unwrap().content
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pinned_ends_without_overflow_skips_summary() {
+        let summarizer = FakeSummarizer::default();
+        let mut session = long_session(4);
+        let msgs = pinned(100_000)
+            .trim_with_summary(&mut session, &[], &summarizer)
+            .await
+            .unwrap();
+        assert_eq!(msgs.len(), 5);
+        assert!(summarizer.calls.lock().unwrap().is_empty());
+        assert!(session.metadata.summary.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pinned_ends_never_splits_tool_pairs() {
+        for max in (250..700).step_by(7) {
+            let mgr = pinned(max);
+            let mut session = tool_session(12);
+            assert_no_orphan_results(&mgr.trim(&session.build_messages(), &[]));
+            let msgs = mgr
+                .trim_with_summary(&mut session, &[], &FakeSummarizer::default())
+                .await
+                .unwrap();
+            assert_no_orphan_results(&msgs);
+        }
+    }
 
     fn make_msgs(n: usize) -> Vec<Message> {
         let mut msgs = vec![Message::system("You are helpful.")];
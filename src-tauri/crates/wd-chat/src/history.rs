@@ -114,6 +114,25 @@ impl ChatStore {
         self.list_by_status("archived", limit)
     }
 
+    /// List every session that is not deleted, most recently updated first.
+    pub fn list_all(&self, limit: usize) -> SqlResult<Vec<ChatSession>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT data FROM sessions WHERE status != 'deleted' ORDER BY updated_at DESC LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit as i64], |row| {
+            let data: String = row.get(0)?;
+            Ok(data)
+        })?;
+        let mut sessions = Vec::new();
+        for row in rows {
+            let data = row?;
+            if let Ok(s) = serde_json::from_str::<ChatSession>(&data) {
+                sessions.push(s);
+            }
+        }
+        Ok(sessions)
+    }
+
     /// Delete a session permanently.
     pub fn delete(&self, id: &str) -> SqlResult<bool> {
         let changed = self
@@ -209,6 +228,19 @@ mod tests {
         assert_eq!(store.list_active(10).unwrap().len(), 0);
     }
 
+    #[test]
+    fn test_list_all_skips_deleted() {
+        let store = ChatStore::in_memory().unwrap();
+        let mut archived = make_session("Old");
+        archived.archive();
+        let mut deleted = make_session("Gone");
+        deleted.delete();
+        store.save(&make_session("New")).unwrap();
+        store.save(&archived).unwrap();
+        store.save(&deleted).unwrap();
+        assert_eq!(store.list_all(10).unwrap().len(), 2);
+    }
+
     #[test]
     fn test_delete() {
         let store = ChatStore::in_memory().unwrap();
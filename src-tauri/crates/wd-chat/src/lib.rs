@@ -5,6 +5,7 @@ pub mod history;
 pub mod persona;
 pub mod prompt;
 pub mod session;
+pub mod summary;
 
 pub use attachment::{Attachment, AttachmentKind};
 pub use context::{ContextConfig, ContextManager, ContextStrategy};
@@ -13,3 +14,4 @@ pub use history::ChatStore;
 pub use persona::{Persona, PersonaKind};
 pub use prompt::PromptLibrary;
 pub use session::{ChatSession, SessionMetadata, SessionStatus};
+pub use summary::{ConversationSummary, LlmSummarizer, Summarizer};
@@ -6,6 +6,7 @@ use wd_llm::{Message, Role};
 
 use crate::attachment::Attachment;
 use crate::persona::{Persona, PersonaKind};
+use crate::summary::ConversationSummary;
 
 /// High-level status of a session.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -24,6 +25,9 @@ pub struct SessionMetadata {
     pub estimated_cost_usd: f64,
     pub model: Option<String>,
     pub last_model: Option<String>,
+    /// Cached summary of messages cut from the context window.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<ConversationSummary>,
 }
 
 impl Default for SessionMetadata {
@@ -34,6 +38,7 @@ impl Default for SessionMetadata {
             estimated_cost_usd: 0.0,
             model: None,
             last_model: None,
+            summary: None,
         }
     }
 }
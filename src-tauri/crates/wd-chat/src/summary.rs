@@ -0,0 +1,167 @@
+use std::future::Future;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use wd_llm::{CompletionRequest, LlmClient, LlmError, Message, Role};
+
+/// Tool output longer than this is cut before it is summarised.
+const MAX_TOOL_OUTPUT_CHARS: usize = 2000;
+
+const SUMMARY_PROMPT: &str = "You condense the earlier part of a domain research \
+conversation so it can continue within a limited context window. Keep domain names, \
+registrars, nameservers, dates, prices, tool findings, decisions and open questions. \
+Reply with the summary only, as short prose or bullets.";
+
+/// Summary of the middle of a conversation, cached in the session so it
+/// is only extended, never regenerated, as the conversation grows.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ConversationSummary {
+    pub text: String,
+    /// Index of the first summarised message among the session's
+    /// non-system messages.
+    pub start: usize,
+    /// Number of messages from `start` the summary covers.
+    pub covered: usize,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ConversationSummary {
+    /// The summary as a message to place where the covered messages were.
+    pub fn to_message(&self) -> Message {
+        Message::system(&format!(
+            "Summary of {} earlier messages in this conversation:\n{}",
+            self.covered, self.text
+        ))
+    }
+}
+
+/// Condenses a run of conversation messages into a short summary.
+pub trait Summarizer: Send + Sync {
+    /// Summarise `messages`, extending `previous` (the summary of the
+    /// messages before them) when given.
+    fn summarize(
+        &self,
+        previous: Option<&str>,
+        messages: &[Message],
+    ) -> impl Future<Output = Result<String, LlmError>> + Send;
+}
+
+/// Summaries written by a configured wd-llm provider.
+pub struct LlmSummarizer {
+    client: LlmClient,
+    model: String,
+    max_tokens: usize,
+}
+
+impl LlmSummarizer {
+    /// Summarise with `model`, which may be a cheaper model than the one
+    /// the conversation uses.
+    pub fn new(client: LlmClient, model: impl Into<String>) -> Self {
+        Self {
+            client,
+            model: model.into(),
+            max_tokens: 512,
+        }
+    }
+
+    /// Cap the length of each summary.
+    pub fn with_max_tokens(mut self, max_tokens: usize) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+}
+
+impl Summarizer for LlmSummarizer {
+    async fn summarize(
+        &self,
+        previous: Option<&str>,
+        messages: &[Message],
+    ) -> Result<String, LlmError> {
+        let mut prompt = String::new();
+        if let Some(previous) = previous {
+            prompt.push_str(&format!(
+                "Summary so far:\n{previous}\n\nContinue it with:\n"
+            ));
+        }
+        prompt.push_str(&transcript(messages));
+        let req = CompletionRequest::new(
+            &self.model,
+            vec![Message::system(SUMMARY_PROMPT), Message::user(&prompt)],
+        )
+        .with_temperature(0.2)
+        .with_max_tokens(self.max_tokens);
+        let res = self.client.complete(&req).await?;
+        res.message
+            .content
+            .map(|c| c.trim().to_string())
+            .filter(|c| !c.is_empty())
+            .ok_or_else(|| LlmError::Other("summary response was empty".into()))
+    }
+}
+
+/// Render messages as a plain-text transcript, one line per turn.
+pub fn transcript(messages: &[Message]) -> String {
+    let mut lines = Vec::with_capacity(messages.len());
+    for m in messages {
+        let content = m.content.as_deref().unwrap_or_default();
+        match m.role {
+            Role::Tool => {
+                let output: String = content.chars().take(MAX_TOOL_OUTPUT_CHARS).collect();
+                lines.push(format!(
+                    "tool {}: {}",
+                    m.name.as_deref().unwrap_or("result"),
+                    output
+                ));
+            }
+            Role::System | Role::User | Role::Assistant => {
+                let role = match m.role {
+                    Role::System => "system",
+                    Role::User => "user",
+                    _ => "assistant",
+                };
+                if !content.is_empty() {
+                    lines.push(format!("{role}: {content}"));
+                }
+                for call in &m.tool_calls {
+                    lines.push(format!(
+                        "{role} called {}({})",
+                        call.function.name, call.function.arguments
+                    ));
+                }
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wd_llm::{FunctionCall, ToolCall};
+
+    #[test]
+    fn test_transcript_renders_tool_turns() {
+        let call = ToolCall {
+            id: "c1".into(),
+            function: FunctionCall {
+                name: "whois_lookup".into(),
+                arguments: serde_json::json!({"domain": "a.com"}),
+            },
+        };
+        let text = transcript(&[
+            Message::user("check a.com"),
+            Message::assistant_tool_calls(vec![call]),
+            Message::tool_result("c1", "whois_lookup", &"x".repeat(5000)),
+        ]);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "user: check a.com");
+        assert_eq!(
+            lines[1],
+            r#"assistant called whois_lookup({"domain":"a.com"})"#
+        );
+        assert!(lines[2].starts_with("tool whois_lookup: xxx"));
+        assert!(lines[2].len() < 2100);
+    }
+}
@@ -3,6 +3,8 @@ use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
+pub mod profile;
+
 // Re-export Connection for consumers that need direct DB access
 pub use rusqlite;
 
@@ -233,6 +235,64 @@ pub fn db_cache_set(
     Ok(())
 }
 
+/// Size and age of the request cache.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct CacheStats {
+    pub entries: u64,
+    pub bytes: u64,
+    /// Millisecond timestamps of the oldest and newest entries.
+    pub oldest: Option<i64>,
+    pub newest: Option<i64>,
+}
+
+/// Count the entries in the cache database at `path`.
+pub fn db_cache_stats(path: &str) -> Result<CacheStats, String> {
+    if !Path::new(path).exists() {
+        return Ok(CacheStats::default());
+    }
+    let conn = Connection::open(path).map_err(|e| e.to_string())?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS cache (key TEXT PRIMARY KEY, response TEXT, timestamp INTEGER)",
+        [],
+    )
+    .map_err(|e| e.to_string())?;
+    conn.query_row(
+        "SELECT COUNT(*), COALESCE(SUM(LENGTH(response)), 0), MIN(timestamp), MAX(timestamp) FROM cache",
+        [],
+        |r| {
+            Ok(CacheStats {
+                entries: r.get::<_, i64>(0)? as u64,
+                bytes: r.get::<_, i64>(1)? as u64,
+                oldest: r.get(2)?,
+                newest: r.get(3)?,
+            })
+        },
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Delete cached responses, either all of them or only those for `domain`
+/// (keys are `<type>:<domain>`). Returns how many entries were removed.
+pub fn db_cache_clear(path: &str, domain: Option<&str>) -> Result<usize, String> {
+    if !Path::new(path).exists() {
+        return Ok(0);
+    }
+    let conn = Connection::open(path).map_err(|e| e.to_string())?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS cache (key TEXT PRIMARY KEY, response TEXT, timestamp INTEGER)",
+        [],
+    )
+    .map_err(|e| e.to_string())?;
+    match domain {
+        Some(domain) => conn.execute(
+            "DELETE FROM cache WHERE key = ?1 OR substr(key, instr(key, ':') + 1) = ?1",
+            [domain],
+        ),
+        None => conn.execute("DELETE FROM cache", []),
+    }
+    .map_err(|e| e.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -356,6 +416,30 @@ mod tests {
         let _ = stdfs::remove_dir_all(&dir);
     }
 
+    #[test]
+    fn test_db_cache_stats_and_clear() {
+        let dir = std::env::temp_dir().join("wd_test_cache_stats_clear");
+        let _ = stdfs::remove_dir_all(&dir);
+        let _ = stdfs::create_dir_all(&dir);
+        let path_str = dir.join("cache.sqlite").to_string_lossy().to_string();
+
+        assert_eq!(db_cache_stats(&path_str).unwrap(), CacheStats::default());
+        db_cache_set(&path_str, "whois:example.com", "abcd", None).unwrap();
+        db_cache_set(&path_str, "rdap:example.com", "ef", None).unwrap();
+        db_cache_set(&path_str, "whois:other.com", "g", None).unwrap();
+
+        let stats = db_cache_stats(&path_str).unwrap();
+        assert_eq!(stats.entries, 3);
+        assert_eq!(stats.bytes, 7);
+        assert!(stats.oldest <= stats.newest);
+
+        assert_eq!(db_cache_clear(&path_str, Some("example.com")).unwrap(), 2);
+        assert_eq!(db_cache_stats(&path_str).unwrap().entries, 1);
+        assert_eq!(db_cache_clear(&path_str, None).unwrap(), 1);
+
+        let _ = stdfs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn test_db_cache_ttl_expired() {
         let dir = std::env::temp_dir().join("wd_test_cache_ttl");
@@ -0,0 +1,128 @@
+//! Where the desktop app keeps per-profile data, for tools that run
+//! outside it (the CLI and the API server).
+
+use std::path::{Path, PathBuf};
+
+/// Bundle identifier the desktop app stores its data under.
+pub const APP_IDENTIFIER: &str = "com.whoisteam.whoisdigger";
+
+/// The desktop app's data directory, e.g.
+/// `~/.local/share/com.whoisteam.whoisdigger` on Linux.
+pub fn app_data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|d| d.join(APP_IDENTIFIER))
+}
+
+/// Reject profile names that could escape the profiles directory.
+pub fn sanitize_name(name: &str) -> Result<&str, String> {
+    if name.is_empty() {
+        return Err("Name cannot be empty".into());
+    }
+    if name.contains("..")
+        || !name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '.')
+    {
+        return Err(format!("Invalid profile name: {}", name));
+    }
+    Ok(name)
+}
+
+/// The profile selected in the app, `"default"` when none is.
+pub fn current_profile(data_dir: &Path) -> String {
+    std::fs::read_to_string(data_dir.join("current-profile"))
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| sanitize_name(s).is_ok())
+        .unwrap_or_else(|| "default".into())
+}
+
+/// One profile's data directory and the database files in it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProfileDir {
+    pub name: String,
+    pub dir: PathBuf,
+}
+
+impl ProfileDir {
+    /// `data_dir/profiles/<name>`, created if missing.
+    pub fn open(data_dir: &Path, name: &str) -> Result<Self, String> {
+        let name = sanitize_name(name)?.to_string();
+        let dir = data_dir.join("profiles").join(&name);
+        std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        Ok(Self { name, dir })
+    }
+
+    /// `<stem>-<profile>.sqlite` inside the profile directory.
+    pub fn database(&self, stem: &str) -> PathBuf {
+        self.dir.join(format!("{}-{}.sqlite", stem, self.name))
+    }
+
+    pub fn history(&self) -> PathBuf {
+        self.database("history")
+    }
+
+    pub fn snapshots(&self) -> PathBuf {
+        self.database("snapshots")
+    }
+
+    pub fn watchlist(&self) -> PathBuf {
+        self.database("watchlist")
+    }
+
+    pub fn portfolio(&self) -> PathBuf {
+        self.database("portfolio")
+    }
+
+    pub fn scheduler(&self) -> PathBuf {
+        self.database("scheduler")
+    }
+
+    /// The notification settings the desktop app saves for this profile.
+    pub fn notifications(&self) -> PathBuf {
+        self.dir.join(format!("notifications-{}.json", self.name))
+    }
+
+    /// Where undeliverable notifications go when the settings name no
+    /// dead-letter file.
+    pub fn dead_letters(&self) -> PathBuf {
+        self.dir
+            .join(format!("notify-dead-letters-{}.jsonl", self.name))
+    }
+
+    /// The wordlist recipe book the desktop app saves for this profile.
+    pub fn wordlist_recipes(&self) -> PathBuf {
+        self.dir.join("wordlist-recipes.json")
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_paths() {
+        let data = std::env::temp_dir().join("wd_test_profile_paths");
+        let _ = std::fs::remove_dir_all(&data);
+        assert_eq!(current_profile(&data), "default");
+        std::fs::create_dir_all(&data).unwrap();
+        std::fs::write(data.join("current-profile"), "work\n").unwrap();
+        let profile = ProfileDir::open(&data, &current_profile(&data)).unwrap();
+        assert!(profile.dir.is_dir());
+        assert_eq!(
+            profile.watchlist(),
+            data.join("profiles/work/watchlist-work.sqlite")
+        );
+        assert_eq!(
+            profile.notifications(),
+            data.join("profiles/work/notifications-work.json")
+        );
+        assert_eq!(
+            profile.wordlist_recipes(),
+            data.join("profiles/work/wordlist-recipes.json")
+        );
+        assert!(ProfileDir::open(&data, "../etc").is_err());
+        let _ = std::fs::remove_dir_all(&data);
+    }
+}
@@ -9,3 +9,11 @@ pub mod chat {
 pub mod llm {
     pub use wd_llm::*;
 }
+
+pub mod search {
+    pub use wd_search::*;
+}
+
+pub mod audit {
+    pub use wd_audit::agent::*;
+}
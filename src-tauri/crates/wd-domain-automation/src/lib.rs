@@ -14,6 +14,14 @@ pub mod import {
     pub use wd_import::*;
 }
 
+pub mod notify {
+    pub use wd_notify::*;
+}
+
+pub mod portfolio {
+    pub use wd_portfolio::*;
+}
+
 pub mod scheduler {
     pub use wd_scheduler::*;
 }
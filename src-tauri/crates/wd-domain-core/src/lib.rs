@@ -22,6 +22,10 @@ pub mod availability {
     pub use wd_availability::*;
 }
 
+pub mod bulk {
+    pub use wd_bulk::*;
+}
+
 pub mod export {
     pub use wd_export::*;
 }
@@ -6,3 +6,7 @@ pub use wd_db::{
 pub mod db {
     pub use wd_db::*;
 }
+
+pub mod audit {
+    pub use wd_audit::*;
+}
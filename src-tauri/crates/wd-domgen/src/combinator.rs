@@ -55,73 +55,65 @@ impl Default for CombinatorConfig {
 /// - prefix + sep + word + sep + suffix + tld
 /// - word1 + sep + word2 + tld (pairwise)
 pub fn expand_combinations(config: &CombinatorConfig) -> Vec<String> {
-    let mut results = Vec::new();
-    let sep = &config.separator;
-
-    for tld in &config.tlds {
-        let tld = normalize_tld(tld);
-
-        for word in &config.words {
-            // Bare word
-            if config.include_bare {
-                push_if_valid(
-                    &mut results,
-                    format!("{word}{tld}"),
-                    config.max_label_length,
-                );
-            }
-
-            // Prefix + word
-            for prefix in &config.prefixes {
-                push_if_valid(
-                    &mut results,
-                    format!("{prefix}{sep}{word}{tld}"),
-                    config.max_label_length,
-                );
-            }
-
-            // Word + suffix
-            for suffix in &config.suffixes {
-                push_if_valid(
-                    &mut results,
-                    format!("{word}{sep}{suffix}{tld}"),
-                    config.max_label_length,
-                );
-            }
-
-            // Prefix + word + suffix
-            for prefix in &config.prefixes {
-                for suffix in &config.suffixes {
-                    push_if_valid(
-                        &mut results,
-                        format!("{prefix}{sep}{word}{sep}{suffix}{tld}"),
-                        config.max_label_length,
-                    );
-                }
-            }
-        }
-
-        // Pairwise word combinations
-        for (i, w1) in config.words.iter().enumerate() {
-            for w2 in config.words.iter().skip(i + 1) {
-                push_if_valid(
-                    &mut results,
-                    format!("{w1}{sep}{w2}{tld}"),
-                    config.max_label_length,
-                );
-                push_if_valid(
-                    &mut results,
-                    format!("{w2}{sep}{w1}{tld}"),
-                    config.max_label_length,
-                );
-            }
-        }
-    }
-
     // Dedup while preserving order
     let mut seen = std::collections::HashSet::new();
-    results.retain(|d| seen.insert(d.clone()));
-    results
+    iter_combinations(config)
+        .filter(|d| seen.insert(d.clone()))
+        .collect()
+}
+
+/// Lazily yield the same candidates as [`expand_combinations`], in the same
+/// order, without materialising the full matrix. Duplicates are not removed.
+pub fn iter_combinations(config: &CombinatorConfig) -> impl Iterator<Item = String> + '_ {
+    let sep = config.separator.as_str();
+    let max_label = config.max_label_length;
+
+    config
+        .tlds
+        .iter()
+        .map(|tld| normalize_tld(tld))
+        .flat_map(move |tld| {
+            let per_word = {
+                let tld = tld.clone();
+                config.words.iter().flat_map(move |word| {
+                    let tld = tld.clone();
+                    let bare = config
+                        .include_bare
+                        .then(|| format!("{word}{tld}"))
+                        .into_iter();
+                    let prefixed = {
+                        let tld = tld.clone();
+                        config
+                            .prefixes
+                            .iter()
+                            .map(move |prefix| format!("{prefix}{sep}{word}{tld}"))
+                    };
+                    let suffixed = {
+                        let tld = tld.clone();
+                        config
+                            .suffixes
+                            .iter()
+                            .map(move |suffix| format!("{word}{sep}{suffix}{tld}"))
+                    };
+                    let both = config.prefixes.iter().flat_map(move |prefix| {
+                        let tld = tld.clone();
+                        config
+                            .suffixes
+                            .iter()
+                            .map(move |suffix| format!("{prefix}{sep}{word}{sep}{suffix}{tld}"))
+                    });
+                    bare.chain(prefixed).chain(suffixed).chain(both)
+                })
+            };
+            let pairwise = config.words.iter().enumerate().flat_map(move |(i, w1)| {
+                let tld = tld.clone();
+                config.words.iter().skip(i + 1).flat_map(move |w2| {
+                    [format!("{w1}{sep}{w2}{tld}"), format!("{w2}{sep}{w1}{tld}")]
+                })
+            });
+            per_word.chain(pairwise)
+        })
+        .filter_map(move |domain| valid_candidate(domain, max_label))
 }
 
 fn normalize_tld(tld: &str) -> String {
@@ -132,14 +124,11 @@ fn normalize_tld(tld: &str) -> String {
     }
 }
 
-fn push_if_valid(out: &mut Vec<String>, domain: String, max_label: usize) {
+fn valid_candidate(domain: String, max_label: usize) -> Option<String> {
     // Extract the label part (everything before the first dot)
-    if let Some(dot_pos) = domain.find('.') {
-        let label = &domain[..dot_pos];
-        if !label.is_empty() && label.len() <= max_label {
-            out.push(domain.to_lowercase());
-        }
-    }
+    let dot_pos = domain.find('.')?;
+    let label = &domain[..dot_pos];
+    (!label.is_empty() && label.len() <= max_label).then(|| domain.to_lowercase())
 }
 
 // ─── Tests ───────────────────────────────────────────────────────────────────
@@ -255,4 +244,18 @@ mod tests {
         // "pre" + 60 chars = 63, which is exactly at limit
         assert!(results.iter().any(|d| d.starts_with("pre")));
     }
+
+    #[test]
+    fn test_iter_matches_expand_order() {
+        let config = CombinatorConfig {
+            words: vec!["fire".into(), "wall".into()],
+            prefixes: vec!["get".into()],
+            suffixes: vec!["hub".into()],
+            tlds: vec![".com".into(), "io".into()],
+            ..Default::default()
+        };
+        let lazy: Vec<String> = iter_combinations(&config).collect();
+        assert_eq!(lazy, expand_combinations(&config));
+        assert_eq!(lazy.first().map(String::as_str), Some("fire.com"));
+    }
 }
@@ -0,0 +1,445 @@
+use std::collections::HashSet;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+
+use crate::combinator::{iter_combinations, CombinatorConfig};
+use crate::filter::{DomainFilter, FilterConfig};
+use crate::score::DomainScorer;
+
+/// Result of an authoritative availability check (usually WHOIS).
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckVerdict {
+    Available,
+    Registered,
+    Unknown,
+}
+
+/// Availability backend used by [`Discovery`].
+///
+/// The pipeline asks [`resolves`](Self::resolves) first because a DNS
+/// delegation is cheap to test and proves registration. Only names that do
+/// not resolve are passed to [`confirm`](Self::confirm).
+pub trait AvailabilityChecker: Sync {
+    /// `true` when the domain has a live delegation (NS records).
+    fn resolves(&self, domain: &str) -> impl Future<Output = bool> + Send;
+
+    /// Authoritative check for a domain that did not resolve.
+    fn confirm(&self, domain: &str) -> impl Future<Output = Result<CheckVerdict, String>> + Send;
+}
+
+/// Configuration for a generate-then-check discovery run.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DiscoveryConfig {
+    /// Candidate source.
+    pub combinator: CombinatorConfig,
+    /// Rules applied to each candidate before any network check.
+    #[serde(default)]
+    pub filter: FilterConfig,
+    /// Stop once this many available domains have been found.
+    #[serde(default = "default_target")]
+    pub target: usize,
+    /// Upper bound on candidates sent to the checker (`None` = unlimited).
+    #[serde(default)]
+    pub max_checks: Option<usize>,
+    /// Number of checks in flight at once.
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+    /// Skip candidates whose brandability score is below this (0.0 – 1.0).
+    #[serde(default)]
+    pub min_score: Option<f64>,
+    /// Confirm DNS misses with WHOIS. When disabled, a missing delegation is
+    /// reported as available — faster, but parked or held names slip through.
+    #[serde(default = "default_true")]
+    pub confirm_with_whois: bool,
+}
+
+fn default_target() -> usize {
+    10
+}
+fn default_concurrency() -> usize {
+    4
+}
+fn default_true() -> bool {
+    true
+}
+
+impl Default for DiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            combinator: CombinatorConfig::default(),
+            filter: FilterConfig::default(),
+            target: default_target(),
+            max_checks: None,
+            concurrency: default_concurrency(),
+            min_score: None,
+            confirm_with_whois: true,
+        }
+    }
+}
+
+/// What happened to a single checked candidate.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CandidateOutcome {
+    /// Has NS records; rejected without a WHOIS query.
+    Resolves,
+    Available,
+    Registered,
+    Unknown,
+    Error(String),
+}
+
+/// Emitted once per checked candidate.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DiscoveryEvent {
+    pub domain: String,
+    pub score: Option<f64>,
+    pub outcome: CandidateOutcome,
+}
+
+/// Running counters for a discovery run.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct DiscoveryProgress {
+    /// Candidates produced by the combinator.
+    pub generated: usize,
+    /// Candidates dropped by the filter, dedup, or score threshold.
+    pub skipped: usize,
+    /// Candidates whose check has completed.
+    pub checked: usize,
+    /// Candidates rejected by the DNS pre-screen.
+    pub resolved: usize,
+    /// Candidates sent for WHOIS confirmation.
+    pub confirmed: usize,
+    pub found: usize,
+    pub target: usize,
+}
+
+/// Why a run stopped.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    TargetReached,
+    CheckLimit,
+    Exhausted,
+    Cancelled,
+}
+
+/// An available domain found by discovery.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DiscoveredDomain {
+    pub domain: String,
+    pub score: Option<f64>,
+    /// `false` when only the DNS pre-screen was run.
+    pub confirmed: bool,
+}
+
+/// Final outcome of [`Discovery::run`].
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DiscoveryReport {
+    /// Available domains, best score first when a scorer was attached.
+    pub found: Vec<DiscoveredDomain>,
+    pub progress: DiscoveryProgress,
+    pub stop_reason: StopReason,
+}
+
+/// Generate-then-check pipeline: candidates are pulled lazily from the
+/// combinator, filtered and optionally scored, then checked with bounded
+/// concurrency until the target is met or the source runs dry.
+pub struct Discovery<C> {
+    config: DiscoveryConfig,
+    checker: C,
+    scorer: Option<DomainScorer>,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl<C: AvailabilityChecker> Discovery<C> {
+    pub fn new(config: DiscoveryConfig, checker: C) -> Self {
+        Self {
+            config,
+            checker,
+            scorer: None,
+            cancel: None,
+        }
+    }
+
+    /// Score candidates; required for `min_score` to have any effect.
+    pub fn with_scorer(mut self, scorer: DomainScorer) -> Self {
+        self.scorer = Some(scorer);
+        self
+    }
+
+    /// Stop the run at the next completed check once `flag` is set.
+    pub fn with_cancel_flag(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(flag);
+        self
+    }
+
+    /// Run the pipeline, calling `on_event` after every completed check.
+    pub async fn run<F>(&self, mut on_event: F) -> DiscoveryReport
+    where
+        F: FnMut(&DiscoveryProgress, &DiscoveryEvent),
+    {
+        let filter = DomainFilter::new(self.config.filter.clone());
+        let generated = AtomicUsize::new(0);
+        let skipped = AtomicUsize::new(0);
+        let mut seen = HashSet::new();
+
+        let candidates = iter_combinations(&self.config.combinator)
+            .inspect(|_| {
+                generated.fetch_add(1, Ordering::Relaxed);
+            })
+            .filter_map(|domain| {
+                let score = self
+                    .scorer
+                    .as_ref()
+                    .map(|scorer| scorer.score_domain(&domain).total);
+                let passes = seen.insert(domain.clone())
+                    && filter.accepts(&domain)
+                    && match (score, self.config.min_score) {
+                        (Some(score), Some(min)) => score >= min,
+                        _ => true,
+                    };
+                if passes {
+                    Some((domain, score))
+                } else {
+                    skipped.fetch_add(1, Ordering::Relaxed);
+                    None
+                }
+            })
+            .take(self.config.max_checks.unwrap_or(usize::MAX));
+
+        let mut checks = stream::iter(candidates)
+            .map(|(domain, score)| async move {
+                let outcome = self.check(&domain).await;
+                DiscoveryEvent {
+                    domain,
+                    score,
+                    outcome,
+                }
+            })
+            .buffer_unordered(self.config.concurrency.max(1));
+
+        let mut progress = DiscoveryProgress {
+            target: self.config.target,
+            ..Default::default()
+        };
+        let mut found = Vec::new();
+        let mut stop_reason = None;
+
+        if self.config.target == 0 {
+            stop_reason = Some(StopReason::TargetReached);
+        }
+
+        while stop_reason.is_none() {
+            let Some(event) = checks.next().await else {
+                break;
+            };
+            progress.checked += 1;
+            match event.outcome {
+                CandidateOutcome::Resolves => progress.resolved += 1,
+                CandidateOutcome::Available => {
+                    found.push(DiscoveredDomain {
+                        domain: event.domain.clone(),
+                        score: event.score,
+                        confirmed: self.config.confirm_with_whois,
+                    });
+                }
+                _ => {}
+            }
+            if self.config.confirm_with_whois && event.outcome != CandidateOutcome::Resolves {
+                progress.confirmed += 1;
+            }
+            progress.found = found.len();
+            progress.generated = generated.load(Ordering::Relaxed);
+            progress.skipped = skipped.load(Ordering::Relaxed);
+            on_event(&progress, &event);
+
+            if found.len() >= self.config.target {
+                stop_reason = Some(StopReason::TargetReached);
+            } else if self
+                .cancel
+                .as_ref()
+                .is_some_and(|flag| flag.load(Ordering::Relaxed))
+            {
+                stop_reason = Some(StopReason::Cancelled);
+            }
+        }
+        drop(checks);
+
+        progress.generated = generated.load(Ordering::Relaxed);
+        progress.skipped = skipped.load(Ordering::Relaxed);
+        let stop_reason = stop_reason.unwrap_or_else(|| {
+            if self.config.max_checks == Some(progress.checked) {
+                StopReason::CheckLimit
+            } else {
+                StopReason::Exhausted
+            }
+        });
+
+        if self.scorer.is_some() {
+            found.sort_by(|a, b| {
+                b.score
+                    .unwrap_or(0.0)
+                    .partial_cmp(&a.score.unwrap_or(0.0))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+        }
+
+        DiscoveryReport {
+            found,
+            progress,
+            stop_reason,
+        }
+    }
+
+    async fn check(&self, domain: &str) -> CandidateOutcome {
+        if self.checker.resolves(domain).await {
+            return CandidateOutcome::Resolves;
+        }
+        if !self.config.confirm_with_whois {
+            return CandidateOutcome::Available;
+        }
+        match self.checker.confirm(domain).await {
+            Ok(CheckVerdict::Available) => CandidateOutcome::Available,
+            Ok(CheckVerdict::Registered) => CandidateOutcome::Registered,
+            Ok(CheckVerdict::Unknown) => CandidateOutcome::Unknown,
+            Err(e) => CandidateOutcome::Error(e),
+        }
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use std::sync::Mutex;
+
+    /// Resolves anything in `dns`, reports anything in `whois` as registered.
+    #[derive(Default)]
+    struct FakeChecker {
+        dns: Vec<&'static str>,
+        whois: Vec<&'static str>,
+        confirmed: Mutex<Vec<String>>,
+    }
+
+    impl AvailabilityChecker for FakeChecker {
+        async fn resolves(&self, domain: &str) -> bool {
+            self.dns.contains(&domain)
+        }
+
+        async fn confirm(&self, domain: &str) -> Result<CheckVerdict, String> {
+            self.confirmed.lock().unwrap().push(domain.to_string());
+            if self.whois.contains(&domain) {
+                Ok(CheckVerdict::Registered)
+            } else {
+                Ok(CheckVerdict::Available)
+            }
+        }
+    }
+
+    fn config(words: &[&str], target: usize) -> DiscoveryConfig {
+        DiscoveryConfig {
+            combinator: CombinatorConfig {
+                words: words.iter().map(|w| w.to_string()).collect(),
+                ..Default::default()
+            },
+            target,
+            concurrency: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_dns_hits_skip_whois() {
+        let checker = FakeChecker {
+            dns: vec!["cloud.com"],
+            whois: vec!["fire.com"],
+            ..Default::default()
+        };
+        let discovery = Discovery::new(config(&["cloud", "fire", "wall"], 10), checker);
+        let report = block_on(discovery.run(|_, _| {}));
+
+        let found: Vec<&str> = report.found.iter().map(|d| d.domain.as_str()).collect();
+        assert!(found.contains(&"wall.com"));
+        assert!(!found.contains(&"cloud.com"));
+        assert!(!found.contains(&"fire.com"));
+        let confirmed = discovery.checker.confirmed.lock().unwrap();
+        assert!(!confirmed.contains(&"cloud.com".to_string()));
+        assert_eq!(report.progress.resolved, 1);
+        assert_eq!(report.stop_reason, StopReason::Exhausted);
+    }
+
+    #[test]
+    fn test_stops_at_target() {
+        let discovery = Discovery::new(
+            config(&["alpha", "bravo", "delta"], 2),
+            FakeChecker::default(),
+        );
+        let mut events = 0;
+        let report = block_on(discovery.run(|_, _| events += 1));
+        assert_eq!(report.found.len(), 2);
+        assert_eq!(events, 2);
+        assert_eq!(report.stop_reason, StopReason::TargetReached);
+        // The combinator would have produced 9 candidates.
+        assert!(report.progress.generated < 9);
+    }
+
+    #[test]
+    fn test_filter_and_limit() {
+        let mut cfg = config(&["ab", "longer"], 10);
+        cfg.filter.min_length = 3;
+        cfg.max_checks = Some(1);
+        let discovery = Discovery::new(cfg, FakeChecker::default());
+        let report = block_on(discovery.run(|_, _| {}));
+        assert_eq!(report.progress.checked, 1);
+        assert_eq!(report.found[0].domain, "longer.com");
+        assert_eq!(report.progress.skipped, 1);
+        assert_eq!(report.stop_reason, StopReason::CheckLimit);
+    }
+
+    #[test]
+    fn test_dns_only_mode() {
+        let mut cfg = config(&["cloud"], 5);
+        cfg.confirm_with_whois = false;
+        let discovery = Discovery::new(cfg, FakeChecker::default());
+        let report = block_on(discovery.run(|_, _| {}));
+        assert_eq!(report.found.len(), 1);
+        assert!(!report.found[0].confirmed);
+        assert!(discovery.checker.confirmed.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_min_score_requires_scorer_and_ranks() {
+        let scorer = DomainScorer::default();
+        let good = scorer.score_domain("cloud.com").total;
+        let bad = scorer.score_domain("xq7zk.com").total;
+        assert!(good > bad);
+        let threshold = (good + bad) / 2.0;
+
+        let mut cfg = config(&["cloud", "xq7zk"], 10);
+        cfg.min_score = Some(threshold);
+        let discovery = Discovery::new(cfg, FakeChecker::default()).with_scorer(scorer);
+        let report = block_on(discovery.run(|_, _| {}));
+        assert!(report.found.iter().any(|d| d.domain == "cloud.com"));
+        assert!(report.found.iter().all(|d| d.score.unwrap() >= threshold));
+        assert!(!report.found.iter().any(|d| d.domain == "xq7zk.com"));
+        assert!(report.progress.skipped > 0);
+    }
+
+    #[test]
+    fn test_cancel_flag() {
+        let flag = Arc::new(AtomicBool::new(true));
+        let discovery = Discovery::new(config(&["one", "two"], 10), FakeChecker::default())
+            .with_cancel_flag(flag);
+        let report = block_on(discovery.run(|_, _| {}));
+        assert_eq!(report.progress.checked, 1);
+        assert_eq!(report.stop_reason, StopReason::Cancelled);
+    }
+}
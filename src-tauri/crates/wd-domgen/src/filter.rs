@@ -61,37 +61,42 @@ impl DomainFilter {
 
         for domain in domains {
             let lower = domain.to_lowercase();
-            let label = extract_label(&lower);
 
             if self.config.dedup && !seen.insert(lower.clone()) {
                 continue;
             }
 
-            if label.len() < self.config.min_length || label.len() > self.config.max_length {
-                continue;
+            if self.accepts(&lower) {
+                out.push(lower);
             }
+        }
 
-            if self.config.ascii_only && !is_valid_domain_label(label) {
-                continue;
-            }
+        out
+    }
 
-            if self.blocklist_set.contains(label) {
-                continue;
-            }
+    /// Check a single domain against every rule except deduplication, which
+    /// needs state across calls. Used by streaming callers such as discovery.
+    pub fn accepts(&self, domain: &str) -> bool {
+        let lower = domain.to_lowercase();
+        let label = extract_label(&lower);
 
-            if self
-                .config
-                .blocked_substrings
-                .iter()
-                .any(|sub| label.contains(sub.as_str()))
-            {
-                continue;
-            }
+        if label.len() < self.config.min_length || label.len() > self.config.max_length {
+            return false;
+        }
 
-            out.push(lower);
+        if self.config.ascii_only && !is_valid_domain_label(label) {
+            return false;
         }
 
-        out
+        if self.blocklist_set.contains(label) {
+            return false;
+        }
+
+        !self
+            .config
+            .blocked_substrings
+            .iter()
+            .any(|sub| label.contains(sub.as_str()))
     }
 
     /// Count how many domains would pass.
@@ -196,4 +201,15 @@ mod tests {
         let input = vec!["a.com".into(), "b.com".into()];
         assert_eq!(f.count_passing(&input), 2);
     }
+
+    #[test]
+    fn test_accepts_single() {
+        let f = DomainFilter::new(FilterConfig {
+            blocklist: vec!["nope".into()],
+            ..Default::default()
+        });
+        assert!(f.accepts("Fine.com"));
+        assert!(!f.accepts("NOPE.com"));
+        assert!(!f.accepts("-x.com"));
+    }
 }
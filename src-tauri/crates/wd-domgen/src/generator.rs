@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::score::ScoredDomain;
+
 /// A generated domain suggestion with metadata.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct GeneratedDomain {
@@ -70,6 +72,20 @@ impl GeneratorEngine {
     /// AI suggestions require a separate async API call — this method returns
     /// a prompt string via `ai_prompt()` that can be sent to an LLM.
     pub fn generate(&self) -> Vec<GeneratedDomain> {
+        let mut results = self.generate_all();
+        results.truncate(self.config.max_results);
+        results
+    }
+
+    /// Generate suggestions and rank them with `scorer`, best first. Ranking
+    /// happens before `max_results` is applied, so the cut keeps the best.
+    pub fn generate_ranked(&self, scorer: &crate::score::DomainScorer) -> Vec<ScoredDomain> {
+        let mut ranked = scorer.rank(self.generate_all());
+        ranked.truncate(self.config.max_results);
+        ranked
+    }
+
+    fn generate_all(&self) -> Vec<GeneratedDomain> {
         let mut results = Vec::new();
 
         // 1. Generate from combinator
@@ -135,7 +151,6 @@ impl GeneratorEngine {
             }
         }
 
-        results.truncate(self.config.max_results);
         results
     }
 
@@ -285,6 +300,22 @@ mod tests {
         assert!(results.len() <= 5);
     }
 
+    #[test]
+    fn test_generate_ranked_sorted_and_limited() {
+        let config = GeneratorConfig {
+            keywords: vec!["cloud".into(), "speed".into()],
+            tlds: vec![".com".into()],
+            max_results: 10,
+            ..Default::default()
+        };
+        let ranked =
+            GeneratorEngine::new(config).generate_ranked(&crate::score::DomainScorer::default());
+        assert_eq!(ranked.len(), 10);
+        assert!(ranked
+            .windows(2)
+            .all(|w| w[0].breakdown.total >= w[1].breakdown.total));
+    }
+
     #[test]
     fn test_custom_ai_prompt_template() {
         let config = GeneratorConfig {
@@ -8,13 +8,22 @@
 //! - **mutator** – character substitution, hyphenation, typosquatting detection
 //! - **generator** – rule-based and AI-prompt domain suggestion
 //! - **filter** – dedup, length, charset, and blocklist filtering
+//! - **score** – pronounceability and brandability ranking
+//! - **discovery** – generate-then-check pipeline that stops after N available names
 
 pub mod combinator;
+pub mod discovery;
 pub mod filter;
 pub mod generator;
 pub mod mutator;
+pub mod score;
 
-pub use combinator::{expand_combinations, CombinatorConfig};
+pub use combinator::{expand_combinations, iter_combinations, CombinatorConfig};
+pub use discovery::{
+    AvailabilityChecker, CandidateOutcome, CheckVerdict, DiscoveredDomain, Discovery,
+    DiscoveryConfig, DiscoveryEvent, DiscoveryProgress, DiscoveryReport, StopReason,
+};
 pub use filter::{DomainFilter, FilterConfig};
 pub use generator::{GeneratedDomain, GeneratorConfig, GeneratorEngine};
 pub use mutator::{mutate_domain, MutationKind, MutatorConfig};
+pub use score::{DomainScorer, ScoreBreakdown, ScoreConfig, ScoreWeights, ScoredDomain};
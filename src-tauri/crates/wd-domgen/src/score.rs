@@ -0,0 +1,564 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+use crate::generator::GeneratedDomain;
+
+/// Embedded English word list used to train the bigram model and as the
+/// dictionary for word detection.
+const ENGLISH_CORPUS: &str = include_str!("corpus/english.txt");
+
+/// Shared model and dictionary, built once on first use.
+static ENGLISH: LazyLock<LanguageModel> = LazyLock::new(|| LanguageModel::train(ENGLISH_CORPUS));
+
+/// Mean log2 transition probability of a typical corpus word; maps to 1.0.
+const PRONOUNCEABLE_BITS: f64 = -3.5;
+/// Mean log2 transition probability of random letter soup; maps to 0.0.
+const UNPRONOUNCEABLE_BITS: f64 = -6.5;
+
+// ─── Configuration ───────────────────────────────────────────────────────────
+
+/// Relative weight of each scoring factor. Weights need not sum to 1.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ScoreWeights {
+    pub pronounceability: f64,
+    pub length: f64,
+    pub dictionary: f64,
+    pub hyphen_digit: f64,
+    pub keyboard: f64,
+    pub tld_fit: f64,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        Self {
+            pronounceability: 0.30,
+            length: 0.20,
+            dictionary: 0.20,
+            hyphen_digit: 0.15,
+            keyboard: 0.05,
+            tld_fit: 0.10,
+        }
+    }
+}
+
+/// Configuration for the domain scorer.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ScoreConfig {
+    pub weights: ScoreWeights,
+    /// Label lengths in this inclusive range get full length score.
+    pub ideal_min_length: usize,
+    pub ideal_max_length: usize,
+    /// TLDs the user prefers; these always score 1.0 for TLD fit.
+    #[serde(default)]
+    pub preferred_tlds: Vec<String>,
+    /// Per-TLD fit overrides (without leading dot).
+    #[serde(default)]
+    pub tld_scores: HashMap<String, f64>,
+}
+
+impl Default for ScoreConfig {
+    fn default() -> Self {
+        Self {
+            weights: ScoreWeights::default(),
+            ideal_min_length: 4,
+            ideal_max_length: 10,
+            preferred_tlds: Vec::new(),
+            tld_scores: HashMap::new(),
+        }
+    }
+}
+
+// ─── Results ─────────────────────────────────────────────────────────────────
+
+/// Per-factor scores (each 0.0 – 1.0) and the weighted total.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ScoreBreakdown {
+    pub pronounceability: f64,
+    pub length: f64,
+    pub dictionary: f64,
+    pub hyphen_digit: f64,
+    pub keyboard: f64,
+    pub tld_fit: f64,
+    /// Weighted combination of the factors above.
+    pub total: f64,
+    /// Dictionary words found in the label, in order.
+    #[serde(default)]
+    pub words: Vec<String>,
+}
+
+/// A generated domain together with its score breakdown.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ScoredDomain {
+    #[serde(flatten)]
+    pub domain: GeneratedDomain,
+    pub breakdown: ScoreBreakdown,
+}
+
+// ─── Scorer ──────────────────────────────────────────────────────────────────
+
+/// Rates domain labels on pronounceability and brandability.
+pub struct DomainScorer {
+    config: ScoreConfig,
+}
+
+impl Default for DomainScorer {
+    fn default() -> Self {
+        Self::new(ScoreConfig::default())
+    }
+}
+
+impl DomainScorer {
+    pub fn new(config: ScoreConfig) -> Self {
+        Self { config }
+    }
+
+    /// Score a full domain name (`label.tld`).
+    pub fn score_domain(&self, domain: &str) -> ScoreBreakdown {
+        let lower = domain.trim().trim_end_matches('.').to_lowercase();
+        let (label, tld) = match lower.split_once('.') {
+            Some((label, tld)) => (label, tld),
+            None => (lower.as_str(), ""),
+        };
+        self.score_label(label, tld)
+    }
+
+    /// Score a label against the TLD it would be registered under.
+    pub fn score_label(&self, label: &str, tld: &str) -> ScoreBreakdown {
+        let label = label.to_lowercase();
+        let (dictionary, words) = dictionary_score(&label);
+        let mut b = ScoreBreakdown {
+            pronounceability: pronounceability(&label),
+            length: self.length_score(label.chars().count()),
+            dictionary,
+            hyphen_digit: hyphen_digit_score(&label),
+            keyboard: keyboard_ease(&label),
+            tld_fit: self.tld_fit(tld),
+            total: 0.0,
+            words,
+        };
+        let w = &self.config.weights;
+        let weight_sum =
+            w.pronounceability + w.length + w.dictionary + w.hyphen_digit + w.keyboard + w.tld_fit;
+        if weight_sum > 0.0 {
+            b.total = (b.pronounceability * w.pronounceability
+                + b.length * w.length
+                + b.dictionary * w.dictionary
+                + b.hyphen_digit * w.hyphen_digit
+                + b.keyboard * w.keyboard
+                + b.tld_fit * w.tld_fit)
+                / weight_sum;
+        }
+        b
+    }
+
+    /// Score and sort generated domains, best first. Each domain's `score`
+    /// is set to the combined total.
+    pub fn rank(&self, domains: Vec<GeneratedDomain>) -> Vec<ScoredDomain> {
+        let mut scored: Vec<ScoredDomain> = domains
+            .into_iter()
+            .map(|mut domain| {
+                let breakdown = self.score_domain(&domain.domain);
+                domain.score = Some(breakdown.total);
+                ScoredDomain { domain, breakdown }
+            })
+            .collect();
+        scored.sort_by(|a, b| {
+            b.breakdown
+                .total
+                .partial_cmp(&a.breakdown.total)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.domain.domain.cmp(&b.domain.domain))
+        });
+        scored
+    }
+
+    /// Score and sort plain domain strings, e.g. from `expand_combinations`
+    /// or `mutate_domain`, best first.
+    pub fn rank_names(&self, domains: &[String]) -> Vec<(String, ScoreBreakdown)> {
+        let mut scored: Vec<(String, ScoreBreakdown)> = domains
+            .iter()
+            .map(|d| (d.clone(), self.score_domain(d)))
+            .collect();
+        scored.sort_by(|a, b| {
+            b.1.total
+                .partial_cmp(&a.1.total)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        scored
+    }
+
+    fn length_score(&self, len: usize) -> f64 {
+        let (lo, hi) = (self.config.ideal_min_length, self.config.ideal_max_length);
+        if len == 0 {
+            0.0
+        } else if len < lo {
+            len as f64 / lo as f64
+        } else if len <= hi {
+            1.0
+        } else {
+            // Lose all length credit 15 characters past the ideal maximum.
+            (1.0 - (len - hi) as f64 / 15.0).max(0.0)
+        }
+    }
+
+    fn tld_fit(&self, tld: &str) -> f64 {
+        let tld = tld.trim_start_matches('.');
+        if tld.is_empty() {
+            return 0.5;
+        }
+        if self
+            .config
+            .preferred_tlds
+            .iter()
+            .any(|p| p.trim_start_matches('.').eq_ignore_ascii_case(tld))
+        {
+            return 1.0;
+        }
+        if let Some(score) = self.config.tld_scores.get(tld) {
+            return score.clamp(0.0, 1.0);
+        }
+        match tld {
+            "com" => 1.0,
+            "io" | "ai" | "co" | "app" | "dev" => 0.85,
+            "net" | "org" => 0.75,
+            "xyz" | "info" | "biz" | "top" => 0.35,
+            _ if tld.contains('.') => 0.5, // e.g. co.uk
+            _ if tld.len() == 2 => 0.6,    // other ccTLDs
+            _ => 0.55,
+        }
+    }
+}
+
+// ─── Factors ─────────────────────────────────────────────────────────────────
+
+/// Pronounceability from the bigram model, averaged over the alphabetic runs
+/// of the label (digits and hyphens split runs).
+pub fn pronounceability(label: &str) -> f64 {
+    let runs: Vec<&str> = label
+        .split(|c: char| !c.is_ascii_lowercase())
+        .filter(|s| !s.is_empty())
+        .collect();
+    let total_len: usize = runs.iter().map(|r| r.len()).sum();
+    if total_len == 0 {
+        return 0.0;
+    }
+    runs.iter()
+        .map(|r| ENGLISH.pronounceability(r) * r.len() as f64)
+        .sum::<f64>()
+        / total_len as f64
+}
+
+/// Dictionary coverage: how much of the label segments into known words,
+/// discounted when it takes many words to do so.
+fn dictionary_score(label: &str) -> (f64, Vec<String>) {
+    let letters: String = label.chars().filter(|c| c.is_ascii_lowercase()).collect();
+    if letters.is_empty() {
+        return (0.0, vec![]);
+    }
+    let words = ENGLISH.segment(&letters);
+    let covered: usize = words.iter().map(|w| w.len()).sum();
+    let coverage = covered as f64 / letters.len() as f64;
+    let discount = match words.len() {
+        0 => 0.0,
+        1 => 1.0,
+        2 => 0.9,
+        3 => 0.75,
+        _ => 0.6,
+    };
+    (coverage * discount, words)
+}
+
+fn hyphen_digit_score(label: &str) -> f64 {
+    let hyphens = label.chars().filter(|c| *c == '-').count() as f64;
+    let digits = label.chars().filter(|c| c.is_ascii_digit()).count() as f64;
+    (1.0 - hyphens * 0.3 - digits * 0.15).max(0.0)
+}
+
+/// QWERTY typing ease: share of consecutive letter pairs that avoid
+/// same-finger repeats and same-hand jumps between the top and bottom rows.
+fn keyboard_ease(label: &str) -> f64 {
+    let keys: Vec<(usize, usize)> = label.chars().filter_map(qwerty_position).collect();
+    if keys.len() < 2 {
+        return 1.0;
+    }
+    let pairs = keys.len() - 1;
+    let awkward = keys
+        .windows(2)
+        .filter(|w| {
+            let ((r1, c1), (r2, c2)) = (w[0], w[1]);
+            if (r1, c1) == (r2, c2) {
+                return false;
+            }
+            let same_finger = finger(c1) == finger(c2);
+            let same_hand = (c1 < 5) == (c2 < 5);
+            same_finger || (same_hand && r1.abs_diff(r2) == 2)
+        })
+        .count();
+    1.0 - awkward as f64 / pairs as f64
+}
+
+fn qwerty_position(c: char) -> Option<(usize, usize)> {
+    const ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+    ROWS.iter()
+        .enumerate()
+        .find_map(|(row, keys)| keys.find(c).map(|col| (row, col)))
+}
+
+/// Touch-typing finger for a column (index fingers cover two columns each).
+fn finger(col: usize) -> usize {
+    match col {
+        0..=2 => col,
+        3 | 4 => 3,
+        5 | 6 => 4,
+        _ => col - 2,
+    }
+}
+
+// ─── Language model ──────────────────────────────────────────────────────────
+
+const ALPHABET: usize = 26;
+const BOUNDARY: usize = ALPHABET;
+
+/// Character bigram model with word-boundary markers, plus a dictionary.
+struct LanguageModel {
+    /// log2 P(next | prev), add-k smoothed; index 26 is the word boundary.
+    log_probs: Vec<[f64; ALPHABET + 1]>,
+    dictionary: HashSet<String>,
+    longest_word: usize,
+}
+
+impl LanguageModel {
+    fn train(corpus: &str) -> Self {
+        const K: f64 = 0.5;
+        let mut counts = vec![[0u32; ALPHABET + 1]; ALPHABET + 1];
+        let mut dictionary = HashSet::new();
+        let mut longest_word = 0;
+        for word in corpus.split_whitespace() {
+            let word = word.to_lowercase();
+            if !word.chars().all(|c| c.is_ascii_lowercase()) {
+                continue;
+            }
+            let mut prev = BOUNDARY;
+            for idx in word.bytes().map(|b| (b - b'a') as usize) {
+                counts[prev][idx] += 1;
+                prev = idx;
+            }
+            counts[prev][BOUNDARY] += 1;
+            longest_word = longest_word.max(word.len());
+            dictionary.insert(word);
+        }
+        let log_probs = counts
+            .iter()
+            .map(|row| {
+                let total: u32 = row.iter().sum();
+                let mut out = [0.0; ALPHABET + 1];
+                for (o, &c) in out.iter_mut().zip(row.iter()) {
+                    *o = ((c as f64 + K) / (total as f64 + K * (ALPHABET + 1) as f64)).log2();
+                }
+                out
+            })
+            .collect();
+        Self {
+            log_probs,
+            dictionary,
+            longest_word,
+        }
+    }
+
+    /// 0.0 – 1.0 pronounceability of a run of lowercase ASCII letters.
+    fn pronounceability(&self, run: &str) -> f64 {
+        let mut prev = BOUNDARY;
+        let mut sum = 0.0;
+        for idx in run.bytes().map(|b| (b - b'a') as usize) {
+            sum += self.log_probs[prev][idx];
+            prev = idx;
+        }
+        sum += self.log_probs[prev][BOUNDARY];
+        let mean = sum / (run.len() + 1) as f64;
+        let base = ((mean - UNPRONOUNCEABLE_BITS) / (PRONOUNCEABLE_BITS - UNPRONOUNCEABLE_BITS))
+            .clamp(0.0, 1.0);
+        base * cluster_penalty(run)
+    }
+
+    /// Split letters into dictionary words, minimising the number of pieces
+    /// and treating unmatched letters as costly single-character pieces.
+    /// Returns only the dictionary words.
+    fn segment(&self, letters: &str) -> Vec<String> {
+        const UNKNOWN_COST: usize = 3;
+        let n = letters.len();
+        let mut best: Vec<Option<(usize, usize, bool)>> = vec![None; n + 1];
+        best[0] = Some((0, 0, false));
+        for end in 1..=n {
+            for start in end.saturating_sub(self.longest_word)..end {
+                let Some((cost, _, _)) = best[start] else {
+                    continue;
+                };
+                let piece = &letters[start..end];
+                let is_word = piece.len() >= 2 && self.dictionary.contains(piece);
+                let step = if is_word {
+                    1
+                } else if piece.len() == 1 {
+                    UNKNOWN_COST
+                } else {
+                    continue;
+                };
+                if best[end].is_none_or(|(c, _, _)| cost + step < c) {
+                    best[end] = Some((cost + step, start, is_word));
+                }
+            }
+        }
+        let mut words = vec![];
+        let mut end = n;
+        while end > 0 {
+            let Some((_, start, is_word)) = best[end] else {
+                break;
+            };
+            if is_word {
+                words.push(letters[start..end].to_string());
+            }
+            end = start;
+        }
+        words.reverse();
+        words
+    }
+}
+
+/// Penalise long consonant clusters and vowel-less runs, which the bigram
+/// model alone scores too kindly (e.g. "strngth").
+fn cluster_penalty(run: &str) -> f64 {
+    let is_vowel = |c: char| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+    if run.len() >= 3 && !run.chars().any(is_vowel) {
+        return 0.3;
+    }
+    let mut longest = 0;
+    let mut current = 0;
+    for c in run.chars() {
+        if is_vowel(c) {
+            current = 0;
+        } else {
+            current += 1;
+            longest = longest.max(current);
+        }
+    }
+    if longest >= 4 {
+        (1.0 - 0.15 * (longest - 3) as f64).max(0.3)
+    } else {
+        1.0
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::GenerationSource;
+
+    #[test]
+    fn test_pronounceable_beats_random() {
+        assert!(pronounceability("brandly") > 0.8);
+        assert!(pronounceability("getcloud") > pronounceability("xkcdq"));
+        assert!(pronounceability("xkcdq") < 0.3);
+        assert!(pronounceability("mnbvcx") < 0.2);
+    }
+
+    #[test]
+    fn test_digits_only_not_pronounceable() {
+        assert_eq!(pronounceability("1234"), 0.0);
+    }
+
+    #[test]
+    fn test_dictionary_segmentation() {
+        let (score, words) = dictionary_score("cloudhub");
+        assert_eq!(words, vec!["cloud", "hub"]);
+        assert!((score - 0.9).abs() < 1e-9);
+
+        let (single, words) = dictionary_score("rocket");
+        assert_eq!(words, vec!["rocket"]);
+        assert_eq!(single, 1.0);
+
+        let (none, words) = dictionary_score("qzx");
+        assert!(words.is_empty());
+        assert_eq!(none, 0.0);
+    }
+
+    #[test]
+    fn test_hyphen_digit_penalty() {
+        assert_eq!(hyphen_digit_score("cloud"), 1.0);
+        assert!(hyphen_digit_score("cloud-hub") < 1.0);
+        assert!(hyphen_digit_score("cloud-hub-24") < hyphen_digit_score("cloud-hub"));
+    }
+
+    #[test]
+    fn test_keyboard_ease() {
+        // "ded" uses the same finger for every key change.
+        assert!(keyboard_ease("ded") < keyboard_ease("fork"));
+        assert_eq!(keyboard_ease("a"), 1.0);
+    }
+
+    #[test]
+    fn test_length_score() {
+        let scorer = DomainScorer::default();
+        assert_eq!(scorer.length_score(6), 1.0);
+        assert!(scorer.length_score(2) < 1.0);
+        assert!(scorer.length_score(30) < scorer.length_score(12));
+    }
+
+    #[test]
+    fn test_tld_fit() {
+        let scorer = DomainScorer::new(ScoreConfig {
+            preferred_tlds: vec![".dev".into()],
+            ..Default::default()
+        });
+        assert_eq!(scorer.score_domain("a.com").tld_fit, 1.0);
+        assert_eq!(scorer.score_domain("a.dev").tld_fit, 1.0);
+        assert!(scorer.score_domain("a.xyz").tld_fit < 0.5);
+    }
+
+    #[test]
+    fn test_rank_orders_by_total() {
+        let make = |d: &str| GeneratedDomain {
+            domain: d.into(),
+            source: GenerationSource::Combinator,
+            score: None,
+            tags: vec![],
+        };
+        let ranked = DomainScorer::default().rank(vec![
+            make("xq-7z9k.xyz"),
+            make("cloudhub.com"),
+            make("cloudhub.xyz"),
+        ]);
+        assert_eq!(ranked[0].domain.domain, "cloudhub.com");
+        assert_eq!(ranked[2].domain.domain, "xq-7z9k.xyz");
+        assert!(ranked
+            .windows(2)
+            .all(|w| w[0].breakdown.total >= w[1].breakdown.total));
+        assert_eq!(ranked[0].domain.score, Some(ranked[0].breakdown.total));
+    }
+
+    #[test]
+    fn test_rank_names() {
+        let ranked = DomainScorer::default()
+            .rank_names(&["zzqx.com".to_string(), "sunrise.com".to_string()]);
+        assert_eq!(ranked[0].0, "sunrise.com");
+        assert_eq!(ranked[0].1.words, vec!["sun", "rise"]);
+    }
+
+    #[test]
+    fn test_zero_weights_total_is_zero() {
+        let scorer = DomainScorer::new(ScoreConfig {
+            weights: ScoreWeights {
+                pronounceability: 0.0,
+                length: 0.0,
+                dictionary: 0.0,
+                hyphen_digit: 0.0,
+                keyboard: 0.0,
+                tld_fit: 0.0,
+            },
+            ..Default::default()
+        });
+        assert_eq!(scorer.score_domain("cloud.com").total, 0.0);
+    }
+}
@@ -0,0 +1,653 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use wd_registrar::{CartItem, ShoppingCart};
+
+use crate::domain::DomainExpiry;
+use crate::dropcatch::{DropEstimate, DropStrategy};
+
+// ─── Clock ───────────────────────────────────────────────────────────────────
+
+/// Time source for the catcher. Swapping in [`FakeClock`] turns a run that
+/// would take days into an instant simulation.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Resolve once `now() >= at`.
+    fn sleep_until(&self, at: DateTime<Utc>) -> impl Future<Output = ()> + Send;
+}
+
+/// Wall-clock time backed by tokio timers.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    async fn sleep_until(&self, at: DateTime<Utc>) {
+        if let Ok(wait) = (at - Utc::now()).to_std() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// Manually driven clock. `sleep_until` jumps straight to the target time.
+#[derive(Clone, Debug)]
+pub struct FakeClock {
+    now: Arc<Mutex<DateTime<Utc>>>,
+}
+
+impl FakeClock {
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            now: Arc::new(Mutex::new(start)),
+        }
+    }
+
+    pub fn set(&self, at: DateTime<Utc>) {
+        *self.now.lock().unwrap() = at;
+    }
+
+    pub fn advance(&self, by: Duration) {
+        *self.now.lock().unwrap() += by;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+
+    async fn sleep_until(&self, at: DateTime<Utc>) {
+        let mut now = self.now.lock().unwrap();
+        if at > *now {
+            *now = at;
+        }
+    }
+}
+
+// ─── Probing ─────────────────────────────────────────────────────────────────
+
+/// Which lookup detected a domain as available.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProbeMethod {
+    Whois,
+    Rdap,
+    Dns,
+}
+
+/// Result of a single availability probe.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProbeOutcome {
+    Registered,
+    Available(ProbeMethod),
+    Error(String),
+}
+
+/// Availability check used on every poll (WHOIS, RDAP, DNS or a mix).
+pub trait DropProbe: Sync {
+    fn probe(&self, domain: &str) -> impl Future<Output = ProbeOutcome> + Send;
+}
+
+// ─── Actions ─────────────────────────────────────────────────────────────────
+
+/// Runs when a watched domain is detected as available.
+pub trait CatchAction: Send + Sync {
+    fn name(&self) -> &str;
+    /// Returns a short human-readable result on success.
+    fn on_caught(&self, domain: &str, via: ProbeMethod) -> Result<String, String>;
+}
+
+/// Calls a closure, e.g. to raise a desktop notification.
+pub struct NotifyAction<F> {
+    notify: F,
+}
+
+impl<F: Fn(&str, ProbeMethod) + Send + Sync> NotifyAction<F> {
+    pub fn new(notify: F) -> Self {
+        Self { notify }
+    }
+}
+
+impl<F: Fn(&str, ProbeMethod) + Send + Sync> CatchAction for NotifyAction<F> {
+    fn name(&self) -> &str {
+        "notify"
+    }
+
+    fn on_caught(&self, domain: &str, via: ProbeMethod) -> Result<String, String> {
+        (self.notify)(domain, via);
+        Ok(format!("notified for {}", domain))
+    }
+}
+
+/// Adds the caught domain to a shared shopping cart as a registration.
+pub struct AddToCartAction {
+    cart: Arc<Mutex<ShoppingCart>>,
+    registrar_id: String,
+    years: u32,
+}
+
+impl AddToCartAction {
+    pub fn new(cart: Arc<Mutex<ShoppingCart>>, registrar_id: impl Into<String>) -> Self {
+        Self {
+            cart,
+            registrar_id: registrar_id.into(),
+            years: 1,
+        }
+    }
+
+    pub fn with_years(mut self, years: u32) -> Self {
+        self.years = years;
+        self
+    }
+}
+
+impl CatchAction for AddToCartAction {
+    fn name(&self) -> &str {
+        "add_to_cart"
+    }
+
+    fn on_caught(&self, domain: &str, _via: ProbeMethod) -> Result<String, String> {
+        let mut cart = self.cart.lock().map_err(|e| e.to_string())?;
+        cart.add(CartItem::register(domain, &self.registrar_id, self.years));
+        Ok(format!("added to cart ({} items)", cart.len()))
+    }
+}
+
+/// Adapter for a registrar's registration API.
+pub trait RegistrarAdapter: Send + Sync {
+    fn registrar_id(&self) -> &str;
+    /// Attempt the registration; returns an order or transaction reference.
+    fn register(&self, domain: &str, years: u32) -> Result<String, String>;
+}
+
+/// Registers the caught domain immediately through a [`RegistrarAdapter`].
+pub struct RegisterAction<A> {
+    adapter: A,
+    years: u32,
+}
+
+impl<A: RegistrarAdapter> RegisterAction<A> {
+    pub fn new(adapter: A) -> Self {
+        Self { adapter, years: 1 }
+    }
+
+    pub fn with_years(mut self, years: u32) -> Self {
+        self.years = years;
+        self
+    }
+}
+
+impl<A: RegistrarAdapter> CatchAction for RegisterAction<A> {
+    fn name(&self) -> &str {
+        "register"
+    }
+
+    fn on_caught(&self, domain: &str, _via: ProbeMethod) -> Result<String, String> {
+        self.adapter.register(domain, self.years).map(|reference| {
+            format!(
+                "registered via {}: {}",
+                self.adapter.registrar_id(),
+                reference
+            )
+        })
+    }
+}
+
+// ─── Engine ──────────────────────────────────────────────────────────────────
+
+/// Polling window around the estimated drop.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CatchConfig {
+    pub strategy: DropStrategy,
+    /// Start polling this many hours before the earliest estimated drop.
+    pub lead_hours: i64,
+    /// Keep polling this many hours past the latest estimated drop.
+    pub grace_hours: i64,
+}
+
+impl Default for CatchConfig {
+    fn default() -> Self {
+        Self {
+            strategy: DropStrategy::default(),
+            lead_hours: 24,
+            grace_hours: 48,
+        }
+    }
+}
+
+/// Lifecycle of a catch target.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CatchState {
+    /// Drop window not open yet.
+    Waiting,
+    /// Inside the window, polling at the strategy interval.
+    Polling,
+    Caught,
+    /// Window closed without the domain becoming available.
+    Missed,
+    /// Passive strategy: alert raised, no polling.
+    Alerted,
+}
+
+/// A domain being watched for its drop.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CatchTarget {
+    pub estimate: DropEstimate,
+    pub state: CatchState,
+    pub next_poll: DateTime<Utc>,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+}
+
+impl CatchTarget {
+    fn is_finished(&self) -> bool {
+        matches!(
+            self.state,
+            CatchState::Caught | CatchState::Missed | CatchState::Alerted
+        )
+    }
+}
+
+/// Outcome of a single action run.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ActionResult {
+    pub action: String,
+    pub ok: bool,
+    pub message: String,
+}
+
+/// What happened on a tick.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CatchEventKind {
+    /// Polled and still registered (or the probe failed).
+    Polled {
+        attempt: u32,
+        error: Option<String>,
+    },
+    Caught {
+        via: ProbeMethod,
+        actions: Vec<ActionResult>,
+    },
+    Missed {
+        attempts: u32,
+    },
+    /// Passive strategy reached the drop window.
+    Alert,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CatchEvent {
+    pub domain: String,
+    pub at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub kind: CatchEventKind,
+}
+
+/// Drop-catch engine: polls each target during its drop window at the
+/// interval its [`DropEstimate`] recommends and fires the configured
+/// actions once the domain becomes available.
+pub struct DropCatcher<P, C = SystemClock> {
+    probe: P,
+    clock: C,
+    config: CatchConfig,
+    actions: Vec<Box<dyn CatchAction>>,
+    targets: Vec<CatchTarget>,
+}
+
+impl<P: DropProbe> DropCatcher<P, SystemClock> {
+    pub fn new(probe: P) -> Self {
+        Self::with_clock(probe, SystemClock)
+    }
+}
+
+impl<P: DropProbe, C: Clock> DropCatcher<P, C> {
+    /// Build an engine on a custom clock, e.g. [`FakeClock`] for simulation.
+    pub fn with_clock(probe: P, clock: C) -> Self {
+        Self {
+            probe,
+            clock,
+            config: CatchConfig::default(),
+            actions: Vec::new(),
+            targets: Vec::new(),
+        }
+    }
+
+    pub fn with_config(mut self, config: CatchConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn with_action(mut self, action: impl CatchAction + 'static) -> Self {
+        self.actions.push(Box::new(action));
+        self
+    }
+
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    pub fn targets(&self) -> &[CatchTarget] {
+        &self.targets
+    }
+
+    pub fn target(&self, domain: &str) -> Option<&CatchTarget> {
+        self.targets.iter().find(|t| t.estimate.domain == domain)
+    }
+
+    /// Watch a domain using its expiry record. Returns `false` when no drop
+    /// date can be estimated.
+    pub fn watch(&mut self, expiry: &DomainExpiry) -> bool {
+        let now = self.clock.now();
+        match DropEstimate::from_expiry_at(expiry, self.config.strategy.clone(), now) {
+            Some(estimate) => {
+                self.watch_estimate(estimate);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Watch a precomputed estimate, replacing any target for the same domain.
+    pub fn watch_estimate(&mut self, estimate: DropEstimate) {
+        self.unwatch(&estimate.domain);
+        let window_start = estimate.earliest_drop - Duration::hours(self.config.lead_hours);
+        self.targets.push(CatchTarget {
+            next_poll: window_start.max(self.clock.now()),
+            estimate,
+            state: CatchState::Waiting,
+            attempts: 0,
+            last_error: None,
+        });
+    }
+
+    pub fn unwatch(&mut self, domain: &str) -> bool {
+        let before = self.targets.len();
+        self.targets.retain(|t| t.estimate.domain != domain);
+        self.targets.len() < before
+    }
+
+    /// Earliest time any unfinished target needs attention.
+    pub fn next_wake(&self) -> Option<DateTime<Utc>> {
+        self.targets
+            .iter()
+            .filter(|t| !t.is_finished())
+            .map(|t| t.next_poll)
+            .min()
+    }
+
+    /// Poll every target that is due at the clock's current time.
+    pub async fn tick(&mut self) -> Vec<CatchEvent> {
+        let now = self.clock.now();
+        let mut events = Vec::new();
+
+        for i in 0..self.targets.len() {
+            let target = &self.targets[i];
+            if target.is_finished() || target.next_poll > now {
+                continue;
+            }
+            let window_end = target.estimate.latest_drop + Duration::hours(self.config.grace_hours);
+            let domain = target.estimate.domain.clone();
+
+            if now > window_end {
+                let target = &mut self.targets[i];
+                target.state = CatchState::Missed;
+                events.push(CatchEvent {
+                    domain,
+                    at: now,
+                    kind: CatchEventKind::Missed {
+                        attempts: target.attempts,
+                    },
+                });
+                continue;
+            }
+
+            if target.estimate.strategy == DropStrategy::PassiveAlert {
+                self.targets[i].state = CatchState::Alerted;
+                events.push(CatchEvent {
+                    domain,
+                    at: now,
+                    kind: CatchEventKind::Alert,
+                });
+                continue;
+            }
+
+            let outcome = self.probe.probe(&domain).await;
+            let target = &mut self.targets[i];
+            target.attempts += 1;
+            target.estimate.refresh(now);
+
+            let kind = match outcome {
+                ProbeOutcome::Available(via) => {
+                    target.state = CatchState::Caught;
+                    target.last_error = None;
+                    let actions = self
+                        .actions
+                        .iter()
+                        .map(|action| {
+                            let result = action.on_caught(&domain, via);
+                            if let Err(e) = &result {
+                                log::warn!(
+                                    "drop-catch action {} failed for {}: {}",
+                                    action.name(),
+                                    domain,
+                                    e
+                                );
+                            }
+                            ActionResult {
+                                action: action.name().to_string(),
+                                ok: result.is_ok(),
+                                message: result.unwrap_or_else(|e| e),
+                            }
+                        })
+                        .collect();
+                    CatchEventKind::Caught { via, actions }
+                }
+                ProbeOutcome::Registered | ProbeOutcome::Error(_) => {
+                    let error = match outcome {
+                        ProbeOutcome::Error(e) => Some(e),
+                        _ => None,
+                    };
+                    target.state = CatchState::Polling;
+                    target.last_error = error.clone();
+                    let interval = target.estimate.current_interval_secs().max(1);
+                    target.next_poll = now + Duration::seconds(interval as i64);
+                    CatchEventKind::Polled {
+                        attempt: target.attempts,
+                        error,
+                    }
+                }
+            };
+            events.push(CatchEvent {
+                domain,
+                at: now,
+                kind,
+            });
+        }
+
+        events
+    }
+
+    /// Sleep until each target is due and poll it, until every target is
+    /// caught, missed or alerted. `on_event` sees events as they happen.
+    pub async fn run<F: FnMut(&CatchEvent)>(&mut self, mut on_event: F) {
+        while let Some(wake) = self.next_wake() {
+            self.clock.sleep_until(wake).await;
+            for event in self.tick().await {
+                on_event(&event);
+            }
+        }
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn utc(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, 0, 0, 0).unwrap()
+    }
+
+    /// Reports the domain as registered until `drops_at`, then available.
+    struct ScriptedProbe {
+        clock: FakeClock,
+        drops_at: Option<DateTime<Utc>>,
+        polls: Mutex<Vec<DateTime<Utc>>>,
+    }
+
+    impl DropProbe for ScriptedProbe {
+        async fn probe(&self, _domain: &str) -> ProbeOutcome {
+            let now = self.clock.now();
+            self.polls.lock().unwrap().push(now);
+            match self.drops_at {
+                Some(at) if now >= at => ProbeOutcome::Available(ProbeMethod::Rdap),
+                _ => ProbeOutcome::Registered,
+            }
+        }
+    }
+
+    struct FakeRegistrar;
+
+    impl RegistrarAdapter for FakeRegistrar {
+        fn registrar_id(&self) -> &str {
+            "fake"
+        }
+
+        fn register(&self, domain: &str, _years: u32) -> Result<String, String> {
+            Ok(format!("order-{}", domain))
+        }
+    }
+
+    // Default grace is 65 days: expiry 2026-01-01 drops 2026-03-07.
+    fn expiry(domain: &str) -> DomainExpiry {
+        DomainExpiry::compute(domain, Some(utc(2026, 1, 1)), None, utc(2026, 1, 1))
+    }
+
+    fn catcher(drops_at: Option<DateTime<Utc>>) -> DropCatcher<ScriptedProbe, FakeClock> {
+        let clock = FakeClock::new(utc(2026, 3, 1));
+        let probe = ScriptedProbe {
+            clock: clock.clone(),
+            drops_at,
+            polls: Mutex::new(Vec::new()),
+        };
+        DropCatcher::with_clock(probe, clock)
+    }
+
+    #[tokio::test]
+    async fn test_waits_for_window() {
+        let mut c = catcher(None);
+        assert!(c.watch(&expiry("wait.com")));
+        assert!(c.tick().await.is_empty());
+        assert_eq!(c.next_wake(), Some(utc(2026, 3, 6)));
+        assert_eq!(c.target("wait.com").unwrap().state, CatchState::Waiting);
+    }
+
+    #[tokio::test]
+    async fn test_simulated_catch_runs_actions() {
+        let drop_time = utc(2026, 3, 7) + Duration::minutes(90);
+        let cart = Arc::new(Mutex::new(ShoppingCart::new()));
+        let notified = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&notified);
+
+        let mut c = catcher(Some(drop_time))
+            .with_config(CatchConfig {
+                strategy: DropStrategy::Escalating {
+                    normal_interval_secs: 3600,
+                    hot_interval_secs: 600,
+                },
+                ..Default::default()
+            })
+            .with_action(NotifyAction::new(move |d: &str, _| {
+                sink.lock().unwrap().push(d.to_string())
+            }))
+            .with_action(AddToCartAction::new(Arc::clone(&cart), "porkbun"))
+            .with_action(RegisterAction::new(FakeRegistrar));
+        c.watch(&expiry("drop.com"));
+
+        let mut events = Vec::new();
+        c.run(|e| events.push(e.clone())).await;
+
+        let target = c.target("drop.com").unwrap();
+        assert_eq!(target.state, CatchState::Caught);
+        // Caught within one hot interval of the real drop.
+        let now = c.clock().now();
+        assert!(now >= drop_time && now < drop_time + Duration::minutes(10));
+        // Hot-zone polling every 10 minutes from the window start.
+        let polls = c.probe.polls.lock().unwrap();
+        assert_eq!(polls[1] - polls[0], Duration::minutes(10));
+
+        let Some(CatchEventKind::Caught { via, actions }) = events.last().map(|e| &e.kind) else {
+            panic!("expected a catch event");
+        };
+        assert_eq!(*via, ProbeMethod::Rdap);
+        assert_eq!(actions.len(), 3);
+        assert!(actions.iter().all(|a| a.ok));
+        assert_eq!(actions[2].message, "registered via fake: order-drop.com");
+        assert_eq!(notified.lock().unwrap().as_slice(), ["drop.com"]);
+        assert_eq!(cart.lock().unwrap().items[0].registrar_id, "porkbun");
+    }
+
+    #[tokio::test]
+    async fn test_missed_after_window() {
+        let mut c = catcher(None).with_config(CatchConfig {
+            strategy: DropStrategy::Polling {
+                interval_secs: 6 * 3600,
+            },
+            ..Default::default()
+        });
+        c.watch(&expiry("never.com"));
+
+        let mut last = None;
+        c.run(|e| last = Some(e.clone())).await;
+
+        assert_eq!(c.target("never.com").unwrap().state, CatchState::Missed);
+        assert!(matches!(
+            last.map(|e| e.kind),
+            Some(CatchEventKind::Missed { attempts }) if attempts > 0
+        ));
+        // Window: 2026-03-06 → latest drop 2026-03-09 + 48h.
+        assert!(c.clock().now() > utc(2026, 3, 11));
+    }
+
+    #[tokio::test]
+    async fn test_passive_alert_does_not_poll() {
+        let mut c = catcher(None).with_config(CatchConfig {
+            strategy: DropStrategy::PassiveAlert,
+            ..Default::default()
+        });
+        c.watch(&expiry("passive.com"));
+        let mut events = Vec::new();
+        c.run(|e| events.push(e.clone())).await;
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0].kind, CatchEventKind::Alert));
+        assert!(c.probe.polls.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_watch_replaces_and_unwatch() {
+        let mut c = catcher(None);
+        c.watch(&expiry("dup.com"));
+        c.watch(&expiry("dup.com"));
+        assert_eq!(c.targets().len(), 1);
+        assert!(c.unwatch("dup.com"));
+        assert!(c.next_wake().is_none());
+        assert!(!c.watch(&DomainExpiry::compute(
+            "none.com",
+            None,
+            None,
+            utc(2026, 1, 1)
+        )));
+    }
+}
@@ -1,6 +1,25 @@
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::lifecycle::{EppStatus, PhaseBasis, PhaseEvidence};
+
+/// ccTLD grace policies, as (tld, auto-renew, redemption, pending delete)
+/// days. ccTLD registries set these themselves, so they override the
+/// registrar profiles; gTLDs follow the ICANN defaults and are not listed.
+const TLD_GRACE: &[(&str, i64, i64, i64)] = &[
+    // Nominet suspends 30 days after expiry and cancels at day 92.
+    ("uk", 30, 60, 2),
+    // Registries that delete at expiry into a redemption or quarantine phase.
+    ("de", 0, 30, 0),
+    ("fr", 0, 30, 0),
+    ("it", 0, 30, 5),
+    ("eu", 0, 40, 0),
+    ("nl", 0, 40, 0),
+    ("be", 0, 40, 0),
+    ("ch", 0, 40, 0),
+    ("li", 0, 40, 0),
+];
+
 /// The lifecycle phase of a domain with respect to expiry.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -69,6 +88,23 @@ impl GracePeriod {
             _ => Self::default(),
         }
     }
+
+    /// The registry-mandated grace period for `tld`, if it has its own.
+    pub fn for_tld(tld: &str) -> Option<Self> {
+        let tld = tld.trim_start_matches('.').to_ascii_lowercase();
+        TLD_GRACE
+            .iter()
+            .find(|(t, ..)| *t == tld)
+            .map(|&(_, ar, rd, pd)| Self::custom(ar, rd, pd))
+    }
+
+    /// The grace period for `domain`: its TLD's policy if there is one,
+    /// otherwise the registrar's profile.
+    pub fn resolve(domain: &str, registrar: Option<&str>) -> Self {
+        Self::for_tld(tld_of(domain))
+            .or_else(|| registrar.map(Self::for_registrar))
+            .unwrap_or_default()
+    }
 }
 
 /// Complete expiry state for a single domain.
@@ -84,39 +120,118 @@ pub struct DomainExpiry {
     pub estimated_drop: Option<DateTime<Utc>>,
     /// Days until expiry (negative if already expired).
     pub days_until_expiry: Option<i64>,
+    /// How sure the phase is, from 0.0 to 1.0.
+    #[serde(default)]
+    pub confidence: f32,
+    /// What the phase was derived from.
+    #[serde(default)]
+    pub basis: PhaseBasis,
 }
 
 impl DomainExpiry {
-    /// Compute the full expiry state for a domain.
+    /// Compute the full expiry state for a domain from its expiry date.
     pub fn compute(
         domain: impl Into<String>,
         expiry_date: Option<DateTime<Utc>>,
         registrar: Option<&str>,
         now: DateTime<Utc>,
     ) -> Self {
+        Self::assess(
+            domain,
+            &PhaseEvidence::from_dates(expiry_date, registrar),
+            now,
+        )
+    }
+
+    /// Compute the expiry state from everything a lookup found. Lifecycle
+    /// status codes are what the registry says and win over dates; a phase
+    /// worked out from the expiry date alone is a guess, since registries
+    /// auto-renew and registrars pick their own grace periods.
+    pub fn assess(domain: impl Into<String>, evidence: &PhaseEvidence, now: DateTime<Utc>) -> Self {
         let domain = domain.into();
-        let grace = registrar
-            .map(|r| GracePeriod::for_registrar(r))
+        let tld_policy = GracePeriod::for_tld(tld_of(&domain));
+        let known_policy = tld_policy.is_some();
+        let grace = tld_policy
+            .or_else(|| {
+                evidence
+                    .registrar
+                    .as_deref()
+                    .map(GracePeriod::for_registrar)
+            })
             .unwrap_or_default();
 
-        let (phase, days_until, estimated_drop) = match expiry_date {
-            Some(exp) => {
-                let days = (exp - now).num_days();
-                let drop = exp + Duration::days(grace.total_days());
-                let phase = compute_phase(exp, &grace, now);
-                (phase, Some(days), Some(drop))
-            }
-            None => (ExpiryPhase::Unknown, None, None),
+        let expiry_date = evidence.expiry();
+        let days_until = expiry_date.map(|exp| (exp - now).num_days());
+        let date_drop = evidence
+            .event("deletion")
+            .or_else(|| expiry_date.map(|exp| exp + Duration::days(grace.total_days())));
+
+        let status_phase = if evidence.has(EppStatus::PendingDelete) {
+            Some((ExpiryPhase::PendingDelete, 0.95, grace.pending_delete_days))
+        } else if evidence.has(EppStatus::RedemptionPeriod)
+            || evidence.has(EppStatus::PendingRestore)
+        {
+            Some((
+                ExpiryPhase::RedemptionGrace,
+                0.95,
+                grace.redemption_days + grace.pending_delete_days,
+            ))
+        } else if evidence.has(EppStatus::AutoRenewPeriod) {
+            Some((ExpiryPhase::AutoRenewGrace, 0.9, grace.total_days()))
+        } else {
+            None
+        };
+
+        let (phase, confidence, basis, estimated_drop) = if evidence.available {
+            (ExpiryPhase::Deleted, 0.95, PhaseBasis::Availability, None)
+        } else if let Some((phase, confidence, remaining_days)) = status_phase {
+            // Registries touch `last changed` when the status changes, so
+            // it marks the start of the phase.
+            let drop = evidence
+                .event("deletion")
+                .or_else(|| {
+                    evidence
+                        .event("last changed")
+                        .map(|at| at + Duration::days(remaining_days))
+                })
+                .or(date_drop);
+            (phase, confidence, PhaseBasis::Status, drop)
+        } else if let Some(exp) = expiry_date {
+            let phase = compute_phase(exp, &grace, now);
+            let basis = if evidence.expiry_date.is_some() {
+                PhaseBasis::Dates
+            } else {
+                PhaseBasis::Events
+            };
+            let confidence = match phase {
+                ExpiryPhase::Active | ExpiryPhase::ExpiringAoon => 0.9,
+                // Still registered past every grace period: most likely
+                // renewed with a stale expiry date.
+                ExpiryPhase::Deleted => 0.3,
+                _ => {
+                    let held = evidence.status.iter().any(EppStatus::is_hold);
+                    let mut c: f32 = if held { 0.7 } else { 0.5 };
+                    if known_policy {
+                        c += 0.1;
+                    }
+                    c
+                }
+            };
+            (phase, confidence, basis, date_drop)
+        } else {
+            (ExpiryPhase::Unknown, 0.0, PhaseBasis::None, date_drop)
         };
 
         Self {
             domain,
             expiry_date,
-            registrar: registrar.map(|s| s.to_string()),
+            registrar: evidence.registrar.clone(),
             grace,
             phase,
-            estimated_drop: estimated_drop,
+            estimated_drop,
             days_until_expiry: days_until,
+            confidence,
+            basis,
         }
     }
 
@@ -142,6 +257,24 @@ impl DomainExpiry {
     }
 }
 
+/// Lowercased first word of a registrar name, matching the slugs used by
+/// `GracePeriod::for_registrar` (e.g. "GoDaddy.com, LLC" → "godaddy").
+pub fn registrar_slug(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .find(|w| !w.is_empty())
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+/// The last label of `domain`.
+fn tld_of(domain: &str) -> &str {
+    domain
+        .trim_end_matches('.')
+        .rsplit('.')
+        .next()
+        .unwrap_or_default()
+}
+
 fn compute_phase(expiry: DateTime<Utc>, grace: &GracePeriod, now: DateTime<Utc>) -> ExpiryPhase {
     let days_since_expiry = (now - expiry).num_days();
 
@@ -254,4 +387,64 @@ mod tests {
         let unknown = GracePeriod::for_registrar("unknown_registrar");
         assert_eq!(unknown.auto_renew_days, 30); // default
     }
+
+    #[test]
+    fn test_tld_policy_overrides_registrar() {
+        assert_eq!(GracePeriod::for_tld(".EU").unwrap().redemption_days, 40);
+        assert!(GracePeriod::for_tld("com").is_none());
+        let grace = GracePeriod::resolve("example.de", Some("godaddy"));
+        assert_eq!(grace.auto_renew_days, 0);
+        assert_eq!(
+            GracePeriod::resolve("example.com", Some("godaddy")).auto_renew_days,
+            25
+        );
+
+        // .eu has no auto-renew grace: ten days after expiry is quarantine.
+        let de = DomainExpiry::compute("test.eu", Some(utc(2026, 1, 1)), None, utc(2026, 1, 11));
+        assert_eq!(de.phase, ExpiryPhase::RedemptionGrace);
+        assert_eq!(de.estimated_drop.unwrap(), utc(2026, 2, 10));
+    }
+
+    #[test]
+    fn test_status_wins_over_dates() {
+        // Dates alone say auto-renew grace; the registry says redemption.
+        let evidence = PhaseEvidence::from_dates(Some(utc(2026, 1, 1)), Some("godaddy"))
+            .with_status_codes(["redemptionPeriod https://icann.org/epp#redemptionPeriod"])
+            .with_rdap(&serde_json::json!({
+                "events": [{"eventAction": "last changed", "eventDate": "2026-01-10T00:00:00Z"}]
+            }));
+        let de = DomainExpiry::assess("test.com", &evidence, utc(2026, 1, 15));
+        assert_eq!(de.phase, ExpiryPhase::RedemptionGrace);
+        assert_eq!(de.basis, PhaseBasis::Status);
+        assert!(de.confidence > 0.9);
+        // Redemption started at the last change: 30 + 5 days to drop.
+        assert_eq!(de.estimated_drop.unwrap(), utc(2026, 2, 14));
+    }
+
+    #[test]
+    fn test_confidence_by_evidence() {
+        let now = utc(2026, 1, 15);
+        let dates = PhaseEvidence::from_dates(Some(utc(2026, 1, 1)), None);
+        let guess = DomainExpiry::assess("test.com", &dates, now);
+        assert_eq!(guess.phase, ExpiryPhase::AutoRenewGrace);
+        assert_eq!(guess.basis, PhaseBasis::Dates);
+
+        let held = DomainExpiry::assess(
+            "test.com",
+            &dates.clone().with_status_codes(["clientHold"]),
+            now,
+        );
+        assert!(held.confidence > guess.confidence);
+
+        let pending = dates.clone().with_status_codes(["pending delete"]);
+        let de = DomainExpiry::assess("test.com", &pending, now);
+        assert_eq!(de.phase, ExpiryPhase::PendingDelete);
+
+        let gone = DomainExpiry::assess("test.com", &dates.with_available(true), now);
+        assert_eq!(gone.phase, ExpiryPhase::Deleted);
+        assert_eq!(gone.basis, PhaseBasis::Availability);
+
+        let none = DomainExpiry::assess("test.com", &PhaseEvidence::new(), now);
+        assert_eq!((none.phase, none.confidence), (ExpiryPhase::Unknown, 0.0));
+    }
 }
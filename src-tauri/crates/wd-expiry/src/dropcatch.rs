@@ -58,19 +58,24 @@ impl DropEstimate {
         now: DateTime<Utc>,
     ) -> Option<Self> {
         let estimated_drop = expiry.estimated_drop?;
-        let earliest = estimated_drop;
-        let latest = estimated_drop + Duration::days(2);
-        let hours = (earliest - now).num_hours();
-        let is_hot = hours <= 24 && hours > -48;
-
-        Some(Self {
+        let mut estimate = Self {
             domain: expiry.domain.clone(),
-            earliest_drop: earliest,
-            latest_drop: latest,
-            is_hot,
-            hours_until_drop: hours,
+            earliest_drop: estimated_drop,
+            latest_drop: estimated_drop + Duration::days(2),
+            is_hot: false,
+            hours_until_drop: 0,
             strategy,
-        })
+        };
+        estimate.refresh(now);
+        Some(estimate)
+    }
+
+    /// Recompute the time-dependent fields (`hours_until_drop`, `is_hot`)
+    /// relative to `now`.
+    pub fn refresh(&mut self, now: DateTime<Utc>) {
+        let hours = (self.earliest_drop - now).num_hours();
+        self.hours_until_drop = hours;
+        self.is_hot = hours <= 24 && hours > -48;
     }
 
     /// Compute the recommended polling interval in seconds based on proximity to drop.
@@ -183,6 +188,17 @@ mod tests {
         assert_eq!(estimates[0].domain, "sooner.com");
     }
 
+    #[test]
+    fn test_refresh_enters_hot_zone() {
+        let exp = DomainExpiry::compute("r.com", Some(utc(2026, 1, 1)), None, utc(2026, 1, 1));
+        let mut est =
+            DropEstimate::from_expiry_at(&exp, DropStrategy::default(), utc(2026, 1, 1)).unwrap();
+        assert!(!est.is_hot);
+        est.refresh(utc(2026, 3, 6));
+        assert!(est.is_hot);
+        assert_eq!(est.hours_until_drop, 24);
+    }
+
     #[test]
     fn test_no_estimate_without_expiry() {
         let exp = DomainExpiry::compute("no.com", None, None, Utc::now());
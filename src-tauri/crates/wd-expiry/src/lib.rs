@@ -6,16 +6,31 @@
 //! ## Modules
 //!
 //! - **domain** – domain expiry state model and grace period logic
+//! - **lifecycle** – EPP status codes and RDAP events used to detect the expiry phase
 //! - **watchlist** – prioritised watchlist for monitored domains
 //! - **dropcatch** – drop date estimation and catch scheduling
+//! - **catcher** – drop-catch engine that polls during the drop window and fires actions
+//! - **monitor** – periodic watchlist checks that report registration changes
 //! - **store** – SQLite persistence for watchlist state
 
+pub mod catcher;
 pub mod domain;
 pub mod dropcatch;
+pub mod lifecycle;
+pub mod monitor;
 pub mod store;
 pub mod watchlist;
 
-pub use domain::{DomainExpiry, ExpiryPhase, GracePeriod};
+pub use catcher::{
+    AddToCartAction, CatchAction, CatchConfig, CatchEvent, CatchEventKind, CatchState, CatchTarget,
+    Clock, DropCatcher, DropProbe, FakeClock, NotifyAction, ProbeMethod, ProbeOutcome,
+    RegisterAction, RegistrarAdapter, SystemClock,
+};
+pub use domain::{registrar_slug, DomainExpiry, ExpiryPhase, GracePeriod};
 pub use dropcatch::{DropEstimate, DropStrategy};
+pub use lifecycle::{EppStatus, PhaseBasis, PhaseEvidence, RdapEvent};
+pub use monitor::{
+    TickReport, WatchChange, WatchField, WatchMonitor, WatchObservation, WatchProbe, WatchUpdate,
+};
 pub use store::ExpiryStore;
 pub use watchlist::{WatchEntry, WatchPriority, Watchlist};
@@ -0,0 +1,252 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// EPP status codes (RFC 5731 / RFC 3915) that say something about where a
+/// domain is in its lifecycle. Other codes, such as the transfer and update
+/// locks, are ignored.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum EppStatus {
+    Ok,
+    Inactive,
+    ClientHold,
+    ServerHold,
+    AddPeriod,
+    AutoRenewPeriod,
+    RenewPeriod,
+    TransferPeriod,
+    RedemptionPeriod,
+    PendingRestore,
+    PendingDelete,
+}
+
+impl EppStatus {
+    /// Parse a status as WHOIS (`redemptionPeriod https://icann.org/epp#...`)
+    /// or RDAP (`redemption period`) writes it. ccTLD quarantine statuses map
+    /// to [`EppStatus::RedemptionPeriod`].
+    pub fn parse(s: &str) -> Option<Self> {
+        let code = s.split("http").next().unwrap_or_default();
+        let key: String = code
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        Some(match key.as_str() {
+            "ok" | "active" => Self::Ok,
+            "inactive" => Self::Inactive,
+            "clienthold" => Self::ClientHold,
+            "serverhold" => Self::ServerHold,
+            "addperiod" => Self::AddPeriod,
+            "autorenewperiod" => Self::AutoRenewPeriod,
+            "renewperiod" => Self::RenewPeriod,
+            "transferperiod" => Self::TransferPeriod,
+            "redemptionperiod" | "quarantine" | "quarantined" => Self::RedemptionPeriod,
+            "pendingrestore" => Self::PendingRestore,
+            "pendingdelete" => Self::PendingDelete,
+            _ => return None,
+        })
+    }
+
+    /// Parse every recognised code in `codes`, dropping duplicates.
+    pub fn parse_all<I, S>(codes: I) -> Vec<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut out = Vec::new();
+        for status in codes.into_iter().filter_map(|c| Self::parse(c.as_ref())) {
+            if !out.contains(&status) {
+                out.push(status);
+            }
+        }
+        out
+    }
+
+    /// Whether the domain has been taken out of the DNS.
+    pub fn is_hold(&self) -> bool {
+        matches!(self, Self::ClientHold | Self::ServerHold)
+    }
+}
+
+/// A dated event from an RDAP domain object, e.g. `expiration` or
+/// `last changed`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RdapEvent {
+    pub action: String,
+    pub date: DateTime<Utc>,
+}
+
+impl RdapEvent {
+    /// The `events` of an RDAP domain response. Events with a missing or
+    /// unparsable date are skipped.
+    pub fn parse_all(rdap: &Value) -> Vec<Self> {
+        let Some(events) = rdap["events"].as_array() else {
+            return Vec::new();
+        };
+        events
+            .iter()
+            .filter_map(|e| {
+                let action = e["eventAction"].as_str()?.to_lowercase();
+                let date = DateTime::parse_from_rfc3339(e["eventDate"].as_str()?).ok()?;
+                Some(Self {
+                    action,
+                    date: date.with_timezone(&Utc),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Everything a lookup found out that bears on the expiry phase.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct PhaseEvidence {
+    pub status: Vec<EppStatus>,
+    pub events: Vec<RdapEvent>,
+    pub expiry_date: Option<DateTime<Utc>>,
+    /// Registrar slug for `GracePeriod::for_registrar`.
+    pub registrar: Option<String>,
+    /// The lookup found the domain unregistered.
+    pub available: bool,
+}
+
+impl PhaseEvidence {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Evidence from dates alone, as `DomainExpiry::compute` has it.
+    pub fn from_dates(expiry_date: Option<DateTime<Utc>>, registrar: Option<&str>) -> Self {
+        Self {
+            expiry_date,
+            registrar: registrar.map(|r| r.to_string()),
+            ..Self::default()
+        }
+    }
+
+    /// Add status codes as WHOIS or RDAP reports them; unrecognised codes
+    /// are ignored.
+    pub fn with_status_codes<I, S>(mut self, codes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for status in EppStatus::parse_all(codes) {
+            if !self.status.contains(&status) {
+                self.status.push(status);
+            }
+        }
+        self
+    }
+
+    /// Add RDAP status codes and events from an RDAP domain response.
+    pub fn with_rdap(mut self, rdap: &Value) -> Self {
+        let codes = rdap["status"]
+            .as_array()
+            .map(|s| s.iter().filter_map(|c| c.as_str()).collect::<Vec<_>>())
+            .unwrap_or_default();
+        self = self.with_status_codes(codes);
+        self.events.extend(RdapEvent::parse_all(rdap));
+        self
+    }
+
+    pub fn with_expiry_date(mut self, expiry_date: Option<DateTime<Utc>>) -> Self {
+        self.expiry_date = expiry_date;
+        self
+    }
+
+    pub fn with_registrar(mut self, registrar: impl Into<String>) -> Self {
+        self.registrar = Some(registrar.into());
+        self
+    }
+
+    pub fn with_available(mut self, available: bool) -> Self {
+        self.available = available;
+        self
+    }
+
+    pub fn has(&self, status: EppStatus) -> bool {
+        self.status.contains(&status)
+    }
+
+    /// Date of the latest event with `action`.
+    pub fn event(&self, action: &str) -> Option<DateTime<Utc>> {
+        self.events
+            .iter()
+            .filter(|e| e.action == action)
+            .map(|e| e.date)
+            .max()
+    }
+
+    /// The expiry date, falling back to the RDAP `expiration` event.
+    pub fn expiry(&self) -> Option<DateTime<Utc>> {
+        self.expiry_date.or_else(|| self.event("expiration"))
+    }
+}
+
+/// What an expiry phase was derived from, strongest first.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PhaseBasis {
+    /// The lookup found the domain unregistered.
+    Availability,
+    /// An EPP lifecycle status code.
+    Status,
+    /// An RDAP `expiration` event.
+    Events,
+    /// The WHOIS expiry date and the grace period policy.
+    Dates,
+    #[default]
+    None,
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_whois_and_rdap_forms() {
+        assert_eq!(
+            EppStatus::parse("redemptionPeriod https://icann.org/epp#redemptionPeriod"),
+            Some(EppStatus::RedemptionPeriod)
+        );
+        assert_eq!(
+            EppStatus::parse("pending delete"),
+            Some(EppStatus::PendingDelete)
+        );
+        assert_eq!(
+            EppStatus::parse("QUARANTINED"),
+            Some(EppStatus::RedemptionPeriod)
+        );
+        assert_eq!(EppStatus::parse("clientTransferProhibited"), None);
+        assert_eq!(
+            EppStatus::parse_all(["client hold", "clientHold", "ok"]),
+            [EppStatus::ClientHold, EppStatus::Ok]
+        );
+    }
+
+    #[test]
+    fn test_evidence_from_rdap() {
+        let rdap = serde_json::json!({
+            "status": ["client hold", "auto renew period", "client transfer prohibited"],
+            "events": [
+                {"eventAction": "expiration", "eventDate": "2026-01-01T00:00:00Z"},
+                {"eventAction": "last changed", "eventDate": "2026-01-02T12:00:00Z"},
+                {"eventAction": "registration"}
+            ]
+        });
+        let ev = PhaseEvidence::new().with_rdap(&rdap);
+        assert_eq!(
+            ev.status,
+            [EppStatus::ClientHold, EppStatus::AutoRenewPeriod]
+        );
+        assert_eq!(ev.events.len(), 2);
+        assert_eq!(
+            ev.expiry().unwrap(),
+            DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z").unwrap()
+        );
+        assert!(ev.event("deletion").is_none());
+    }
+}
@@ -0,0 +1,396 @@
+use std::future::Future;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::catcher::{Clock, SystemClock};
+use crate::domain::{registrar_slug, DomainExpiry, ExpiryPhase};
+use crate::lifecycle::PhaseEvidence;
+use crate::watchlist::{WatchEntry, Watchlist};
+
+// ─── Observations ────────────────────────────────────────────────────────────
+
+/// What a single lookup found out about a watched domain.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct WatchObservation {
+    /// Availability status (`"available"`, `"unavailable"`, `"expired"`, ...).
+    pub status: String,
+    pub registrar: Option<String>,
+    pub nameservers: Vec<String>,
+    pub expiry_date: Option<DateTime<Utc>>,
+    /// EPP status codes as the lookup reported them.
+    #[serde(default)]
+    pub status_codes: Vec<String>,
+}
+
+impl WatchObservation {
+    /// Whether the lookup found the domain unregistered.
+    pub fn is_available(&self) -> bool {
+        self.status == "available"
+    }
+}
+
+/// Looks up a watched domain, e.g. over WHOIS. Implementations may also
+/// record the raw reply as a history snapshot.
+pub trait WatchProbe: Sync {
+    fn observe(
+        &self,
+        domain: &str,
+    ) -> impl Future<Output = Result<WatchObservation, String>> + Send;
+}
+
+// ─── Changes ─────────────────────────────────────────────────────────────────
+
+/// A tracked property of a watched domain.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchField {
+    Status,
+    Registrar,
+    Nameservers,
+    Phase,
+}
+
+/// One property that differs from the previous check.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WatchChange {
+    pub field: WatchField,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// A check that changed something worth reporting.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WatchUpdate {
+    pub domain: String,
+    pub changes: Vec<WatchChange>,
+    pub status: String,
+    pub phase: ExpiryPhase,
+    pub expiry_date: Option<DateTime<Utc>>,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Outcome of one [`WatchMonitor::tick`].
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct TickReport {
+    /// Domains looked up, in the order they were checked.
+    pub checked: Vec<String>,
+    pub updates: Vec<WatchUpdate>,
+    /// Domains whose lookup failed, with the error.
+    pub errors: Vec<(String, String)>,
+}
+
+/// Expiry phase implied by `obs`. A domain reported available is treated
+/// as deleted whatever its last known expiry date.
+pub fn phase_of(domain: &str, obs: &WatchObservation, now: DateTime<Utc>) -> ExpiryPhase {
+    let slug = obs.registrar.as_deref().map(registrar_slug);
+    let evidence = PhaseEvidence::from_dates(obs.expiry_date, slug.as_deref())
+        .with_status_codes(&obs.status_codes)
+        .with_available(obs.is_available());
+    DomainExpiry::assess(domain, &evidence, now).phase
+}
+
+/// Store `obs` in `entry` and return what changed. The first observation
+/// of an entry is a baseline and reports no changes.
+pub fn record_observation(
+    entry: &mut WatchEntry,
+    obs: &WatchObservation,
+    now: DateTime<Utc>,
+) -> Vec<WatchChange> {
+    let phase = phase_of(&entry.domain, obs, now);
+    let nameservers = normalize_nameservers(&obs.nameservers);
+    let baseline = entry.status.is_none();
+
+    let mut changes = Vec::new();
+    let mut compare = |field, old: Option<String>, new: Option<String>| {
+        if old != new {
+            changes.push(WatchChange { field, old, new });
+        }
+    };
+    compare(
+        WatchField::Status,
+        entry.status.clone(),
+        Some(obs.status.clone()),
+    );
+    compare(
+        WatchField::Registrar,
+        entry.registrar.clone(),
+        obs.registrar.clone(),
+    );
+    compare(
+        WatchField::Nameservers,
+        join(&normalize_nameservers(&entry.nameservers)),
+        join(&nameservers),
+    );
+    compare(
+        WatchField::Phase,
+        entry.phase.as_ref().map(phase_name),
+        Some(phase_name(&phase)),
+    );
+
+    entry.last_checked = Some(now);
+    entry.status = Some(obs.status.clone());
+    entry.registrar = obs.registrar.clone();
+    entry.nameservers = nameservers;
+    entry.phase = Some(phase);
+    if obs.expiry_date.is_some() || obs.is_available() {
+        entry.expiry_date = obs.expiry_date;
+    }
+
+    if baseline {
+        Vec::new()
+    } else {
+        changes
+    }
+}
+
+fn normalize_nameservers(nameservers: &[String]) -> Vec<String> {
+    let mut out: Vec<String> = nameservers
+        .iter()
+        .map(|ns| ns.trim().trim_end_matches('.').to_lowercase())
+        .filter(|ns| !ns.is_empty())
+        .collect();
+    out.sort();
+    out.dedup();
+    out
+}
+
+fn join(nameservers: &[String]) -> Option<String> {
+    (!nameservers.is_empty()).then(|| nameservers.join(", "))
+}
+
+fn phase_name(phase: &ExpiryPhase) -> String {
+    match serde_json::to_value(phase) {
+        Ok(serde_json::Value::String(s)) => s,
+        _ => format!("{phase:?}"),
+    }
+}
+
+// ─── Monitor ─────────────────────────────────────────────────────────────────
+
+/// Re-checks watchlist entries as their priority's interval elapses and
+/// reports status, registrar, nameserver and phase changes.
+pub struct WatchMonitor<P, C = SystemClock> {
+    probe: P,
+    clock: C,
+}
+
+impl<P: WatchProbe> WatchMonitor<P> {
+    pub fn new(probe: P) -> Self {
+        Self {
+            probe,
+            clock: SystemClock,
+        }
+    }
+}
+
+impl<P: WatchProbe, C: Clock> WatchMonitor<P, C> {
+    pub fn with_clock<C2: Clock>(self, clock: C2) -> WatchMonitor<P, C2> {
+        WatchMonitor {
+            probe: self.probe,
+            clock,
+        }
+    }
+
+    /// Check every entry of `watchlist` that is due, most urgent first.
+    /// A failed lookup still counts as a check, so an unreachable server
+    /// is retried after the entry's interval rather than on every tick.
+    pub async fn tick(&self, watchlist: &mut Watchlist) -> TickReport {
+        let now = self.clock.now();
+        let due: Vec<String> = watchlist
+            .due_by_priority(now)
+            .into_iter()
+            .map(|e| e.domain.clone())
+            .collect();
+
+        let mut report = TickReport::default();
+        for domain in due {
+            let result = self.probe.observe(&domain).await;
+            let now = self.clock.now();
+            let Some(entry) = watchlist.get_mut(&domain) else {
+                continue;
+            };
+            report.checked.push(domain.clone());
+            match result {
+                Ok(obs) => {
+                    let changes = record_observation(entry, &obs, now);
+                    if !changes.is_empty() {
+                        report.updates.push(WatchUpdate {
+                            domain,
+                            changes,
+                            status: obs.status,
+                            phase: entry.phase.clone().unwrap_or(ExpiryPhase::Unknown),
+                            expiry_date: entry.expiry_date,
+                            checked_at: now,
+                        });
+                    }
+                }
+                Err(e) => {
+                    log::warn!("Watch check failed for {domain}: {e}");
+                    entry.last_checked = Some(now);
+                    report.errors.push((domain, e));
+                }
+            }
+        }
+        report
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catcher::FakeClock;
+    use crate::watchlist::WatchPriority;
+    use chrono::{Duration, TimeZone};
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    fn utc(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, 0, 0, 0).unwrap()
+    }
+
+    /// Serves canned observations and counts lookups.
+    #[derive(Default)]
+    struct ScriptedProbe {
+        replies: Mutex<HashMap<String, Result<WatchObservation, String>>>,
+        lookups: Mutex<Vec<String>>,
+    }
+
+    impl ScriptedProbe {
+        fn set(&self, domain: &str, reply: Result<WatchObservation, String>) {
+            self.replies.lock().unwrap().insert(domain.into(), reply);
+        }
+    }
+
+    impl WatchProbe for ScriptedProbe {
+        async fn observe(&self, domain: &str) -> Result<WatchObservation, String> {
+            self.lookups.lock().unwrap().push(domain.into());
+            self.replies
+                .lock()
+                .unwrap()
+                .get(domain)
+                .cloned()
+                .unwrap_or_else(|| Err("no reply".into()))
+        }
+    }
+
+    fn registered(registrar: &str, ns: &[&str], expiry: DateTime<Utc>) -> WatchObservation {
+        WatchObservation {
+            status: "unavailable".into(),
+            registrar: Some(registrar.into()),
+            nameservers: ns.iter().map(|s| s.to_string()).collect(),
+            expiry_date: Some(expiry),
+            status_codes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_first_observation_is_baseline() {
+        let mut entry = WatchEntry::new("a.com");
+        let obs = registered("GoDaddy.com, LLC", &["NS1.A.COM."], utc(2027, 1, 1));
+        assert!(record_observation(&mut entry, &obs, utc(2026, 1, 1)).is_empty());
+        assert_eq!(entry.nameservers, ["ns1.a.com"]);
+        assert_eq!(entry.phase, Some(ExpiryPhase::Active));
+        assert_eq!(entry.last_checked, Some(utc(2026, 1, 1)));
+    }
+
+    #[test]
+    fn test_reports_only_changed_fields() {
+        let mut entry = WatchEntry::new("a.com");
+        let now = utc(2026, 1, 1);
+        let obs = registered("Acme", &["ns1.a.com", "ns2.a.com"], utc(2027, 1, 1));
+        record_observation(&mut entry, &obs, now);
+
+        // Same nameservers in another order and case: nothing to report.
+        let same = registered("Acme", &["NS2.A.COM", "ns1.a.com"], utc(2027, 1, 1));
+        assert!(record_observation(&mut entry, &same, now).is_empty());
+
+        let moved = registered("Other Registrar", &["ns1.b.net"], utc(2027, 1, 1));
+        let changes = record_observation(&mut entry, &moved, now);
+        let fields: Vec<WatchField> = changes.iter().map(|c| c.field).collect();
+        assert_eq!(fields, [WatchField::Registrar, WatchField::Nameservers]);
+        assert_eq!(changes[1].old.as_deref(), Some("ns1.a.com, ns2.a.com"));
+        assert_eq!(changes[1].new.as_deref(), Some("ns1.b.net"));
+    }
+
+    #[test]
+    fn test_phase_change_as_time_passes() {
+        let mut entry = WatchEntry::new("a.com");
+        let obs = registered("Acme", &[], utc(2026, 1, 1));
+        record_observation(&mut entry, &obs, utc(2025, 12, 31));
+        let changes = record_observation(&mut entry, &obs, utc(2026, 2, 5));
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, WatchField::Phase);
+        assert_eq!(changes[0].old.as_deref(), Some("expiring_aoon"));
+        assert_eq!(changes[0].new.as_deref(), Some("redemption_grace"));
+    }
+
+    #[test]
+    fn test_status_code_sets_phase() {
+        // Renewed on the registry side, but the registrar let it lapse.
+        let mut entry = WatchEntry::new("a.com");
+        let mut obs = registered("Acme", &[], utc(2027, 1, 1));
+        record_observation(&mut entry, &obs, utc(2026, 1, 1));
+        obs.status_codes = vec!["pendingDelete".into()];
+        let changes = record_observation(&mut entry, &obs, utc(2026, 1, 2));
+        assert_eq!(changes[0].new.as_deref(), Some("pending_delete"));
+        assert_eq!(entry.phase, Some(ExpiryPhase::PendingDelete));
+    }
+
+    #[tokio::test]
+    async fn test_tick_checks_due_entries_by_priority() {
+        let start = utc(2026, 1, 1);
+        let clock = FakeClock::new(start);
+        let probe = ScriptedProbe::default();
+        let obs = registered("Acme", &["ns1.a.com"], utc(2027, 1, 1));
+        probe.set("crit.com", Ok(obs.clone()));
+        probe.set("low.com", Ok(obs.clone()));
+        probe.set("down.com", Err("timeout".into()));
+
+        let mut wl = Watchlist::new();
+        wl.add(WatchEntry::new("low.com").with_priority(WatchPriority::Low));
+        wl.add(WatchEntry::new("crit.com").with_priority(WatchPriority::Critical));
+        wl.add(WatchEntry::new("down.com"));
+        let monitor = WatchMonitor::new(probe).with_clock(clock.clone());
+
+        let report = monitor.tick(&mut wl).await;
+        assert_eq!(report.checked, ["crit.com", "down.com", "low.com"]);
+        assert!(report.updates.is_empty());
+        assert_eq!(report.errors, [("down.com".into(), "timeout".into())]);
+
+        // Nothing is due a minute later.
+        clock.advance(Duration::minutes(1));
+        assert!(monitor.tick(&mut wl).await.checked.is_empty());
+
+        // After 20 minutes only the critical entry is due; it has dropped.
+        clock.advance(Duration::minutes(20));
+        monitor.probe.set(
+            "crit.com",
+            Ok(WatchObservation {
+                status: "available".into(),
+                ..Default::default()
+            }),
+        );
+        let report = monitor.tick(&mut wl).await;
+        assert_eq!(report.checked, ["crit.com"]);
+        let update = &report.updates[0];
+        assert_eq!(update.phase, ExpiryPhase::Deleted);
+        let fields: Vec<WatchField> = update.changes.iter().map(|c| c.field).collect();
+        assert_eq!(
+            fields,
+            [
+                WatchField::Status,
+                WatchField::Registrar,
+                WatchField::Nameservers,
+                WatchField::Phase
+            ]
+        );
+        assert_eq!(
+            monitor.probe.lookups.lock().unwrap().len(),
+            4,
+            "low.com and down.com were not re-checked"
+        );
+    }
+}
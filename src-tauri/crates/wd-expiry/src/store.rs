@@ -1,4 +1,5 @@
-use crate::watchlist::{WatchEntry, WatchPriority};
+use crate::domain::ExpiryPhase;
+use crate::watchlist::{WatchEntry, WatchPriority, Watchlist};
 use rusqlite::{params, Connection, Result as SqlResult};
 use std::sync::Mutex;
 
@@ -41,11 +42,29 @@ impl ExpiryStore {
                 registrar    TEXT,
                 notify       INTEGER NOT NULL DEFAULT 1,
                 notes        TEXT,
-                active       INTEGER NOT NULL DEFAULT 1
+                active       INTEGER NOT NULL DEFAULT 1,
+                status       TEXT,
+                nameservers  TEXT NOT NULL DEFAULT '[]',
+                phase        TEXT
             );
             CREATE INDEX IF NOT EXISTS idx_watch_domain ON watchlist(domain);
         ",
         )?;
+        // Watchlists created before the monitor recorded lookup results.
+        for (column, decl) in [
+            ("status", "TEXT"),
+            ("nameservers", "TEXT NOT NULL DEFAULT '[]'"),
+            ("phase", "TEXT"),
+        ] {
+            let exists: bool = conn.query_row(
+                "SELECT COUNT(*) > 0 FROM pragma_table_info('watchlist') WHERE name = ?1",
+                params![column],
+                |r| r.get(0),
+            )?;
+            if !exists {
+                conn.execute_batch(&format!("ALTER TABLE watchlist ADD COLUMN {column} {decl}"))?;
+            }
+        }
         Ok(())
     }
 
@@ -54,12 +73,14 @@ impl ExpiryStore {
         let conn = self.conn.lock().unwrap();
         conn.execute(
             "INSERT INTO watchlist (domain, priority, added_at, last_checked, expiry_date,
-                                    registrar, notify, notes, active)
-             VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9)
+                                    registrar, notify, notes, active, status, nameservers, phase)
+             VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12)
              ON CONFLICT(domain) DO UPDATE SET
                 priority=excluded.priority, last_checked=excluded.last_checked,
                 expiry_date=excluded.expiry_date, registrar=excluded.registrar,
-                notify=excluded.notify, notes=excluded.notes, active=excluded.active",
+                notify=excluded.notify, notes=excluded.notes, active=excluded.active,
+                status=excluded.status, nameservers=excluded.nameservers,
+                phase=excluded.phase",
             params![
                 entry.domain,
                 format!("{:?}", entry.priority).to_lowercase(),
@@ -70,6 +91,9 @@ impl ExpiryStore {
                 entry.notify as i32,
                 entry.notes,
                 entry.active as i32,
+                entry.status,
+                serde_json::to_string(&entry.nameservers).unwrap_or_else(|_| "[]".into()),
+                entry.phase.as_ref().and_then(phase_to_str),
             ],
         )?;
         Ok(conn.last_insert_rowid())
@@ -80,7 +104,7 @@ impl ExpiryStore {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
             "SELECT id, domain, priority, added_at, last_checked, expiry_date,
-                    registrar, notify, notes, active
+                    registrar, notify, notes, active, status, nameservers, phase
              FROM watchlist ORDER BY domain",
         )?;
         let rows = stmt.query_map([], |row| Ok(row_to_entry(row)))?;
@@ -91,6 +115,45 @@ impl ExpiryStore {
         Ok(out)
     }
 
+    /// Load the whole watchlist.
+    pub fn load(&self) -> SqlResult<Watchlist> {
+        Ok(Watchlist {
+            entries: self.get_all()?,
+        })
+    }
+
+    /// Persist every entry of `watchlist`.
+    pub fn save(&self, watchlist: &Watchlist) -> SqlResult<()> {
+        for entry in &watchlist.entries {
+            self.upsert(entry)?;
+        }
+        Ok(())
+    }
+
+    /// Store the results of checking `entry` (last check, expiry, registrar,
+    /// status, nameservers and phase) without touching its settings.
+    /// Returns `false` and writes nothing if the domain is no longer
+    /// watched, so a check that finishes after the domain was removed does
+    /// not bring it back.
+    pub fn record_check(&self, entry: &WatchEntry) -> SqlResult<bool> {
+        let conn = self.conn.lock().unwrap();
+        let n = conn.execute(
+            "UPDATE watchlist SET last_checked=?2, expiry_date=?3, registrar=?4, status=?5,
+                                  nameservers=?6, phase=?7
+             WHERE domain = ?1",
+            params![
+                entry.domain,
+                entry.last_checked.map(|d| d.to_rfc3339()),
+                entry.expiry_date.map(|d| d.to_rfc3339()),
+                entry.registrar,
+                entry.status,
+                serde_json::to_string(&entry.nameservers).unwrap_or_else(|_| "[]".into()),
+                entry.phase.as_ref().and_then(phase_to_str),
+            ],
+        )?;
+        Ok(n > 0)
+    }
+
     /// Remove a domain from the watchlist.
     pub fn remove(&self, domain: &str) -> SqlResult<bool> {
         let conn = self.conn.lock().unwrap();
@@ -116,6 +179,9 @@ fn row_to_entry(row: &rusqlite::Row) -> WatchEntry {
     let notify: i32 = row.get_unwrap(7);
     let notes: Option<String> = row.get_unwrap(8);
     let active: i32 = row.get_unwrap(9);
+    let status: Option<String> = row.get_unwrap(10);
+    let nameservers: String = row.get_unwrap(11);
+    let phase: Option<String> = row.get_unwrap(12);
 
     let priority = match priority_str.as_str() {
         "low" => WatchPriority::Low,
@@ -145,6 +211,16 @@ fn row_to_entry(row: &rusqlite::Row) -> WatchEntry {
         notify: notify != 0,
         notes,
         active: active != 0,
+        status,
+        nameservers: serde_json::from_str(&nameservers).unwrap_or_default(),
+        phase: phase.and_then(|p| serde_json::from_value(serde_json::Value::String(p)).ok()),
+    }
+}
+
+fn phase_to_str(phase: &ExpiryPhase) -> Option<String> {
+    match serde_json::to_value(phase) {
+        Ok(serde_json::Value::String(s)) => Some(s),
+        _ => None,
     }
 }
 
@@ -179,6 +255,75 @@ mod tests {
         assert_eq!(all[0].priority, WatchPriority::Critical);
     }
 
+    #[test]
+    fn test_store_round_trips_check_results() {
+        let store = ExpiryStore::open_in_memory().unwrap();
+        let mut wl = Watchlist::new();
+        let mut entry = WatchEntry::new("a.com");
+        entry.status = Some("unavailable".into());
+        entry.nameservers = vec!["ns1.a.com".into(), "ns2.a.com".into()];
+        entry.phase = Some(ExpiryPhase::RedemptionGrace);
+        wl.add(entry);
+        store.save(&wl).unwrap();
+
+        let loaded = store.load().unwrap();
+        let entry = loaded.get("a.com").unwrap();
+        assert_eq!(entry.status.as_deref(), Some("unavailable"));
+        assert_eq!(entry.nameservers, ["ns1.a.com", "ns2.a.com"]);
+        assert_eq!(entry.phase, Some(ExpiryPhase::RedemptionGrace));
+    }
+
+    #[test]
+    fn test_record_check_keeps_settings_and_skips_removed() {
+        let store = ExpiryStore::open_in_memory().unwrap();
+        store
+            .upsert(&WatchEntry::new("a.com").with_priority(WatchPriority::Low))
+            .unwrap();
+        let mut checked = WatchEntry::new("a.com");
+        checked.status = Some("available".into());
+        checked.last_checked = Some(chrono::Utc::now());
+
+        // The priority was changed while the check ran.
+        store
+            .upsert(&WatchEntry::new("a.com").with_priority(WatchPriority::Critical))
+            .unwrap();
+        assert!(store.record_check(&checked).unwrap());
+        let entry = store.get_all().unwrap().remove(0);
+        assert_eq!(entry.priority, WatchPriority::Critical);
+        assert_eq!(entry.status.as_deref(), Some("available"));
+        assert!(entry.last_checked.is_some());
+
+        store.remove("a.com").unwrap();
+        assert!(!store.record_check(&checked).unwrap());
+        assert_eq!(store.count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_store_migrates_old_schema() {
+        let dir = std::env::temp_dir().join(format!("wd-expiry-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("old.sqlite");
+        let _ = std::fs::remove_file(&path);
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE watchlist (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT, domain TEXT NOT NULL UNIQUE,
+                    priority TEXT NOT NULL DEFAULT 'medium', added_at TEXT NOT NULL,
+                    last_checked TEXT, expiry_date TEXT, registrar TEXT,
+                    notify INTEGER NOT NULL DEFAULT 1, notes TEXT,
+                    active INTEGER NOT NULL DEFAULT 1);
+                 INSERT INTO watchlist (domain, added_at) VALUES ('old.com', '2026-01-01T00:00:00Z');",
+            )
+            .unwrap();
+
+        let store = ExpiryStore::open(path.to_str().unwrap()).unwrap();
+        let all = store.get_all().unwrap();
+        assert_eq!(all[0].domain, "old.com");
+        assert!(all[0].status.is_none() && all[0].nameservers.is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn test_store_remove() {
         let store = ExpiryStore::open_in_memory().unwrap();
@@ -1,6 +1,8 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::domain::ExpiryPhase;
+
 /// Priority level for a watched domain.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 #[serde(rename_all = "lowercase")]
@@ -11,6 +13,18 @@ pub enum WatchPriority {
     Critical,
 }
 
+impl WatchPriority {
+    /// How often the monitor re-checks entries of this priority.
+    pub fn check_interval(&self) -> Duration {
+        match self {
+            Self::Critical => Duration::minutes(15),
+            Self::High => Duration::hours(1),
+            Self::Medium => Duration::hours(6),
+            Self::Low => Duration::hours(24),
+        }
+    }
+}
+
 impl Default for WatchPriority {
     fn default() -> Self {
         Self::Medium
@@ -35,6 +49,16 @@ pub struct WatchEntry {
     pub notes: Option<String>,
     /// Whether this entry is active (paused entries aren't polled).
     pub active: bool,
+    /// Availability status from the last check (`"available"`,
+    /// `"unavailable"`, ...). `None` until the monitor first checks it.
+    #[serde(default)]
+    pub status: Option<String>,
+    /// Nameservers from the last check.
+    #[serde(default)]
+    pub nameservers: Vec<String>,
+    /// Expiry phase from the last check.
+    #[serde(default)]
+    pub phase: Option<ExpiryPhase>,
 }
 
 impl WatchEntry {
@@ -50,6 +74,9 @@ impl WatchEntry {
             notify: true,
             notes: None,
             active: true,
+            status: None,
+            nameservers: Vec::new(),
+            phase: None,
         }
     }
 
@@ -134,6 +161,18 @@ impl Watchlist {
             .collect()
     }
 
+    /// Active entries whose priority's [`check_interval`](WatchPriority::check_interval)
+    /// has passed since they were last checked, most urgent first.
+    pub fn due_by_priority(&self, now: DateTime<Utc>) -> Vec<&WatchEntry> {
+        self.sorted_by_urgency()
+            .into_iter()
+            .filter(|e| {
+                e.last_checked
+                    .is_none_or(|lc| now - lc >= e.priority.check_interval())
+            })
+            .collect()
+    }
+
     pub fn len(&self) -> usize {
         self.entries.len()
     }
@@ -200,6 +239,28 @@ mod tests {
         assert_eq!(due.len(), 2); // old.com + never.com
     }
 
+    #[test]
+    fn test_due_by_priority() {
+        let now = Utc::now();
+        let mut wl = Watchlist::new();
+        for (domain, priority) in [
+            ("crit.com", WatchPriority::Critical),
+            ("low.com", WatchPriority::Low),
+        ] {
+            let mut e = WatchEntry::new(domain).with_priority(priority);
+            e.last_checked = Some(now - Duration::hours(2));
+            wl.add(e);
+        }
+        wl.add(WatchEntry::new("never.com").with_priority(WatchPriority::Low));
+
+        let due: Vec<&str> = wl
+            .due_by_priority(now)
+            .iter()
+            .map(|e| e.domain.as_str())
+            .collect();
+        assert_eq!(due, ["crit.com", "never.com"]);
+    }
+
     #[test]
     fn test_active_count() {
         let mut wl = Watchlist::new();
@@ -0,0 +1,358 @@
+//! iCalendar (RFC 5545) export of expiry dates for watched and owned
+//! domains.
+//!
+//! Every event's UID is derived from the domain and the kind of event, so
+//! importing a newer export updates the events of an earlier one instead of
+//! adding duplicates.
+
+use chrono::{DateTime, Duration, Utc};
+use wd_expiry::{registrar_slug, DomainExpiry, DropEstimate, DropStrategy, WatchEntry};
+use wd_portfolio::OwnedDomain;
+
+const PRODID: &str = "-//whoisdigger//Domain Expiry Calendar//EN";
+const UID_HOST: &str = "whoisdigger";
+/// Longest content line in octets before it is folded (RFC 5545 §3.1).
+const MAX_LINE_OCTETS: usize = 75;
+
+/// A date in a domain's lifecycle worth a calendar entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CalendarEventKind {
+    Expiry,
+    /// End of the Auto-Renew Grace Period.
+    AutoRenewEnd,
+    /// End of the Redemption Grace Period.
+    RedemptionEnd,
+    /// Estimated drop window.
+    Drop,
+}
+
+impl CalendarEventKind {
+    pub const ALL: [Self; 4] = [
+        Self::Expiry,
+        Self::AutoRenewEnd,
+        Self::RedemptionEnd,
+        Self::Drop,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Expiry => "expiry",
+            Self::AutoRenewEnd => "auto-renew-end",
+            Self::RedemptionEnd => "redemption-end",
+            Self::Drop => "drop",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|k| k.as_str().eq_ignore_ascii_case(s.trim()))
+    }
+}
+
+/// One event of the calendar.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalendarEvent {
+    pub domain: String,
+    pub kind: CalendarEventKind,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub summary: String,
+    pub description: String,
+}
+
+impl CalendarEvent {
+    /// Stable identifier: the same domain and kind always get the same UID.
+    pub fn uid(&self) -> String {
+        format!("{}-{}@{}", self.domain, self.kind.as_str(), UID_HOST)
+    }
+}
+
+/// Builds an iCalendar file from watchlist and portfolio domains.
+#[derive(Clone, Debug)]
+pub struct IcsCalendar {
+    name: String,
+    kinds: Vec<CalendarEventKind>,
+    alarms: Vec<Duration>,
+    include_past: bool,
+    events: Vec<CalendarEvent>,
+}
+
+impl Default for IcsCalendar {
+    fn default() -> Self {
+        Self::new("Domain expiry")
+    }
+}
+
+impl IcsCalendar {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            kinds: CalendarEventKind::ALL.to_vec(),
+            alarms: vec![Duration::days(7), Duration::days(1)],
+            include_past: false,
+            events: Vec::new(),
+        }
+    }
+
+    /// Only emit events of these kinds.
+    pub fn with_kinds(mut self, kinds: Vec<CalendarEventKind>) -> Self {
+        self.kinds = kinds;
+        self
+    }
+
+    /// Reminders, each this long before an event. Empty for none.
+    pub fn with_alarms(mut self, alarms: Vec<Duration>) -> Self {
+        self.alarms = alarms;
+        self
+    }
+
+    /// Keep events that are already in the past.
+    pub fn with_past_events(mut self, include: bool) -> Self {
+        self.include_past = include;
+        self
+    }
+
+    pub fn events(&self) -> &[CalendarEvent] {
+        &self.events
+    }
+
+    /// Add the lifecycle dates of `expiry`.
+    pub fn add_expiry(&mut self, expiry: &DomainExpiry, now: DateTime<Utc>) {
+        let Some(expires) = expiry.expiry_date else {
+            return;
+        };
+        let domain = &expiry.domain;
+        let grace = &expiry.grace;
+        let registrar = expiry
+            .registrar
+            .as_deref()
+            .map(|r| format!("Registrar: {r}\n"))
+            .unwrap_or_default();
+        let detail = format!(
+            "{registrar}Expires: {}\nGrace: {} days auto-renew, {} days redemption, {} days pending delete",
+            expires.format("%Y-%m-%d %H:%M UTC"),
+            grace.auto_renew_days,
+            grace.redemption_days,
+            grace.pending_delete_days,
+        );
+
+        let mut candidates = vec![(
+            CalendarEventKind::Expiry,
+            expires,
+            None,
+            format!("{domain} expires"),
+        )];
+        if grace.auto_renew_days > 0 {
+            candidates.push((
+                CalendarEventKind::AutoRenewEnd,
+                expires + Duration::days(grace.auto_renew_days),
+                None,
+                format!("{domain}: auto-renew grace ends"),
+            ));
+        }
+        if grace.redemption_days > 0 {
+            candidates.push((
+                CalendarEventKind::RedemptionEnd,
+                expires + Duration::days(grace.auto_renew_days + grace.redemption_days),
+                None,
+                format!("{domain}: redemption period ends"),
+            ));
+        }
+        if let Some(drop) = DropEstimate::from_expiry_at(expiry, DropStrategy::PassiveAlert, now) {
+            candidates.push((
+                CalendarEventKind::Drop,
+                drop.earliest_drop,
+                Some(drop.latest_drop),
+                format!("{domain} may drop"),
+            ));
+        }
+
+        // Re-adding a domain replaces its events.
+        self.events.retain(|e| e.domain != *domain);
+        for (kind, start, end, summary) in candidates {
+            if !self.kinds.contains(&kind) || (!self.include_past && start < now) {
+                continue;
+            }
+            self.events.push(CalendarEvent {
+                domain: domain.clone(),
+                kind,
+                start,
+                end: end.unwrap_or(start + Duration::hours(1)),
+                summary,
+                description: detail.clone(),
+            });
+        }
+    }
+
+    /// Add a watchlist entry, using its last known expiry date and registrar.
+    pub fn add_watch_entry(&mut self, entry: &WatchEntry, now: DateTime<Utc>) {
+        let slug = entry.registrar.as_deref().map(registrar_slug);
+        let expiry = DomainExpiry::compute(&entry.domain, entry.expiry_date, slug.as_deref(), now);
+        self.add_expiry(&expiry, now);
+    }
+
+    /// Add a domain from the portfolio.
+    pub fn add_owned(&mut self, domain: &OwnedDomain, now: DateTime<Utc>) {
+        let slug = domain.registrar.as_deref().map(registrar_slug);
+        let expiry =
+            DomainExpiry::compute(&domain.domain, domain.expiry_date, slug.as_deref(), now);
+        self.add_expiry(&expiry, now);
+    }
+
+    /// Render the calendar, with events in date order.
+    pub fn to_ics(&self, now: DateTime<Utc>) -> String {
+        let mut events: Vec<&CalendarEvent> = self.events.iter().collect();
+        events.sort_by(|a, b| a.start.cmp(&b.start).then_with(|| a.domain.cmp(&b.domain)));
+
+        let mut out = String::new();
+        let mut line = |s: &str| push_folded(&mut out, s);
+        line("BEGIN:VCALENDAR");
+        line("VERSION:2.0");
+        line(&format!("PRODID:{PRODID}"));
+        line("CALSCALE:GREGORIAN");
+        line("METHOD:PUBLISH");
+        line(&format!("X-WR-CALNAME:{}", escape_text(&self.name)));
+        for e in events {
+            line("BEGIN:VEVENT");
+            line(&format!("UID:{}", e.uid()));
+            line(&format!("DTSTAMP:{}", format_time(now)));
+            line(&format!("DTSTART:{}", format_time(e.start)));
+            line(&format!("DTEND:{}", format_time(e.end)));
+            line(&format!("SUMMARY:{}", escape_text(&e.summary)));
+            line(&format!("DESCRIPTION:{}", escape_text(&e.description)));
+            line(&format!("CATEGORIES:{}", e.kind.as_str()));
+            for before in &self.alarms {
+                line("BEGIN:VALARM");
+                line("ACTION:DISPLAY");
+                line(&format!("DESCRIPTION:{}", escape_text(&e.summary)));
+                line(&format!("TRIGGER:-{}", format_duration(*before)));
+                line("END:VALARM");
+            }
+            line("END:VEVENT");
+        }
+        line("END:VCALENDAR");
+        out
+    }
+}
+
+fn format_time(t: DateTime<Utc>) -> String {
+    t.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// RFC 5545 duration, in the largest whole unit.
+fn format_duration(d: Duration) -> String {
+    let minutes = d.num_minutes().max(0);
+    if minutes > 0 && minutes % (24 * 60) == 0 {
+        format!("P{}D", minutes / (24 * 60))
+    } else if minutes > 0 && minutes % 60 == 0 {
+        format!("PT{}H", minutes / 60)
+    } else {
+        format!("PT{minutes}M")
+    }
+}
+
+fn escape_text(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Append `line` with CRLF, folding it into continuation lines of at most
+/// 75 octets without splitting a UTF-8 character.
+fn push_folded(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if width + len > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += len;
+    }
+    out.push_str("\r\n");
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn utc(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, 0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_events_for_watch_entry() {
+        let now = utc(2026, 1, 1);
+        let entry = WatchEntry::new("brand.com").with_expiry(utc(2026, 6, 1));
+        let mut cal = IcsCalendar::default();
+        cal.add_watch_entry(&entry, now);
+        let kinds: Vec<CalendarEventKind> = cal.events().iter().map(|e| e.kind).collect();
+        assert_eq!(kinds, CalendarEventKind::ALL);
+        let drop = &cal.events()[3];
+        assert_eq!(drop.start, utc(2026, 8, 5));
+        assert_eq!(drop.end, utc(2026, 8, 7));
+
+        // Re-adding after a renewal moves the events instead of duplicating.
+        cal.add_watch_entry(&entry.clone().with_expiry(utc(2027, 6, 1)), now);
+        assert_eq!(cal.events().len(), 4);
+        assert_eq!(cal.events()[0].start, utc(2027, 6, 1));
+    }
+
+    #[test]
+    fn test_kinds_and_past_events_are_filtered() {
+        let now = utc(2026, 1, 15);
+        let owned = OwnedDomain::new("side.eu").with_expiry(utc(2026, 1, 1));
+        let mut cal = IcsCalendar::new("Owned").with_kinds(vec![
+            CalendarEventKind::Expiry,
+            CalendarEventKind::RedemptionEnd,
+        ]);
+        cal.add_owned(&owned, now);
+        // Expiry is past and .eu has a 40-day quarantine.
+        assert_eq!(cal.events().len(), 1);
+        assert_eq!(cal.events()[0].kind, CalendarEventKind::RedemptionEnd);
+        assert_eq!(cal.events()[0].start, utc(2026, 2, 10));
+    }
+
+    #[test]
+    fn test_ics_output() {
+        let now = utc(2026, 1, 1);
+        let mut cal = IcsCalendar::new("Team, domains")
+            .with_kinds(vec![CalendarEventKind::Expiry])
+            .with_alarms(vec![Duration::days(14), Duration::hours(2)]);
+        cal.add_owned(
+            &OwnedDomain::new("brand.com")
+                .with_registrar("GoDaddy.com, LLC")
+                .with_expiry(utc(2026, 3, 10)),
+            now,
+        );
+        let ics = cal.to_ics(now);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains("X-WR-CALNAME:Team\\, domains\r\n"));
+        assert!(ics.contains("UID:brand.com-expiry@whoisdigger\r\n"));
+        assert!(ics.contains("DTSTART:20260310T000000Z\r\n"));
+        assert!(ics.contains("TRIGGER:-P14D\r\n"));
+        assert!(ics.contains("TRIGGER:-PT2H\r\n"));
+        assert!(ics.split("\r\n").all(|l| l.len() <= MAX_LINE_OCTETS));
+    }
+
+    #[test]
+    fn test_folding_keeps_characters_whole() {
+        let mut out = String::new();
+        push_folded(&mut out, &format!("SUMMARY:{}", "é".repeat(60)));
+        let lines: Vec<&str> = out.split("\r\n").filter(|l| !l.is_empty()).collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|l| l.len() <= MAX_LINE_OCTETS));
+        assert!(lines[1].starts_with(' '));
+        assert_eq!(
+            CalendarEventKind::parse(" Drop"),
+            Some(CalendarEventKind::Drop)
+        );
+    }
+}
@@ -1,3 +1,6 @@
+#[cfg(feature = "ics")]
+pub mod ics;
+
 use serde::{Deserialize, Serialize};
 use std::io::Write;
 use wd_availability::WhoisParams;
@@ -40,6 +40,9 @@ impl ImportFormat {
                 }
             }
         }
+        if trimmed.starts_with("$ORIGIN") || trimmed.starts_with("$TTL") {
+            return ImportFormat::ZoneFile;
+        }
         if trimmed.contains("\t")
             && (trimmed.contains("IN\tA")
                 || trimmed.contains("IN\tNS")
@@ -145,6 +148,12 @@ mod tests {
         assert_eq!(ImportFormat::detect(content), ImportFormat::CommaSeparated);
     }
 
+    #[test]
+    fn test_detect_zone_file_directives() {
+        let content = "$TTL 3600\n@ IN SOA ns1 hostmaster 1 2 3 4 5";
+        assert_eq!(ImportFormat::detect(content), ImportFormat::ZoneFile);
+    }
+
     #[test]
     fn test_import_source() {
         let src = ImportSource::from_content("test.txt", "a\nb\nc", ImportFormat::NewlineDelimited);
@@ -8,8 +8,12 @@ pub mod format;
 pub mod parser;
 pub mod stats;
 pub mod validate;
+pub mod zone;
 
 pub use format::{ImportFormat, ImportSource};
 pub use parser::{parse_import, ImportResult};
 pub use stats::ImportStats;
 pub use validate::{DomainValidator, ValidationLevel};
+pub use zone::{
+    parse_zone, Delegation, RData, RecordClass, RecordType, Zone, ZoneError, ZoneParser, ZoneRecord,
+};
@@ -2,6 +2,7 @@ use std::collections::HashSet;
 
 use crate::format::ImportFormat;
 use crate::stats::ImportStats;
+use crate::zone::ZoneParser;
 
 /// Result of parsing an import.
 #[derive(Debug, Clone)]
@@ -101,23 +102,15 @@ fn parse_json_objects(content: &str, domain_key: &str) -> Vec<String> {
 }
 
 fn parse_zone_file(content: &str) -> Vec<String> {
-    // Extract domain names from zone file records
-    // Format: name TTL class type rdata
-    let mut domains = vec![];
-    for line in content.lines() {
-        let line = line.trim();
-        if line.is_empty() || line.starts_with(';') || line.starts_with('$') {
-            continue;
-        }
-        // Take the first field as the domain name
-        if let Some(first) = line.split_whitespace().next() {
-            let domain = first.trim_end_matches('.');
-            if domain.contains('.') || !domain.contains('@') {
-                domains.push(domain.to_string());
-            }
+    // Registry zone files carry thousands of glue records, so only the
+    // delegated names are imported; see `Zone::registrable_domains`.
+    match ZoneParser::new().lenient(true).parse(content) {
+        Ok(zone) => zone.registrable_domains(),
+        Err(e) => {
+            log::warn!("zone file parse failed: {}", e);
+            vec![]
         }
     }
-    domains
 }
 
 fn parse_ct_log(content: &str) -> Vec<String> {
@@ -244,6 +237,13 @@ mod tests {
         assert!(r.domains.contains(&"ns2.example.com".to_string()));
     }
 
+    #[test]
+    fn test_parse_zone_file_tld_delegations() {
+        let zone = "$ORIGIN com.\n$TTL 172800\n@ IN SOA a.gtld-servers.net. nstld.verisign-grs.com. (\n  1 1800 900 604800 86400 )\n  IN NS a.gtld-servers.net.\nexample IN NS ns1.example\n  IN NS ns2.example\nns1.example IN A 192.0.2.1\nother NS ns.host.net.\n";
+        let r = parse_import(zone, &ImportFormat::ZoneFile);
+        assert_eq!(r.domains, vec!["example.com", "other.com"]);
+    }
+
     #[test]
     fn test_parse_ct_log() {
         let ct =
@@ -0,0 +1,1114 @@
+//! RFC 1035 master-file ("zone file") parser.
+//!
+//! Handles `$ORIGIN`, `$TTL` and `$INCLUDE` directives, `@` and relative
+//! owner names, blank owners (inherit the previous owner), TTL/class in
+//! either order, parenthesised multi-line records (e.g. SOA), quoted strings
+//! and `;` comments. Records come out typed so callers can pick out NS
+//! delegations or glue without re-tokenising rdata.
+
+use std::collections::BTreeMap;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Errors produced while reading a master file.
+#[derive(Error, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ZoneError {
+    #[error("line {line}: {message}")]
+    Syntax { line: usize, message: String },
+
+    #[error("$INCLUDE {path}: {message}")]
+    Include { path: String, message: String },
+}
+
+/// DNS class of a record.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RecordClass {
+    In,
+    Cs,
+    Ch,
+    Hs,
+    /// `CLASSnnn` or anything else not listed above.
+    Other(String),
+}
+
+impl RecordClass {
+    /// Parse a class mnemonic, returning `None` if the token is not a class.
+    pub fn from_mnemonic(s: &str) -> Option<Self> {
+        let upper = s.to_ascii_uppercase();
+        match upper.as_str() {
+            "IN" => Some(Self::In),
+            "CS" => Some(Self::Cs),
+            "CH" => Some(Self::Ch),
+            "HS" => Some(Self::Hs),
+            _ if is_numbered(&upper, "CLASS") => Some(Self::Other(upper)),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::In => "IN",
+            Self::Cs => "CS",
+            Self::Ch => "CH",
+            Self::Hs => "HS",
+            Self::Other(s) => s,
+        }
+    }
+}
+
+/// DNS record type.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RecordType {
+    A,
+    Aaaa,
+    Ns,
+    Cname,
+    Soa,
+    Mx,
+    Txt,
+    Ptr,
+    Srv,
+    Ds,
+    Dnskey,
+    Caa,
+    /// Any other mnemonic or `TYPEnnn`, upper-cased.
+    Other(String),
+}
+
+impl RecordType {
+    /// Parse a type mnemonic. Unknown mnemonics map to `Other`.
+    pub fn from_mnemonic(s: &str) -> Self {
+        let upper = s.to_ascii_uppercase();
+        match upper.as_str() {
+            "A" => Self::A,
+            "AAAA" => Self::Aaaa,
+            "NS" => Self::Ns,
+            "CNAME" => Self::Cname,
+            "SOA" => Self::Soa,
+            "MX" => Self::Mx,
+            "TXT" => Self::Txt,
+            "PTR" => Self::Ptr,
+            "SRV" => Self::Srv,
+            "DS" => Self::Ds,
+            "DNSKEY" => Self::Dnskey,
+            "CAA" => Self::Caa,
+            _ => Self::Other(upper),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::A => "A",
+            Self::Aaaa => "AAAA",
+            Self::Ns => "NS",
+            Self::Cname => "CNAME",
+            Self::Soa => "SOA",
+            Self::Mx => "MX",
+            Self::Txt => "TXT",
+            Self::Ptr => "PTR",
+            Self::Srv => "SRV",
+            Self::Ds => "DS",
+            Self::Dnskey => "DNSKEY",
+            Self::Caa => "CAA",
+            Self::Other(s) => s,
+        }
+    }
+}
+
+/// Typed record data. Domain names are fully qualified, lower-case and
+/// without the trailing dot.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RData {
+    A {
+        address: Ipv4Addr,
+    },
+    Aaaa {
+        address: Ipv6Addr,
+    },
+    Ns {
+        nameserver: String,
+    },
+    Cname {
+        target: String,
+    },
+    Ptr {
+        target: String,
+    },
+    Mx {
+        preference: u16,
+        exchange: String,
+    },
+    Soa {
+        mname: String,
+        rname: String,
+        serial: u32,
+        refresh: u32,
+        retry: u32,
+        expire: u32,
+        minimum: u32,
+    },
+    Txt {
+        strings: Vec<String>,
+    },
+    Srv {
+        priority: u16,
+        weight: u16,
+        port: u16,
+        target: String,
+    },
+    /// Rdata for types without a dedicated variant, as raw tokens.
+    Other {
+        fields: Vec<String>,
+    },
+}
+
+/// A single resource record from a master file.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ZoneRecord {
+    /// Fully qualified owner name, lower-case, no trailing dot.
+    pub owner: String,
+    pub ttl: u32,
+    pub class: RecordClass,
+    pub rtype: RecordType,
+    pub rdata: RData,
+    /// 1-based line on which the record starts.
+    pub line: usize,
+}
+
+/// An NS delegation: a child name and the nameservers it is delegated to.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Delegation {
+    pub domain: String,
+    pub nameservers: Vec<String>,
+}
+
+/// A parsed zone.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Zone {
+    /// Zone apex: the SOA owner if present, otherwise the initial origin.
+    pub apex: Option<String>,
+    pub records: Vec<ZoneRecord>,
+    /// Problems skipped over when parsing leniently.
+    pub errors: Vec<ZoneError>,
+}
+
+impl Zone {
+    /// The SOA record, if the zone has one.
+    pub fn soa(&self) -> Option<&ZoneRecord> {
+        self.records.iter().find(|r| r.rtype == RecordType::Soa)
+    }
+
+    /// Records of the given type.
+    pub fn records_of<'a>(
+        &'a self,
+        rtype: &'a RecordType,
+    ) -> impl Iterator<Item = &'a ZoneRecord> + 'a {
+        self.records.iter().filter(move |r| &r.rtype == rtype)
+    }
+
+    /// NS delegations below the apex, grouped by child name in file order.
+    pub fn delegations(&self) -> Vec<Delegation> {
+        let mut order: Vec<String> = vec![];
+        let mut by_owner: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for record in &self.records {
+            let RData::Ns { nameserver } = &record.rdata else {
+                continue;
+            };
+            if self.apex.as_deref() == Some(record.owner.as_str()) {
+                continue;
+            }
+            let entry = by_owner.entry(record.owner.clone()).or_insert_with(|| {
+                order.push(record.owner.clone());
+                vec![]
+            });
+            if !entry.contains(nameserver) {
+                entry.push(nameserver.clone());
+            }
+        }
+        order
+            .into_iter()
+            .map(|domain| {
+                let nameservers = by_owner.remove(&domain).unwrap_or_default();
+                Delegation {
+                    domain,
+                    nameservers,
+                }
+            })
+            .collect()
+    }
+
+    /// Domain names worth importing from this zone.
+    ///
+    /// For a registry (TLD) zone these are the delegated children, so glue
+    /// records for nameserver hosts are not mistaken for registrations. A
+    /// zone without delegations yields every distinct non-apex, non-wildcard
+    /// owner instead.
+    pub fn registrable_domains(&self) -> Vec<String> {
+        let delegations = self.delegations();
+        if !delegations.is_empty() {
+            return delegations.into_iter().map(|d| d.domain).collect();
+        }
+        let mut seen = std::collections::HashSet::new();
+        self.records
+            .iter()
+            .map(|r| &r.owner)
+            .filter(|o| !o.is_empty() && !o.starts_with("*."))
+            .filter(|o| self.apex.as_deref() != Some(o.as_str()))
+            .filter(|o| seen.insert(o.to_string()))
+            .cloned()
+            .collect()
+    }
+}
+
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// Configurable master-file parser.
+#[derive(Debug, Clone, Default)]
+pub struct ZoneParser {
+    origin: Option<String>,
+    default_ttl: Option<u32>,
+    include_root: Option<PathBuf>,
+    lenient: bool,
+}
+
+impl ZoneParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Initial origin, used until the file sets `$ORIGIN`.
+    pub fn with_origin(mut self, origin: impl Into<String>) -> Self {
+        self.origin = Some(normalise_name(&origin.into()));
+        self
+    }
+
+    /// TTL for records before any `$TTL` or explicit TTL is seen.
+    pub fn with_default_ttl(mut self, ttl: u32) -> Self {
+        self.default_ttl = Some(ttl);
+        self
+    }
+
+    /// Directory `$INCLUDE` paths are resolved against. Without one,
+    /// `$INCLUDE` is rejected.
+    pub fn with_include_root(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.include_root = Some(dir.into());
+        self
+    }
+
+    /// Skip malformed entries and collect them in [`Zone::errors`] instead of
+    /// failing on the first one.
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Parse master-file content.
+    pub fn parse(&self, content: &str) -> Result<Zone, ZoneError> {
+        let mut state = ParseState {
+            origin: self.origin.clone().unwrap_or_default(),
+            default_ttl: self.default_ttl,
+            last_ttl: None,
+            last_owner: None,
+            last_class: RecordClass::In,
+            first_origin: self.origin.clone(),
+            records: vec![],
+            errors: vec![],
+        };
+        self.parse_into(content, &mut state, 0)?;
+
+        let apex = state
+            .records
+            .iter()
+            .find(|r| r.rtype == RecordType::Soa)
+            .map(|r| r.owner.clone())
+            .or(state.first_origin);
+
+        Ok(Zone {
+            apex,
+            records: state.records,
+            errors: state.errors,
+        })
+    }
+
+    /// Read and parse a file. `$INCLUDE` resolves against the file's
+    /// directory unless an include root was set explicitly.
+    pub fn parse_file(&self, path: &Path) -> Result<Zone, ZoneError> {
+        let content = std::fs::read_to_string(path).map_err(|e| ZoneError::Include {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })?;
+        let mut parser = self.clone();
+        if parser.include_root.is_none() {
+            parser.include_root = path.parent().map(Path::to_path_buf);
+        }
+        parser.parse(&content)
+    }
+
+    fn parse_into(
+        &self,
+        content: &str,
+        state: &mut ParseState,
+        depth: usize,
+    ) -> Result<(), ZoneError> {
+        for entry in tokenize(content) {
+            let entry = match entry {
+                Ok(e) => e,
+                Err(err) => {
+                    self.fail(state, err)?;
+                    continue;
+                }
+            };
+            let result = match entry.tokens.first() {
+                Some(first)
+                    if !entry.blank_owner && !first.quoted && first.text.starts_with('$') =>
+                {
+                    self.directive(&entry, state, depth)
+                }
+                _ => parse_record(&entry, state).map(|r| state.records.push(r)),
+            };
+            if let Err(err) = result {
+                self.fail(state, err)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn fail(&self, state: &mut ParseState, err: ZoneError) -> Result<(), ZoneError> {
+        if self.lenient {
+            log::debug!("zone parse: skipping {}", err);
+            state.errors.push(err);
+            Ok(())
+        } else {
+            Err(err)
+        }
+    }
+
+    fn directive(
+        &self,
+        entry: &Entry,
+        state: &mut ParseState,
+        depth: usize,
+    ) -> Result<(), ZoneError> {
+        let name = entry.tokens[0].text.to_ascii_uppercase();
+        let arg = |i: usize| {
+            entry
+                .tokens
+                .get(i)
+                .map(|t| t.text.as_str())
+                .ok_or_else(|| syntax(entry.line, format!("{} requires an argument", name)))
+        };
+        match name.as_str() {
+            "$ORIGIN" => {
+                state.origin = qualify(arg(1)?, &state.origin);
+                if state.first_origin.is_none() {
+                    state.first_origin = Some(state.origin.clone());
+                }
+                Ok(())
+            }
+            "$TTL" => {
+                let raw = arg(1)?;
+                let ttl = parse_ttl(raw)
+                    .ok_or_else(|| syntax(entry.line, format!("invalid $TTL '{}'", raw)))?;
+                state.default_ttl = Some(ttl);
+                Ok(())
+            }
+            "$INCLUDE" => {
+                let file = arg(1)?;
+                let Some(root) = &self.include_root else {
+                    return Err(ZoneError::Include {
+                        path: file.to_string(),
+                        message: "no include root configured".into(),
+                    });
+                };
+                if depth >= MAX_INCLUDE_DEPTH {
+                    return Err(ZoneError::Include {
+                        path: file.to_string(),
+                        message: format!("nesting deeper than {}", MAX_INCLUDE_DEPTH),
+                    });
+                }
+                let path = resolve_include(root, file)?;
+                let content = std::fs::read_to_string(&path).map_err(|e| ZoneError::Include {
+                    path: path.display().to_string(),
+                    message: e.to_string(),
+                })?;
+                // The included file may set its own origin; ours is restored
+                // afterwards (RFC 1035 §5.1).
+                let saved_origin = state.origin.clone();
+                if let Some(Token { text, .. }) = entry.tokens.get(2) {
+                    state.origin = qualify(text, &saved_origin);
+                }
+                let result = self.parse_into(&content, state, depth + 1);
+                state.origin = saved_origin;
+                result
+            }
+            other => Err(syntax(
+                entry.line,
+                format!("unsupported directive {}", other),
+            )),
+        }
+    }
+}
+
+/// `file` under `root`, refused when it resolves, through `..`, an
+/// absolute path or a symlink, to somewhere outside `root`.
+fn resolve_include(root: &Path, file: &str) -> Result<PathBuf, ZoneError> {
+    let error = |message: String| ZoneError::Include {
+        path: file.to_string(),
+        message,
+    };
+    let root = match root.as_os_str().is_empty() {
+        true => Path::new("."),
+        false => root,
+    };
+    let root = root.canonicalize().map_err(|e| error(e.to_string()))?;
+    let path = root
+        .join(file)
+        .canonicalize()
+        .map_err(|e| error(e.to_string()))?;
+    if !path.starts_with(&root) {
+        return Err(error("outside the include root".into()));
+    }
+    Ok(path)
+}
+
+/// Parse master-file content with default settings.
+pub fn parse_zone(content: &str) -> Result<Zone, ZoneError> {
+    ZoneParser::new().parse(content)
+}
+
+// ─── Record parsing ──────────────────────────────────────────────────────────
+
+struct ParseState {
+    origin: String,
+    default_ttl: Option<u32>,
+    last_ttl: Option<u32>,
+    last_owner: Option<String>,
+    last_class: RecordClass,
+    first_origin: Option<String>,
+    records: Vec<ZoneRecord>,
+    errors: Vec<ZoneError>,
+}
+
+fn parse_record(entry: &Entry, state: &mut ParseState) -> Result<ZoneRecord, ZoneError> {
+    let line = entry.line;
+    let mut tokens = entry.tokens.iter().peekable();
+
+    let owner = if entry.blank_owner {
+        state
+            .last_owner
+            .clone()
+            .ok_or_else(|| syntax(line, "record has no owner and none to inherit"))?
+    } else {
+        let tok = tokens.next().ok_or_else(|| syntax(line, "empty record"))?;
+        qualify(&tok.text, &state.origin)
+    };
+
+    // TTL and class are both optional and may appear in either order.
+    let mut ttl = None;
+    let mut class = None;
+    for _ in 0..2 {
+        let Some(tok) = tokens.peek() else { break };
+        if ttl.is_none() {
+            if let Some(t) = parse_ttl(&tok.text) {
+                ttl = Some(t);
+                tokens.next();
+                continue;
+            }
+        }
+        if class.is_none() {
+            if let Some(c) = RecordClass::from_mnemonic(&tok.text) {
+                class = Some(c);
+                tokens.next();
+                continue;
+            }
+        }
+        break;
+    }
+
+    let type_tok = tokens
+        .next()
+        .ok_or_else(|| syntax(line, "missing record type"))?;
+    let rtype = RecordType::from_mnemonic(&type_tok.text);
+    let fields: Vec<&Token> = tokens.collect();
+    let rdata = parse_rdata(&rtype, &fields, &state.origin, line)?;
+
+    let ttl = match ttl {
+        Some(t) => {
+            state.last_ttl = Some(t);
+            t
+        }
+        None => state
+            .default_ttl
+            .or(state.last_ttl)
+            .or(match &rdata {
+                RData::Soa { minimum, .. } => Some(*minimum),
+                _ => None,
+            })
+            .unwrap_or(0),
+    };
+    let class = class.unwrap_or_else(|| state.last_class.clone());
+    state.last_class = class.clone();
+    state.last_owner = Some(owner.clone());
+
+    Ok(ZoneRecord {
+        owner,
+        ttl,
+        class,
+        rtype,
+        rdata,
+        line,
+    })
+}
+
+fn parse_rdata(
+    rtype: &RecordType,
+    fields: &[&Token],
+    origin: &str,
+    line: usize,
+) -> Result<RData, ZoneError> {
+    let need = |n: usize| {
+        if fields.len() < n {
+            Err(syntax(
+                line,
+                format!(
+                    "{} needs {} rdata fields, got {}",
+                    rtype.as_str(),
+                    n,
+                    fields.len()
+                ),
+            ))
+        } else {
+            Ok(())
+        }
+    };
+    let num = |i: usize| -> Result<u32, ZoneError> {
+        parse_ttl(&fields[i].text).ok_or_else(|| {
+            syntax(
+                line,
+                format!("invalid number '{}' in {}", fields[i].text, rtype.as_str()),
+            )
+        })
+    };
+    let short = |i: usize| -> Result<u16, ZoneError> {
+        fields[i].text.parse::<u16>().map_err(|_| {
+            syntax(
+                line,
+                format!("invalid number '{}' in {}", fields[i].text, rtype.as_str()),
+            )
+        })
+    };
+    let name = |i: usize| qualify(&fields[i].text, origin);
+
+    let rdata = match rtype {
+        RecordType::A => {
+            need(1)?;
+            RData::A {
+                address: fields[0]
+                    .text
+                    .parse()
+                    .map_err(|_| syntax(line, format!("invalid IPv4 '{}'", fields[0].text)))?,
+            }
+        }
+        RecordType::Aaaa => {
+            need(1)?;
+            RData::Aaaa {
+                address: fields[0]
+                    .text
+                    .parse()
+                    .map_err(|_| syntax(line, format!("invalid IPv6 '{}'", fields[0].text)))?,
+            }
+        }
+        RecordType::Ns => {
+            need(1)?;
+            RData::Ns {
+                nameserver: name(0),
+            }
+        }
+        RecordType::Cname => {
+            need(1)?;
+            RData::Cname { target: name(0) }
+        }
+        RecordType::Ptr => {
+            need(1)?;
+            RData::Ptr { target: name(0) }
+        }
+        RecordType::Mx => {
+            need(2)?;
+            RData::Mx {
+                preference: short(0)?,
+                exchange: name(1),
+            }
+        }
+        RecordType::Soa => {
+            need(7)?;
+            RData::Soa {
+                mname: name(0),
+                rname: name(1),
+                serial: fields[2].text.parse().map_err(|_| {
+                    syntax(line, format!("invalid SOA serial '{}'", fields[2].text))
+                })?,
+                refresh: num(3)?,
+                retry: num(4)?,
+                expire: num(5)?,
+                minimum: num(6)?,
+            }
+        }
+        RecordType::Txt => {
+            need(1)?;
+            RData::Txt {
+                strings: fields.iter().map(|t| t.text.clone()).collect(),
+            }
+        }
+        RecordType::Srv => {
+            need(4)?;
+            RData::Srv {
+                priority: short(0)?,
+                weight: short(1)?,
+                port: short(2)?,
+                target: name(3),
+            }
+        }
+        _ => RData::Other {
+            fields: fields.iter().map(|t| t.text.clone()).collect(),
+        },
+    };
+    Ok(rdata)
+}
+
+/// Parse a TTL: plain seconds or BIND-style units (`1h30m`, `2d`, `1w`).
+fn parse_ttl(s: &str) -> Option<u32> {
+    if !s.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    if let Ok(n) = s.parse::<u32>() {
+        return Some(n);
+    }
+    let mut total: u64 = 0;
+    let mut current: u64 = 0;
+    let mut pending = false;
+    for c in s.chars() {
+        if let Some(d) = c.to_digit(10) {
+            current = current.checked_mul(10)?.checked_add(d as u64)?;
+            pending = true;
+            continue;
+        }
+        let unit = match c.to_ascii_lowercase() {
+            's' => 1,
+            'm' => 60,
+            'h' => 3_600,
+            'd' => 86_400,
+            'w' => 604_800,
+            _ => return None,
+        };
+        if !pending {
+            return None;
+        }
+        total = total.checked_add(current.checked_mul(unit)?)?;
+        current = 0;
+        pending = false;
+    }
+    total = total.checked_add(current)?;
+    u32::try_from(total).ok()
+}
+
+fn is_numbered(upper: &str, prefix: &str) -> bool {
+    upper
+        .strip_prefix(prefix)
+        .map(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+        .unwrap_or(false)
+}
+
+/// Lower-case a name and drop the trailing dot.
+fn normalise_name(name: &str) -> String {
+    name.trim_end_matches('.').to_lowercase()
+}
+
+/// Resolve `@` and relative names against the origin.
+fn qualify(name: &str, origin: &str) -> String {
+    if name == "@" {
+        return origin.to_string();
+    }
+    if name.ends_with('.') {
+        return normalise_name(name);
+    }
+    if origin.is_empty() {
+        return name.to_lowercase();
+    }
+    format!("{}.{}", name.to_lowercase(), origin)
+}
+
+fn syntax(line: usize, message: impl Into<String>) -> ZoneError {
+    ZoneError::Syntax {
+        line,
+        message: message.into(),
+    }
+}
+
+// ─── Tokenizer ───────────────────────────────────────────────────────────────
+
+#[derive(Debug)]
+struct Token {
+    text: String,
+    quoted: bool,
+}
+
+/// One logical entry: a line, or several joined by parentheses.
+#[derive(Debug)]
+struct Entry {
+    line: usize,
+    /// The entry started with whitespace, so the owner is inherited.
+    blank_owner: bool,
+    tokens: Vec<Token>,
+}
+
+fn tokenize(content: &str) -> Vec<Result<Entry, ZoneError>> {
+    let mut entries = vec![];
+    let mut chars = content.chars().peekable();
+    let mut line = 1;
+    let mut depth = 0usize;
+    let mut current = Entry {
+        line,
+        blank_owner: false,
+        tokens: vec![],
+    };
+    let mut at_line_start = true;
+    let mut paren_line = 0;
+
+    let flush = |entries: &mut Vec<Result<Entry, ZoneError>>, current: &mut Entry, next: usize| {
+        let done = std::mem::replace(
+            current,
+            Entry {
+                line: next,
+                blank_owner: false,
+                tokens: vec![],
+            },
+        );
+        if !done.tokens.is_empty() {
+            entries.push(Ok(done));
+        }
+    };
+
+    while let Some(c) = chars.next() {
+        if at_line_start && depth == 0 {
+            current.line = line;
+            current.blank_owner = c == ' ' || c == '\t';
+        }
+        at_line_start = false;
+        match c {
+            '\n' => {
+                line += 1;
+                at_line_start = true;
+                if depth == 0 {
+                    flush(&mut entries, &mut current, line);
+                }
+            }
+            ' ' | '\t' | '\r' => {}
+            ';' => {
+                while chars.peek().is_some_and(|&n| n != '\n') {
+                    chars.next();
+                }
+            }
+            '(' => {
+                if depth == 0 {
+                    paren_line = line;
+                }
+                depth += 1;
+            }
+            ')' => {
+                if depth == 0 {
+                    entries.push(Err(syntax(line, "unbalanced ')'")));
+                } else {
+                    depth -= 1;
+                }
+            }
+            '"' => {
+                let mut text = String::new();
+                let mut closed = false;
+                while let Some(n) = chars.next() {
+                    match n {
+                        '"' => {
+                            closed = true;
+                            break;
+                        }
+                        '\\' => text.push(read_escape(&mut chars)),
+                        '\n' => {
+                            line += 1;
+                            text.push(n);
+                        }
+                        _ => text.push(n),
+                    }
+                }
+                if !closed {
+                    entries.push(Err(syntax(current.line, "unterminated quoted string")));
+                }
+                current.tokens.push(Token { text, quoted: true });
+            }
+            _ => {
+                let mut text = String::new();
+                let mut c = c;
+                loop {
+                    if c == '\\' {
+                        // Keep escapes in names verbatim; they are rare and
+                        // only meaningful to a wire-format encoder.
+                        text.push(c);
+                        if let Some(n) = chars.next() {
+                            text.push(n);
+                        }
+                    } else {
+                        text.push(c);
+                    }
+                    match chars.peek() {
+                        Some(&n)
+                            if !matches!(n, ' ' | '\t' | '\r' | '\n' | ';' | '(' | ')' | '"') =>
+                        {
+                            c = n;
+                            chars.next();
+                        }
+                        _ => break,
+                    }
+                }
+                current.tokens.push(Token {
+                    text,
+                    quoted: false,
+                });
+            }
+        }
+    }
+
+    if depth > 0 {
+        entries.push(Err(syntax(paren_line, "unclosed '('")));
+    } else {
+        flush(&mut entries, &mut current, line);
+    }
+    entries
+}
+
+/// Decode the character after a backslash inside a quoted string:
+/// `\DDD` is a decimal byte, anything else is taken literally.
+fn read_escape(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> char {
+    let mut digits = String::new();
+    while digits.len() < 3 && chars.peek().is_some_and(|c| c.is_ascii_digit()) {
+        digits.push(chars.next().unwrap_or('0'));
+    }
+    if digits.is_empty() {
+        return chars.next().unwrap_or('\\');
+    }
+    digits
+        .parse::<u8>()
+        .map(char::from)
+        .unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ZONE: &str = r#"
+$ORIGIN example.com.
+$TTL 1h
+@   IN  SOA ns1.example.com. hostmaster (
+        2024010101 ; serial
+        7200       ; refresh
+        1h         ; retry
+        2w         ; expire
+        300 )      ; minimum
+    IN  NS  ns1
+    IN  NS  ns2.example.net.
+www 600 IN A 192.0.2.1
+        IN AAAA 2001:db8::1
+mail    MX 10 mx1
+txt     TXT "hello; world" "with \"quotes\""
+"#;
+
+    #[test]
+    fn test_soa_multiline() {
+        let zone = parse_zone(ZONE).unwrap();
+        let soa = zone.soa().expect("soa");
+        assert_eq!(soa.owner, "example.com");
+        assert_eq!(soa.ttl, 3600);
+        assert_eq!(
+            soa.rdata,
+            RData::Soa {
+                mname: "ns1.example.com".into(),
+                rname: "hostmaster.example.com".into(),
+                serial: 2024010101,
+                refresh: 7200,
+                retry: 3600,
+                expire: 1_209_600,
+                minimum: 300,
+            }
+        );
+        assert_eq!(zone.apex.as_deref(), Some("example.com"));
+    }
+
+    #[test]
+    fn test_blank_owner_and_relative_names() {
+        let zone = parse_zone(ZONE).unwrap();
+        let ns: Vec<_> = zone.records_of(&RecordType::Ns).collect();
+        assert_eq!(ns.len(), 2);
+        assert!(ns.iter().all(|r| r.owner == "example.com"));
+        assert_eq!(
+            ns[0].rdata,
+            RData::Ns {
+                nameserver: "ns1.example.com".into()
+            }
+        );
+        assert_eq!(
+            ns[1].rdata,
+            RData::Ns {
+                nameserver: "ns2.example.net".into()
+            }
+        );
+
+        let aaaa = zone.records_of(&RecordType::Aaaa).next().unwrap();
+        assert_eq!(aaaa.owner, "www.example.com");
+        assert_eq!(aaaa.ttl, 3600);
+    }
+
+    #[test]
+    fn test_ttl_and_class_inheritance() {
+        let zone = parse_zone(ZONE).unwrap();
+        let a = zone.records_of(&RecordType::A).next().unwrap();
+        assert_eq!(a.ttl, 600);
+        let mx = zone.records_of(&RecordType::Mx).next().unwrap();
+        assert_eq!(mx.class, RecordClass::In);
+        assert_eq!(
+            mx.rdata,
+            RData::Mx {
+                preference: 10,
+                exchange: "mx1.example.com".into()
+            }
+        );
+    }
+
+    #[test]
+    fn test_quoted_strings_and_comments() {
+        let zone = parse_zone(ZONE).unwrap();
+        let txt = zone.records_of(&RecordType::Txt).next().unwrap();
+        assert_eq!(
+            txt.rdata,
+            RData::Txt {
+                strings: vec!["hello; world".into(), "with \"quotes\"".into()]
+            }
+        );
+    }
+
+    #[test]
+    fn test_class_before_ttl() {
+        let zone = parse_zone("a.example. IN 120 A 192.0.2.5\n").unwrap();
+        assert_eq!(zone.records[0].ttl, 120);
+        assert_eq!(zone.records[0].class, RecordClass::In);
+    }
+
+    #[test]
+    fn test_tld_zone_delegations_skip_glue() {
+        let zone = "com.\t900\tin\tsoa\ta.gtld-servers.net. nstld.verisign-grs.com. 1 1800 900 604800 86400\n\
+                    com.\t172800\tin\tns\ta.gtld-servers.net.\n\
+                    example.com.\t172800\tin\tns\tns1.example.com.\n\
+                    example.com.\t172800\tin\tns\tns2.example.com.\n\
+                    ns1.example.com.\t172800\tin\ta\t192.0.2.53\n\
+                    other.com.\t172800\tin\tns\tns.host.net.\n";
+        let zone = parse_zone(zone).unwrap();
+        let delegations = zone.delegations();
+        assert_eq!(delegations.len(), 2);
+        assert_eq!(delegations[0].domain, "example.com");
+        assert_eq!(
+            delegations[0].nameservers,
+            vec!["ns1.example.com", "ns2.example.com"]
+        );
+        assert_eq!(
+            zone.registrable_domains(),
+            vec!["example.com".to_string(), "other.com".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_origin_relative_to_previous_origin() {
+        let zone = parse_zone("$ORIGIN com.\n$ORIGIN example\nwww 60 IN A 192.0.2.1\n").unwrap();
+        assert_eq!(zone.records[0].owner, "www.example.com");
+    }
+
+    #[test]
+    fn test_unknown_type_kept_as_other() {
+        let zone = parse_zone("x.example. 60 IN TYPE65 \\# 0\n").unwrap();
+        assert_eq!(zone.records[0].rtype, RecordType::Other("TYPE65".into()));
+    }
+
+    #[test]
+    fn test_include_without_root_is_error() {
+        let err = parse_zone("$INCLUDE other.zone\n").unwrap_err();
+        assert!(matches!(err, ZoneError::Include { .. }));
+    }
+
+    #[test]
+    fn test_include_resolves_relative_to_file() {
+        let dir = std::env::temp_dir().join(format!("wd-import-zone-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("child.zone"), "www 60 IN A 192.0.2.9\n").unwrap();
+        std::fs::write(
+            dir.join("main.zone"),
+            "$ORIGIN example.com.\n$INCLUDE child.zone sub.example.com.\nmail 60 IN A 192.0.2.10\n",
+        )
+        .unwrap();
+
+        let zone = ZoneParser::new()
+            .parse_file(&dir.join("main.zone"))
+            .unwrap();
+        let owners: Vec<_> = zone.records.iter().map(|r| r.owner.as_str()).collect();
+        assert_eq!(owners, vec!["www.sub.example.com", "mail.example.com"]);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_include_outside_root_is_error() {
+        let base = std::env::temp_dir().join(format!("wd-import-escape-{}", std::process::id()));
+        let root = base.join("zones");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(base.join("secret.zone"), "www 60 IN A 192.0.2.9\n").unwrap();
+        let parser = ZoneParser::new().with_include_root(&root);
+
+        for include in ["../secret.zone", base.join("secret.zone").to_str().unwrap()] {
+            let err = parser
+                .parse(&format!("$ORIGIN example.com.\n$INCLUDE {}\n", include))
+                .unwrap_err();
+            assert!(
+                matches!(&err, ZoneError::Include { message, .. } if message == "outside the include root"),
+                "{include}: {err}"
+            );
+        }
+
+        std::fs::remove_dir_all(&base).ok();
+    }
+
+    #[test]
+    fn test_strict_rejects_bad_rdata() {
+        let err = parse_zone("a.example. 60 IN A not-an-ip\n").unwrap_err();
+        assert!(matches!(err, ZoneError::Syntax { line: 1, .. }));
+    }
+
+    #[test]
+    fn test_lenient_collects_errors() {
+        let zone = ZoneParser::new()
+            .lenient(true)
+            .parse("a.example. 60 IN A nope\nb.example. 60 IN A 192.0.2.1\n(\n")
+            .unwrap();
+        assert_eq!(zone.records.len(), 1);
+        assert_eq!(zone.errors.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_ttl_units() {
+        assert_eq!(parse_ttl("3600"), Some(3600));
+        assert_eq!(parse_ttl("1h30m"), Some(5400));
+        assert_eq!(parse_ttl("1W"), Some(604_800));
+        assert_eq!(parse_ttl("IN"), None);
+        assert_eq!(parse_ttl("1x"), None);
+    }
+}
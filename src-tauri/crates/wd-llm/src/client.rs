@@ -0,0 +1,694 @@
+//! Async HTTP transport: sends `CompletionRequest`s through a provider with
+//! retries, timeouts, proxy support and cancellation, and drives SSE
+//! streams into `StreamChunk`s.
+
+use std::collections::{HashSet, VecDeque};
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures::{Stream, StreamExt};
+use tokio::sync::Notify;
+
+use crate::config::LlmConfig;
+use crate::error::LlmError;
+use crate::provider::LlmProvider;
+use crate::providers::{
+    apply_cost, provider_from_config, request, retry_after, send_error, with_retry_after,
+};
+use crate::request::CompletionRequest;
+use crate::response::CompletionResponse;
+use crate::stream::{SseDecoder, SseEvent, StreamChunk, TextAccumulator};
+use crate::token::TokenEstimator;
+
+/// Boxed chunk stream returned by [`LlmClient::complete_stream`].
+pub type ChunkStream = Pin<Box<dyn Stream<Item = StreamChunk> + Send>>;
+
+/// Cloneable cancellation handle shared between a client and its caller.
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken {
+    inner: Arc<CancelInner>,
+}
+
+#[derive(Debug, Default)]
+struct CancelInner {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel every request using this token, including in-flight ones.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        self.inner.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once [`cancel`](Self::cancel) has been called.
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.inner.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Executes completion requests against one provider.
+pub struct LlmClient {
+    config: LlmConfig,
+    provider: Arc<dyn LlmProvider>,
+    http: reqwest::Client,
+    base_backoff: Duration,
+    max_backoff: Duration,
+    cancel: CancelToken,
+    estimator: TokenEstimator,
+}
+
+impl LlmClient {
+    /// Build a client for the provider named by `config.provider`.
+    pub fn new(config: LlmConfig) -> Result<Self, LlmError> {
+        let provider: Arc<dyn LlmProvider> = Arc::from(provider_from_config(&config)?);
+        Self::with_provider(config, provider)
+    }
+
+    /// Build a client around an already constructed provider. `config`
+    /// still supplies timeouts, retries and the proxy.
+    pub fn with_provider(
+        config: LlmConfig,
+        provider: Arc<dyn LlmProvider>,
+    ) -> Result<Self, LlmError> {
+        let timeout = Duration::from_secs(config.timeout_secs.max(1));
+        // The read timeout bounds each wait for bytes, so long streams are
+        // fine as long as the server keeps sending.
+        let mut builder = reqwest::Client::builder()
+            .connect_timeout(timeout)
+            .read_timeout(timeout);
+        if let Some(proxy) = config.proxy.as_deref().filter(|p| !p.is_empty()) {
+            let proxy = reqwest::Proxy::all(proxy)
+                .map_err(|e| LlmError::Config(format!("invalid proxy '{proxy}': {e}")))?;
+            builder = builder.proxy(proxy);
+        }
+        let http = builder
+            .build()
+            .map_err(|e| LlmError::Config(format!("failed to build HTTP client: {e}")))?;
+        let estimator = TokenEstimator::for_model(
+            provider.kind(),
+            &config.model,
+            config.tokenizer_dir.as_deref().map(Path::new),
+        );
+        Ok(Self {
+            config,
+            provider,
+            http,
+            base_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            cancel: CancelToken::new(),
+            estimator,
+        })
+    }
+
+    /// Set the initial retry delay (doubled per attempt) and its ceiling.
+    /// The ceiling also caps server-requested `Retry-After` delays.
+    pub fn with_backoff(mut self, base: Duration, max: Duration) -> Self {
+        self.base_backoff = base;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Share a cancellation token with the caller.
+    pub fn with_cancel_token(mut self, token: CancelToken) -> Self {
+        self.cancel = token;
+        self
+    }
+
+    pub fn cancel_token(&self) -> CancelToken {
+        self.cancel.clone()
+    }
+
+    /// Replace the token counter chosen from the config.
+    pub fn with_token_estimator(mut self, estimator: TokenEstimator) -> Self {
+        self.estimator = estimator;
+        self
+    }
+
+    /// Token counter for this client's model.
+    pub fn token_estimator(&self) -> &TokenEstimator {
+        &self.estimator
+    }
+
+    pub fn provider(&self) -> &dyn LlmProvider {
+        self.provider.as_ref()
+    }
+
+    pub fn config(&self) -> &LlmConfig {
+        &self.config
+    }
+
+    /// Send a non-streaming request, retrying transient failures. Usage the
+    /// provider does not report is counted locally.
+    pub async fn complete(&self, req: &CompletionRequest) -> Result<CompletionResponse, LlmError> {
+        let mut req = req.clone();
+        req.stream = false;
+        let (res, started) = self.send_with_retry(&req).await?;
+        let status = res.status().as_u16();
+        let text = tokio::select! {
+            text = res.text() => text.map_err(|e| send_error(e, self.config.timeout_secs))?,
+            _ = self.cancel.cancelled() => return Err(LlmError::Cancelled),
+        };
+        let mut res =
+            self.provider
+                .parse_response(status, &text, started.elapsed().as_millis() as u64)?;
+        if res.usage.total_tokens == 0 {
+            res.usage = self.estimator.estimate_usage(&req, &res.message);
+            apply_cost(
+                &mut res.usage,
+                &self.provider.supported_models(),
+                &req.model,
+            );
+        }
+        Ok(res)
+    }
+
+    /// Embed `inputs` with `model`, returning one vector per input in the
+    /// same order. Retries apply as for [`complete`](Self::complete).
+    pub async fn embed(&self, model: &str, inputs: &[String]) -> Result<Vec<Vec<f32>>, LlmError> {
+        if !self.provider.supports_embeddings() {
+            return Err(LlmError::Unsupported(format!(
+                "{} does not support embeddings",
+                self.provider.display_name()
+            )));
+        }
+        if inputs.is_empty() {
+            return Ok(Vec::new());
+        }
+        let body = self.provider.build_embeddings_body(model, inputs)?;
+        let url = self.provider.embeddings_endpoint(model);
+        let (res, _) = self
+            .retrying(
+                || {
+                    let mut builder = self
+                        .http
+                        .post(&url)
+                        .json(&body)
+                        .timeout(Duration::from_secs(self.config.timeout_secs.max(1)));
+                    for (name, value) in self.provider.headers() {
+                        builder = builder.header(name, value);
+                    }
+                    builder
+                },
+                |status, text| match self.provider.parse_embeddings(status, text) {
+                    Err(e) => e,
+                    Ok(_) => LlmError::Http {
+                        status,
+                        body: text.to_string(),
+                    },
+                },
+            )
+            .await?;
+        let status = res.status().as_u16();
+        let text = tokio::select! {
+            text = res.text() => text.map_err(|e| send_error(e, self.config.timeout_secs))?,
+            _ = self.cancel.cancelled() => return Err(LlmError::Cancelled),
+        };
+        let vectors = self.provider.parse_embeddings(status, &text)?;
+        if vectors.len() != inputs.len() {
+            return Err(LlmError::Other(format!(
+                "expected {} embeddings, got {}",
+                inputs.len(),
+                vectors.len()
+            )));
+        }
+        Ok(vectors)
+    }
+
+    /// Send a streaming request. Retries apply until the response headers
+    /// arrive; afterwards failures surface as `StreamChunk::Error`.
+    ///
+    /// Tool-call ids are normalised so every delta of one call carries the
+    /// id it started with, and exactly one `Done` chunk is emitted.
+    pub async fn complete_stream(&self, req: &CompletionRequest) -> Result<ChunkStream, LlmError> {
+        if !self.provider.supports_streaming() {
+            return Err(LlmError::Unsupported(format!(
+                "{} does not support streaming",
+                self.provider.display_name()
+            )));
+        }
+        let mut req = req.clone();
+        req.stream = true;
+        let (res, _) = self.send_with_retry(&req).await?;
+
+        let state = StreamState {
+            bytes: Box::pin(res.bytes_stream().map(|b| b.map(|b| b.to_vec()))),
+            decoder: SseDecoder::new(),
+            pending: VecDeque::new(),
+            provider: Arc::clone(&self.provider),
+            cancel: self.cancel.clone(),
+            timeout_secs: self.config.timeout_secs,
+            tool_ids: HashSet::new(),
+            current_tool: None,
+            done: false,
+            finished: false,
+        };
+        Ok(Box::pin(futures::stream::unfold(
+            state,
+            |mut state| async move { state.next_chunk().await.map(|chunk| (chunk, state)) },
+        )))
+    }
+
+    /// Stream a request to completion, calling `on_chunk` for each chunk
+    /// (e.g. to forward tokens to the UI) and returning the accumulated
+    /// text, tool calls and usage.
+    pub async fn complete_streaming<F>(
+        &self,
+        req: &CompletionRequest,
+        mut on_chunk: F,
+    ) -> Result<TextAccumulator, LlmError>
+    where
+        F: FnMut(&StreamChunk),
+    {
+        let mut stream = self.complete_stream(req).await?;
+        let mut acc = TextAccumulator::new();
+        while let Some(chunk) = stream.next().await {
+            on_chunk(&chunk);
+            if let StreamChunk::Error(e) = chunk {
+                return Err(e);
+            }
+            acc.push(&chunk);
+        }
+        Ok(acc)
+    }
+
+    /// POST the request until a 2xx response arrives, backing off between
+    /// retryable failures.
+    async fn send_with_retry(
+        &self,
+        req: &CompletionRequest,
+    ) -> Result<(reqwest::Response, Instant), LlmError> {
+        let body = self.provider.build_request_body(req)?;
+        self.retrying(
+            || {
+                let builder = request(&self.http, self.provider.as_ref(), req, &body);
+                if req.stream {
+                    builder
+                } else {
+                    builder.timeout(Duration::from_secs(self.config.timeout_secs.max(1)))
+                }
+            },
+            |status, text| match self.provider.parse_response(status, text, 0) {
+                Err(e) => e,
+                Ok(_) => LlmError::Http {
+                    status,
+                    body: text.to_string(),
+                },
+            },
+        )
+        .await
+    }
+
+    /// Send the request built by `build` until it succeeds, retrying
+    /// transient failures with backoff. `error` maps a non-2xx response to
+    /// the error it represents.
+    async fn retrying(
+        &self,
+        build: impl Fn() -> reqwest::RequestBuilder,
+        error: impl Fn(u16, &str) -> LlmError,
+    ) -> Result<(reqwest::Response, Instant), LlmError> {
+        let mut attempt = 0u32;
+        loop {
+            if self.cancel.is_cancelled() {
+                return Err(LlmError::Cancelled);
+            }
+            let builder = build();
+
+            let started = Instant::now();
+            let sent = tokio::select! {
+                sent = builder.send() => sent,
+                _ = self.cancel.cancelled() => return Err(LlmError::Cancelled),
+            };
+            let (err, delay_hint) = match sent {
+                Ok(res) if res.status().is_success() => return Ok((res, started)),
+                Ok(res) => {
+                    let status = res.status().as_u16();
+                    let hint = retry_after(res.headers());
+                    let text = res.text().await.unwrap_or_default();
+                    (with_retry_after(error(status, &text), hint), hint)
+                }
+                Err(e) => (send_error(e, self.config.timeout_secs), None),
+            };
+
+            if !err.is_retryable() || attempt >= self.config.max_retries {
+                return Err(err);
+            }
+            let delay = delay_hint
+                .map(Duration::from_secs)
+                .unwrap_or_else(|| self.base_backoff.saturating_mul(1 << attempt.min(16)))
+                .min(self.max_backoff);
+            log::warn!(
+                "{} request failed ({err}); retry {}/{} in {:?}",
+                self.provider.display_name(),
+                attempt + 1,
+                self.config.max_retries,
+                delay
+            );
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = self.cancel.cancelled() => return Err(LlmError::Cancelled),
+            }
+            attempt += 1;
+        }
+    }
+}
+
+type ByteStream = Pin<Box<dyn Stream<Item = reqwest::Result<Vec<u8>>> + Send>>;
+
+struct StreamState {
+    bytes: ByteStream,
+    decoder: SseDecoder,
+    pending: VecDeque<StreamChunk>,
+    provider: Arc<dyn LlmProvider>,
+    cancel: CancelToken,
+    timeout_secs: u64,
+    tool_ids: HashSet<String>,
+    current_tool: Option<String>,
+    done: bool,
+    finished: bool,
+}
+
+impl StreamState {
+    async fn next_chunk(&mut self) -> Option<StreamChunk> {
+        loop {
+            if let Some(chunk) = self.pending.pop_front() {
+                return Some(chunk);
+            }
+            if self.finished {
+                return None;
+            }
+            let next = tokio::select! {
+                next = self.bytes.next() => next,
+                _ = self.cancel.cancelled() => {
+                    self.finished = true;
+                    return Some(StreamChunk::Error(LlmError::Cancelled));
+                }
+            };
+            match next {
+                Some(Ok(bytes)) => {
+                    for event in self.decoder.push(&bytes) {
+                        self.enqueue(&event);
+                    }
+                }
+                Some(Err(e)) => {
+                    self.finished = true;
+                    self.pending
+                        .push_back(StreamChunk::Error(send_error(e, self.timeout_secs)));
+                }
+                None => {
+                    self.finished = true;
+                    if let Some(event) = self.decoder.finish() {
+                        self.enqueue(&event);
+                    }
+                    if !self.done {
+                        self.pending
+                            .push_back(StreamChunk::Error(LlmError::StreamParse(
+                                "stream ended before completion".into(),
+                            )));
+                    }
+                }
+            }
+        }
+    }
+
+    fn enqueue(&mut self, event: &SseEvent) {
+        for chunk in self.provider.parse_stream_event(event) {
+            match chunk {
+                // Providers finish with both a reason and a terminator
+                // (`[DONE]`, `message_stop`); keep only the first.
+                StreamChunk::Done(_) if self.done => {}
+                StreamChunk::Done(reason) => {
+                    self.done = true;
+                    self.pending.push_back(StreamChunk::Done(reason));
+                }
+                StreamChunk::ToolCallDelta {
+                    id,
+                    name,
+                    arguments_delta,
+                } => {
+                    let id = self.tool_call_id(id, name.is_some());
+                    self.pending.push_back(StreamChunk::ToolCallDelta {
+                        id,
+                        name,
+                        arguments_delta,
+                    });
+                }
+                StreamChunk::Error(e) => {
+                    self.done = true;
+                    self.finished = true;
+                    self.pending.push_back(StreamChunk::Error(e));
+                }
+                other => self.pending.push_back(other),
+            }
+        }
+    }
+
+    /// Continuation deltas identify their call by position (OpenAI sends no
+    /// id, Anthropic the block index); attribute them to the call in flight.
+    fn tool_call_id(&mut self, id: String, starts_call: bool) -> String {
+        if starts_call {
+            let id = if id.is_empty() {
+                format!("call_{}", uuid::Uuid::new_v4().simple())
+            } else {
+                id
+            };
+            self.tool_ids.insert(id.clone());
+            self.current_tool = Some(id.clone());
+            id
+        } else if self.tool_ids.contains(&id) {
+            id
+        } else {
+            self.current_tool.clone().unwrap_or(id)
+        }
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::Message;
+    use crate::provider::ProviderKind;
+    use crate::providers::mock;
+    use crate::response::FinishReason;
+
+    const SSE: &str = "text/event-stream";
+
+    fn client(url: &str, kind: ProviderKind, model: &str) -> LlmClient {
+        let cfg = LlmConfig::new(kind, model)
+            .with_api_key("k")
+            .with_api_url(url)
+            .with_max_retries(2);
+        LlmClient::new(cfg)
+            .unwrap()
+            .with_backoff(Duration::from_millis(5), Duration::from_millis(20))
+    }
+
+    fn ok_body() -> String {
+        r#"{"id":"c1","model":"gpt-4o","choices":[{"message":{"role":"assistant",
+            "content":"ok"},"finish_reason":"stop"}],
+            "usage":{"prompt_tokens":1,"completion_tokens":1}}"#
+            .into()
+    }
+
+    #[tokio::test]
+    async fn test_complete_retries_transient_errors() {
+        let (url, server) = mock::serve(vec![
+            (
+                503,
+                "application/json",
+                r#"{"error":{"message":"busy"}}"#.into(),
+            ),
+            (
+                429,
+                "application/json",
+                r#"{"error":{"message":"slow"}}"#.into(),
+            ),
+            (200, "application/json", ok_body()),
+        ])
+        .await;
+        let c = client(&url, ProviderKind::OpenAi, "gpt-4o");
+        let req = CompletionRequest::new("gpt-4o", vec![Message::user("hi")]);
+        let res = c.complete(&req).await.unwrap();
+        assert_eq!(res.message.content.as_deref(), Some("ok"));
+        assert_eq!(server.await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_complete_gives_up_after_max_retries() {
+        let busy = || (500, "application/json", r#"{"error":"boom"}"#.to_string());
+        let (url, server) = mock::serve(vec![busy(), busy(), busy()]).await;
+        let c = client(&url, ProviderKind::OpenAi, "gpt-4o");
+        let req = CompletionRequest::new("gpt-4o", vec![Message::user("hi")]);
+        let err = c.complete(&req).await.unwrap_err();
+        assert!(matches!(err, LlmError::Http { status: 500, .. }));
+        assert_eq!(server.await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_complete_does_not_retry_auth_errors() {
+        let (url, server) = mock::serve_once(401, r#"{"error":{"message":"bad key"}}"#).await;
+        let c = client(&url, ProviderKind::OpenAi, "gpt-4o");
+        let req = CompletionRequest::new("gpt-4o", vec![Message::user("hi")]);
+        assert!(matches!(c.complete(&req).await, Err(LlmError::Auth(_))));
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_embed_orders_vectors_and_retries() {
+        let (url, server) = mock::serve(vec![
+            (
+                503,
+                "application/json",
+                r#"{"error":{"message":"busy"}}"#.into(),
+            ),
+            (
+                200,
+                "application/json",
+                r#"{"data":[{"index":1,"embedding":[0.5,0.5]},
+                    {"index":0,"embedding":[1.0,0.0]}]}"#
+                    .into(),
+            ),
+        ])
+        .await;
+        let c = client(&url, ProviderKind::OpenAi, "text-embedding-3-small");
+        let inputs = vec!["a".to_string(), "b".to_string()];
+        let vectors = c.embed("text-embedding-3-small", &inputs).await.unwrap();
+        assert_eq!(vectors, vec![vec![1.0, 0.0], vec![0.5, 0.5]]);
+
+        let captured = server.await.unwrap();
+        assert_eq!(captured.len(), 2);
+        assert!(captured[1].head.starts_with("POST /embeddings"));
+        assert_eq!(captured[1].body["input"][1], "b");
+    }
+
+    #[tokio::test]
+    async fn test_embed_unsupported_provider() {
+        let c = client("http://127.0.0.1:9", ProviderKind::Anthropic, "claude");
+        let err = c.embed("claude", &["a".to_string()]).await.unwrap_err();
+        assert!(matches!(err, LlmError::Unsupported(_)));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_before_send() {
+        let c = client("http://127.0.0.1:9", ProviderKind::OpenAi, "gpt-4o");
+        c.cancel_token().cancel();
+        let req = CompletionRequest::new("gpt-4o", vec![Message::user("hi")]);
+        assert!(matches!(c.complete(&req).await, Err(LlmError::Cancelled)));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_in_flight() {
+        // Accept the connection but never answer.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let _hold = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            drop(socket);
+        });
+        let c = client(&url, ProviderKind::OpenAi, "gpt-4o");
+        let token = c.cancel_token();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            token.cancel();
+        });
+        let req = CompletionRequest::new("gpt-4o", vec![Message::user("hi")]);
+        assert!(matches!(c.complete(&req).await, Err(LlmError::Cancelled)));
+    }
+
+    #[test]
+    fn test_invalid_proxy_is_config_error() {
+        let cfg = LlmConfig::new(ProviderKind::Ollama, "llama3.1:8b").with_proxy("::not a url");
+        assert!(matches!(LlmClient::new(cfg), Err(LlmError::Config(_))));
+    }
+
+    #[tokio::test]
+    async fn test_stream_openai_tool_call_accumulates() {
+        let body = [
+            r#"data: {"choices":[{"delta":{"tool_calls":[{"index":0,"id":"call_1","function":{"name":"whois_lookup","arguments":""}}]}}]}"#,
+            r#"data: {"choices":[{"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"domain\":"}}]}}]}"#,
+            r#"data: {"choices":[{"delta":{"tool_calls":[{"index":0,"function":{"arguments":"\"a.com\"}"}}]}}]}"#,
+            r#"data: {"choices":[{"delta":{},"finish_reason":"tool_calls"}]}"#,
+            r#"data: {"choices":[],"usage":{"prompt_tokens":9,"completion_tokens":4}}"#,
+            "data: [DONE]",
+        ]
+        .join("\n\n")
+            + "\n\n";
+        let (url, server) = mock::serve(vec![(200, SSE, body)]).await;
+        let c = client(&url, ProviderKind::OpenAi, "gpt-4o");
+        let req = CompletionRequest::new("gpt-4o", vec![Message::user("hi")]);
+
+        let mut seen = 0;
+        let acc = c.complete_streaming(&req, |_| seen += 1).await.unwrap();
+        let captured = server.await.unwrap();
+
+        assert_eq!(captured[0].body["stream"], true);
+        assert_eq!(seen, 5);
+        assert_eq!(acc.tool_calls.len(), 1);
+        assert_eq!(acc.tool_calls[0].id, "call_1");
+        assert_eq!(
+            acc.tool_calls[0].parse_arguments().unwrap()["domain"],
+            "a.com"
+        );
+        assert_eq!(acc.finish_reason, Some(FinishReason::ToolUse));
+        assert_eq!(acc.usage.unwrap().total_tokens, 13);
+    }
+
+    #[tokio::test]
+    async fn test_stream_anthropic_text() {
+        let body = [
+            "event: message_start\ndata: {\"type\":\"message_start\"}",
+            "event: content_block_delta\ndata: {\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Hel\"}}",
+            "event: content_block_delta\ndata: {\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"lo\"}}",
+            "event: message_delta\ndata: {\"delta\":{\"stop_reason\":\"end_turn\"}}",
+            "event: message_stop\ndata: {\"type\":\"message_stop\"}",
+        ]
+        .join("\n\n")
+            + "\n\n";
+        let (url, server) = mock::serve(vec![(200, SSE, body)]).await;
+        let c = client(&url, ProviderKind::Anthropic, "claude-3-5-haiku-20241022");
+        let req = CompletionRequest::new("claude-3-5-haiku-20241022", vec![Message::user("hi")]);
+        let chunks: Vec<StreamChunk> = c.complete_stream(&req).await.unwrap().collect().await;
+        server.await.unwrap();
+
+        let mut acc = TextAccumulator::new();
+        chunks.iter().for_each(|c| acc.push(c));
+        assert_eq!(acc.text, "Hello");
+        let dones = chunks
+            .iter()
+            .filter(|c| matches!(c, StreamChunk::Done(_)))
+            .count();
+        assert_eq!(dones, 1);
+    }
+
+    #[tokio::test]
+    async fn test_stream_truncated_reports_error() {
+        let body = "data: {\"choices\":[{\"delta\":{\"content\":\"par\"}}]}\n\n".to_string();
+        let (url, server) = mock::serve(vec![(200, SSE, body)]).await;
+        let c = client(&url, ProviderKind::OpenAi, "gpt-4o");
+        let req = CompletionRequest::new("gpt-4o", vec![Message::user("hi")]);
+        let err = c.complete_streaming(&req, |_| {}).await.unwrap_err();
+        server.await.unwrap();
+        assert!(matches!(err, LlmError::StreamParse(_)));
+    }
+}
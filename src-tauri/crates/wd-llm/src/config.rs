@@ -46,6 +46,11 @@ pub struct LlmConfig {
     /// Custom headers to send with every request (e.g. org ID).
     #[serde(default)]
     pub extra_headers: std::collections::HashMap<String, String>,
+
+    /// Directory holding OpenAI `.tiktoken` vocabularies for exact token
+    /// counts. Counts are estimated when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokenizer_dir: Option<String>,
 }
 
 fn default_max_retries() -> u32 {
@@ -75,6 +80,7 @@ impl LlmConfig {
             max_output_tokens: default_max_output_tokens(),
             temperature: None,
             extra_headers: Default::default(),
+            tokenizer_dir: None,
         }
     }
 
@@ -114,6 +120,12 @@ impl LlmConfig {
         self
     }
 
+    /// Set the directory to load tokenizer vocabularies from.
+    pub fn with_tokenizer_dir(mut self, dir: &str) -> Self {
+        self.tokenizer_dir = Some(dir.to_string());
+        self
+    }
+
     /// Resolve the effective API URL — uses the provided URL or a
     /// well-known default for the chosen provider.
     pub fn effective_api_url(&self) -> &str {
@@ -40,6 +40,9 @@ pub enum LlmError {
     #[error("configuration error: {0}")]
     Config(String),
 
+    #[error("request cancelled")]
+    Cancelled,
+
     #[error("{0}")]
     Other(String),
 }
@@ -50,6 +53,18 @@ impl From<String> for LlmError {
     }
 }
 
+impl LlmError {
+    /// Whether the request may succeed if sent again: rate limits, timeouts,
+    /// network failures and server-side (5xx) errors.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RateLimit { .. } | Self::Timeout { .. } | Self::Network(_) => true,
+            Self::Http { status, .. } => *status >= 500 || *status == 408,
+            _ => false,
+        }
+    }
+}
+
 // ─── Tests ───────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -88,6 +103,23 @@ mod tests {
         assert!(matches!(e, LlmError::Other(_)));
     }
 
+    #[test]
+    fn test_is_retryable() {
+        assert!(LlmError::Network("reset".into()).is_retryable());
+        assert!(LlmError::Http {
+            status: 503,
+            body: String::new()
+        }
+        .is_retryable());
+        assert!(!LlmError::Http {
+            status: 400,
+            body: String::new()
+        }
+        .is_retryable());
+        assert!(!LlmError::Auth("bad key".into()).is_retryable());
+        assert!(!LlmError::Cancelled.is_retryable());
+    }
+
     #[test]
     fn test_error_serialize_roundtrip() {
         let e = LlmError::Http {
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use crate::error::LlmError;
 use crate::request::CompletionRequest;
 use crate::response::CompletionResponse;
+use crate::stream::{parse_openai_sse_line, SseEvent, StreamChunk};
 
 /// Supported LLM provider backends.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
@@ -84,6 +85,52 @@ pub trait LlmProvider: Send + Sync {
 
     /// The base URL for this provider's API.
     fn api_url(&self) -> &str;
+
+    /// Full URL a request is sent to. Defaults to the OpenAI-style
+    /// `{api_url}/chat/completions`.
+    fn endpoint(&self, _req: &CompletionRequest) -> String {
+        format!("{}/chat/completions", self.api_url().trim_end_matches('/'))
+    }
+
+    /// Headers to send with every request (authentication, API version).
+    fn headers(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+
+    /// Translate one SSE event of a streaming response into chunks.
+    /// Defaults to the OpenAI chat-completions stream format.
+    fn parse_stream_event(&self, event: &SseEvent) -> Vec<StreamChunk> {
+        parse_openai_sse_line(&format!("data: {}", event.data))
+            .into_iter()
+            .collect()
+    }
+
+    /// Whether this provider can turn text into embedding vectors.
+    fn supports_embeddings(&self) -> bool {
+        true
+    }
+
+    /// Full URL an embeddings request is sent to. Defaults to the
+    /// OpenAI-style `{api_url}/embeddings`.
+    fn embeddings_endpoint(&self, _model: &str) -> String {
+        format!("{}/embeddings", self.api_url().trim_end_matches('/'))
+    }
+
+    /// Build the HTTP body embedding `inputs` with `model`. Defaults to the
+    /// OpenAI embeddings format.
+    fn build_embeddings_body(
+        &self,
+        model: &str,
+        inputs: &[String],
+    ) -> Result<serde_json::Value, LlmError> {
+        Ok(serde_json::json!({ "model": model, "input": inputs }))
+    }
+
+    /// Parse an embeddings response into one vector per input, in input
+    /// order. Defaults to the OpenAI embeddings format.
+    fn parse_embeddings(&self, status: u16, body: &str) -> Result<Vec<Vec<f32>>, LlmError> {
+        crate::providers::parse_openai_embeddings(status, body)
+    }
 }
 
 /// Registry of configured providers.
@@ -272,6 +319,39 @@ pub fn ollama_default_models() -> Vec<ModelInfo> {
     ]
 }
 
+/// Known Google Gemini models.
+pub fn gemini_models() -> Vec<ModelInfo> {
+    vec![
+        ModelInfo {
+            id: "gemini-2.0-flash".into(),
+            display_name: "Gemini 2.0 Flash".into(),
+            context_window: 1_048_576,
+            supports_tools: true,
+            supports_vision: true,
+            input_cost_per_1k: Some(0.0001),
+            output_cost_per_1k: Some(0.0004),
+        },
+        ModelInfo {
+            id: "gemini-1.5-pro".into(),
+            display_name: "Gemini 1.5 Pro".into(),
+            context_window: 2_097_152,
+            supports_tools: true,
+            supports_vision: true,
+            input_cost_per_1k: Some(0.00125),
+            output_cost_per_1k: Some(0.005),
+        },
+        ModelInfo {
+            id: "gemini-1.5-flash".into(),
+            display_name: "Gemini 1.5 Flash".into(),
+            context_window: 1_048_576,
+            supports_tools: true,
+            supports_vision: true,
+            input_cost_per_1k: Some(0.000075),
+            output_cost_per_1k: Some(0.0003),
+        },
+    ]
+}
+
 // ─── Tests ───────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -375,6 +455,14 @@ mod tests {
         assert!(models.iter().any(|m| m.id.contains("claude")));
     }
 
+    #[test]
+    fn test_default_endpoint_and_headers() {
+        let p = DummyProvider;
+        let req = CompletionRequest::new("m", vec![]);
+        assert_eq!(p.endpoint(&req), "http://localhost/chat/completions");
+        assert!(p.headers().is_empty());
+    }
+
     #[test]
     fn test_model_info_serde() {
         let m = &openai_models()[0];
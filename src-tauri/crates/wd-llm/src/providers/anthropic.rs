@@ -0,0 +1,434 @@
+use serde_json::{json, Value};
+
+use super::{apply_cost, context_window, error_from_status, parse_json};
+use crate::config::LlmConfig;
+use crate::error::LlmError;
+use crate::message::{FunctionCall, Message, MessageMetadata, Role, ToolCall};
+use crate::provider::{anthropic_models, LlmProvider, ModelInfo, ProviderKind};
+use crate::request::{CompletionRequest, ResponseFormat, ToolChoice};
+use crate::response::{CompletionResponse, FinishReason, TokenUsage};
+use crate::stream::{parse_anthropic_sse_line, SseEvent, StreamChunk};
+
+/// Value of the required `anthropic-version` header.
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Anthropic Messages API provider.
+pub struct AnthropicProvider {
+    api_url: String,
+    api_key: String,
+    default_max_tokens: usize,
+    extra_headers: Vec<(String, String)>,
+    models: Vec<ModelInfo>,
+}
+
+impl AnthropicProvider {
+    pub fn from_config(config: &LlmConfig) -> Self {
+        let mut extra_headers: Vec<(String, String)> = config
+            .extra_headers
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        extra_headers.sort();
+        Self {
+            api_url: config.effective_api_url().trim_end_matches('/').to_string(),
+            api_key: config.api_key.clone(),
+            default_max_tokens: config.max_output_tokens,
+            extra_headers,
+            models: anthropic_models(),
+        }
+    }
+
+    /// Convert the conversation into Anthropic's `(system, messages)` pair.
+    /// System prompts are hoisted out, and consecutive tool results are
+    /// merged into one user turn as the API requires.
+    fn convert_messages(messages: &[Message]) -> (Option<String>, Vec<Value>) {
+        let mut system = Vec::new();
+        let mut out: Vec<Value> = Vec::new();
+
+        for msg in messages {
+            match msg.role {
+                Role::System => {
+                    if let Some(c) = &msg.content {
+                        system.push(c.clone());
+                    }
+                }
+                Role::Tool => {
+                    let block = json!({
+                        "type": "tool_result",
+                        "tool_use_id": msg.tool_call_id,
+                        "content": msg.content.clone().unwrap_or_default(),
+                    });
+                    let merge = out.last().is_some_and(|last| {
+                        last["role"] == "user"
+                            && last["content"]
+                                .as_array()
+                                .and_then(|blocks| blocks.first())
+                                .is_some_and(|b| b["type"] == "tool_result")
+                    });
+                    if merge {
+                        if let Some(blocks) = out
+                            .last_mut()
+                            .and_then(|last| last["content"].as_array_mut())
+                        {
+                            blocks.push(block);
+                        }
+                    } else {
+                        out.push(json!({"role": "user", "content": [block]}));
+                    }
+                }
+                Role::Assistant if !msg.tool_calls.is_empty() => {
+                    let mut blocks = Vec::new();
+                    if let Some(text) = msg.content.as_deref().filter(|t| !t.is_empty()) {
+                        blocks.push(json!({"type": "text", "text": text}));
+                    }
+                    for tc in &msg.tool_calls {
+                        blocks.push(json!({
+                            "type": "tool_use",
+                            "id": tc.id,
+                            "name": tc.function.name,
+                            "input": tc.function.arguments,
+                        }));
+                    }
+                    out.push(json!({"role": "assistant", "content": blocks}));
+                }
+                Role::User | Role::Assistant => {
+                    let role = if msg.role == Role::User {
+                        "user"
+                    } else {
+                        "assistant"
+                    };
+                    out.push(json!({
+                        "role": role,
+                        "content": msg.content.clone().unwrap_or_default(),
+                    }));
+                }
+            }
+        }
+
+        let system = (!system.is_empty()).then(|| system.join("\n\n"));
+        (system, out)
+    }
+}
+
+impl LlmProvider for AnthropicProvider {
+    fn kind(&self) -> ProviderKind {
+        ProviderKind::Anthropic
+    }
+
+    fn display_name(&self) -> &str {
+        "Anthropic"
+    }
+
+    fn supported_models(&self) -> Vec<ModelInfo> {
+        self.models.clone()
+    }
+
+    fn max_context_tokens(&self, model: &str) -> usize {
+        context_window(&self.models, model, 200_000)
+    }
+
+    fn supports_tools(&self) -> bool {
+        true
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn build_request_body(&self, req: &CompletionRequest) -> Result<Value, LlmError> {
+        let (system, messages) = Self::convert_messages(&req.messages);
+        let mut body = json!({
+            "model": req.model,
+            "max_tokens": req.max_tokens.unwrap_or(self.default_max_tokens),
+            "messages": messages,
+        });
+        let obj = body.as_object_mut().expect("body is an object");
+
+        if let Some(system) = system {
+            obj.insert("system".into(), json!(system));
+        }
+        if !req.tools.is_empty() {
+            obj.insert(
+                "tools".into(),
+                req.tools.iter().map(|t| t.to_anthropic_json()).collect(),
+            );
+            let choice = match &req.tool_choice {
+                ToolChoice::Auto => json!({"type": "auto"}),
+                ToolChoice::None => json!({"type": "none"}),
+                ToolChoice::Required => json!({"type": "any"}),
+                ToolChoice::Specific(name) => json!({"type": "tool", "name": name}),
+            };
+            obj.insert("tool_choice".into(), choice);
+        }
+        if let Some(t) = req.temperature {
+            obj.insert("temperature".into(), json!(t));
+        }
+        if let Some(p) = req.top_p {
+            obj.insert("top_p".into(), json!(p));
+        }
+        if !req.stop_sequences.is_empty() {
+            obj.insert("stop_sequences".into(), json!(req.stop_sequences));
+        }
+        if matches!(
+            req.response_format,
+            Some(ResponseFormat::JsonObject | ResponseFormat::JsonSchema { .. })
+        ) {
+            return Err(LlmError::Unsupported(
+                "Anthropic has no native JSON response mode; use a tool instead".into(),
+            ));
+        }
+        if req.stream {
+            obj.insert("stream".into(), json!(true));
+        }
+        Ok(body)
+    }
+
+    fn parse_response(
+        &self,
+        status: u16,
+        body: &str,
+        latency_ms: u64,
+    ) -> Result<CompletionResponse, LlmError> {
+        if !(200..300).contains(&status) {
+            return Err(error_from_status(status, body));
+        }
+        let json = parse_json(status, body)?;
+        if json["type"] == "error" {
+            return Err(error_from_status(500, body));
+        }
+
+        let mut text = String::new();
+        let mut tool_calls = Vec::new();
+        for block in json["content"].as_array().into_iter().flatten() {
+            match block["type"].as_str() {
+                Some("text") => text.push_str(block["text"].as_str().unwrap_or_default()),
+                Some("tool_use") => {
+                    let name = block["name"].as_str().ok_or_else(|| {
+                        LlmError::InvalidToolCall("tool_use block without a name".into())
+                    })?;
+                    tool_calls.push(ToolCall {
+                        id: block["id"].as_str().unwrap_or_default().to_string(),
+                        function: FunctionCall {
+                            name: name.to_string(),
+                            arguments: block.get("input").cloned().unwrap_or(json!({})),
+                        },
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        let finish_reason = match json["stop_reason"].as_str() {
+            Some("end_turn") | Some("stop_sequence") | None => FinishReason::Stop,
+            Some("tool_use") => FinishReason::ToolUse,
+            Some("max_tokens") => FinishReason::Length,
+            Some("refusal") => FinishReason::ContentFilter,
+            Some(other) => FinishReason::Error(other.to_string()),
+        };
+        let model = json["model"].as_str().unwrap_or_default().to_string();
+        let mut usage = TokenUsage::new(
+            json["usage"]["input_tokens"].as_u64().unwrap_or(0) as usize,
+            json["usage"]["output_tokens"].as_u64().unwrap_or(0) as usize,
+        );
+        apply_cost(&mut usage, &self.models, &model);
+
+        let mut message = if tool_calls.is_empty() {
+            Message::assistant(&text)
+        } else {
+            let mut m = Message::assistant_tool_calls(tool_calls);
+            m.content = (!text.is_empty()).then_some(text);
+            m
+        };
+        message.metadata = MessageMetadata {
+            token_count: Some(usage.completion_tokens),
+            model: Some(model.clone()),
+            latency_ms: Some(latency_ms),
+            cost_usd: usage.estimated_cost_usd,
+            ..MessageMetadata::now()
+        };
+
+        Ok(CompletionResponse {
+            id: json["id"].as_str().unwrap_or_default().to_string(),
+            model,
+            message,
+            finish_reason,
+            usage,
+            latency_ms,
+        })
+    }
+
+    fn api_url(&self) -> &str {
+        &self.api_url
+    }
+
+    fn endpoint(&self, _req: &CompletionRequest) -> String {
+        format!("{}/messages", self.api_url)
+    }
+
+    fn parse_stream_event(&self, event: &SseEvent) -> Vec<StreamChunk> {
+        let kind = event.event.as_deref().unwrap_or_default();
+        if kind == "error" {
+            return vec![StreamChunk::Error(error_from_status(500, &event.data))];
+        }
+        parse_anthropic_sse_line(kind, &event.data)
+            .into_iter()
+            .collect()
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        let mut headers = vec![
+            ("x-api-key".to_string(), self.api_key.clone()),
+            (
+                "anthropic-version".to_string(),
+                ANTHROPIC_VERSION.to_string(),
+            ),
+        ];
+        headers.extend(self.extra_headers.iter().cloned());
+        headers
+    }
+
+    fn supports_embeddings(&self) -> bool {
+        false
+    }
+
+    fn build_embeddings_body(&self, _model: &str, _inputs: &[String]) -> Result<Value, LlmError> {
+        Err(LlmError::Unsupported(
+            "Anthropic does not offer an embeddings API".into(),
+        ))
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{complete, mock};
+    use crate::tools::ToolBuilder;
+
+    fn provider() -> AnthropicProvider {
+        AnthropicProvider::from_config(
+            &LlmConfig::new(ProviderKind::Anthropic, "claude-3-5-haiku-20241022")
+                .with_api_key("ak"),
+        )
+    }
+
+    fn call(id: &str) -> ToolCall {
+        ToolCall {
+            id: id.into(),
+            function: FunctionCall {
+                name: "dns_lookup".into(),
+                arguments: json!({"domain": "a.com"}),
+            },
+        }
+    }
+
+    #[test]
+    fn test_build_body_hoists_system_and_merges_tool_results() {
+        let req = CompletionRequest::new(
+            "claude-3-5-haiku-20241022",
+            vec![
+                Message::system("You check domains."),
+                Message::user("check a.com twice"),
+                Message::assistant_tool_calls(vec![call("t1"), call("t2")]),
+                Message::tool_result("t1", "dns_lookup", "NS found"),
+                Message::tool_result("t2", "dns_lookup", "NS found"),
+            ],
+        )
+        .with_tools(
+            vec![ToolBuilder::new("dns_lookup", "DNS").build()],
+            ToolChoice::Required,
+        );
+        let body = provider().build_request_body(&req).unwrap();
+
+        assert_eq!(body["system"], "You check domains.");
+        assert_eq!(body["max_tokens"], 4096);
+        let messages = body["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[1]["content"][1]["type"], "tool_use");
+        assert_eq!(messages[1]["content"][1]["input"]["domain"], "a.com");
+        assert_eq!(messages[2]["role"], "user");
+        assert_eq!(messages[2]["content"].as_array().unwrap().len(), 2);
+        assert_eq!(body["tool_choice"]["type"], "any");
+        assert!(body["tools"][0]["input_schema"].is_object());
+    }
+
+    #[test]
+    fn test_json_mode_unsupported() {
+        let mut req = CompletionRequest::new("m", vec![Message::user("x")]);
+        req.response_format = Some(ResponseFormat::JsonObject);
+        assert!(matches!(
+            provider().build_request_body(&req),
+            Err(LlmError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_text_and_tool_use() {
+        let body = r#"{
+            "id": "msg_1", "type": "message", "model": "claude-3-5-haiku-20241022",
+            "content": [
+                {"type": "text", "text": "Let me check."},
+                {"type": "tool_use", "id": "toolu_1", "name": "whois_lookup",
+                 "input": {"domain": "b.com"}}
+            ],
+            "stop_reason": "tool_use",
+            "usage": {"input_tokens": 2000, "output_tokens": 100}
+        }"#;
+        let res = provider().parse_response(200, body, 5).unwrap();
+        assert!(res.finish_reason.is_tool_use());
+        assert_eq!(res.message.content.as_deref(), Some("Let me check."));
+        assert_eq!(res.message.tool_calls[0].id, "toolu_1");
+        assert_eq!(
+            res.message.tool_calls[0].function.arguments["domain"],
+            "b.com"
+        );
+        assert_eq!(res.usage.prompt_tokens, 2000);
+        assert!(res.usage.estimated_cost_usd.is_some());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let p = provider();
+        let overloaded =
+            r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#;
+        assert!(matches!(
+            p.parse_response(529, overloaded, 0),
+            Err(LlmError::Http { status: 529, body }) if body == "Overloaded"
+        ));
+        assert!(matches!(
+            p.parse_response(
+                401,
+                r#"{"type":"error","error":{"message":"invalid x-api-key"}}"#,
+                0
+            ),
+            Err(LlmError::Auth(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_complete_against_mock_server() {
+        let (url, server) = mock::serve_once(
+            200,
+            r#"{"id":"msg_2","type":"message","model":"claude-3-5-haiku-20241022",
+               "content":[{"type":"text","text":"Taken."}],"stop_reason":"end_turn",
+               "usage":{"input_tokens":3,"output_tokens":2}}"#,
+        )
+        .await;
+        let p = AnthropicProvider::from_config(
+            &LlmConfig::new(ProviderKind::Anthropic, "claude-3-5-haiku-20241022")
+                .with_api_key("ak")
+                .with_api_url(&url),
+        );
+        let req = CompletionRequest::new("claude-3-5-haiku-20241022", vec![Message::user("x")]);
+        let res = complete(&reqwest::Client::new(), &p, &req).await.unwrap();
+        let captured = server.await.unwrap();
+
+        assert_eq!(res.message.content.as_deref(), Some("Taken."));
+        assert!(res.finish_reason.is_stop());
+        assert!(captured.head.starts_with("POST /messages"));
+        let head = captured.head.to_lowercase();
+        assert!(head.contains("x-api-key: ak"));
+        assert!(head.contains("anthropic-version: 2023-06-01"));
+    }
+}
@@ -0,0 +1,585 @@
+use serde_json::{json, Value};
+
+use super::{apply_cost, context_window, error_from_status, parse_json, vector};
+use crate::config::LlmConfig;
+use crate::error::LlmError;
+use crate::message::{FunctionCall, Message, MessageMetadata, Role, ToolCall};
+use crate::provider::{gemini_models, LlmProvider, ModelInfo, ProviderKind};
+use crate::request::{CompletionRequest, ResponseFormat, ToolChoice};
+use crate::response::{CompletionResponse, FinishReason, TokenUsage};
+use crate::stream::{SseEvent, StreamChunk};
+
+/// Google Gemini `generateContent` provider.
+pub struct GeminiProvider {
+    api_url: String,
+    api_key: String,
+    extra_headers: Vec<(String, String)>,
+    models: Vec<ModelInfo>,
+}
+
+impl GeminiProvider {
+    pub fn from_config(config: &LlmConfig) -> Self {
+        let mut extra_headers: Vec<(String, String)> = config
+            .extra_headers
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        extra_headers.sort();
+        Self {
+            api_url: config.effective_api_url().trim_end_matches('/').to_string(),
+            api_key: config.api_key.clone(),
+            extra_headers,
+            models: gemini_models(),
+        }
+    }
+
+    /// Convert the conversation into Gemini's `(systemInstruction, contents)`
+    /// pair. Consecutive tool results share one `user` turn.
+    fn convert_messages(messages: &[Message]) -> (Option<String>, Vec<Value>) {
+        let mut system = Vec::new();
+        let mut out: Vec<Value> = Vec::new();
+
+        for msg in messages {
+            match msg.role {
+                Role::System => {
+                    if let Some(c) = &msg.content {
+                        system.push(c.clone());
+                    }
+                }
+                Role::Tool => {
+                    let output = msg.content.clone().unwrap_or_default();
+                    // `response` must be an object; wrap anything else.
+                    let response = match serde_json::from_str::<Value>(&output) {
+                        Ok(v @ Value::Object(_)) => v,
+                        _ => json!({ "content": output }),
+                    };
+                    let part = json!({
+                        "functionResponse": {
+                            "name": msg.name.clone().unwrap_or_default(),
+                            "response": response,
+                        }
+                    });
+                    let merge = out.last().is_some_and(|last| {
+                        last["role"] == "user" && last["parts"][0].get("functionResponse").is_some()
+                    });
+                    if merge {
+                        if let Some(parts) =
+                            out.last_mut().and_then(|last| last["parts"].as_array_mut())
+                        {
+                            parts.push(part);
+                        }
+                    } else {
+                        out.push(json!({"role": "user", "parts": [part]}));
+                    }
+                }
+                Role::Assistant => {
+                    let mut parts = Vec::new();
+                    if let Some(text) = msg.content.as_deref().filter(|t| !t.is_empty()) {
+                        parts.push(json!({"text": text}));
+                    }
+                    for tc in &msg.tool_calls {
+                        parts.push(json!({
+                            "functionCall": {
+                                "name": tc.function.name,
+                                "args": tc.function.arguments,
+                            }
+                        }));
+                    }
+                    out.push(json!({"role": "model", "parts": parts}));
+                }
+                Role::User => out.push(json!({
+                    "role": "user",
+                    "parts": [{"text": msg.content.clone().unwrap_or_default()}],
+                })),
+            }
+        }
+
+        let system = (!system.is_empty()).then(|| system.join("\n\n"));
+        (system, out)
+    }
+}
+
+impl LlmProvider for GeminiProvider {
+    fn kind(&self) -> ProviderKind {
+        ProviderKind::GoogleGemini
+    }
+
+    fn display_name(&self) -> &str {
+        "Google Gemini"
+    }
+
+    fn supported_models(&self) -> Vec<ModelInfo> {
+        self.models.clone()
+    }
+
+    fn max_context_tokens(&self, model: &str) -> usize {
+        context_window(&self.models, model, 1_048_576)
+    }
+
+    fn supports_tools(&self) -> bool {
+        true
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn build_request_body(&self, req: &CompletionRequest) -> Result<Value, LlmError> {
+        let (system, contents) = Self::convert_messages(&req.messages);
+        let mut body = json!({ "contents": contents });
+        let obj = body.as_object_mut().expect("body is an object");
+
+        if let Some(system) = system {
+            obj.insert(
+                "systemInstruction".into(),
+                json!({"parts": [{"text": system}]}),
+            );
+        }
+        if !req.tools.is_empty() {
+            let declarations: Vec<Value> = req
+                .tools
+                .iter()
+                .map(|t| {
+                    json!({
+                        "name": t.name,
+                        "description": t.description,
+                        "parameters": t.parameters,
+                    })
+                })
+                .collect();
+            obj.insert(
+                "tools".into(),
+                json!([{ "functionDeclarations": declarations }]),
+            );
+            let config = match &req.tool_choice {
+                ToolChoice::Auto => json!({"mode": "AUTO"}),
+                ToolChoice::None => json!({"mode": "NONE"}),
+                ToolChoice::Required => json!({"mode": "ANY"}),
+                ToolChoice::Specific(name) => {
+                    json!({"mode": "ANY", "allowedFunctionNames": [name]})
+                }
+            };
+            obj.insert(
+                "toolConfig".into(),
+                json!({ "functionCallingConfig": config }),
+            );
+        }
+
+        let mut generation = serde_json::Map::new();
+        if let Some(t) = req.temperature {
+            generation.insert("temperature".into(), json!(t));
+        }
+        if let Some(n) = req.max_tokens {
+            generation.insert("maxOutputTokens".into(), json!(n));
+        }
+        if let Some(p) = req.top_p {
+            generation.insert("topP".into(), json!(p));
+        }
+        if !req.stop_sequences.is_empty() {
+            generation.insert("stopSequences".into(), json!(req.stop_sequences));
+        }
+        match &req.response_format {
+            Some(ResponseFormat::JsonObject) => {
+                generation.insert("responseMimeType".into(), json!("application/json"));
+            }
+            Some(ResponseFormat::JsonSchema { schema, .. }) => {
+                generation.insert("responseMimeType".into(), json!("application/json"));
+                generation.insert("responseSchema".into(), schema.clone());
+            }
+            Some(ResponseFormat::Text) | None => {}
+        }
+        if !generation.is_empty() {
+            obj.insert("generationConfig".into(), Value::Object(generation));
+        }
+        Ok(body)
+    }
+
+    fn parse_response(
+        &self,
+        status: u16,
+        body: &str,
+        latency_ms: u64,
+    ) -> Result<CompletionResponse, LlmError> {
+        if !(200..300).contains(&status) {
+            return Err(error_from_status(status, body));
+        }
+        let json = parse_json(status, body)?;
+        let candidate = &json["candidates"][0];
+        if candidate.is_null() {
+            // A blocked prompt comes back with no candidates at all.
+            if let Some(reason) = json["promptFeedback"]["blockReason"].as_str() {
+                return Err(LlmError::Other(format!("prompt blocked: {reason}")));
+            }
+            return Err(LlmError::Http {
+                status,
+                body: format!("response has no candidates: {body}"),
+            });
+        }
+
+        let mut text = String::new();
+        let mut tool_calls = Vec::new();
+        for part in candidate["content"]["parts"]
+            .as_array()
+            .into_iter()
+            .flatten()
+        {
+            if let Some(t) = part["text"].as_str() {
+                text.push_str(t);
+            } else if let Some(call) = part.get("functionCall") {
+                let name = call["name"].as_str().ok_or_else(|| {
+                    LlmError::InvalidToolCall("functionCall part without a name".into())
+                })?;
+                tool_calls.push(ToolCall {
+                    id: format!("call_{}", uuid::Uuid::new_v4().simple()),
+                    function: FunctionCall {
+                        name: name.to_string(),
+                        arguments: call.get("args").cloned().unwrap_or(json!({})),
+                    },
+                });
+            }
+        }
+
+        let finish_reason = if !tool_calls.is_empty() {
+            FinishReason::ToolUse
+        } else {
+            match candidate["finishReason"].as_str() {
+                Some("STOP") | None => FinishReason::Stop,
+                Some("MAX_TOKENS") => FinishReason::Length,
+                Some("SAFETY")
+                | Some("RECITATION")
+                | Some("BLOCKLIST")
+                | Some("PROHIBITED_CONTENT") => FinishReason::ContentFilter,
+                Some(other) => FinishReason::Error(other.to_string()),
+            }
+        };
+        let model = json["modelVersion"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let meta = &json["usageMetadata"];
+        let mut usage = TokenUsage::new(
+            meta["promptTokenCount"].as_u64().unwrap_or(0) as usize,
+            meta["candidatesTokenCount"].as_u64().unwrap_or(0) as usize,
+        );
+        apply_cost(&mut usage, &self.models, &model);
+
+        let mut message = if tool_calls.is_empty() {
+            Message::assistant(&text)
+        } else {
+            let mut m = Message::assistant_tool_calls(tool_calls);
+            m.content = (!text.is_empty()).then_some(text);
+            m
+        };
+        message.metadata = MessageMetadata {
+            token_count: Some(usage.completion_tokens),
+            model: Some(model.clone()),
+            latency_ms: Some(latency_ms),
+            cost_usd: usage.estimated_cost_usd,
+            ..MessageMetadata::now()
+        };
+
+        Ok(CompletionResponse {
+            id: json["responseId"]
+                .as_str()
+                .map(String::from)
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+            model,
+            message,
+            finish_reason,
+            usage,
+            latency_ms,
+        })
+    }
+
+    fn api_url(&self) -> &str {
+        &self.api_url
+    }
+
+    fn endpoint(&self, req: &CompletionRequest) -> String {
+        if req.stream {
+            format!(
+                "{}/models/{}:streamGenerateContent?alt=sse",
+                self.api_url, req.model
+            )
+        } else {
+            format!("{}/models/{}:generateContent", self.api_url, req.model)
+        }
+    }
+
+    /// Each streamed event is a complete `GenerateContentResponse` holding
+    /// only the newly generated parts; function calls arrive whole.
+    fn parse_stream_event(&self, event: &SseEvent) -> Vec<StreamChunk> {
+        let Ok(json) = serde_json::from_str::<Value>(&event.data) else {
+            return Vec::new();
+        };
+        if json.get("error").is_some() {
+            return vec![StreamChunk::Error(error_from_status(500, &event.data))];
+        }
+
+        let mut chunks = Vec::new();
+        let candidate = &json["candidates"][0];
+        let mut saw_call = false;
+        for part in candidate["content"]["parts"]
+            .as_array()
+            .into_iter()
+            .flatten()
+        {
+            if let Some(text) = part["text"].as_str().filter(|t| !t.is_empty()) {
+                chunks.push(StreamChunk::Delta {
+                    content: text.to_string(),
+                });
+            } else if let Some(call) = part.get("functionCall") {
+                saw_call = true;
+                chunks.push(StreamChunk::ToolCallDelta {
+                    id: format!("call_{}", uuid::Uuid::new_v4().simple()),
+                    name: call["name"].as_str().map(String::from),
+                    arguments_delta: call.get("args").cloned().unwrap_or(json!({})).to_string(),
+                });
+            }
+        }
+        if let Some(meta) = json.get("usageMetadata") {
+            chunks.push(StreamChunk::Usage(TokenUsage::new(
+                meta["promptTokenCount"].as_u64().unwrap_or(0) as usize,
+                meta["candidatesTokenCount"].as_u64().unwrap_or(0) as usize,
+            )));
+        }
+        if let Some(reason) = candidate["finishReason"].as_str() {
+            chunks.push(StreamChunk::Done(match reason {
+                _ if saw_call => FinishReason::ToolUse,
+                "STOP" => FinishReason::Stop,
+                "MAX_TOKENS" => FinishReason::Length,
+                "SAFETY" | "RECITATION" | "BLOCKLIST" | "PROHIBITED_CONTENT" => {
+                    FinishReason::ContentFilter
+                }
+                other => FinishReason::Error(other.to_string()),
+            }));
+        }
+        chunks
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        let mut headers = vec![("x-goog-api-key".to_string(), self.api_key.clone())];
+        headers.extend(self.extra_headers.iter().cloned());
+        headers
+    }
+
+    fn embeddings_endpoint(&self, model: &str) -> String {
+        format!("{}/models/{}:batchEmbedContents", self.api_url, model)
+    }
+
+    fn build_embeddings_body(&self, model: &str, inputs: &[String]) -> Result<Value, LlmError> {
+        let requests: Vec<Value> = inputs
+            .iter()
+            .map(|text| {
+                json!({
+                    "model": format!("models/{model}"),
+                    "content": { "parts": [{ "text": text }] },
+                })
+            })
+            .collect();
+        Ok(json!({ "requests": requests }))
+    }
+
+    fn parse_embeddings(&self, status: u16, body: &str) -> Result<Vec<Vec<f32>>, LlmError> {
+        if !(200..300).contains(&status) {
+            return Err(error_from_status(status, body));
+        }
+        let json = parse_json(status, body)?;
+        let embeddings = json
+            .get("embeddings")
+            .and_then(|e| e.as_array())
+            .ok_or_else(|| LlmError::Other("embeddings response has no embeddings".into()))?;
+        Ok(embeddings.iter().map(|e| vector(e.get("values"))).collect())
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{complete, mock};
+    use crate::tools::{ParamType, ToolBuilder};
+
+    fn provider() -> GeminiProvider {
+        GeminiProvider::from_config(
+            &LlmConfig::new(ProviderKind::GoogleGemini, "gemini-2.0-flash").with_api_key("gk"),
+        )
+    }
+
+    #[test]
+    fn test_build_body_roles_and_tools() {
+        let call = ToolCall {
+            id: "c1".into(),
+            function: FunctionCall {
+                name: "whois_lookup".into(),
+                arguments: json!({"domain": "a.com"}),
+            },
+        };
+        let req = CompletionRequest::new(
+            "gemini-2.0-flash",
+            vec![
+                Message::system("Be brief."),
+                Message::user("is a.com free?"),
+                Message::assistant_tool_calls(vec![call]),
+                Message::tool_result("c1", "whois_lookup", "registered"),
+            ],
+        )
+        .with_tools(
+            vec![ToolBuilder::new("whois_lookup", "WHOIS")
+                .param("domain", ParamType::String, "Domain", true)
+                .build()],
+            ToolChoice::Specific("whois_lookup".into()),
+        )
+        .with_max_tokens(256);
+        let body = provider().build_request_body(&req).unwrap();
+
+        assert_eq!(body["systemInstruction"]["parts"][0]["text"], "Be brief.");
+        let contents = body["contents"].as_array().unwrap();
+        assert_eq!(contents.len(), 3);
+        assert_eq!(contents[1]["role"], "model");
+        assert_eq!(
+            contents[1]["parts"][0]["functionCall"]["args"]["domain"],
+            "a.com"
+        );
+        let response = &contents[2]["parts"][0]["functionResponse"];
+        assert_eq!(response["name"], "whois_lookup");
+        assert_eq!(response["response"]["content"], "registered");
+        assert_eq!(
+            body["tools"][0]["functionDeclarations"][0]["name"],
+            "whois_lookup"
+        );
+        let fcc = &body["toolConfig"]["functionCallingConfig"];
+        assert_eq!(fcc["mode"], "ANY");
+        assert_eq!(fcc["allowedFunctionNames"][0], "whois_lookup");
+        assert_eq!(body["generationConfig"]["maxOutputTokens"], 256);
+    }
+
+    #[test]
+    fn test_json_schema_response_format() {
+        let mut req = CompletionRequest::new("gemini-2.0-flash", vec![Message::user("x")]);
+        req.response_format = Some(ResponseFormat::JsonSchema {
+            name: "r".into(),
+            schema: json!({"type": "object"}),
+        });
+        let body = provider().build_request_body(&req).unwrap();
+        assert_eq!(
+            body["generationConfig"]["responseMimeType"],
+            "application/json"
+        );
+        assert_eq!(body["generationConfig"]["responseSchema"]["type"], "object");
+    }
+
+    #[test]
+    fn test_endpoint_switches_for_streaming() {
+        let p = provider();
+        let req = CompletionRequest::new("gemini-2.0-flash", vec![]);
+        assert!(p
+            .endpoint(&req)
+            .ends_with("/models/gemini-2.0-flash:generateContent"));
+        assert!(p
+            .endpoint(&req.with_stream())
+            .ends_with(":streamGenerateContent?alt=sse"));
+    }
+
+    #[test]
+    fn test_embeddings_request_and_response() {
+        let p = provider();
+        assert!(p
+            .embeddings_endpoint("text-embedding-004")
+            .ends_with("/models/text-embedding-004:batchEmbedContents"));
+        let body = p
+            .build_embeddings_body("text-embedding-004", &["hello".to_string()])
+            .unwrap();
+        assert_eq!(body["requests"][0]["model"], "models/text-embedding-004");
+        assert_eq!(body["requests"][0]["content"]["parts"][0]["text"], "hello");
+
+        let vectors = p
+            .parse_embeddings(
+                200,
+                r#"{"embeddings":[{"values":[0.1,0.2]},{"values":[]}]}"#,
+            )
+            .unwrap();
+        assert_eq!(vectors.len(), 2);
+        assert_eq!(vectors[0], vec![0.1, 0.2]);
+        assert!(p.parse_embeddings(400, "{}").is_err());
+    }
+
+    #[test]
+    fn test_parse_function_call_and_filter() {
+        let p = provider();
+        let body = r#"{
+            "candidates": [{"content": {"role": "model", "parts": [
+                {"functionCall": {"name": "dns_lookup", "args": {"domain": "c.com"}}}
+            ]}, "finishReason": "STOP"}],
+            "usageMetadata": {"promptTokenCount": 1000, "candidatesTokenCount": 10},
+            "modelVersion": "gemini-2.0-flash", "responseId": "r1"
+        }"#;
+        let res = p.parse_response(200, body, 9).unwrap();
+        assert_eq!(res.id, "r1");
+        assert!(res.finish_reason.is_tool_use());
+        assert!(res.message.tool_calls[0].id.starts_with("call_"));
+        assert!(res.usage.estimated_cost_usd.is_some());
+
+        let safety = r#"{"candidates":[{"content":{"parts":[]},"finishReason":"SAFETY"}]}"#;
+        assert_eq!(
+            p.parse_response(200, safety, 0).unwrap().finish_reason,
+            FinishReason::ContentFilter
+        );
+        let blocked = r#"{"promptFeedback":{"blockReason":"SAFETY"}}"#;
+        assert!(matches!(
+            p.parse_response(200, blocked, 0),
+            Err(LlmError::Other(m)) if m == "prompt blocked: SAFETY"
+        ));
+    }
+
+    #[test]
+    fn test_parse_stream_event() {
+        let event = SseEvent {
+            event: None,
+            data: r#"{"candidates":[{"content":{"parts":[{"text":"Hi"},
+                {"functionCall":{"name":"dns_lookup","args":{"domain":"d.com"}}}]},
+                "finishReason":"STOP"}],"usageMetadata":{"promptTokenCount":5,
+                "candidatesTokenCount":2}}"#
+                .into(),
+        };
+        let chunks = provider().parse_stream_event(&event);
+        assert_eq!(chunks.len(), 4);
+        assert!(matches!(&chunks[0], StreamChunk::Delta { content } if content == "Hi"));
+        assert!(matches!(
+            &chunks[1],
+            StreamChunk::ToolCallDelta { name: Some(n), arguments_delta, .. }
+                if n == "dns_lookup" && arguments_delta.contains("d.com")
+        ));
+        assert!(matches!(
+            &chunks[3],
+            StreamChunk::Done(FinishReason::ToolUse)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_complete_against_mock_server() {
+        let (url, server) = mock::serve_once(
+            200,
+            r#"{"candidates":[{"content":{"role":"model","parts":[{"text":"Free."}]},
+               "finishReason":"STOP"}],"usageMetadata":{"promptTokenCount":4,
+               "candidatesTokenCount":1},"modelVersion":"gemini-2.0-flash"}"#,
+        )
+        .await;
+        let p = GeminiProvider::from_config(
+            &LlmConfig::new(ProviderKind::GoogleGemini, "gemini-2.0-flash")
+                .with_api_key("gk")
+                .with_api_url(&url),
+        );
+        let req = CompletionRequest::new("gemini-2.0-flash", vec![Message::user("x")]);
+        let res = complete(&reqwest::Client::new(), &p, &req).await.unwrap();
+        let captured = server.await.unwrap();
+
+        assert_eq!(res.message.content.as_deref(), Some("Free."));
+        assert!(captured
+            .head
+            .starts_with("POST /models/gemini-2.0-flash:generateContent"));
+        assert!(captured.head.to_lowercase().contains("x-goog-api-key: gk"));
+        assert_eq!(captured.body["contents"][0]["parts"][0]["text"], "x");
+    }
+}
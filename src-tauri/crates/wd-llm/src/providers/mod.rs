@@ -0,0 +1,355 @@
+//! Concrete `LlmProvider` implementations.
+//!
+//! - **openai** – OpenAI chat completions, also used for OpenRouter, Azure
+//!   OpenAI, Ollama and custom OpenAI-compatible servers
+//! - **anthropic** – Anthropic Messages API
+//! - **gemini** – Google Gemini `generateContent`
+
+pub mod anthropic;
+pub mod gemini;
+pub mod openai;
+
+pub use anthropic::AnthropicProvider;
+pub use gemini::GeminiProvider;
+pub use openai::OpenAiProvider;
+
+use std::time::Instant;
+
+use crate::config::LlmConfig;
+use crate::error::LlmError;
+use crate::provider::{LlmProvider, ModelInfo, ProviderKind};
+use crate::request::CompletionRequest;
+use crate::response::{CompletionResponse, TokenUsage};
+
+/// Build the provider matching `config.provider`.
+pub fn provider_from_config(config: &LlmConfig) -> Result<Box<dyn LlmProvider>, LlmError> {
+    config
+        .validate()
+        .map_err(|errors| LlmError::Config(errors.join("; ")))?;
+    Ok(match config.provider {
+        ProviderKind::Anthropic => Box::new(AnthropicProvider::from_config(config)),
+        ProviderKind::GoogleGemini => Box::new(GeminiProvider::from_config(config)),
+        ProviderKind::OpenAi
+        | ProviderKind::OpenRouter
+        | ProviderKind::AzureOpenAi
+        | ProviderKind::Ollama
+        | ProviderKind::Custom => Box::new(OpenAiProvider::from_config(config)),
+    })
+}
+
+/// Send a single non-streaming completion request. No retries; see
+/// [`LlmClient`](crate::client::LlmClient) for the full transport.
+pub async fn complete(
+    client: &reqwest::Client,
+    provider: &dyn LlmProvider,
+    req: &CompletionRequest,
+) -> Result<CompletionResponse, LlmError> {
+    let body = provider.build_request_body(req)?;
+    let started = Instant::now();
+    let res = request(client, provider, req, &body)
+        .send()
+        .await
+        .map_err(|e| send_error(e, 0))?;
+    let status = res.status().as_u16();
+    let retry_after = retry_after(res.headers());
+    let text = res
+        .text()
+        .await
+        .map_err(|e| LlmError::Network(e.to_string()))?;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    provider
+        .parse_response(status, &text, latency_ms)
+        .map_err(|e| with_retry_after(e, retry_after))
+}
+
+/// POST `body` to the provider's endpoint with its headers attached.
+pub(crate) fn request(
+    client: &reqwest::Client,
+    provider: &dyn LlmProvider,
+    req: &CompletionRequest,
+    body: &serde_json::Value,
+) -> reqwest::RequestBuilder {
+    let mut builder = client.post(provider.endpoint(req)).json(body);
+    for (name, value) in provider.headers() {
+        builder = builder.header(name, value);
+    }
+    builder
+}
+
+/// Map a `reqwest` transport failure to an `LlmError`.
+pub(crate) fn send_error(e: reqwest::Error, timeout_secs: u64) -> LlmError {
+    if e.is_timeout() {
+        LlmError::Timeout { secs: timeout_secs }
+    } else {
+        LlmError::Network(e.to_string())
+    }
+}
+
+/// `Retry-After` in whole seconds, when the server sent one.
+pub(crate) fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<u64> {
+    headers
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+}
+
+/// Fill in a rate-limit error's delay from the response headers.
+pub(crate) fn with_retry_after(err: LlmError, retry_after: Option<u64>) -> LlmError {
+    match err {
+        LlmError::RateLimit {
+            message,
+            retry_after_secs: None,
+        } => LlmError::RateLimit {
+            message,
+            retry_after_secs: retry_after,
+        },
+        other => other,
+    }
+}
+
+/// Map a non-2xx response to an `LlmError`, pulling the message out of the
+/// usual `{"error": {"message": …}}` envelope when present.
+pub(crate) fn error_from_status(status: u16, body: &str) -> LlmError {
+    let message = serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|json| {
+            json.get("error")
+                .and_then(|e| e.get("message").or(Some(e)))
+                .and_then(|m| m.as_str())
+                .map(String::from)
+        })
+        .unwrap_or_else(|| body.to_string());
+
+    match status {
+        401 | 403 => LlmError::Auth(message),
+        429 => LlmError::RateLimit {
+            message,
+            retry_after_secs: None,
+        },
+        408 | 504 => LlmError::Timeout { secs: 0 },
+        _ => LlmError::Http {
+            status,
+            body: message,
+        },
+    }
+}
+
+/// Parse a JSON body, reporting malformed payloads as HTTP errors so the
+/// raw body is kept for debugging.
+pub(crate) fn parse_json(status: u16, body: &str) -> Result<serde_json::Value, LlmError> {
+    serde_json::from_str(body).map_err(|e| LlmError::Http {
+        status,
+        body: format!("invalid JSON response ({e}): {body}"),
+    })
+}
+
+/// Parse an OpenAI `/embeddings` response (`{"data": [{"index", "embedding"}]}`)
+/// into vectors ordered by `index`.
+pub(crate) fn parse_openai_embeddings(status: u16, body: &str) -> Result<Vec<Vec<f32>>, LlmError> {
+    if !(200..300).contains(&status) {
+        return Err(error_from_status(status, body));
+    }
+    let json = parse_json(status, body)?;
+    let mut data: Vec<(u64, Vec<f32>)> = json
+        .get("data")
+        .and_then(|d| d.as_array())
+        .ok_or_else(|| LlmError::Other("embeddings response has no data".into()))?
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let index = item
+                .get("index")
+                .and_then(|i| i.as_u64())
+                .unwrap_or(i as u64);
+            (index, vector(item.get("embedding")))
+        })
+        .collect();
+    data.sort_by_key(|(index, _)| *index);
+    Ok(data.into_iter().map(|(_, v)| v).collect())
+}
+
+/// A JSON number array as `f32`s; anything else is empty.
+pub(crate) fn vector(value: Option<&serde_json::Value>) -> Vec<f32> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|x| x.as_f64())
+                .map(|x| x as f32)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Fill in `estimated_cost_usd` when the model has known pricing.
+pub(crate) fn apply_cost(usage: &mut TokenUsage, models: &[ModelInfo], model: &str) {
+    let info = models
+        .iter()
+        .find(|m| m.id == model || model.starts_with(&format!("{}-", m.id)));
+    if let Some(ModelInfo {
+        input_cost_per_1k: Some(input),
+        output_cost_per_1k: Some(output),
+        ..
+    }) = info
+    {
+        usage.estimate_cost(*input, *output);
+    }
+}
+
+/// Context window for `model`, falling back to `default` for unknown models.
+pub(crate) fn context_window(models: &[ModelInfo], model: &str, default: usize) -> usize {
+    models
+        .iter()
+        .find(|m| m.id == model)
+        .map_or(default, |m| m.context_window)
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// A request captured by the mock server.
+    pub struct Captured {
+        pub head: String,
+        pub body: serde_json::Value,
+    }
+
+    /// Serve exactly one JSON response on a random local port. Returns the
+    /// base URL and a handle resolving to the captured request.
+    pub async fn serve_once(
+        status: u16,
+        body: &str,
+    ) -> (String, tokio::task::JoinHandle<Captured>) {
+        let (url, handle) = serve(vec![(status, "application/json", body.to_string())]).await;
+        let handle = tokio::spawn(async move { handle.await.unwrap().remove(0) });
+        (url, handle)
+    }
+
+    /// Serve `(status, content_type, body)` responses to consecutive
+    /// connections, one each.
+    pub async fn serve(
+        responses: Vec<(u16, &'static str, String)>,
+    ) -> (String, tokio::task::JoinHandle<Vec<Captured>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let mut captured = Vec::new();
+            for (status, content_type, body) in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = Vec::new();
+                let mut chunk = [0u8; 4096];
+                let (head, body_start, length) = loop {
+                    let n = socket.read(&mut chunk).await.unwrap();
+                    buf.extend_from_slice(&chunk[..n]);
+                    if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                        let head = String::from_utf8_lossy(&buf[..pos]).to_string();
+                        let length = head
+                            .lines()
+                            .find_map(|l| {
+                                let (k, v) = l.split_once(':')?;
+                                k.eq_ignore_ascii_case("content-length")
+                                    .then(|| v.trim().parse::<usize>().ok())?
+                            })
+                            .unwrap_or(0);
+                        break (head, pos + 4, length);
+                    }
+                };
+                while buf.len() < body_start + length {
+                    let n = socket.read(&mut chunk).await.unwrap();
+                    buf.extend_from_slice(&chunk[..n]);
+                }
+                let request_body = serde_json::from_slice(&buf[body_start..body_start + length])
+                    .unwrap_or_default();
+
+                let response = format!(
+                    "HTTP/1.1 {status} X\r\ncontent-type: {content_type}\r\ncontent-length: {}\r\nretry-after: 7\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+                socket.shutdown().await.ok();
+                captured.push(Captured {
+                    head,
+                    body: request_body,
+                });
+            }
+            captured
+        });
+        (url, handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::Message;
+
+    #[test]
+    fn test_error_from_status() {
+        let body = r#"{"error":{"message":"bad key","type":"invalid_request_error"}}"#;
+        assert!(matches!(error_from_status(401, body), LlmError::Auth(m) if m == "bad key"));
+        assert!(matches!(
+            error_from_status(429, "slow"),
+            LlmError::RateLimit { .. }
+        ));
+        assert!(matches!(
+            error_from_status(500, r#"{"error":"boom"}"#),
+            LlmError::Http { status: 500, body } if body == "boom"
+        ));
+    }
+
+    #[test]
+    fn test_provider_from_config() {
+        let cfg =
+            LlmConfig::new(ProviderKind::Anthropic, "claude-3-5-haiku-20241022").with_api_key("k");
+        assert_eq!(
+            provider_from_config(&cfg).unwrap().kind(),
+            ProviderKind::Anthropic
+        );
+        let cfg = LlmConfig::new(ProviderKind::Ollama, "llama3.1:8b");
+        assert_eq!(
+            provider_from_config(&cfg).unwrap().kind(),
+            ProviderKind::Ollama
+        );
+        let missing_key = LlmConfig::new(ProviderKind::OpenAi, "gpt-4o");
+        assert!(matches!(
+            provider_from_config(&missing_key),
+            Err(LlmError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_apply_cost_matches_dated_model() {
+        let mut usage = TokenUsage::new(1000, 1000);
+        apply_cost(
+            &mut usage,
+            &crate::provider::openai_models(),
+            "gpt-4o-2024-08-06",
+        );
+        assert!(usage.estimated_cost_usd.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_complete_rate_limit_reads_retry_after() {
+        let (url, server) = mock::serve_once(429, r#"{"error":{"message":"slow down"}}"#).await;
+        let cfg = LlmConfig::new(ProviderKind::OpenAi, "gpt-4o")
+            .with_api_key("k")
+            .with_api_url(&url);
+        let provider = OpenAiProvider::from_config(&cfg);
+        let req = CompletionRequest::new("gpt-4o", vec![Message::user("hi")]);
+        let err = complete(&reqwest::Client::new(), &provider, &req)
+            .await
+            .unwrap_err();
+        server.await.unwrap();
+        assert!(matches!(
+            err,
+            LlmError::RateLimit {
+                retry_after_secs: Some(7),
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,442 @@
+use serde_json::{json, Value};
+
+use super::{apply_cost, context_window, error_from_status, parse_json};
+use crate::config::LlmConfig;
+use crate::error::LlmError;
+use crate::message::{FunctionCall, Message, MessageMetadata, Role, ToolCall};
+use crate::provider::{ollama_default_models, openai_models, LlmProvider, ModelInfo, ProviderKind};
+use crate::request::{CompletionRequest, ResponseFormat, ToolChoice};
+use crate::response::{CompletionResponse, FinishReason, TokenUsage};
+
+/// Azure OpenAI data-plane API version used when the URL does not pin one.
+const AZURE_API_VERSION: &str = "2024-06-01";
+
+/// OpenAI chat-completions provider. The same wire format is spoken by
+/// OpenRouter, Azure OpenAI, Ollama (`/v1`) and most self-hosted servers;
+/// `kind` only changes authentication and URL handling.
+pub struct OpenAiProvider {
+    kind: ProviderKind,
+    api_url: String,
+    api_key: String,
+    extra_headers: Vec<(String, String)>,
+    models: Vec<ModelInfo>,
+}
+
+impl OpenAiProvider {
+    pub fn from_config(config: &LlmConfig) -> Self {
+        let mut api_url = config.effective_api_url().trim_end_matches('/').to_string();
+        // Ollama's default base is its native `/api`; the OpenAI-compatible
+        // routes live under `/v1`.
+        if config.provider == ProviderKind::Ollama {
+            if let Some(base) = api_url.strip_suffix("/api") {
+                api_url = format!("{base}/v1");
+            }
+        }
+        let models = match config.provider {
+            ProviderKind::OpenAi | ProviderKind::AzureOpenAi => openai_models(),
+            ProviderKind::Ollama => ollama_default_models(),
+            _ => Vec::new(),
+        };
+        let mut extra_headers: Vec<(String, String)> = config
+            .extra_headers
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        extra_headers.sort();
+        Self {
+            kind: config.provider.clone(),
+            api_url,
+            api_key: config.api_key.clone(),
+            extra_headers,
+            models,
+        }
+    }
+
+    /// Azure requires an `api-version` query parameter on every route.
+    fn with_api_version(&self, url: String) -> String {
+        if self.kind == ProviderKind::AzureOpenAi && !url.contains("api-version=") {
+            format!("{url}?api-version={AZURE_API_VERSION}")
+        } else {
+            url
+        }
+    }
+
+    fn message_json(msg: &Message) -> Value {
+        match msg.role {
+            Role::Tool => json!({
+                "role": "tool",
+                "tool_call_id": msg.tool_call_id,
+                "content": msg.content.clone().unwrap_or_default(),
+            }),
+            Role::Assistant if !msg.tool_calls.is_empty() => json!({
+                "role": "assistant",
+                "content": msg.content,
+                "tool_calls": msg.tool_calls.iter().map(|tc| json!({
+                    "id": tc.id,
+                    "type": "function",
+                    "function": {
+                        "name": tc.function.name,
+                        "arguments": tc.function.arguments.to_string(),
+                    },
+                })).collect::<Vec<_>>(),
+            }),
+            _ => {
+                let role = match msg.role {
+                    Role::System => "system",
+                    Role::User => "user",
+                    _ => "assistant",
+                };
+                json!({ "role": role, "content": msg.content.clone().unwrap_or_default() })
+            }
+        }
+    }
+
+    fn tool_choice_json(choice: &ToolChoice) -> Value {
+        match choice {
+            ToolChoice::Auto => json!("auto"),
+            ToolChoice::None => json!("none"),
+            ToolChoice::Required => json!("required"),
+            ToolChoice::Specific(name) => json!({"type": "function", "function": {"name": name}}),
+        }
+    }
+}
+
+impl LlmProvider for OpenAiProvider {
+    fn kind(&self) -> ProviderKind {
+        self.kind.clone()
+    }
+
+    fn display_name(&self) -> &str {
+        match self.kind {
+            ProviderKind::OpenRouter => "OpenRouter",
+            ProviderKind::AzureOpenAi => "Azure OpenAI",
+            ProviderKind::Ollama => "Ollama",
+            ProviderKind::Custom => "OpenAI-compatible",
+            _ => "OpenAI",
+        }
+    }
+
+    fn supported_models(&self) -> Vec<ModelInfo> {
+        self.models.clone()
+    }
+
+    fn max_context_tokens(&self, model: &str) -> usize {
+        context_window(&self.models, model, 128_000)
+    }
+
+    fn supports_tools(&self) -> bool {
+        true
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn build_request_body(&self, req: &CompletionRequest) -> Result<Value, LlmError> {
+        let mut body = json!({
+            "model": req.model,
+            "messages": req.messages.iter().map(Self::message_json).collect::<Vec<_>>(),
+        });
+        let obj = body.as_object_mut().expect("body is an object");
+
+        if !req.tools.is_empty() {
+            obj.insert(
+                "tools".into(),
+                req.tools.iter().map(|t| t.to_openai_json()).collect(),
+            );
+            obj.insert(
+                "tool_choice".into(),
+                Self::tool_choice_json(&req.tool_choice),
+            );
+        }
+        if let Some(t) = req.temperature {
+            obj.insert("temperature".into(), json!(t));
+        }
+        if let Some(n) = req.max_tokens {
+            obj.insert("max_tokens".into(), json!(n));
+        }
+        if let Some(p) = req.top_p {
+            obj.insert("top_p".into(), json!(p));
+        }
+        if !req.stop_sequences.is_empty() {
+            obj.insert("stop".into(), json!(req.stop_sequences));
+        }
+        if let Some(format) = &req.response_format {
+            let value = match format {
+                ResponseFormat::Text => json!({"type": "text"}),
+                ResponseFormat::JsonObject => json!({"type": "json_object"}),
+                ResponseFormat::JsonSchema { name, schema } => json!({
+                    "type": "json_schema",
+                    "json_schema": {"name": name, "schema": schema},
+                }),
+            };
+            obj.insert("response_format".into(), value);
+        }
+        if req.stream {
+            obj.insert("stream".into(), json!(true));
+            obj.insert("stream_options".into(), json!({"include_usage": true}));
+        }
+        Ok(body)
+    }
+
+    fn parse_response(
+        &self,
+        status: u16,
+        body: &str,
+        latency_ms: u64,
+    ) -> Result<CompletionResponse, LlmError> {
+        if !(200..300).contains(&status) {
+            return Err(error_from_status(status, body));
+        }
+        let json = parse_json(status, body)?;
+        let choice = json
+            .get("choices")
+            .and_then(|c| c.get(0))
+            .ok_or_else(|| LlmError::Other("response has no choices".into()))?;
+        let raw = choice.get("message").cloned().unwrap_or_default();
+
+        let mut tool_calls = Vec::new();
+        for tc in raw
+            .get("tool_calls")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+        {
+            let name = tc["function"]["name"]
+                .as_str()
+                .ok_or_else(|| LlmError::InvalidToolCall("tool call without a name".into()))?;
+            // Arguments arrive as a JSON-encoded string; some compatible
+            // servers send the object directly.
+            let arguments = match &tc["function"]["arguments"] {
+                Value::String(s) if s.trim().is_empty() => json!({}),
+                Value::String(s) => serde_json::from_str(s)
+                    .map_err(|e| LlmError::InvalidToolCall(format!("{name}: {e}")))?,
+                Value::Null => json!({}),
+                other => other.clone(),
+            };
+            tool_calls.push(ToolCall {
+                id: tc["id"].as_str().unwrap_or_default().to_string(),
+                function: FunctionCall {
+                    name: name.to_string(),
+                    arguments,
+                },
+            });
+        }
+
+        let finish_reason = match choice.get("finish_reason").and_then(|v| v.as_str()) {
+            Some("stop") | None => FinishReason::Stop,
+            Some("tool_calls") | Some("function_call") => FinishReason::ToolUse,
+            Some("length") => FinishReason::Length,
+            Some("content_filter") => FinishReason::ContentFilter,
+            Some(other) => FinishReason::Error(other.to_string()),
+        };
+        let model = json["model"].as_str().unwrap_or_default().to_string();
+        let mut usage = TokenUsage::new(
+            json["usage"]["prompt_tokens"].as_u64().unwrap_or(0) as usize,
+            json["usage"]["completion_tokens"].as_u64().unwrap_or(0) as usize,
+        );
+        apply_cost(&mut usage, &self.models, &model);
+
+        let content = raw
+            .get("content")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+        let mut message = if tool_calls.is_empty() {
+            Message::assistant(content.as_deref().unwrap_or_default())
+        } else {
+            let mut m = Message::assistant_tool_calls(tool_calls);
+            m.content = content.filter(|c| !c.is_empty());
+            m
+        };
+        message.metadata = MessageMetadata {
+            token_count: Some(usage.completion_tokens),
+            model: Some(model.clone()),
+            latency_ms: Some(latency_ms),
+            cost_usd: usage.estimated_cost_usd,
+            ..MessageMetadata::now()
+        };
+
+        Ok(CompletionResponse {
+            id: json["id"].as_str().unwrap_or_default().to_string(),
+            model,
+            message,
+            finish_reason,
+            usage,
+            latency_ms,
+        })
+    }
+
+    fn api_url(&self) -> &str {
+        &self.api_url
+    }
+
+    fn endpoint(&self, _req: &CompletionRequest) -> String {
+        self.with_api_version(format!("{}/chat/completions", self.api_url))
+    }
+
+    fn embeddings_endpoint(&self, _model: &str) -> String {
+        self.with_api_version(format!("{}/embeddings", self.api_url))
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        let mut headers = Vec::new();
+        if !self.api_key.is_empty() {
+            if self.kind == ProviderKind::AzureOpenAi {
+                headers.push(("api-key".to_string(), self.api_key.clone()));
+            } else {
+                headers.push((
+                    "Authorization".to_string(),
+                    format!("Bearer {}", self.api_key),
+                ));
+            }
+        }
+        headers.extend(self.extra_headers.iter().cloned());
+        headers
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{complete, mock};
+    use crate::tools::{ParamType, ToolBuilder};
+
+    fn provider(kind: ProviderKind) -> OpenAiProvider {
+        OpenAiProvider::from_config(&LlmConfig::new(kind, "gpt-4o").with_api_key("sk-test"))
+    }
+
+    #[test]
+    fn test_build_body_with_tools_and_history() {
+        let p = provider(ProviderKind::OpenAi);
+        let call = ToolCall {
+            id: "call_1".into(),
+            function: FunctionCall {
+                name: "whois_lookup".into(),
+                arguments: json!({"domain": "a.com"}),
+            },
+        };
+        let tool = ToolBuilder::new("whois_lookup", "WHOIS")
+            .param("domain", ParamType::String, "Domain", true)
+            .build();
+        let req = CompletionRequest::new(
+            "gpt-4o",
+            vec![
+                Message::system("be brief"),
+                Message::user("check a.com"),
+                Message::assistant_tool_calls(vec![call]),
+                Message::tool_result("call_1", "whois_lookup", "No match"),
+            ],
+        )
+        .with_tools(vec![tool], ToolChoice::Specific("whois_lookup".into()))
+        .with_max_tokens(100)
+        .with_stream();
+
+        let body = p.build_request_body(&req).unwrap();
+        assert_eq!(body["messages"][0]["role"], "system");
+        assert_eq!(
+            body["messages"][2]["tool_calls"][0]["function"]["arguments"],
+            r#"{"domain":"a.com"}"#
+        );
+        assert_eq!(body["messages"][3]["tool_call_id"], "call_1");
+        assert_eq!(body["tools"][0]["function"]["name"], "whois_lookup");
+        assert_eq!(body["tool_choice"]["function"]["name"], "whois_lookup");
+        assert_eq!(body["max_tokens"], 100);
+        assert_eq!(body["stream_options"]["include_usage"], true);
+    }
+
+    #[test]
+    fn test_parse_tool_call_response() {
+        let p = provider(ProviderKind::OpenAi);
+        let body = r#"{
+            "id": "chatcmpl-1", "model": "gpt-4o",
+            "choices": [{"index": 0, "finish_reason": "tool_calls", "message": {
+                "role": "assistant", "content": null,
+                "tool_calls": [{"id": "call_9", "type": "function",
+                    "function": {"name": "dns_lookup", "arguments": "{\"domain\":\"x.io\"}"}}]
+            }}],
+            "usage": {"prompt_tokens": 1000, "completion_tokens": 500}
+        }"#;
+        let res = p.parse_response(200, body, 42).unwrap();
+        assert!(res.finish_reason.is_tool_use());
+        assert_eq!(res.message.tool_calls[0].id, "call_9");
+        assert_eq!(
+            res.message.tool_calls[0].function.arguments["domain"],
+            "x.io"
+        );
+        assert_eq!(res.usage.total_tokens, 1500);
+        assert!(res.usage.estimated_cost_usd.is_some());
+        assert_eq!(res.latency_ms, 42);
+    }
+
+    #[test]
+    fn test_parse_invalid_tool_arguments() {
+        let p = provider(ProviderKind::OpenAi);
+        let body = r#"{"choices":[{"message":{"tool_calls":[
+            {"id":"c","function":{"name":"f","arguments":"{not json"}}]}}]}"#;
+        assert!(matches!(
+            p.parse_response(200, body, 0),
+            Err(LlmError::InvalidToolCall(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_error_status() {
+        let p = provider(ProviderKind::OpenAi);
+        let err = p
+            .parse_response(401, r#"{"error":{"message":"Incorrect API key"}}"#, 0)
+            .unwrap_err();
+        assert!(matches!(err, LlmError::Auth(_)));
+    }
+
+    #[test]
+    fn test_azure_and_ollama_urls() {
+        let azure = OpenAiProvider::from_config(
+            &LlmConfig::new(ProviderKind::AzureOpenAi, "gpt-4o")
+                .with_api_key("az")
+                .with_api_url("https://r.openai.azure.com/openai/deployments/d/"),
+        );
+        let req = CompletionRequest::new("gpt-4o", vec![]);
+        assert_eq!(
+            azure.endpoint(&req),
+            "https://r.openai.azure.com/openai/deployments/d/chat/completions?api-version=2024-06-01"
+        );
+        assert_eq!(azure.headers()[0].0, "api-key");
+
+        let ollama = OpenAiProvider::from_config(&LlmConfig::new(ProviderKind::Ollama, "llama3"));
+        assert_eq!(
+            ollama.endpoint(&req),
+            "http://localhost:11434/v1/chat/completions"
+        );
+        assert!(ollama.headers().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_complete_against_mock_server() {
+        let (url, server) = mock::serve_once(
+            200,
+            r#"{"id":"r1","model":"gpt-4o","choices":[{"finish_reason":"stop",
+               "message":{"role":"assistant","content":"available"}}],
+               "usage":{"prompt_tokens":5,"completion_tokens":1}}"#,
+        )
+        .await;
+        let mut cfg = LlmConfig::new(ProviderKind::OpenRouter, "gpt-4o")
+            .with_api_key("or-key")
+            .with_api_url(&url);
+        cfg.extra_headers
+            .insert("X-Title".into(), "whoisdigger".into());
+        let p = OpenAiProvider::from_config(&cfg);
+        let req = CompletionRequest::new("gpt-4o", vec![Message::user("is x.com free?")]);
+
+        let res = complete(&reqwest::Client::new(), &p, &req).await.unwrap();
+        let captured = server.await.unwrap();
+
+        assert_eq!(res.message.content.as_deref(), Some("available"));
+        assert!(captured.head.starts_with("POST /chat/completions"));
+        let head = captured.head.to_lowercase();
+        assert!(head.contains("authorization: bearer or-key"));
+        assert!(head.contains("x-title: whoisdigger"));
+        assert_eq!(captured.body["messages"][0]["content"], "is x.com free?");
+    }
+}
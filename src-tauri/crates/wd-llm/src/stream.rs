@@ -107,6 +107,82 @@ impl TextAccumulator {
     }
 }
 
+/// One Server-Sent Event: the optional `event:` name and the joined
+/// `data:` lines.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    pub event: Option<String>,
+    pub data: String,
+}
+
+/// Incremental SSE decoder. Bytes arrive in arbitrary network chunks, so
+/// partial lines (and partial UTF-8 sequences) are buffered until complete.
+#[derive(Clone, Debug, Default)]
+pub struct SseDecoder {
+    buf: Vec<u8>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed raw bytes, returning every event completed by them.
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<SseEvent> {
+        self.buf.extend_from_slice(bytes);
+        let mut events = Vec::new();
+        while let Some(pos) = self.buf.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buf.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            if let Some(event) = self.process_line(line.trim_end_matches(['\r', '\n'])) {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    /// Flush a trailing event that was not terminated by a blank line.
+    pub fn finish(&mut self) -> Option<SseEvent> {
+        if !self.buf.is_empty() {
+            let line = String::from_utf8_lossy(&std::mem::take(&mut self.buf)).to_string();
+            if let Some(event) = self.process_line(line.trim_end_matches('\r')) {
+                return Some(event);
+            }
+        }
+        self.dispatch()
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "event" => self.event = Some(value.to_string()),
+            "data" => self.data.push(value.to_string()),
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = self.event.take();
+        if self.data.is_empty() {
+            return None;
+        }
+        Some(SseEvent {
+            event,
+            data: std::mem::take(&mut self.data).join("\n"),
+        })
+    }
+}
+
 /// Parse an SSE line from an OpenAI-compatible `/v1/chat/completions` stream.
 ///
 /// Returns `None` for keep-alive, comment, or `[DONE]` lines; returns a
@@ -122,7 +198,11 @@ pub fn parse_openai_sse_line(line: &str) -> Option<StreamChunk> {
     }
 
     let json: serde_json::Value = serde_json::from_str(data).ok()?;
-    let choice = json.get("choices")?.get(0)?;
+    // With `stream_options.include_usage` the final chunk carries usage and
+    // an empty `choices` array.
+    let Some(choice) = json.get("choices").and_then(|c| c.get(0)) else {
+        return openai_usage_chunk(&json);
+    };
     let delta = choice.get("delta")?;
 
     // Check for tool calls
@@ -176,19 +256,20 @@ pub fn parse_openai_sse_line(line: &str) -> Option<StreamChunk> {
     }
 
     // Check for usage at top level
-    if let Some(usage) = json.get("usage") {
-        let prompt = usage
-            .get("prompt_tokens")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0) as usize;
-        let completion = usage
-            .get("completion_tokens")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0) as usize;
-        return Some(StreamChunk::Usage(TokenUsage::new(prompt, completion)));
-    }
-
-    None
+    openai_usage_chunk(&json)
+}
+
+fn openai_usage_chunk(json: &serde_json::Value) -> Option<StreamChunk> {
+    let usage = json.get("usage").filter(|u| u.is_object())?;
+    let prompt = usage
+        .get("prompt_tokens")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0) as usize;
+    let completion = usage
+        .get("completion_tokens")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0) as usize;
+    Some(StreamChunk::Usage(TokenUsage::new(prompt, completion)))
 }
 
 /// Parse an SSE event from the Anthropic `/v1/messages` stream format.
@@ -376,6 +457,47 @@ mod tests {
         assert!(matches!(chunk, Some(StreamChunk::Done(FinishReason::Stop))));
     }
 
+    #[test]
+    fn test_parse_openai_sse_usage_only_chunk() {
+        let line = r#"data: {"choices":[],"usage":{"prompt_tokens":12,"completion_tokens":3}}"#;
+        match parse_openai_sse_line(line) {
+            Some(StreamChunk::Usage(u)) => assert_eq!(u.total_tokens, 15),
+            _ => panic!("Expected Usage"),
+        }
+    }
+
+    #[test]
+    fn test_sse_decoder_split_chunks() {
+        let mut dec = SseDecoder::new();
+        assert!(dec.push(b"event: message_start\r\nda").is_empty());
+        let events = dec.push(b"ta: {\"a\":1}\r\n\r\n: ping\n\ndata: x\ndata: y\n\n");
+        assert_eq!(
+            events,
+            vec![
+                SseEvent {
+                    event: Some("message_start".into()),
+                    data: r#"{"a":1}"#.into(),
+                },
+                SseEvent {
+                    event: None,
+                    data: "x\ny".into(),
+                },
+            ]
+        );
+        assert!(dec.push(b"data: [DONE]").is_empty());
+        assert_eq!(dec.finish().unwrap().data, "[DONE]");
+        assert!(dec.finish().is_none());
+    }
+
+    #[test]
+    fn test_sse_decoder_multibyte_boundary() {
+        let mut dec = SseDecoder::new();
+        let bytes = "data: café\n\n".as_bytes();
+        let split = bytes.iter().position(|&b| b == 0xc3).unwrap() + 1;
+        assert!(dec.push(&bytes[..split]).is_empty());
+        assert_eq!(dec.push(&bytes[split..])[0].data, "café");
+    }
+
     #[test]
     fn test_stream_chunk_serde_roundtrip() {
         let c = StreamChunk::Delta {
@@ -1,15 +1,24 @@
-use crate::message::Message;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::message::{Message, Role};
+use crate::provider::ProviderKind;
+use crate::request::CompletionRequest;
+use crate::response::TokenUsage;
+use crate::tokenizer::{BpeEncoding, BpeTokenizer, HeuristicTokenizer, Tokenizer};
 use crate::tools::ToolDefinition;
 
-/// Fast heuristic token estimator (no external tokenizer needed).
+/// Token accounting for prompts, backed by a pluggable [`Tokenizer`].
 ///
-/// Uses the widely-cited ~4 characters per token average for English
-/// text. More accurate than nothing; less accurate than tiktoken.
-pub struct TokenEstimator;
+/// [`for_model`](Self::for_model) uses the exact BPE vocabulary for OpenAI
+/// models when it is available on disk and a per-provider character ratio
+/// otherwise. The default is the OpenAI ratio of ~4 characters per token.
+#[derive(Clone)]
+pub struct TokenEstimator {
+    tokenizer: Arc<dyn Tokenizer>,
+}
 
 impl TokenEstimator {
-    /// Chars-per-token ratio. GPT models average ~3.5–4.5.
-    const CHARS_PER_TOKEN: f64 = 4.0;
     /// Fixed overhead per message for role/formatting tokens.
     const MSG_OVERHEAD: usize = 4;
     /// Fixed overhead for a system message.
@@ -17,66 +26,115 @@ impl TokenEstimator {
     /// Per-tool overhead in the prompt.
     const TOOL_OVERHEAD: usize = 20;
 
+    pub fn new(tokenizer: Arc<dyn Tokenizer>) -> Self {
+        Self { tokenizer }
+    }
+
+    /// Character-ratio estimates tuned for `kind`.
+    pub fn heuristic(kind: ProviderKind) -> Self {
+        Self::new(Arc::new(HeuristicTokenizer::for_provider(kind)))
+    }
+
+    /// The most accurate tokenizer available for `model`: the OpenAI BPE
+    /// vocabulary from `vocab_dir` for OpenAI models (including Azure
+    /// deployments and `openai/` models on OpenRouter), the heuristic for
+    /// everything else or when the vocabulary cannot be loaded.
+    pub fn for_model(kind: ProviderKind, model: &str, vocab_dir: Option<&Path>) -> Self {
+        let encoding = match kind {
+            // Azure deployment names need not match the model name.
+            ProviderKind::OpenAi | ProviderKind::AzureOpenAi => {
+                Some(BpeEncoding::for_model(model).unwrap_or(BpeEncoding::O200kBase))
+            }
+            ProviderKind::OpenRouter | ProviderKind::Custom => BpeEncoding::for_model(model),
+            _ => None,
+        };
+        if let (Some(encoding), Some(dir)) = (encoding, vocab_dir) {
+            match BpeTokenizer::load(encoding, dir) {
+                Ok(bpe) => return Self::new(bpe),
+                Err(e) => log::debug!("using heuristic token counts for {model}: {e}"),
+            }
+        }
+        Self::heuristic(kind)
+    }
+
+    pub fn tokenizer(&self) -> &dyn Tokenizer {
+        self.tokenizer.as_ref()
+    }
+
     /// Estimate token count for a raw string.
-    pub fn estimate_tokens(text: &str) -> usize {
-        (text.len() as f64 / Self::CHARS_PER_TOKEN).ceil() as usize
+    pub fn estimate_tokens(&self, text: &str) -> usize {
+        self.tokenizer.count(text)
     }
 
     /// Estimate token count for a single message.
-    pub fn estimate_message(msg: &Message) -> usize {
-        let content_tokens = msg.content.as_deref().map_or(0, Self::estimate_tokens);
+    pub fn estimate_message(&self, msg: &Message) -> usize {
+        let content_tokens = msg
+            .content
+            .as_deref()
+            .map_or(0, |c| self.estimate_tokens(c));
         let tool_tokens: usize = msg
             .tool_calls
             .iter()
             .map(|tc| {
-                Self::estimate_tokens(&tc.function.name)
-                    + Self::estimate_tokens(&tc.function.arguments.to_string())
+                self.estimate_tokens(&tc.function.name)
+                    + self.estimate_tokens(&tc.function.arguments.to_string())
             })
             .sum();
         content_tokens + tool_tokens + Self::MSG_OVERHEAD
     }
 
     /// Estimate total token count for a message list.
-    pub fn estimate_messages(messages: &[Message]) -> usize {
+    pub fn estimate_messages(&self, messages: &[Message]) -> usize {
         let mut total: usize = Self::SYSTEM_OVERHEAD;
         for msg in messages {
-            total += Self::estimate_message(msg);
+            total += self.estimate_message(msg);
         }
         total
     }
 
     /// Estimate tokens consumed by tool definitions in the prompt.
-    pub fn estimate_tools(tools: &[ToolDefinition]) -> usize {
+    pub fn estimate_tools(&self, tools: &[ToolDefinition]) -> usize {
         tools
             .iter()
             .map(|t| {
-                Self::estimate_tokens(&t.name)
-                    + Self::estimate_tokens(&t.description)
-                    + Self::estimate_tokens(&t.parameters.to_string())
+                self.estimate_tokens(&t.name)
+                    + self.estimate_tokens(&t.description)
+                    + self.estimate_tokens(&t.parameters.to_string())
                     + Self::TOOL_OVERHEAD
             })
             .sum()
     }
 
+    /// Estimate the usage of a request and its reply, for providers that
+    /// do not report it.
+    pub fn estimate_usage(&self, req: &CompletionRequest, reply: &Message) -> TokenUsage {
+        TokenUsage::new(
+            self.estimate_messages(&req.messages) + self.estimate_tools(&req.tools),
+            self.estimate_message(reply) - Self::MSG_OVERHEAD,
+        )
+    }
+
     /// Check if messages + tools fit within a context window, reserving
     /// space for the response.
     pub fn fits_context(
+        &self,
         messages: &[Message],
         tools: &[ToolDefinition],
         max_context_tokens: usize,
         reserved_for_output: usize,
     ) -> bool {
-        let used = Self::estimate_messages(messages) + Self::estimate_tools(tools);
+        let used = self.estimate_messages(messages) + self.estimate_tools(tools);
         used + reserved_for_output <= max_context_tokens
     }
 
     /// Available tokens for output after accounting for messages + tools.
     pub fn available_for_output(
+        &self,
         messages: &[Message],
         tools: &[ToolDefinition],
         max_context_tokens: usize,
     ) -> usize {
-        let used = Self::estimate_messages(messages) + Self::estimate_tools(tools);
+        let used = self.estimate_messages(messages) + self.estimate_tools(tools);
         max_context_tokens.saturating_sub(used)
     }
 
@@ -84,21 +142,19 @@ impl TokenEstimator {
     ///
     /// Returns the number of messages dropped.
     pub fn truncate_to_budget(
+        &self,
         messages: &mut Vec<Message>,
         tools: &[ToolDefinition],
         max_context_tokens: usize,
         reserved_for_output: usize,
     ) -> usize {
-        let tool_tokens = Self::estimate_tools(tools);
+        let tool_tokens = self.estimate_tools(tools);
         let budget = max_context_tokens.saturating_sub(tool_tokens + reserved_for_output);
         let mut dropped = 0;
 
-        while Self::estimate_messages(messages) > budget && messages.len() > 1 {
+        while self.estimate_messages(messages) > budget && messages.len() > 1 {
             // Find the first non-system message and remove it
-            if let Some(idx) = messages
-                .iter()
-                .position(|m| !matches!(m.role, crate::message::Role::System))
-            {
+            if let Some(idx) = messages.iter().position(|m| m.role != Role::System) {
                 messages.remove(idx);
                 dropped += 1;
             } else {
@@ -109,6 +165,20 @@ impl TokenEstimator {
     }
 }
 
+impl Default for TokenEstimator {
+    fn default() -> Self {
+        Self::heuristic(ProviderKind::OpenAi)
+    }
+}
+
+impl std::fmt::Debug for TokenEstimator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenEstimator")
+            .field("tokenizer", &self.tokenizer.name())
+            .finish()
+    }
+}
+
 // ─── Tests ───────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -120,20 +190,20 @@ mod tests {
 
     #[test]
     fn test_estimate_tokens_short() {
-        let est = TokenEstimator::estimate_tokens("hello world");
+        let est = TokenEstimator::default().estimate_tokens("hello world");
         // 11 chars / 4 = 2.75 → ceil → 3
         assert_eq!(est, 3);
     }
 
     #[test]
     fn test_estimate_tokens_empty() {
-        assert_eq!(TokenEstimator::estimate_tokens(""), 0);
+        assert_eq!(TokenEstimator::default().estimate_tokens(""), 0);
     }
 
     #[test]
     fn test_estimate_message() {
         let m = Message::user("Test message here");
-        let est = TokenEstimator::estimate_message(&m);
+        let est = TokenEstimator::default().estimate_message(&m);
         // 17 chars → 5 tokens + 4 overhead = 9
         assert!(est > 0);
         assert!(est < 20);
@@ -142,7 +212,7 @@ mod tests {
     #[test]
     fn test_estimate_messages_includes_system_overhead() {
         let msgs = vec![Message::system("be helpful")];
-        let est = TokenEstimator::estimate_messages(&msgs);
+        let est = TokenEstimator::default().estimate_messages(&msgs);
         // system overhead (3) + msg overhead (4) + content tokens
         assert!(est >= 7);
     }
@@ -151,7 +221,7 @@ mod tests {
     fn test_fits_context() {
         let msgs = vec![Message::user("short")];
         // With a massive context, should always fit
-        assert!(TokenEstimator::fits_context(&msgs, &[], 100_000, 4096));
+        assert!(TokenEstimator::default().fits_context(&msgs, &[], 100_000, 4096));
     }
 
     #[test]
@@ -159,7 +229,7 @@ mod tests {
         let msgs = vec![Message::system(
             "You are a long system prompt ".repeat(100).as_str(),
         )];
-        assert!(!TokenEstimator::fits_context(&msgs, &[], 10, 5));
+        assert!(!TokenEstimator::default().fits_context(&msgs, &[], 10, 5));
     }
 
     #[test]
@@ -172,7 +242,7 @@ mod tests {
             Message::assistant("resp 2"),
             Message::user("msg 3"),
         ];
-        let dropped = TokenEstimator::truncate_to_budget(&mut msgs, &[], 30, 5);
+        let dropped = TokenEstimator::default().truncate_to_budget(&mut msgs, &[], 30, 5);
         assert!(dropped > 0);
         // System message should survive
         assert!(matches!(msgs[0].role, crate::message::Role::System));
@@ -181,7 +251,7 @@ mod tests {
     #[test]
     fn test_available_for_output() {
         let msgs = vec![Message::user("hi")];
-        let avail = TokenEstimator::available_for_output(&msgs, &[], 10000);
+        let avail = TokenEstimator::default().available_for_output(&msgs, &[], 10000);
         assert!(avail > 9900);
     }
 
@@ -190,7 +260,26 @@ mod tests {
         let tools = vec![ToolBuilder::new("whois", "Whois lookup")
             .param("domain", ParamType::String, "Domain", true)
             .build()];
-        let est = TokenEstimator::estimate_tools(&tools);
+        let est = TokenEstimator::default().estimate_tools(&tools);
         assert!(est > 20);
     }
+
+    #[test]
+    fn test_for_model_falls_back_to_heuristic() {
+        let dir = std::env::temp_dir().join("wd-llm-no-vocab");
+        let est = TokenEstimator::for_model(ProviderKind::OpenAi, "gpt-4o", Some(&dir));
+        assert_eq!(est.tokenizer().name(), "heuristic");
+        let est = TokenEstimator::for_model(ProviderKind::Anthropic, "claude-sonnet-4", None);
+        assert_eq!(est.estimate_tokens(&"x".repeat(35)), 10);
+    }
+
+    #[test]
+    fn test_estimate_usage() {
+        let est = TokenEstimator::default();
+        let req = CompletionRequest::new("gpt-4o", vec![Message::user("hello world")]);
+        let usage = est.estimate_usage(&req, &Message::assistant("hi there"));
+        // 3 system + 4 message + 3 content; 8 chars → 2
+        assert_eq!(usage.prompt_tokens, 10);
+        assert_eq!(usage.completion_tokens, 2);
+    }
 }
@@ -0,0 +1,445 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use base64::Engine;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::error::LlmError;
+use crate::provider::ProviderKind;
+
+/// Counts the tokens a model would see for a piece of text.
+pub trait Tokenizer: Send + Sync {
+    /// Short identifier, e.g. `"cl100k_base"` or `"heuristic"`.
+    fn name(&self) -> &str;
+
+    /// Number of tokens in `text`.
+    fn count(&self, text: &str) -> usize;
+}
+
+// ─── Heuristic ───────────────────────────────────────────────────────────────
+
+/// Character-ratio estimate for models whose vocabulary is not available.
+#[derive(Clone, Debug)]
+pub struct HeuristicTokenizer {
+    chars_per_token: f64,
+}
+
+impl HeuristicTokenizer {
+    pub fn new(chars_per_token: f64) -> Self {
+        Self { chars_per_token }
+    }
+
+    /// Average bytes per token observed for the provider's usual models on
+    /// English text. Claude and Llama vocabularies split text slightly
+    /// finer than OpenAI's.
+    pub fn for_provider(kind: ProviderKind) -> Self {
+        let chars_per_token = match kind {
+            ProviderKind::Anthropic => 3.5,
+            ProviderKind::Ollama => 3.7,
+            ProviderKind::OpenAi
+            | ProviderKind::AzureOpenAi
+            | ProviderKind::OpenRouter
+            | ProviderKind::GoogleGemini
+            | ProviderKind::Custom => 4.0,
+        };
+        Self::new(chars_per_token)
+    }
+
+    pub fn chars_per_token(&self) -> f64 {
+        self.chars_per_token
+    }
+}
+
+impl Tokenizer for HeuristicTokenizer {
+    fn name(&self) -> &str {
+        "heuristic"
+    }
+
+    fn count(&self, text: &str) -> usize {
+        (text.len() as f64 / self.chars_per_token).ceil() as usize
+    }
+}
+
+// ─── BPE ─────────────────────────────────────────────────────────────────────
+
+const CL100K_PATTERN: &str = r"(?i:'s|'t|'re|'ve|'m|'ll|'d)|[^\r\n\p{L}\p{N}]?\p{L}+|\p{N}{1,3}| ?[^\s\p{L}\p{N}]+[\r\n]*|\s*[\r\n]+|\s+";
+
+const O200K_PATTERN: &str = concat!(
+    r"[^\r\n\p{L}\p{N}]?[\p{Lu}\p{Lt}\p{Lm}\p{Lo}\p{M}]*[\p{Ll}\p{Lm}\p{Lo}\p{M}]+(?i:'s|'t|'re|'ve|'m|'ll|'d)?",
+    r"|[^\r\n\p{L}\p{N}]?[\p{Lu}\p{Lt}\p{Lm}\p{Lo}\p{M}]+[\p{Ll}\p{Lm}\p{Lo}\p{M}]*(?i:'s|'t|'re|'ve|'m|'ll|'d)?",
+    r"|\p{N}{1,3}| ?[^\s\p{L}\p{N}]+[\r\n/]*|\s*[\r\n]+|\s+",
+);
+
+/// OpenAI byte-pair encodings.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum BpeEncoding {
+    /// GPT-4, GPT-3.5 and the `text-embedding-3` models.
+    Cl100kBase,
+    /// GPT-4o, GPT-4.1, GPT-5 and the o-series.
+    O200kBase,
+}
+
+impl BpeEncoding {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Cl100kBase => "cl100k_base",
+            Self::O200kBase => "o200k_base",
+        }
+    }
+
+    /// File name of the vocabulary as published by OpenAI
+    /// (`cl100k_base.tiktoken`).
+    pub fn vocab_file(&self) -> String {
+        format!("{}.tiktoken", self.name())
+    }
+
+    /// The encoding used by an OpenAI model, if it is known. Accepts
+    /// OpenRouter-style `openai/` prefixes.
+    pub fn for_model(model: &str) -> Option<Self> {
+        let model = model.trim_start_matches("openai/");
+        let o200k = [
+            "gpt-4o",
+            "gpt-4.1",
+            "gpt-4.5",
+            "gpt-5",
+            "chatgpt-4o",
+            "o1",
+            "o3",
+            "o4",
+        ];
+        let cl100k = ["gpt-4", "gpt-3.5", "gpt-35", "text-embedding-"];
+        if o200k.iter().any(|p| model.starts_with(p)) {
+            Some(Self::O200kBase)
+        } else if cl100k.iter().any(|p| model.starts_with(p)) {
+            Some(Self::Cl100kBase)
+        } else {
+            None
+        }
+    }
+
+    fn pattern(&self) -> &'static str {
+        match self {
+            Self::Cl100kBase => CL100K_PATTERN,
+            Self::O200kBase => O200K_PATTERN,
+        }
+    }
+}
+
+/// Byte-pair encoder for a tiktoken vocabulary loaded from disk.
+///
+/// Special tokens such as `<|endoftext|>` are encoded as ordinary text.
+pub struct BpeTokenizer {
+    encoding: BpeEncoding,
+    ranks: HashMap<Vec<u8>, u32>,
+    decoder: HashMap<u32, Vec<u8>>,
+    pattern: Regex,
+}
+
+impl BpeTokenizer {
+    /// Build from token ranks. Every single byte must have a rank.
+    pub fn from_ranks(
+        encoding: BpeEncoding,
+        ranks: HashMap<Vec<u8>, u32>,
+    ) -> Result<Self, LlmError> {
+        if let Some(byte) = (0..=255u8).find(|b| !ranks.contains_key(&vec![*b])) {
+            return Err(LlmError::Config(format!(
+                "{} vocabulary has no token for byte {byte:#04x}",
+                encoding.name()
+            )));
+        }
+        let pattern = Regex::new(encoding.pattern())
+            .map_err(|e| LlmError::Config(format!("invalid split pattern: {e}")))?;
+        let decoder = ranks.iter().map(|(k, v)| (*v, k.clone())).collect();
+        Ok(Self {
+            encoding,
+            ranks,
+            decoder,
+            pattern,
+        })
+    }
+
+    /// Parse a `.tiktoken` file: one base64 token and its rank per line.
+    pub fn parse(encoding: BpeEncoding, data: &str) -> Result<Self, LlmError> {
+        let mut ranks = HashMap::new();
+        for (n, line) in data
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+        {
+            let invalid = || LlmError::Config(format!("invalid vocabulary line {}", n + 1));
+            let (token, rank) = line.trim().split_once(' ').ok_or_else(invalid)?;
+            let token = base64::engine::general_purpose::STANDARD
+                .decode(token)
+                .map_err(|_| invalid())?;
+            ranks.insert(token, rank.parse().map_err(|_| invalid())?);
+        }
+        Self::from_ranks(encoding, ranks)
+    }
+
+    pub fn from_file(encoding: BpeEncoding, path: impl AsRef<Path>) -> Result<Self, LlmError> {
+        let path = path.as_ref();
+        let data = std::fs::read_to_string(path)
+            .map_err(|e| LlmError::Config(format!("failed to read {}: {e}", path.display())))?;
+        Self::parse(encoding, &data)
+    }
+
+    /// Load `encoding` from `dir`, sharing one instance per file across the
+    /// process since vocabularies take a moment to parse.
+    pub fn load(encoding: BpeEncoding, dir: impl AsRef<Path>) -> Result<Arc<Self>, LlmError> {
+        static LOADED: OnceLock<Mutex<HashMap<PathBuf, Arc<BpeTokenizer>>>> = OnceLock::new();
+        let path = dir.as_ref().join(encoding.vocab_file());
+        let loaded = LOADED.get_or_init(Default::default);
+        if let Some(tokenizer) = loaded.lock().unwrap().get(&path) {
+            return Ok(tokenizer.clone());
+        }
+        let tokenizer = Arc::new(Self::from_file(encoding, &path)?);
+        loaded.lock().unwrap().insert(path, tokenizer.clone());
+        Ok(tokenizer)
+    }
+
+    pub fn encoding(&self) -> BpeEncoding {
+        self.encoding
+    }
+
+    /// Token ids for `text`.
+    pub fn encode(&self, text: &str) -> Vec<u32> {
+        let mut tokens = Vec::new();
+        for piece in self.split(text) {
+            match self.ranks.get(piece.as_bytes()) {
+                Some(&rank) => tokens.push(rank),
+                None => tokens.extend(self.merge(piece.as_bytes())),
+            }
+        }
+        tokens
+    }
+
+    /// Text for `tokens`; invalid UTF-8 (a split character) is replaced.
+    pub fn decode(&self, tokens: &[u32]) -> String {
+        let bytes: Vec<u8> = tokens
+            .iter()
+            .filter_map(|t| self.decoder.get(t))
+            .flatten()
+            .copied()
+            .collect();
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+
+    /// Split `text` into the pieces that are encoded independently.
+    fn split<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        let mut pieces = Vec::new();
+        let mut start = 0;
+        while let Some(m) = self.pattern.find_at(text, start) {
+            let mut end = m.end();
+            // The published patterns end in `\s+(?!\S)|\s+`. Without
+            // lookahead, a run of spaces followed by text hands its last
+            // character on, so " world" after the run stays one piece.
+            let piece = m.as_str();
+            if end < text.len()
+                && piece.chars().all(char::is_whitespace)
+                && !piece.ends_with(['\r', '\n'])
+            {
+                if let Some((last, _)) = piece.char_indices().last().filter(|(i, _)| *i > 0) {
+                    end = m.start() + last;
+                }
+            }
+            pieces.push(&text[m.start()..end]);
+            start = end;
+        }
+        pieces
+    }
+
+    /// Merge the lowest-ranked adjacent pair until no pair is in the
+    /// vocabulary.
+    fn merge(&self, piece: &[u8]) -> Vec<u32> {
+        let rank = |range: std::ops::Range<usize>| {
+            self.ranks.get(&piece[range]).copied().unwrap_or(u32::MAX)
+        };
+        // Rank of part `j` merged with the next one.
+        let pair_rank = |parts: &[(usize, u32)], j: usize| {
+            if j + 2 < parts.len() {
+                rank(parts[j].0..parts[j + 2].0)
+            } else {
+                u32::MAX
+            }
+        };
+        // Part start offsets, each with its pair rank; the last entry marks
+        // the end of the piece.
+        let mut parts: Vec<(usize, u32)> = (0..=piece.len()).map(|i| (i, u32::MAX)).collect();
+        for j in 0..parts.len() {
+            parts[j].1 = pair_rank(&parts, j);
+        }
+
+        while let Some((i, _)) = parts
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, r))| *r != u32::MAX)
+            .min_by_key(|(_, (_, r))| *r)
+        {
+            parts.remove(i + 1);
+            parts[i].1 = pair_rank(&parts, i);
+            if i > 0 {
+                parts[i - 1].1 = pair_rank(&parts, i - 1);
+            }
+        }
+
+        parts.windows(2).map(|w| rank(w[0].0..w[1].0)).collect()
+    }
+}
+
+impl Tokenizer for BpeTokenizer {
+    fn name(&self) -> &str {
+        self.encoding.name()
+    }
+
+    fn count(&self, text: &str) -> usize {
+        self.split(text)
+            .into_iter()
+            .map(|piece| {
+                if self.ranks.contains_key(piece.as_bytes()) {
+                    1
+                } else {
+                    self.merge(piece.as_bytes()).len()
+                }
+            })
+            .sum()
+    }
+}
+
+impl std::fmt::Debug for BpeTokenizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BpeTokenizer")
+            .field("encoding", &self.encoding)
+            .field("vocab_size", &self.ranks.len())
+            .finish()
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// All single bytes plus a few merges, in rank order.
+    fn toy(encoding: BpeEncoding) -> BpeTokenizer {
+        let mut ranks: HashMap<Vec<u8>, u32> = (0..=255u8).map(|b| (vec![b], b as u32)).collect();
+        for (i, token) in ["ll", "he", "llo", "hello", " w", "or", " wor", " world"]
+            .iter()
+            .enumerate()
+        {
+            ranks.insert(token.as_bytes().to_vec(), 256 + i as u32);
+        }
+        BpeTokenizer::from_ranks(encoding, ranks).unwrap()
+    }
+
+    /// The directory of `.tiktoken` files the ignored reference tests load;
+    /// run them with `WD_TIKTOKEN_DIR=... cargo test -- --ignored`.
+    fn vocab_dir() -> PathBuf {
+        let dir =
+            PathBuf::from(std::env::var("WD_TIKTOKEN_DIR").expect("WD_TIKTOKEN_DIR is not set"));
+        assert!(dir.is_dir(), "WD_TIKTOKEN_DIR is not a directory");
+        dir
+    }
+
+    #[test]
+    fn test_heuristic_per_provider() {
+        let openai = HeuristicTokenizer::for_provider(ProviderKind::OpenAi);
+        let claude = HeuristicTokenizer::for_provider(ProviderKind::Anthropic);
+        assert_eq!(openai.count("hello world"), 3);
+        assert_eq!(openai.count(""), 0);
+        assert!(claude.count(&"x".repeat(700)) > openai.count(&"x".repeat(700)));
+    }
+
+    #[test]
+    fn test_encoding_for_model() {
+        assert_eq!(
+            BpeEncoding::for_model("gpt-4o-mini"),
+            Some(BpeEncoding::O200kBase)
+        );
+        assert_eq!(
+            BpeEncoding::for_model("openai/o3"),
+            Some(BpeEncoding::O200kBase)
+        );
+        assert_eq!(
+            BpeEncoding::for_model("gpt-4-turbo"),
+            Some(BpeEncoding::Cl100kBase)
+        );
+        assert_eq!(
+            BpeEncoding::for_model("text-embedding-3-small"),
+            Some(BpeEncoding::Cl100kBase)
+        );
+        assert_eq!(BpeEncoding::for_model("llama3"), None);
+    }
+
+    #[test]
+    fn test_split_matches_reference_pieces() {
+        let bpe = toy(BpeEncoding::Cl100kBase);
+        assert_eq!(
+            bpe.split("Hello world's  test 12345\n\nok   "),
+            ["Hello", " world", "'s", " ", " test", " ", "123", "45", "\n\n", "ok", "   "]
+        );
+        let bpe = toy(BpeEncoding::O200kBase);
+        assert_eq!(
+            bpe.split("HelloWorld ns1.example.com/x\n"),
+            ["Hello", "World", " ns", "1", ".example", ".com", "/x", "\n"]
+        );
+    }
+
+    #[test]
+    fn test_merges_lowest_rank_first() {
+        let bpe = toy(BpeEncoding::Cl100kBase);
+        assert_eq!(bpe.encode("hello"), [259]);
+        assert_eq!(bpe.encode("hello world"), [259, 263]);
+        // "he" + "l" + "o": "lo" is not in the vocabulary.
+        assert_eq!(bpe.encode("helo"), [257, b'l' as u32, b'o' as u32]);
+        assert_eq!(bpe.count("hello wor"), 2);
+        assert_eq!(bpe.decode(&bpe.encode("hello wörld")), "hello wörld");
+    }
+
+    #[test]
+    fn test_parse_vocab_file() {
+        let mut data: String = (0..=255u8)
+            .map(|b| {
+                format!(
+                    "{} {b}\n",
+                    base64::engine::general_purpose::STANDARD.encode([b])
+                )
+            })
+            .collect();
+        data.push_str("aGk= 256\n");
+        let bpe = BpeTokenizer::parse(BpeEncoding::Cl100kBase, &data).unwrap();
+        assert_eq!(bpe.encode("hi"), [256]);
+        assert_eq!(bpe.name(), "cl100k_base");
+
+        let err = BpeTokenizer::parse(BpeEncoding::Cl100kBase, "aGk= 0\n").unwrap_err();
+        assert!(err.to_string().contains("byte 0x00"));
+        assert!(BpeTokenizer::parse(BpeEncoding::Cl100kBase, "!! x\n").is_err());
+    }
+
+    #[test]
+    #[ignore = "needs the tiktoken vocab files in WD_TIKTOKEN_DIR"]
+    fn test_cl100k_reference_counts() {
+        let dir = vocab_dir();
+        let bpe = BpeTokenizer::load(BpeEncoding::Cl100kBase, dir).unwrap();
+        assert_eq!(bpe.encode("hello world"), [15339, 1917]);
+        assert_eq!(
+            bpe.encode("tiktoken is great!"),
+            [83, 1609, 5963, 374, 2294, 0]
+        );
+        let text = "Registrar: Example Registrar, Inc.\nName Server: NS1.EXAMPLE.COM";
+        assert_eq!(bpe.decode(&bpe.encode(text)), text);
+    }
+
+    #[test]
+    #[ignore = "needs the tiktoken vocab files in WD_TIKTOKEN_DIR"]
+    fn test_o200k_reference_counts() {
+        let dir = vocab_dir();
+        let bpe = BpeTokenizer::load(BpeEncoding::O200kBase, dir).unwrap();
+        assert_eq!(bpe.count("hello world"), 2);
+        let text = "Domain expires 2027-03-01 — renew via ãcme.com";
+        assert_eq!(bpe.decode(&bpe.encode(text)), text);
+    }
+}
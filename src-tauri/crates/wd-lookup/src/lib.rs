@@ -1,4 +1,5 @@
 use hickory_resolver::config::*;
+use hickory_resolver::error::ResolveErrorKind;
 use hickory_resolver::TokioAsyncResolver;
 use publicsuffix::Psl;
 use rand::Rng;
@@ -100,6 +101,52 @@ pub struct LookupSettings {
     pub randomize_time_between: RandomizeSettings,
 }
 
+/// The lookup sections of the app's `settings.json`, which the frontend
+/// saves under `lookup*` keys rather than the IPC names above.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct AppLookupSections {
+    #[serde(rename = "lookupGeneral")]
+    general: LookupGeneralSettings,
+    #[serde(rename = "lookupConversion")]
+    conversion: ConversionSettings,
+    #[serde(rename = "lookupRandomizeFollow")]
+    randomize_follow: RandomizeSettings,
+    #[serde(rename = "lookupRandomizeTimeout")]
+    randomize_timeout: RandomizeSettings,
+    #[serde(rename = "lookupRandomizeTimeBetween")]
+    randomize_time_between: RandomizeSettings,
+}
+
+impl LookupSettings {
+    /// Read the lookup settings out of the app's `settings.json` contents.
+    /// Missing sections keep their defaults; an empty server means "pick
+    /// the registry's server".
+    pub fn from_app_settings(json: &str) -> Result<Self, String> {
+        let sections: AppLookupSections =
+            serde_json::from_str(json).map_err(|e| format!("Invalid settings file: {e}"))?;
+        let mut general = sections.general;
+        general.server = general.server.filter(|s| !s.trim().is_empty());
+        Ok(Self {
+            general,
+            conversion: sections.conversion,
+            randomize_follow: sections.randomize_follow,
+            randomize_timeout: sections.randomize_timeout,
+            randomize_time_between: sections.randomize_time_between,
+        })
+    }
+
+    /// [`from_app_settings`](Self::from_app_settings) on the file at
+    /// `path`, or the defaults when there is no such file.
+    pub fn load_app_settings(path: &std::path::Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => Self::from_app_settings(&json),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+}
+
 /// Compute the effective follow depth, possibly randomised.
 pub fn get_follow(settings: &LookupSettings) -> u64 {
     let rf = &settings.randomize_follow;
@@ -214,6 +261,24 @@ pub async fn dns_lookup(domain: &str) -> Result<bool, String> {
     }
 }
 
+/// Resolve the PTR names of an IP address.
+pub async fn reverse_dns_lookup(ip: &str) -> Result<Vec<String>, String> {
+    let addr: std::net::IpAddr = ip
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid IP address: {ip}"))?;
+    let resolver = TokioAsyncResolver::tokio(ResolverConfig::default(), ResolverOpts::default());
+
+    match resolver.reverse_lookup(addr).await {
+        Ok(names) => Ok(names
+            .iter()
+            .map(|name| name.to_string().trim_end_matches('.').to_string())
+            .collect()),
+        Err(e) if matches!(e.kind(), ResolveErrorKind::NoRecordsFound { .. }) => Ok(Vec::new()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
 /// Query the RDAP service for domain registration data.
 pub async fn rdap_lookup(domain: &str) -> Result<String, String> {
     let client = reqwest::Client::new();
@@ -341,6 +406,35 @@ mod tests {
         assert_eq!(prepare_domain("héllo.com", &settings), "hllo.com");
     }
 
+    #[test]
+    fn test_from_app_settings_maps_frontend_keys() {
+        let json = r#"{
+            "lookupGeneral": {"type": "whois", "psl": true, "server": "",
+                "follow": 2, "timeout": 4000, "timeBetween": 50},
+            "lookupConversion": {"enabled": true, "algorithm": "uts46"},
+            "lookupRandomizeTimeout": {"randomize": true, "minimum": 2500, "maximum": 3500},
+            "theme": {"darkMode": true}
+        }"#;
+        let settings = LookupSettings::from_app_settings(json).unwrap();
+        assert_eq!(settings.general.server, None);
+        assert_eq!(settings.general.follow, Some(2));
+        assert_eq!(settings.general.time_between, Some(50));
+        assert_eq!(settings.conversion.algorithm, ConversionAlgorithm::Uts46);
+        assert!(settings.randomize_timeout.randomize);
+        assert_eq!(settings.randomize_timeout.maximum, Some(3500));
+        assert!(!settings.randomize_follow.randomize);
+
+        assert!(LookupSettings::from_app_settings("{}").is_ok());
+        assert!(LookupSettings::from_app_settings("not json").is_err());
+        let missing = std::env::temp_dir().join("wd_lookup_no_such_settings.json");
+        assert!(LookupSettings::load_app_settings(&missing).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reverse_dns_rejects_invalid_ip() {
+        assert!(reverse_dns_lookup("not-an-ip").await.is_err());
+    }
+
     // ── DNS / RDAP (network-dependent, edge cases only) ──────────────────
 
     #[tokio::test]
@@ -0,0 +1,193 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::deadletter::DeadLetterLog;
+use crate::error::NotifyError;
+use crate::router::{Notifier, Rule};
+use crate::sinks::{
+    DesktopSink, FileFormat, FileSink, SmtpSecurity, SmtpSink, WebhookFormat, WebhookSink,
+};
+
+/// A sink as written in the notification settings.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SinkConfig {
+    Webhook {
+        name: String,
+        url: String,
+        #[serde(default)]
+        format: WebhookFormat,
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+    },
+    Smtp {
+        name: String,
+        host: String,
+        /// Defaults to the usual port for `security`.
+        #[serde(default)]
+        port: Option<u16>,
+        #[serde(default)]
+        security: SmtpSecurity,
+        #[serde(default)]
+        username: Option<String>,
+        #[serde(default)]
+        password: Option<String>,
+        from: String,
+        to: Vec<String>,
+    },
+    Desktop {
+        name: String,
+    },
+    File {
+        name: String,
+        path: String,
+        #[serde(default)]
+        format: FileFormat,
+    },
+}
+
+impl SinkConfig {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Webhook { name, .. }
+            | Self::Smtp { name, .. }
+            | Self::Desktop { name }
+            | Self::File { name, .. } => name,
+        }
+    }
+}
+
+/// Notification settings: sinks, routing rules and where to keep dead
+/// letters.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct NotifyConfig {
+    #[serde(default)]
+    pub sinks: Vec<SinkConfig>,
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+    /// Path of the dead-letter JSONL file.
+    #[serde(default)]
+    pub dead_letter: Option<String>,
+}
+
+impl NotifyConfig {
+    /// Read settings from a JSON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, NotifyError> {
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| NotifyError::Config(e.to_string()))
+    }
+
+    /// Build a notifier, rejecting duplicate sink names and rules that name
+    /// an undefined sink.
+    pub fn build(&self) -> Result<Notifier, NotifyError> {
+        let mut names = HashSet::new();
+        for sink in &self.sinks {
+            if !names.insert(sink.name()) {
+                return Err(NotifyError::Config(format!(
+                    "duplicate sink name: {}",
+                    sink.name()
+                )));
+            }
+        }
+        for rule in &self.rules {
+            if let Some(missing) = rule.sinks.iter().find(|s| !names.contains(s.as_str())) {
+                return Err(NotifyError::Config(format!(
+                    "rule {} names unknown sink {}",
+                    rule.name, missing
+                )));
+            }
+        }
+
+        let mut notifier = Notifier::new();
+        for sink in &self.sinks {
+            notifier = match sink.clone() {
+                SinkConfig::Webhook {
+                    name,
+                    url,
+                    format,
+                    headers,
+                } => notifier.with_sink(headers.into_iter().fold(
+                    WebhookSink::new(name, url).with_format(format),
+                    |s, (k, v)| s.with_header(k, v),
+                )),
+                SinkConfig::Smtp {
+                    name,
+                    host,
+                    port,
+                    security,
+                    username,
+                    password,
+                    from,
+                    to,
+                } => {
+                    let mut smtp = SmtpSink::new(name, host, from).with_security(security);
+                    if let Some(port) = port {
+                        smtp = smtp.with_port(port);
+                    }
+                    if let Some(user) = username {
+                        smtp = smtp.with_credentials(user, password.unwrap_or_default());
+                    }
+                    notifier.with_sink(to.into_iter().fold(smtp, |s, r| s.with_recipient(r)))
+                }
+                SinkConfig::Desktop { name } => notifier.with_sink(DesktopSink::new(name)),
+                SinkConfig::File { name, path, format } => {
+                    notifier.with_sink(FileSink::new(name, path).with_format(format))
+                }
+            };
+        }
+        for rule in &self.rules {
+            notifier = notifier.with_rule(rule.clone());
+        }
+        if let Some(path) = &self.dead_letter {
+            notifier = notifier.with_dead_letter(DeadLetterLog::new(path));
+        }
+        Ok(notifier)
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::{NotificationKind, Severity};
+
+    #[test]
+    fn test_parse_and_validate() {
+        let json = r#"{
+            "sinks": [
+                {"type": "webhook", "name": "slack", "url": "https://hooks.slack.test/x", "format": "slack"},
+                {"type": "smtp", "name": "mail", "host": "smtp.test", "security": "tls",
+                 "username": "bot", "password": "pw", "from": "bot@test", "to": ["ops@test"]},
+                {"type": "file", "name": "log", "path": "alerts.jsonl"}
+            ],
+            "rules": [
+                {"name": "drops", "sinks": ["slack", "mail"], "kinds": ["phase_change"],
+                 "min_severity": "critical", "rate_limit": {"max": 5, "per_secs": 3600}},
+                {"name": "everything", "sinks": ["log"]}
+            ],
+            "dead_letter": "dead.jsonl"
+        }"#;
+        let config: NotifyConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.rules[0].kinds, [NotificationKind::PhaseChange]);
+        assert_eq!(config.rules[0].min_severity, Severity::Critical);
+        assert_eq!(config.rules[1].min_severity, Severity::Info);
+        let notifier = config.build().unwrap();
+        assert_eq!(notifier.rules().len(), 2);
+        assert!(notifier.dead_letter().is_some());
+
+        let mut bad = config.clone();
+        bad.rules[1].sinks.push("pager".into());
+        assert_eq!(
+            bad.build().err(),
+            Some(NotifyError::Config(
+                "rule everything names unknown sink pager".into()
+            ))
+        );
+        let mut dup = config;
+        dup.sinks.push(SinkConfig::Desktop { name: "log".into() });
+        assert!(dup.build().is_err());
+    }
+}
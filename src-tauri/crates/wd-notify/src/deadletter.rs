@@ -0,0 +1,58 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::error::NotifyError;
+use crate::sinks::file::append_line;
+use crate::template::Message;
+
+/// A message a sink failed to deliver.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DeadLetter {
+    pub failed_at: DateTime<Utc>,
+    pub rule: String,
+    pub sink: String,
+    pub error: NotifyError,
+    pub message: Message,
+}
+
+/// JSONL file of undelivered messages, kept so they can be inspected or
+/// sent again by hand.
+pub struct DeadLetterLog {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl DeadLetterLog {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub async fn record(&self, letter: &DeadLetter) -> Result<(), NotifyError> {
+        let line = serde_json::to_string(letter).map_err(|e| NotifyError::Io(e.to_string()))?;
+        let _guard = self.lock.lock().await;
+        append_line(&self.path, &format!("{line}\n")).await
+    }
+
+    /// Every recorded letter, oldest first. Unreadable lines are skipped.
+    pub fn entries(&self) -> Result<Vec<DeadLetter>, NotifyError> {
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(c) => c,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(content
+            .lines()
+            .filter_map(|l| serde_json::from_str(l).ok())
+            .collect())
+    }
+}
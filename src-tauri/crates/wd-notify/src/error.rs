@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Errors raised while delivering a notification.
+#[derive(Error, Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum NotifyError {
+    #[error("sink returned HTTP {status}: {body}")]
+    Http { status: u16, body: String },
+
+    #[error("network error: {0}")]
+    Network(String),
+
+    #[error("SMTP error: {0}")]
+    Smtp(String),
+
+    #[error("I/O error: {0}")]
+    Io(String),
+
+    #[error("notifier command failed: {0}")]
+    Command(String),
+
+    #[error("timeout after {secs}s")]
+    Timeout { secs: u64 },
+
+    #[error("unknown sink: {0}")]
+    UnknownSink(String),
+
+    #[error("configuration error: {0}")]
+    Config(String),
+}
+
+impl From<std::io::Error> for NotifyError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e.to_string())
+    }
+}
+
+impl From<reqwest::Error> for NotifyError {
+    fn from(e: reqwest::Error) -> Self {
+        Self::Network(e.to_string())
+    }
+}
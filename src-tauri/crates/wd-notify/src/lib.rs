@@ -0,0 +1,34 @@
+//! # wd-notify
+//!
+//! Gets monitoring alerts out of the app: watchlist changes, expiry phase
+//! changes, scheduler run diffs and threat hits.
+//!
+//! ## Modules
+//!
+//! - **message** – notifications and their constructors from monitoring events
+//! - **template** – `{{name}}` title and body templates
+//! - **sink** – the `NotificationSink` trait
+//! - **sinks** – webhook (JSON, Slack, Discord), SMTP, desktop and file sinks
+//! - **router** – routing rules, rate limits and the `Notifier`
+//! - **deadletter** – JSONL log of undelivered messages
+//! - **config** – notification settings as JSON
+
+pub mod config;
+pub mod deadletter;
+pub mod error;
+pub mod message;
+pub mod router;
+pub mod sink;
+pub mod sinks;
+pub mod template;
+
+pub use config::{NotifyConfig, SinkConfig};
+pub use deadletter::{DeadLetter, DeadLetterLog};
+pub use error::NotifyError;
+pub use message::{Notification, NotificationKind, Severity};
+pub use router::{Delivery, DispatchReport, Failure, Notifier, RateLimit, Rule};
+pub use sink::{NotificationSink, SinkFuture};
+pub use sinks::{
+    DesktopSink, FileFormat, FileSink, SmtpSecurity, SmtpSink, WebhookFormat, WebhookSink,
+};
+pub use template::{Message, Template};
@@ -0,0 +1,344 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use wd_expiry::{ExpiryPhase, WatchField, WatchUpdate};
+use wd_scheduler::runner::DomainRunResult;
+use wd_scheduler::RunDiff;
+use wd_threat::{RiskAssessment, ThreatLevel};
+
+/// Most entries listed in a notification body before the rest is summarised.
+const MAX_LISTED: usize = 20;
+
+/// What produced a notification.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// A watched domain's status, registrar or nameservers changed.
+    WatchChange,
+    /// A watched domain moved to another expiry phase.
+    PhaseChange,
+    /// Two runs of a scheduled job differ.
+    RunDiff,
+    /// A domain scored as a threat.
+    ThreatHit,
+    Custom,
+}
+
+impl NotificationKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::WatchChange => "watch_change",
+            Self::PhaseChange => "phase_change",
+            Self::RunDiff => "run_diff",
+            Self::ThreatHit => "threat_hit",
+            Self::Custom => "custom",
+        }
+    }
+}
+
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    #[default]
+    Info,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+/// An event worth telling someone about, before any template is applied.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    pub severity: Severity,
+    pub title: String,
+    pub body: String,
+    pub domain: Option<String>,
+    /// Event details, available to templates as `{{name}}`.
+    pub fields: BTreeMap<String, String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Notification {
+    pub fn new(kind: NotificationKind, severity: Severity, title: impl Into<String>) -> Self {
+        Self {
+            kind,
+            severity,
+            title: title.into(),
+            body: String::new(),
+            domain: None,
+            fields: BTreeMap::new(),
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_body(mut self, body: impl Into<String>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    pub fn with_domain(mut self, domain: impl Into<String>) -> Self {
+        self.domain = Some(domain.into());
+        self
+    }
+
+    pub fn with_field(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.fields.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn with_created_at(mut self, at: DateTime<Utc>) -> Self {
+        self.created_at = at;
+        self
+    }
+
+    /// Notification for a watchlist check that changed something. A domain
+    /// becoming available or entering pending delete is critical; registrar,
+    /// nameserver and phase changes are warnings.
+    pub fn from_watch_update(update: &WatchUpdate) -> Self {
+        let phase = label(&update.phase);
+        let phase_changed = update.changes.iter().any(|c| c.field == WatchField::Phase);
+        let kind = if phase_changed {
+            NotificationKind::PhaseChange
+        } else {
+            NotificationKind::WatchChange
+        };
+        let severity = if update.status == "available"
+            || (phase_changed
+                && matches!(
+                    update.phase,
+                    ExpiryPhase::PendingDelete | ExpiryPhase::Deleted
+                )) {
+            Severity::Critical
+        } else if update.changes.iter().any(|c| c.field != WatchField::Status) {
+            Severity::Warning
+        } else {
+            Severity::Info
+        };
+        let title = if phase_changed {
+            format!("{} entered {}", update.domain, phase.replace('_', " "))
+        } else {
+            let fields: Vec<String> = update.changes.iter().map(|c| label(&c.field)).collect();
+            format!("{}: {} changed", update.domain, fields.join(", "))
+        };
+
+        let mut n = Self::new(kind, severity, title)
+            .with_domain(&update.domain)
+            .with_field("status", &update.status)
+            .with_field("phase", phase)
+            .with_created_at(update.checked_at);
+        if let Some(expiry) = update.expiry_date {
+            n = n.with_field("expiry_date", expiry.format("%Y-%m-%d").to_string());
+        }
+        let mut lines = Vec::new();
+        for change in &update.changes {
+            let field = label(&change.field);
+            let old = change.old.as_deref().unwrap_or("-");
+            let new = change.new.as_deref().unwrap_or("-");
+            lines.push(format!("{field}: {old} → {new}"));
+            n.fields.insert(format!("{field}_old"), old.to_string());
+            n.fields.insert(field, new.to_string());
+        }
+        n.with_body(lines.join("\n"))
+    }
+
+    /// Notification for the difference between two runs of a scheduled
+    /// job, or `None` when the runs found the same domains with the same
+    /// results.
+    pub fn from_run_diff(diff: &RunDiff) -> Option<Self> {
+        let changed =
+            diff.new_domains.len() + diff.removed_domains.len() + diff.status_changes.len();
+        if changed == 0 {
+            return None;
+        }
+        let severity = if !diff.status_changes.is_empty() || diff.success_rate_delta < 0.0 {
+            Severity::Warning
+        } else {
+            Severity::Info
+        };
+        let title = format!(
+            "Job {}: {} change{} between runs {} and {}",
+            diff.job_id,
+            changed,
+            if changed == 1 { "" } else { "s" },
+            diff.run_a,
+            diff.run_b
+        );
+
+        let mut lines: Vec<String> = diff
+            .new_domains
+            .iter()
+            .map(|d| format!("+ {d}"))
+            .chain(diff.removed_domains.iter().map(|d| format!("- {d}")))
+            .chain(diff.status_changes.iter().map(|c| {
+                format!(
+                    "{}: {} → {}",
+                    c.domain,
+                    run_result(&c.old),
+                    run_result(&c.new)
+                )
+            }))
+            .collect();
+        if lines.len() > MAX_LISTED {
+            let rest = lines.len() - MAX_LISTED;
+            lines.truncate(MAX_LISTED);
+            lines.push(format!("… and {rest} more"));
+        }
+
+        Some(
+            Self::new(NotificationKind::RunDiff, severity, title)
+                .with_body(lines.join("\n"))
+                .with_field("job_id", &diff.job_id)
+                .with_field("run_a", diff.run_a.to_string())
+                .with_field("run_b", diff.run_b.to_string())
+                .with_field("new_domains", diff.new_domains.len().to_string())
+                .with_field("removed_domains", diff.removed_domains.len().to_string())
+                .with_field("status_changes", diff.status_changes.len().to_string())
+                .with_field(
+                    "success_rate_delta",
+                    format!("{:+.1}", diff.success_rate_delta),
+                ),
+        )
+    }
+
+    /// Notification for a risk assessment, or `None` when nothing was
+    /// found. High and critical risk is critical, medium risk a warning.
+    pub fn from_risk(assessment: &RiskAssessment) -> Option<Self> {
+        let risk = &assessment.risk;
+        let severity = match risk.level {
+            ThreatLevel::None => return None,
+            ThreatLevel::Low => Severity::Info,
+            ThreatLevel::Medium => Severity::Warning,
+            ThreatLevel::High | ThreatLevel::Critical => Severity::Critical,
+        };
+        let level = label(&risk.level);
+        let body: Vec<String> = risk
+            .indicators
+            .iter()
+            .take(MAX_LISTED)
+            .map(|i| format!("{}: {}", label(&i.category), i.description))
+            .collect();
+        Some(
+            Self::new(
+                NotificationKind::ThreatHit,
+                severity,
+                format!(
+                    "{} scored {} risk ({}/100)",
+                    assessment.domain, level, risk.score
+                ),
+            )
+            .with_domain(&assessment.domain)
+            .with_body(if body.is_empty() {
+                risk.summary.clone()
+            } else {
+                body.join("\n")
+            })
+            .with_field("score", risk.score.to_string())
+            .with_field("level", level)
+            .with_field("summary", &risk.summary)
+            .with_created_at(assessment.assessed_at),
+        )
+    }
+}
+
+/// The serde name of a unit-like enum value, e.g. `pending_delete`.
+fn label<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        Ok(other) => other.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+fn run_result(result: &DomainRunResult) -> String {
+    match result {
+        DomainRunResult::Error(e) => format!("error ({e})"),
+        other => label(other),
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wd_expiry::WatchChange;
+    use wd_scheduler::runner::StatusChange;
+
+    fn update(phase: ExpiryPhase, changes: Vec<WatchChange>) -> WatchUpdate {
+        WatchUpdate {
+            domain: "brand.com".into(),
+            changes,
+            status: "expired".into(),
+            phase,
+            expiry_date: None,
+            checked_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_watch_update_kind_and_severity() {
+        let n = Notification::from_watch_update(&update(
+            ExpiryPhase::PendingDelete,
+            vec![WatchChange {
+                field: WatchField::Phase,
+                old: Some("redemption_grace".into()),
+                new: Some("pending_delete".into()),
+            }],
+        ));
+        assert_eq!(n.kind, NotificationKind::PhaseChange);
+        assert_eq!(n.severity, Severity::Critical);
+        assert_eq!(n.title, "brand.com entered pending delete");
+        assert_eq!(n.body, "phase: redemption_grace → pending_delete");
+        assert_eq!(n.fields["phase_old"], "redemption_grace");
+
+        let n = Notification::from_watch_update(&update(
+            ExpiryPhase::Active,
+            vec![WatchChange {
+                field: WatchField::Registrar,
+                old: None,
+                new: Some("Porkbun".into()),
+            }],
+        ));
+        assert_eq!(n.kind, NotificationKind::WatchChange);
+        assert_eq!(n.severity, Severity::Warning);
+        assert_eq!(n.title, "brand.com: registrar changed");
+    }
+
+    #[test]
+    fn test_run_diff_lists_changes() {
+        let mut diff = RunDiff {
+            job_id: "nightly".into(),
+            run_a: 4,
+            run_b: 5,
+            new_domains: Vec::new(),
+            removed_domains: Vec::new(),
+            status_changes: Vec::new(),
+            success_rate_delta: 0.0,
+        };
+        assert!(Notification::from_run_diff(&diff).is_none());
+
+        diff.new_domains.push("new.com".into());
+        diff.status_changes.push(StatusChange {
+            domain: "slow.com".into(),
+            old: DomainRunResult::Success,
+            new: DomainRunResult::Timeout,
+        });
+        let n = Notification::from_run_diff(&diff).unwrap();
+        assert_eq!(n.severity, Severity::Warning);
+        assert_eq!(n.title, "Job nightly: 2 changes between runs 4 and 5");
+        assert_eq!(n.body, "+ new.com\nslow.com: success → timeout");
+    }
+}
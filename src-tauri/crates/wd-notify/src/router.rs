@@ -0,0 +1,398 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::deadletter::{DeadLetter, DeadLetterLog};
+use crate::error::NotifyError;
+use crate::message::{Notification, NotificationKind, Severity};
+use crate::sink::NotificationSink;
+use crate::template::Template;
+
+/// At most `max` notifications per `per_secs` seconds.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    pub max: u32,
+    pub per_secs: u64,
+}
+
+/// Routes matching notifications to one or more sinks.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Rule {
+    pub name: String,
+    /// Names of the sinks to deliver to.
+    pub sinks: Vec<String>,
+    /// Kinds the rule applies to; empty for all.
+    #[serde(default)]
+    pub kinds: Vec<NotificationKind>,
+    #[serde(default)]
+    pub min_severity: Severity,
+    /// Domains the rule applies to; empty for all. `example.com` matches
+    /// the domain and its subdomains, `*.example.com` only subdomains.
+    #[serde(default)]
+    pub domains: Vec<String>,
+    /// Template for the rule's messages; the notification's own title and
+    /// body when unset.
+    #[serde(default)]
+    pub template: Option<Template>,
+    #[serde(default)]
+    pub rate_limit: Option<RateLimit>,
+}
+
+impl Rule {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            sinks: Vec::new(),
+            kinds: Vec::new(),
+            min_severity: Severity::Info,
+            domains: Vec::new(),
+            template: None,
+            rate_limit: None,
+        }
+    }
+
+    pub fn to_sink(mut self, sink: impl Into<String>) -> Self {
+        self.sinks.push(sink.into());
+        self
+    }
+
+    pub fn for_kind(mut self, kind: NotificationKind) -> Self {
+        self.kinds.push(kind);
+        self
+    }
+
+    pub fn for_domain(mut self, pattern: impl Into<String>) -> Self {
+        self.domains.push(pattern.into().to_lowercase());
+        self
+    }
+
+    pub fn with_min_severity(mut self, severity: Severity) -> Self {
+        self.min_severity = severity;
+        self
+    }
+
+    pub fn with_template(mut self, template: Template) -> Self {
+        self.template = Some(template);
+        self
+    }
+
+    pub fn with_rate_limit(mut self, max: u32, per_secs: u64) -> Self {
+        self.rate_limit = Some(RateLimit { max, per_secs });
+        self
+    }
+
+    pub fn matches(&self, n: &Notification) -> bool {
+        if n.severity < self.min_severity {
+            return false;
+        }
+        if !self.kinds.is_empty() && !self.kinds.contains(&n.kind) {
+            return false;
+        }
+        if self.domains.is_empty() {
+            return true;
+        }
+        let Some(domain) = n.domain.as_deref().map(str::to_lowercase) else {
+            return false;
+        };
+        self.domains.iter().any(|p| match p.strip_prefix("*.") {
+            Some(parent) => domain.ends_with(&format!(".{parent}")),
+            None => domain == *p || domain.ends_with(&format!(".{p}")),
+        })
+    }
+}
+
+/// A message handed to a sink.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Delivery {
+    pub rule: String,
+    pub sink: String,
+}
+
+/// A message a sink failed to take.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Failure {
+    pub rule: String,
+    pub sink: String,
+    pub error: NotifyError,
+}
+
+/// Outcome of routing one or more notifications.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct DispatchReport {
+    pub delivered: Vec<Delivery>,
+    pub failed: Vec<Failure>,
+    /// Rules that matched but were over their rate limit.
+    pub rate_limited: Vec<String>,
+}
+
+impl DispatchReport {
+    pub fn merge(&mut self, other: DispatchReport) {
+        self.delivered.extend(other.delivered);
+        self.failed.extend(other.failed);
+        self.rate_limited.extend(other.rate_limited);
+    }
+}
+
+/// Sends notifications to sinks according to routing rules.
+///
+/// Each matching rule renders the notification with its template and
+/// delivers it to every sink it names. Deliveries that fail are written to
+/// the dead-letter log when one is set.
+#[derive(Default)]
+pub struct Notifier {
+    sinks: HashMap<String, Arc<dyn NotificationSink>>,
+    rules: Vec<Rule>,
+    dead_letter: Option<DeadLetterLog>,
+    /// Recent send times per rule, for rate limiting.
+    sent: Mutex<HashMap<String, VecDeque<DateTime<Utc>>>>,
+}
+
+impl Notifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a sink under its own name, replacing any sink of that name.
+    pub fn with_sink(mut self, sink: impl NotificationSink + 'static) -> Self {
+        self.add_sink(Arc::new(sink));
+        self
+    }
+
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    pub fn with_dead_letter(mut self, log: DeadLetterLog) -> Self {
+        self.dead_letter = Some(log);
+        self
+    }
+
+    pub fn add_sink(&mut self, sink: Arc<dyn NotificationSink>) {
+        self.sinks.insert(sink.name().to_string(), sink);
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    pub fn dead_letter(&self) -> Option<&DeadLetterLog> {
+        self.dead_letter.as_ref()
+    }
+
+    pub async fn notify(&self, notification: &Notification) -> DispatchReport {
+        self.notify_at(notification, Utc::now()).await
+    }
+
+    pub async fn notify_all(&self, notifications: &[Notification]) -> DispatchReport {
+        let mut report = DispatchReport::default();
+        for n in notifications {
+            report.merge(self.notify(n).await);
+        }
+        report
+    }
+
+    /// Route `notification` as of `now`, which drives rate limiting.
+    pub async fn notify_at(
+        &self,
+        notification: &Notification,
+        now: DateTime<Utc>,
+    ) -> DispatchReport {
+        let mut report = DispatchReport::default();
+        for rule in self.rules.iter().filter(|r| r.matches(notification)) {
+            if !self.admit(rule, now) {
+                log::debug!("notification rule {} is rate limited", rule.name);
+                report.rate_limited.push(rule.name.clone());
+                continue;
+            }
+            let message = rule
+                .template
+                .clone()
+                .unwrap_or_default()
+                .render(notification);
+            for sink_name in &rule.sinks {
+                let result = match self.sinks.get(sink_name) {
+                    Some(sink) => sink.send(&message).await,
+                    None => Err(NotifyError::UnknownSink(sink_name.clone())),
+                };
+                match result {
+                    Ok(()) => report.delivered.push(Delivery {
+                        rule: rule.name.clone(),
+                        sink: sink_name.clone(),
+                    }),
+                    Err(error) => {
+                        log::warn!("notification sink {sink_name} failed: {error}");
+                        if let Some(log) = &self.dead_letter {
+                            let letter = DeadLetter {
+                                failed_at: now,
+                                rule: rule.name.clone(),
+                                sink: sink_name.clone(),
+                                error: error.clone(),
+                                message: message.clone(),
+                            };
+                            if let Err(e) = log.record(&letter).await {
+                                log::error!("could not write dead letter: {e}");
+                            }
+                        }
+                        report.failed.push(Failure {
+                            rule: rule.name.clone(),
+                            sink: sink_name.clone(),
+                            error,
+                        });
+                    }
+                }
+            }
+        }
+        report
+    }
+
+    /// Whether `rule` may send at `now`, recording the send if so.
+    fn admit(&self, rule: &Rule, now: DateTime<Utc>) -> bool {
+        let Some(limit) = rule.rate_limit else {
+            return true;
+        };
+        let mut sent = self.sent.lock().unwrap();
+        let times = sent.entry(rule.name.clone()).or_default();
+        let window_start = now - Duration::seconds(limit.per_secs as i64);
+        while times.front().is_some_and(|t| *t <= window_start) {
+            times.pop_front();
+        }
+        if times.len() >= limit.max as usize {
+            return false;
+        }
+        times.push_back(now);
+        true
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sink::SinkFuture;
+    use crate::template::Message;
+
+    /// Records what it is sent, or fails every delivery.
+    struct TestSink {
+        name: &'static str,
+        fail: bool,
+        sent: Mutex<Vec<String>>,
+    }
+
+    impl TestSink {
+        fn new(name: &'static str, fail: bool) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                fail,
+                sent: Mutex::new(Vec::new()),
+            })
+        }
+    }
+
+    impl NotificationSink for TestSink {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn send<'a>(&'a self, message: &'a Message) -> SinkFuture<'a> {
+            Box::pin(async move {
+                if self.fail {
+                    return Err(NotifyError::Network("connection refused".into()));
+                }
+                self.sent.lock().unwrap().push(message.title.clone());
+                Ok(())
+            })
+        }
+    }
+
+    fn alert(domain: &str, severity: Severity) -> Notification {
+        Notification::new(
+            NotificationKind::PhaseChange,
+            severity,
+            format!("{domain} changed"),
+        )
+        .with_domain(domain)
+        .with_field("phase", "pending_delete")
+    }
+
+    #[test]
+    fn test_rule_matching() {
+        let rule = Rule::new("brand")
+            .for_kind(NotificationKind::PhaseChange)
+            .for_domain("brand.com")
+            .for_domain("*.shop.io")
+            .with_min_severity(Severity::Warning);
+        assert!(rule.matches(&alert("brand.com", Severity::Warning)));
+        assert!(rule.matches(&alert("www.brand.com", Severity::Critical)));
+        assert!(!rule.matches(&alert("brand.com", Severity::Info)));
+        assert!(!rule.matches(&alert("shop.io", Severity::Critical)));
+        assert!(rule.matches(&alert("eu.shop.io", Severity::Critical)));
+        assert!(!rule.matches(&alert("notbrand.com", Severity::Critical)));
+        assert!(!rule.matches(&Notification::new(
+            NotificationKind::RunDiff,
+            Severity::Critical,
+            "job"
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_routing_rate_limit_and_dead_letters() {
+        let ok = TestSink::new("ok", false);
+        let broken = TestSink::new("broken", true);
+        let dir = std::env::temp_dir().join(format!("wd-notify-router-{}", std::process::id()));
+        let mut notifier = Notifier::new()
+            .with_rule(
+                Rule::new("critical")
+                    .with_min_severity(Severity::Critical)
+                    .to_sink("ok")
+                    .to_sink("broken")
+                    .to_sink("missing")
+                    .with_template(Template::new("!! {{title}} ({{phase}})", "{{body}}")),
+            )
+            .with_rule(Rule::new("all").to_sink("ok").with_rate_limit(2, 60))
+            .with_dead_letter(DeadLetterLog::new(dir.join("dead.jsonl")));
+        notifier.add_sink(ok.clone());
+        notifier.add_sink(broken.clone());
+
+        let t0 = Utc::now();
+        let report = notifier
+            .notify_at(&alert("drop.com", Severity::Critical), t0)
+            .await;
+        assert_eq!(report.delivered.len(), 2);
+        assert_eq!(report.failed.len(), 2);
+        assert_eq!(
+            report.failed[1].error,
+            NotifyError::UnknownSink("missing".into())
+        );
+        assert_eq!(
+            *ok.sent.lock().unwrap(),
+            ["!! drop.com changed (pending_delete)", "drop.com changed"]
+        );
+
+        // The "all" rule allows two per minute.
+        let second = notifier
+            .notify_at(&alert("a.com", Severity::Info), t0 + Duration::seconds(10))
+            .await;
+        assert_eq!(second.delivered.len(), 1);
+        let third = notifier
+            .notify_at(&alert("b.com", Severity::Info), t0 + Duration::seconds(20))
+            .await;
+        assert_eq!(third.rate_limited, ["all"]);
+        let later = notifier
+            .notify_at(&alert("c.com", Severity::Info), t0 + Duration::seconds(61))
+            .await;
+        assert_eq!(later.delivered.len(), 1);
+
+        let letters = notifier.dead_letter().unwrap().entries().unwrap();
+        assert_eq!(letters.len(), 2);
+        assert_eq!(letters[0].sink, "broken");
+        assert_eq!(
+            letters[0].message.title,
+            "!! drop.com changed (pending_delete)"
+        );
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
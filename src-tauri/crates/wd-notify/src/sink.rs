@@ -0,0 +1,17 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::error::NotifyError;
+use crate::template::Message;
+
+/// Boxed future returned by [`NotificationSink::send`], so sinks can be
+/// held as trait objects.
+pub type SinkFuture<'a> = Pin<Box<dyn Future<Output = Result<(), NotifyError>> + Send + 'a>>;
+
+/// Somewhere notifications are delivered to.
+pub trait NotificationSink: Send + Sync {
+    /// Name that routing rules refer to the sink by.
+    fn name(&self) -> &str;
+
+    fn send<'a>(&'a self, message: &'a Message) -> SinkFuture<'a>;
+}
@@ -0,0 +1,154 @@
+use tokio::process::Command;
+
+use crate::error::NotifyError;
+use crate::sink::{NotificationSink, SinkFuture};
+use crate::template::Message;
+
+/// Raises a desktop notification by running the platform's notifier:
+/// `notify-send` on Linux and the BSDs, `osascript` on macOS and a
+/// PowerShell toast on Windows.
+///
+/// The title and body are passed as the `WD_NOTIFY_TITLE` and
+/// `WD_NOTIFY_BODY` environment variables, and replace `{title}` and
+/// `{body}` in the command arguments.
+pub struct DesktopSink {
+    name: String,
+    program: String,
+    args: Vec<String>,
+}
+
+impl DesktopSink {
+    pub fn new(name: impl Into<String>) -> Self {
+        let (program, args) = platform_command();
+        Self {
+            name: name.into(),
+            program: program.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    /// Run `program` instead of the platform notifier.
+    pub fn with_command<I, S>(mut self, program: impl Into<String>, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.program = program.into();
+        self.args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    async fn run(&self, message: &Message) -> Result<(), NotifyError> {
+        let args = self.args.iter().map(|a| {
+            a.replace("{title}", &message.title)
+                .replace("{body}", &message.body)
+        });
+        let output = Command::new(&self.program)
+            .args(args)
+            .env("WD_NOTIFY_TITLE", &message.title)
+            .env("WD_NOTIFY_BODY", &message.body)
+            .kill_on_drop(true)
+            .output()
+            .await
+            .map_err(|e| NotifyError::Command(format!("{}: {}", self.program, e)))?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(NotifyError::Command(format!(
+                "{} exited with {}: {}",
+                self.program,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )))
+        }
+    }
+}
+
+impl NotificationSink for DesktopSink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn send<'a>(&'a self, message: &'a Message) -> SinkFuture<'a> {
+        Box::pin(self.run(message))
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn platform_command() -> (&'static str, &'static [&'static str]) {
+    (
+        "osascript",
+        &[
+            "-e",
+            "display notification (system attribute \"WD_NOTIFY_BODY\") \
+             with title (system attribute \"WD_NOTIFY_TITLE\")",
+        ],
+    )
+}
+
+#[cfg(target_os = "windows")]
+fn platform_command() -> (&'static str, &'static [&'static str]) {
+    (
+        "powershell",
+        &[
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, \
+             ContentType = WindowsRuntime] > $null; \
+             $t = [Windows.UI.Notifications.ToastNotificationManager]::GetTemplateContent(\
+             [Windows.UI.Notifications.ToastTemplateType]::ToastText02); \
+             $x = $t.GetElementsByTagName('text'); \
+             $x.Item(0).AppendChild($t.CreateTextNode($env:WD_NOTIFY_TITLE)) > $null; \
+             $x.Item(1).AppendChild($t.CreateTextNode($env:WD_NOTIFY_BODY)) > $null; \
+             [Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier(\
+             'Whoisdigger').Show([Windows.UI.Notifications.ToastNotification]::new($t))",
+        ],
+    )
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn platform_command() -> (&'static str, &'static [&'static str]) {
+    (
+        "notify-send",
+        &["--app-name=Whoisdigger", "{title}", "{body}"],
+    )
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::message::{Notification, NotificationKind, Severity};
+    use crate::template::Template;
+
+    #[tokio::test]
+    async fn test_runs_command_with_message() {
+        let dir = std::env::temp_dir().join(format!("wd-notify-desktop-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let out = dir.join("out.txt");
+        let sink = DesktopSink::new("desktop").with_command(
+            "sh",
+            [
+                "-c",
+                "printf '%s|%s' \"$1\" \"$WD_NOTIFY_BODY\" > \"$0\"",
+                out.to_str().unwrap(),
+                "{title}",
+            ],
+        );
+        let message = Template::default().render(
+            &Notification::new(NotificationKind::Custom, Severity::Info, "it's up")
+                .with_body("b o d y"),
+        );
+        sink.send(&message).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&out).unwrap(), "it's up|b o d y");
+
+        let failing = DesktopSink::new("desktop").with_command("sh", ["-c", "exit 3"]);
+        assert!(matches!(
+            failing.send(&message).await,
+            Err(NotifyError::Command(_))
+        ));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,147 @@
+use std::path::PathBuf;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+use crate::error::NotifyError;
+use crate::sink::{NotificationSink, SinkFuture};
+use crate::template::Message;
+
+/// Line format of a [`FileSink`].
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FileFormat {
+    /// One JSON object per line.
+    #[default]
+    Jsonl,
+    /// A readable log line per message, with the body indented below.
+    Text,
+}
+
+/// Appends notifications to a local file.
+pub struct FileSink {
+    name: String,
+    path: PathBuf,
+    format: FileFormat,
+    lock: Mutex<()>,
+}
+
+impl FileSink {
+    pub fn new(name: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            name: name.into(),
+            path: path.into(),
+            format: FileFormat::Jsonl,
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub fn with_format(mut self, format: FileFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    fn line(&self, message: &Message) -> String {
+        let n = &message.notification;
+        match self.format {
+            FileFormat::Jsonl => {
+                let record = json!({
+                    "written_at": Utc::now().to_rfc3339(),
+                    "title": message.title,
+                    "body": message.body,
+                    "notification": n,
+                });
+                format!("{record}\n")
+            }
+            FileFormat::Text => {
+                let mut out = format!(
+                    "{} [{}] {}\n",
+                    n.created_at.to_rfc3339(),
+                    n.severity.as_str().to_uppercase(),
+                    message.title
+                );
+                for line in message.body.lines() {
+                    out.push_str("    ");
+                    out.push_str(line);
+                    out.push('\n');
+                }
+                out
+            }
+        }
+    }
+
+    async fn append(&self, message: &Message) -> Result<(), NotifyError> {
+        let line = self.line(message);
+        let _guard = self.lock.lock().await;
+        append_line(&self.path, &line).await
+    }
+}
+
+impl NotificationSink for FileSink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn send<'a>(&'a self, message: &'a Message) -> SinkFuture<'a> {
+        Box::pin(self.append(message))
+    }
+}
+
+/// Append `line` to `path`, creating the file and its directory.
+pub(crate) async fn append_line(path: &std::path::Path, line: &str) -> Result<(), NotifyError> {
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    file.write_all(line.as_bytes()).await?;
+    file.flush().await?;
+    Ok(())
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::{Notification, NotificationKind, Severity};
+    use crate::template::Template;
+
+    #[tokio::test]
+    async fn test_appends_jsonl_and_text() {
+        let dir = std::env::temp_dir().join(format!("wd-notify-file-{}", std::process::id()));
+        let message = Template::default().render(
+            &Notification::new(
+                NotificationKind::WatchChange,
+                Severity::Warning,
+                "brand.com changed",
+            )
+            .with_body("registrar: - → Porkbun")
+            .with_domain("brand.com"),
+        );
+
+        let jsonl = FileSink::new("log", dir.join("alerts.jsonl"));
+        jsonl.send(&message).await.unwrap();
+        jsonl.send(&message).await.unwrap();
+        let content = std::fs::read_to_string(dir.join("alerts.jsonl")).unwrap();
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["notification"]["domain"], "brand.com");
+        assert_eq!(lines[1]["title"], "brand.com changed");
+
+        let text = FileSink::new("log", dir.join("alerts.log")).with_format(FileFormat::Text);
+        text.send(&message).await.unwrap();
+        let content = std::fs::read_to_string(dir.join("alerts.log")).unwrap();
+        assert!(content.contains(" [WARNING] brand.com changed\n    registrar: - → Porkbun\n"));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
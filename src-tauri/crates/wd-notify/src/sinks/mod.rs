@@ -0,0 +1,9 @@
+pub mod desktop;
+pub mod file;
+pub mod smtp;
+pub mod webhook;
+
+pub use desktop::DesktopSink;
+pub use file::{FileFormat, FileSink};
+pub use smtp::{SmtpSecurity, SmtpSink};
+pub use webhook::{WebhookFormat, WebhookSink};
@@ -0,0 +1,356 @@
+use std::time::Duration;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+use crate::error::NotifyError;
+use crate::sink::{NotificationSink, SinkFuture};
+use crate::template::Message;
+
+/// How the connection to the mail server is secured.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpSecurity {
+    /// Plain text; only for local relays.
+    None,
+    /// Upgrade with `STARTTLS` (submission, port 587).
+    #[default]
+    StartTls,
+    /// TLS from the first byte (port 465).
+    Tls,
+}
+
+impl SmtpSecurity {
+    pub fn default_port(self) -> u16 {
+        match self {
+            Self::None => 25,
+            Self::StartTls => 587,
+            Self::Tls => 465,
+        }
+    }
+}
+
+/// Sends notifications as plain-text email.
+pub struct SmtpSink {
+    name: String,
+    host: String,
+    port: u16,
+    security: SmtpSecurity,
+    credentials: Option<(String, String)>,
+    from: String,
+    to: Vec<String>,
+    timeout: Duration,
+}
+
+impl SmtpSink {
+    pub fn new(name: impl Into<String>, host: impl Into<String>, from: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            host: host.into(),
+            port: SmtpSecurity::StartTls.default_port(),
+            security: SmtpSecurity::StartTls,
+            credentials: None,
+            from: from.into(),
+            to: Vec::new(),
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Set the security mode and switch to its default port.
+    pub fn with_security(mut self, security: SmtpSecurity) -> Self {
+        self.security = security;
+        self.port = security.default_port();
+        self
+    }
+
+    /// Authenticate with `AUTH PLAIN`.
+    pub fn with_credentials(
+        mut self,
+        user: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.credentials = Some((user.into(), password.into()));
+        self
+    }
+
+    pub fn with_recipient(mut self, to: impl Into<String>) -> Self {
+        self.to.push(to.into());
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The RFC 5322 message for `message`, with CRLF line endings and
+    /// without the terminating dot.
+    pub fn email(&self, message: &Message) -> String {
+        let mut out = String::new();
+        out.push_str(&format!("From: {}\r\n", self.from));
+        out.push_str(&format!("To: {}\r\n", self.to.join(", ")));
+        out.push_str(&format!("Subject: {}\r\n", encode_header(&message.title)));
+        out.push_str(&format!("Date: {}\r\n", Utc::now().to_rfc2822()));
+        out.push_str("MIME-Version: 1.0\r\n");
+        out.push_str("Content-Type: text/plain; charset=utf-8\r\n");
+        out.push_str("Content-Transfer-Encoding: 8bit\r\n\r\n");
+        for line in message.body.lines() {
+            // Dot-stuffing (RFC 5321 §4.5.2).
+            if line.starts_with('.') {
+                out.push('.');
+            }
+            out.push_str(line);
+            out.push_str("\r\n");
+        }
+        out
+    }
+
+    async fn deliver(&self, message: &Message) -> Result<(), NotifyError> {
+        if self.to.is_empty() {
+            return Err(NotifyError::Config(format!("{}: no recipients", self.name)));
+        }
+        let tcp = TcpStream::connect((self.host.as_str(), self.port))
+            .await
+            .map_err(|e| NotifyError::Network(e.to_string()))?;
+        match self.security {
+            SmtpSecurity::None => {
+                let mut conn = SmtpConn::new(tcp);
+                conn.expect(220).await?;
+                self.session(conn, message).await
+            }
+            SmtpSecurity::Tls => {
+                let mut conn = SmtpConn::new(self.tls(tcp).await?);
+                conn.expect(220).await?;
+                self.session(conn, message).await
+            }
+            SmtpSecurity::StartTls => {
+                let mut conn = SmtpConn::new(tcp);
+                conn.expect(220).await?;
+                conn.command(&format!("EHLO {}", hello_name()), 250).await?;
+                conn.command("STARTTLS", 220).await?;
+                let tls = self.tls(conn.into_inner()).await?;
+                self.session(SmtpConn::new(tls), message).await
+            }
+        }
+    }
+
+    async fn tls(
+        &self,
+        tcp: TcpStream,
+    ) -> Result<tokio_native_tls::TlsStream<TcpStream>, NotifyError> {
+        let connector =
+            native_tls::TlsConnector::new().map_err(|e| NotifyError::Network(e.to_string()))?;
+        tokio_native_tls::TlsConnector::from(connector)
+            .connect(&self.host, tcp)
+            .await
+            .map_err(|e| NotifyError::Network(e.to_string()))
+    }
+
+    /// Everything after the greeting (and TLS upgrade, if any).
+    async fn session<S: AsyncRead + AsyncWrite + Unpin>(
+        &self,
+        mut conn: SmtpConn<S>,
+        message: &Message,
+    ) -> Result<(), NotifyError> {
+        conn.command(&format!("EHLO {}", hello_name()), 250).await?;
+        if let Some((user, password)) = &self.credentials {
+            let token = BASE64.encode(format!("\0{user}\0{password}"));
+            conn.command(&format!("AUTH PLAIN {token}"), 235).await?;
+        }
+        conn.command(&format!("MAIL FROM:<{}>", address(&self.from)), 250)
+            .await?;
+        for to in &self.to {
+            conn.command(&format!("RCPT TO:<{}>", address(to)), 250)
+                .await?;
+        }
+        conn.command("DATA", 354).await?;
+        conn.write(&self.email(message)).await?;
+        conn.command(".", 250).await?;
+        // The message is accepted; a failed QUIT changes nothing.
+        let _ = conn.command("QUIT", 221).await;
+        Ok(())
+    }
+}
+
+impl NotificationSink for SmtpSink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn send<'a>(&'a self, message: &'a Message) -> SinkFuture<'a> {
+        Box::pin(async move {
+            tokio::time::timeout(self.timeout, self.deliver(message))
+                .await
+                .map_err(|_| NotifyError::Timeout {
+                    secs: self.timeout.as_secs(),
+                })?
+        })
+    }
+}
+
+/// One SMTP connection, plain or TLS.
+struct SmtpConn<S> {
+    stream: BufReader<S>,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> SmtpConn<S> {
+    fn new(stream: S) -> Self {
+        Self {
+            stream: BufReader::new(stream),
+        }
+    }
+
+    fn into_inner(self) -> S {
+        self.stream.into_inner()
+    }
+
+    async fn write(&mut self, data: &str) -> Result<(), NotifyError> {
+        self.stream.write_all(data.as_bytes()).await?;
+        self.stream.flush().await?;
+        Ok(())
+    }
+
+    /// Read a (possibly multi-line) reply and check its code.
+    async fn expect(&mut self, code: u16) -> Result<String, NotifyError> {
+        let mut reply = String::new();
+        loop {
+            let mut line = String::new();
+            if self.stream.read_line(&mut line).await? == 0 {
+                return Err(NotifyError::Smtp("connection closed".into()));
+            }
+            reply.push_str(&line);
+            // "250-..." continues, "250 ..." ends the reply.
+            if line.as_bytes().get(3) != Some(&b'-') {
+                break;
+            }
+        }
+        match reply.get(..3).and_then(|c| c.parse::<u16>().ok()) {
+            Some(got) if got == code => Ok(reply),
+            _ => Err(NotifyError::Smtp(reply.trim_end().to_string())),
+        }
+    }
+
+    async fn command(&mut self, line: &str, code: u16) -> Result<String, NotifyError> {
+        self.write(&format!("{line}\r\n")).await?;
+        self.expect(code).await
+    }
+}
+
+fn hello_name() -> &'static str {
+    "whoisdigger.localhost"
+}
+
+/// The bare address of `Name <addr>` or `addr`.
+fn address(mailbox: &str) -> &str {
+    match (mailbox.rfind('<'), mailbox.rfind('>')) {
+        (Some(start), Some(end)) if start < end => &mailbox[start + 1..end],
+        _ => mailbox.trim(),
+    }
+}
+
+/// RFC 2047 encoding for non-ASCII header values.
+fn encode_header(value: &str) -> String {
+    let value = value.replace(['\r', '\n'], " ");
+    if value.is_ascii() {
+        value
+    } else {
+        format!("=?UTF-8?B?{}?=", BASE64.encode(value))
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::{Notification, NotificationKind, Severity};
+    use crate::template::Template;
+    use tokio::net::TcpListener;
+
+    /// Stand-in mail server that accepts one message and returns the
+    /// commands it received and the message data.
+    async fn serve_once() -> (u16, tokio::task::JoinHandle<(Vec<String>, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut conn = BufReader::new(socket);
+            let mut commands = Vec::new();
+            let mut data = String::new();
+            conn.write_all(b"220 mail.test ESMTP\r\n").await.unwrap();
+            loop {
+                let mut line = String::new();
+                if conn.read_line(&mut line).await.unwrap() == 0 {
+                    break;
+                }
+                let line = line.trim_end().to_string();
+                let reply: &[u8] = match line.split(' ').next().unwrap() {
+                    "EHLO" => b"250-mail.test\r\n250 AUTH PLAIN\r\n",
+                    "AUTH" => b"235 ok\r\n",
+                    "DATA" => b"354 go ahead\r\n",
+                    "QUIT" => b"221 bye\r\n",
+                    _ => b"250 ok\r\n",
+                };
+                commands.push(line.clone());
+                conn.write_all(reply).await.unwrap();
+                if line == "DATA" {
+                    loop {
+                        let mut l = String::new();
+                        conn.read_line(&mut l).await.unwrap();
+                        if l == ".\r\n" {
+                            break;
+                        }
+                        data.push_str(&l);
+                    }
+                    conn.write_all(b"250 queued\r\n").await.unwrap();
+                }
+                if line == "QUIT" {
+                    break;
+                }
+            }
+            (commands, data)
+        });
+        (port, handle)
+    }
+
+    #[tokio::test]
+    async fn test_delivers_over_plain_smtp() {
+        let (port, server) = serve_once().await;
+        let sink = SmtpSink::new("mail", "127.0.0.1", "Alerts <alerts@example.com>")
+            .with_security(SmtpSecurity::None)
+            .with_port(port)
+            .with_credentials("bot", "pw")
+            .with_recipient("ops@example.com")
+            .with_recipient("Owner <owner@example.com>");
+        let message = Template::default().render(
+            &Notification::new(NotificationKind::Custom, Severity::Info, "Prüfung")
+                .with_body("first\n.hidden dot"),
+        );
+        sink.send(&message).await.unwrap();
+
+        let (commands, data) = server.await.unwrap();
+        assert_eq!(
+            commands,
+            [
+                "EHLO whoisdigger.localhost",
+                &format!("AUTH PLAIN {}", BASE64.encode("\0bot\0pw")),
+                "MAIL FROM:<alerts@example.com>",
+                "RCPT TO:<ops@example.com>",
+                "RCPT TO:<owner@example.com>",
+                "DATA",
+                "QUIT",
+            ]
+        );
+        assert!(data.contains("Subject: =?UTF-8?B?"));
+        assert!(data.ends_with("\r\n\r\nfirst\r\n..hidden dot\r\n"));
+    }
+}
@@ -0,0 +1,240 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::error::NotifyError;
+use crate::message::Severity;
+use crate::sink::{NotificationSink, SinkFuture};
+use crate::template::Message;
+
+/// Discord allows at most this many fields per embed.
+const DISCORD_MAX_FIELDS: usize = 25;
+
+/// Payload shape a webhook expects.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookFormat {
+    /// The rendered message plus the full notification as JSON.
+    #[default]
+    Json,
+    /// Slack incoming-webhook payload.
+    Slack,
+    /// Discord webhook payload.
+    Discord,
+}
+
+/// POSTs notifications to an HTTP endpoint.
+pub struct WebhookSink {
+    name: String,
+    url: String,
+    format: WebhookFormat,
+    headers: Vec<(String, String)>,
+    timeout: Duration,
+    client: reqwest::Client,
+}
+
+impl WebhookSink {
+    pub fn new(name: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            url: url.into(),
+            format: WebhookFormat::Json,
+            headers: Vec::new(),
+            timeout: Duration::from_secs(10),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub fn with_format(mut self, format: WebhookFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Extra request header, e.g. an `Authorization` token.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The JSON body sent for `message`.
+    pub fn payload(&self, message: &Message) -> Value {
+        let n = &message.notification;
+        match self.format {
+            WebhookFormat::Json => json!({
+                "title": message.title,
+                "body": message.body,
+                "notification": n,
+            }),
+            WebhookFormat::Slack => json!({
+                "text": format!("*{}*", message.title),
+                "attachments": [{
+                    "color": format!("#{:06x}", color(n.severity)),
+                    "title": message.title,
+                    "text": message.body,
+                    "fields": n.fields.iter().map(|(k, v)| json!({
+                        "title": k,
+                        "value": v,
+                        "short": true,
+                    })).collect::<Vec<_>>(),
+                    "ts": n.created_at.timestamp(),
+                }],
+            }),
+            WebhookFormat::Discord => json!({
+                "username": "Whoisdigger",
+                "embeds": [{
+                    "title": message.title,
+                    "description": message.body,
+                    "color": color(n.severity),
+                    "fields": n.fields.iter().take(DISCORD_MAX_FIELDS).map(|(k, v)| json!({
+                        "name": k,
+                        "value": if v.is_empty() { "-" } else { v.as_str() },
+                        "inline": true,
+                    })).collect::<Vec<_>>(),
+                    "timestamp": n.created_at.to_rfc3339(),
+                }],
+            }),
+        }
+    }
+
+    async fn post(&self, message: &Message) -> Result<(), NotifyError> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .timeout(self.timeout)
+            .json(&self.payload(message));
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let mut body = response.text().await.unwrap_or_default();
+        body.truncate(500);
+        Err(NotifyError::Http {
+            status: status.as_u16(),
+            body,
+        })
+    }
+}
+
+impl NotificationSink for WebhookSink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn send<'a>(&'a self, message: &'a Message) -> SinkFuture<'a> {
+        Box::pin(self.post(message))
+    }
+}
+
+/// Accent colour for a severity, as 0xRRGGBB.
+fn color(severity: Severity) -> u32 {
+    match severity {
+        Severity::Info => 0x3498db,
+        Severity::Warning => 0xf1c40f,
+        Severity::Critical => 0xe74c3c,
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::{Notification, NotificationKind};
+    use crate::template::Template;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Stand-in HTTP server answering one request with `status` and
+    /// returning the request head and body.
+    async fn serve_once(
+        status: &'static str,
+    ) -> (String, tokio::task::JoinHandle<(String, Value)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = Vec::new();
+            let mut chunk = [0u8; 4096];
+            let (head, body_start) = loop {
+                let n = socket.read(&mut chunk).await.unwrap();
+                buf.extend_from_slice(&chunk[..n]);
+                if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break (String::from_utf8_lossy(&buf[..pos]).to_string(), pos + 4);
+                }
+            };
+            let length: usize = head
+                .lines()
+                .find_map(|l| {
+                    let (k, v) = l.split_once(':')?;
+                    k.eq_ignore_ascii_case("content-length")
+                        .then(|| v.trim().parse().ok())?
+                })
+                .unwrap_or(0);
+            while buf.len() < body_start + length {
+                let n = socket.read(&mut chunk).await.unwrap();
+                buf.extend_from_slice(&chunk[..n]);
+            }
+            let reply =
+                format!("HTTP/1.1 {status}\r\nContent-Length: 4\r\nConnection: close\r\n\r\nnope");
+            socket.write_all(reply.as_bytes()).await.unwrap();
+            (head, serde_json::from_slice(&buf[body_start..]).unwrap())
+        });
+        (url, handle)
+    }
+
+    fn message() -> Message {
+        Template::default().render(
+            &Notification::new(
+                NotificationKind::PhaseChange,
+                Severity::Critical,
+                "drop.com entered pending delete",
+            )
+            .with_body("phase: redemption_grace → pending_delete")
+            .with_field("phase", "pending_delete"),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_slack_payload_and_headers() {
+        let (url, server) = serve_once("200 OK").await;
+        let sink = WebhookSink::new("slack", url)
+            .with_format(WebhookFormat::Slack)
+            .with_header("X-Token", "secret");
+        sink.send(&message()).await.unwrap();
+        let (head, body) = server.await.unwrap();
+        assert!(head.starts_with("POST /hook"));
+        assert!(head.to_lowercase().contains("x-token: secret"));
+        assert_eq!(body["text"], "*drop.com entered pending delete*");
+        assert_eq!(body["attachments"][0]["color"], "#e74c3c");
+        assert_eq!(
+            body["attachments"][0]["fields"][0]["value"],
+            "pending_delete"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_error_status_is_reported() {
+        let (url, server) = serve_once("500 Internal Server Error").await;
+        let sink = WebhookSink::new("discord", url).with_format(WebhookFormat::Discord);
+        let err = sink.send(&message()).await.unwrap_err();
+        assert_eq!(
+            err,
+            NotifyError::Http {
+                status: 500,
+                body: "nope".into()
+            }
+        );
+        let (_, body) = server.await.unwrap();
+        assert_eq!(body["embeds"][0]["color"], 0xe74c3c);
+    }
+}
@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+
+use crate::message::Notification;
+
+/// Title and body templates with `{{name}}` placeholders.
+///
+/// Besides the notification's own `fields`, placeholders can name `title`,
+/// `body`, `domain`, `kind`, `severity` and `created_at`. Unknown names
+/// render as an empty string.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Template {
+    pub title: String,
+    pub body: String,
+}
+
+impl Default for Template {
+    fn default() -> Self {
+        Self::new("{{title}}", "{{body}}")
+    }
+}
+
+impl Template {
+    pub fn new(title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            body: body.into(),
+        }
+    }
+
+    pub fn render(&self, notification: &Notification) -> Message {
+        Message {
+            title: render(&self.title, notification),
+            body: render(&self.body, notification),
+            notification: notification.clone(),
+        }
+    }
+}
+
+/// A notification rendered for delivery.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Message {
+    pub title: String,
+    pub body: String,
+    /// The notification the message was rendered from, for sinks that
+    /// send structured payloads.
+    pub notification: Notification,
+}
+
+/// Substitute the placeholders of `template` from `notification`.
+pub fn render(template: &str, notification: &Notification) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        out.push_str(&value(
+            rest[start + 2..start + 2 + len].trim(),
+            notification,
+        ));
+        rest = &rest[start + 2 + len + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn value(name: &str, n: &Notification) -> String {
+    match name {
+        "title" => n.title.clone(),
+        "body" => n.body.clone(),
+        "domain" => n.domain.clone().unwrap_or_default(),
+        "kind" => n.kind.as_str().to_string(),
+        "severity" => n.severity.as_str().to_string(),
+        "created_at" => n.created_at.to_rfc3339(),
+        field => n.fields.get(field).cloned().unwrap_or_default(),
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::{NotificationKind, Severity};
+
+    #[test]
+    fn test_render_placeholders() {
+        let n = Notification::new(NotificationKind::ThreatHit, Severity::Critical, "bad.com")
+            .with_domain("bad.com")
+            .with_field("score", "91");
+        let t = Template::new(
+            "[{{ severity }}] {{domain}} scored {{score}}{{missing}}",
+            "{{kind}} {{ unclosed",
+        );
+        let m = t.render(&n);
+        assert_eq!(m.title, "[critical] bad.com scored 91");
+        assert_eq!(m.body, "threat_hit {{ unclosed");
+        assert_eq!(Template::default().render(&n).title, "bad.com");
+    }
+}
@@ -1,3 +1,4 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 use html_escape::decode_html_entities;
 use regex::Regex;
 use std::collections::HashMap;
@@ -81,6 +82,77 @@ pub fn parse_raw_data(raw_data: &str) -> HashMap<String, String> {
     result
 }
 
+/// Values of every line whose key (case-insensitive) is one of `keys`.
+fn field_values<'a>(raw_data: &'a str, keys: &[&str]) -> Vec<&'a str> {
+    raw_data
+        .lines()
+        .filter_map(|line| line.trim().split_once(':'))
+        .filter(|(key, _)| keys.iter().any(|k| key.trim().eq_ignore_ascii_case(k)))
+        .map(|(_, value)| value.trim())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+/// Nameservers listed in a WHOIS reply, lowercased without a trailing dot,
+/// in reply order and without duplicates. Glue addresses after the host
+/// name are dropped.
+pub fn parse_nameservers(raw_data: &str) -> Vec<String> {
+    let mut nameservers: Vec<String> = Vec::new();
+    for value in field_values(
+        raw_data,
+        &["name server", "nameserver", "nserver", "nameservers"],
+    ) {
+        let host = value
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches('.')
+            .to_lowercase();
+        if !host.is_empty() && !nameservers.contains(&host) {
+            nameservers.push(host);
+        }
+    }
+    nameservers
+}
+
+/// EPP status codes in a WHOIS reply (`clientTransferProhibited`,
+/// `redemptionPeriod`, ...), without the ICANN URL that often follows.
+pub fn parse_status_codes(raw_data: &str) -> Vec<String> {
+    let mut codes: Vec<String> = Vec::new();
+    for value in field_values(raw_data, &["domain status", "status"]) {
+        if let Some(code) = value.split_whitespace().next() {
+            if !codes.iter().any(|c| c == code) {
+                codes.push(code.to_string());
+            }
+        }
+    }
+    codes
+}
+
+/// Parse the date formats commonly found in WHOIS replies.
+pub fn parse_whois_date(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    for fmt in [
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y.%m.%d %H:%M:%S",
+    ] {
+        let trimmed = value.trim_end_matches('Z');
+        if let Ok(dt) = NaiveDateTime::parse_from_str(trimmed, fmt) {
+            return Some(dt.and_utc());
+        }
+    }
+    for fmt in ["%Y-%m-%d", "%Y.%m.%d", "%d-%b-%Y", "%d.%m.%Y", "%Y/%m/%d"] {
+        if let Ok(d) = NaiveDate::parse_from_str(value, fmt) {
+            return d.and_hms_opt(0, 0, 0).map(|dt| dt.and_utc());
+        }
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -299,4 +371,37 @@ Name Server: B.IANA-SERVERS.NET";
         let result = parse_raw_data(raw);
         assert_eq!(result.get("registrar").unwrap(), "日本レジストラ");
     }
+
+    // ── Field extraction ─────────────────────────────────────────────────
+
+    #[test]
+    fn test_parse_nameservers() {
+        let raw = "Name Server: NS1.EXAMPLE.COM\nName Server: ns2.example.com.\n\
+nserver: ns3.example.net 192.0.2.1\nName Server: ns1.example.com\nName Server:";
+        assert_eq!(
+            parse_nameservers(raw),
+            ["ns1.example.com", "ns2.example.com", "ns3.example.net"]
+        );
+    }
+
+    #[test]
+    fn test_parse_status_codes() {
+        let raw = "Domain Status: clientTransferProhibited https://icann.org/epp#clientTransferProhibited\n\
+Domain Status: redemptionPeriod https://icann.org/epp#redemptionPeriod\nRegistrar: X";
+        assert_eq!(
+            parse_status_codes(raw),
+            ["clientTransferProhibited", "redemptionPeriod"]
+        );
+    }
+
+    #[test]
+    fn test_parse_whois_date() {
+        let expected = NaiveDate::from_ymd_opt(2025, 8, 13)
+            .and_then(|d| d.and_hms_opt(4, 0, 0))
+            .map(|d| d.and_utc());
+        assert_eq!(parse_whois_date("2025-08-13T04:00:00Z"), expected);
+        assert_eq!(parse_whois_date("2025-08-13 04:00:00"), expected);
+        assert!(parse_whois_date("13-Aug-2025").is_some());
+        assert!(parse_whois_date("soon").is_none());
+    }
 }
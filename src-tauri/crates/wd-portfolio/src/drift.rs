@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+use wd_history::Snapshot;
+
+use crate::holding::{OwnedDomain, Portfolio};
+
+/// WHOIS fields that may name the registrant, most specific first.
+const REGISTRANT_FIELDS: &[&str] = &[
+    "registrant organization",
+    "registrant organisation",
+    "registrant name",
+    "registrant",
+];
+
+/// A property of an owned domain that is checked against WHOIS.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftField {
+    Registrant,
+    Nameservers,
+}
+
+/// WHOIS disagrees with what we expect for a domain we own.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Drift {
+    pub domain: String,
+    pub field: DriftField,
+    pub expected: String,
+    pub actual: String,
+}
+
+fn normalize_nameservers(ns: &[String]) -> Vec<String> {
+    let mut out: Vec<String> = ns
+        .iter()
+        .map(|n| n.trim().trim_end_matches('.').to_lowercase())
+        .filter(|n| !n.is_empty())
+        .collect();
+    out.sort();
+    out.dedup();
+    out
+}
+
+/// Compare `snapshot` with the expected registrant and nameservers of
+/// `domain`. Fields the snapshot does not show, or shows redacted, are not
+/// reported: there is nothing to compare.
+pub fn check_drift(domain: &OwnedDomain, snapshot: &Snapshot) -> Vec<Drift> {
+    let mut drift = Vec::new();
+
+    if let Some(expected) = &domain.expected_registrant {
+        let shown: Vec<&str> = REGISTRANT_FIELDS
+            .iter()
+            .filter_map(|f| snapshot.fields.get(*f))
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty() && !v.to_lowercase().contains("redacted"))
+            .collect();
+        if !shown.is_empty()
+            && !shown
+                .iter()
+                .any(|v| v.eq_ignore_ascii_case(expected.trim()))
+        {
+            drift.push(Drift {
+                domain: domain.domain.clone(),
+                field: DriftField::Registrant,
+                expected: expected.clone(),
+                actual: shown[0].to_string(),
+            });
+        }
+    }
+
+    let expected = normalize_nameservers(&domain.expected_nameservers);
+    let actual = normalize_nameservers(&snapshot.nameservers);
+    if !expected.is_empty() && !actual.is_empty() && expected != actual {
+        drift.push(Drift {
+            domain: domain.domain.clone(),
+            field: DriftField::Nameservers,
+            expected: expected.join(", "),
+            actual: actual.join(", "),
+        });
+    }
+
+    drift
+}
+
+impl Portfolio {
+    /// Check every domain against its latest snapshot, as returned by
+    /// `latest`. Domains without a snapshot are skipped.
+    pub fn drift<F>(&self, mut latest: F) -> Vec<Drift>
+    where
+        F: FnMut(&str) -> Option<Snapshot>,
+    {
+        self.domains
+            .iter()
+            .filter_map(|d| latest(&d.domain).map(|s| check_drift(d, &s)))
+            .flatten()
+            .collect()
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wd_history::LookupProtocol;
+
+    fn owned() -> OwnedDomain {
+        OwnedDomain::new("brand.com")
+            .with_expected_registrant("Brand Holdings Ltd")
+            .with_expected_nameservers(["ns1.brand.net", "NS2.BRAND.NET."])
+    }
+
+    #[test]
+    fn test_matching_snapshot_has_no_drift() {
+        let snap = Snapshot::new("brand.com", LookupProtocol::Whois, "")
+            .with_field("Registrant Organization", "BRAND HOLDINGS LTD")
+            .with_nameservers(vec!["ns2.brand.net".into(), "ns1.brand.net".into()]);
+        assert!(check_drift(&owned(), &snap).is_empty());
+
+        // Redacted registrant and missing nameservers cannot drift.
+        let redacted = Snapshot::new("brand.com", LookupProtocol::Whois, "")
+            .with_field("Registrant Organization", "REDACTED FOR PRIVACY");
+        assert!(check_drift(&owned(), &redacted).is_empty());
+    }
+
+    #[test]
+    fn test_portfolio_reports_drift() {
+        let mut p = Portfolio::new();
+        p.add(owned());
+        p.add(OwnedDomain::new("other.com"));
+        let drift = p.drift(|d| {
+            (d == "brand.com").then(|| {
+                Snapshot::new(d, LookupProtocol::Whois, "")
+                    .with_field("Registrant Name", "Someone Else")
+                    .with_nameservers(vec!["ns1.parking.example".into()])
+            })
+        });
+        let fields: Vec<DriftField> = drift.iter().map(|d| d.field).collect();
+        assert_eq!(fields, [DriftField::Registrant, DriftField::Nameservers]);
+        assert_eq!(drift[0].actual, "Someone Else");
+        assert_eq!(drift[1].expected, "ns1.brand.net, ns2.brand.net");
+    }
+}
@@ -0,0 +1,183 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A domain we hold.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct OwnedDomain {
+    pub id: Option<i64>,
+    pub domain: String,
+    /// Registrar the domain is held at.
+    pub registrar: Option<String>,
+    pub purchased_at: Option<DateTime<Utc>>,
+    /// Purchase price in USD cents.
+    pub purchase_cents: Option<u64>,
+    /// Negotiated renewal price in USD cents. When unset the renewal cost
+    /// comes from `RegistrarPricing`.
+    pub renewal_cents: Option<u64>,
+    pub expiry_date: Option<DateTime<Utc>>,
+    pub auto_renew: bool,
+    pub tags: Vec<String>,
+    pub notes: Option<String>,
+    /// Registrant name or organisation WHOIS should show.
+    pub expected_registrant: Option<String>,
+    /// Nameservers the domain should be delegated to.
+    pub expected_nameservers: Vec<String>,
+    pub added_at: DateTime<Utc>,
+}
+
+impl OwnedDomain {
+    pub fn new(domain: impl Into<String>) -> Self {
+        Self {
+            id: None,
+            domain: domain.into().trim().to_lowercase(),
+            registrar: None,
+            purchased_at: None,
+            purchase_cents: None,
+            renewal_cents: None,
+            expiry_date: None,
+            auto_renew: true,
+            tags: Vec::new(),
+            notes: None,
+            expected_registrant: None,
+            expected_nameservers: Vec::new(),
+            added_at: Utc::now(),
+        }
+    }
+
+    pub fn with_registrar(mut self, registrar: impl Into<String>) -> Self {
+        self.registrar = Some(registrar.into());
+        self
+    }
+
+    pub fn with_purchase(mut self, at: DateTime<Utc>, cents: u64) -> Self {
+        self.purchased_at = Some(at);
+        self.purchase_cents = Some(cents);
+        self
+    }
+
+    pub fn with_renewal_cents(mut self, cents: u64) -> Self {
+        self.renewal_cents = Some(cents);
+        self
+    }
+
+    pub fn with_expiry(mut self, expiry: DateTime<Utc>) -> Self {
+        self.expiry_date = Some(expiry);
+        self
+    }
+
+    pub fn with_auto_renew(mut self, auto_renew: bool) -> Self {
+        self.auto_renew = auto_renew;
+        self
+    }
+
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        let tag = tag.into();
+        if !self.has_tag(&tag) {
+            self.tags.push(tag);
+        }
+        self
+    }
+
+    pub fn with_notes(mut self, notes: impl Into<String>) -> Self {
+        self.notes = Some(notes.into());
+        self
+    }
+
+    pub fn with_expected_registrant(mut self, registrant: impl Into<String>) -> Self {
+        self.expected_registrant = Some(registrant.into());
+        self
+    }
+
+    pub fn with_expected_nameservers<I, S>(mut self, nameservers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.expected_nameservers = nameservers.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// The domain's TLD, without the leading dot.
+    pub fn tld(&self) -> &str {
+        self.domain.rsplit('.').next().unwrap_or_default()
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+}
+
+/// The set of domains we own.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Portfolio {
+    pub domains: Vec<OwnedDomain>,
+}
+
+impl Portfolio {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a domain, replacing any entry for the same name.
+    pub fn add(&mut self, domain: OwnedDomain) {
+        self.domains.retain(|d| d.domain != domain.domain);
+        self.domains.push(domain);
+    }
+
+    pub fn remove(&mut self, domain: &str) -> bool {
+        let before = self.domains.len();
+        self.domains.retain(|d| d.domain != domain);
+        self.domains.len() < before
+    }
+
+    pub fn get(&self, domain: &str) -> Option<&OwnedDomain> {
+        self.domains.iter().find(|d| d.domain == domain)
+    }
+
+    pub fn get_mut(&mut self, domain: &str) -> Option<&mut OwnedDomain> {
+        self.domains.iter_mut().find(|d| d.domain == domain)
+    }
+
+    pub fn len(&self) -> usize {
+        self.domains.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.domains.is_empty()
+    }
+
+    /// Domains carrying `tag` (case-insensitive).
+    pub fn with_tag(&self, tag: &str) -> Vec<&OwnedDomain> {
+        self.domains.iter().filter(|d| d.has_tag(tag)).collect()
+    }
+
+    /// Total purchase spend in USD cents.
+    pub fn purchase_total_cents(&self) -> u64 {
+        self.domains.iter().filter_map(|d| d.purchase_cents).sum()
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_portfolio_add_replaces_and_filters_by_tag() {
+        let mut p = Portfolio::new();
+        p.add(OwnedDomain::new("Brand.COM").with_tag("brand"));
+        p.add(
+            OwnedDomain::new("brand.com")
+                .with_tag("core")
+                .with_tag("Core"),
+        );
+        p.add(OwnedDomain::new("side.io").with_tag("brand"));
+        assert_eq!(p.len(), 2);
+        assert_eq!(p.get("brand.com").unwrap().tags, ["core"]);
+        assert_eq!(p.with_tag("BRAND").len(), 1);
+        assert_eq!(p.get("side.io").unwrap().tld(), "io");
+        assert!(p.remove("side.io"));
+        assert!(!p.remove("side.io"));
+    }
+}
@@ -0,0 +1,20 @@
+//! # wd-portfolio
+//!
+//! Tracking for the domains we own rather than the ones we watch.
+//!
+//! ## Modules
+//!
+//! - **holding** – owned domains and the portfolio that groups them
+//! - **renewal** – renewal cost lookup, monthly spend and the renewal calendar
+//! - **drift** – registrant and nameserver drift against WHOIS snapshots
+//! - **store** – SQLite persistence for the portfolio
+
+pub mod drift;
+pub mod holding;
+pub mod renewal;
+pub mod store;
+
+pub use drift::{check_drift, Drift, DriftField};
+pub use holding::{OwnedDomain, Portfolio};
+pub use renewal::{calendar_csv, CostSource, MonthlySpend, RenewalCost, RenewalEvent};
+pub use store::PortfolioStore;
@@ -0,0 +1,318 @@
+use chrono::{DateTime, Datelike, Months, Utc};
+use serde::{Deserialize, Serialize};
+use wd_history::RegistrarPricing;
+
+use crate::holding::{OwnedDomain, Portfolio};
+
+/// Where a renewal price came from.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CostSource {
+    /// The price recorded on the domain itself.
+    Recorded,
+    /// A quote from the domain's registrar for its TLD.
+    Registrar,
+    /// The cheapest quote for the TLD at any registrar; the domain's own
+    /// registrar has none.
+    Cheapest,
+}
+
+/// Yearly renewal price of one domain.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RenewalCost {
+    /// USD cents.
+    pub cents: u64,
+    pub source: CostSource,
+}
+
+/// One upcoming renewal.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RenewalEvent {
+    pub domain: String,
+    pub date: DateTime<Utc>,
+    pub registrar: Option<String>,
+    pub auto_renew: bool,
+    pub cost: Option<RenewalCost>,
+}
+
+/// Renewal spend falling in one calendar month.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MonthlySpend {
+    /// `YYYY-MM`.
+    pub month: String,
+    /// USD cents, over the domains with a known price.
+    pub total_cents: u64,
+    pub domains: Vec<String>,
+    /// Domains renewing this month with no known price.
+    pub unpriced: Vec<String>,
+}
+
+/// Lowercased first word of a registrar name ("GoDaddy.com, LLC" →
+/// "godaddy"), so WHOIS names and pricing names compare equal.
+fn registrar_slug(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .find(|w| !w.is_empty())
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+impl OwnedDomain {
+    /// Yearly renewal price: the recorded price, else the registrar's quote
+    /// for the TLD, else the cheapest quote for the TLD.
+    pub fn renewal_cost(&self, pricing: &RegistrarPricing) -> Option<RenewalCost> {
+        if let Some(cents) = self.renewal_cents {
+            return Some(RenewalCost {
+                cents,
+                source: CostSource::Recorded,
+            });
+        }
+        let tld = pricing.get_tld(self.tld())?;
+        let own = self.registrar.as_deref().map(registrar_slug);
+        let quoted = tld.quotes.iter().find(|q| {
+            q.renewal_cents.is_some() && own.as_deref() == Some(&*registrar_slug(&q.registrar))
+        });
+        if let Some(cents) = quoted.and_then(|q| q.renewal_cents) {
+            return Some(RenewalCost {
+                cents,
+                source: CostSource::Registrar,
+            });
+        }
+        tld.cheapest_renewal()
+            .and_then(|q| q.renewal_cents)
+            .map(|cents| RenewalCost {
+                cents,
+                source: CostSource::Cheapest,
+            })
+    }
+
+    /// Renewal dates in `[from, until]`, assuming yearly renewals from the
+    /// current expiry date.
+    pub fn renewal_dates(&self, from: DateTime<Utc>, until: DateTime<Utc>) -> Vec<DateTime<Utc>> {
+        let Some(mut date) = self.expiry_date else {
+            return Vec::new();
+        };
+        let year = Months::new(12);
+        while date < from {
+            match date.checked_add_months(year) {
+                Some(next) => date = next,
+                None => return Vec::new(),
+            }
+        }
+        let mut dates = Vec::new();
+        while date <= until {
+            dates.push(date);
+            match date.checked_add_months(year) {
+                Some(next) => date = next,
+                None => break,
+            }
+        }
+        dates
+    }
+}
+
+impl Portfolio {
+    /// Every renewal in `[from, until]`, earliest first.
+    pub fn renewal_calendar(
+        &self,
+        pricing: &RegistrarPricing,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Vec<RenewalEvent> {
+        let mut events: Vec<RenewalEvent> = self
+            .domains
+            .iter()
+            .flat_map(|d| {
+                let cost = d.renewal_cost(pricing);
+                d.renewal_dates(from, until)
+                    .into_iter()
+                    .map(move |date| RenewalEvent {
+                        domain: d.domain.clone(),
+                        date,
+                        registrar: d.registrar.clone(),
+                        auto_renew: d.auto_renew,
+                        cost,
+                    })
+            })
+            .collect();
+        events.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.domain.cmp(&b.domain)));
+        events
+    }
+
+    /// Renewal spend for each of the `months` calendar months starting with
+    /// the month of `from`. Months without renewals are included with a
+    /// zero total.
+    pub fn renewal_spend(
+        &self,
+        pricing: &RegistrarPricing,
+        from: DateTime<Utc>,
+        months: u32,
+    ) -> Vec<MonthlySpend> {
+        let Some(start) = from
+            .date_naive()
+            .with_day(1)
+            .and_then(|d| d.and_hms_opt(0, 0, 0))
+            .map(|d| d.and_utc())
+        else {
+            return Vec::new();
+        };
+        let mut spend: Vec<MonthlySpend> = (0..months)
+            .filter_map(|i| start.checked_add_months(Months::new(i)))
+            .map(|m| MonthlySpend {
+                month: m.format("%Y-%m").to_string(),
+                total_cents: 0,
+                domains: Vec::new(),
+                unpriced: Vec::new(),
+            })
+            .collect();
+        let Some(end) = start.checked_add_months(Months::new(months)) else {
+            return spend;
+        };
+        for event in self.renewal_calendar(pricing, start, end) {
+            let month = event.date.format("%Y-%m").to_string();
+            let Some(bucket) = spend.iter_mut().find(|s| s.month == month) else {
+                continue;
+            };
+            match event.cost {
+                Some(cost) => {
+                    bucket.total_cents += cost.cents;
+                    bucket.domains.push(event.domain);
+                }
+                None => bucket.unpriced.push(event.domain),
+            }
+        }
+        spend
+    }
+}
+
+/// Render a renewal calendar as CSV, one row per renewal.
+pub fn calendar_csv(events: &[RenewalEvent]) -> String {
+    let mut w = csv::Writer::from_writer(Vec::new());
+    let _ = w.write_record([
+        "Date",
+        "Domain",
+        "Registrar",
+        "Auto Renew",
+        "Renewal Cost",
+        "Cost Source",
+    ]);
+    for e in events {
+        let (cost, source) = match e.cost {
+            Some(c) => (
+                format!("{}.{:02}", c.cents / 100, c.cents % 100),
+                match c.source {
+                    CostSource::Recorded => "recorded",
+                    CostSource::Registrar => "registrar",
+                    CostSource::Cheapest => "cheapest",
+                },
+            ),
+            None => (String::new(), ""),
+        };
+        let _ = w.write_record([
+            e.date.format("%Y-%m-%d").to_string().as_str(),
+            &e.domain,
+            e.registrar.as_deref().unwrap_or_default(),
+            if e.auto_renew { "yes" } else { "no" },
+            &cost,
+            source,
+        ]);
+    }
+    w.into_inner()
+        .map(|buf| String::from_utf8_lossy(&buf).into_owned())
+        .unwrap_or_default()
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use wd_history::pricing::{PriceQuote, PriceSource};
+
+    fn utc(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, 0, 0, 0).unwrap()
+    }
+
+    fn quote(registrar: &str, tld: &str, renewal: u64) -> PriceQuote {
+        PriceQuote {
+            registrar: registrar.into(),
+            tld: tld.into(),
+            register_cents: Some(renewal - 100),
+            renewal_cents: Some(renewal),
+            transfer_cents: None,
+            currency: "USD".into(),
+            privacy_included: false,
+            source: PriceSource::Manual,
+        }
+    }
+
+    fn pricing() -> RegistrarPricing {
+        let mut p = RegistrarPricing::new();
+        p.add_quote("com", quote("GoDaddy", "com", 2199));
+        p.add_quote("com", quote("Cloudflare", "com", 1044));
+        p
+    }
+
+    fn portfolio() -> Portfolio {
+        let mut p = Portfolio::new();
+        p.add(
+            OwnedDomain::new("brand.com")
+                .with_registrar("GoDaddy.com, LLC")
+                .with_expiry(utc(2025, 3, 10)),
+        );
+        p.add(
+            OwnedDomain::new("deal.com")
+                .with_registrar("Namecheap, Inc.")
+                .with_expiry(utc(2026, 3, 20)),
+        );
+        p.add(
+            OwnedDomain::new("side.io")
+                .with_auto_renew(false)
+                .with_expiry(utc(2026, 5, 1)),
+        );
+        p.add(
+            OwnedDomain::new("fixed.com")
+                .with_renewal_cents(900)
+                .with_expiry(utc(2027, 1, 1)),
+        );
+        p
+    }
+
+    #[test]
+    fn test_renewal_cost_sources() {
+        let p = portfolio();
+        let pricing = pricing();
+        let cost = |d: &str| p.get(d).unwrap().renewal_cost(&pricing);
+        assert_eq!(cost("brand.com").unwrap().source, CostSource::Registrar);
+        assert_eq!(cost("brand.com").unwrap().cents, 2199);
+        assert_eq!(cost("deal.com").unwrap().source, CostSource::Cheapest);
+        assert_eq!(cost("fixed.com").unwrap().source, CostSource::Recorded);
+        assert_eq!(cost("side.io"), None);
+    }
+
+    #[test]
+    fn test_renewal_spend_by_month() {
+        let spend = portfolio().renewal_spend(&pricing(), utc(2026, 2, 14), 4);
+        let months: Vec<&str> = spend.iter().map(|s| s.month.as_str()).collect();
+        assert_eq!(months, ["2026-02", "2026-03", "2026-04", "2026-05"]);
+        // brand.com rolls forward a year from its stale 2025 expiry.
+        assert_eq!(spend[1].domains, ["brand.com", "deal.com"]);
+        assert_eq!(spend[1].total_cents, 2199 + 1044);
+        assert_eq!(spend[3].unpriced, ["side.io"]);
+        assert_eq!(spend[0].total_cents, 0);
+    }
+
+    #[test]
+    fn test_calendar_csv() {
+        let events = portfolio().renewal_calendar(&pricing(), utc(2026, 1, 1), utc(2026, 12, 31));
+        assert_eq!(events.len(), 3);
+        let csv = calendar_csv(&events);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[1],
+            "2026-03-10,brand.com,\"GoDaddy.com, LLC\",yes,21.99,registrar"
+        );
+        assert_eq!(lines[3], "2026-05-01,side.io,,no,,");
+    }
+}
@@ -0,0 +1,204 @@
+use crate::holding::{OwnedDomain, Portfolio};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, Result as SqlResult};
+use std::sync::Mutex;
+
+/// SQLite-backed persistence for the portfolio.
+pub struct PortfolioStore {
+    conn: Mutex<Connection>,
+}
+
+impl PortfolioStore {
+    pub fn open(path: &str) -> SqlResult<Self> {
+        let conn = Connection::open(path)?;
+        let store = Self {
+            conn: Mutex::new(conn),
+        };
+        store.init_tables()?;
+        Ok(store)
+    }
+
+    pub fn open_in_memory() -> SqlResult<Self> {
+        let conn = Connection::open_in_memory()?;
+        let store = Self {
+            conn: Mutex::new(conn),
+        };
+        store.init_tables()?;
+        Ok(store)
+    }
+
+    fn init_tables(&self) -> SqlResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute_batch(
+            "
+            PRAGMA journal_mode = WAL;
+            CREATE TABLE IF NOT EXISTS portfolio (
+                id                   INTEGER PRIMARY KEY AUTOINCREMENT,
+                domain               TEXT NOT NULL UNIQUE,
+                registrar            TEXT,
+                purchased_at         TEXT,
+                purchase_cents       INTEGER,
+                renewal_cents        INTEGER,
+                expiry_date          TEXT,
+                auto_renew           INTEGER NOT NULL DEFAULT 1,
+                tags                 TEXT NOT NULL DEFAULT '[]',
+                notes                TEXT,
+                expected_registrant  TEXT,
+                expected_nameservers TEXT NOT NULL DEFAULT '[]',
+                added_at             TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_portfolio_expiry ON portfolio(expiry_date);
+        ",
+        )?;
+        Ok(())
+    }
+
+    /// Insert or update an owned domain (upsert on domain).
+    pub fn upsert(&self, d: &OwnedDomain) -> SqlResult<i64> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO portfolio (domain, registrar, purchased_at, purchase_cents,
+                                    renewal_cents, expiry_date, auto_renew, tags, notes,
+                                    expected_registrant, expected_nameservers, added_at)
+             VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12)
+             ON CONFLICT(domain) DO UPDATE SET
+                registrar=excluded.registrar, purchased_at=excluded.purchased_at,
+                purchase_cents=excluded.purchase_cents, renewal_cents=excluded.renewal_cents,
+                expiry_date=excluded.expiry_date, auto_renew=excluded.auto_renew,
+                tags=excluded.tags, notes=excluded.notes,
+                expected_registrant=excluded.expected_registrant,
+                expected_nameservers=excluded.expected_nameservers",
+            params![
+                d.domain,
+                d.registrar,
+                d.purchased_at.map(|t| t.to_rfc3339()),
+                d.purchase_cents.map(|c| c as i64),
+                d.renewal_cents.map(|c| c as i64),
+                d.expiry_date.map(|t| t.to_rfc3339()),
+                d.auto_renew as i32,
+                serde_json::to_string(&d.tags).unwrap_or_else(|_| "[]".into()),
+                d.notes,
+                d.expected_registrant,
+                serde_json::to_string(&d.expected_nameservers).unwrap_or_else(|_| "[]".into()),
+                d.added_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// All owned domains, by name.
+    pub fn get_all(&self) -> SqlResult<Vec<OwnedDomain>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, domain, registrar, purchased_at, purchase_cents, renewal_cents,
+                    expiry_date, auto_renew, tags, notes, expected_registrant,
+                    expected_nameservers, added_at
+             FROM portfolio ORDER BY domain",
+        )?;
+        let rows = stmt.query_map([], |row| Ok(row_to_domain(row)))?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    /// Load the whole portfolio.
+    pub fn load(&self) -> SqlResult<Portfolio> {
+        Ok(Portfolio {
+            domains: self.get_all()?,
+        })
+    }
+
+    /// Persist every domain of `portfolio`.
+    pub fn save(&self, portfolio: &Portfolio) -> SqlResult<()> {
+        for d in &portfolio.domains {
+            self.upsert(d)?;
+        }
+        Ok(())
+    }
+
+    pub fn remove(&self, domain: &str) -> SqlResult<bool> {
+        let conn = self.conn.lock().unwrap();
+        let n = conn.execute("DELETE FROM portfolio WHERE domain = ?1", params![domain])?;
+        Ok(n > 0)
+    }
+
+    pub fn count(&self) -> SqlResult<i64> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row("SELECT COUNT(*) FROM portfolio", [], |r| r.get(0))
+    }
+}
+
+fn parse_time(s: Option<String>) -> Option<DateTime<Utc>> {
+    s.and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+        .map(|d| d.with_timezone(&Utc))
+}
+
+fn row_to_domain(row: &rusqlite::Row) -> OwnedDomain {
+    let tags: String = row.get(8).unwrap_or_default();
+    let nameservers: String = row.get(11).unwrap_or_default();
+    OwnedDomain {
+        id: row.get(0).ok(),
+        domain: row.get(1).unwrap_or_default(),
+        registrar: row.get(2).ok().flatten(),
+        purchased_at: parse_time(row.get(3).ok().flatten()),
+        purchase_cents: row
+            .get::<_, Option<i64>>(4)
+            .ok()
+            .flatten()
+            .map(|c| c as u64),
+        renewal_cents: row
+            .get::<_, Option<i64>>(5)
+            .ok()
+            .flatten()
+            .map(|c| c as u64),
+        expiry_date: parse_time(row.get(6).ok().flatten()),
+        auto_renew: row.get::<_, i32>(7).unwrap_or(1) != 0,
+        tags: serde_json::from_str(&tags).unwrap_or_default(),
+        notes: row.get(9).ok().flatten(),
+        expected_registrant: row.get(10).ok().flatten(),
+        expected_nameservers: serde_json::from_str(&nameservers).unwrap_or_default(),
+        added_at: parse_time(row.get(12).ok()).unwrap_or_else(Utc::now),
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_round_trip_and_upsert() {
+        let store = PortfolioStore::open_in_memory().unwrap();
+        let expiry = Utc.with_ymd_and_hms(2027, 4, 1, 0, 0, 0).unwrap();
+        let d = OwnedDomain::new("brand.com")
+            .with_registrar("Porkbun")
+            .with_purchase(Utc.with_ymd_and_hms(2020, 4, 1, 0, 0, 0).unwrap(), 120_000)
+            .with_expiry(expiry)
+            .with_auto_renew(false)
+            .with_tag("brand")
+            .with_notes("keep forever")
+            .with_expected_registrant("Brand Ltd")
+            .with_expected_nameservers(["ns1.brand.net"]);
+        store.upsert(&d).unwrap();
+
+        let loaded = store.load().unwrap();
+        let got = loaded.get("brand.com").unwrap();
+        assert_eq!(got.purchase_cents, Some(120_000));
+        assert_eq!(got.expiry_date, Some(expiry));
+        assert!(!got.auto_renew);
+        assert_eq!(got.tags, ["brand"]);
+        assert_eq!(got.expected_nameservers, ["ns1.brand.net"]);
+
+        store
+            .upsert(&got.clone().with_renewal_cents(1_000))
+            .unwrap();
+        assert_eq!(store.count().unwrap(), 1);
+        assert_eq!(store.get_all().unwrap()[0].renewal_cents, Some(1_000));
+        assert!(store.remove("brand.com").unwrap());
+        assert_eq!(store.count().unwrap(), 0);
+    }
+}
@@ -38,4 +38,4 @@ pub use cache::postgres::PostgresCache;
 
 // ─── Re-exports: RBAC ────────────────────────────────────────────────────────
 
-pub use rbac::{Permission, Rbac, Role, RoleBinding};
+pub use rbac::{Denial, Permission, Rbac, Role, RoleBinding, RBAC_AUDIT_FILE, RBAC_FILE};
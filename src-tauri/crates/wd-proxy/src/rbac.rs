@@ -1,10 +1,23 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::sync::RwLock;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+
+/// Denials kept in memory for [`Rbac::denials`].
+const MAX_DENIALS: usize = 1000;
+
+/// Persisted RBAC roles and bindings in the app data directory.
+pub const RBAC_FILE: &str = "rbac.json";
+
+/// Failed permission checks in the app data directory, one JSON object per
+/// line.
+pub const RBAC_AUDIT_FILE: &str = "rbac-denials.jsonl";
 
 // ─── Permissions ─────────────────────────────────────────────────────────────
 
-/// Fine-grained permissions for proxy and cache operations.
+/// Fine-grained permissions for proxy, cache and application operations.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum Permission {
@@ -26,13 +39,64 @@ pub enum Permission {
     RbacView,
     /// May create/modify/delete roles and bindings
     RbacAdmin,
+    /// May run single-domain lookups
+    Lookup,
+    /// May submit, cancel and read bulk lookup jobs
+    BulkRun,
+    /// May export results, reports and calendars
+    Export,
+    /// May read lookup history
+    HistoryRead,
+    /// May delete lookup history
+    HistoryDelete,
+    /// May list scheduled jobs
+    SchedulerRead,
+    /// May create, pause, resume and delete scheduled jobs
+    SchedulerManage,
+    /// May view the watchlist
+    WatchlistRead,
+    /// May add to and remove from the watchlist
+    WatchlistManage,
+    /// May let the agent run tools
+    AgentToolUse,
+    /// May buy domains through a registrar
+    RegistrarPurchase,
     /// Wildcard — grants all permissions
     All,
 }
 
+impl Permission {
+    /// Every permission except the `All` wildcard.
+    pub const SPECIFIC: [Permission; 20] = [
+        Permission::ProxyUse,
+        Permission::ProxyManage,
+        Permission::ProxyView,
+        Permission::CacheRead,
+        Permission::CacheWrite,
+        Permission::CachePurge,
+        Permission::CacheAdmin,
+        Permission::RbacView,
+        Permission::RbacAdmin,
+        Permission::Lookup,
+        Permission::BulkRun,
+        Permission::Export,
+        Permission::HistoryRead,
+        Permission::HistoryDelete,
+        Permission::SchedulerRead,
+        Permission::SchedulerManage,
+        Permission::WatchlistRead,
+        Permission::WatchlistManage,
+        Permission::AgentToolUse,
+        Permission::RegistrarPurchase,
+    ];
+}
+
 // ─── Role ────────────────────────────────────────────────────────────────────
 
 /// A named role with a set of permissions.
+///
+/// A role also grants everything its parent roles grant. Denied permissions
+/// win over granted ones, from this role or any other role of the subject.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Role {
     /// Unique role identifier (e.g. "admin", "operator", "viewer").
@@ -42,6 +106,13 @@ pub struct Role {
     pub description: String,
     /// Permissions granted by this role.
     pub permissions: Vec<Permission>,
+    /// Roles whose permissions this role inherits.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inherits: Vec<String>,
+    /// Permissions refused to anyone holding this role. `All` refuses
+    /// everything.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub denied: Vec<Permission>,
 }
 
 impl Role {
@@ -51,6 +122,8 @@ impl Role {
             name: name.into(),
             description: String::new(),
             permissions,
+            inherits: Vec::new(),
+            denied: Vec::new(),
         }
     }
 
@@ -60,10 +133,28 @@ impl Role {
         self
     }
 
-    /// Check whether this role grants a specific permission.
+    /// Inherit the permissions of another role.
+    pub fn inherit(mut self, parent: impl Into<String>) -> Self {
+        self.inherits.push(parent.into());
+        self
+    }
+
+    /// Refuse a permission regardless of other grants.
+    pub fn deny(mut self, perm: Permission) -> Self {
+        self.denied.push(perm);
+        self
+    }
+
+    /// Check whether this role grants a specific permission, ignoring
+    /// inheritance and denials.
     pub fn has_permission(&self, perm: &Permission) -> bool {
         self.permissions.contains(&Permission::All) || self.permissions.contains(perm)
     }
+
+    /// Check whether this role refuses a specific permission.
+    pub fn denies(&self, perm: &Permission) -> bool {
+        self.denied.contains(&Permission::All) || self.denied.contains(perm)
+    }
 }
 
 // ─── Role Binding ────────────────────────────────────────────────────────────
@@ -75,19 +166,43 @@ pub struct RoleBinding {
     pub roles: Vec<String>,
 }
 
+// ─── Denial ──────────────────────────────────────────────────────────────────
+
+/// A failed permission check.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Denial {
+    pub at: DateTime<Utc>,
+    pub subject: String,
+    pub permission: Permission,
+    /// Roles bound to the subject at the time.
+    pub roles: Vec<String>,
+}
+
 // ─── RBAC Manager ────────────────────────────────────────────────────────────
 
 /// Thread-safe role-based access control manager.
 ///
-/// Pre-loaded with three built-in roles:
+/// Pre-loaded with five built-in roles:
 /// - **admin** — all permissions
-/// - **operator** — proxy use/manage, cache read/write/purge
-/// - **viewer** — proxy view, cache read
+/// - **viewer** — proxy view, cache read, and read access to history,
+///   watchlist, scheduled jobs and exports
+/// - **analyst** — viewer, plus lookups, bulk jobs, watchlist and
+///   scheduler management and agent tool use
+/// - **operator** — analyst, plus proxy use/manage, cache write/purge and
+///   history deletion
+/// - **purchaser** — registrar purchasing, to combine with another role
 ///
-/// Custom roles and bindings can be added at runtime.
+/// Custom roles and bindings can be added at runtime. An RBAC opened with
+/// [`Rbac::open`] saves every change to its file, and one given an audit
+/// log appends every failed check to it.
 pub struct Rbac {
     roles: RwLock<HashMap<String, Role>>,
     bindings: RwLock<HashMap<String, Vec<String>>>,
+    denials: RwLock<VecDeque<Denial>>,
+    path: Option<PathBuf>,
+    /// Serializes [`save`](Rbac::save) calls, which share a temporary file.
+    saving: Mutex<()>,
+    audit_log: Option<PathBuf>,
 }
 
 impl Default for Rbac {
@@ -118,9 +233,27 @@ impl Rbac {
                     Permission::CacheRead,
                     Permission::CacheWrite,
                     Permission::CachePurge,
+                    Permission::HistoryDelete,
+                ],
+            )
+            .inherit("analyst")
+            .with_description("Analyst access plus proxy, cache and history administration"),
+        );
+
+        roles.insert(
+            "analyst".into(),
+            Role::new(
+                "analyst",
+                vec![
+                    Permission::Lookup,
+                    Permission::BulkRun,
+                    Permission::WatchlistManage,
+                    Permission::SchedulerManage,
+                    Permission::AgentToolUse,
                 ],
             )
-            .with_description("Can use/manage proxies and read/write cache"),
+            .inherit("viewer")
+            .with_description("Can run lookups, bulk jobs and the agent, and manage watches"),
         );
 
         roles.insert(
@@ -131,14 +264,77 @@ impl Rbac {
                     Permission::ProxyView,
                     Permission::CacheRead,
                     Permission::RbacView,
+                    Permission::HistoryRead,
+                    Permission::WatchlistRead,
+                    Permission::SchedulerRead,
+                    Permission::Export,
                 ],
             )
-            .with_description("Read-only access to proxy settings and cache"),
+            .with_description("Read-only access to settings, cache, history and watches"),
+        );
+
+        roles.insert(
+            "purchaser".into(),
+            Role::new("purchaser", vec![Permission::RegistrarPurchase])
+                .with_description("Can buy domains through a registrar"),
         );
 
         Self {
             roles: RwLock::new(roles),
             bindings: RwLock::new(HashMap::new()),
+            denials: RwLock::new(VecDeque::new()),
+            path: None,
+            saving: Mutex::new(()),
+            audit_log: None,
+        }
+    }
+
+    /// Open an RBAC persisted at `path`, in the format of
+    /// [`export_json`](Self::export_json). A missing file starts from the
+    /// built-in roles. Every later change is saved back to `path`.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, String> {
+        let path = path.into();
+        let mut rbac = Self::new();
+        match std::fs::read_to_string(&path) {
+            Ok(json) => rbac.import_json(&json)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.to_string()),
+        }
+        rbac.path = Some(path);
+        Ok(rbac)
+    }
+
+    /// Append every failed permission check to `path` as a JSON line.
+    pub fn with_audit_log(mut self, path: impl Into<PathBuf>) -> Self {
+        self.audit_log = Some(path.into());
+        self
+    }
+
+    /// Write roles and bindings to the file given to [`open`](Self::open).
+    /// The file is replaced in one step, so a crash mid-write cannot leave
+    /// it truncated.
+    pub fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        // Export under the lock too, so a newer snapshot is never
+        // overwritten by an older one.
+        let _saving = self.saving.lock().unwrap();
+        let json = self.export_json()?;
+        let mut tmp = path.clone().into_os_string();
+        tmp.push(format!(".{}.tmp", std::process::id()));
+        let tmp = PathBuf::from(tmp);
+        std::fs::write(&tmp, json)
+            .and_then(|()| std::fs::rename(&tmp, path))
+            .map_err(|e| {
+                std::fs::remove_file(&tmp).ok();
+                e.to_string()
+            })
+    }
+
+    fn persist(&self) {
+        if let Err(e) = self.save() {
+            log::warn!("could not save RBAC: {e}");
         }
     }
 
@@ -147,11 +343,16 @@ impl Rbac {
     /// Add or update a custom role.
     pub fn add_role(&self, role: Role) {
         self.roles.write().unwrap().insert(role.name.clone(), role);
+        self.persist();
     }
 
     /// Remove a role by name. Returns `true` if the role existed.
     pub fn remove_role(&self, name: &str) -> bool {
-        self.roles.write().unwrap().remove(name).is_some()
+        let removed = self.roles.write().unwrap().remove(name).is_some();
+        if removed {
+            self.persist();
+        }
+        removed
     }
 
     /// Get a clone of a role by name.
@@ -169,29 +370,38 @@ impl Rbac {
     /// Bind a subject to one or more roles. Merges with existing bindings.
     pub fn bind(&self, subject: impl Into<String>, role_names: Vec<String>) {
         let subject = subject.into();
-        let mut bindings = self.bindings.write().unwrap();
-        let entry = bindings.entry(subject).or_default();
-        for rn in role_names {
-            if !entry.contains(&rn) {
-                entry.push(rn);
+        {
+            let mut bindings = self.bindings.write().unwrap();
+            let entry = bindings.entry(subject).or_default();
+            for rn in role_names {
+                if !entry.contains(&rn) {
+                    entry.push(rn);
+                }
             }
         }
+        self.persist();
     }
 
     /// Remove all role bindings for a subject.
     pub fn unbind(&self, subject: &str) {
-        self.bindings.write().unwrap().remove(subject);
+        if self.bindings.write().unwrap().remove(subject).is_some() {
+            self.persist();
+        }
     }
 
     /// Remove a specific role from a subject's bindings.
     pub fn unbind_role(&self, subject: &str, role: &str) {
-        let mut bindings = self.bindings.write().unwrap();
-        if let Some(roles) = bindings.get_mut(subject) {
+        {
+            let mut bindings = self.bindings.write().unwrap();
+            let Some(roles) = bindings.get_mut(subject) else {
+                return;
+            };
             roles.retain(|r| r != role);
             if roles.is_empty() {
                 bindings.remove(subject);
             }
         }
+        self.persist();
     }
 
     /// Get the role bindings for a subject.
@@ -213,46 +423,120 @@ impl Rbac {
 
     // ── Authorization ────────────────────────────────────────────────────
 
-    /// Check whether a subject has a specific permission through any of their roles.
+    /// Check whether a subject has a specific permission through any of
+    /// their roles or the roles those inherit, and none of them denies it.
+    /// Failures are recorded as [`Denial`]s.
     pub fn check(&self, subject: &str, permission: &Permission) -> bool {
-        let bindings = self.bindings.read().unwrap();
-        let roles = self.roles.read().unwrap();
-
-        if let Some(role_names) = bindings.get(subject) {
-            for rn in role_names {
-                if let Some(role) = roles.get(rn) {
-                    if role.has_permission(permission) {
-                        return true;
-                    }
-                }
-            }
+        let (allowed, bound) = {
+            let bindings = self.bindings.read().unwrap();
+            let roles = self.roles.read().unwrap();
+            let bound = bindings.get(subject).cloned().unwrap_or_default();
+            let closure = resolve(&roles, &bound);
+            let allowed = !closure.iter().any(|r| r.denies(permission))
+                && closure.iter().any(|r| r.has_permission(permission));
+            (allowed, bound)
+        };
+        if !allowed {
+            self.record_denial(Denial {
+                at: Utc::now(),
+                subject: subject.to_string(),
+                permission: permission.clone(),
+                roles: bound,
+            });
         }
-        false
+        allowed
+    }
+
+    /// Like [`check`](Self::check), but a subject without any bindings is
+    /// unrestricted. Used for the desktop app's local user, who is only
+    /// limited once roles have been bound to them.
+    pub fn check_if_bound(&self, subject: &str, permission: &Permission) -> bool {
+        let bound = self.bindings.read().unwrap().contains_key(subject);
+        !bound || self.check(subject, permission)
     }
 
-    /// Return all *effective* permissions for a subject (union of all role perms).
+    /// Return all *effective* permissions for a subject: the union of their
+    /// roles' and inherited roles' permissions, less any denied ones.
     pub fn effective_permissions(&self, subject: &str) -> Vec<Permission> {
         let bindings = self.bindings.read().unwrap();
         let roles = self.roles.read().unwrap();
 
+        let bound = bindings.get(subject).cloned().unwrap_or_default();
+        let closure = resolve(&roles, &bound);
+        if closure.iter().any(|r| r.denied.contains(&Permission::All)) {
+            return Vec::new();
+        }
+        let denied: Vec<&Permission> = closure.iter().flat_map(|r| &r.denied).collect();
+        let wildcard = closure
+            .iter()
+            .any(|r| r.permissions.contains(&Permission::All));
+        if wildcard && denied.is_empty() {
+            return vec![Permission::All];
+        }
+        let granted: Vec<&Permission> = if wildcard {
+            Permission::SPECIFIC.iter().collect()
+        } else {
+            closure.iter().flat_map(|r| &r.permissions).collect()
+        };
         let mut perms = Vec::new();
-        if let Some(role_names) = bindings.get(subject) {
-            for rn in role_names {
-                if let Some(role) = roles.get(rn) {
-                    if role.permissions.contains(&Permission::All) {
-                        return vec![Permission::All];
-                    }
-                    for p in &role.permissions {
-                        if !perms.contains(p) {
-                            perms.push(p.clone());
-                        }
-                    }
-                }
+        for p in granted {
+            if !perms.contains(p) && !denied.contains(&p) {
+                perms.push(p.clone());
             }
         }
         perms
     }
 
+    // ── Audit ────────────────────────────────────────────────────────────
+
+    /// The most recent failed checks, oldest first.
+    pub fn denials(&self) -> Vec<Denial> {
+        self.denials.read().unwrap().iter().cloned().collect()
+    }
+
+    /// Read the failed checks appended to an audit log file.
+    pub fn read_audit_log(path: &Path) -> Result<Vec<Denial>, String> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.to_string()),
+        };
+        content
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| serde_json::from_str(l).map_err(|e| e.to_string()))
+            .collect()
+    }
+
+    fn record_denial(&self, denial: Denial) {
+        log::warn!(
+            "RBAC denied {:?} to '{}' (roles: {})",
+            denial.permission,
+            denial.subject,
+            denial.roles.join(",")
+        );
+        if let Some(path) = &self.audit_log {
+            let written = serde_json::to_string(&denial)
+                .map_err(|e| e.to_string())
+                .and_then(|line| {
+                    std::fs::OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open(path)
+                        .and_then(|mut f| writeln!(f, "{}", line))
+                        .map_err(|e| e.to_string())
+                });
+            if let Err(e) = written {
+                log::warn!("could not write RBAC audit log: {e}");
+            }
+        }
+        let mut denials = self.denials.write().unwrap();
+        if denials.len() == MAX_DENIALS {
+            denials.pop_front();
+        }
+        denials.push_back(denial);
+    }
+
     // ── Serialization ────────────────────────────────────────────────────
 
     /// Export all roles and bindings as JSON.
@@ -305,10 +589,28 @@ impl Rbac {
             }
         }
 
+        self.persist();
         Ok(())
     }
 }
 
+/// The bound roles and every role they inherit from, each once.
+fn resolve<'a>(roles: &'a HashMap<String, Role>, bound: &[String]) -> Vec<&'a Role> {
+    let mut seen = HashSet::new();
+    let mut pending: Vec<&str> = bound.iter().map(String::as_str).collect();
+    let mut closure = Vec::new();
+    while let Some(name) = pending.pop() {
+        if !seen.insert(name) {
+            continue;
+        }
+        if let Some(role) = roles.get(name) {
+            pending.extend(role.inherits.iter().map(String::as_str));
+            closure.push(role);
+        }
+    }
+    closure
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -468,6 +770,109 @@ mod tests {
         assert!(rbac2.check("user1", &Permission::ProxyUse));
     }
 
+    #[test]
+    fn test_inherited_permissions() {
+        let rbac = Rbac::new();
+        rbac.bind("jane", vec!["operator".into()]);
+        // operator → analyst → viewer
+        assert!(rbac.check("jane", &Permission::Lookup));
+        assert!(rbac.check("jane", &Permission::HistoryRead));
+        assert!(rbac.check("jane", &Permission::HistoryDelete));
+        assert!(!rbac.check("jane", &Permission::RegistrarPurchase));
+
+        rbac.bind("jane", vec!["purchaser".into()]);
+        assert!(rbac.check("jane", &Permission::RegistrarPurchase));
+        let perms = rbac.effective_permissions("jane");
+        assert!(perms.contains(&Permission::ProxyView));
+        assert!(perms.contains(&Permission::RegistrarPurchase));
+    }
+
+    #[test]
+    fn test_inheritance_cycle_terminates() {
+        let rbac = Rbac::new();
+        rbac.add_role(Role::new("a", vec![Permission::Lookup]).inherit("b"));
+        rbac.add_role(Role::new("b", vec![Permission::Export]).inherit("a"));
+        rbac.bind("kim", vec!["a".into()]);
+        assert!(rbac.check("kim", &Permission::Export));
+        assert!(!rbac.check("kim", &Permission::BulkRun));
+    }
+
+    #[test]
+    fn test_deny_overrides_grants() {
+        let rbac = Rbac::new();
+        rbac.add_role(Role::new("no_history_delete", vec![]).deny(Permission::HistoryDelete));
+        rbac.bind("lee", vec!["admin".into(), "no_history_delete".into()]);
+        assert!(rbac.check("lee", &Permission::RbacAdmin));
+        assert!(!rbac.check("lee", &Permission::HistoryDelete));
+        let perms = rbac.effective_permissions("lee");
+        assert!(!perms.contains(&Permission::All));
+        assert!(!perms.contains(&Permission::HistoryDelete));
+        assert!(perms.contains(&Permission::RegistrarPurchase));
+
+        rbac.add_role(Role::new("locked", vec![]).deny(Permission::All));
+        rbac.bind("lee", vec!["locked".into()]);
+        assert!(!rbac.check("lee", &Permission::ProxyView));
+        assert!(rbac.effective_permissions("lee").is_empty());
+    }
+
+    #[test]
+    fn test_denials_are_audited() {
+        let log = std::env::temp_dir().join(format!("wd-rbac-audit-{}.jsonl", std::process::id()));
+        let rbac = Rbac::new().with_audit_log(&log);
+        rbac.bind("mia", vec!["viewer".into()]);
+        assert!(rbac.check("mia", &Permission::CacheRead));
+        assert!(!rbac.check("mia", &Permission::BulkRun));
+        assert!(!rbac.check("nobody", &Permission::Lookup));
+
+        let denials = rbac.denials();
+        assert_eq!(denials.len(), 2);
+        assert_eq!(denials[0].subject, "mia");
+        assert_eq!(denials[0].permission, Permission::BulkRun);
+        assert_eq!(denials[0].roles, vec!["viewer"]);
+        assert_eq!(Rbac::read_audit_log(&log).unwrap(), denials);
+        std::fs::remove_file(&log).ok();
+    }
+
+    #[test]
+    fn test_open_persists_changes() {
+        let path = std::env::temp_dir().join(format!("wd-rbac-{}.json", std::process::id()));
+        std::fs::remove_file(&path).ok();
+        {
+            let rbac = Rbac::open(&path).unwrap();
+            rbac.add_role(Role::new("auditor", vec![Permission::HistoryRead]).inherit("viewer"));
+            rbac.bind("nora", vec!["auditor".into()]);
+        }
+        let rbac = Rbac::open(&path).unwrap();
+        assert!(rbac.check("nora", &Permission::WatchlistRead));
+        assert_eq!(rbac.get_role("auditor").unwrap().inherits, vec!["viewer"]);
+        rbac.unbind("nora");
+        assert!(!Rbac::open(&path)
+            .unwrap()
+            .check("nora", &Permission::HistoryRead));
+        let leftovers = std::fs::read_dir(std::env::temp_dir())
+            .unwrap()
+            .filter_map(Result::ok)
+            .filter(|e| {
+                let name = e.file_name().to_string_lossy().into_owned();
+                name.starts_with(&format!("wd-rbac-{}.json.", std::process::id()))
+            })
+            .count();
+        assert_eq!(leftovers, 0);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_check_if_bound() {
+        let rbac = Rbac::new();
+        assert!(rbac.check_if_bound("user:local", &Permission::RegistrarPurchase));
+        assert!(rbac.denials().is_empty());
+        rbac.bind("user:local", vec!["analyst".into()]);
+        assert!(rbac.check_if_bound("user:local", &Permission::AgentToolUse));
+        assert!(!rbac.check_if_bound("user:local", &Permission::RegistrarPurchase));
+        assert!(!rbac.check_if_bound("user:local", &Permission::HistoryDelete));
+        assert_eq!(rbac.denials().len(), 2);
+    }
+
     #[test]
     fn test_duplicate_bind_no_dupes() {
         let rbac = Rbac::new();
@@ -1,7 +1,8 @@
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
 use std::sync::Mutex;
 
 use crate::job::{Job, JobStatus};
+use crate::runner::RunRecord;
 
 /// SQLite-backed persistent store for scheduled jobs.
 pub struct SchedulerStore {
@@ -12,30 +13,27 @@ impl SchedulerStore {
     pub fn open(path: &str) -> Result<Self, rusqlite::Error> {
         let conn = Connection::open(path)?;
         conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA busy_timeout=5000;")?;
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS jobs (
-                id TEXT PRIMARY KEY,
-                data TEXT NOT NULL,
-                status TEXT NOT NULL,
-                created_at INTEGER NOT NULL
-            )",
-            [],
-        )?;
-        Ok(Self {
-            conn: Mutex::new(conn),
-        })
+        Self::init(conn)
     }
 
     pub fn in_memory() -> Result<Self, rusqlite::Error> {
-        let conn = Connection::open_in_memory()?;
-        conn.execute(
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self, rusqlite::Error> {
+        conn.execute_batch(
             "CREATE TABLE IF NOT EXISTS jobs (
                 id TEXT PRIMARY KEY,
                 data TEXT NOT NULL,
                 status TEXT NOT NULL,
                 created_at INTEGER NOT NULL
-            )",
-            [],
+            );
+            CREATE TABLE IF NOT EXISTS runs (
+                job_id TEXT NOT NULL,
+                run_number INTEGER NOT NULL,
+                data TEXT NOT NULL,
+                PRIMARY KEY (job_id, run_number)
+            );",
         )?;
         Ok(Self {
             conn: Mutex::new(conn),
@@ -98,13 +96,38 @@ impl SchedulerStore {
         Ok(jobs)
     }
 
-    /// Delete a job.
+    /// Delete a job and its recorded runs.
     pub fn delete(&self, id: &str) -> Result<bool, rusqlite::Error> {
         let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM runs WHERE job_id = ?1", params![id])?;
         let count = conn.execute("DELETE FROM jobs WHERE id = ?1", params![id])?;
         Ok(count > 0)
     }
 
+    /// Keep the record of a finished run, to diff the next run against.
+    pub fn save_run(&self, run: &RunRecord) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let data = serde_json::to_string(run).unwrap_or_default();
+        conn.execute(
+            "INSERT OR REPLACE INTO runs (job_id, run_number, data) VALUES (?1, ?2, ?3)",
+            params![run.job_id, run.run_number as i64, data],
+        )?;
+        Ok(())
+    }
+
+    /// The most recent recorded run of a job.
+    pub fn last_run(&self, job_id: &str) -> Result<Option<RunRecord>, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let data: Option<String> = conn
+            .query_row(
+                "SELECT data FROM runs WHERE job_id = ?1 ORDER BY run_number DESC LIMIT 1",
+                params![job_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(data.and_then(|d| serde_json::from_str(&d).ok()))
+    }
+
     /// Count all jobs.
     pub fn count(&self) -> Result<usize, rusqlite::Error> {
         let conn = self.conn.lock().unwrap();
@@ -158,4 +181,40 @@ mod tests {
         store.save(&make_job("x")).unwrap();
         assert_eq!(store.count().unwrap(), 1);
     }
+
+    #[test]
+    fn test_runs_are_kept_per_job() {
+        use crate::runner::DomainRunResult;
+        use std::collections::HashMap;
+
+        let store = SchedulerStore::in_memory().unwrap();
+        let job = make_job("runs");
+        store.save(&job).unwrap();
+        assert!(store.last_run(&job.id).unwrap().is_none());
+
+        let results = |r: DomainRunResult| HashMap::from([("example.com".to_string(), r)]);
+        let started = chrono::Utc::now();
+        store
+            .save_run(&RunRecord::completed(
+                &job.id,
+                1,
+                started,
+                results(DomainRunResult::Success),
+            ))
+            .unwrap();
+        store
+            .save_run(&RunRecord::completed(
+                &job.id,
+                2,
+                started,
+                results(DomainRunResult::Timeout),
+            ))
+            .unwrap();
+        let last = store.last_run(&job.id).unwrap().unwrap();
+        assert_eq!(last.run_number, 2);
+        assert_eq!(last.results["example.com"], DomainRunResult::Timeout);
+
+        store.delete(&job.id).unwrap();
+        assert!(store.last_run(&job.id).unwrap().is_none());
+    }
 }
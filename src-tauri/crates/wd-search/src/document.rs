@@ -0,0 +1,210 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use wd_chat::ChatSession;
+use wd_history::Snapshot;
+use wd_llm::Role;
+
+/// Where an indexed document came from.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceKind {
+    /// One message of a chat session.
+    ChatMessage,
+    /// One attachment of a chat session.
+    Attachment,
+    /// A stored WHOIS / RDAP snapshot.
+    Snapshot,
+}
+
+impl SourceKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ChatMessage => "chat_message",
+            Self::Attachment => "attachment",
+            Self::Snapshot => "snapshot",
+        }
+    }
+
+    /// Parse a kind name, accepting the short forms used in queries
+    /// (`chat`, `whois`, `history`).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "chat_message" | "chat" | "message" | "messages" => Some(Self::ChatMessage),
+            "attachment" | "attachments" => Some(Self::Attachment),
+            "snapshot" | "snapshots" | "whois" | "history" => Some(Self::Snapshot),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for SourceKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A unit of searchable text with the fields queries can filter on.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Document {
+    /// Stable identifier, e.g. `snapshot:42` or `chat:<session>:3`.
+    pub id: String,
+    pub kind: SourceKind,
+    /// Snapshot id or chat session id the document was taken from.
+    pub source_id: String,
+    pub title: String,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registrar: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nameservers: Vec<String>,
+    /// When the underlying record was captured or written.
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Document {
+    pub fn new(
+        id: impl Into<String>,
+        kind: SourceKind,
+        source_id: impl Into<String>,
+        title: impl Into<String>,
+        text: impl Into<String>,
+        timestamp: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            kind,
+            source_id: source_id.into(),
+            title: title.into(),
+            text: text.into(),
+            domain: None,
+            registrar: None,
+            nameservers: Vec::new(),
+            timestamp,
+        }
+    }
+
+    pub fn with_domain(mut self, domain: impl Into<String>) -> Self {
+        self.domain = Some(domain.into().to_lowercase());
+        self
+    }
+
+    pub fn with_registrar(mut self, registrar: impl Into<String>) -> Self {
+        self.registrar = Some(registrar.into());
+        self
+    }
+
+    pub fn with_nameservers(mut self, nameservers: Vec<String>) -> Self {
+        self.nameservers = nameservers.into_iter().map(|n| n.to_lowercase()).collect();
+        self
+    }
+
+    /// One document for a snapshot: its domain, registrar, nameservers,
+    /// status codes, tags and the raw response.
+    pub fn from_snapshot(snapshot: &Snapshot) -> Self {
+        let source_id = match snapshot.id {
+            Some(id) => id.to_string(),
+            None => format!("{}@{}", snapshot.domain, snapshot.captured_at.timestamp()),
+        };
+        let mut lines = vec![snapshot.domain.clone()];
+        lines.extend(snapshot.registrar.clone());
+        lines.push(snapshot.nameservers.join(" "));
+        lines.push(snapshot.status_codes.join(" "));
+        lines.push(snapshot.tags.join(" "));
+        lines.push(snapshot.raw_response.clone());
+        lines.retain(|l| !l.is_empty());
+
+        let mut doc = Self::new(
+            format!("snapshot:{source_id}"),
+            SourceKind::Snapshot,
+            source_id,
+            format!("{} ({})", snapshot.domain, snapshot.protocol),
+            lines.join("\n"),
+            snapshot.captured_at,
+        )
+        .with_domain(&snapshot.domain)
+        .with_nameservers(snapshot.nameservers.clone());
+        if let Some(registrar) = &snapshot.registrar {
+            doc = doc.with_registrar(registrar);
+        }
+        doc
+    }
+
+    /// One document per non-empty, non-system message and one per
+    /// attachment of `session`.
+    pub fn from_session(session: &ChatSession) -> Vec<Self> {
+        let messages = session
+            .messages
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.role != Role::System)
+            .filter_map(|(i, m)| {
+                let text = m.content.as_deref().filter(|c| !c.trim().is_empty())?;
+                Some(Self::new(
+                    format!("chat:{}:{i}", session.id),
+                    SourceKind::ChatMessage,
+                    &session.id,
+                    &session.title,
+                    text,
+                    m.metadata.timestamp.unwrap_or(session.updated_at),
+                ))
+            });
+        let attachments = session.attachments.iter().enumerate().map(|(i, a)| {
+            Self::new(
+                format!("attachment:{}:{i}", session.id),
+                SourceKind::Attachment,
+                &session.id,
+                format!("{} — {}", session.title, a.label),
+                &a.content,
+                session.created_at,
+            )
+        });
+        messages.chain(attachments).collect()
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wd_chat::{Attachment, AttachmentKind, PersonaKind};
+    use wd_history::LookupProtocol;
+
+    #[test]
+    fn test_from_snapshot() {
+        let mut snap = Snapshot::new("Example.com", LookupProtocol::Whois, "Domain: example.com")
+            .with_registrar("Acme Registrar")
+            .with_nameservers(vec!["NS1.HOST.NET".into()]);
+        snap.id = Some(7);
+        let doc = Document::from_snapshot(&snap);
+        assert_eq!(doc.id, "snapshot:7");
+        assert_eq!(doc.domain.as_deref(), Some("example.com"));
+        assert_eq!(doc.nameservers, ["ns1.host.net"]);
+        assert!(doc.text.contains("Acme Registrar"));
+    }
+
+    #[test]
+    fn test_from_session_skips_system_and_empty_messages() {
+        let mut session = ChatSession::new(PersonaKind::DomainExpert, Some("Audit"));
+        session.add_user_message("Who registered example.com?");
+        session.add_assistant_message("   ");
+        session.attachments.push(Attachment::new(
+            AttachmentKind::Text,
+            "notes",
+            "registrar changed",
+        ));
+        let docs = Document::from_session(&session);
+        let kinds: Vec<SourceKind> = docs.iter().map(|d| d.kind).collect();
+        assert_eq!(kinds, [SourceKind::ChatMessage, SourceKind::Attachment]);
+        assert!(docs.iter().all(|d| d.source_id == session.id));
+    }
+
+    #[test]
+    fn test_parse_kind() {
+        assert_eq!(SourceKind::parse("whois"), Some(SourceKind::Snapshot));
+        assert_eq!(SourceKind::parse("Chat"), Some(SourceKind::ChatMessage));
+        assert_eq!(SourceKind::parse("x"), None);
+    }
+}
@@ -0,0 +1,43 @@
+use std::future::Future;
+
+use wd_llm::{LlmClient, LlmConfig, LlmError};
+
+/// Turns text into embedding vectors for semantic search.
+pub trait Embedder: Send + Sync {
+    /// One vector per input, in input order.
+    fn embed(
+        &self,
+        inputs: &[String],
+    ) -> impl Future<Output = Result<Vec<Vec<f32>>, LlmError>> + Send;
+}
+
+/// Embeddings from a configured wd-llm provider.
+pub struct LlmEmbedder {
+    client: LlmClient,
+    model: String,
+}
+
+impl LlmEmbedder {
+    pub fn new(client: LlmClient, model: impl Into<String>) -> Self {
+        Self {
+            client,
+            model: model.into(),
+        }
+    }
+
+    /// Build a client for `config` and embed with `model`, which is usually
+    /// distinct from the chat model in `config`.
+    pub fn from_config(config: LlmConfig, model: impl Into<String>) -> Result<Self, LlmError> {
+        Ok(Self::new(LlmClient::new(config)?, model))
+    }
+
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+}
+
+impl Embedder for LlmEmbedder {
+    async fn embed(&self, inputs: &[String]) -> Result<Vec<Vec<f32>>, LlmError> {
+        self.client.embed(&self.model, inputs).await
+    }
+}
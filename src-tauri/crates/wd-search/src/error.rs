@@ -0,0 +1,12 @@
+use thiserror::Error;
+use wd_llm::LlmError;
+
+/// Errors from index operations that involve an embedding provider.
+#[derive(Error, Debug)]
+pub enum SearchError {
+    #[error("database error: {0}")]
+    Database(#[from] rusqlite::Error),
+
+    #[error("embedding failed: {0}")]
+    Embedding(#[from] LlmError),
+}
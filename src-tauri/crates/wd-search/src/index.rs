@@ -0,0 +1,703 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Result as SqlResult, Transaction};
+use serde::{Deserialize, Serialize};
+use wd_chat::{ChatSession, ChatStore};
+use wd_history::HistoryStore;
+
+use crate::document::{Document, SourceKind};
+use crate::embed::Embedder;
+use crate::error::SearchError;
+use crate::query::SearchQuery;
+use crate::tokenize::tokenize;
+
+/// BM25 term-frequency saturation.
+const K1: f64 = 1.2;
+/// BM25 document-length normalisation.
+const B: f64 = 0.75;
+/// Share of the hybrid score given to vector similarity; the rest goes to
+/// BM25 normalised against the best hit.
+const VECTOR_WEIGHT: f64 = 0.5;
+/// Longest text sent to the embedding provider per document.
+const MAX_EMBED_CHARS: usize = 8_000;
+
+/// A ranked search result.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SearchHit {
+    pub document: Document,
+    /// Combined relevance, higher is better. Zero when the query had no
+    /// free text and results are listed newest first.
+    pub score: f64,
+    /// BM25 score of the free text alone.
+    pub bm25: f64,
+    /// Cosine similarity to the query vector, when both have one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub similarity: Option<f32>,
+}
+
+/// Persistent retrieval index over chat messages, attachments and WHOIS
+/// snapshots: an inverted index ranked with BM25, plus optional embedding
+/// vectors for semantic ranking.
+pub struct SearchIndex {
+    conn: Mutex<Connection>,
+}
+
+impl SearchIndex {
+    /// Open or create an index database at the given path.
+    pub fn open(path: &str) -> SqlResult<Self> {
+        let conn = Connection::open(path)?;
+        let index = Self {
+            conn: Mutex::new(conn),
+        };
+        index.init_tables()?;
+        Ok(index)
+    }
+
+    /// Open an in-memory index (for tests).
+    pub fn open_in_memory() -> SqlResult<Self> {
+        let conn = Connection::open_in_memory()?;
+        let index = Self {
+            conn: Mutex::new(conn),
+        };
+        index.init_tables()?;
+        Ok(index)
+    }
+
+    fn init_tables(&self) -> SqlResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute_batch(
+            "
+            PRAGMA journal_mode = WAL;
+            CREATE TABLE IF NOT EXISTS documents (
+                id           TEXT PRIMARY KEY,
+                kind         TEXT NOT NULL,
+                source_id    TEXT NOT NULL,
+                title        TEXT NOT NULL,
+                body         TEXT NOT NULL,
+                domain       TEXT,
+                registrar    TEXT,
+                nameservers  TEXT NOT NULL DEFAULT '[]',
+                timestamp    TEXT NOT NULL,
+                length       INTEGER NOT NULL,
+                embedding    BLOB
+            );
+            CREATE INDEX IF NOT EXISTS idx_documents_source ON documents(source_id);
+            CREATE TABLE IF NOT EXISTS postings (
+                term    TEXT NOT NULL,
+                doc_id  TEXT NOT NULL,
+                tf      INTEGER NOT NULL,
+                PRIMARY KEY (term, doc_id)
+            ) WITHOUT ROWID;
+            CREATE INDEX IF NOT EXISTS idx_postings_doc ON postings(doc_id);
+        ",
+        )?;
+        Ok(())
+    }
+
+    /// Add or replace one document.
+    pub fn add(&self, doc: &Document) -> SqlResult<()> {
+        self.add_all(std::slice::from_ref(doc)).map(|_| ())
+    }
+
+    /// Add or replace documents in one transaction. A replaced document
+    /// keeps its embedding unless its title or text changed.
+    pub fn add_all(&self, docs: &[Document]) -> SqlResult<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        for doc in docs {
+            upsert(&tx, doc)?;
+        }
+        tx.commit()?;
+        Ok(docs.len())
+    }
+
+    /// Index every snapshot in `store`, dropping snapshots no longer in it.
+    pub fn index_history(&self, store: &HistoryStore) -> SqlResult<usize> {
+        let mut docs = Vec::new();
+        for domain in store.list_domains()? {
+            docs.extend(
+                store
+                    .get_domain_snapshots(&domain)?
+                    .iter()
+                    .map(Document::from_snapshot),
+            );
+        }
+        self.sync(&[SourceKind::Snapshot], &docs)
+    }
+
+    /// Index every session in `store` that is not deleted, dropping
+    /// messages and attachments no longer in it.
+    pub fn index_chat(&self, store: &ChatStore) -> SqlResult<usize> {
+        let docs: Vec<Document> = store
+            .list_all(i64::MAX as usize)?
+            .iter()
+            .flat_map(Document::from_session)
+            .collect();
+        self.sync(&[SourceKind::ChatMessage, SourceKind::Attachment], &docs)
+    }
+
+    /// Re-index one session after it changed.
+    pub fn index_session(&self, session: &ChatSession) -> SqlResult<usize> {
+        let docs = Document::from_session(session);
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let keep: HashSet<&str> = docs.iter().map(|d| d.id.as_str()).collect();
+        for id in ids_where(&tx, "source_id = ?1 AND kind != 'snapshot'", &session.id)? {
+            if !keep.contains(id.as_str()) {
+                delete(&tx, &id)?;
+            }
+        }
+        for doc in &docs {
+            upsert(&tx, doc)?;
+        }
+        tx.commit()?;
+        Ok(docs.len())
+    }
+
+    /// Make the documents of `kinds` exactly `docs`.
+    fn sync(&self, kinds: &[SourceKind], docs: &[Document]) -> SqlResult<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let keep: HashSet<&str> = docs.iter().map(|d| d.id.as_str()).collect();
+        for kind in kinds {
+            for id in ids_where(&tx, "kind = ?1", kind.as_str())? {
+                if !keep.contains(id.as_str()) {
+                    delete(&tx, &id)?;
+                }
+            }
+        }
+        for doc in docs {
+            upsert(&tx, doc)?;
+        }
+        tx.commit()?;
+        Ok(docs.len())
+    }
+
+    /// Fetch a document by id.
+    pub fn get(&self, id: &str) -> SqlResult<Option<Document>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(&format!("{SELECT} WHERE id = ?1"), params![id], |row| {
+            row_to_document(row).map(|(doc, _)| doc)
+        })
+        .optional()
+    }
+
+    /// Delete one document. Returns whether it existed.
+    pub fn remove(&self, id: &str) -> SqlResult<bool> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let removed = delete(&tx, id)?;
+        tx.commit()?;
+        Ok(removed)
+    }
+
+    /// Delete every document taken from one snapshot or session.
+    pub fn remove_source(&self, source_id: &str) -> SqlResult<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let ids = ids_where(&tx, "source_id = ?1", source_id)?;
+        for id in &ids {
+            delete(&tx, id)?;
+        }
+        tx.commit()?;
+        Ok(ids.len())
+    }
+
+    /// Count indexed documents.
+    pub fn count(&self) -> SqlResult<i64> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row("SELECT COUNT(*) FROM documents", [], |r| r.get(0))
+    }
+
+    /// Count documents that have an embedding.
+    pub fn count_embedded(&self) -> SqlResult<i64> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT COUNT(*) FROM documents WHERE embedding IS NOT NULL",
+            [],
+            |r| r.get(0),
+        )
+    }
+
+    /// Delete everything.
+    pub fn clear(&self) -> SqlResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute_batch("DELETE FROM postings; DELETE FROM documents;")?;
+        Ok(())
+    }
+
+    /// Documents still lacking an embedding, as `(id, text)` pairs.
+    pub fn pending_embeddings(&self, limit: usize) -> SqlResult<Vec<(String, String)>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, title || char(10) || body FROM documents
+             WHERE embedding IS NULL ORDER BY timestamp DESC LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit as i64], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect()
+    }
+
+    /// Store the embedding of a document. Returns whether it exists.
+    pub fn set_embedding(&self, id: &str, vector: &[f32]) -> SqlResult<bool> {
+        let conn = self.conn.lock().unwrap();
+        let blob: Vec<u8> = vector.iter().flat_map(|x| x.to_le_bytes()).collect();
+        Ok(conn.execute(
+            "UPDATE documents SET embedding = ?2 WHERE id = ?1",
+            params![id, blob],
+        )? > 0)
+    }
+
+    /// Rank documents against `query` by BM25 alone.
+    pub fn search(&self, query: &SearchQuery) -> SqlResult<Vec<SearchHit>> {
+        self.search_with_vector(query, None)
+    }
+
+    /// Rank documents against `query`, blending BM25 with cosine similarity
+    /// to `vector` when one is given. Documents without an embedding are
+    /// then ranked by BM25 alone.
+    ///
+    /// A query without free text or vector lists every document passing
+    /// its filters, newest first.
+    pub fn search_with_vector(
+        &self,
+        query: &SearchQuery,
+        vector: Option<&[f32]>,
+    ) -> SqlResult<Vec<SearchHit>> {
+        let conn = self.conn.lock().unwrap();
+        let bm25 = bm25_scores(&conn, &query.text)?;
+        let ranked = vector.is_some() || !tokenize(&query.text).is_empty();
+
+        let mut candidates: Vec<(Document, Option<Vec<f32>>)> = Vec::new();
+        if ranked && vector.is_none() {
+            let mut stmt = conn.prepare(&format!("{SELECT} WHERE id = ?1"))?;
+            for id in bm25.keys() {
+                if let Some(found) = stmt.query_row(params![id], row_to_document).optional()? {
+                    candidates.push(found);
+                }
+            }
+        } else {
+            let mut stmt = conn.prepare(&format!("{SELECT} ORDER BY timestamp DESC"))?;
+            let rows = stmt.query_map([], row_to_document)?;
+            for row in rows {
+                candidates.push(row?);
+            }
+        }
+
+        let best = bm25.values().copied().fold(0.0, f64::max);
+        let mut hits: Vec<SearchHit> = candidates
+            .into_iter()
+            .filter(|(doc, _)| query.matches(doc))
+            .filter_map(|(document, embedding)| {
+                let bm25 = bm25.get(&document.id).copied().unwrap_or(0.0);
+                let similarity = vector.zip(embedding.as_deref()).map(|(q, d)| cosine(q, d));
+                let score = match (vector, similarity) {
+                    (Some(_), Some(sim)) => {
+                        let text = if best > 0.0 { bm25 / best } else { 0.0 };
+                        (1.0 - VECTOR_WEIGHT) * text + VECTOR_WEIGHT * sim.max(0.0) as f64
+                    }
+                    (Some(_), None) if best > 0.0 => (1.0 - VECTOR_WEIGHT) * bm25 / best,
+                    _ => bm25,
+                };
+                (!ranked || score > 0.0).then_some(SearchHit {
+                    document,
+                    score,
+                    bm25,
+                    similarity,
+                })
+            })
+            .collect();
+        if ranked {
+            hits.sort_by(|a, b| {
+                b.score
+                    .total_cmp(&a.score)
+                    .then(b.document.timestamp.cmp(&a.document.timestamp))
+            });
+        }
+        hits.truncate(query.limit);
+        Ok(hits)
+    }
+
+    /// Embed every document lacking a vector, `batch_size` at a time.
+    /// Returns the number embedded.
+    pub async fn embed_pending(
+        &self,
+        embedder: &impl Embedder,
+        batch_size: usize,
+    ) -> Result<usize, SearchError> {
+        let mut embedded = 0;
+        loop {
+            let pending = self.pending_embeddings(batch_size.max(1))?;
+            if pending.is_empty() {
+                return Ok(embedded);
+            }
+            let texts: Vec<String> = pending
+                .iter()
+                .map(|(_, text)| text.chars().take(MAX_EMBED_CHARS).collect())
+                .collect();
+            let vectors = embedder.embed(&texts).await?;
+            for ((id, _), vector) in pending.iter().zip(&vectors) {
+                self.set_embedding(id, vector)?;
+            }
+            embedded += pending.len();
+        }
+    }
+
+    /// Rank documents against `query`, embedding its free text with
+    /// `embedder` for a hybrid BM25 and vector ranking.
+    pub async fn search_semantic(
+        &self,
+        query: &SearchQuery,
+        embedder: &impl Embedder,
+    ) -> Result<Vec<SearchHit>, SearchError> {
+        if query.text.trim().is_empty() {
+            return Ok(self.search(query)?);
+        }
+        let vector = embedder
+            .embed(std::slice::from_ref(&query.text))
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+        Ok(self.search_with_vector(query, Some(&vector))?)
+    }
+}
+
+const SELECT: &str = "SELECT id, kind, source_id, title, body, domain, registrar, nameservers, timestamp, embedding FROM documents";
+
+fn upsert(tx: &Transaction, doc: &Document) -> SqlResult<()> {
+    let terms = tokenize(&format!("{}\n{}", doc.title, doc.text));
+    let mut tf: HashMap<&str, i64> = HashMap::new();
+    for term in &terms {
+        *tf.entry(term.as_str()).or_default() += 1;
+    }
+
+    tx.execute(
+        "INSERT INTO documents (id, kind, source_id, title, body, domain, registrar, nameservers, timestamp, length)
+         VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10)
+         ON CONFLICT(id) DO UPDATE SET
+            kind = excluded.kind,
+            source_id = excluded.source_id,
+            domain = excluded.domain,
+            registrar = excluded.registrar,
+            nameservers = excluded.nameservers,
+            timestamp = excluded.timestamp,
+            length = excluded.length,
+            embedding = CASE WHEN title = excluded.title AND body = excluded.body
+                             THEN embedding END,
+            title = excluded.title,
+            body = excluded.body",
+        params![
+            doc.id,
+            doc.kind.as_str(),
+            doc.source_id,
+            doc.title,
+            doc.text,
+            doc.domain,
+            doc.registrar,
+            serde_json::to_string(&doc.nameservers).unwrap_or_default(),
+            doc.timestamp.to_rfc3339(),
+            terms.len() as i64,
+        ],
+    )?;
+    tx.execute("DELETE FROM postings WHERE doc_id = ?1", params![doc.id])?;
+    let mut stmt =
+        tx.prepare_cached("INSERT INTO postings (term, doc_id, tf) VALUES (?1,?2,?3)")?;
+    for (term, count) in tf {
+        stmt.execute(params![term, doc.id, count])?;
+    }
+    Ok(())
+}
+
+fn delete(tx: &Transaction, id: &str) -> SqlResult<bool> {
+    tx.execute("DELETE FROM postings WHERE doc_id = ?1", params![id])?;
+    Ok(tx.execute("DELETE FROM documents WHERE id = ?1", params![id])? > 0)
+}
+
+fn ids_where(tx: &Transaction, condition: &str, value: &str) -> SqlResult<Vec<String>> {
+    let mut stmt = tx.prepare(&format!("SELECT id FROM documents WHERE {condition}"))?;
+    let rows = stmt.query_map(params![value], |row| row.get(0))?;
+    rows.collect()
+}
+
+/// BM25 score of every document matching at least one term of `text`.
+fn bm25_scores(conn: &Connection, text: &str) -> SqlResult<HashMap<String, f64>> {
+    let mut terms = tokenize(text);
+    terms.sort();
+    terms.dedup();
+    let mut scores: HashMap<String, f64> = HashMap::new();
+    if terms.is_empty() {
+        return Ok(scores);
+    }
+
+    let (n, avg_len): (i64, f64) = conn.query_row(
+        "SELECT COUNT(*), COALESCE(AVG(length), 0) FROM documents",
+        [],
+        |r| Ok((r.get(0)?, r.get(1)?)),
+    )?;
+    let mut stmt = conn.prepare(
+        "SELECT p.doc_id, p.tf, d.length FROM postings p
+         JOIN documents d ON d.id = p.doc_id WHERE p.term = ?1",
+    )?;
+    for term in &terms {
+        let postings: Vec<(String, i64, i64)> = stmt
+            .query_map(params![term], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))?
+            .collect::<SqlResult<_>>()?;
+        if postings.is_empty() {
+            continue;
+        }
+        let df = postings.len() as f64;
+        let idf = (1.0 + (n as f64 - df + 0.5) / (df + 0.5)).ln();
+        for (id, tf, len) in postings {
+            let tf = tf as f64;
+            let norm = K1 * (1.0 - B + B * len as f64 / avg_len.max(1.0));
+            *scores.entry(id).or_default() += idf * tf * (K1 + 1.0) / (tf + norm);
+        }
+    }
+    Ok(scores)
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let denom = norm(a) * norm(b);
+    if denom == 0.0 {
+        0.0
+    } else {
+        dot / denom
+    }
+}
+
+fn row_to_document(row: &rusqlite::Row) -> SqlResult<(Document, Option<Vec<f32>>)> {
+    let kind: String = row.get(1)?;
+    let nameservers: String = row.get(7)?;
+    let timestamp: String = row.get(8)?;
+    let embedding: Option<Vec<u8>> = row.get(9)?;
+    let doc = Document {
+        id: row.get(0)?,
+        kind: SourceKind::parse(&kind).unwrap_or(SourceKind::ChatMessage),
+        source_id: row.get(2)?,
+        title: row.get(3)?,
+        text: row.get(4)?,
+        domain: row.get(5)?,
+        registrar: row.get(6)?,
+        nameservers: serde_json::from_str(&nameservers).unwrap_or_default(),
+        timestamp: DateTime::parse_from_rfc3339(&timestamp)
+            .map(|d| d.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now()),
+    };
+    let embedding = embedding.map(|bytes| {
+        bytes
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect()
+    });
+    Ok((doc, embedding))
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use wd_chat::PersonaKind;
+    use wd_history::{LookupProtocol, Snapshot};
+    use wd_llm::LlmError;
+
+    fn snapshot(domain: &str, registrar: &str, ns: &str, days_ago: i64) -> Snapshot {
+        let mut snap = Snapshot::new(
+            domain,
+            LookupProtocol::Whois,
+            format!("Domain Name: {domain}"),
+        )
+        .with_registrar(registrar)
+        .with_nameservers(vec![ns.to_string()]);
+        snap.captured_at = Utc::now() - Duration::days(days_ago);
+        snap
+    }
+
+    fn history() -> HistoryStore {
+        let store = HistoryStore::open_in_memory().unwrap();
+        store
+            .insert(&snapshot(
+                "alpha.com",
+                "Acme Registrar",
+                "ns1.cloudflare.com",
+                10,
+            ))
+            .unwrap();
+        store
+            .insert(&snapshot("beta.com", "Acme Registrar", "ns1.other.net", 20))
+            .unwrap();
+        store
+            .insert(&snapshot(
+                "gamma.com",
+                "Acme Registrar",
+                "ns2.cloudflare.com",
+                200,
+            ))
+            .unwrap();
+        store
+            .insert(&snapshot("delta.com", "Other LLC", "ns1.cloudflare.com", 5))
+            .unwrap();
+        store
+    }
+
+    #[test]
+    fn test_filter_only_query_lists_newest_first() {
+        let index = SearchIndex::open_in_memory().unwrap();
+        assert_eq!(index.index_history(&history()).unwrap(), 4);
+
+        let hits = index
+            .search(&SearchQuery::parse(
+                "registrar:acme ns:cloudflare last quarter",
+            ))
+            .unwrap();
+        let domains: Vec<_> = hits
+            .iter()
+            .map(|h| h.document.domain.clone().unwrap())
+            .collect();
+        assert_eq!(domains, ["alpha.com"]);
+    }
+
+    #[test]
+    fn test_bm25_ranks_rarer_and_repeated_terms_higher() {
+        let index = SearchIndex::open_in_memory().unwrap();
+        let now = Utc::now();
+        index
+            .add_all(&[
+                Document::new(
+                    "a",
+                    SourceKind::ChatMessage,
+                    "s",
+                    "t",
+                    "transfer lock transfer",
+                    now,
+                ),
+                Document::new(
+                    "b",
+                    SourceKind::ChatMessage,
+                    "s",
+                    "t",
+                    "transfer pending",
+                    now,
+                ),
+                Document::new(
+                    "c",
+                    SourceKind::ChatMessage,
+                    "s",
+                    "t",
+                    "unrelated words",
+                    now,
+                ),
+            ])
+            .unwrap();
+        let hits = index.search(&SearchQuery::new("transfer lock")).unwrap();
+        let ids: Vec<_> = hits.iter().map(|h| h.document.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b"]);
+        assert!(hits[0].bm25 > hits[1].bm25);
+        assert!(index
+            .search(&SearchQuery::new("nothing"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_sync_drops_stale_documents() {
+        let index = SearchIndex::open_in_memory().unwrap();
+        let chat = ChatStore::in_memory().unwrap();
+        let mut session = ChatSession::new(PersonaKind::DomainExpert, Some("Audit"));
+        session.add_user_message("check example.com");
+        session.add_assistant_message("registered at Acme");
+        chat.save(&session).unwrap();
+        assert_eq!(index.index_chat(&chat).unwrap(), 2);
+        index.index_history(&history()).unwrap();
+
+        chat.delete(&session.id).unwrap();
+        assert_eq!(index.index_chat(&chat).unwrap(), 0);
+        assert_eq!(index.count().unwrap(), 4);
+
+        session.messages.truncate(1);
+        index.index_session(&session).unwrap();
+        assert_eq!(index.remove_source(&session.id).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_embedding_survives_unchanged_reindex() {
+        let index = SearchIndex::open_in_memory().unwrap();
+        let doc = Document::new("a", SourceKind::Attachment, "s", "t", "body", Utc::now());
+        index.add(&doc).unwrap();
+        assert!(index.set_embedding("a", &[1.0, 0.0]).unwrap());
+
+        index.add(&doc).unwrap();
+        assert_eq!(index.count_embedded().unwrap(), 1);
+        index
+            .add(&Document {
+                text: "new body".into(),
+                ..doc
+            })
+            .unwrap();
+        assert_eq!(index.count_embedded().unwrap(), 0);
+        assert_eq!(index.get("a").unwrap().unwrap().text, "new body");
+        assert!(index.remove("a").unwrap());
+    }
+
+    /// Embeds text as `[mentions cloudflare, mentions registrar]`.
+    struct KeywordEmbedder;
+
+    impl Embedder for KeywordEmbedder {
+        async fn embed(&self, inputs: &[String]) -> Result<Vec<Vec<f32>>, LlmError> {
+            Ok(inputs
+                .iter()
+                .map(|t| {
+                    let t = t.to_lowercase();
+                    vec![
+                        t.contains("cloudflare") as u8 as f32,
+                        t.contains("registrar") as u8 as f32,
+                    ]
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_semantic_search_finds_documents_without_shared_terms() {
+        let index = SearchIndex::open_in_memory().unwrap();
+        let now = Utc::now();
+        index
+            .add_all(&[
+                Document::new(
+                    "cf",
+                    SourceKind::ChatMessage,
+                    "s",
+                    "dns",
+                    "uses cloudflare",
+                    now,
+                ),
+                Document::new(
+                    "reg",
+                    SourceKind::ChatMessage,
+                    "s",
+                    "who",
+                    "the registrar",
+                    now,
+                ),
+            ])
+            .unwrap();
+        assert_eq!(index.embed_pending(&KeywordEmbedder, 1).await.unwrap(), 2);
+
+        // "CDN cloudflare" shares a term with "cf" only; "reg" has none.
+        let hits = index
+            .search_semantic(&SearchQuery::new("CDN cloudflare"), &KeywordEmbedder)
+            .await
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].document.id, "cf");
+        assert_eq!(hits[0].similarity, Some(1.0));
+    }
+}
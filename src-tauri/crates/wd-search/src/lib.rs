@@ -0,0 +1,33 @@
+//! # wd-search
+//!
+//! Local retrieval over everything whoisdigger has seen: chat messages,
+//! chat attachments and stored WHOIS / RDAP snapshots.
+//!
+//! ## Modules
+//!
+//! - **document** – the searchable unit and its conversion from sessions and snapshots
+//! - **tokenize** – term extraction shared by indexing and querying
+//! - **query** – search queries with field and time filters
+//! - **index** – SQLite-backed BM25 index with optional embedding vectors
+//! - **embed** – embedding providers backed by a configured wd-llm client
+//! - **error** – error type for the embedding paths
+
+pub mod document;
+pub mod embed;
+pub mod error;
+pub mod index;
+pub mod query;
+pub mod tokenize;
+
+// ─── Re-exports ──────────────────────────────────────────────────────────────
+
+pub use document::{Document, SourceKind};
+pub use embed::{Embedder, LlmEmbedder};
+pub use error::SearchError;
+pub use index::{SearchHit, SearchIndex};
+pub use query::SearchQuery;
+pub use tokenize::tokenize;
+
+// The stores an index is built from.
+pub use wd_chat::ChatStore;
+pub use wd_history::HistoryStore;
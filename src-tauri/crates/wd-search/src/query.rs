@@ -0,0 +1,332 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::document::{Document, SourceKind};
+
+/// A search over the index: free text ranked by relevance, narrowed by
+/// optional field and time filters.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SearchQuery {
+    /// Free text to rank by. Empty to list everything matching the filters,
+    /// newest first.
+    pub text: String,
+    /// Only these kinds of document; empty for all.
+    #[serde(default)]
+    pub kinds: Vec<SourceKind>,
+    /// Exact domain.
+    #[serde(default)]
+    pub domain: Option<String>,
+    /// Case-insensitive substring of the registrar name.
+    #[serde(default)]
+    pub registrar: Option<String>,
+    /// Case-insensitive substring of any nameserver.
+    #[serde(default)]
+    pub nameserver: Option<String>,
+    #[serde(default)]
+    pub since: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub until: Option<DateTime<Utc>>,
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+fn default_limit() -> usize {
+    20
+}
+
+impl SearchQuery {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            kinds: Vec::new(),
+            domain: None,
+            registrar: None,
+            nameserver: None,
+            since: None,
+            until: None,
+            limit: default_limit(),
+        }
+    }
+
+    pub fn with_kind(mut self, kind: SourceKind) -> Self {
+        if !self.kinds.contains(&kind) {
+            self.kinds.push(kind);
+        }
+        self
+    }
+
+    pub fn with_domain(mut self, domain: impl Into<String>) -> Self {
+        self.domain = Some(domain.into().to_lowercase());
+        self
+    }
+
+    pub fn with_registrar(mut self, registrar: impl Into<String>) -> Self {
+        self.registrar = Some(registrar.into());
+        self
+    }
+
+    pub fn with_nameserver(mut self, nameserver: impl Into<String>) -> Self {
+        self.nameserver = Some(nameserver.into());
+        self
+    }
+
+    /// Restrict to documents timestamped within `[since, until)`.
+    pub fn with_period(
+        mut self,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> Self {
+        self.since = since;
+        self.until = until;
+        self
+    }
+
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Parse a query string relative to the current time. See
+    /// [`parse_at`](Self::parse_at).
+    pub fn parse(input: &str) -> Self {
+        Self::parse_at(input, Utc::now())
+    }
+
+    /// Parse a query string. Recognised filters, anywhere in the input:
+    ///
+    /// - `registrar:`, `ns:` / `nameserver:`, `domain:` – field filters;
+    ///   quote values containing spaces (`registrar:"Acme Inc"`)
+    /// - `in:` / `kind:` – `chat`, `attachment` or `whois`
+    /// - `since:` / `until:` – `YYYY-MM-DD` or RFC 3339
+    /// - `within:` – a trailing window such as `30d`, `8w`, `3m` or `1y`
+    /// - `last week` / `last month` / `last quarter` / `last year` – the
+    ///   trailing 7, 30, 91 or 365 days
+    ///
+    /// Everything else is free text.
+    pub fn parse_at(input: &str, now: DateTime<Utc>) -> Self {
+        let mut query = Self::new("");
+        let mut words: Vec<String> = Vec::new();
+        let tokens = split_quoted(input);
+        let mut i = 0;
+        while i < tokens.len() {
+            let token = &tokens[i];
+            i += 1;
+            if let Some((key, value)) = token.split_once(':').filter(|(_, v)| !v.is_empty()) {
+                let applied = match key.to_lowercase().as_str() {
+                    "registrar" => {
+                        query.registrar = Some(value.to_string());
+                        true
+                    }
+                    "ns" | "nameserver" => {
+                        query.nameserver = Some(value.to_string());
+                        true
+                    }
+                    "domain" => {
+                        query.domain = Some(value.to_lowercase());
+                        true
+                    }
+                    "in" | "kind" => match SourceKind::parse(value) {
+                        Some(kind) => {
+                            query = query.with_kind(kind);
+                            true
+                        }
+                        None => false,
+                    },
+                    "since" | "after" => parse_date(value).map(|d| query.since = Some(d)).is_some(),
+                    "until" | "before" => {
+                        parse_date(value).map(|d| query.until = Some(d)).is_some()
+                    }
+                    "within" => parse_window(value)
+                        .map(|w| query.since = Some(now - w))
+                        .is_some(),
+                    _ => false,
+                };
+                if applied {
+                    continue;
+                }
+            }
+            if matches!(token.to_lowercase().as_str(), "last" | "past") {
+                if let Some(window) = tokens.get(i).and_then(|next| named_window(next)) {
+                    query.since = Some(now - window);
+                    i += 1;
+                    continue;
+                }
+            }
+            words.push(token.clone());
+        }
+        query.text = words.join(" ");
+        query
+    }
+
+    /// Whether any field or time filter is set.
+    pub fn has_filters(&self) -> bool {
+        !self.kinds.is_empty()
+            || self.domain.is_some()
+            || self.registrar.is_some()
+            || self.nameserver.is_some()
+            || self.since.is_some()
+            || self.until.is_some()
+    }
+
+    /// Whether `doc` passes every filter. The free text is not considered.
+    pub fn matches(&self, doc: &Document) -> bool {
+        if !self.kinds.is_empty() && !self.kinds.contains(&doc.kind) {
+            return false;
+        }
+        if let Some(domain) = &self.domain {
+            if doc.domain.as_deref() != Some(domain.as_str()) {
+                return false;
+            }
+        }
+        if let Some(registrar) = &self.registrar {
+            let registrar = registrar.to_lowercase();
+            if !doc
+                .registrar
+                .as_ref()
+                .is_some_and(|r| r.to_lowercase().contains(&registrar))
+            {
+                return false;
+            }
+        }
+        if let Some(ns) = &self.nameserver {
+            let ns = ns.to_lowercase();
+            if !doc.nameservers.iter().any(|n| n.contains(&ns)) {
+                return false;
+            }
+        }
+        self.since.is_none_or(|since| doc.timestamp >= since)
+            && self.until.is_none_or(|until| doc.timestamp < until)
+    }
+}
+
+/// Split on whitespace, keeping double-quoted runs together (quotes removed).
+fn split_quoted(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in input.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+fn parse_date(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|d| d.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map(|d| d.and_utc())
+        })
+}
+
+/// `30d`, `8w`, `3m` or `1y`.
+fn parse_window(s: &str) -> Option<Duration> {
+    let s = s.to_lowercase();
+    let (n, unit) = s.split_at(s.len().checked_sub(1)?);
+    let n: i64 = n.parse().ok()?;
+    let days = match unit {
+        "d" => n,
+        "w" => n * 7,
+        "m" => n * 30,
+        "y" => n * 365,
+        _ => return None,
+    };
+    Some(Duration::days(days))
+}
+
+fn named_window(s: &str) -> Option<Duration> {
+    let days = match s.to_lowercase().trim_end_matches(['.', ',', '?']) {
+        "week" => 7,
+        "month" => 30,
+        "quarter" => 91,
+        "year" => 365,
+        _ => return None,
+    };
+    Some(Duration::days(days))
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_filters_and_text() {
+        let q = SearchQuery::parse_at(
+            r#"transfer registrar:"Acme Inc" ns:cloudflare in:whois last quarter"#,
+            now(),
+        );
+        assert_eq!(q.text, "transfer");
+        assert_eq!(q.registrar.as_deref(), Some("Acme Inc"));
+        assert_eq!(q.nameserver.as_deref(), Some("cloudflare"));
+        assert_eq!(q.kinds, [SourceKind::Snapshot]);
+        assert_eq!(q.since, Some(now() - Duration::days(91)));
+    }
+
+    #[test]
+    fn test_parse_dates_and_windows() {
+        let q = SearchQuery::parse_at("since:2026-01-01 until:2026-02-01 domain:A.com", now());
+        assert_eq!(
+            q.since,
+            Some(Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            q.until,
+            Some(Utc.with_ymd_and_hms(2026, 2, 1, 0, 0, 0).unwrap())
+        );
+        assert_eq!(q.domain.as_deref(), Some("a.com"));
+        assert!(q.text.is_empty());
+
+        let q = SearchQuery::parse_at("within:2w", now());
+        assert_eq!(q.since, Some(now() - Duration::days(14)));
+    }
+
+    #[test]
+    fn test_unknown_filters_stay_in_text() {
+        let q = SearchQuery::parse_at("color:blue since:someday last time", now());
+        assert_eq!(q.text, "color:blue since:someday last time");
+        assert!(!q.has_filters());
+    }
+
+    #[test]
+    fn test_matches() {
+        let doc = Document::new("d", SourceKind::Snapshot, "1", "t", "x", now())
+            .with_domain("a.com")
+            .with_registrar("Acme Inc")
+            .with_nameservers(vec!["ns1.cloudflare.com".into()]);
+        assert!(SearchQuery::new("")
+            .with_registrar("acme")
+            .with_nameserver("CLOUDFLARE")
+            .matches(&doc));
+        assert!(!SearchQuery::new("").with_registrar("other").matches(&doc));
+        assert!(!SearchQuery::new("")
+            .with_kind(SourceKind::Attachment)
+            .matches(&doc));
+        assert!(!SearchQuery::new("")
+            .with_period(Some(now() + Duration::days(1)), None)
+            .matches(&doc));
+        assert!(!SearchQuery::new("")
+            .with_period(None, Some(now()))
+            .matches(&doc));
+    }
+}
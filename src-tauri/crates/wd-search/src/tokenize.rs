@@ -0,0 +1,56 @@
+/// Words too common to help ranking.
+const STOPWORDS: &[&str] = &[
+    "an", "and", "are", "as", "at", "be", "by", "did", "do", "for", "from", "has", "have", "in",
+    "is", "it", "of", "on", "or", "the", "this", "that", "to", "was", "we", "were", "which",
+    "with",
+];
+
+/// Split `text` into lowercase index terms.
+///
+/// Domains and hostnames are kept whole and also split into their labels, so
+/// `ns1.cloudflare.com` matches both itself and `cloudflare`.
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    for raw in text.split(|c: char| !(c.is_alphanumeric() || matches!(c, '.' | '-' | '_'))) {
+        let token = raw
+            .trim_matches(|c| matches!(c, '.' | '-' | '_'))
+            .to_lowercase();
+        if token.len() < 2 || STOPWORDS.contains(&token.as_str()) {
+            continue;
+        }
+        if token.contains('.') {
+            terms.extend(
+                token
+                    .split('.')
+                    .filter(|label| label.len() >= 2)
+                    .map(str::to_string),
+            );
+        }
+        terms.push(token);
+    }
+    terms
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_splits_hostnames() {
+        let terms = tokenize("Name Server: NS1.Cloudflare.com.");
+        assert!(terms.contains(&"ns1.cloudflare.com".to_string()));
+        assert!(terms.contains(&"cloudflare".to_string()));
+        assert!(terms.contains(&"server".to_string()));
+    }
+
+    #[test]
+    fn test_tokenize_drops_stopwords_and_short_tokens() {
+        assert_eq!(
+            tokenize("a domain of the registrar"),
+            ["domain", "registrar"]
+        );
+        assert!(tokenize("- . x").is_empty());
+    }
+}
@@ -5,6 +5,12 @@ use serde::Deserialize;
 use std::collections::HashSet;
 use std::sync::LazyLock;
 
+pub mod recipe;
+
+pub use recipe::{
+    CharClass, CompiledRecipe, ListSource, Recipe, RecipeBook, RecipeRun, WordlistOp,
+};
+
 /// Compiled regex: match one or more whitespace characters.
 static RE_WHITESPACE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\s+").unwrap());
 
@@ -0,0 +1,734 @@
+//! Ordered, serializable wordlist pipelines ("recipes").
+//!
+//! A [`Recipe`] is a list of [`WordlistOp`] steps. Recipes are compiled into
+//! a chain of stages that process one line at a time, so they can be run over
+//! files far larger than memory with [`Recipe::stream`]. Only steps that need
+//! to see the whole list (sorting, shuffling, sampling) buffer their input.
+
+use std::collections::HashSet;
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::{rot13, to_leet_speak, RE_NON_ALNUM_ALL, RE_NON_ALNUM_TRIM, RE_WHITESPACE};
+
+// ─── Operations ──────────────────────────────────────────────────────────────
+
+/// Character classes used by the character filters.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CharClass {
+    Lower,
+    Upper,
+    Digit,
+    Hyphen,
+    Dot,
+    Underscore,
+    Whitespace,
+    /// Anything outside ASCII.
+    NonAscii,
+    /// ASCII punctuation other than hyphen, dot and underscore.
+    Punctuation,
+}
+
+impl CharClass {
+    pub fn matches(&self, c: char) -> bool {
+        match self {
+            Self::Lower => c.is_ascii_lowercase(),
+            Self::Upper => c.is_ascii_uppercase(),
+            Self::Digit => c.is_ascii_digit(),
+            Self::Hyphen => c == '-',
+            Self::Dot => c == '.',
+            Self::Underscore => c == '_',
+            Self::Whitespace => c.is_whitespace(),
+            Self::NonAscii => !c.is_ascii(),
+            Self::Punctuation => c.is_ascii_punctuation() && !matches!(c, '-' | '.' | '_'),
+        }
+    }
+}
+
+/// Where the right-hand list of a [`WordlistOp::Product`] comes from.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "source", rename_all = "camelCase")]
+pub enum ListSource {
+    Inline { lines: Vec<String> },
+    File { path: String },
+}
+
+/// A single wordlist operation. Serialized with an `op` tag whose names match
+/// the operation strings accepted by the `wordlist_transform` command.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "op", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum WordlistOp {
+    AddPrefix {
+        value: String,
+    },
+    AddSuffix {
+        value: String,
+    },
+    AddAffix {
+        prefix: String,
+        suffix: String,
+    },
+    Sort,
+    SortReverse,
+    Shuffle,
+    TrimSpaces,
+    DeleteSpaces,
+    DeleteBlankLines,
+    TrimNonAlnum,
+    DeleteNonAlnum,
+    Dedupe,
+    DeleteLinesContaining {
+        value: String,
+    },
+    DeleteString {
+        value: String,
+    },
+    ReplaceString {
+        search: String,
+        replacement: String,
+    },
+    DeleteRegex {
+        pattern: String,
+    },
+    TrimRegex {
+        pattern: String,
+    },
+    ReplaceRegex {
+        pattern: String,
+        replacement: String,
+    },
+    ToLowerCase,
+    ToUpperCase,
+    Rot13,
+    LeetSpeak,
+    /// Keep lines whose length in characters lies within the bounds.
+    FilterLength {
+        min: Option<usize>,
+        max: Option<usize>,
+    },
+    /// Keep lines made up only of the given character classes.
+    KeepCharClasses {
+        classes: Vec<CharClass>,
+    },
+    /// Drop lines containing any character of the given classes.
+    DropCharClasses {
+        classes: Vec<CharClass>,
+    },
+    /// Join each run of `n` consecutive lines with `separator`.
+    #[serde(rename = "ngrams")]
+    NGrams {
+        n: usize,
+        #[serde(default)]
+        separator: String,
+    },
+    /// Combine every line with every entry of another list.
+    Product {
+        with: ListSource,
+        #[serde(default)]
+        separator: String,
+    },
+    /// Keep a uniform random sample of `count` lines (reservoir sampling).
+    Sample {
+        count: usize,
+        seed: Option<u64>,
+    },
+}
+
+impl WordlistOp {
+    /// Build an operation from the legacy `wordlist_transform` arguments.
+    pub fn from_legacy(
+        operation: &str,
+        arg1: Option<&str>,
+        arg2: Option<&str>,
+    ) -> Result<Self, String> {
+        let a1 = || arg1.unwrap_or("").to_string();
+        let a2 = || arg2.unwrap_or("").to_string();
+        Ok(match operation {
+            "addPrefix" => Self::AddPrefix { value: a1() },
+            "addSuffix" => Self::AddSuffix { value: a1() },
+            "addAffix" => Self::AddAffix {
+                prefix: a1(),
+                suffix: a2(),
+            },
+            "sort" => Self::Sort,
+            "sortReverse" => Self::SortReverse,
+            "shuffle" => Self::Shuffle,
+            "trimSpaces" => Self::TrimSpaces,
+            "deleteSpaces" => Self::DeleteSpaces,
+            "deleteBlankLines" => Self::DeleteBlankLines,
+            "trimNonAlnum" => Self::TrimNonAlnum,
+            "deleteNonAlnum" => Self::DeleteNonAlnum,
+            "dedupe" => Self::Dedupe,
+            "deleteLinesContaining" => Self::DeleteLinesContaining { value: a1() },
+            "deleteString" => Self::DeleteString { value: a1() },
+            "toLowerCase" => Self::ToLowerCase,
+            "toUpperCase" => Self::ToUpperCase,
+            "rot13" => Self::Rot13,
+            "leetSpeak" => Self::LeetSpeak,
+            "replaceString" => Self::ReplaceString {
+                search: a1(),
+                replacement: a2(),
+            },
+            "deleteRegex" => Self::DeleteRegex { pattern: a1() },
+            "trimRegex" => Self::TrimRegex { pattern: a1() },
+            "replaceRegex" => Self::ReplaceRegex {
+                pattern: a1(),
+                replacement: a2(),
+            },
+            _ => return Err(format!("Unknown operation: {}", operation)),
+        })
+    }
+}
+
+// ─── Recipe ──────────────────────────────────────────────────────────────────
+
+/// A named, ordered list of wordlist operations.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Recipe {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub steps: Vec<WordlistOp>,
+}
+
+/// Line counts from a recipe run.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RecipeRun {
+    #[serde(rename = "linesIn")]
+    pub lines_in: usize,
+    #[serde(rename = "linesOut")]
+    pub lines_out: usize,
+}
+
+impl Recipe {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Append a step.
+    pub fn then(mut self, op: WordlistOp) -> Self {
+        self.steps.push(op);
+        self
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| e.to_string())
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| e.to_string())
+    }
+
+    /// Validate the recipe and prepare it for execution: compiles regexes
+    /// and loads `Product` lists.
+    pub fn compile(&self) -> Result<CompiledRecipe, String> {
+        let stages = self
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(i, op)| Stage::compile(op).map_err(|e| format!("step {}: {}", i + 1, e)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(CompiledRecipe { stages })
+    }
+
+    /// Run the recipe over an in-memory list.
+    pub fn apply(&self, lines: &[String]) -> Result<Vec<String>, String> {
+        let mut compiled = self.compile()?;
+        let mut out = Vec::with_capacity(lines.len());
+        for line in lines {
+            compiled.push(line.clone(), &mut |l| out.push(l));
+        }
+        compiled.finish(&mut |l| out.push(l));
+        Ok(out)
+    }
+
+    /// Run the recipe line by line from `reader` to `writer`.
+    pub fn stream<R: BufRead, W: Write>(
+        &self,
+        reader: R,
+        mut writer: W,
+    ) -> Result<RecipeRun, String> {
+        let mut compiled = self.compile()?;
+        let mut run = RecipeRun::default();
+        let mut write_err: Option<std::io::Error> = None;
+        let mut sink = |l: String| {
+            if write_err.is_none() {
+                run.lines_out += 1;
+                if let Err(e) = writeln!(writer, "{}", l) {
+                    write_err = Some(e);
+                }
+            }
+        };
+        for line in reader.lines() {
+            let line = line.map_err(|e| e.to_string())?;
+            run.lines_in += 1;
+            compiled.push(line, &mut sink);
+        }
+        compiled.finish(&mut sink);
+        if let Some(e) = write_err {
+            return Err(e.to_string());
+        }
+        writer.flush().map_err(|e| e.to_string())?;
+        Ok(run)
+    }
+}
+
+// ─── Recipe book ─────────────────────────────────────────────────────────────
+
+/// A collection of saved recipes, persisted as one JSON file per profile.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct RecipeBook {
+    pub recipes: Vec<Recipe>,
+}
+
+impl RecipeBook {
+    /// Load a recipe book, returning an empty one if the file does not exist.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        serde_json::from_str(&content).map_err(|e| e.to_string())
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, json).map_err(|e| e.to_string())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Recipe> {
+        self.recipes.iter().find(|r| r.name == name)
+    }
+
+    /// Insert a recipe, replacing any existing one with the same name.
+    pub fn upsert(&mut self, recipe: Recipe) {
+        match self.recipes.iter_mut().find(|r| r.name == recipe.name) {
+            Some(existing) => *existing = recipe,
+            None => self.recipes.push(recipe),
+        }
+    }
+
+    /// Remove a recipe by name. Returns whether one was removed.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.recipes.len();
+        self.recipes.retain(|r| r.name != name);
+        self.recipes.len() != before
+    }
+}
+
+// ─── Execution ───────────────────────────────────────────────────────────────
+
+/// A recipe ready to run. Feed lines with [`push`](Self::push) and call
+/// [`finish`](Self::finish) once at the end to flush buffering stages.
+pub struct CompiledRecipe {
+    stages: Vec<Stage>,
+}
+
+impl CompiledRecipe {
+    pub fn push(&mut self, line: String, sink: &mut dyn FnMut(String)) {
+        feed(&mut self.stages, line, sink);
+    }
+
+    pub fn finish(&mut self, sink: &mut dyn FnMut(String)) {
+        for i in 0..self.stages.len() {
+            let (head, rest) = self.stages.split_at_mut(i + 1);
+            head[i].finish(&mut |l| feed(rest, l, sink));
+        }
+    }
+}
+
+fn feed(stages: &mut [Stage], line: String, sink: &mut dyn FnMut(String)) {
+    match stages.split_first_mut() {
+        None => sink(line),
+        Some((first, rest)) => first.push(line, &mut |l| feed(rest, l, sink)),
+    }
+}
+
+enum Stage {
+    Map(Box<dyn Fn(&str) -> String + Send>),
+    Filter(Box<dyn Fn(&str) -> bool + Send>),
+    Dedupe(HashSet<String>),
+    Buffer {
+        lines: Vec<String>,
+        order: BufferOrder,
+    },
+    NGrams {
+        n: usize,
+        separator: String,
+        window: Vec<String>,
+    },
+    Product {
+        other: Vec<String>,
+        separator: String,
+    },
+    Sample {
+        count: usize,
+        seen: usize,
+        reservoir: Vec<String>,
+        rng: Box<StdRng>,
+    },
+}
+
+enum BufferOrder {
+    Asc,
+    Desc,
+    Shuffle,
+}
+
+impl Stage {
+    fn compile(op: &WordlistOp) -> Result<Self, String> {
+        let regex = |p: &str| Regex::new(p).map_err(|e| e.to_string());
+        let stage = match op.clone() {
+            WordlistOp::AddPrefix { value } => Self::map(move |l| format!("{}{}", value, l)),
+            WordlistOp::AddSuffix { value } => Self::map(move |l| format!("{}{}", l, value)),
+            WordlistOp::AddAffix { prefix, suffix } => {
+                Self::map(move |l| format!("{}{}{}", prefix, l, suffix))
+            }
+            WordlistOp::Sort => Self::buffer(BufferOrder::Asc),
+            WordlistOp::SortReverse => Self::buffer(BufferOrder::Desc),
+            WordlistOp::Shuffle => Self::buffer(BufferOrder::Shuffle),
+            WordlistOp::TrimSpaces => Self::map(|l| l.trim().to_string()),
+            WordlistOp::DeleteSpaces => Self::map(|l| RE_WHITESPACE.replace_all(l, "").into()),
+            WordlistOp::DeleteBlankLines => Self::filter(|l| !l.trim().is_empty()),
+            WordlistOp::TrimNonAlnum => Self::map(|l| RE_NON_ALNUM_TRIM.replace_all(l, "").into()),
+            WordlistOp::DeleteNonAlnum => Self::map(|l| RE_NON_ALNUM_ALL.replace_all(l, "").into()),
+            WordlistOp::Dedupe => Self::Dedupe(HashSet::new()),
+            WordlistOp::DeleteLinesContaining { value } => {
+                Self::filter(move |l| !l.contains(value.as_str()))
+            }
+            WordlistOp::DeleteString { value } => Self::map(move |l| l.replace(&value, "")),
+            WordlistOp::ReplaceString {
+                search,
+                replacement,
+            } => Self::map(move |l| l.replace(&search, &replacement)),
+            WordlistOp::DeleteRegex { pattern } => {
+                let re = regex(&pattern)?;
+                Self::filter(move |l| !re.is_match(l))
+            }
+            WordlistOp::TrimRegex { pattern } => {
+                let re = regex(&pattern)?;
+                Self::map(move |l| re.replace_all(l, "").into())
+            }
+            WordlistOp::ReplaceRegex {
+                pattern,
+                replacement,
+            } => {
+                let re = regex(&pattern)?;
+                Self::map(move |l| re.replace_all(l, replacement.as_str()).into())
+            }
+            WordlistOp::ToLowerCase => Self::map(|l| l.to_lowercase()),
+            WordlistOp::ToUpperCase => Self::map(|l| l.to_uppercase()),
+            WordlistOp::Rot13 => Self::map(rot13),
+            WordlistOp::LeetSpeak => Self::map(to_leet_speak),
+            WordlistOp::FilterLength { min, max } => {
+                if let (Some(lo), Some(hi)) = (min, max) {
+                    if lo > hi {
+                        return Err(format!("min length {} exceeds max {}", lo, hi));
+                    }
+                }
+                Self::filter(move |l| {
+                    let len = l.chars().count();
+                    min.is_none_or(|m| len >= m) && max.is_none_or(|m| len <= m)
+                })
+            }
+            WordlistOp::KeepCharClasses { classes } => {
+                Self::filter(move |l| l.chars().all(|c| classes.iter().any(|k| k.matches(c))))
+            }
+            WordlistOp::DropCharClasses { classes } => {
+                Self::filter(move |l| !l.chars().any(|c| classes.iter().any(|k| k.matches(c))))
+            }
+            WordlistOp::NGrams { n, separator } => {
+                if n == 0 {
+                    return Err("n-gram size must be at least 1".into());
+                }
+                Self::NGrams {
+                    n,
+                    separator,
+                    window: Vec::with_capacity(n),
+                }
+            }
+            WordlistOp::Product { with, separator } => Self::Product {
+                other: load_list(&with)?,
+                separator,
+            },
+            WordlistOp::Sample { count, seed } => Self::Sample {
+                count,
+                seen: 0,
+                reservoir: Vec::with_capacity(count),
+                rng: Box::new(match seed {
+                    Some(s) => StdRng::seed_from_u64(s),
+                    None => StdRng::from_entropy(),
+                }),
+            },
+        };
+        Ok(stage)
+    }
+
+    fn map(f: impl Fn(&str) -> String + Send + 'static) -> Self {
+        Self::Map(Box::new(f))
+    }
+
+    fn filter(f: impl Fn(&str) -> bool + Send + 'static) -> Self {
+        Self::Filter(Box::new(f))
+    }
+
+    fn buffer(order: BufferOrder) -> Self {
+        Self::Buffer {
+            lines: vec![],
+            order,
+        }
+    }
+
+    fn push(&mut self, line: String, out: &mut dyn FnMut(String)) {
+        match self {
+            Self::Map(f) => out(f(&line)),
+            Self::Filter(f) => {
+                if f(&line) {
+                    out(line)
+                }
+            }
+            Self::Dedupe(seen) => {
+                if seen.insert(line.clone()) {
+                    out(line)
+                }
+            }
+            Self::Buffer { lines, .. } => lines.push(line),
+            Self::NGrams {
+                n,
+                separator,
+                window,
+            } => {
+                if window.len() == *n {
+                    window.remove(0);
+                }
+                window.push(line);
+                if window.len() == *n {
+                    out(window.join(separator.as_str()));
+                }
+            }
+            Self::Product { other, separator } => {
+                for o in other.iter() {
+                    out(format!("{}{}{}", line, separator, o));
+                }
+            }
+            Self::Sample {
+                count,
+                seen,
+                reservoir,
+                rng,
+            } => {
+                *seen += 1;
+                if reservoir.len() < *count {
+                    reservoir.push(line);
+                } else {
+                    let j = rng.gen_range(0..*seen);
+                    if j < *count {
+                        reservoir[j] = line;
+                    }
+                }
+            }
+        }
+    }
+
+    fn finish(&mut self, out: &mut dyn FnMut(String)) {
+        match self {
+            Self::Buffer { lines, order } => {
+                let mut lines = std::mem::take(lines);
+                match order {
+                    BufferOrder::Asc => lines.sort(),
+                    BufferOrder::Desc => {
+                        lines.sort();
+                        lines.reverse();
+                    }
+                    BufferOrder::Shuffle => lines.shuffle(&mut rand::thread_rng()),
+                }
+                lines.into_iter().for_each(out);
+            }
+            Self::Sample { reservoir, .. } => std::mem::take(reservoir).into_iter().for_each(out),
+            _ => {}
+        }
+    }
+}
+
+fn load_list(source: &ListSource) -> Result<Vec<String>, String> {
+    let lines = match source {
+        ListSource::Inline { lines } => lines.clone(),
+        ListSource::File { path } => std::fs::read_to_string(path)
+            .map_err(|e| format!("{}: {}", path, e))?
+            .lines()
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty())
+            .collect(),
+    };
+    Ok(lines)
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(strs: &[&str]) -> Vec<String> {
+        strs.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_recipe_matches_legacy_functions() {
+        let input = lines(&["  Hello ", "world", "", "hello"]);
+        let recipe = Recipe::new("clean")
+            .then(WordlistOp::TrimSpaces)
+            .then(WordlistOp::DeleteBlankLines)
+            .then(WordlistOp::ToLowerCase)
+            .then(WordlistOp::Dedupe)
+            .then(WordlistOp::Sort);
+        let expected = crate::sort_lines(&crate::dedupe_lines(&crate::to_lower_case_lines(
+            &crate::delete_blank_lines(&crate::trim_spaces(&input)),
+        )));
+        assert_eq!(recipe.apply(&input).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_json_roundtrip_uses_op_tags() {
+        let recipe = Recipe::new("r")
+            .then(WordlistOp::ReplaceRegex {
+                pattern: r"\d".into(),
+                replacement: "x".into(),
+            })
+            .then(WordlistOp::FilterLength {
+                min: Some(2),
+                max: None,
+            });
+        let json = recipe.to_json().unwrap();
+        assert!(json.contains(r#""op": "replaceRegex""#));
+        assert!(json.contains(r#""op": "filterLength""#));
+        assert_eq!(Recipe::from_json(&json).unwrap(), recipe);
+    }
+
+    #[test]
+    fn test_from_legacy() {
+        assert_eq!(
+            WordlistOp::from_legacy("leetSpeak", None, None).unwrap(),
+            WordlistOp::LeetSpeak
+        );
+        assert!(WordlistOp::from_legacy("nope", None, None).is_err());
+    }
+
+    #[test]
+    fn test_filter_length_and_char_classes() {
+        let recipe = Recipe::new("f")
+            .then(WordlistOp::FilterLength {
+                min: Some(3),
+                max: Some(5),
+            })
+            .then(WordlistOp::KeepCharClasses {
+                classes: vec![CharClass::Lower, CharClass::Hyphen],
+            });
+        let out = recipe
+            .apply(&lines(&["ab", "abc", "ab-c", "abc1", "abcdef", "ABC"]))
+            .unwrap();
+        assert_eq!(out, lines(&["abc", "ab-c"]));
+
+        let drop = Recipe::new("d").then(WordlistOp::DropCharClasses {
+            classes: vec![CharClass::Digit],
+        });
+        assert_eq!(drop.apply(&lines(&["a1", "bb"])).unwrap(), lines(&["bb"]));
+    }
+
+    #[test]
+    fn test_ngrams() {
+        let recipe = Recipe::new("n").then(WordlistOp::NGrams {
+            n: 2,
+            separator: "-".into(),
+        });
+        assert_eq!(
+            recipe.apply(&lines(&["red", "blue", "green"])).unwrap(),
+            lines(&["red-blue", "blue-green"])
+        );
+    }
+
+    #[test]
+    fn test_product() {
+        let recipe = Recipe::new("p").then(WordlistOp::Product {
+            with: ListSource::Inline {
+                lines: lines(&["hub", "lab"]),
+            },
+            separator: String::new(),
+        });
+        assert_eq!(
+            recipe.apply(&lines(&["code", "data"])).unwrap(),
+            lines(&["codehub", "codelab", "datahub", "datalab"])
+        );
+    }
+
+    #[test]
+    fn test_sample_is_deterministic_with_seed() {
+        let input: Vec<String> = (0..100).map(|i| i.to_string()).collect();
+        let recipe = Recipe::new("s").then(WordlistOp::Sample {
+            count: 10,
+            seed: Some(7),
+        });
+        let a = recipe.apply(&input).unwrap();
+        let b = recipe.apply(&input).unwrap();
+        assert_eq!(a.len(), 10);
+        assert_eq!(a, b);
+        assert!(a.iter().all(|l| input.contains(l)));
+    }
+
+    #[test]
+    fn test_steps_after_buffering_stage_run_on_flush() {
+        let recipe = Recipe::new("b")
+            .then(WordlistOp::SortReverse)
+            .then(WordlistOp::AddSuffix {
+                value: ".com".into(),
+            });
+        assert_eq!(
+            recipe.apply(&lines(&["a", "c", "b"])).unwrap(),
+            lines(&["c.com", "b.com", "a.com"])
+        );
+    }
+
+    #[test]
+    fn test_stream() {
+        let recipe = Recipe::new("s")
+            .then(WordlistOp::DeleteBlankLines)
+            .then(WordlistOp::AddPrefix { value: "x".into() });
+        let mut out = Vec::new();
+        let run = recipe.stream("a\n\nb\n".as_bytes(), &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "xa\nxb\n");
+        assert_eq!(run.lines_in, 3);
+        assert_eq!(run.lines_out, 2);
+    }
+
+    #[test]
+    fn test_compile_reports_step() {
+        let recipe = Recipe::new("bad")
+            .then(WordlistOp::Dedupe)
+            .then(WordlistOp::DeleteRegex {
+                pattern: "[oops".into(),
+            });
+        let err = recipe.compile().err().unwrap();
+        assert!(err.starts_with("step 2:"));
+    }
+
+    #[test]
+    fn test_recipe_book() {
+        let mut book = RecipeBook::default();
+        book.upsert(Recipe::new("a").then(WordlistOp::Sort));
+        book.upsert(Recipe::new("a").then(WordlistOp::Shuffle));
+        assert_eq!(book.recipes.len(), 1);
+        assert_eq!(book.get("a").unwrap().steps, vec![WordlistOp::Shuffle]);
+        assert!(book.remove("a"));
+        assert!(!book.remove("a"));
+    }
+}
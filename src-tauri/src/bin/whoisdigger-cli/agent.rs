@@ -0,0 +1,250 @@
+use clap::{Args, Subcommand};
+use std::fs;
+use std::io::{IsTerminal, Write};
+use whoisdigger::agentic::agent::{
+    Agent, AgentConfig, AgentEvent, BuiltinTools, ConfirmationChannel, ConfirmationDecision,
+    EventKind, KnowledgeStore, Pipeline, PipelineKind, PipelineReport, PipelineRun, PipelineRunner,
+    Sandbox, SandboxConfig, ToolExecutor, Toolbox,
+};
+use whoisdigger::agentic::llm::{LlmClient, LlmConfig, ProviderKind};
+use whoisdigger::agentic::search::{HistoryStore, SearchIndex};
+use whoisdigger::storage::audit::{self, AuditLog, AUDIT_FILE};
+
+use crate::profile::{load_lookup_settings, open_profile, ProfileArgs};
+use crate::results::open_input;
+use crate::{label, parse_enum};
+
+/// Model options for `agent` (API key from WHOISDIGGER_LLM_API_KEY).
+#[derive(Args, Debug)]
+pub(crate) struct AgentArgs {
+    /// Model to run
+    #[arg(long, global = true, default_value = "gpt-4o-mini")]
+    model: String,
+    /// Provider serving the model
+    #[arg(long, global = true, default_value = "openai")]
+    provider: String,
+    /// Base URL of the provider's API
+    #[arg(long, global = true)]
+    url: Option<String>,
+    /// Stop a run once it has cost this many US dollars
+    #[arg(long, global = true, default_value_t = 1.0)]
+    max_cost: f64,
+    /// Print the full result as JSON
+    #[arg(long, global = true)]
+    json: bool,
+    /// Allow exports and cache clears without asking
+    #[arg(long, short = 'y', global = true)]
+    yes: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum AgentCommand {
+    /// Answer a free-form question
+    Ask {
+        /// The question
+        question: String,
+    },
+    /// Run a pre-built pipeline
+    Pipeline {
+        /// domain_audit, security_scan, brand_protection, portfolio_analysis
+        /// or drop_watch
+        #[arg(value_parser = parse_enum::<PipelineKind>)]
+        kind: PipelineKind,
+        /// Domain or brand, for the pipelines that work on one
+        target: Option<String>,
+        /// File passed to every step, e.g. a domain list ("-" reads stdin)
+        #[arg(short, long)]
+        input: Option<String>,
+    },
+    /// List the pre-built pipelines
+    Pipelines,
+}
+
+const AGENT_SYSTEM_PROMPT: &str = "You are a domain intelligence analyst. Answer the user's \
+     question with the available tools and say so when a tool fails instead of guessing.";
+
+pub(crate) async fn run(
+    command: AgentCommand,
+    args: AgentArgs,
+    profile: ProfileArgs,
+) -> anyhow::Result<()> {
+    match command {
+        AgentCommand::Ask { question } => {
+            let (mut agent, executor, client) = agent_setup(&args, profile)?;
+            let tools = Toolbox::full().all_definitions();
+            let result = agent
+                .run_with_client(AGENT_SYSTEM_PROMPT, &question, &tools, &executor, &client)
+                .await;
+            if args.json {
+                println!("{}", serde_json::to_string_pretty(&result)?);
+            } else if let Some(answer) = &result.answer {
+                println!("{}", answer);
+            }
+            if !result.completed {
+                anyhow::bail!("the agent stopped before answering");
+            }
+        }
+        AgentCommand::Pipeline {
+            kind,
+            target,
+            input,
+        } => {
+            let pipeline =
+                Pipeline::for_kind(&kind, target.as_deref()).map_err(anyhow::Error::msg)?;
+            let mut run = PipelineRun::new(pipeline);
+            if let Some(path) = input {
+                let mut text = String::new();
+                std::io::Read::read_to_string(&mut open_input(&path)?, &mut text)?;
+                run = run.with_input(text.trim());
+            }
+            let (agent, executor, client) = agent_setup(&args, profile)?;
+            let report = PipelineRunner::new(agent)
+                .run_with_client(&mut run, &executor, &client)
+                .await;
+            if args.json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                print_pipeline_report(&report);
+            }
+            if !report.completed {
+                anyhow::bail!("the pipeline stopped at a failed step");
+            }
+        }
+        AgentCommand::Pipelines => {
+            for (kind, name, description) in Pipeline::catalog() {
+                println!("{:<20} {:<20} {}", label(&kind), name, description);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// An agent with the built-in tools over the profile's history, search
+/// index, agent memory and request cache, and a client for the model.
+/// Tool calls are shown on stderr and recorded in the audit log.
+fn agent_setup(
+    args: &AgentArgs,
+    profile: ProfileArgs,
+) -> anyhow::Result<(Agent, ToolExecutor, LlmClient)> {
+    let provider: ProviderKind = parse_enum(&args.provider)
+        .map_err(|_| anyhow::anyhow!("unknown provider '{}'", args.provider))?;
+    let api_key = std::env::var("WHOISDIGGER_LLM_API_KEY").unwrap_or_default();
+    let mut config = LlmConfig::new(provider, &args.model).with_api_key(&api_key);
+    if let Some(url) = &args.url {
+        config = config.with_api_url(url);
+    }
+    let client = LlmClient::new(config)?;
+
+    let (data_dir, profile) = open_profile(profile)?;
+    let knowledge = std::sync::Arc::new(KnowledgeStore::open(
+        &profile.database("agent-memory").to_string_lossy(),
+    )?);
+    let mut executor = ToolExecutor::new();
+    BuiltinTools::new(load_lookup_settings(&data_dir))
+        .with_history(std::sync::Arc::new(HistoryStore::open(
+            &profile.snapshots().to_string_lossy(),
+        )?))
+        .with_search(std::sync::Arc::new(SearchIndex::open(
+            &profile.database("search-index").to_string_lossy(),
+        )?))
+        .with_knowledge(std::sync::Arc::clone(&knowledge))
+        .with_cache(profile.dir.join("request-cache.sqlite"))
+        .with_export_dir(profile.dir.join("exports"))
+        .install(&mut executor);
+
+    // Tools that write files or clear the cache are confirmed on the
+    // terminal (or by --yes) before they run.
+    let mut sandbox = Sandbox::new(SandboxConfig {
+        require_confirmation: true,
+        ..SandboxConfig::default()
+    });
+    sandbox.require_confirmation_for_defaults();
+
+    fs::create_dir_all(&data_dir)?;
+    let log = std::sync::Arc::new(AuditLog::open(
+        &data_dir.join(AUDIT_FILE).to_string_lossy(),
+    )?);
+    let audit = whoisdigger::agentic::audit::agent_sink(log, audit::local_actor());
+    let confirmations = ConfirmationChannel::new();
+    let yes = args.yes;
+    let agent = Agent::new(AgentConfig {
+        max_cost_usd: args.max_cost,
+        ..AgentConfig::default()
+    })
+    .with_sandbox(sandbox)
+    .with_confirmation_channel(confirmations.clone())
+    .with_knowledge_store(knowledge)
+    .with_event_sink(move |event: &AgentEvent| {
+        print_agent_event(event);
+        audit(event);
+        if let EventKind::ConfirmationRequested {
+            request_id,
+            tool_name,
+            arguments,
+        } = &event.kind
+        {
+            let decision = confirm_tool_call(tool_name, arguments, yes);
+            if let Err(e) = confirmations.resolve(request_id, decision) {
+                eprintln!("error: {}", e);
+            }
+        }
+    });
+    Ok((agent, executor, client))
+}
+
+/// Ask on the terminal whether the agent may run `tool_name`. Without a
+/// terminal to ask on the call is denied unless `--yes` was given.
+fn confirm_tool_call(
+    tool_name: &str,
+    arguments: &serde_json::Value,
+    yes: bool,
+) -> ConfirmationDecision {
+    if yes {
+        return ConfirmationDecision::Approve;
+    }
+    if !std::io::stdin().is_terminal() {
+        return ConfirmationDecision::Deny {
+            reason: Some("no terminal to confirm on; rerun with --yes to allow it".into()),
+        };
+    }
+    eprint!("Allow {} {}? [y/N] ", tool_name, arguments);
+    let _ = std::io::stderr().flush();
+    let mut answer = String::new();
+    match std::io::stdin().read_line(&mut answer) {
+        Ok(_) if matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes") => {
+            ConfirmationDecision::Approve
+        }
+        _ => ConfirmationDecision::Deny { reason: None },
+    }
+}
+
+fn print_agent_event(event: &AgentEvent) {
+    match &event.kind {
+        EventKind::PlanStepStarted {
+            step_index,
+            description,
+        } => eprintln!("[{}] {}", step_index, description),
+        EventKind::ToolCallStarted { tool_name } => eprintln!("  -> {}", tool_name),
+        EventKind::ToolCallCompleted {
+            tool_name,
+            success: false,
+        } => eprintln!("  !! {} failed", tool_name),
+        EventKind::Error { message } => eprintln!("error: {}", message),
+        _ => {}
+    }
+}
+
+fn print_pipeline_report(report: &PipelineReport) {
+    println!("{} (${:.4})", report.name, report.total_cost_usd);
+    for step in &report.steps {
+        println!("\n{}. {} [{}]", step.index, step.name, label(&step.status));
+        if let Some(outcome) = &step.outcome {
+            if let Some(answer) = &outcome.answer {
+                println!("{}", answer);
+            }
+            if let Some(error) = &outcome.error {
+                println!("error: {}", error);
+            }
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use clap::Args;
+use std::fs;
+use whoisdigger::api::ApiKeys;
+use whoisdigger::storage::audit::Action;
+
+use crate::audit::{cli_event, record};
+use crate::profile::resolve_data_dir;
+use crate::serve::API_KEYS_FILE;
+use crate::split_list;
+
+#[derive(Args, Debug)]
+pub(crate) struct ApiKeyArgs {
+    /// App data directory holding the API keys
+    #[arg(long)]
+    data_dir: Option<String>,
+    /// Create a key with this name and print it
+    #[arg(long)]
+    create: Option<String>,
+    /// Roles for the new key (comma separated)
+    #[arg(long, default_value = "viewer")]
+    roles: String,
+    /// Revoke the key with this name
+    #[arg(long)]
+    revoke: Option<String>,
+}
+
+pub(crate) fn run(args: ApiKeyArgs) -> anyhow::Result<()> {
+    let ApiKeyArgs {
+        data_dir,
+        create,
+        roles,
+        revoke,
+    } = args;
+    let data_dir = resolve_data_dir(data_dir)?;
+    fs::create_dir_all(&data_dir)?;
+    let path = data_dir.join(API_KEYS_FILE);
+    let mut keys = ApiKeys::load(&path).map_err(anyhow::Error::msg)?;
+    if let Some(name) = create {
+        let key = keys
+            .create(&name, split_list(&roles))
+            .map_err(anyhow::Error::msg)?;
+        keys.save(&path).map_err(anyhow::Error::msg)?;
+        record(
+            Some(&data_dir),
+            cli_event(Action::ApiKeyCreated, &name).with_detail("roles", roles),
+        );
+        println!(
+            "Created key '{}'. It will not be shown again:\n{}",
+            name, key
+        );
+    } else if let Some(name) = revoke {
+        if !keys.revoke(&name) {
+            anyhow::bail!("no API key named '{}'", name);
+        }
+        keys.save(&path).map_err(anyhow::Error::msg)?;
+        record(Some(&data_dir), cli_event(Action::ApiKeyRevoked, &name));
+        println!("Revoked key '{}'.", name);
+    } else {
+        for key in &keys.keys {
+            println!(
+                "{:<20} {:<30} {}",
+                key.name,
+                key.roles.join(","),
+                key.created_at.format("%Y-%m-%d")
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,189 @@
+use clap::Args;
+use std::fs;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+use whoisdigger::proxy::{Permission, Rbac, RBAC_AUDIT_FILE, RBAC_FILE};
+use whoisdigger::storage::audit::{
+    self, Action, AuditEvent, AuditLog, AuditQuery, Source, AUDIT_FILE,
+};
+use whoisdigger::storage::db::profile::app_data_dir;
+
+use crate::profile::resolve_data_dir;
+
+#[derive(Args, Debug)]
+pub(crate) struct AuditArgs {
+    /// App data directory holding the audit log
+    #[arg(long)]
+    data_dir: Option<String>,
+    /// Only entries by this actor (e.g. user:alice, key:ci)
+    #[arg(long)]
+    actor: Option<String>,
+    /// Only entries for this action (e.g. cache_cleared, bulk_started)
+    #[arg(long)]
+    action: Option<String>,
+    /// Only entries from this source (gui, cli, api, agent)
+    #[arg(long)]
+    source: Option<String>,
+    /// Only entries at or after this time (RFC 3339 or YYYY-MM-DD)
+    #[arg(long)]
+    since: Option<String>,
+    /// Only entries at or before this time (RFC 3339 or YYYY-MM-DD)
+    #[arg(long)]
+    until: Option<String>,
+    /// Most recent entries to show (0 for all)
+    #[arg(short, long, default_value_t = 50)]
+    limit: usize,
+    /// Write the matching entries to this JSONL file
+    #[arg(long)]
+    export: Option<String>,
+    /// Check the whole log for gaps and tampering
+    #[arg(long)]
+    verify: bool,
+    /// Check a full JSONL export instead of the log
+    #[arg(long)]
+    verify_file: Option<String>,
+}
+
+pub(crate) fn run(args: AuditArgs) -> anyhow::Result<()> {
+    let AuditArgs {
+        data_dir,
+        actor,
+        action,
+        source,
+        since,
+        until,
+        limit,
+        export,
+        verify,
+        verify_file,
+    } = args;
+    if let Some(file) = verify_file {
+        let report = audit::verify_jsonl(BufReader::new(fs::File::open(&file)?))?;
+        return print_verify(&report, &file);
+    }
+    let data_dir = resolve_data_dir(data_dir)?;
+    let path = data_dir.join(AUDIT_FILE);
+    if !path.is_file() {
+        anyhow::bail!("no audit log at {}", path.display());
+    }
+    let log = AuditLog::open(&path.to_string_lossy())?;
+    if verify {
+        return print_verify(&log.verify()?, &path.to_string_lossy());
+    }
+    let mut query = AuditQuery {
+        actor,
+        limit: (limit > 0).then_some(limit),
+        since: since.as_deref().map(parse_time).transpose()?,
+        until: until.as_deref().map(parse_time).transpose()?,
+        ..AuditQuery::default()
+    };
+    if let Some(a) = action {
+        query.action =
+            Some(Action::parse(&a).ok_or_else(|| anyhow::anyhow!("unknown action '{}'", a))?);
+    }
+    if let Some(s) = source {
+        query.source =
+            Some(Source::parse(&s).ok_or_else(|| anyhow::anyhow!("unknown source '{}'", s))?);
+    }
+    if let Some(output) = export {
+        let mut out = BufWriter::new(fs::File::create(&output)?);
+        let n = log.export_jsonl(&query, &mut out)?;
+        println!("Exported {} entries to {}.", n, output);
+    } else {
+        for entry in log.query(&query)? {
+            println!(
+                "{:>6}  {}  {:<5}  {:<20} {:<20} {}",
+                entry.seq,
+                entry.at.format("%Y-%m-%d %H:%M:%S"),
+                entry.event.source.as_str(),
+                entry.event.actor,
+                entry.event.action.as_str(),
+                entry.event.target.as_deref().unwrap_or("")
+            );
+        }
+    }
+    Ok(())
+}
+
+/// An audit event for a CLI action by the local user.
+pub(crate) fn cli_event(action: Action, target: &str) -> AuditEvent {
+    AuditEvent::new(audit::local_actor(), Source::Cli, action).with_target(target)
+}
+
+/// Append `event` to the audit log in `data_dir` (the app's by default).
+/// A log that cannot be written is reported but does not fail the command.
+pub(crate) fn record(data_dir: Option<&Path>, event: AuditEvent) {
+    let Some(dir) = data_dir.map(Path::to_path_buf).or_else(app_data_dir) else {
+        return;
+    };
+    let result = fs::create_dir_all(&dir)
+        .map_err(anyhow::Error::from)
+        .and_then(|_| Ok(AuditLog::open(&dir.join(AUDIT_FILE).to_string_lossy())?))
+        .and_then(|log| Ok(log.append(event)?));
+    if let Err(e) = result {
+        eprintln!("warning: could not write the audit log: {}", e);
+    }
+}
+
+/// Fail unless the local user holds `permission` under the RBAC file in
+/// `data_dir` (the app's by default). As in the desktop app, the local user
+/// is unrestricted until roles are bound to them; refusals are recorded in
+/// the RBAC denial log and the audit log.
+pub(crate) fn require_permission(
+    data_dir: Option<&Path>,
+    permission: Permission,
+) -> anyhow::Result<()> {
+    let Some(dir) = data_dir.map(Path::to_path_buf).or_else(app_data_dir) else {
+        return Ok(());
+    };
+    let rbac = Rbac::open(dir.join(RBAC_FILE))
+        .map_err(anyhow::Error::msg)?
+        .with_audit_log(dir.join(RBAC_AUDIT_FILE));
+    if rbac.check_if_bound(&audit::local_actor(), &permission) {
+        return Ok(());
+    }
+    let name = serde_json::to_value(&permission)
+        .ok()
+        .and_then(|v| v.as_str().map(String::from))
+        .unwrap_or_default();
+    record(Some(&dir), cli_event(Action::PermissionDenied, &name));
+    anyhow::bail!("permission denied: {}", name)
+}
+
+/// Print a verification report; fails when the chain is broken.
+fn print_verify(report: &audit::VerifyReport, source: &str) -> anyhow::Result<()> {
+    for problem in &report.problems {
+        match problem {
+            audit::Problem::Gap { after, next } => {
+                println!("gap: entries {}..{} missing", after + 1, next - 1)
+            }
+            audit::Problem::Altered { seq } => println!("altered: entry {}", seq),
+            audit::Problem::BrokenLink { seq } => println!("broken link: entry {}", seq),
+        }
+    }
+    if !report.is_intact() {
+        anyhow::bail!(
+            "{}: {} problem(s) in {} entries",
+            source,
+            report.problems.len(),
+            report.checked
+        );
+    }
+    println!(
+        "{}: {} entries intact, head {}",
+        source,
+        report.checked,
+        report.head.as_deref().unwrap_or("-")
+    );
+    Ok(())
+}
+
+/// Parse `2026-01-31` or an RFC 3339 timestamp.
+fn parse_time(s: &str) -> anyhow::Result<chrono::DateTime<chrono::Utc>> {
+    if let Ok(t) = chrono::DateTime::parse_from_rfc3339(s) {
+        return Ok(t.with_timezone(&chrono::Utc));
+    }
+    chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map(|d| d.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+        .map_err(|_| anyhow::anyhow!("invalid time '{}', expected YYYY-MM-DD or RFC 3339", s))
+}
@@ -0,0 +1,27 @@
+use clap::Args;
+use whoisdigger::proxy::Permission;
+use whoisdigger::storage::audit::Action;
+
+use crate::audit::{cli_event, record, require_permission};
+
+#[derive(Args, Debug)]
+pub(crate) struct CacheArgs {
+    /// Path to cache database
+    #[arg(short, long, default_value = "request-cache.sqlite")]
+    path: String,
+    /// Clear the entire cache
+    #[arg(long)]
+    clear: bool,
+}
+
+pub(crate) fn run(args: CacheArgs) -> anyhow::Result<()> {
+    let CacheArgs { path, clear } = args;
+    if clear {
+        require_permission(None, Permission::CachePurge)?;
+        let conn = rusqlite::Connection::open(&path)?;
+        conn.execute("DELETE FROM cache", [])?;
+        record(None, cli_event(Action::CacheCleared, &path));
+        println!("Cache cleared.");
+    }
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use clap::Args;
+use std::fs;
+use whoisdigger::automation::{
+    expiry::ExpiryStore,
+    export::ics::{CalendarEventKind, IcsCalendar},
+    portfolio::PortfolioStore,
+};
+use whoisdigger::proxy::Permission;
+
+use crate::audit::require_permission;
+use crate::profile::{open_profile, ProfileArgs};
+use crate::split_list;
+
+#[derive(Args, Debug)]
+pub(crate) struct CalendarArgs {
+    /// Output .ics path
+    #[arg(short, long, default_value = "domains.ics")]
+    output: String,
+    /// Reminders before each event (comma separated, e.g. 7d,1d,2h; empty for none)
+    #[arg(long, default_value = "7d,1d")]
+    alarms: String,
+    /// Events to include (comma separated: expiry, auto-renew-end, redemption-end, drop)
+    #[arg(long, default_value = "expiry,auto-renew-end,redemption-end,drop")]
+    events: String,
+    /// Calendar name shown by calendar apps
+    #[arg(long, default_value = "Domain expiry")]
+    name: String,
+    #[command(flatten)]
+    profile: ProfileArgs,
+}
+
+pub(crate) fn run(args: CalendarArgs) -> anyhow::Result<()> {
+    let CalendarArgs {
+        output,
+        alarms,
+        events,
+        name,
+        profile,
+    } = args;
+    let kinds = split_list(&events)
+        .iter()
+        .map(|k| {
+            CalendarEventKind::parse(k)
+                .ok_or_else(|| anyhow::anyhow!("unknown calendar event '{}'", k))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let alarms = split_list(&alarms)
+        .iter()
+        .map(|a| parse_alarm(a))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let (data_dir, profile) = open_profile(profile)?;
+    require_permission(Some(&data_dir), Permission::Export)?;
+    let (watchlist, portfolio) = (profile.watchlist(), profile.portfolio());
+    let mut calendar = IcsCalendar::new(name).with_kinds(kinds).with_alarms(alarms);
+    let now = chrono::Utc::now();
+    let mut sources = 0;
+    if watchlist.is_file() {
+        for entry in ExpiryStore::open(&watchlist.to_string_lossy())?.get_all()? {
+            calendar.add_watch_entry(&entry, now);
+        }
+        sources += 1;
+    }
+    if portfolio.is_file() {
+        for domain in PortfolioStore::open(&portfolio.to_string_lossy())?.get_all()? {
+            calendar.add_owned(&domain, now);
+        }
+        sources += 1;
+    }
+    if sources == 0 {
+        anyhow::bail!(
+            "neither {} nor {} exists",
+            watchlist.display(),
+            portfolio.display()
+        );
+    }
+    fs::write(&output, calendar.to_ics(now))?;
+    println!("Wrote {} events to {}.", calendar.events().len(), output);
+    Ok(())
+}
+
+/// Parse a reminder offset such as `7d`, `12h` or `30m`.
+fn parse_alarm(s: &str) -> anyhow::Result<chrono::Duration> {
+    let (n, unit) = s.split_at(s.len().saturating_sub(1));
+    let n: i64 = n
+        .parse()
+        .map_err(|_| anyhow::anyhow!("invalid reminder '{}', expected e.g. 7d, 12h or 30m", s))?;
+    match unit {
+        "d" => Ok(chrono::Duration::days(n)),
+        "h" => Ok(chrono::Duration::hours(n)),
+        "m" => Ok(chrono::Duration::minutes(n)),
+        _ => anyhow::bail!("invalid reminder '{}', expected e.g. 7d, 12h or 30m", s),
+    }
+}
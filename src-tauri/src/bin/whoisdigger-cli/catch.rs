@@ -0,0 +1,111 @@
+use clap::Args;
+use whoisdigger::automation::{
+    expiry::{CatchEventKind, Clock, DomainExpiry, DropCatcher, ExpiryStore},
+    notify::{Notification, NotificationKind, Severity},
+};
+use whoisdigger::dropcatch::NetworkProbe;
+use whoisdigger::proxy::Permission;
+
+use crate::audit::require_permission;
+use crate::label;
+use crate::notify::send_notifications;
+use crate::profile::{load_lookup_settings, open_profile, ProfileArgs};
+
+#[derive(Args, Debug)]
+pub(crate) struct CatchArgs {
+    /// Watched domains to catch (defaults to every active entry with an
+    /// expiry date)
+    domains: Vec<String>,
+    /// Skip RDAP and let WHOIS alone confirm availability
+    #[arg(long)]
+    no_rdap: bool,
+    #[command(flatten)]
+    profile: ProfileArgs,
+}
+
+/// Drive the drop catcher over the profile's watched domains until every
+/// one is caught, missed or alerted on.
+pub(crate) async fn run(args: CatchArgs) -> anyhow::Result<()> {
+    let CatchArgs {
+        domains,
+        no_rdap,
+        profile,
+    } = args;
+    let (data_dir, profile) = open_profile(profile)?;
+    require_permission(Some(&data_dir), Permission::Lookup)?;
+    let watchlist = ExpiryStore::open(&profile.watchlist().to_string_lossy())?.load()?;
+    let named = whoisdigger::bulk::expand_domains(&domains, &[]);
+    if let Some(missing) = named.iter().find(|d| watchlist.get(d).is_none()) {
+        anyhow::bail!("{} is not on the watchlist", missing);
+    }
+
+    let mut probe = NetworkProbe::new(load_lookup_settings(&data_dir));
+    if no_rdap {
+        probe = probe.without_rdap();
+    }
+    let mut catcher = DropCatcher::new(probe);
+    let now = chrono::Utc::now();
+    for entry in &watchlist.entries {
+        let wanted = match named.is_empty() {
+            true => entry.active,
+            false => named.contains(&entry.domain),
+        };
+        if !wanted {
+            continue;
+        }
+        let expiry = DomainExpiry::compute(
+            &entry.domain,
+            entry.expiry_date,
+            entry.registrar.as_deref(),
+            now,
+        );
+        if !catcher.watch(&expiry) {
+            eprintln!("{}: no expiry date to estimate a drop from", entry.domain);
+        }
+    }
+    if catcher.targets().is_empty() {
+        anyhow::bail!("no watched domain has a drop to catch");
+    }
+    for target in catcher.targets() {
+        println!(
+            "{}: drop expected {} to {}",
+            target.estimate.domain,
+            target.estimate.earliest_drop.format("%Y-%m-%d %H:%M"),
+            target.estimate.latest_drop.format("%Y-%m-%d %H:%M")
+        );
+    }
+
+    while let Some(wake) = catcher.next_wake() {
+        catcher.clock().sleep_until(wake).await;
+        let mut notifications = Vec::new();
+        for event in catcher.tick().await {
+            match event.kind {
+                CatchEventKind::Polled { error: Some(e), .. } => {
+                    eprintln!("{}: {}", event.domain, e);
+                }
+                CatchEventKind::Polled { .. } => {}
+                CatchEventKind::Caught { via, .. } => {
+                    println!("{}: available ({})", event.domain, label(&via));
+                    notifications.push(
+                        Notification::new(
+                            NotificationKind::Custom,
+                            Severity::Critical,
+                            format!("{} dropped and is available", event.domain),
+                        )
+                        .with_domain(event.domain.as_str())
+                        .with_field("via", label(&via)),
+                    );
+                }
+                CatchEventKind::Missed { attempts } => {
+                    println!(
+                        "{}: still registered after the drop window ({} checks)",
+                        event.domain, attempts
+                    );
+                }
+                CatchEventKind::Alert => println!("{}: drop window open", event.domain),
+            }
+        }
+        send_notifications(&profile, notifications).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use clap::Args;
+use std::fs;
+
+#[derive(Args, Debug)]
+pub(crate) struct ConfigArgs {
+    /// Path to settings.json
+    #[arg(short, long, default_value = "settings.json")]
+    path: String,
+    /// Set a value (e.g. lookupGeneral.concurrency=10)
+    #[arg(short, long)]
+    set: Option<String>,
+    /// Get a value
+    #[arg(short, long)]
+    get: Option<String>,
+}
+
+pub(crate) fn run(args: ConfigArgs) -> anyhow::Result<()> {
+    let ConfigArgs { path, set, get } = args;
+    let content = fs::read_to_string(&path).unwrap_or_else(|_| "{}".to_string());
+    let mut settings: serde_json::Value = serde_json::from_str(&content)?;
+
+    if let Some(key) = get {
+        let ptr = format!("/{}", key.replace('.', "/"));
+        let val = settings.pointer(&ptr).unwrap_or(&serde_json::Value::Null);
+        println!("{}: {}", key, val);
+    } else if let Some(kv_str) = set {
+        if let Some((k, v)) = kv_str.split_once('=') {
+            settings[k] = serde_json::Value::String(v.to_string());
+            fs::write(path, serde_json::to_string_pretty(&settings)?)?;
+            println!("Updated {}={}", k, v);
+        }
+    } else {
+        println!("{}", serde_json::to_string_pretty(&settings)?);
+    }
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use clap::Args;
+use indicatif::{ProgressBar, ProgressStyle};
+use std::fs;
+use whoisdigger::proxy::Permission;
+use whoisdigger::{discovery, LookupSettings};
+
+use crate::audit::require_permission;
+use crate::split_list;
+
+#[derive(Args, Debug)]
+pub(crate) struct DiscoverArgs {
+    /// Keywords to combine (comma separated)
+    #[arg(short, long)]
+    words: String,
+    /// Prefixes to prepend (comma separated)
+    #[arg(long, default_value = "")]
+    prefixes: String,
+    /// Suffixes to append (comma separated)
+    #[arg(long, default_value = "")]
+    suffixes: String,
+    /// TLDs to try (comma separated)
+    #[arg(short, long, default_value = "com")]
+    tlds: String,
+    /// Stop after this many available domains
+    #[arg(short = 'n', long, default_value_t = 10)]
+    target: usize,
+    /// Maximum candidates to check before giving up
+    #[arg(long)]
+    max_checks: Option<usize>,
+    /// Maximum label length
+    #[arg(long, default_value_t = 63)]
+    max_length: usize,
+    /// Minimum brandability score (0.0 - 1.0)
+    #[arg(long)]
+    min_score: Option<f64>,
+    /// Number of concurrent checks
+    #[arg(short, long, default_value_t = 4)]
+    concurrency: usize,
+    /// WHOIS timeout in milliseconds
+    #[arg(long, default_value_t = 5000)]
+    timeout: u64,
+    /// Trust the DNS pre-screen and skip WHOIS confirmation
+    #[arg(long)]
+    dns_only: bool,
+    /// Write the full report as JSON
+    #[arg(short, long)]
+    output: Option<String>,
+}
+
+pub(crate) async fn run(args: DiscoverArgs) -> anyhow::Result<()> {
+    let DiscoverArgs {
+        words,
+        prefixes,
+        suffixes,
+        tlds,
+        target,
+        max_checks,
+        max_length,
+        min_score,
+        concurrency,
+        timeout,
+        dns_only,
+        output,
+    } = args;
+    require_permission(None, Permission::BulkRun)?;
+    let config = discovery::DiscoveryConfig {
+        combinator: whoisdigger::intelligence::domgen::CombinatorConfig {
+            words: split_list(&words),
+            prefixes: split_list(&prefixes),
+            suffixes: split_list(&suffixes),
+            tlds: split_list(&tlds),
+            ..Default::default()
+        },
+        filter: whoisdigger::intelligence::domgen::FilterConfig {
+            max_length,
+            ..Default::default()
+        },
+        target,
+        max_checks,
+        concurrency,
+        min_score,
+        confirm_with_whois: !dns_only,
+    };
+    process_discover(config, timeout, output.as_deref()).await
+}
+
+async fn process_discover(
+    config: discovery::DiscoveryConfig,
+    timeout: u64,
+    output: Option<&str>,
+) -> anyhow::Result<()> {
+    if config.combinator.words.is_empty() {
+        anyhow::bail!("at least one keyword is required");
+    }
+    println!(
+        "Discovering up to {} available domains (concurrency: {}, {})...",
+        config.target,
+        config.concurrency,
+        if config.confirm_with_whois {
+            "DNS + WHOIS"
+        } else {
+            "DNS only"
+        }
+    );
+
+    let pb = ProgressBar::new(config.target as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template(
+                "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} found {msg}",
+            )?
+            .progress_chars("#>- "),
+    );
+
+    let checker = discovery::LookupChecker::new(LookupSettings::default()).with_timeout(timeout);
+    let discovery = discovery::Discovery::new(config, checker)
+        .with_scorer(whoisdigger::intelligence::domgen::DomainScorer::default());
+    let report = discovery
+        .run(|progress, event| {
+            pb.set_position(progress.found as u64);
+            pb.set_message(format!("({} checked) {}", progress.checked, event.domain));
+        })
+        .await;
+    pb.finish_and_clear();
+
+    println!(
+        "Checked {} of {} generated candidates ({} skipped, {} resolved in DNS); stopped: {:?}",
+        report.progress.checked,
+        report.progress.generated,
+        report.progress.skipped,
+        report.progress.resolved,
+        report.stop_reason
+    );
+    for found in &report.found {
+        println!(
+            "{:<40} {:.2}",
+            found.domain,
+            found.score.unwrap_or_default()
+        );
+    }
+    if let Some(path) = output {
+        fs::write(path, serde_json::to_string_pretty(&report)?)?;
+        println!("Report written to {}", path);
+    }
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use clap::Args;
+use std::fs;
+use std::io::Write;
+use whoisdigger::proxy::Permission;
+use whoisdigger::storage::audit::Action;
+
+use crate::audit::{cli_event, record, require_permission};
+use crate::results::{
+    open_output, parse_status_filter, read_results, status_matches, write_results, OutputFormat,
+};
+
+#[derive(Args, Debug)]
+pub(crate) struct ExportArgs {
+    /// Path to input JSON or JSONL results
+    #[arg(short, long)]
+    input: String,
+    /// Output path (writes stdout when omitted)
+    #[arg(short, long)]
+    output: Option<String>,
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Csv)]
+    format: OutputFormat,
+    /// Only keep results with these statuses (comma separated; "error"
+    /// matches every error)
+    #[arg(short, long)]
+    status: Option<String>,
+}
+
+pub(crate) fn run(args: ExportArgs) -> anyhow::Result<()> {
+    let ExportArgs {
+        input,
+        output,
+        format,
+        status,
+    } = args;
+    require_permission(None, Permission::Export)?;
+    let mut results = read_results(&fs::read_to_string(&input)?)?;
+    if let Some(filter) = status.as_deref().map(parse_status_filter).transpose()? {
+        results.retain(|r| status_matches(&filter, &r.status));
+    }
+    let mut out = open_output(output.as_deref())?;
+    write_results(&results, format, &mut out)?;
+    out.flush()?;
+    if let Some(path) = output {
+        record(
+            None,
+            cli_event(Action::ResultsExported, &path)
+                .with_detail("results", results.len())
+                .with_detail("format", format!("{:?}", format).to_lowercase()),
+        );
+        eprintln!("Exported {} results to {}.", results.len(), path);
+    }
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use clap::Args;
+use std::io::{BufWriter, Write};
+use whoisdigger::intelligence::domgen::{DomainScorer, GeneratorConfig, GeneratorEngine};
+
+use crate::split_list;
+
+#[derive(Args, Debug)]
+pub(crate) struct GenerateArgs {
+    /// Keywords the names should convey
+    #[arg(required = true)]
+    keywords: Vec<String>,
+    /// TLDs to generate under (comma separated)
+    #[arg(short, long, default_value = "com")]
+    tlds: String,
+    /// Industry or theme, e.g. tech, food or finance
+    #[arg(long)]
+    industry: Option<String>,
+    /// Maximum number of suggestions
+    #[arg(short = 'n', long, default_value_t = 100)]
+    limit: usize,
+    /// Rank by pronounceability and brandability, best first
+    #[arg(long)]
+    rank: bool,
+    /// Print the suggestions with their source and score as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+pub(crate) fn run(args: GenerateArgs) -> anyhow::Result<()> {
+    let GenerateArgs {
+        keywords,
+        tlds,
+        industry,
+        limit,
+        rank,
+        json,
+    } = args;
+    let engine = GeneratorEngine::new(GeneratorConfig {
+        keywords,
+        tlds: split_list(&tlds),
+        industry,
+        max_results: limit,
+        ..GeneratorConfig::default()
+    });
+    let mut out = BufWriter::new(std::io::stdout());
+    if rank {
+        let ranked = engine.generate_ranked(&DomainScorer::default());
+        if json {
+            serde_json::to_writer_pretty(&mut out, &ranked)?;
+            writeln!(out)?;
+        } else {
+            for r in &ranked {
+                writeln!(out, "{}", r.domain.domain)?;
+            }
+        }
+    } else {
+        let generated = engine.generate();
+        if json {
+            serde_json::to_writer_pretty(&mut out, &generated)?;
+            writeln!(out)?;
+        } else {
+            for g in &generated {
+                writeln!(out, "{}", g.domain)?;
+            }
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use clap::Args;
+use whoisdigger::db_history_get;
+
+#[derive(Args, Debug)]
+pub(crate) struct HistoryArgs {
+    /// Path to history database
+    #[arg(short, long, default_value = "history-default.sqlite")]
+    path: String,
+    /// Number of entries to show
+    #[arg(short, long, default_value_t = 20)]
+    limit: u32,
+}
+
+pub(crate) fn run(args: HistoryArgs) -> anyhow::Result<()> {
+    let HistoryArgs { path, limit } = args;
+    let entries = db_history_get(&path, limit).map_err(|e| anyhow::anyhow!(e))?;
+    println!("{:<30} | {:<15} | {:<20}", "Domain", "Status", "Timestamp");
+    println!("{:-<30}-|-{:-<15}-|-{:-<20}", "", "", "");
+    for e in entries {
+        println!("{:<30} | {:<15} | {:<20}", e.domain, e.status, e.timestamp);
+    }
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use clap::{Args, ValueEnum};
+use std::io::Write;
+use whoisdigger::automation::{
+    expiry::{ExpiryStore, WatchEntry},
+    import::{parse_import, DomainValidator, ImportFormat},
+    scheduler::{Job, SchedulerStore},
+};
+use whoisdigger::proxy::Permission;
+use whoisdigger::storage::audit::Action;
+
+use crate::audit::{cli_event, record, require_permission};
+use crate::job::parse_schedule;
+use crate::profile::{open_profile, ProfileArgs};
+use crate::results::{open_input, open_output};
+
+/// Input formats understood by `import`.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub(crate) enum ImportKind {
+    Auto,
+    Lines,
+    Csv,
+    Json,
+    Zone,
+    Ct,
+    Spaces,
+    Commas,
+}
+
+#[derive(Args, Debug)]
+pub(crate) struct ImportArgs {
+    /// File to import ("-" reads stdin)
+    input: String,
+    /// Input format
+    #[arg(short, long, value_enum, default_value_t = ImportKind::Auto)]
+    format: ImportKind,
+    /// CSV column holding the domain (0-based)
+    #[arg(long, default_value_t = 0)]
+    column: usize,
+    /// JSON key holding the domain, for arrays of objects
+    #[arg(long)]
+    key: Option<String>,
+    /// Validate labels and TLDs strictly
+    #[arg(long)]
+    strict: bool,
+    /// Write the domains to this file
+    #[arg(short, long)]
+    output: Option<String>,
+    /// Add the domains to the profile's watchlist
+    #[arg(long)]
+    watch: bool,
+    /// Create a scheduled job with this name over the domains
+    #[arg(long)]
+    job: Option<String>,
+    /// Schedule for --job: once, 30m, 6h, daily@3 or cron:<expr>
+    #[arg(long, default_value = "daily@3")]
+    schedule: String,
+    #[command(flatten)]
+    profile: ProfileArgs,
+}
+
+pub(crate) fn run(args: ImportArgs) -> anyhow::Result<()> {
+    let ImportArgs {
+        input,
+        format,
+        column,
+        key,
+        strict,
+        output,
+        watch,
+        job,
+        schedule,
+        profile,
+    } = args;
+    let schedule = job
+        .as_ref()
+        .map(|_| parse_schedule(&schedule))
+        .transpose()?;
+    let domains = import_domains(&input, import_format(format, column, key), strict)?;
+    if output.is_some() || (!watch && job.is_none()) {
+        let mut out = open_output(output.as_deref())?;
+        for d in &domains {
+            writeln!(out, "{}", d)?;
+        }
+        out.flush()?;
+    }
+
+    if watch || job.is_some() {
+        let (data_dir, profile) = open_profile(profile)?;
+        if watch {
+            require_permission(Some(&data_dir), Permission::WatchlistManage)?;
+        }
+        if job.is_some() {
+            require_permission(Some(&data_dir), Permission::SchedulerManage)?;
+        }
+        if watch {
+            let store = ExpiryStore::open(&profile.watchlist().to_string_lossy())?;
+            let watchlist = store.load()?;
+            let mut added = 0;
+            for domain in domains.iter().filter(|d| watchlist.get(d).is_none()) {
+                store.upsert(&WatchEntry::new(domain.as_str()))?;
+                added += 1;
+            }
+            record(
+                Some(&data_dir),
+                cli_event(Action::WatchlistChanged, &input)
+                    .with_detail("change", "import")
+                    .with_detail("domains", added),
+            );
+            eprintln!(
+                "Added {} domains to the watchlist of '{}'.",
+                added, profile.name
+            );
+        }
+        if let (Some(name), Some(schedule)) = (job, schedule) {
+            let job = Job::new(name, domains, schedule);
+            SchedulerStore::open(&profile.scheduler().to_string_lossy())?.save(&job)?;
+            record(
+                Some(&data_dir),
+                cli_event(Action::SchedulerChanged, &job.id).with_detail("change", "create"),
+            );
+            eprintln!(
+                "Created job {} over {} domains ({}).",
+                job.id,
+                job.domains.len(),
+                job.schedule.describe()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn import_format(kind: ImportKind, column: usize, key: Option<String>) -> ImportFormat {
+    match (kind, key) {
+        (ImportKind::Auto, _) => ImportFormat::Auto,
+        (ImportKind::Lines, _) => ImportFormat::NewlineDelimited,
+        (ImportKind::Csv, _) => ImportFormat::Csv {
+            domain_column: column,
+        },
+        (ImportKind::Json, Some(domain_key)) => ImportFormat::JsonObjects { domain_key },
+        (ImportKind::Json, None) => ImportFormat::JsonArray,
+        (ImportKind::Zone, _) => ImportFormat::ZoneFile,
+        (ImportKind::Ct, _) => ImportFormat::CtLog,
+        (ImportKind::Spaces, _) => ImportFormat::SpaceSeparated,
+        (ImportKind::Commas, _) => ImportFormat::CommaSeparated,
+    }
+}
+
+/// Parse and validate the domains in `path` ("-" for stdin), printing the
+/// import statistics to stderr.
+pub(crate) fn import_domains(
+    path: &str,
+    format: ImportFormat,
+    strict: bool,
+) -> anyhow::Result<Vec<String>> {
+    use std::io::Read;
+
+    let mut content = String::new();
+    open_input(path)?.read_to_string(&mut content)?;
+    let imported = parse_import(&content, &format);
+    let validator = match strict {
+        true => DomainValidator::strict(),
+        false => DomainValidator::default(),
+    };
+    let (domains, removed) = validator.filter(imported.domains);
+    eprintln!("{}", imported.stats.merge(removed).summary());
+    Ok(domains)
+}
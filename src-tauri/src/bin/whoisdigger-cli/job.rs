@@ -0,0 +1,313 @@
+use clap::Subcommand;
+use std::io::Write;
+use whoisdigger::automation::{
+    import::ImportFormat,
+    notify::Notification,
+    scheduler::{
+        runner::{diff_runs, DomainRunResult},
+        Job, RunRecord, Schedule, ScheduleKind, SchedulerStore,
+    },
+};
+use whoisdigger::proxy::Permission;
+use whoisdigger::storage::audit::Action;
+use whoisdigger::{availability::DomainStatus, bulk::BulkResult, db_history_add};
+
+use crate::audit::{cli_event, record, require_permission};
+use crate::import::import_domains;
+use crate::label;
+use crate::lookup::{process_lookup, LookupType};
+use crate::notify::send_notifications;
+use crate::profile::{cli_lookup_settings, open_profile, ProfileArgs};
+use crate::results::{open_output, write_results, OutputFormat};
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum JobCommand {
+    /// Create a recurring scan over a list of domains
+    Create {
+        /// Job name
+        name: String,
+        /// Domain to scan (repeatable)
+        #[arg(short, long)]
+        domain: Vec<String>,
+        /// File of domains in any import format ("-" reads stdin)
+        #[arg(short, long)]
+        input: Option<String>,
+        /// When to run: once, 30m, 6h, daily@3 or cron:<expr>
+        #[arg(short, long, default_value = "daily@3")]
+        schedule: String,
+        /// What the job is for
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// List jobs
+    List {
+        /// Print the jobs as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Run a job now, or every job that is due when none is named
+    ///
+    /// Results are saved to the profile's history. Exits 3 when some lookups
+    /// failed.
+    Run {
+        /// Job id or name
+        job: Option<String>,
+        /// Number of concurrent lookups
+        #[arg(short, long, default_value_t = 5)]
+        concurrency: usize,
+        /// Timeout in milliseconds (defaults to the app's setting)
+        #[arg(long)]
+        timeout: Option<u64>,
+        /// Output format for the results
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        /// Write the results to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Stop a job from running until it is resumed
+    Pause {
+        /// Job id or name
+        job: String,
+    },
+    /// Resume a paused job
+    Resume {
+        /// Job id or name
+        job: String,
+    },
+    /// Delete a job
+    Delete {
+        /// Job id or name
+        job: String,
+    },
+}
+
+/// Run a `job` subcommand on the profile's scheduler. Returns `false` when
+/// some lookups failed.
+pub(crate) async fn run(command: JobCommand, profile: ProfileArgs) -> anyhow::Result<bool> {
+    let (data_dir, profile) = open_profile(profile)?;
+    let permission = match &command {
+        JobCommand::List { .. } => Permission::SchedulerRead,
+        _ => Permission::SchedulerManage,
+    };
+    require_permission(Some(&data_dir), permission)?;
+    let store = SchedulerStore::open(&profile.scheduler().to_string_lossy())?;
+    let changed = |id: &str, change: &str| {
+        record(
+            Some(&data_dir),
+            cli_event(Action::SchedulerChanged, id).with_detail("change", change),
+        )
+    };
+    match command {
+        JobCommand::Create {
+            name,
+            domain,
+            input,
+            schedule,
+            description,
+        } => {
+            let schedule = parse_schedule(&schedule)?;
+            let mut domains = whoisdigger::bulk::expand_domains(&domain, &[]);
+            if let Some(path) = &input {
+                domains.extend(import_domains(path, ImportFormat::Auto, false)?);
+            }
+            if domains.is_empty() {
+                anyhow::bail!("a job needs domains: pass --domain or --input");
+            }
+            let mut job = Job::new(name, domains, schedule);
+            job.description = description;
+            store.save(&job)?;
+            changed(&job.id, "create");
+            println!(
+                "Created job {} over {} domains ({}).",
+                job.id,
+                job.domains.len(),
+                job.schedule.describe()
+            );
+        }
+        JobCommand::List { json } => {
+            let jobs = store.get_all()?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&jobs)?);
+                return Ok(true);
+            }
+            println!(
+                "{:<18} | {:<20} | {:<9} | {:<24} | {:>7} | {:<16} | {:>4}",
+                "Id", "Name", "Status", "Schedule", "Domains", "Next run", "Runs"
+            );
+            println!(
+                "{:-<18}-|-{:-<20}-|-{:-<9}-|-{:-<24}-|-{:->7}-|-{:-<16}-|-{:->4}",
+                "", "", "", "", "", "", ""
+            );
+            for job in &jobs {
+                println!(
+                    "{:<18} | {:<20} | {:<9} | {:<24} | {:>7} | {:<16} | {:>4}",
+                    job.id,
+                    job.name,
+                    label(&job.status),
+                    job.schedule.describe(),
+                    job.domains.len(),
+                    job.next_run
+                        .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+                        .unwrap_or_else(|| "-".into()),
+                    job.run_count
+                );
+            }
+        }
+        JobCommand::Run {
+            job,
+            concurrency,
+            timeout,
+            format,
+            output,
+        } => {
+            let jobs = match job {
+                Some(job) => vec![find_job(&store, &job)?],
+                None => store
+                    .get_active()?
+                    .into_iter()
+                    .filter(Job::is_due)
+                    .collect(),
+            };
+            if jobs.is_empty() {
+                eprintln!("No jobs are due.");
+                return Ok(true);
+            }
+            let history = profile.history().to_string_lossy().into_owned();
+            let mut out = open_output(output.as_deref())?;
+            let mut ok = true;
+            let mut notifications = Vec::new();
+            for mut job in jobs {
+                record(
+                    Some(&data_dir),
+                    cli_event(Action::BulkStarted, &job.id)
+                        .with_detail("job", job.name.as_str())
+                        .with_detail("domains", job.domains.len())
+                        .with_detail("concurrency", concurrency),
+                );
+                let started_at = chrono::Utc::now();
+                let results = process_lookup(
+                    job.domains.clone(),
+                    concurrency,
+                    cli_lookup_settings(&data_dir, timeout),
+                    &LookupType::Whois,
+                )
+                .await?;
+                for r in &results {
+                    db_history_add(&history, &r.domain, &r.status).map_err(anyhow::Error::msg)?;
+                }
+                let outcomes = results
+                    .iter()
+                    .map(|r| (r.domain.clone(), run_outcome(r)))
+                    .collect();
+                let run = RunRecord::completed(&job.id, job.run_count + 1, started_at, outcomes);
+                if let Some(previous) = store.last_run(&job.id)? {
+                    notifications.extend(Notification::from_run_diff(&diff_runs(&previous, &run)));
+                }
+                store.save_run(&run)?;
+                if run.success {
+                    job.record_success();
+                } else {
+                    job.record_failure();
+                    ok = false;
+                }
+                store.save(&job)?;
+                write_results(&results, format, &mut out)?;
+                eprintln!(
+                    "Job '{}' run {}: {}/{} domains succeeded in {} ms.",
+                    job.name,
+                    run.run_number,
+                    run.domains_succeeded,
+                    run.domains_queried,
+                    run.duration_ms
+                );
+            }
+            out.flush()?;
+            send_notifications(&profile, notifications).await?;
+            return Ok(ok);
+        }
+        JobCommand::Pause { job } => {
+            let mut job = find_job(&store, &job)?;
+            job.pause();
+            store.save(&job)?;
+            changed(&job.id, "pause");
+            println!("Paused job '{}'.", job.name);
+        }
+        JobCommand::Resume { job } => {
+            let mut job = find_job(&store, &job)?;
+            job.resume();
+            store.save(&job)?;
+            changed(&job.id, "resume");
+            println!("Resumed job '{}'.", job.name);
+        }
+        JobCommand::Delete { job } => {
+            let job = find_job(&store, &job)?;
+            store.delete(&job.id)?;
+            changed(&job.id, "delete");
+            println!("Deleted job '{}'.", job.name);
+        }
+    }
+    Ok(true)
+}
+
+/// A job by id, or by name when no id matches.
+fn find_job(store: &SchedulerStore, job: &str) -> anyhow::Result<Job> {
+    if let Some(found) = store.get(job)? {
+        return Ok(found);
+    }
+    let mut named: Vec<Job> = store
+        .get_all()?
+        .into_iter()
+        .filter(|j| j.name == job)
+        .collect();
+    match named.len() {
+        0 => anyhow::bail!("no job with id or name '{}'", job),
+        1 => Ok(named.remove(0)),
+        n => anyhow::bail!("{} jobs are named '{}'; use the id", n, job),
+    }
+}
+
+/// How a scheduled run records one lookup.
+fn run_outcome(result: &BulkResult) -> DomainRunResult {
+    match (&result.error, result.status.as_str()) {
+        (Some(e), _) if e.starts_with("Timeout") => DomainRunResult::Timeout,
+        (Some(e), _) => DomainRunResult::Error(e.clone()),
+        (None, status) if status == DomainStatus::ErrorRateLimiting.as_str() => {
+            DomainRunResult::RateLimited
+        }
+        _ => DomainRunResult::Success,
+    }
+}
+
+/// Parse `once`, `30m`, `6h`, `daily@3` or `cron:<expr>`.
+pub(crate) fn parse_schedule(s: &str) -> anyhow::Result<Schedule> {
+    let s = s.trim();
+    let number = |n: &str| {
+        n.parse::<u32>()
+            .ok()
+            .filter(|n| *n > 0)
+            .ok_or_else(|| anyhow::anyhow!("invalid schedule '{}'", s))
+    };
+    if s == "once" {
+        Ok(Schedule::once())
+    } else if let Some(expr) = s.strip_prefix("cron:") {
+        Ok(Schedule {
+            kind: ScheduleKind::Cron(expr.trim().to_string()),
+            enabled: true,
+        })
+    } else if let Some(hour) = s.strip_prefix("daily@") {
+        match hour.parse::<u32>() {
+            Ok(hour) if hour < 24 => Ok(Schedule::daily_at(hour)),
+            _ => anyhow::bail!("invalid schedule '{}': the hour must be 0-23", s),
+        }
+    } else if let Some(n) = s.strip_suffix('m') {
+        Ok(Schedule::every_minutes(number(n)?))
+    } else if let Some(n) = s.strip_suffix('h') {
+        Ok(Schedule::every_hours(number(n)?))
+    } else {
+        anyhow::bail!(
+            "invalid schedule '{}': use once, 30m, 6h, daily@3 or cron:<expr>",
+            s
+        )
+    }
+}
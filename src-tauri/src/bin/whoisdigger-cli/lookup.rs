@@ -0,0 +1,233 @@
+use clap::{Args, ValueEnum};
+use indicatif::{ProgressBar, ProgressStyle};
+use std::io::{IsTerminal, Write};
+use std::time::Duration;
+use whoisdigger::proxy::Permission;
+use whoisdigger::storage::audit::Action;
+use whoisdigger::{
+    bulk::{classify_dns, classify_rdap, dns, rdap, whois, BulkControl, BulkResult, BulkRunner},
+    db_history_add, get_timeout, LookupSettings,
+};
+
+use crate::audit::{cli_event, record, require_permission};
+use crate::profile::{cli_lookup_settings, open_profile, resolve_data_dir, ProfileArgs};
+use crate::results::{
+    open_input, open_output, parse_status_filter, read_domains, status_matches, write_results,
+    OutputFormat,
+};
+use crate::{split_list, EXIT_LOOKUP_ERRORS, EXIT_NO_MATCH};
+
+#[derive(ValueEnum, Clone, Debug)]
+pub(crate) enum LookupType {
+    Whois,
+    Dns,
+    Rdap,
+}
+
+#[derive(Args, Debug)]
+pub(crate) struct LookupArgs {
+    /// Domain to look up (repeatable)
+    #[arg(short, long)]
+    domain: Vec<String>,
+
+    /// Wordlist file path for bulk lookup ("-" reads stdin, as does
+    /// giving no domains at all)
+    #[arg(short, long)]
+    wordlist: Option<String>,
+
+    /// TLDs to append to each entry's first label (comma separated);
+    /// entries are looked up as given when omitted
+    #[arg(short, long)]
+    tlds: Option<String>,
+
+    /// Number of concurrent lookups
+    #[arg(short, long, default_value_t = 5)]
+    concurrency: usize,
+
+    /// Timeout in milliseconds
+    #[arg(long, default_value_t = 5000)]
+    timeout: u64,
+
+    /// Type of lookup to perform
+    #[arg(short, long, value_enum, default_value_t = LookupType::Whois)]
+    lookup_type: LookupType,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+
+    /// Write results to this file instead of stdout
+    #[arg(short, long)]
+    output: Option<String>,
+
+    /// Only keep results with these statuses (comma separated; "error"
+    /// matches every error)
+    #[arg(short, long)]
+    status: Option<String>,
+
+    /// Save results to this history database, or to the desktop app's
+    /// current profile when no path is given
+    #[arg(long, num_args = 0..=1, default_missing_value = "")]
+    history: Option<String>,
+
+    /// App data directory holding the lookup settings and, for --history
+    /// without a path, the profiles
+    #[arg(long)]
+    data_dir: Option<String>,
+}
+
+pub(crate) async fn run(args: LookupArgs) -> anyhow::Result<()> {
+    let LookupArgs {
+        domain,
+        wordlist,
+        tlds,
+        concurrency,
+        timeout,
+        lookup_type,
+        format,
+        output,
+        status,
+        history,
+        data_dir,
+    } = args;
+    let filter = status.as_deref().map(parse_status_filter).transpose()?;
+    let single = domain.len() == 1 && wordlist.is_none();
+    let app_dir = resolve_data_dir(data_dir.clone())?;
+    require_permission(
+        Some(&app_dir),
+        match single {
+            true => Permission::Lookup,
+            false => Permission::BulkRun,
+        },
+    )?;
+    let mut domains = domain;
+    match wordlist.as_deref() {
+        Some(path) => domains.extend(read_domains(open_input(path)?)?),
+        None if domains.is_empty() => {
+            if std::io::stdin().is_terminal() {
+                anyhow::bail!(
+                    "nothing to look up: pass --domain, --wordlist or pipe domains on stdin"
+                );
+            }
+            domains.extend(read_domains(std::io::stdin().lock())?);
+        }
+        None => {}
+    }
+    let tlds = tlds.as_deref().map(split_list).unwrap_or_default();
+    let domains = whoisdigger::bulk::expand_domains(&domains, &tlds);
+    if !single {
+        record(
+            None,
+            cli_event(Action::BulkStarted, wordlist.as_deref().unwrap_or("stdin"))
+                .with_detail("domains", domains.len())
+                .with_detail("concurrency", concurrency),
+        );
+    }
+
+    let settings = cli_lookup_settings(&app_dir, Some(timeout));
+    let results = process_lookup(domains, concurrency, settings, &lookup_type).await?;
+    if let Some(path) = history {
+        let path = history_path(path, data_dir)?;
+        for r in &results {
+            db_history_add(&path, &r.domain, &r.status).map_err(anyhow::Error::msg)?;
+        }
+        eprintln!("Saved {} results to {}.", results.len(), path);
+    }
+
+    let failed = results.iter().any(|r| r.error.is_some());
+    let results: Vec<BulkResult> = match &filter {
+        Some(f) => results
+            .into_iter()
+            .filter(|r| status_matches(f, &r.status))
+            .collect(),
+        None => results,
+    };
+    if single && format == OutputFormat::Table && output.is_none() {
+        for r in &results {
+            println!("{}: {}", r.domain, r.status);
+            match (&r.data, &r.error) {
+                (Some(data), _) => println!("---\n{}\n---", data),
+                (None, Some(e)) => eprintln!("Error: {}", e),
+                (None, None) => {}
+            }
+        }
+    } else {
+        let mut out = open_output(output.as_deref())?;
+        write_results(&results, format, &mut out)?;
+        out.flush()?;
+    }
+
+    if failed {
+        std::process::exit(EXIT_LOOKUP_ERRORS);
+    }
+    if filter.is_some() && results.is_empty() {
+        std::process::exit(EXIT_NO_MATCH);
+    }
+    Ok(())
+}
+
+/// Look `domains` up with the bulk engine the desktop app uses. Ctrl-C
+/// stops starting new lookups; the rest report as stopped.
+pub(crate) async fn process_lookup(
+    domains: Vec<String>,
+    concurrency: usize,
+    settings: LookupSettings,
+    lookup_type: &LookupType,
+) -> anyhow::Result<Vec<BulkResult>> {
+    let timeout = get_timeout(&settings);
+    let runner = match lookup_type {
+        LookupType::Whois => BulkRunner::new(whois(settings)),
+        LookupType::Dns => BulkRunner::new(dns()).with_classifier(classify_dns),
+        LookupType::Rdap => BulkRunner::new(rdap()).with_classifier(classify_rdap),
+    }
+    .with_concurrency(concurrency)
+    .with_timeout(Some(Duration::from_millis(timeout)))
+    .with_control(BulkControl::new());
+
+    let control = runner.control().clone();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            control.stop();
+        }
+    });
+
+    if domains.len() > 1 {
+        eprintln!(
+            "Starting bulk lookup for {} domains (concurrency: {}, type: {:?})...",
+            domains.len(),
+            concurrency,
+            lookup_type
+        );
+    }
+    let pb = ProgressBar::new(domains.len() as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template(
+                "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})",
+            )?
+            .progress_chars("#>- "),
+    );
+    if domains.len() <= 1 {
+        pb.set_draw_target(indicatif::ProgressDrawTarget::hidden());
+    }
+    let progress = pb.clone();
+    let results = runner
+        .run(domains, move |_, done, _| {
+            progress.set_position(done as u64)
+        })
+        .await;
+    pb.finish_and_clear();
+    Ok(results)
+}
+
+/// `--history` with a path, or the current profile's history database.
+fn history_path(path: String, data_dir: Option<String>) -> anyhow::Result<String> {
+    if !path.is_empty() {
+        return Ok(path);
+    }
+    let (_, profile) = open_profile(ProfileArgs {
+        data_dir,
+        profile: None,
+    })?;
+    Ok(profile.history().to_string_lossy().into_owned())
+}
@@ -0,0 +1,245 @@
+use clap::{Parser, Subcommand};
+
+#[cfg(feature = "domain-agentic")]
+mod agent;
+#[cfg(feature = "api-server")]
+mod api_key;
+mod audit;
+mod cache;
+#[cfg(feature = "domain-automation")]
+mod calendar;
+#[cfg(feature = "domain-automation")]
+mod catch;
+mod config;
+#[cfg(feature = "domain-intelligence")]
+mod discover;
+mod export;
+#[cfg(feature = "domain-intelligence")]
+mod generate;
+mod history;
+#[cfg(feature = "domain-automation")]
+mod import;
+#[cfg(feature = "domain-automation")]
+mod job;
+mod lookup;
+#[cfg(feature = "domain-agentic")]
+mod memory;
+#[cfg(feature = "domain-automation")]
+mod notify;
+mod profile;
+mod results;
+#[cfg(feature = "domain-agentic")]
+mod search;
+#[cfg(feature = "api-server")]
+mod serve;
+#[cfg(feature = "domain-intelligence")]
+mod threat;
+#[cfg(feature = "domain-automation")]
+mod watch;
+mod wordlist;
+
+/// Exit status when some lookups failed or were stopped.
+const EXIT_LOOKUP_ERRORS: i32 = 3;
+/// Exit status when no result matched `--status`.
+const EXIT_NO_MATCH: i32 = 4;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Look up domains given as arguments, in a wordlist or on stdin
+    ///
+    /// Exits 0 on success, 1 on failure, 3 when some lookups failed or were
+    /// stopped, and 4 when no result matched --status.
+    Lookup(lookup::LookupArgs),
+    /// View lookup history
+    History(history::HistoryArgs),
+    /// Manage request cache
+    Cache(cache::CacheArgs),
+    /// Convert saved lookup results (a JSON array or JSONL) to another format
+    Export(export::ExportArgs),
+    /// Manage configuration
+    Config(config::ConfigArgs),
+    /// Run a saved wordlist recipe over a file or stdin
+    Wordlist(wordlist::WordlistArgs),
+    /// Generate candidate names and check them until enough are available
+    #[cfg(feature = "domain-intelligence")]
+    Discover(discover::DiscoverArgs),
+    /// Ask the agent a question or run a pre-built pipeline with the
+    /// built-in tools over the profile's data
+    #[cfg(feature = "domain-agentic")]
+    Agent {
+        #[command(subcommand)]
+        command: agent::AgentCommand,
+        #[command(flatten)]
+        llm: agent::AgentArgs,
+        #[command(flatten)]
+        profile: profile::ProfileArgs,
+    },
+    /// Inspect and prune the agent's long-term memory
+    #[cfg(feature = "domain-agentic")]
+    Memory(memory::MemoryArgs),
+    /// Search stored WHOIS snapshots and chat sessions
+    #[cfg(feature = "domain-agentic")]
+    Search(search::SearchArgs),
+    /// Write watchlist and portfolio expiry dates to an iCalendar file
+    #[cfg(feature = "domain-automation")]
+    Calendar(calendar::CalendarArgs),
+    /// Manage and check the profile's watchlist
+    #[cfg(feature = "domain-automation")]
+    Watch {
+        #[command(subcommand)]
+        command: watch::WatchCommand,
+        #[command(flatten)]
+        profile: profile::ProfileArgs,
+    },
+    /// Manage and run the profile's scheduled scan jobs
+    #[cfg(feature = "domain-automation")]
+    Job {
+        #[command(subcommand)]
+        command: job::JobCommand,
+        #[command(flatten)]
+        profile: profile::ProfileArgs,
+    },
+    /// Poll watched domains through their drop window until they become
+    /// available or the window closes
+    ///
+    /// A domain counts as available once DNS has no delegation and both
+    /// RDAP and WHOIS agree; catches go to the profile's notifier.
+    #[cfg(feature = "domain-automation")]
+    Catch(catch::CatchArgs),
+    /// Assess domains for phishing, squatting and other threat indicators
+    #[cfg(feature = "domain-intelligence")]
+    Threat {
+        #[command(subcommand)]
+        command: threat::ThreatCommand,
+        #[command(flatten)]
+        profile: profile::ProfileArgs,
+    },
+    /// Generate domain name suggestions from keywords, one per line
+    #[cfg(feature = "domain-intelligence")]
+    Generate(generate::GenerateArgs),
+    /// Extract domains from zone files, CT logs, CSV, JSON or plain lists
+    #[cfg(feature = "domain-automation")]
+    Import(import::ImportArgs),
+    /// Query, export and verify the audit log of user, API and agent actions
+    Audit(audit::AuditArgs),
+    /// Serve the REST API over a profile's data
+    #[cfg(feature = "api-server")]
+    Serve(serve::ServeArgs),
+    /// Manage the API keys accepted by `serve`
+    #[cfg(feature = "api-server")]
+    ApiKey(api_key::ApiKeyArgs),
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Commands::Lookup(args) => lookup::run(args).await?,
+        Commands::History(args) => history::run(args)?,
+        Commands::Cache(args) => cache::run(args)?,
+        Commands::Export(args) => export::run(args)?,
+        Commands::Config(args) => config::run(args)?,
+        Commands::Wordlist(args) => wordlist::run(args)?,
+        #[cfg(feature = "domain-intelligence")]
+        Commands::Discover(args) => discover::run(args).await?,
+        #[cfg(feature = "domain-agentic")]
+        Commands::Agent {
+            command,
+            llm,
+            profile,
+        } => agent::run(command, llm, profile).await?,
+        #[cfg(feature = "domain-agentic")]
+        Commands::Memory(args) => memory::run(args)?,
+        #[cfg(feature = "domain-agentic")]
+        Commands::Search(args) => search::run(args).await?,
+        #[cfg(feature = "domain-automation")]
+        Commands::Calendar(args) => calendar::run(args)?,
+        #[cfg(feature = "domain-automation")]
+        Commands::Watch { command, profile } => {
+            if !watch::run(command, profile).await? {
+                std::process::exit(EXIT_LOOKUP_ERRORS);
+            }
+        }
+        #[cfg(feature = "domain-automation")]
+        Commands::Job { command, profile } => {
+            if !job::run(command, profile).await? {
+                std::process::exit(EXIT_LOOKUP_ERRORS);
+            }
+        }
+        #[cfg(feature = "domain-automation")]
+        Commands::Catch(args) => catch::run(args).await?,
+        #[cfg(feature = "domain-intelligence")]
+        Commands::Threat { command, profile } => threat::run(command, profile).await?,
+        #[cfg(feature = "domain-intelligence")]
+        Commands::Generate(args) => generate::run(args)?,
+        #[cfg(feature = "domain-automation")]
+        Commands::Import(args) => import::run(args)?,
+        Commands::Audit(args) => audit::run(args)?,
+        #[cfg(feature = "api-server")]
+        Commands::Serve(args) => serve::run(args).await?,
+        #[cfg(feature = "api-server")]
+        Commands::ApiKey(args) => api_key::run(args)?,
+    }
+
+    Ok(())
+}
+
+fn split_list(s: &str) -> Vec<String> {
+    s.split(',')
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+/// Parse a lowercase enum name the way the app stores it, e.g. a watch
+/// priority or threat level.
+#[cfg(any(
+    feature = "domain-automation",
+    feature = "domain-intelligence",
+    feature = "domain-agentic"
+))]
+fn parse_enum<T: serde::de::DeserializeOwned>(s: &str) -> Result<T, String> {
+    serde_json::from_value(serde_json::json!(s.to_lowercase()))
+        .map_err(|_| format!("unknown value '{}'", s))
+}
+
+/// The name an enum value is stored under, for tables.
+#[cfg(any(
+    feature = "domain-automation",
+    feature = "domain-intelligence",
+    feature = "domain-agentic"
+))]
+fn label<T: serde::Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        Ok(other) => other.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(all(test, feature = "domain-automation"))]
+mod tests {
+    use super::*;
+    use whoisdigger::automation::expiry::WatchPriority;
+
+    #[test]
+    fn test_parse_enum_and_label_round_trip() {
+        let priority: WatchPriority = parse_enum("High").unwrap();
+        assert_eq!(priority, WatchPriority::High);
+        assert_eq!(label(&priority), "high");
+        assert_eq!(
+            parse_enum::<WatchPriority>("urgent").unwrap_err(),
+            "unknown value 'urgent'"
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use clap::Args;
+use whoisdigger::agentic::agent::{KnowledgeStore, StoredKnowledge};
+
+use crate::profile::{open_profile, ProfileArgs};
+
+#[derive(Args, Debug)]
+pub(crate) struct MemoryArgs {
+    /// Path to the agent memory database (defaults to the profile's)
+    #[arg(long)]
+    path: Option<String>,
+    /// Only show entries for this domain
+    #[arg(short, long)]
+    domain: Option<String>,
+    /// Show entries relevant to this query instead of listing
+    #[arg(short, long)]
+    search: Option<String>,
+    /// Include expired entries in the listing
+    #[arg(long)]
+    all: bool,
+    /// Delete the entry with this id
+    #[arg(long)]
+    forget: Option<i64>,
+    /// Delete expired entries
+    #[arg(long)]
+    prune: bool,
+    /// With --prune, also delete entries older than this many days
+    #[arg(long)]
+    older_than: Option<i64>,
+    #[command(flatten)]
+    profile: ProfileArgs,
+}
+
+pub(crate) fn run(args: MemoryArgs) -> anyhow::Result<()> {
+    let MemoryArgs {
+        path,
+        domain,
+        search,
+        all,
+        forget,
+        prune,
+        older_than,
+        profile,
+    } = args;
+    let path = match path {
+        Some(path) => path,
+        None => open_profile(profile)?
+            .1
+            .database("agent-memory")
+            .to_string_lossy()
+            .into_owned(),
+    };
+    let store = KnowledgeStore::open(&path)?;
+    if let Some(id) = forget {
+        if store.forget(id)? {
+            println!("Forgot entry {}.", id);
+        } else {
+            println!("No entry {}.", id);
+        }
+    } else if prune {
+        let mut removed = store.prune_expired()?;
+        if let Some(days) = older_than {
+            removed += store.prune_older_than(chrono::Utc::now() - chrono::Duration::days(days))?;
+        }
+        println!("Pruned {} entries.", removed);
+    } else {
+        let entries = match search {
+            Some(query) => store.search(&query, 50)?,
+            None => store.list(domain.as_deref(), all)?,
+        };
+        print_memory(&entries);
+    }
+    Ok(())
+}
+
+fn print_memory(entries: &[StoredKnowledge]) {
+    let now = chrono::Utc::now();
+    println!(
+        "{:<6} | {:<30} | {:<12} | {:<16} | {:<20} | Summary",
+        "Id", "Domain", "Kind", "Source", "Recorded"
+    );
+    println!(
+        "{:-<6}-|-{:-<30}-|-{:-<12}-|-{:-<16}-|-{:-<20}-|-{:-<20}",
+        "", "", "", "", "", ""
+    );
+    for e in entries {
+        let k = &e.knowledge;
+        println!(
+            "{:<6} | {:<30} | {:<12} | {:<16} | {:<20} | {}{}",
+            e.id,
+            k.domain,
+            k.kind,
+            k.source.as_deref().unwrap_or("-"),
+            e.recorded_at.format("%Y-%m-%d %H:%M"),
+            k.summary,
+            if e.is_expired(now) { " (expired)" } else { "" }
+        );
+    }
+}
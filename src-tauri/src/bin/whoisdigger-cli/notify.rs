@@ -0,0 +1,40 @@
+use whoisdigger::automation::notify::{Notification, Notifier, NotifyConfig};
+use whoisdigger::storage::db::profile::ProfileDir;
+
+/// The profile's notifier as set up in the desktop app, or `None` when
+/// notifications are not configured.
+fn load_notifier(profile: &ProfileDir) -> anyhow::Result<Option<Notifier>> {
+    let path = profile.notifications();
+    if !path.exists() {
+        return Ok(None);
+    }
+    let mut config = NotifyConfig::load(&path)?;
+    let dead_letter = match &config.dead_letter {
+        Some(file) => profile.dir.join(file),
+        None => profile.dead_letters(),
+    };
+    config.dead_letter = Some(dead_letter.to_string_lossy().into_owned());
+    Ok(Some(config.build()?))
+}
+
+/// Route `notifications` through the profile's notifier, if one is set up.
+/// Undelivered messages are reported but do not fail the command.
+pub(crate) async fn send_notifications(
+    profile: &ProfileDir,
+    notifications: Vec<Notification>,
+) -> anyhow::Result<()> {
+    if notifications.is_empty() {
+        return Ok(());
+    }
+    let Some(notifier) = load_notifier(profile)? else {
+        return Ok(());
+    };
+    let sent = notifier.notify_all(&notifications).await;
+    if !sent.failed.is_empty() {
+        eprintln!(
+            "{} notifications could not be delivered.",
+            sent.failed.len()
+        );
+    }
+    Ok(())
+}
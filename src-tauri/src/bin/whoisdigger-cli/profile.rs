@@ -0,0 +1,51 @@
+use clap::Args;
+use std::path::{Path, PathBuf};
+use whoisdigger::storage::db::profile::{app_data_dir, current_profile, ProfileDir};
+use whoisdigger::LookupSettings;
+
+/// The desktop app profile a command reads and writes.
+#[derive(Args, Debug)]
+pub(crate) struct ProfileArgs {
+    /// App data directory holding the profiles
+    #[arg(long, global = true)]
+    pub(crate) data_dir: Option<String>,
+    /// Profile to use (defaults to the app's current profile)
+    #[arg(short, long, global = true)]
+    pub(crate) profile: Option<String>,
+}
+
+/// `--data-dir`, or the desktop app's data directory.
+pub(crate) fn resolve_data_dir(data_dir: Option<String>) -> anyhow::Result<PathBuf> {
+    data_dir
+        .map(Into::into)
+        .or_else(app_data_dir)
+        .ok_or_else(|| anyhow::anyhow!("cannot locate the app data directory; pass --data-dir"))
+}
+
+/// The data directory and the chosen profile in it, the app's current one
+/// by default.
+pub(crate) fn open_profile(args: ProfileArgs) -> anyhow::Result<(PathBuf, ProfileDir)> {
+    let data_dir = resolve_data_dir(args.data_dir)?;
+    let name = args.profile.unwrap_or_else(|| current_profile(&data_dir));
+    let profile = ProfileDir::open(&data_dir, &name).map_err(anyhow::Error::msg)?;
+    Ok((data_dir, profile))
+}
+
+/// The lookup settings saved by the desktop app in `data_dir`, or the
+/// defaults when there are none.
+pub(crate) fn load_lookup_settings(data_dir: &Path) -> LookupSettings {
+    LookupSettings::load_app_settings(&data_dir.join("settings.json")).unwrap_or_else(|e| {
+        eprintln!("warning: using default lookup settings: {}", e);
+        LookupSettings::default()
+    })
+}
+
+/// [`load_lookup_settings`] with `--timeout`, when given, in place of the
+/// saved timeout.
+pub(crate) fn cli_lookup_settings(data_dir: &Path, timeout: Option<u64>) -> LookupSettings {
+    let mut settings = load_lookup_settings(data_dir);
+    if timeout.is_some() {
+        settings.general.timeout = timeout;
+    }
+    settings
+}
@@ -0,0 +1,121 @@
+use clap::ValueEnum;
+use std::fs;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use whoisdigger::{availability::DomainStatus, bulk::BulkResult, export::build_csv};
+
+use crate::split_list;
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub(crate) enum OutputFormat {
+    Table,
+    Json,
+    Jsonl,
+    Csv,
+}
+
+/// Domains from a wordlist, one per line; blank lines and `#` comments are
+/// skipped.
+pub(crate) fn read_domains(reader: impl BufRead) -> anyhow::Result<Vec<String>> {
+    let mut domains = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if !line.is_empty() && !line.starts_with('#') {
+            domains.push(line.to_string());
+        }
+    }
+    Ok(domains)
+}
+
+/// Results saved as a JSON array (`--format json`, the API) or as JSONL.
+pub(crate) fn read_results(content: &str) -> anyhow::Result<Vec<BulkResult>> {
+    if content.trim_start().starts_with('[') {
+        return Ok(serde_json::from_str(content)?);
+    }
+    content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| Ok(serde_json::from_str(l)?))
+        .collect()
+}
+
+/// A file, or stdin for "-".
+pub(crate) fn open_input(path: &str) -> anyhow::Result<Box<dyn BufRead>> {
+    Ok(match path {
+        "-" => Box::new(std::io::stdin().lock()),
+        path => Box::new(BufReader::new(fs::File::open(path)?)),
+    })
+}
+
+/// A buffered writer to `path`, or to stdout.
+pub(crate) fn open_output(path: Option<&str>) -> anyhow::Result<Box<dyn Write>> {
+    Ok(match path {
+        Some(path) => Box::new(BufWriter::new(fs::File::create(path)?)),
+        None => Box::new(BufWriter::new(std::io::stdout())),
+    })
+}
+
+/// Validate a comma separated `--status` list.
+pub(crate) fn parse_status_filter(s: &str) -> anyhow::Result<Vec<String>> {
+    split_list(s)
+        .into_iter()
+        .map(|status| {
+            if DomainStatus::from_str_loose(&status).as_str() == status {
+                Ok(status)
+            } else {
+                anyhow::bail!("unknown status '{}'", status)
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn status_matches(filter: &[String], status: &str) -> bool {
+    filter
+        .iter()
+        .any(|f| f == status || (f == "error" && status.starts_with("error")))
+}
+
+pub(crate) fn write_results(
+    results: &[BulkResult],
+    format: OutputFormat,
+    out: &mut dyn Write,
+) -> anyhow::Result<()> {
+    match format {
+        OutputFormat::Table => {
+            writeln!(
+                out,
+                "{:<30} | {:<20} | {:<12} | Details",
+                "Domain", "Status", "Expiry"
+            )?;
+            writeln!(out, "{:-<30}-|-{:-<20}-|-{:-<12}-|-{:-<20}", "", "", "", "")?;
+            for r in results {
+                let params = r.params.as_ref();
+                let expiry = params.and_then(|p| p.expiry_date.as_deref());
+                let details = r
+                    .error
+                    .as_deref()
+                    .or_else(|| params.and_then(|p| p.registrar.as_deref()));
+                writeln!(
+                    out,
+                    "{:<30} | {:<20} | {:<12} | {}",
+                    r.domain,
+                    r.status,
+                    expiry.unwrap_or(""),
+                    details.unwrap_or("")
+                )?;
+            }
+        }
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *out, results)?;
+            writeln!(out)?;
+        }
+        OutputFormat::Jsonl => {
+            for r in results {
+                serde_json::to_writer(&mut *out, r)?;
+                writeln!(out)?;
+            }
+        }
+        OutputFormat::Csv => out.write_all(build_csv(results).as_bytes())?,
+    }
+    Ok(())
+}
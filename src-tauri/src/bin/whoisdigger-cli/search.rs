@@ -0,0 +1,121 @@
+use clap::Args;
+use whoisdigger::agentic::llm::{LlmConfig, ProviderKind};
+use whoisdigger::agentic::search::{
+    ChatStore, HistoryStore, LlmEmbedder, SearchHit, SearchIndex, SearchQuery,
+};
+
+use crate::profile::{open_profile, ProfileArgs};
+
+#[derive(Args, Debug)]
+pub(crate) struct SearchArgs {
+    /// Keywords plus optional filters: registrar:, ns:, domain:, in:,
+    /// since:, until:, within:, or "last quarter"
+    query: Option<String>,
+    /// Path to the search index database (defaults to the profile's)
+    #[arg(short, long)]
+    index: Option<String>,
+    /// Index the snapshots in this WHOIS history database first
+    #[arg(long)]
+    history: Option<String>,
+    /// Index the sessions in this chat database first
+    #[arg(long)]
+    chat: Option<String>,
+    /// Embedding model for semantic ranking (API key from WHOISDIGGER_LLM_API_KEY)
+    #[arg(long)]
+    embed_model: Option<String>,
+    /// Provider serving the embedding model
+    #[arg(long, default_value = "open_ai")]
+    embed_provider: String,
+    /// Base URL of the embedding provider
+    #[arg(long)]
+    embed_url: Option<String>,
+    /// Number of results to show
+    #[arg(short, long, default_value_t = 20)]
+    limit: usize,
+    #[command(flatten)]
+    profile: ProfileArgs,
+}
+
+pub(crate) async fn run(args: SearchArgs) -> anyhow::Result<()> {
+    let SearchArgs {
+        query,
+        index,
+        history,
+        chat,
+        embed_model,
+        embed_provider,
+        embed_url,
+        limit,
+        profile,
+    } = args;
+    let index = match index {
+        Some(index) => index,
+        None => open_profile(profile)?
+            .1
+            .database("search-index")
+            .to_string_lossy()
+            .into_owned(),
+    };
+    let index = SearchIndex::open(&index)?;
+    if let Some(path) = history {
+        let count = index.index_history(&HistoryStore::open(&path)?)?;
+        println!("Indexed {} snapshots.", count);
+    }
+    if let Some(path) = chat {
+        let count = index.index_chat(&ChatStore::open(&path)?)?;
+        println!("Indexed {} chat documents.", count);
+    }
+    let mut query = SearchQuery::parse(query.as_deref().unwrap_or(""));
+    query.limit = limit;
+    let hits = match embed_model {
+        Some(model) => {
+            let provider: ProviderKind = serde_json::from_value(serde_json::json!(embed_provider))
+                .map_err(|_| anyhow::anyhow!("unknown provider '{}'", embed_provider))?;
+            let api_key = std::env::var("WHOISDIGGER_LLM_API_KEY").unwrap_or_default();
+            let mut config = LlmConfig::new(provider, &model).with_api_key(&api_key);
+            if let Some(url) = embed_url {
+                config = config.with_api_url(&url);
+            }
+            let embedder = LlmEmbedder::from_config(config, &model)?;
+            let embedded = index.embed_pending(&embedder, 32).await?;
+            if embedded > 0 {
+                println!("Embedded {} documents.", embedded);
+            }
+            index.search_semantic(&query, &embedder).await?
+        }
+        None => index.search(&query)?,
+    };
+    print_search(&hits);
+    Ok(())
+}
+
+fn print_search(hits: &[SearchHit]) {
+    println!(
+        "{:<7} | {:<12} | {:<30} | {:<16} | Excerpt",
+        "Score", "Kind", "Title", "Date"
+    );
+    println!(
+        "{:-<7}-|-{:-<12}-|-{:-<30}-|-{:-<16}-|-{:-<20}",
+        "", "", "", "", ""
+    );
+    for hit in hits {
+        let doc = &hit.document;
+        let excerpt: String = doc
+            .text
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .take(80)
+            .collect();
+        let title: String = doc.title.chars().take(30).collect();
+        println!(
+            "{:<7.3} | {:<12} | {:<30} | {:<16} | {}",
+            hit.score,
+            doc.kind.as_str(),
+            title,
+            doc.timestamp.format("%Y-%m-%d %H:%M"),
+            excerpt
+        );
+    }
+}
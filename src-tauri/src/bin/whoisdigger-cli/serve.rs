@@ -0,0 +1,66 @@
+use clap::Args;
+use whoisdigger::api::{ApiKeys, ApiState};
+use whoisdigger::proxy::{Rbac, RBAC_AUDIT_FILE, RBAC_FILE};
+use whoisdigger::storage::audit::{self, Action, AuditEvent, AuditLog, Source, AUDIT_FILE};
+
+use crate::profile::{load_lookup_settings, open_profile, ProfileArgs};
+
+/// API keys file in the app data directory.
+pub(crate) const API_KEYS_FILE: &str = "api-keys.json";
+
+#[derive(Args, Debug)]
+pub(crate) struct ServeArgs {
+    /// Address to listen on
+    #[arg(short, long, default_value = "127.0.0.1:8750")]
+    bind: std::net::SocketAddr,
+    /// App data directory holding the profiles and API keys
+    #[arg(long)]
+    data_dir: Option<String>,
+    /// Profile to serve (defaults to the app's current profile)
+    #[arg(short, long)]
+    profile: Option<String>,
+    /// RBAC roles and bindings file, saved on change (defaults to rbac.json
+    /// in the data directory)
+    #[arg(long)]
+    rbac: Option<String>,
+    /// Highest concurrency a bulk job may request
+    #[arg(long, default_value_t = 32)]
+    max_concurrency: usize,
+}
+
+pub(crate) async fn run(args: ServeArgs) -> anyhow::Result<()> {
+    let ServeArgs {
+        bind,
+        data_dir,
+        profile,
+        rbac,
+        max_concurrency,
+    } = args;
+    let (data_dir, profile) = open_profile(ProfileArgs { data_dir, profile })?;
+    let keys = ApiKeys::load(&data_dir.join(API_KEYS_FILE)).map_err(anyhow::Error::msg)?;
+    if keys.keys.is_empty() {
+        anyhow::bail!("no API keys; create one with `api-key --create <name>`");
+    }
+    println!(
+        "Serving profile '{}' on http://{} with {} API key(s).",
+        profile.name,
+        bind,
+        keys.keys.len()
+    );
+    let rbac = Rbac::open(rbac.map_or_else(|| data_dir.join(RBAC_FILE), Into::into))
+        .map_err(anyhow::Error::msg)?
+        .with_audit_log(data_dir.join(RBAC_AUDIT_FILE));
+    let audit = AuditLog::open(&data_dir.join(AUDIT_FILE).to_string_lossy())?;
+    audit.append(
+        AuditEvent::new(audit::local_actor(), Source::Cli, Action::ServerStarted)
+            .with_target(bind.to_string())
+            .with_detail("profile", profile.name.clone()),
+    )?;
+    let state = ApiState::new(profile, keys)
+        .with_lookup_settings(load_lookup_settings(&data_dir))
+        .with_rbac(rbac)
+        .with_max_concurrency(max_concurrency)
+        .with_audit(audit);
+    whoisdigger::api::serve(bind, state).await?;
+    Ok(())
+}
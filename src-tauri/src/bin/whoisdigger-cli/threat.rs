@@ -0,0 +1,148 @@
+use clap::Subcommand;
+use std::fs;
+use std::io::IsTerminal;
+#[cfg(feature = "domain-automation")]
+use whoisdigger::automation::notify::Notification;
+use whoisdigger::intelligence::threat::{
+    assess_domain, blocklist::check_blocklists, Blocklist, ThreatCategory, ThreatLevel,
+};
+use whoisdigger::proxy::Permission;
+
+use crate::audit::require_permission;
+use crate::lookup::{process_lookup, LookupType};
+#[cfg(feature = "domain-automation")]
+use crate::notify::send_notifications;
+use crate::profile::{cli_lookup_settings, open_profile, ProfileArgs};
+use crate::results::{open_input, read_domains};
+use crate::{label, parse_enum};
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum ThreatCommand {
+    /// Score domains for threat indicators, highest risk first
+    Scan {
+        /// Domains to scan (reads stdin when none are given)
+        domains: Vec<String>,
+        /// File of domains to scan, one per line ("-" reads stdin)
+        #[arg(short, long)]
+        input: Option<String>,
+        /// Blocklist file, one domain per line (repeatable)
+        #[arg(short, long)]
+        blocklist: Vec<String>,
+        /// Look the domains up over WHOIS to weigh registrar and age
+        #[arg(long)]
+        whois: bool,
+        /// Number of concurrent WHOIS lookups
+        #[arg(short, long, default_value_t = 5)]
+        concurrency: usize,
+        /// WHOIS timeout in milliseconds (defaults to the app's setting)
+        #[arg(long)]
+        timeout: Option<u64>,
+        /// Only report domains at or above this level (none, low, medium,
+        /// high, critical)
+        #[arg(long, value_parser = parse_enum::<ThreatLevel>)]
+        min_level: Option<ThreatLevel>,
+        /// Print the assessments as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Run `threat scan`: score each domain from its name, blocklist hits and,
+/// with `--whois`, its registrar and age. Risky domains are sent to the
+/// profile's notifier.
+pub(crate) async fn run(command: ThreatCommand, profile: ProfileArgs) -> anyhow::Result<()> {
+    let ThreatCommand::Scan {
+        domains,
+        input,
+        blocklist,
+        whois,
+        concurrency,
+        timeout,
+        min_level,
+        json,
+    } = command;
+
+    let mut domains = domains;
+    match input.as_deref() {
+        Some(path) => domains.extend(read_domains(open_input(path)?)?),
+        None if domains.is_empty() => {
+            if std::io::stdin().is_terminal() {
+                anyhow::bail!("nothing to scan: pass domains, --input or pipe domains on stdin");
+            }
+            domains.extend(read_domains(std::io::stdin().lock())?);
+        }
+        None => {}
+    }
+    let domains = whoisdigger::bulk::expand_domains(&domains, &[]);
+    let blocklists = blocklist
+        .iter()
+        .map(|path| {
+            let text = fs::read_to_string(path)?;
+            Ok(Blocklist::from_text(
+                path.as_str(),
+                ThreatCategory::BlocklistMatch,
+                &text,
+            ))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    #[cfg_attr(not(feature = "domain-automation"), allow(unused_variables))]
+    let (data_dir, profile) = open_profile(profile)?;
+    let lookups = match whois {
+        true => {
+            require_permission(Some(&data_dir), Permission::BulkRun)?;
+            let settings = cli_lookup_settings(&data_dir, timeout);
+            process_lookup(domains.clone(), concurrency, settings, &LookupType::Whois).await?
+        }
+        false => Vec::new(),
+    };
+
+    let now = chrono::Utc::now();
+    let mut assessments: Vec<_> = domains
+        .iter()
+        .map(|domain| {
+            let params = lookups
+                .iter()
+                .find(|r| &r.domain == domain)
+                .and_then(|r| r.params.as_ref());
+            let registrar = params.and_then(|p| p.registrar.as_deref());
+            let age = params
+                .and_then(|p| p.creation_date.as_deref())
+                .and_then(whoisdigger::parser::parse_whois_date)
+                .map(|created| (now - created).num_days());
+            let hits = check_blocklists(domain, &blocklists).indicators;
+            assess_domain(domain, registrar, age, &hits)
+        })
+        .filter(|a| min_level.as_ref().is_none_or(|min| &a.risk.level >= min))
+        .collect();
+    assessments.sort_by_key(|a| std::cmp::Reverse(a.risk.score));
+    #[cfg(feature = "domain-automation")]
+    send_notifications(
+        &profile,
+        assessments
+            .iter()
+            .filter_map(Notification::from_risk)
+            .collect(),
+    )
+    .await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&assessments)?);
+        return Ok(());
+    }
+    for a in &assessments {
+        println!(
+            "{:<40} {:>3}  {:<8}  {}",
+            a.domain,
+            a.risk.score,
+            label(&a.risk.level),
+            a.risk.summary
+        );
+        for indicator in &a.risk.indicators {
+            match &indicator.evidence {
+                Some(evidence) => println!("    - {} ({})", indicator.description, evidence),
+                None => println!("    - {}", indicator.description),
+            }
+        }
+    }
+    Ok(())
+}
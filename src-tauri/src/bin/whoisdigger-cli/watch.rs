@@ -0,0 +1,265 @@
+use clap::Subcommand;
+use whoisdigger::automation::{
+    expiry::{ExpiryStore, WatchEntry, WatchMonitor, WatchPriority, Watchlist},
+    notify::Notification,
+};
+use whoisdigger::monitor::WhoisWatchProbe;
+use whoisdigger::proxy::Permission;
+use whoisdigger::storage::audit::Action;
+
+use crate::audit::{cli_event, record, require_permission};
+use crate::notify::send_notifications;
+use crate::profile::{cli_lookup_settings, open_profile, ProfileArgs};
+use crate::{label, parse_enum};
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum WatchCommand {
+    /// Watch domains, or change the priority and notes of watched ones
+    Add {
+        #[arg(required = true)]
+        domains: Vec<String>,
+        /// low, medium, high or critical
+        #[arg(long, value_parser = parse_enum::<WatchPriority>)]
+        priority: Option<WatchPriority>,
+        /// Notes or tags
+        #[arg(long)]
+        notes: Option<String>,
+    },
+    /// List watched domains, most urgent first
+    List {
+        /// Print the entries as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Stop watching domains
+    Remove {
+        #[arg(required = true)]
+        domains: Vec<String>,
+    },
+    /// Look watched domains up and report what changed
+    ///
+    /// Without arguments only the entries that are due are checked, as the
+    /// app's monitor does. Exits 3 when some lookups failed.
+    Check {
+        /// Watched domains to check now, paused ones included
+        domains: Vec<String>,
+        /// Check every active entry now; paused entries are skipped
+        #[arg(long)]
+        all: bool,
+        /// WHOIS timeout in milliseconds (defaults to the app's setting)
+        #[arg(long)]
+        timeout: Option<u64>,
+    },
+}
+
+/// Run a `watch` subcommand on the profile's watchlist. Returns `false` when
+/// some lookups failed.
+pub(crate) async fn run(command: WatchCommand, profile: ProfileArgs) -> anyhow::Result<bool> {
+    let (data_dir, profile) = open_profile(profile)?;
+    let permission = match &command {
+        WatchCommand::Add { .. } | WatchCommand::Remove { .. } => Permission::WatchlistManage,
+        WatchCommand::List { .. } => Permission::WatchlistRead,
+        WatchCommand::Check { .. } => Permission::Lookup,
+    };
+    require_permission(Some(&data_dir), permission)?;
+    let store = ExpiryStore::open(&profile.watchlist().to_string_lossy())?;
+    let watchlist = store.load()?;
+    match command {
+        WatchCommand::Add {
+            domains,
+            priority,
+            notes,
+        } => {
+            for domain in whoisdigger::bulk::expand_domains(&domains, &[]) {
+                let mut entry = watchlist
+                    .get(&domain)
+                    .cloned()
+                    .unwrap_or_else(|| WatchEntry::new(domain.as_str()));
+                if let Some(priority) = &priority {
+                    entry.priority = priority.clone();
+                }
+                if let Some(notes) = &notes {
+                    entry.notes = Some(notes.clone());
+                }
+                entry.active = true;
+                store.upsert(&entry)?;
+                record(
+                    Some(&data_dir),
+                    cli_event(Action::WatchlistChanged, &domain).with_detail("change", "watch"),
+                );
+                println!("Watching {} ({}).", domain, label(&entry.priority));
+            }
+        }
+        WatchCommand::List { json } => {
+            let mut entries = watchlist.sorted_by_urgency();
+            entries.extend(watchlist.entries.iter().filter(|e| !e.active));
+            if json {
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+                return Ok(true);
+            }
+            println!(
+                "{:<30} | {:<8} | {:<12} | {:<18} | {:<10} | {:<16}",
+                "Domain", "Priority", "Status", "Phase", "Expiry", "Last checked"
+            );
+            println!(
+                "{:-<30}-|-{:-<8}-|-{:-<12}-|-{:-<18}-|-{:-<10}-|-{:-<16}",
+                "", "", "", "", "", ""
+            );
+            for e in entries {
+                let priority = match e.active {
+                    true => label(&e.priority),
+                    false => "paused".to_string(),
+                };
+                println!(
+                    "{:<30} | {:<8} | {:<12} | {:<18} | {:<10} | {:<16}",
+                    e.domain,
+                    priority,
+                    e.status.as_deref().unwrap_or("-"),
+                    e.phase.as_ref().map(label).unwrap_or_else(|| "-".into()),
+                    e.expiry_date
+                        .map(|d| d.format("%Y-%m-%d").to_string())
+                        .unwrap_or_else(|| "-".into()),
+                    e.last_checked
+                        .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+                        .unwrap_or_else(|| "never".into()),
+                );
+            }
+        }
+        WatchCommand::Remove { domains } => {
+            for domain in whoisdigger::bulk::expand_domains(&domains, &[]) {
+                if !store.remove(&domain)? {
+                    eprintln!("{} is not on the watchlist.", domain);
+                    continue;
+                }
+                record(
+                    Some(&data_dir),
+                    cli_event(Action::WatchlistChanged, &domain).with_detail("change", "unwatch"),
+                );
+                println!("Stopped watching {}.", domain);
+            }
+        }
+        WatchCommand::Check {
+            domains,
+            all,
+            timeout,
+        } => {
+            // Checking named entries (or all of them) now means treating them
+            // as never checked; only the check results are saved, so paused
+            // entries stay paused.
+            let named = whoisdigger::bulk::expand_domains(&domains, &[]);
+            if let Some(missing) = named.iter().find(|d| watchlist.get(d).is_none()) {
+                anyhow::bail!("{} is not on the watchlist", missing);
+            }
+            let mut checking = watch_subset(&watchlist, &named, all);
+
+            let settings = cli_lookup_settings(&data_dir, timeout);
+            let history = whoisdigger::automation::history::HistoryStore::open(
+                &profile.snapshots().to_string_lossy(),
+            )?;
+            let probe = WhoisWatchProbe::new(settings).with_history(std::sync::Arc::new(history));
+            let report = WatchMonitor::new(probe).tick(&mut checking).await;
+
+            for domain in &report.checked {
+                if let Some(entry) = checking.get(domain) {
+                    store.record_check(entry)?;
+                }
+            }
+            for update in &report.updates {
+                println!(
+                    "{}: {} ({})",
+                    update.domain,
+                    update.status,
+                    label(&update.phase)
+                );
+                for change in &update.changes {
+                    println!(
+                        "    {}: {} -> {}",
+                        label(&change.field),
+                        change.old.as_deref().unwrap_or("-"),
+                        change.new.as_deref().unwrap_or("-")
+                    );
+                }
+            }
+            for (domain, error) in &report.errors {
+                eprintln!("{}: {}", domain, error);
+            }
+            send_notifications(
+                &profile,
+                report
+                    .updates
+                    .iter()
+                    .map(Notification::from_watch_update)
+                    .collect(),
+            )
+            .await?;
+            eprintln!(
+                "Checked {} domains: {} changed, {} failed.",
+                report.checked.len(),
+                report.updates.len(),
+                report.errors.len()
+            );
+            return Ok(report.errors.is_empty());
+        }
+    }
+    Ok(true)
+}
+
+/// The entries `watch check` looks at: the named ones, paused or not, and
+/// with `all` every active one, each marked due; otherwise the whole
+/// watchlist so the monitor picks the due entries itself.
+fn watch_subset(watchlist: &Watchlist, named: &[String], all: bool) -> Watchlist {
+    if !all && named.is_empty() {
+        return watchlist.clone();
+    }
+    let mut subset = Watchlist::new();
+    for entry in &watchlist.entries {
+        if (all && entry.active) || named.contains(&entry.domain) {
+            let mut entry = entry.clone();
+            entry.active = true;
+            entry.last_checked = None;
+            subset.add(entry);
+        }
+    }
+    subset
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watch_subset() {
+        let checked = chrono::Utc::now();
+        let mut watchlist = Watchlist::new();
+        for (domain, active) in [("a.com", true), ("b.com", false), ("c.com", true)] {
+            let mut entry = WatchEntry::new(domain);
+            entry.active = active;
+            entry.last_checked = Some(checked);
+            watchlist.add(entry);
+        }
+        let domains = |list: &Watchlist| {
+            list.entries
+                .iter()
+                .map(|e| e.domain.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let unchanged = watch_subset(&watchlist, &[], false);
+        assert_eq!(domains(&unchanged), ["a.com", "b.com", "c.com"]);
+        assert_eq!(unchanged.get("a.com").unwrap().last_checked, Some(checked));
+
+        let all = watch_subset(&watchlist, &[], true);
+        assert_eq!(domains(&all), ["a.com", "c.com"]);
+        assert!(all.entries.iter().all(|e| e.last_checked.is_none()));
+
+        let named = watch_subset(&watchlist, &["b.com".to_string()], false);
+        assert_eq!(domains(&named), ["b.com"]);
+        assert!(named.get("b.com").unwrap().active);
+        assert!(named.get("b.com").unwrap().last_checked.is_none());
+
+        let both = watch_subset(&watchlist, &["b.com".to_string()], true);
+        assert_eq!(domains(&both), ["a.com", "b.com", "c.com"]);
+    }
+}
@@ -0,0 +1,72 @@
+use clap::Args;
+use std::fs;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use whoisdigger::wordlist::{Recipe, RecipeBook};
+
+use crate::profile::{open_profile, ProfileArgs};
+
+#[derive(Args, Debug)]
+pub(crate) struct WordlistArgs {
+    /// Recipe name in the recipe book, or path to a recipe JSON file
+    #[arg(short, long)]
+    recipe: String,
+    /// Path to the recipe book (defaults to the profile's)
+    #[arg(short = 'b', long)]
+    recipes: Option<String>,
+    /// Input wordlist (reads stdin when omitted)
+    #[arg(short, long)]
+    input: Option<String>,
+    /// Output path (writes stdout when omitted)
+    #[arg(short, long)]
+    output: Option<String>,
+    #[command(flatten)]
+    profile: ProfileArgs,
+}
+
+pub(crate) fn run(args: WordlistArgs) -> anyhow::Result<()> {
+    let WordlistArgs {
+        recipe,
+        recipes,
+        input,
+        output,
+        profile,
+    } = args;
+    let recipe = load_recipe(&recipe, recipes, profile)?;
+    let reader: Box<dyn BufRead> = match input {
+        Some(path) => Box::new(BufReader::new(fs::File::open(path)?)),
+        None => Box::new(BufReader::new(std::io::stdin())),
+    };
+    let writer: Box<dyn Write> = match output {
+        Some(path) => Box::new(BufWriter::new(fs::File::create(path)?)),
+        None => Box::new(BufWriter::new(std::io::stdout())),
+    };
+    let run = recipe
+        .stream(reader, writer)
+        .map_err(|e| anyhow::anyhow!(e))?;
+    eprintln!(
+        "Recipe '{}': {} lines in, {} lines out.",
+        recipe.name, run.lines_in, run.lines_out
+    );
+    Ok(())
+}
+
+fn load_recipe(
+    recipe: &str,
+    book_path: Option<String>,
+    profile: ProfileArgs,
+) -> anyhow::Result<Recipe> {
+    let path = Path::new(recipe);
+    if path.is_file() {
+        let content = fs::read_to_string(path)?;
+        return Recipe::from_json(&content).map_err(|e| anyhow::anyhow!(e));
+    }
+    let book_path = match book_path {
+        Some(path) => PathBuf::from(path),
+        None => open_profile(profile)?.1.wordlist_recipes(),
+    };
+    let book = RecipeBook::load(&book_path).map_err(|e| anyhow::anyhow!(e))?;
+    book.get(recipe)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("recipe '{}' not found in {}", recipe, book_path.display()))
+}
@@ -0,0 +1,102 @@
+//! Live availability backend for the wd-domgen discovery pipeline: DNS NS
+//! lookups for the pre-screen, WHOIS plus the availability parser for
+//! confirmation.
+
+use std::time::Duration;
+
+use crate::availability::{is_domain_available, DomainStatus};
+use crate::intelligence::domgen::{AvailabilityChecker, CheckVerdict};
+use crate::{dns_lookup, get_time_between, perform_lookup_with_settings, LookupSettings};
+
+pub use crate::intelligence::domgen::{
+    DiscoveredDomain, Discovery, DiscoveryConfig, DiscoveryEvent, DiscoveryProgress,
+    DiscoveryReport, StopReason,
+};
+
+/// Checks candidates against the network using the app's lookup settings.
+pub struct LookupChecker {
+    settings: LookupSettings,
+    timeout: Option<Duration>,
+}
+
+impl LookupChecker {
+    pub fn new(settings: LookupSettings) -> Self {
+        Self {
+            settings,
+            timeout: None,
+        }
+    }
+
+    /// Abort a WHOIS confirmation after `timeout_ms` (0 = no limit).
+    pub fn with_timeout(mut self, timeout_ms: u64) -> Self {
+        self.timeout = (timeout_ms > 0).then(|| Duration::from_millis(timeout_ms));
+        self
+    }
+}
+
+impl AvailabilityChecker for LookupChecker {
+    async fn resolves(&self, domain: &str) -> bool {
+        dns_lookup(domain).await.unwrap_or(false)
+    }
+
+    async fn confirm(&self, domain: &str) -> Result<CheckVerdict, String> {
+        let pause = get_time_between(&self.settings);
+        if pause > 0 {
+            tokio::time::sleep(Duration::from_millis(pause)).await;
+        }
+
+        // The WHOIS client does blocking socket I/O, so it runs on the
+        // blocking pool. A timed-out lookup is left to finish there.
+        let (domain_owned, settings) = (domain.to_string(), self.settings.clone());
+        let lookup = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(perform_lookup_with_settings(&domain_owned, &settings))
+        });
+        let joined = match self.timeout {
+            Some(limit) => tokio::time::timeout(limit, lookup)
+                .await
+                .map_err(|_| format!("Timeout after {}ms", limit.as_millis()))?,
+            None => lookup.await,
+        };
+        let reply = joined.map_err(|e| e.to_string())??;
+        verdict_for(&is_domain_available(&reply))
+    }
+}
+
+/// Map a parsed WHOIS status to a discovery verdict. Reply errors such as
+/// rate limiting are surfaced as errors so they are not mistaken for a
+/// definite answer.
+fn verdict_for(status: &DomainStatus) -> Result<CheckVerdict, String> {
+    match status {
+        DomainStatus::Available => Ok(CheckVerdict::Available),
+        DomainStatus::Unavailable
+        | DomainStatus::Expired
+        | DomainStatus::ErrorReservedByRegulator
+        | DomainStatus::ErrorUnregistrable => Ok(CheckVerdict::Registered),
+        DomainStatus::ErrorUnparsable | DomainStatus::ErrorNoContent => Ok(CheckVerdict::Unknown),
+        other => Err(other.as_str().to_string()),
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verdict_mapping() {
+        assert_eq!(
+            verdict_for(&DomainStatus::Available),
+            Ok(CheckVerdict::Available)
+        );
+        assert_eq!(
+            verdict_for(&DomainStatus::Expired),
+            Ok(CheckVerdict::Registered)
+        );
+        assert_eq!(
+            verdict_for(&DomainStatus::ErrorNoContent),
+            Ok(CheckVerdict::Unknown)
+        );
+        assert!(verdict_for(&DomainStatus::ErrorRateLimiting).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+//! Live probe for the wd-expiry drop-catch engine. A delegation in DNS
+//! means the name is still registered; otherwise RDAP and WHOIS must both
+//! agree it has been released before it counts as available.
+
+use crate::automation::expiry::{DropProbe, ProbeMethod, ProbeOutcome};
+use crate::availability::{is_domain_available, DomainStatus};
+use crate::{dns_lookup, perform_lookup_with_settings, rdap_lookup, LookupSettings};
+
+/// Probes availability through DNS, RDAP and WHOIS, in that order. A
+/// domain is only reported available once every enabled source says so.
+pub struct NetworkProbe {
+    settings: LookupSettings,
+    use_rdap: bool,
+}
+
+impl NetworkProbe {
+    pub fn new(settings: LookupSettings) -> Self {
+        Self {
+            settings,
+            use_rdap: true,
+        }
+    }
+
+    /// Skip the RDAP step, e.g. for TLDs without an RDAP service; WHOIS
+    /// alone then decides.
+    pub fn without_rdap(mut self) -> Self {
+        self.use_rdap = false;
+        self
+    }
+}
+
+impl DropProbe for NetworkProbe {
+    async fn probe(&self, domain: &str) -> ProbeOutcome {
+        if dns_lookup(domain).await.unwrap_or(false) {
+            return ProbeOutcome::Registered;
+        }
+
+        // An RDAP 404 also comes back for names the server isn't
+        // authoritative for, so it only counts once WHOIS agrees.
+        let method = if self.use_rdap {
+            match rdap_lookup(domain).await.map(|body| rdap_registered(&body)) {
+                Ok(Some(true)) => return ProbeOutcome::Registered,
+                Ok(Some(false)) => ProbeMethod::Rdap,
+                Ok(None) => return ProbeOutcome::Error("inconclusive RDAP response".into()),
+                Err(e) => return ProbeOutcome::Error(e),
+            }
+        } else {
+            ProbeMethod::Whois
+        };
+
+        let (domain, settings) = (domain.to_string(), self.settings.clone());
+        // The WHOIS client reads its socket synchronously; on the blocking
+        // pool it leaves the async workers free and a timeout can fire.
+        let reply = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(perform_lookup_with_settings(&domain, &settings))
+        })
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|reply| reply);
+        match reply {
+            Ok(reply) => match is_domain_available(&reply) {
+                DomainStatus::Available => ProbeOutcome::Available(method),
+                DomainStatus::Unavailable | DomainStatus::Expired => ProbeOutcome::Registered,
+                other => ProbeOutcome::Error(other.as_str().to_string()),
+            },
+            Err(e) => ProbeOutcome::Error(e),
+        }
+    }
+}
+
+/// Interpret an RDAP domain response: an object with `ldhName` is a live
+/// registration, an `errorCode` of 404 means the server has no record of
+/// it. Anything else is inconclusive.
+fn rdap_registered(body: &str) -> Option<bool> {
+    let json: serde_json::Value = serde_json::from_str(body).ok()?;
+    if json.get("ldhName").is_some() {
+        return Some(true);
+    }
+    match json.get("errorCode").and_then(|c| c.as_u64()) {
+        Some(404) => Some(false),
+        _ => None,
+    }
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rdap_registered() {
+        assert_eq!(rdap_registered(r#"{"ldhName":"EXAMPLE.COM"}"#), Some(true));
+        assert_eq!(
+            rdap_registered(r#"{"errorCode":404,"title":"Not Found"}"#),
+            Some(false)
+        );
+        assert_eq!(rdap_registered(r#"{"errorCode":429}"#), None);
+        assert_eq!(rdap_registered("<html>"), None);
+    }
+}
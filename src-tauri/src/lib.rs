@@ -6,12 +6,26 @@ pub mod automation {
     pub use wd_domain_automation::*;
 }
 
+#[cfg(feature = "domain-automation")]
+pub mod dropcatch;
+
+#[cfg(feature = "domain-automation")]
+pub mod monitor;
+
 #[cfg(feature = "domain-intelligence")]
 pub mod intelligence {
     pub use wd_domain_intelligence::*;
 }
 
+#[cfg(feature = "domain-intelligence")]
+pub mod discovery;
+
 #[cfg(feature = "domain-agentic")]
 pub mod agentic {
     pub use wd_domain_agentic::*;
 }
+
+#[cfg(feature = "api-server")]
+pub mod api {
+    pub use wd_api::*;
+}
@@ -0,0 +1,125 @@
+//! Live WHOIS probe for the wd-expiry watchlist monitor. Every lookup is
+//! also kept as a history snapshot, so changes can be diffed later.
+
+use std::sync::Arc;
+
+use crate::automation::expiry::{WatchObservation, WatchProbe};
+use crate::automation::history::{HistoryStore, LookupProtocol, Snapshot};
+use crate::availability::{get_domain_parameters, is_domain_available, DomainStatus};
+use crate::parser::{parse_nameservers, parse_raw_data, parse_status_codes, parse_whois_date};
+use crate::{perform_lookup_with_settings, LookupSettings};
+
+/// Looks watched domains up over WHOIS.
+pub struct WhoisWatchProbe {
+    settings: LookupSettings,
+    history: Option<Arc<HistoryStore>>,
+}
+
+impl WhoisWatchProbe {
+    pub fn new(settings: LookupSettings) -> Self {
+        Self {
+            settings,
+            history: None,
+        }
+    }
+
+    /// Record a snapshot of every successful lookup in `store`.
+    pub fn with_history(mut self, store: Arc<HistoryStore>) -> Self {
+        self.history = Some(store);
+        self
+    }
+}
+
+impl WatchProbe for WhoisWatchProbe {
+    async fn observe(&self, domain: &str) -> Result<WatchObservation, String> {
+        let (domain, settings) = (domain.to_string(), self.settings.clone());
+        let history = self.history.clone();
+        // The WHOIS client reads its socket synchronously and the history
+        // store writes to SQLite; on the blocking pool both leave the async
+        // workers free and a timeout can fire.
+        tokio::task::spawn_blocking(move || {
+            let reply =
+                futures::executor::block_on(perform_lookup_with_settings(&domain, &settings))?;
+            let (obs, snapshot) = observe_reply(&domain, reply)?;
+            if let Some(store) = &history {
+                if let Err(e) = store.insert(&snapshot) {
+                    log::warn!("Failed to record history snapshot for {domain}: {e}");
+                }
+            }
+            Ok(obs)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+}
+
+/// Interpret a WHOIS reply. Replies that could not be classified (rate
+/// limits, empty or unparsable answers) are errors rather than a status,
+/// so they never show up as a change.
+fn observe_reply(domain: &str, reply: String) -> Result<(WatchObservation, Snapshot), String> {
+    let status = is_domain_available(&reply);
+    if !matches!(
+        status,
+        DomainStatus::Available | DomainStatus::Unavailable | DomainStatus::Expired
+    ) {
+        return Err(format!("lookup returned {}", status.as_str()));
+    }
+    let nameservers = parse_nameservers(&reply);
+    let status_codes = parse_status_codes(&reply);
+    let params = get_domain_parameters(Some(domain.to_string()), Some(status.clone()), reply);
+    let expiry_date = params.expiry_date.as_deref().and_then(parse_whois_date);
+
+    let raw = params.whoisreply.unwrap_or_default();
+    let mut snapshot = Snapshot::new(domain, LookupProtocol::Whois, raw.as_str())
+        .with_nameservers(nameservers.clone())
+        .with_status_codes(status_codes.clone());
+    snapshot.fields = parse_raw_data(&raw)
+        .into_iter()
+        .map(|(k, v)| (k.to_lowercase(), v))
+        .collect();
+    snapshot.registrar = params.registrar.clone();
+    snapshot.created_date = params.creation_date.as_deref().and_then(parse_whois_date);
+    snapshot.updated_date = params.update_date.as_deref().and_then(parse_whois_date);
+    snapshot.expiry_date = expiry_date;
+
+    let obs = WatchObservation {
+        status: status.as_str().to_string(),
+        registrar: params.registrar,
+        nameservers,
+        expiry_date,
+        status_codes,
+    };
+    Ok((obs, snapshot))
+}
+
+// ─── Tests ───────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_observe_registered_reply() {
+        let reply = "Domain Name: EXAMPLE.COM\n\
+Registrar: Example Registrar, Inc.\n\
+Registry Expiry Date: 2027-08-13T04:00:00Z\n\
+Domain Status: clientTransferProhibited https://icann.org/epp#clientTransferProhibited\n\
+Name Server: A.IANA-SERVERS.NET\n\
+Name Server: B.IANA-SERVERS.NET\n";
+        let (obs, snapshot) = observe_reply("example.com", reply.to_string()).unwrap();
+        assert_eq!(obs.status, "unavailable");
+        assert_eq!(obs.registrar.as_deref(), Some("Example Registrar, Inc."));
+        assert_eq!(
+            obs.nameservers,
+            ["a.iana-servers.net", "b.iana-servers.net"]
+        );
+        assert!(obs.expiry_date.is_some());
+        assert_eq!(snapshot.status_codes, ["clientTransferProhibited"]);
+        assert_eq!(obs.status_codes, snapshot.status_codes);
+    }
+
+    #[test]
+    fn test_unclassified_reply_is_an_error() {
+        assert!(observe_reply("example.com", String::new()).is_err());
+    }
+}
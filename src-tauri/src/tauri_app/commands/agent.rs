@@ -0,0 +1,304 @@
+use std::sync::Arc;
+
+use tauri::{Emitter, Runtime};
+
+use crate::agentic::agent::{
+    Agent, AgentConfig, AgentResult, BuiltinTools, ConfirmationChannel, ConfirmationDecision,
+    ConfirmationRequest, KnowledgeStore, Pipeline, PipelineKind, PipelineReport, PipelineRun,
+    PipelineRunner, Sandbox, SandboxConfig, ToolExecutor, Toolbox,
+};
+use crate::agentic::audit::agent_sink;
+use crate::agentic::llm::{LlmClient, LlmConfig, ProviderKind};
+use crate::agentic::search::{HistoryStore, SearchIndex};
+use crate::proxy::Permission;
+use crate::storage::audit::local_actor;
+use crate::tauri_app::commands::audit::{open_log, require_permission};
+use crate::tauri_app::state::AppState;
+use crate::tauri_app::support::{
+    get_current_profile, get_profile_dir, get_user_data_dir, load_lookup_settings,
+    load_openai_settings_from_json,
+};
+
+const SYSTEM_PROMPT: &str = "You are a domain intelligence analyst. Answer the user's question \
+     with the available tools and say so when a tool fails instead of guessing.";
+
+const DEFAULT_MODEL: &str = "gpt-4o-mini";
+
+/// An agent with the built-in tools over the current profile's history,
+/// search index, agent memory and request cache, and a client for the
+/// model configured in `settings.json`. Every agent event is emitted as
+/// `agent:event` and recorded in the audit log; tool calls that need
+/// confirmation wait on `confirmations`.
+async fn prepare<R: Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+    confirmations: ConfirmationChannel,
+) -> Result<(Agent, ToolExecutor, LlmClient), String> {
+    let data_dir = get_user_data_dir(app_handle)?;
+    let profile = get_current_profile(app_handle)?;
+    let dir = get_profile_dir(app_handle, &profile)?;
+    let lookup = load_lookup_settings(app_handle);
+    let ai = std::fs::read_to_string(data_dir.join("settings.json"))
+        .ok()
+        .and_then(|content| load_openai_settings_from_json(&content))
+        .unwrap_or_default();
+
+    let api_key = ai.api_key.filter(|k| !k.is_empty()).ok_or(
+        "No AI API key is configured; set one in the AI settings before running the agent",
+    )?;
+    let mut config = LlmConfig::new(
+        ProviderKind::OpenAi,
+        ai.model.as_deref().unwrap_or(DEFAULT_MODEL),
+    )
+    .with_api_key(&api_key);
+    if let Some(url) = ai.url.filter(|u| !u.is_empty()) {
+        // The AI settings hold the full completions endpoint.
+        config = config.with_api_url(url.trim_end_matches("/chat/completions"));
+    }
+    let client = LlmClient::new(config).map_err(|e| e.to_string())?;
+
+    let path = |name: String| dir.join(name).to_string_lossy().into_owned();
+    let history_path = path(format!("snapshots-{}.sqlite", profile));
+    let index_path = path(format!("search-index-{}.sqlite", profile));
+    let memory_path = path(format!("agent-memory-{}.sqlite", profile));
+    let audit = open_log(app_handle).await?;
+    let (history, index, knowledge) = tokio::task::spawn_blocking(move || {
+        Ok::<_, rusqlite::Error>((
+            HistoryStore::open(&history_path)?,
+            SearchIndex::open(&index_path)?,
+            KnowledgeStore::open(&memory_path)?,
+        ))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+    let knowledge = Arc::new(knowledge);
+
+    let mut executor = ToolExecutor::new();
+    BuiltinTools::new(lookup)
+        .with_history(Arc::new(history))
+        .with_search(Arc::new(index))
+        .with_knowledge(Arc::clone(&knowledge))
+        .with_cache(dir.join("request-cache.sqlite"))
+        .with_export_dir(dir.join("exports"))
+        .install(&mut executor);
+
+    // Tools that write files or clear the cache wait for
+    // `agent_confirmation_resolve`.
+    let mut sandbox = Sandbox::new(SandboxConfig {
+        require_confirmation: true,
+        ..SandboxConfig::default()
+    });
+    sandbox.require_confirmation_for_defaults();
+
+    let audit = agent_sink(audit, local_actor());
+    let app = app_handle.clone();
+    let agent = Agent::new(AgentConfig::default())
+        .with_sandbox(sandbox)
+        .with_confirmation_channel(confirmations)
+        .with_knowledge_store(knowledge)
+        .with_event_sink(move |event| {
+            let _ = app.emit("agent:event", event);
+            audit(event);
+        });
+    Ok((agent, executor, client))
+}
+
+/// Answer a free-form question with every built-in tool available.
+#[tauri::command]
+pub async fn agent_ask<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    data: AppState<'_>,
+    query: String,
+) -> Result<AgentResult, String> {
+    if query.trim().is_empty() {
+        return Err("A question is required".into());
+    }
+    require_permission(&app_handle, Permission::AgentToolUse).await?;
+    let (mut agent, executor, client) =
+        prepare(&app_handle, data.agent_confirmations.clone()).await?;
+    let tools = Toolbox::full().all_definitions();
+    Ok(agent
+        .run_with_client(SYSTEM_PROMPT, &query, &tools, &executor, &client)
+        .await)
+}
+
+/// Run a pre-built pipeline. `target` is the domain or brand for the kinds
+/// that take one; `input` (e.g. a domain list) is appended to every step.
+#[tauri::command]
+pub async fn agent_pipeline_run<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    data: AppState<'_>,
+    kind: PipelineKind,
+    target: Option<String>,
+    input: Option<String>,
+) -> Result<PipelineReport, String> {
+    let pipeline = Pipeline::for_kind(&kind, target.as_deref())?;
+    require_permission(&app_handle, Permission::AgentToolUse).await?;
+    let (agent, executor, client) = prepare(&app_handle, data.agent_confirmations.clone()).await?;
+    let mut run = PipelineRun::new(pipeline);
+    if let Some(input) = input.as_deref().filter(|i| !i.trim().is_empty()) {
+        run = run.with_input(input);
+    }
+    Ok(PipelineRunner::new(agent)
+        .run_with_client(&mut run, &executor, &client)
+        .await)
+}
+
+/// Tool calls of running agents that wait for the user's decision. Each
+/// one is also announced by a `confirmation_requested` agent event.
+#[tauri::command]
+pub async fn agent_confirmations_list(
+    data: AppState<'_>,
+) -> Result<Vec<ConfirmationRequest>, String> {
+    Ok(data.agent_confirmations.pending())
+}
+
+/// Approve, deny or edit the pending tool call `id`; the agent resumes
+/// with the decision.
+#[tauri::command]
+pub async fn agent_confirmation_resolve(
+    data: AppState<'_>,
+    id: String,
+    decision: ConfirmationDecision,
+) -> Result<(), String> {
+    data.agent_confirmations.resolve(&id, decision)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::{json, Value};
+    use tauri::ipc::{CallbackFn, InvokeBody};
+    use tauri::test::{get_ipc_response, mock_builder, mock_context, noop_assets, INVOKE_KEY};
+    use tauri::webview::InvokeRequest;
+    use tauri::{Manager, WebviewWindow, WebviewWindowBuilder};
+
+    use super::*;
+    use crate::agentic::llm::{
+        CompletionResponse, FinishReason, FunctionCall, Message, TokenUsage, ToolCall,
+    };
+    use crate::lookup::LookupSettings;
+    use crate::tauri_app::state::AppData;
+
+    fn invoke(
+        webview: &WebviewWindow<tauri::test::MockRuntime>,
+        cmd: &str,
+        body: Value,
+    ) -> Result<Value, Value> {
+        get_ipc_response(
+            webview,
+            InvokeRequest {
+                cmd: cmd.into(),
+                callback: CallbackFn(0),
+                error: CallbackFn(1),
+                url: "http://tauri.localhost".parse().unwrap(),
+                body: InvokeBody::Json(body),
+                headers: Default::default(),
+                invoke_key: INVOKE_KEY.to_string(),
+            },
+        )
+        .map(|body| body.deserialize().unwrap())
+    }
+
+    fn response(message: Message, finish_reason: FinishReason) -> CompletionResponse {
+        CompletionResponse {
+            id: "r".into(),
+            model: "test".into(),
+            message,
+            finish_reason,
+            usage: TokenUsage {
+                prompt_tokens: 10,
+                completion_tokens: 5,
+                total_tokens: 15,
+                estimated_cost_usd: None,
+            },
+            latency_ms: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_confirmation_commands_resume_agent() {
+        let app = mock_builder()
+            .manage(AppData::new())
+            .invoke_handler(tauri::generate_handler![
+                agent_confirmations_list,
+                agent_confirmation_resolve
+            ])
+            .build(mock_context(noop_assets()))
+            .unwrap();
+        let webview = WebviewWindowBuilder::new(&app, "main", Default::default())
+            .build()
+            .unwrap();
+        let channel = app.state::<AppData>().agent_confirmations.clone();
+
+        let dir = std::env::temp_dir().join(format!("wd_agent_confirm_{}", std::process::id()));
+        let mut executor = ToolExecutor::new();
+        BuiltinTools::new(LookupSettings::default())
+            .with_export_dir(dir.join("exports"))
+            .install(&mut executor);
+        let mut sandbox = Sandbox::new(SandboxConfig {
+            require_confirmation: true,
+            ..SandboxConfig::default()
+        });
+        sandbox.require_confirmation_for_defaults();
+        let mut agent = Agent::new(AgentConfig::default())
+            .with_sandbox(sandbox)
+            .with_confirmation_channel(channel);
+
+        // The frontend's side: poll the pending list, then approve.
+        let responder = tokio::task::spawn_blocking(move || {
+            let request = loop {
+                let pending = invoke(&webview, "agent_confirmations_list", json!({})).unwrap();
+                if let Some(request) = pending.as_array().and_then(|p| p.first()).cloned() {
+                    break request;
+                }
+                std::thread::sleep(Duration::from_millis(10));
+            };
+            assert_eq!(request["tool_name"], "export_csv");
+            let id = request["id"].as_str().unwrap().to_string();
+            invoke(
+                &webview,
+                "agent_confirmation_resolve",
+                json!({"id": id, "decision": {"decision": "approve"}}),
+            )
+            .unwrap();
+            assert!(invoke(
+                &webview,
+                "agent_confirmation_resolve",
+                json!({"id": id, "decision": {"decision": "approve"}}),
+            )
+            .is_err());
+        });
+
+        let mut calls = 0;
+        let result = agent
+            .run_async("System", "Export it", &[], &executor, |_, _| {
+                calls += 1;
+                let reply = if calls == 1 {
+                    response(
+                        Message::assistant_tool_calls(vec![ToolCall {
+                            id: "tc1".into(),
+                            function: FunctionCall {
+                                name: "export_csv".into(),
+                                arguments: json!({"data": ["a.com"], "filename": "out"}),
+                            },
+                        }]),
+                        FinishReason::ToolUse,
+                    )
+                } else {
+                    response(Message::assistant("Exported."), FinishReason::Stop)
+                };
+                async move { Ok(reply) }
+            })
+            .await;
+        responder.await.unwrap();
+
+        assert!(result.completed);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("exports").join("out.csv")).unwrap(),
+            "a.com\n"
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
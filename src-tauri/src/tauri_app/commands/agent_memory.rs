@@ -0,0 +1,88 @@
+use std::path::PathBuf;
+
+use chrono::{Duration, Utc};
+use tauri::Runtime;
+
+use crate::agentic::agent::{KnowledgeStore, StoredKnowledge};
+use crate::tauri_app::support::{get_current_profile, get_profile_dir};
+
+fn memory_path<R: Runtime>(app_handle: &tauri::AppHandle<R>) -> Result<PathBuf, String> {
+    let profile = get_current_profile(app_handle)?;
+    Ok(get_profile_dir(app_handle, &profile)?.join(format!("agent-memory-{}.sqlite", profile)))
+}
+
+/// Open the current profile's store on a blocking thread and run `f` on it.
+async fn with_store<R, T, F>(app_handle: &tauri::AppHandle<R>, f: F) -> Result<T, String>
+where
+    R: Runtime,
+    T: Send + 'static,
+    F: FnOnce(&KnowledgeStore) -> rusqlite::Result<T> + Send + 'static,
+{
+    let path = memory_path(app_handle)?;
+    tokio::task::spawn_blocking(move || {
+        let store = KnowledgeStore::open(&path.to_string_lossy()).map_err(|e| e.to_string())?;
+        f(&store).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn agent_memory_list<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    domain: Option<String>,
+    include_expired: Option<bool>,
+) -> Result<Vec<StoredKnowledge>, String> {
+    with_store(&app_handle, move |store| {
+        store.list(domain.as_deref(), include_expired.unwrap_or(false))
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn agent_memory_search<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<StoredKnowledge>, String> {
+    with_store(&app_handle, move |store| {
+        store.search(&query, limit.unwrap_or(20))
+    })
+    .await
+}
+
+/// Delete one entry by id, or every entry for `domain`. Returns the number
+/// of entries removed.
+#[tauri::command]
+pub async fn agent_memory_forget<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    id: Option<i64>,
+    domain: Option<String>,
+) -> Result<usize, String> {
+    match (id, domain) {
+        (Some(id), _) => {
+            with_store(&app_handle, move |store| store.forget(id).map(usize::from)).await
+        }
+        (None, Some(domain)) => {
+            with_store(&app_handle, move |store| store.forget_domain(&domain)).await
+        }
+        (None, None) => Err("Either an id or a domain is required".into()),
+    }
+}
+
+/// Delete expired entries and, when `older_than_days` is given, entries
+/// recorded before then. Returns the number of entries removed.
+#[tauri::command]
+pub async fn agent_memory_prune<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    older_than_days: Option<u32>,
+) -> Result<usize, String> {
+    with_store(&app_handle, move |store| {
+        let mut removed = store.prune_expired()?;
+        if let Some(days) = older_than_days {
+            removed += store.prune_older_than(Utc::now() - Duration::days(days as i64))?;
+        }
+        Ok(removed)
+    })
+    .await
+}
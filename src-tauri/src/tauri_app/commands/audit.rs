@@ -0,0 +1,233 @@
+use std::sync::Arc;
+
+use chrono::DateTime;
+use serde::Deserialize;
+use tauri::{Manager, Runtime};
+
+use crate::proxy::{Permission, Rbac, RBAC_AUDIT_FILE, RBAC_FILE};
+use crate::storage::audit::{
+    local_actor, Action, AuditEntry, AuditEvent, AuditLog, AuditQuery, Source, VerifyReport,
+    AUDIT_FILE,
+};
+use crate::tauri_app::state::AppData;
+use crate::tauri_app::support::get_user_data_dir;
+
+/// The audit log kept in the app state, opened on first use.
+pub(crate) async fn open_log<R: Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+) -> Result<Arc<AuditLog>, String> {
+    let data = app_handle.state::<AppData>();
+    data.audit_log
+        .get_or_try_init(|| async {
+            let path = get_user_data_dir(app_handle)?
+                .join(AUDIT_FILE)
+                .to_string_lossy()
+                .into_owned();
+            tokio::task::spawn_blocking(move || AuditLog::open(&path))
+                .await
+                .map_err(|e| e.to_string())?
+                .map(Arc::new)
+                .map_err(|e| e.to_string())
+        })
+        .await
+        .cloned()
+}
+
+/// Record a GUI action by the local user. Failures are logged rather than
+/// failing the command that performed the action.
+pub(crate) async fn record<R: Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+    action: Action,
+    target: Option<String>,
+) {
+    record_event(app_handle, action, target, Vec::new()).await
+}
+
+/// [`record`] with extra details.
+pub(crate) async fn record_event<R: Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+    action: Action,
+    target: Option<String>,
+    details: Vec<(&'static str, serde_json::Value)>,
+) {
+    let mut event = AuditEvent::new(local_actor(), Source::Gui, action);
+    event.target = target;
+    for (key, value) in details {
+        event = event.with_detail(key, value);
+    }
+    let result = match open_log(app_handle).await {
+        Ok(log) => tokio::task::spawn_blocking(move || log.append(event).map(|_| ()))
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|r| r.map_err(|e| e.to_string())),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        log::warn!("could not record {} in the audit log: {e}", action.as_str());
+    }
+}
+
+/// Fail unless the local user holds `permission` under the app's RBAC file.
+/// The local user is unrestricted until roles are bound to them; refusals
+/// are recorded in the RBAC denial log and the audit log.
+pub(crate) async fn require_permission<R: Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+    permission: Permission,
+) -> Result<(), String> {
+    let dir = get_user_data_dir(app_handle)?;
+    let name = serde_json::to_value(&permission)
+        .ok()
+        .and_then(|v| v.as_str().map(String::from))
+        .unwrap_or_default();
+    let allowed = tokio::task::spawn_blocking(move || {
+        let rbac = Rbac::open(dir.join(RBAC_FILE))?.with_audit_log(dir.join(RBAC_AUDIT_FILE));
+        Ok::<_, String>(rbac.check_if_bound(&local_actor(), &permission))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    if allowed {
+        return Ok(());
+    }
+    record(app_handle, Action::PermissionDenied, Some(name.clone())).await;
+    Err(format!("Permission denied: {}", name))
+}
+
+/// Audit log filter sent by the frontend.
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditFilter {
+    pub actor: Option<String>,
+    pub action: Option<String>,
+    pub source: Option<String>,
+    pub since_ms: Option<i64>,
+    pub until_ms: Option<i64>,
+    pub limit: Option<usize>,
+}
+
+impl AuditFilter {
+    fn into_query(self) -> Result<AuditQuery, String> {
+        let mut query = AuditQuery {
+            actor: self.actor,
+            limit: self.limit,
+            since: self.since_ms.and_then(DateTime::from_timestamp_millis),
+            until: self.until_ms.and_then(DateTime::from_timestamp_millis),
+            ..AuditQuery::default()
+        };
+        if let Some(action) = self.action {
+            query.action =
+                Some(Action::parse(&action).ok_or(format!("Unknown action: {}", action))?);
+        }
+        if let Some(source) = self.source {
+            query.source =
+                Some(Source::parse(&source).ok_or(format!("Unknown source: {}", source))?);
+        }
+        Ok(query)
+    }
+}
+
+#[tauri::command]
+pub async fn audit_query<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    filter: Option<AuditFilter>,
+) -> Result<Vec<AuditEntry>, String> {
+    let query = filter.unwrap_or_default().into_query()?;
+    let log = open_log(&app_handle).await?;
+    tokio::task::spawn_blocking(move || log.query(&query))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn audit_verify<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+) -> Result<VerifyReport, String> {
+    let log = open_log(&app_handle).await?;
+    tokio::task::spawn_blocking(move || log.verify())
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Write matching entries to `path` as JSONL and return how many.
+#[tauri::command]
+pub async fn audit_export<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    path: String,
+    filter: Option<AuditFilter>,
+) -> Result<usize, String> {
+    let query = filter.unwrap_or_default().into_query()?;
+    let log = open_log(&app_handle).await?;
+    tokio::task::spawn_blocking(move || {
+        let mut out =
+            std::io::BufWriter::new(std::fs::File::create(&path).map_err(|e| e.to_string())?);
+        log.export_jsonl(&query, &mut out)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use tauri::ipc::{CallbackFn, InvokeBody};
+    use tauri::test::{get_ipc_response, mock_builder, mock_context, noop_assets, INVOKE_KEY};
+    use tauri::webview::InvokeRequest;
+    use tauri::{WebviewWindow, WebviewWindowBuilder};
+
+    use super::*;
+    use crate::tauri_app::commands::{bulk, cache};
+
+    fn invoke(
+        webview: &WebviewWindow<tauri::test::MockRuntime>,
+        cmd: &str,
+        body: Value,
+    ) -> Result<Value, Value> {
+        get_ipc_response(
+            webview,
+            InvokeRequest {
+                cmd: cmd.into(),
+                callback: CallbackFn(0),
+                error: CallbackFn(1),
+                url: "http://tauri.localhost".parse().unwrap(),
+                body: InvokeBody::Json(body),
+                headers: Default::default(),
+                invoke_key: INVOKE_KEY.to_string(),
+            },
+        )
+        .map(|body| body.deserialize().unwrap())
+    }
+
+    #[test]
+    fn test_viewer_is_refused_bulk_runs_and_cache_purges() {
+        let mut context = mock_context(noop_assets());
+        context.config_mut().identifier = format!("wd-rbac-test-{}", std::process::id());
+        let app = mock_builder()
+            .manage(AppData::new())
+            .invoke_handler(tauri::generate_handler![
+                bulk::bulk_whois_lookup,
+                cache::db_gui_cache_clear
+            ])
+            .build(context)
+            .unwrap();
+        let webview = WebviewWindowBuilder::new(&app, "main", Default::default())
+            .build()
+            .unwrap();
+        let dir = get_user_data_dir(app.handle()).unwrap();
+        let rbac = Rbac::open(dir.join(RBAC_FILE)).unwrap();
+        rbac.bind(local_actor(), vec!["viewer".into()]);
+        rbac.save().unwrap();
+
+        let bulk = invoke(
+            &webview,
+            "bulk_whois_lookup",
+            json!({"domains": ["example.com"], "concurrency": 1, "timeoutMs": 1000}),
+        );
+        let purge = invoke(&webview, "db_gui_cache_clear", json!({}));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(bulk.unwrap_err(), json!("Permission denied: bulk_run"));
+        assert_eq!(purge.unwrap_err(), json!("Permission denied: cache_purge"));
+    }
+}
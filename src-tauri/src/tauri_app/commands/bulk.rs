@@ -1,18 +1,18 @@
-use std::sync::Arc;
+use std::time::Duration;
 
 use crate::{
-    availability::{get_domain_parameters, is_domain_available},
+    bulk::{expand_domains, whois, BulkRunner},
     export::{export_results, BulkResult, ExportOpts},
-    perform_lookup_with_settings,
 };
-use futures::future::join_all;
 use rayon::prelude::*;
 use tauri::{Emitter, Runtime};
-use tokio::sync::Semaphore;
 
+use crate::proxy::Permission;
+use crate::storage::audit::Action;
 use crate::tauri_app::{
+    commands::audit::{record, record_event, require_permission},
     state::AppState,
-    support::{domain_status_to_string, BulkProgress},
+    support::BulkProgress,
 };
 
 #[tauri::command]
@@ -24,162 +24,89 @@ pub async fn bulk_whois_lookup<R: Runtime>(
     concurrency: usize,
     timeout_ms: u64,
 ) -> Result<Vec<BulkResult>, String> {
-    {
-        let mut state = data.bulk_state.lock().await;
-        state.paused = false;
-        state.stopped = false;
-    }
+    require_permission(&app_handle, Permission::BulkRun).await?;
+    run_bulk(app_handle, data, domains, tlds, concurrency, timeout_ms).await
+}
 
-    let expanded_domains = if let Some(ref tld_list) = tlds {
-        if !tld_list.is_empty() {
-            let mut expanded = Vec::new();
-            for domain in &domains {
-                let base = domain.split('.').next().unwrap_or(domain);
-                for tld in tld_list {
-                    let tld_clean = tld.trim_start_matches('.');
-                    expanded.push(format!("{}.{}", base, tld_clean));
-                }
-            }
-            expanded
-        } else {
-            domains
-        }
-    } else {
-        domains
-    };
+/// Look `domains` up with the bulk runner, reporting progress as
+/// `bulk:status` events. Callers check [`Permission::BulkRun`] first.
+async fn run_bulk<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    data: AppState<'_>,
+    domains: Vec<String>,
+    tlds: Option<Vec<String>>,
+    concurrency: usize,
+    timeout_ms: u64,
+) -> Result<Vec<BulkResult>, String> {
+    data.bulk_control.reset();
 
+    let expanded_domains = expand_domains(&domains, &tlds.unwrap_or_default());
     let lookup_settings = data.lookup_settings.lock().await.clone();
     let total = expanded_domains.len() as u32;
-    let semaphore = Arc::new(Semaphore::new(concurrency));
-    let mut tasks = Vec::new();
-    let sent_counter = Arc::new(tokio::sync::Mutex::new(0u32));
-    let per_domain_timeout = if timeout_ms > 0 {
-        Some(tokio::time::Duration::from_millis(timeout_ms))
-    } else {
-        None
-    };
-
-    for domain in expanded_domains {
-        let sem = Arc::clone(&semaphore);
-        let app = app_handle.clone();
-        let sent = Arc::clone(&sent_counter);
-        let bulk_state = Arc::clone(&data.bulk_state);
-        let settings = lookup_settings.clone();
-        let domain_timeout = per_domain_timeout;
-
-        tasks.push(tokio::spawn(async move {
-            {
-                let state = bulk_state.lock().await;
-                if state.stopped {
-                    return BulkResult {
-                        domain,
-                        data: None,
-                        error: Some("Stopped".into()),
-                        status: "error".into(),
-                        params: None,
-                    };
-                }
-            }
-
-            loop {
-                let state = bulk_state.lock().await;
-                if state.stopped {
-                    return BulkResult {
-                        domain,
-                        data: None,
-                        error: Some("Stopped".into()),
-                        status: "error".into(),
-                        params: None,
-                    };
-                }
-                if !state.paused {
-                    break;
-                }
-                drop(state);
-                tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
-            }
-
-            let _permit = match sem.acquire().await {
-                Ok(p) => p,
-                Err(_) => {
-                    return BulkResult {
-                        domain,
-                        data: None,
-                        error: Some("Semaphore closed".into()),
-                        status: "error".into(),
-                        params: None,
-                    }
-                }
-            };
-
-            let lookup_future = perform_lookup_with_settings(&domain, &settings);
-            let lookup_result = if let Some(timeout_dur) = domain_timeout {
-                match tokio::time::timeout(timeout_dur, lookup_future).await {
-                    Ok(res) => res,
-                    Err(_) => Err(format!("Timeout after {}ms", timeout_dur.as_millis())),
-                }
-            } else {
-                lookup_future.await
-            };
-
-            let (data_val, err, status, params) = match lookup_result {
-                Ok(res) => {
-                    let s = is_domain_available(&res);
-                    let p =
-                        get_domain_parameters(Some(domain.clone()), Some(s.clone()), res.clone());
-                    let s_str = domain_status_to_string(&s);
-                    (Some(res), None, s_str, Some(p))
-                }
-                Err(e) => (None, Some(e.to_string()), "error".to_string(), None),
-            };
-
-            let mut s = sent.lock().await;
-            *s += 1;
+    record_event(
+        &app_handle,
+        Action::BulkStarted,
+        None,
+        vec![
+            ("domains", total.into()),
+            ("concurrency", concurrency.into()),
+        ],
+    )
+    .await;
+
+    let runner = BulkRunner::new(whois(lookup_settings))
+        .with_concurrency(concurrency)
+        .with_timeout((timeout_ms > 0).then(|| Duration::from_millis(timeout_ms)))
+        .with_control(data.bulk_control.clone());
+    let app = app_handle.clone();
+    let results = runner
+        .run(expanded_domains, move |_, sent, total| {
             let pct = if total > 0 {
-                ((*s as f64 / total as f64) * 1000.0).round() / 10.0
+                ((sent as f64 / total as f64) * 1000.0).round() / 10.0
             } else {
                 0.0
             };
             let _ = app.emit(
                 "bulk:status",
                 BulkProgress {
-                    sent: *s,
-                    total,
+                    sent: sent as u32,
+                    total: total as u32,
                     sent_percent: pct,
                 },
             );
-
-            BulkResult {
-                domain,
-                data: data_val,
-                error: err,
-                status,
-                params,
-            }
-        }));
-    }
-
-    let results = join_all(tasks).await;
-    Ok(results.into_iter().filter_map(|r| r.ok()).collect())
+        })
+        .await;
+    Ok(results)
 }
 
 #[tauri::command]
-pub async fn bulk_whois_pause(data: AppState<'_>) -> Result<(), String> {
-    data.bulk_state.lock().await.paused = true;
+pub async fn bulk_whois_pause<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    data: AppState<'_>,
+) -> Result<(), String> {
+    require_permission(&app_handle, Permission::BulkRun).await?;
+    data.bulk_control.pause();
     Ok(())
 }
 
 #[tauri::command]
-pub async fn bulk_whois_continue(data: AppState<'_>) -> Result<(), String> {
-    data.bulk_state.lock().await.paused = false;
+pub async fn bulk_whois_continue<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    data: AppState<'_>,
+) -> Result<(), String> {
+    require_permission(&app_handle, Permission::BulkRun).await?;
+    data.bulk_control.resume();
     Ok(())
 }
 
 #[tauri::command]
-pub async fn bulk_whois_stop(data: AppState<'_>) -> Result<(), String> {
-    let mut state = data.bulk_state.lock().await;
-    state.stopped = true;
-    state.paused = false;
+pub async fn bulk_whois_stop<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    data: AppState<'_>,
+) -> Result<(), String> {
+    require_permission(&app_handle, Permission::BulkRun).await?;
+    data.bulk_control.stop();
+    record(&app_handle, Action::BulkStopped, None).await;
     Ok(())
 }
 
@@ -192,6 +119,7 @@ pub async fn bulk_whois_lookup_from_file<R: Runtime>(
     concurrency: usize,
     timeout_ms: u64,
 ) -> Result<Vec<BulkResult>, String> {
+    require_permission(&app_handle, Permission::BulkRun).await?;
     let raw = tokio::fs::read_to_string(&path)
         .await
         .map_err(|e| format!("Failed to read {}: {}", path, e))?;
@@ -204,7 +132,7 @@ pub async fn bulk_whois_lookup_from_file<R: Runtime>(
     .await
     .map_err(|e| e.to_string())?;
 
-    bulk_whois_lookup(app_handle, data, domains, tlds, concurrency, timeout_ms).await
+    run_bulk(app_handle, data, domains, tlds, concurrency, timeout_ms).await
 }
 
 #[tauri::command]
@@ -216,6 +144,7 @@ pub async fn bulk_whois_lookup_from_content<R: Runtime>(
     concurrency: usize,
     timeout_ms: u64,
 ) -> Result<Vec<BulkResult>, String> {
+    require_permission(&app_handle, Permission::BulkRun).await?;
     let domains: Vec<String> = tokio::task::spawn_blocking(move || {
         content
             .par_lines()
@@ -226,14 +155,27 @@ pub async fn bulk_whois_lookup_from_content<R: Runtime>(
     .await
     .map_err(|e| e.to_string())?;
 
-    bulk_whois_lookup(app_handle, data, domains, tlds, concurrency, timeout_ms).await
+    run_bulk(app_handle, data, domains, tlds, concurrency, timeout_ms).await
 }
 
 #[tauri::command]
-pub async fn bulk_whois_export(
+pub async fn bulk_whois_export<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
     results: Vec<BulkResult>,
     options: ExportOpts,
     path: String,
 ) -> Result<(), String> {
-    export_results(&results, &options, &path)
+    require_permission(&app_handle, Permission::Export).await?;
+    export_results(&results, &options, &path)?;
+    record_event(
+        &app_handle,
+        Action::ResultsExported,
+        Some(path),
+        vec![
+            ("results", results.len().into()),
+            ("format", options.filetype.into()),
+        ],
+    )
+    .await;
+    Ok(())
 }
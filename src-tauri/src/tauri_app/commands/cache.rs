@@ -4,6 +4,9 @@ use crate::{db_cache_get, db_cache_set};
 use rusqlite::Connection;
 use tauri::Runtime;
 
+use crate::proxy::Permission;
+use crate::storage::audit::Action;
+use crate::tauri_app::commands::audit::{record, record_event, require_permission};
 use crate::tauri_app::support::{get_current_profile, get_profile_dir};
 
 #[tauri::command]
@@ -37,6 +40,7 @@ pub async fn db_gui_cache_set<R: Runtime>(
 
 #[tauri::command]
 pub async fn db_gui_cache_clear<R: Runtime>(app_handle: tauri::AppHandle<R>) -> Result<(), String> {
+    require_permission(&app_handle, Permission::CachePurge).await?;
     let profile = get_current_profile(&app_handle)?;
     let path = get_profile_dir(&app_handle, &profile)?.join("request-cache.sqlite");
     if !path.exists() {
@@ -46,10 +50,12 @@ pub async fn db_gui_cache_clear<R: Runtime>(app_handle: tauri::AppHandle<R>) ->
         let conn = Connection::open(&path).map_err(|e| e.to_string())?;
         conn.execute("DELETE FROM cache", [])
             .map_err(|e| e.to_string())?;
-        Ok(())
+        Ok::<_, String>(())
     })
     .await
-    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())??;
+    record(&app_handle, Action::CacheCleared, Some(profile)).await;
+    Ok(())
 }
 
 #[tauri::command]
@@ -59,6 +65,7 @@ pub async fn cache_merge<R: Runtime>(
 ) -> Result<(), String> {
     let profile = get_current_profile(&app_handle)?;
     let dest_path = get_profile_dir(&app_handle, &profile)?.join("request-cache.sqlite");
+    let paths_count = paths.len();
 
     tokio::task::spawn_blocking(move || {
         let dest_conn = Connection::open(&dest_path).map_err(|e| e.to_string())?;
@@ -97,8 +104,16 @@ pub async fn cache_merge<R: Runtime>(
                     .map_err(|e| e.to_string())?;
             }
         }
-        Ok(())
+        Ok::<_, String>(())
     })
     .await
-    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())??;
+    record_event(
+        &app_handle,
+        Action::CacheMerged,
+        Some(profile),
+        vec![("sources", paths_count.into())],
+    )
+    .await;
+    Ok(())
 }
@@ -0,0 +1,49 @@
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use tauri::{Emitter, Runtime};
+
+use crate::discovery::{Discovery, DiscoveryConfig, DiscoveryReport, LookupChecker};
+use crate::intelligence::domgen::DomainScorer;
+use crate::tauri_app::state::AppState;
+
+/// Generate candidates, pre-screen with DNS, confirm with WHOIS and stop once
+/// `config.target` available domains are found. Emits `discovery:progress`
+/// with `{ progress, event }` after every completed check.
+#[tauri::command]
+pub async fn discovery_run<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    data: AppState<'_>,
+    config: DiscoveryConfig,
+    timeout_ms: u64,
+    rank: Option<bool>,
+) -> Result<DiscoveryReport, String> {
+    if config.combinator.words.is_empty() {
+        return Err("At least one keyword is required".into());
+    }
+    let stop = Arc::clone(&data.discovery_stop);
+    stop.store(false, Ordering::Relaxed);
+
+    let settings = data.lookup_settings.lock().await.clone();
+    let checker = LookupChecker::new(settings).with_timeout(timeout_ms);
+    let mut discovery = Discovery::new(config, checker).with_cancel_flag(stop);
+    if rank.unwrap_or(true) {
+        discovery = discovery.with_scorer(DomainScorer::default());
+    }
+
+    let report = discovery
+        .run(|progress, event| {
+            let _ = app_handle.emit(
+                "discovery:progress",
+                serde_json::json!({ "progress": progress, "event": event }),
+            );
+        })
+        .await;
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn discovery_stop(data: AppState<'_>) -> Result<(), String> {
+    data.discovery_stop.store(true, Ordering::Relaxed);
+    Ok(())
+}
@@ -4,6 +4,9 @@ use crate::{db_history_get, db_history_get_filtered, HistoryEntry};
 use rusqlite::Connection;
 use tauri::Runtime;
 
+use crate::proxy::Permission;
+use crate::storage::audit::Action;
+use crate::tauri_app::commands::audit::{record, record_event, require_permission};
 use crate::tauri_app::support::{get_current_profile, get_profile_dir, HistoryPage};
 
 #[tauri::command]
@@ -66,6 +69,7 @@ pub async fn db_gui_history_get_filtered<R: Runtime>(
 pub async fn db_gui_history_clear<R: Runtime>(
     app_handle: tauri::AppHandle<R>,
 ) -> Result<(), String> {
+    require_permission(&app_handle, Permission::HistoryDelete).await?;
     let profile = get_current_profile(&app_handle)?;
     let path = get_profile_dir(&app_handle, &profile)?.join(format!("history-{}.sqlite", profile));
     if !path.exists() {
@@ -75,10 +79,12 @@ pub async fn db_gui_history_clear<R: Runtime>(
         let conn = Connection::open(&path).map_err(|e| e.to_string())?;
         conn.execute("DELETE FROM history", [])
             .map_err(|e| e.to_string())?;
-        Ok(())
+        Ok::<_, String>(())
     })
     .await
-    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())??;
+    record(&app_handle, Action::HistoryCleared, Some(profile)).await;
+    Ok(())
 }
 
 #[tauri::command]
@@ -89,6 +95,7 @@ pub async fn history_merge<R: Runtime>(
     let profile = get_current_profile(&app_handle)?;
     let dest_path =
         get_profile_dir(&app_handle, &profile)?.join(format!("history-{}.sqlite", profile));
+    let paths_count = paths.len();
 
     tokio::task::spawn_blocking(move || {
         let dest_conn = Connection::open(&dest_path).map_err(|e| e.to_string())?;
@@ -127,8 +134,16 @@ pub async fn history_merge<R: Runtime>(
                     .map_err(|e| e.to_string())?;
             }
         }
-        Ok(())
+        Ok::<_, String>(())
     })
     .await
-    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())??;
+    record_event(
+        &app_handle,
+        Action::HistoryMerged,
+        Some(profile),
+        vec![("sources", paths_count.into())],
+    )
+    .await;
+    Ok(())
 }
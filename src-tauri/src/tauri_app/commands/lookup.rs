@@ -12,7 +12,9 @@ use crate::{
 };
 use tauri::Runtime;
 
+use crate::proxy::Permission;
 use crate::tauri_app::{
+    commands::audit::require_permission,
     state::AppState,
     support::{domain_status_to_string, get_current_profile, get_profile_dir},
 };
@@ -23,6 +25,7 @@ pub async fn whois_lookup<R: Runtime>(
     data: AppState<'_>,
     domain: String,
 ) -> Result<String, String> {
+    require_permission(&app_handle, Permission::Lookup).await?;
     let settings = data.lookup_settings.lock().await.clone();
     let result: String = perform_lookup_with_settings(&domain, &settings).await?;
     log_lookup_history(&app_handle, &domain, &result).await?;
@@ -35,6 +38,7 @@ pub async fn whois_lookup_with_settings<R: Runtime>(
     domain: String,
     settings: LookupSettings,
 ) -> Result<String, String> {
+    require_permission(&app_handle, Permission::Lookup).await?;
     let result: String = perform_lookup_with_settings(&domain, &settings).await?;
     log_lookup_history(&app_handle, &domain, &result).await?;
     Ok(result)
@@ -57,12 +61,20 @@ async fn log_lookup_history<R: Runtime>(
 }
 
 #[tauri::command]
-pub async fn dns_lookup_cmd(domain: String) -> Result<bool, String> {
+pub async fn dns_lookup_cmd<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    domain: String,
+) -> Result<bool, String> {
+    require_permission(&app_handle, Permission::Lookup).await?;
     dns_lookup(&domain).await
 }
 
 #[tauri::command]
-pub async fn rdap_lookup_cmd(domain: String) -> Result<String, String> {
+pub async fn rdap_lookup_cmd<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    domain: String,
+) -> Result<String, String> {
+    require_permission(&app_handle, Permission::Lookup).await?;
     rdap_lookup(&domain).await
 }
 
@@ -1,14 +1,29 @@
+#[cfg(feature = "domain-agentic")]
+pub mod agent;
+#[cfg(feature = "domain-agentic")]
+pub mod agent_memory;
 pub mod ai;
 pub mod analysis;
 pub mod app;
+pub mod audit;
 pub mod bulk;
 pub mod cache;
+#[cfg(feature = "domain-intelligence")]
+pub mod discovery;
 pub mod fs;
 pub mod history;
 pub mod lookup;
 pub mod monitor;
+#[cfg(feature = "domain-automation")]
+pub mod notify;
 pub mod path;
+#[cfg(feature = "domain-automation")]
+pub mod portfolio;
 pub mod profiles;
+#[cfg(feature = "domain-intelligence")]
+pub mod registrar;
+#[cfg(feature = "domain-agentic")]
+pub mod search;
 pub mod settings;
 pub mod shell;
 pub mod stats;
@@ -1,8 +1,131 @@
-use crate::{availability::is_domain_available, perform_lookup};
+#[cfg(not(feature = "domain-automation"))]
+use crate::{availability::is_domain_available, perform_lookup_with_settings};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
 use tauri::{Emitter, Runtime};
 
-use crate::tauri_app::{state::AppState, support::domain_status_to_string};
+use crate::proxy::Permission;
+use crate::tauri_app::commands::audit::require_permission;
+use crate::tauri_app::state::AppState;
+#[cfg(not(feature = "domain-automation"))]
+use crate::tauri_app::support::domain_status_to_string;
 
+#[cfg(feature = "domain-automation")]
+use std::sync::Arc;
+
+#[cfg(feature = "domain-automation")]
+use crate::automation::expiry::{
+    monitor::record_observation, ExpiryPhase, ExpiryStore, WatchEntry, WatchMonitor,
+    WatchObservation, WatchPriority, WatchProbe, WatchUpdate, Watchlist,
+};
+#[cfg(feature = "domain-automation")]
+use crate::automation::history::HistoryStore;
+#[cfg(feature = "domain-automation")]
+use crate::automation::notify::{Notification, Notifier};
+#[cfg(feature = "domain-automation")]
+use crate::monitor::WhoisWatchProbe;
+#[cfg(feature = "domain-automation")]
+use crate::tauri_app::commands::notify::load_notifier;
+#[cfg(feature = "domain-automation")]
+use crate::tauri_app::support::{get_current_profile, get_profile_dir};
+
+/// Result of [`monitor_lookup`]. Only `status` is filled in without the
+/// `domain-automation` feature.
+#[derive(Serialize, Default, Debug)]
+pub struct MonitorLookup {
+    pub status: String,
+    pub registrar: Option<String>,
+    pub nameservers: Vec<String>,
+    pub expiry_date: Option<DateTime<Utc>>,
+    pub status_codes: Vec<String>,
+}
+
+#[cfg(feature = "domain-automation")]
+impl From<WatchObservation> for MonitorLookup {
+    fn from(obs: WatchObservation) -> Self {
+        Self {
+            status: obs.status,
+            registrar: obs.registrar,
+            nameservers: obs.nameservers,
+            expiry_date: obs.expiry_date,
+            status_codes: obs.status_codes,
+        }
+    }
+}
+
+/// How often the monitor looks for watchlist entries that are due. Each
+/// entry is only looked up once its priority's interval has passed.
+const TICK_SECS: u64 = 60;
+
+#[cfg(feature = "domain-automation")]
+struct MonitorStores {
+    watchlist: Arc<ExpiryStore>,
+    history: Arc<HistoryStore>,
+}
+
+#[cfg(feature = "domain-automation")]
+async fn open_stores<R: Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+) -> Result<MonitorStores, String> {
+    let profile = get_current_profile(app_handle)?;
+    let dir = get_profile_dir(app_handle, &profile)?;
+    let watch_path = dir.join(format!("watchlist-{}.sqlite", profile));
+    let history_path = dir.join(format!("snapshots-{}.sqlite", profile));
+    tokio::task::spawn_blocking(move || {
+        let watchlist = ExpiryStore::open(&watch_path.to_string_lossy())?;
+        let history = HistoryStore::open(&history_path.to_string_lossy())?;
+        Ok(MonitorStores {
+            watchlist: Arc::new(watchlist),
+            history: Arc::new(history),
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e: rusqlite::Error| e.to_string())
+}
+
+#[cfg(feature = "domain-automation")]
+async fn load_watchlist(store: &Arc<ExpiryStore>) -> Result<Watchlist, String> {
+    let store = Arc::clone(store);
+    tokio::task::spawn_blocking(move || store.load().map_err(|e| e.to_string()))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[cfg(feature = "domain-automation")]
+async fn save_entries(store: &Arc<ExpiryStore>, entries: Vec<WatchEntry>) -> Result<(), String> {
+    let store = Arc::clone(store);
+    tokio::task::spawn_blocking(move || {
+        entries
+            .iter()
+            .try_for_each(|e| store.upsert(e).map(|_| ()))
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Store check results for entries that are still watched; entries removed
+/// while the check ran stay removed.
+#[cfg(feature = "domain-automation")]
+async fn record_checks(store: &Arc<ExpiryStore>, entries: Vec<WatchEntry>) -> Result<(), String> {
+    let store = Arc::clone(store);
+    tokio::task::spawn_blocking(move || {
+        entries
+            .iter()
+            .try_for_each(|e| store.record_check(e).map(|_| ()))
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Start checking the profile's watchlist in the background. Entries are
+/// looked up as their priority interval elapses, every lookup is stored as
+/// a history snapshot, and `monitor:update` is emitted with a
+/// [`WatchUpdate`] whenever the status, registrar, nameservers or expiry
+/// phase of a domain changes. Changes are also routed to the profile's
+/// notification sinks, if any are configured.
 #[tauri::command]
 pub async fn monitor_start<R: Runtime>(
     app_handle: tauri::AppHandle<R>,
@@ -13,16 +136,33 @@ pub async fn monitor_start<R: Runtime>(
         return Ok(());
     }
 
+    #[cfg(feature = "domain-automation")]
+    let (stores, settings, notifier) = (
+        open_stores(&app_handle).await?,
+        data.lookup_settings.lock().await.clone(),
+        load_notifier(&app_handle).await?,
+    );
+
     let (tx, mut rx) = tokio::sync::oneshot::channel();
     monitor.active = true;
     monitor.cancel_token = Some(tx);
 
     let app = app_handle.clone();
     tauri::async_runtime::spawn(async move {
+        #[cfg(feature = "domain-automation")]
+        let watcher = WatchMonitor::new(
+            WhoisWatchProbe::new(settings).with_history(Arc::clone(&stores.history)),
+        );
+        let mut ticks = tokio::time::interval(tokio::time::Duration::from_secs(TICK_SECS));
         loop {
             tokio::select! {
                 _ = &mut rx => break,
-                _ = tokio::time::sleep(tokio::time::Duration::from_secs(60)) => {
+                _ = ticks.tick() => {
+                    #[cfg(feature = "domain-automation")]
+                    if let Err(e) = check_due(&app, &stores, &watcher, notifier.as_ref()).await {
+                        log::warn!("Watchlist check failed: {e}");
+                    }
+                    #[cfg(not(feature = "domain-automation"))]
                     let _ = app.emit("monitor:heartbeat", ());
                 }
             }
@@ -32,6 +172,41 @@ pub async fn monitor_start<R: Runtime>(
     Ok(())
 }
 
+/// One monitor tick: look up the due entries, persist what was learned and
+/// emit and notify the changes. The watchlist is re-read every time so edits made
+/// while the monitor runs are picked up.
+#[cfg(feature = "domain-automation")]
+async fn check_due<R: Runtime, P: WatchProbe>(
+    app: &tauri::AppHandle<R>,
+    stores: &MonitorStores,
+    watcher: &WatchMonitor<P>,
+    notifier: Option<&Notifier>,
+) -> Result<(), String> {
+    let mut watchlist = load_watchlist(&stores.watchlist).await?;
+    let report = watcher.tick(&mut watchlist).await;
+    let checked = report
+        .checked
+        .iter()
+        .filter_map(|d| watchlist.get(d).cloned())
+        .collect();
+    record_checks(&stores.watchlist, checked).await?;
+    for update in &report.updates {
+        let _ = app.emit("monitor:update", update);
+    }
+    if let Some(notifier) = notifier {
+        let notifications: Vec<Notification> = report
+            .updates
+            .iter()
+            .map(Notification::from_watch_update)
+            .collect();
+        let sent = notifier.notify_all(&notifications).await;
+        if !sent.failed.is_empty() {
+            log::warn!("{} watchlist notifications failed", sent.failed.len());
+        }
+    }
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn monitor_stop(data: AppState<'_>) -> Result<(), String> {
     let mut monitor = data.monitor.lock().await;
@@ -42,13 +217,57 @@ pub async fn monitor_stop(data: AppState<'_>) -> Result<(), String> {
     Ok(())
 }
 
+/// Look `domain` up now with the current lookup settings. A watched domain
+/// is updated as if the monitor had checked it, emitting `monitor:update`
+/// when something changed.
+#[cfg(feature = "domain-automation")]
 #[tauri::command]
 pub async fn monitor_lookup<R: Runtime>(
     app_handle: tauri::AppHandle<R>,
+    data: AppState<'_>,
     domain: String,
-) -> Result<(), String> {
-    let result = perform_lookup(&domain, 10000).await;
-    let status = match result {
+) -> Result<MonitorLookup, String> {
+    require_permission(&app_handle, Permission::Lookup).await?;
+    let domain = domain.trim().to_lowercase();
+    let stores = open_stores(&app_handle).await?;
+    let settings = data.lookup_settings.lock().await.clone();
+    let obs = WhoisWatchProbe::new(settings)
+        .with_history(Arc::clone(&stores.history))
+        .observe(&domain)
+        .await?;
+
+    let mut watchlist = load_watchlist(&stores.watchlist).await?;
+    if let Some(entry) = watchlist.get_mut(&domain) {
+        let now = Utc::now();
+        let changes = record_observation(entry, &obs, now);
+        let entry = entry.clone();
+        if !changes.is_empty() {
+            let update = WatchUpdate {
+                domain: domain.clone(),
+                changes,
+                status: obs.status.clone(),
+                phase: entry.phase.clone().unwrap_or(ExpiryPhase::Unknown),
+                expiry_date: entry.expiry_date,
+                checked_at: now,
+            };
+            let _ = app_handle.emit("monitor:update", &update);
+        }
+        record_checks(&stores.watchlist, vec![entry]).await?;
+    }
+    Ok(obs.into())
+}
+
+#[cfg(not(feature = "domain-automation"))]
+#[tauri::command]
+pub async fn monitor_lookup<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    data: AppState<'_>,
+    domain: String,
+) -> Result<MonitorLookup, String> {
+    require_permission(&app_handle, Permission::Lookup).await?;
+    let domain = domain.trim().to_lowercase();
+    let settings = data.lookup_settings.lock().await.clone();
+    let status = match perform_lookup_with_settings(&domain, &settings).await {
         Ok(ref res) => domain_status_to_string(&is_domain_available(res)),
         Err(_) => "error".to_string(),
     };
@@ -56,5 +275,57 @@ pub async fn monitor_lookup<R: Runtime>(
         "monitor:update",
         serde_json::json!({ "domain": domain, "status": status }),
     );
-    Ok(())
+    Ok(MonitorLookup {
+        status,
+        ..MonitorLookup::default()
+    })
+}
+
+/// The profile's watchlist with the result of each entry's last check.
+#[cfg(feature = "domain-automation")]
+#[tauri::command]
+pub async fn monitor_watchlist<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+) -> Result<Vec<WatchEntry>, String> {
+    require_permission(&app_handle, Permission::WatchlistRead).await?;
+    let stores = open_stores(&app_handle).await?;
+    Ok(load_watchlist(&stores.watchlist).await?.entries)
+}
+
+/// Add `domain` to the watchlist, or change its priority if it is already
+/// watched.
+#[cfg(feature = "domain-automation")]
+#[tauri::command]
+pub async fn monitor_watch<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    domain: String,
+    priority: Option<WatchPriority>,
+) -> Result<(), String> {
+    require_permission(&app_handle, Permission::WatchlistManage).await?;
+    let stores = open_stores(&app_handle).await?;
+    let watchlist = load_watchlist(&stores.watchlist).await?;
+    let domain = domain.trim().to_lowercase();
+    let mut entry = watchlist
+        .get(&domain)
+        .cloned()
+        .unwrap_or_else(|| WatchEntry::new(domain));
+    if let Some(priority) = priority {
+        entry.priority = priority;
+    }
+    entry.active = true;
+    save_entries(&stores.watchlist, vec![entry]).await
+}
+
+#[cfg(feature = "domain-automation")]
+#[tauri::command]
+pub async fn monitor_unwatch<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    domain: String,
+) -> Result<bool, String> {
+    require_permission(&app_handle, Permission::WatchlistManage).await?;
+    let stores = open_stores(&app_handle).await?;
+    tokio::task::spawn_blocking(move || stores.watchlist.remove(&domain))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
 }
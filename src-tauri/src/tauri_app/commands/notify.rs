@@ -0,0 +1,106 @@
+use std::path::PathBuf;
+
+use tauri::Runtime;
+
+use crate::automation::notify::{
+    DeadLetter, DeadLetterLog, DispatchReport, Notification, NotificationKind, Notifier,
+    NotifyConfig, Severity,
+};
+use crate::tauri_app::support::{get_current_profile, get_profile_dir};
+
+fn config_path<R: Runtime>(app_handle: &tauri::AppHandle<R>) -> Result<PathBuf, String> {
+    let profile = get_current_profile(app_handle)?;
+    Ok(get_profile_dir(app_handle, &profile)?.join(format!("notifications-{}.json", profile)))
+}
+
+/// The profile's notification settings, with the dead-letter path resolved
+/// against the profile directory (and defaulted when unset).
+async fn load_config<R: Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+) -> Result<Option<NotifyConfig>, String> {
+    let path = config_path(app_handle)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let mut config = tokio::task::spawn_blocking(move || NotifyConfig::load(&path))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+    let profile = get_current_profile(app_handle)?;
+    let dir = get_profile_dir(app_handle, &profile)?;
+    let dead_letter = config
+        .dead_letter
+        .clone()
+        .unwrap_or_else(|| format!("notify-dead-letters-{}.jsonl", profile));
+    config.dead_letter = Some(dir.join(dead_letter).to_string_lossy().into_owned());
+    Ok(Some(config))
+}
+
+/// The profile's notifier, or `None` when notifications are not set up.
+pub(crate) async fn load_notifier<R: Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+) -> Result<Option<Notifier>, String> {
+    match load_config(app_handle).await? {
+        Some(config) => config.build().map(Some).map_err(|e| e.to_string()),
+        None => Ok(None),
+    }
+}
+
+#[tauri::command]
+pub async fn notify_get_config<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+) -> Result<NotifyConfig, String> {
+    let path = config_path(&app_handle)?;
+    if !path.exists() {
+        return Ok(NotifyConfig::default());
+    }
+    tokio::task::spawn_blocking(move || NotifyConfig::load(&path))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Validate and save the profile's notification settings.
+#[tauri::command]
+pub async fn notify_set_config<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    config: NotifyConfig,
+) -> Result<(), String> {
+    config.build().map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    tokio::fs::write(config_path(&app_handle)?, json)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Route a test notification of `severity` through the saved rules.
+#[tauri::command]
+pub async fn notify_test<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    severity: Option<Severity>,
+) -> Result<DispatchReport, String> {
+    let notifier = load_notifier(&app_handle)
+        .await?
+        .ok_or("notifications are not configured")?;
+    let notification = Notification::new(
+        NotificationKind::Custom,
+        severity.unwrap_or(Severity::Info),
+        "Whoisdigger test notification",
+    )
+    .with_body("Notifications are set up for this profile.");
+    Ok(notifier.notify(&notification).await)
+}
+
+/// Messages that could not be delivered, oldest first.
+#[tauri::command]
+pub async fn notify_dead_letters<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+) -> Result<Vec<DeadLetter>, String> {
+    let Some(path) = load_config(&app_handle).await?.and_then(|c| c.dead_letter) else {
+        return Ok(Vec::new());
+    };
+    tokio::task::spawn_blocking(move || DeadLetterLog::new(path).entries())
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use chrono::{Months, Utc};
+use tauri::Runtime;
+
+use crate::automation::history::{HistoryStore, RegistrarPricing};
+use crate::automation::portfolio::{
+    calendar_csv, Drift, MonthlySpend, OwnedDomain, Portfolio, PortfolioStore,
+};
+use crate::proxy::Permission;
+use crate::tauri_app::commands::audit::require_permission;
+use crate::tauri_app::support::{get_current_profile, get_profile_dir};
+
+/// Months of renewals covered when the caller does not say.
+const DEFAULT_MONTHS: u32 = 12;
+
+async fn open_store<R: Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+) -> Result<Arc<PortfolioStore>, String> {
+    let profile = get_current_profile(app_handle)?;
+    let path = get_profile_dir(app_handle, &profile)?.join(format!("portfolio-{}.sqlite", profile));
+    tokio::task::spawn_blocking(move || PortfolioStore::open(&path.to_string_lossy()))
+        .await
+        .map_err(|e| e.to_string())?
+        .map(Arc::new)
+        .map_err(|e| e.to_string())
+}
+
+async fn load_portfolio<R: Runtime>(app_handle: &tauri::AppHandle<R>) -> Result<Portfolio, String> {
+    let store = open_store(app_handle).await?;
+    tokio::task::spawn_blocking(move || store.load().map_err(|e| e.to_string()))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn portfolio_list<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+) -> Result<Vec<OwnedDomain>, String> {
+    Ok(load_portfolio(&app_handle).await?.domains)
+}
+
+/// Add an owned domain, or replace the record of one already held.
+#[tauri::command]
+pub async fn portfolio_upsert<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    domain: OwnedDomain,
+) -> Result<(), String> {
+    let store = open_store(&app_handle).await?;
+    tokio::task::spawn_blocking(move || store.upsert(&domain).map(|_| ()))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn portfolio_remove<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    domain: String,
+) -> Result<bool, String> {
+    let store = open_store(&app_handle).await?;
+    tokio::task::spawn_blocking(move || store.remove(&domain))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Renewal spend per month from the current month on. Domains without a
+/// recorded renewal price are priced from `pricing` when given.
+#[tauri::command]
+pub async fn portfolio_renewal_spend<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    months: Option<u32>,
+    pricing: Option<RegistrarPricing>,
+) -> Result<Vec<MonthlySpend>, String> {
+    let portfolio = load_portfolio(&app_handle).await?;
+    Ok(portfolio.renewal_spend(
+        &pricing.unwrap_or_default(),
+        Utc::now(),
+        months.unwrap_or(DEFAULT_MONTHS),
+    ))
+}
+
+/// Domains whose latest WHOIS snapshot shows a different registrant or
+/// nameservers than expected.
+#[tauri::command]
+pub async fn portfolio_drift<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+) -> Result<Vec<Drift>, String> {
+    let portfolio = load_portfolio(&app_handle).await?;
+    let profile = get_current_profile(&app_handle)?;
+    let path =
+        get_profile_dir(&app_handle, &profile)?.join(format!("snapshots-{}.sqlite", profile));
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    tokio::task::spawn_blocking(move || {
+        let history = HistoryStore::open(&path.to_string_lossy()).map_err(|e| e.to_string())?;
+        Ok(portfolio.drift(|domain| {
+            history
+                .get_domain_snapshots(domain)
+                .ok()
+                .and_then(|mut snaps| snaps.pop())
+        }))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Write the renewal calendar for the coming `months` to `path` as CSV and
+/// return the number of renewals in it.
+#[tauri::command]
+pub async fn portfolio_export_calendar<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    path: String,
+    months: Option<u32>,
+    pricing: Option<RegistrarPricing>,
+) -> Result<usize, String> {
+    require_permission(&app_handle, Permission::Export).await?;
+    let portfolio = load_portfolio(&app_handle).await?;
+    let now = Utc::now();
+    let until = now
+        .checked_add_months(Months::new(months.unwrap_or(DEFAULT_MONTHS)))
+        .ok_or("calendar range is out of bounds")?;
+    let events = portfolio.renewal_calendar(&pricing.unwrap_or_default(), now, until);
+    let csv = calendar_csv(&events);
+    tokio::fs::write(&path, csv)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(events.len())
+}
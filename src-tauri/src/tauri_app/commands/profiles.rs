@@ -4,6 +4,8 @@ use tauri::Runtime;
 use walkdir::WalkDir;
 use zip::write::SimpleFileOptions;
 
+use crate::storage::audit::Action;
+use crate::tauri_app::commands::audit::{record, record_event};
 use crate::tauri_app::support::{
     epoch_ms_from_metadata, get_profile_dir, get_user_data_dir, sanitize_name, ProfileEntry,
 };
@@ -120,6 +122,7 @@ pub async fn profiles_delete<R: Runtime>(
         tokio::fs::remove_dir_all(dir)
             .await
             .map_err(|e| e.to_string())?;
+        record(&app_handle, Action::ProfileDeleted, Some(id)).await;
     }
     Ok(())
 }
@@ -180,7 +183,15 @@ pub async fn profiles_export<R: Runtime>(
         }
     }
     zip.finish().map_err(|e| e.to_string())?;
-    Ok(zip_path.to_string_lossy().to_string())
+    let zip_path = zip_path.to_string_lossy().to_string();
+    record_event(
+        &app_handle,
+        Action::ProfileExported,
+        Some(profile_id),
+        vec![("archive", zip_path.clone().into())],
+    )
+    .await;
+    Ok(zip_path)
 }
 
 #[tauri::command]
@@ -214,6 +225,14 @@ pub async fn profiles_import<R: Runtime>(
         }
     }
 
+    record_event(
+        &app_handle,
+        Action::ProfileImported,
+        Some(profile_name.clone()),
+        vec![("archive", zip_path.into())],
+    )
+    .await;
+
     Ok(ProfileEntry {
         id: profile_name.clone(),
         name: profile_name,
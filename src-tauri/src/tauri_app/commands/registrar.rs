@@ -0,0 +1,22 @@
+use tauri::Runtime;
+
+use crate::intelligence::registrar::{build_affiliate_url, AffiliateLink};
+use crate::proxy::Permission;
+use crate::tauri_app::commands::audit::require_permission;
+
+/// The checkout URL for buying `domain` at `registrar_id`, for the frontend
+/// to open. Requires the `registrar_purchase` permission.
+#[tauri::command]
+pub async fn registrar_purchase<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    registrar_id: String,
+    domain: String,
+) -> Result<AffiliateLink, String> {
+    require_permission(&app_handle, Permission::RegistrarPurchase).await?;
+    let domain = domain.trim().trim_end_matches('.').to_lowercase();
+    if domain.is_empty() {
+        return Err("A domain is required".into());
+    }
+    build_affiliate_url(&registrar_id, &domain, None)
+        .ok_or_else(|| format!("Unknown registrar: {}", registrar_id))
+}
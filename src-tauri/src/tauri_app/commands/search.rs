@@ -0,0 +1,69 @@
+use std::path::PathBuf;
+
+use tauri::Runtime;
+
+use crate::agentic::search::{ChatStore, HistoryStore, SearchHit, SearchIndex, SearchQuery};
+use crate::tauri_app::support::{get_current_profile, get_profile_dir};
+
+fn index_path<R: Runtime>(app_handle: &tauri::AppHandle<R>) -> Result<PathBuf, String> {
+    let profile = get_current_profile(app_handle)?;
+    Ok(get_profile_dir(app_handle, &profile)?.join(format!("search-index-{}.sqlite", profile)))
+}
+
+/// Open the current profile's index on a blocking thread and run `f` on it.
+async fn with_index<R, T, F>(app_handle: &tauri::AppHandle<R>, f: F) -> Result<T, String>
+where
+    R: Runtime,
+    T: Send + 'static,
+    F: FnOnce(&SearchIndex) -> rusqlite::Result<T> + Send + 'static,
+{
+    let path = index_path(app_handle)?;
+    tokio::task::spawn_blocking(move || {
+        let index = SearchIndex::open(&path.to_string_lossy()).map_err(|e| e.to_string())?;
+        f(&index).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Search the index. `query` accepts the filters understood by
+/// `SearchQuery::parse`, e.g. `registrar:acme ns:cloudflare last quarter`.
+#[tauri::command]
+pub async fn search_query<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<SearchHit>, String> {
+    with_index(&app_handle, move |index| {
+        let mut query = SearchQuery::parse(&query);
+        if let Some(limit) = limit {
+            query.limit = limit;
+        }
+        index.search(&query)
+    })
+    .await
+}
+
+/// Rebuild the index from a WHOIS history database and/or a chat database.
+/// Returns the number of documents indexed.
+#[tauri::command]
+pub async fn search_reindex<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    history_path: Option<String>,
+    chat_path: Option<String>,
+) -> Result<usize, String> {
+    if history_path.is_none() && chat_path.is_none() {
+        return Err("Either a history or a chat database is required".into());
+    }
+    with_index(&app_handle, move |index| {
+        let mut indexed = 0;
+        if let Some(path) = history_path {
+            indexed += index.index_history(&HistoryStore::open(&path)?)?;
+        }
+        if let Some(path) = chat_path {
+            indexed += index.index_chat(&ChatStore::open(&path)?)?;
+        }
+        Ok(indexed)
+    })
+    .await
+}
@@ -1,7 +1,9 @@
 use crate::{lookup::LookupSettings, proxy::ProxySettings};
 use tauri::Runtime;
 
+use crate::storage::audit::Action;
 use crate::tauri_app::{
+    commands::audit::record,
     state::AppState,
     support::{get_user_data_dir, safe_path, sanitize_name},
 };
@@ -48,6 +50,7 @@ pub async fn config_delete<R: Runtime>(
         tokio::fs::remove_file(path)
             .await
             .map_err(|e| e.to_string())?;
+        record(&app_handle, Action::ConfigDeleted, Some(filename)).await;
     }
     Ok(())
 }
@@ -100,5 +103,7 @@ pub async fn config_import<R: Runtime>(
     let path = get_user_data_dir(&app_handle)?.join("settings.json");
     tokio::fs::write(path, content)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    record(&app_handle, Action::ConfigImported, None).await;
+    Ok(())
 }
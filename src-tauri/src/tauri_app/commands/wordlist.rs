@@ -1,4 +1,11 @@
-use crate::wordlist as wd_wordlist_mod;
+use std::io::{BufReader, BufWriter};
+
+use tauri::Runtime;
+
+use crate::tauri_app::support::{get_current_profile, get_profile_dir, validate_fs_path};
+use crate::wordlist::{ListSource, Recipe, RecipeBook, RecipeRun, WordlistOp};
+
+const RECIPE_BOOK_FILE: &str = "wordlist-recipes.json";
 
 #[tauri::command]
 pub async fn wordlist_transform(
@@ -8,41 +15,102 @@ pub async fn wordlist_transform(
     arg2: Option<String>,
 ) -> Result<String, String> {
     let lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
+    let op = WordlistOp::from_legacy(&operation, arg1.as_deref(), arg2.as_deref())?;
+    let result = Recipe::new(operation).then(op).apply(&lines)?;
+    Ok(result.join("\n"))
+}
 
-    let result = match operation.as_str() {
-        "addPrefix" => wd_wordlist_mod::add_prefix(&lines, arg1.as_deref().unwrap_or("")),
-        "addSuffix" => wd_wordlist_mod::add_suffix(&lines, arg1.as_deref().unwrap_or("")),
-        "sort" => wd_wordlist_mod::sort_lines(&lines),
-        "sortReverse" => wd_wordlist_mod::sort_lines_reverse(&lines),
-        "shuffle" => wd_wordlist_mod::shuffle_lines(&lines),
-        "trimSpaces" => wd_wordlist_mod::trim_spaces(&lines),
-        "deleteSpaces" => wd_wordlist_mod::delete_spaces(&lines),
-        "deleteBlankLines" => wd_wordlist_mod::delete_blank_lines(&lines),
-        "trimNonAlnum" => wd_wordlist_mod::trim_non_alnum(&lines),
-        "deleteNonAlnum" => wd_wordlist_mod::delete_non_alnum(&lines),
-        "dedupe" => wd_wordlist_mod::dedupe_lines(&lines),
-        "deleteLinesContaining" => {
-            wd_wordlist_mod::delete_lines_containing(&lines, arg1.as_deref().unwrap_or(""))
+#[tauri::command]
+pub async fn wordlist_recipe_apply<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    content: String,
+    mut recipe: Recipe,
+) -> Result<String, String> {
+    confine_sources(&app_handle, &mut recipe)?;
+    let lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
+    Ok(recipe.apply(&lines)?.join("\n"))
+}
+
+/// Stream a file through a recipe without loading it into memory.
+#[tauri::command]
+pub async fn wordlist_recipe_apply_file<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    input: String,
+    output: String,
+    mut recipe: Recipe,
+) -> Result<RecipeRun, String> {
+    confine_sources(&app_handle, &mut recipe)?;
+    let input = validate_fs_path(&app_handle, &input)?;
+    let output = validate_fs_path(&app_handle, &output)?;
+    tokio::task::spawn_blocking(move || {
+        let reader = BufReader::new(std::fs::File::open(&input).map_err(|e| e.to_string())?);
+        let writer = BufWriter::new(std::fs::File::create(&output).map_err(|e| e.to_string())?);
+        recipe.stream(reader, writer)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn wordlist_recipes_list<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+) -> Result<Vec<Recipe>, String> {
+    let path = recipe_book_path(&app_handle)?;
+    Ok(RecipeBook::load(&path)?.recipes)
+}
+
+#[tauri::command]
+pub async fn wordlist_recipe_save<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    recipe: Recipe,
+) -> Result<(), String> {
+    if recipe.name.trim().is_empty() {
+        return Err("Recipe name cannot be empty".into());
+    }
+    recipe.compile()?;
+    let path = recipe_book_path(&app_handle)?;
+    let mut book = RecipeBook::load(&path)?;
+    book.upsert(recipe);
+    book.save(&path)
+}
+
+#[tauri::command]
+pub async fn wordlist_recipe_delete<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    name: String,
+) -> Result<bool, String> {
+    let path = recipe_book_path(&app_handle)?;
+    let mut book = RecipeBook::load(&path)?;
+    let removed = book.remove(&name);
+    if removed {
+        book.save(&path)?;
+    }
+    Ok(removed)
+}
+
+/// Resolve the recipe's file sources against the app data directory, refusing
+/// any that point outside it.
+fn confine_sources<R: Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+    recipe: &mut Recipe,
+) -> Result<(), String> {
+    for step in &mut recipe.steps {
+        if let WordlistOp::Product {
+            with: ListSource::File { path },
+            ..
+        } = step
+        {
+            *path = validate_fs_path(app_handle, path)?
+                .to_string_lossy()
+                .into_owned();
         }
-        "deleteString" => wd_wordlist_mod::delete_string(&lines, arg1.as_deref().unwrap_or("")),
-        "toLowerCase" => wd_wordlist_mod::to_lower_case_lines(&lines),
-        "toUpperCase" => wd_wordlist_mod::to_upper_case_lines(&lines),
-        "rot13" => wd_wordlist_mod::rot13_lines(&lines),
-        "leetSpeak" => wd_wordlist_mod::to_leet_speak_lines(&lines),
-        "replaceString" => wd_wordlist_mod::replace_string(
-            &lines,
-            arg1.as_deref().unwrap_or(""),
-            arg2.as_deref().unwrap_or(""),
-        ),
-        "deleteRegex" => wd_wordlist_mod::delete_regex(&lines, arg1.as_deref().unwrap_or(""))?,
-        "trimRegex" => wd_wordlist_mod::trim_regex(&lines, arg1.as_deref().unwrap_or(""))?,
-        "replaceRegex" => wd_wordlist_mod::replace_regex(
-            &lines,
-            arg1.as_deref().unwrap_or(""),
-            arg2.as_deref().unwrap_or(""),
-        )?,
-        _ => return Err(format!("Unknown operation: {}", operation)),
-    };
+    }
+    Ok(())
+}
 
-    Ok(result.join("\n"))
+fn recipe_book_path<R: Runtime>(
+    app_handle: &tauri::AppHandle<R>,
+) -> Result<std::path::PathBuf, String> {
+    let profile = get_current_profile(app_handle)?;
+    Ok(get_profile_dir(app_handle, &profile)?.join(RECIPE_BOOK_FILE))
 }
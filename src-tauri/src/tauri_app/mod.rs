@@ -35,6 +35,9 @@ pub fn run() {
             commands::app::i18n_load,
             commands::app::app_get_base_dir,
             commands::app::app_get_user_data_path,
+            commands::audit::audit_query,
+            commands::audit::audit_verify,
+            commands::audit::audit_export,
             commands::history::db_gui_history_get,
             commands::history::db_gui_history_get_filtered,
             commands::history::db_gui_history_clear,
@@ -74,6 +77,32 @@ pub fn run() {
             commands::monitor::monitor_start,
             commands::monitor::monitor_stop,
             commands::monitor::monitor_lookup,
+            #[cfg(feature = "domain-automation")]
+            commands::monitor::monitor_watchlist,
+            #[cfg(feature = "domain-automation")]
+            commands::monitor::monitor_watch,
+            #[cfg(feature = "domain-automation")]
+            commands::monitor::monitor_unwatch,
+            #[cfg(feature = "domain-automation")]
+            commands::notify::notify_get_config,
+            #[cfg(feature = "domain-automation")]
+            commands::notify::notify_set_config,
+            #[cfg(feature = "domain-automation")]
+            commands::notify::notify_test,
+            #[cfg(feature = "domain-automation")]
+            commands::notify::notify_dead_letters,
+            #[cfg(feature = "domain-automation")]
+            commands::portfolio::portfolio_list,
+            #[cfg(feature = "domain-automation")]
+            commands::portfolio::portfolio_upsert,
+            #[cfg(feature = "domain-automation")]
+            commands::portfolio::portfolio_remove,
+            #[cfg(feature = "domain-automation")]
+            commands::portfolio::portfolio_renewal_spend,
+            #[cfg(feature = "domain-automation")]
+            commands::portfolio::portfolio_drift,
+            #[cfg(feature = "domain-automation")]
+            commands::portfolio::portfolio_export_calendar,
             commands::text::to_process,
             commands::text::csv_parse,
             commands::text::csv_parse_file,
@@ -86,7 +115,38 @@ pub fn run() {
             commands::ai::ai_suggest_with_settings,
             commands::ai::ai_download_model,
             commands::ai::ai_predict,
-            commands::wordlist::wordlist_transform
+            commands::wordlist::wordlist_transform,
+            commands::wordlist::wordlist_recipe_apply,
+            commands::wordlist::wordlist_recipe_apply_file,
+            commands::wordlist::wordlist_recipes_list,
+            commands::wordlist::wordlist_recipe_save,
+            commands::wordlist::wordlist_recipe_delete,
+            #[cfg(feature = "domain-intelligence")]
+            commands::discovery::discovery_run,
+            #[cfg(feature = "domain-intelligence")]
+            commands::discovery::discovery_stop,
+            #[cfg(feature = "domain-intelligence")]
+            commands::registrar::registrar_purchase,
+            #[cfg(feature = "domain-agentic")]
+            commands::agent::agent_ask,
+            #[cfg(feature = "domain-agentic")]
+            commands::agent::agent_pipeline_run,
+            #[cfg(feature = "domain-agentic")]
+            commands::agent::agent_confirmations_list,
+            #[cfg(feature = "domain-agentic")]
+            commands::agent::agent_confirmation_resolve,
+            #[cfg(feature = "domain-agentic")]
+            commands::agent_memory::agent_memory_list,
+            #[cfg(feature = "domain-agentic")]
+            commands::agent_memory::agent_memory_search,
+            #[cfg(feature = "domain-agentic")]
+            commands::agent_memory::agent_memory_forget,
+            #[cfg(feature = "domain-agentic")]
+            commands::agent_memory::agent_memory_prune,
+            #[cfg(feature = "domain-agentic")]
+            commands::search::search_query,
+            #[cfg(feature = "domain-agentic")]
+            commands::search::search_reindex
         ])
         .setup(|app| {
             if let Ok(data_dir) = app.path().app_data_dir() {
@@ -1,10 +1,15 @@
 use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Mutex};
 
+#[cfg(feature = "domain-agentic")]
+use crate::agentic::agent::ConfirmationChannel;
+use crate::bulk::BulkControl;
 use crate::lookup::LookupSettings;
 use crate::proxy::{ProxyRotation, ProxySettings};
+use crate::storage::audit::AuditLog;
 use tauri::State;
-use tokio::sync::Mutex as AsyncMutex;
+use tokio::sync::{Mutex as AsyncMutex, OnceCell};
 
 pub struct StatsWatcher {
     pub config_path: String,
@@ -16,19 +21,20 @@ pub struct MonitorState {
     pub cancel_token: Option<tokio::sync::oneshot::Sender<()>>,
 }
 
-pub struct BulkLookupState {
-    pub paused: bool,
-    pub stopped: bool,
-}
-
 pub struct AppData {
     pub stats_watchers: Mutex<HashMap<u32, StatsWatcher>>,
     pub next_watcher_id: Mutex<u32>,
     pub monitor: AsyncMutex<MonitorState>,
-    pub bulk_state: Arc<AsyncMutex<BulkLookupState>>,
+    pub bulk_control: BulkControl,
+    pub discovery_stop: Arc<AtomicBool>,
     pub proxy_settings: AsyncMutex<ProxySettings>,
     pub proxy_rotation: ProxyRotation,
     pub lookup_settings: AsyncMutex<LookupSettings>,
+    /// The app-wide audit log, opened on first use.
+    pub audit_log: OnceCell<Arc<AuditLog>>,
+    /// Tool calls of running agents that wait for the user's decision.
+    #[cfg(feature = "domain-agentic")]
+    pub agent_confirmations: ConfirmationChannel,
 }
 
 impl AppData {
@@ -40,13 +46,14 @@ impl AppData {
                 active: false,
                 cancel_token: None,
             }),
-            bulk_state: Arc::new(AsyncMutex::new(BulkLookupState {
-                paused: false,
-                stopped: false,
-            })),
+            bulk_control: BulkControl::new(),
+            discovery_stop: Arc::new(AtomicBool::new(false)),
             proxy_settings: AsyncMutex::new(ProxySettings::default()),
             proxy_rotation: ProxyRotation::new(),
             lookup_settings: AsyncMutex::new(LookupSettings::default()),
+            audit_log: OnceCell::new(),
+            #[cfg(feature = "domain-agentic")]
+            agent_confirmations: ConfirmationChannel::new(),
         }
     }
 }
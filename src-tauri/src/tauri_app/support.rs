@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use tauri::{Manager, Runtime};
 
 use crate::availability::DomainStatus;
+use crate::lookup::LookupSettings;
 
 #[derive(Serialize, Clone)]
 pub struct FileStat {
@@ -393,6 +394,19 @@ pub fn get_dir_size(path: &Path) -> u64 {
         .sum()
 }
 
+/// The lookup settings saved in the app's `settings.json`; the defaults
+/// when the file is missing or unreadable.
+pub fn load_lookup_settings<R: Runtime>(app_handle: &tauri::AppHandle<R>) -> LookupSettings {
+    let path = match get_user_data_dir(app_handle) {
+        Ok(dir) => dir.join("settings.json"),
+        Err(_) => return LookupSettings::default(),
+    };
+    LookupSettings::load_app_settings(&path).unwrap_or_else(|e| {
+        log::warn!("Using default lookup settings: {e}");
+        LookupSettings::default()
+    })
+}
+
 pub fn load_openai_settings_from_json(content: &str) -> Option<crate::ai::OpenAiSettings> {
     let json: serde_json::Value = serde_json::from_str(content).ok()?;
     let ai = json.get("ai")?;